@@ -0,0 +1,148 @@
+//! Abbreviations with a cursor placeholder, like `gcm: 'git commit -m "%|"'`.
+//!
+//! Reedline expands the other abbreviations itself, but always leaves the cursor after the
+//! expansion, so [`AbbreviationMode`] expands these ones before reedline sees the space or Enter.
+
+use crate::edit_assist::{SharedLine, apply_mode_changes};
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+use nu_protocol::Config;
+use reedline::{
+    EditCommand, EditMode, PromptEditMode, PromptViMode, ReedlineEvent, ReedlineRawEvent,
+};
+use std::collections::HashMap;
+
+/// Where the cursor goes after expanding an abbreviation.
+const CURSOR_PLACEHOLDER: &str = "%|";
+
+/// The abbreviations without a cursor placeholder, which reedline expands.
+pub(crate) fn plain_abbreviations(config: &Config) -> HashMap<String, String> {
+    abbreviations_where(config, |expansion| !expansion.contains(CURSOR_PLACEHOLDER))
+}
+
+/// The abbreviations with a cursor placeholder, which [`AbbreviationMode`] expands.
+pub(crate) fn placeholder_abbreviations(config: &Config) -> HashMap<String, String> {
+    abbreviations_where(config, |expansion| expansion.contains(CURSOR_PLACEHOLDER))
+}
+
+fn abbreviations_where(config: &Config, filter: impl Fn(&str) -> bool) -> HashMap<String, String> {
+    config
+        .abbreviations
+        .iter()
+        .filter(|(_, expansion)| filter(expansion))
+        .map(|(name, expansion)| (name.clone(), expansion.clone()))
+        .collect()
+}
+
+/// Wraps the configured edit mode to expand abbreviations with a cursor placeholder.
+pub(crate) struct AbbreviationMode {
+    inner: Box<dyn EditMode>,
+    abbreviations: HashMap<String, String>,
+    line: SharedLine,
+}
+
+impl AbbreviationMode {
+    pub(crate) fn new(
+        inner: Box<dyn EditMode>,
+        abbreviations: HashMap<String, String>,
+        line: SharedLine,
+    ) -> Self {
+        Self {
+            inner,
+            abbreviations,
+            line,
+        }
+    }
+}
+
+impl EditMode for AbbreviationMode {
+    fn parse_event(&mut self, event: ReedlineRawEvent) -> ReedlineEvent {
+        let event = Event::from(event);
+        let inserting = matches!(
+            self.inner.edit_mode(),
+            PromptEditMode::Default
+                | PromptEditMode::Emacs
+                | PromptEditMode::Vi(PromptViMode::Insert)
+        );
+        let mut expansion = None;
+        if let Event::Key(key) = &event
+            && inserting
+            && key.modifiers.difference(KeyModifiers::SHIFT).is_empty()
+            && matches!(key.code, KeyCode::Char(' ') | KeyCode::Enter)
+        {
+            let (line, cursor) = self.line.get();
+            if let Some((line, cursor)) = expand(&self.abbreviations, &line, cursor) {
+                self.line.update(&line, cursor);
+                let edit = ReedlineEvent::Edit(vec![
+                    EditCommand::Clear,
+                    EditCommand::InsertString(line),
+                    EditCommand::MoveToPosition {
+                        position: cursor,
+                        select: false,
+                    },
+                ]);
+                // The space is dropped, as the cursor is already where the user wants to type
+                if key.code != KeyCode::Enter {
+                    return edit;
+                }
+                expansion = Some(edit);
+            }
+        }
+
+        // Only key releases are rejected, and those never made it here
+        let Ok(event) = ReedlineRawEvent::try_from(event) else {
+            return ReedlineEvent::None;
+        };
+        let event = self.inner.parse_event(event);
+        let event = apply_mode_changes(self.inner.as_mut(), event);
+        match expansion {
+            Some(expansion) => ReedlineEvent::Multiple(vec![expansion, event]),
+            None => event,
+        }
+    }
+
+    fn edit_mode(&self) -> PromptEditMode {
+        self.inner.edit_mode()
+    }
+}
+
+/// The line and byte cursor after expanding the word before the cursor, if it's an abbreviation.
+fn expand(
+    abbreviations: &HashMap<String, String>,
+    line: &str,
+    cursor: usize,
+) -> Option<(String, usize)> {
+    let before = line.get(..cursor)?;
+    let start = before
+        .char_indices()
+        .rfind(|(_, c)| c.is_whitespace())
+        .map_or(0, |(index, c)| index + c.len_utf8());
+    let expansion = abbreviations.get(&before[start..])?;
+    let (head, tail) = expansion.split_once(CURSOR_PLACEHOLDER)?;
+    let line = format!(
+        "{}{head}{}{}",
+        &line[..start],
+        tail.replace(CURSOR_PLACEHOLDER, ""),
+        &line[cursor..]
+    );
+    Some((line, start + head.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_word_before_cursor() {
+        let abbreviations = HashMap::from([("gcm".into(), r#"git commit -m "%|""#.into())]);
+        assert_eq!(
+            expand(&abbreviations, "gcm", 3),
+            Some((r#"git commit -m """#.into(), 15))
+        );
+        assert_eq!(
+            expand(&abbreviations, "cd a; gcm | x", 9),
+            Some((r#"cd a; git commit -m "" | x"#.into(), 21))
+        );
+        assert_eq!(expand(&abbreviations, "gcmx", 4), None);
+        assert_eq!(expand(&abbreviations, "gcm", 2), None);
+    }
+}
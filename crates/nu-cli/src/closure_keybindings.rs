@@ -0,0 +1,249 @@
+//! Keybindings whose event is a closure, which is given the command line and the cursor and
+//! returns what to replace them with:
+//!
+//! ```nushell
+//! {
+//!     name: wrap_in_parens
+//!     modifier: alt
+//!     keycode: char_p
+//!     mode: emacs
+//!     event: {|buffer, cursor| {buffer: $"\(($buffer))", cursor: 1} }
+//! }
+//! ```
+//!
+//! Like `$env.config.edit_assist`, this relies on the line [`SharedLine`] shares with the
+//! highlighter, as reedline doesn't show the buffer to the edit mode.
+
+use crate::{
+    edit_assist::{SharedLine, apply_mode_changes},
+    reedline_config::KeyTrigger,
+};
+use crossterm::event::Event;
+use nu_engine::ClosureEvalOnce;
+use nu_protocol::{
+    Config, IntoPipelineData, ParsedKeybinding, ShellError, Span, Type, Value,
+    engine::{Closure, EngineState, Stack},
+    report_shell_error,
+};
+use reedline::{EditCommand, EditMode, PromptEditMode, ReedlineEvent, ReedlineRawEvent};
+use std::sync::{Arc, Weak};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub(crate) fn is_closure_keybinding(keybinding: &ParsedKeybinding) -> bool {
+    matches!(keybinding.event, Value::Closure { .. })
+}
+
+/// A keybinding that runs a closure.
+pub(crate) struct ClosureKeybinding {
+    trigger: KeyTrigger,
+    closure: Closure,
+}
+
+/// The keybindings in the config that run a closure.
+pub(crate) fn create_closure_keybindings(
+    config: &Config,
+) -> Result<Vec<ClosureKeybinding>, ShellError> {
+    config
+        .keybindings
+        .iter()
+        .filter_map(|keybinding| match &keybinding.event {
+            Value::Closure { val, .. } => Some((keybinding, val)),
+            _ => None,
+        })
+        .map(|(keybinding, closure)| {
+            Ok(ClosureKeybinding {
+                trigger: KeyTrigger::from_keybinding(keybinding)?,
+                closure: (**closure).clone(),
+            })
+        })
+        .collect()
+}
+
+/// Wraps the configured edit mode to run the closures of closure keybindings.
+pub(crate) struct ClosureKeybindingsMode {
+    inner: Box<dyn EditMode>,
+    keybindings: Vec<ClosureKeybinding>,
+    line: SharedLine,
+    engine_state: Arc<EngineState>,
+    /// Weak, so the REPL can take its stack back without cloning it once the line is read
+    /// (see STACK-REFERENCE)
+    stack: Weak<Stack>,
+}
+
+impl ClosureKeybindingsMode {
+    pub(crate) fn new(
+        inner: Box<dyn EditMode>,
+        keybindings: Vec<ClosureKeybinding>,
+        line: SharedLine,
+        engine_state: Arc<EngineState>,
+        stack: Weak<Stack>,
+    ) -> Self {
+        Self {
+            inner,
+            keybindings,
+            line,
+            engine_state,
+            stack,
+        }
+    }
+
+    fn run(&self, closure: &Closure) -> ReedlineEvent {
+        let Some(parent) = self.stack.upgrade() else {
+            return ReedlineEvent::None;
+        };
+        let stack = Stack::with_parent(parent);
+        let span = Span::unknown();
+        let (buffer, cursor) = self.line.get();
+
+        // The cursor is counted in graphemes, like `commandline get-cursor` does
+        let graphemes = buffer
+            .grapheme_indices(true)
+            .take_while(|(index, _)| *index < cursor)
+            .count();
+        let result = ClosureEvalOnce::new(&self.engine_state, &stack, closure.clone())
+            .add_arg(Value::string(&buffer, span))
+            .and_then(|closure| {
+                closure.add_arg(Value::int(
+                    i64::try_from(graphemes).unwrap_or(i64::MAX),
+                    span,
+                ))
+            })
+            .and_then(|closure| {
+                closure.run_with_input(Value::string(&buffer, span).into_pipeline_data())
+            })
+            .and_then(|output| output.into_value(span))
+            .and_then(|value| replacement(value, &buffer, cursor));
+
+        match result {
+            Ok(Some((line, cursor))) => {
+                self.line.update(&line, cursor);
+                ReedlineEvent::Edit(vec![
+                    EditCommand::Clear,
+                    EditCommand::InsertString(line),
+                    EditCommand::MoveToPosition {
+                        position: cursor,
+                        select: false,
+                    },
+                ])
+            }
+            Ok(None) => ReedlineEvent::None,
+            Err(err) => {
+                report_shell_error(Some(&stack), &self.engine_state, &err);
+                ReedlineEvent::Repaint
+            }
+        }
+    }
+}
+
+impl EditMode for ClosureKeybindingsMode {
+    fn parse_event(&mut self, event: ReedlineRawEvent) -> ReedlineEvent {
+        let event = Event::from(event);
+        let mode = self.inner.edit_mode();
+        if let Event::Key(key) = &event
+            && let Some(keybinding) = self
+                .keybindings
+                .iter()
+                .find(|keybinding| keybinding.trigger.matches(key, &mode))
+        {
+            return self.run(&keybinding.closure);
+        }
+
+        // Only key releases are rejected, and those never made it here
+        let Ok(event) = ReedlineRawEvent::try_from(event) else {
+            return ReedlineEvent::None;
+        };
+        let event = self.inner.parse_event(event);
+        apply_mode_changes(self.inner.as_mut(), event)
+    }
+
+    fn edit_mode(&self) -> PromptEditMode {
+        self.inner.edit_mode()
+    }
+}
+
+/// The line and byte cursor to replace the command line with, from what a closure returned: a
+/// string to replace the line with, a record with a `buffer` and/or a `cursor`, or nothing to
+/// leave the line alone.
+fn replacement(
+    value: Value,
+    buffer: &str,
+    cursor: usize,
+) -> Result<Option<(String, usize)>, ShellError> {
+    match value {
+        Value::Nothing { .. } => Ok(None),
+        Value::String { val, .. } => {
+            let cursor = val.len();
+            Ok(Some((val, cursor)))
+        }
+        Value::Record { val, .. } => {
+            let line = match val.get("buffer") {
+                Some(line) => line.coerce_str()?.into_owned(),
+                None => buffer.to_owned(),
+            };
+            let cursor = match val.get("cursor") {
+                Some(position) => byte_offset(&line, position.as_int()?),
+                None if line == buffer => cursor,
+                None => line.len(),
+            };
+            Ok(Some((line, cursor)))
+        }
+        value => Err(ShellError::RuntimeTypeMismatch {
+            expected: Type::custom("string, record, or nothing"),
+            actual: value.get_type(),
+            span: value.span(),
+        }),
+    }
+}
+
+/// The byte offset of a cursor counted in graphemes, like `commandline set-cursor` takes.
+fn byte_offset(line: &str, position: i64) -> usize {
+    usize::try_from(position)
+        .ok()
+        .and_then(|position| line.grapheme_indices(true).nth(position))
+        .map_or(if position <= 0 { 0 } else { line.len() }, |(index, _)| {
+            index
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::record;
+
+    #[test]
+    fn replacement_from_closure_output() {
+        assert_eq!(replacement(Value::test_nothing(), "ls", 1).unwrap(), None);
+        assert_eq!(
+            replacement(Value::test_string("ls -la"), "ls", 1).unwrap(),
+            Some(("ls -la".into(), 6))
+        );
+        assert_eq!(
+            replacement(
+                Value::test_record(record! {"cursor" => Value::test_int(1)}),
+                "ls",
+                2
+            )
+            .unwrap(),
+            Some(("ls".into(), 1))
+        );
+        assert_eq!(
+            replacement(
+                Value::test_record(record! {
+                    "buffer" => Value::test_string("(äb)"),
+                    "cursor" => Value::test_int(2),
+                }),
+                "äb",
+                0
+            )
+            .unwrap(),
+            Some(("(äb)".into(), 3))
+        );
+        assert!(replacement(Value::test_int(1), "ls", 0).is_err());
+    }
+
+    #[test]
+    fn grapheme_cursor_is_clamped() {
+        assert_eq!(byte_offset("abc", -1), 0);
+        assert_eq!(byte_offset("abc", 10), 3);
+    }
+}
@@ -1,4 +1,5 @@
 use nu_engine::{command_prelude::*, get_full_help};
+use std::collections::HashMap;
 
 #[derive(Clone)]
 pub struct Abbreviations;
@@ -40,3 +41,16 @@ impl Command for Abbreviations {
         .into_pipeline_data())
     }
 }
+
+/// Change `$env.config.abbreviations` in the caller's scope.
+pub(super) fn update_abbreviations(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    span: Span,
+    update: impl FnOnce(&mut HashMap<String, String>) -> Result<(), ShellError>,
+) -> Result<(), ShellError> {
+    let mut config = (*stack.get_config(engine_state)).clone();
+    update(&mut config.abbreviations)?;
+    stack.add_env_var("config".into(), config.into_value(span));
+    stack.update_config(engine_state)
+}
@@ -0,0 +1,83 @@
+use super::abbr::update_abbreviations;
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AbbreviationsAdd;
+
+impl Command for AbbreviationsAdd {
+    fn name(&self) -> &str {
+        "abbr add"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required("name", SyntaxShape::String, "The abbreviation to type.")
+            .required(
+                "expansion",
+                SyntaxShape::String,
+                "The text the abbreviation expands to.",
+            )
+            .category(Category::Platform)
+    }
+
+    fn description(&self) -> &str {
+        "Add an abbreviation, or replace an existing one."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"When the abbreviation is typed as a word of its own and followed by a space or Enter, the line
+editor replaces it with its expansion, which can still be edited and ends up in the history as
+it was run. Unlike an alias, it doesn't hide what actually runs.
+
+If the expansion contains `%|`, the cursor is placed there after expanding instead of at the
+end, and the space isn't inserted.
+
+This changes `$env.config.abbreviations` in the current scope, so add it to your config to keep
+it."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["abbreviation", "alias", "shorthand", "expand"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let expansion: String = call.req(engine_state, stack, 1)?;
+
+        if name.item.is_empty() || name.item.contains(char::is_whitespace) {
+            return Err(ShellError::IncorrectValue {
+                msg: "an abbreviation must be a single word".into(),
+                val_span: name.span,
+                call_span: call.head,
+            });
+        }
+
+        update_abbreviations(engine_state, stack, call.head, |abbreviations| {
+            abbreviations.insert(name.item, expansion);
+            Ok(())
+        })?;
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Expand `gco` to `git checkout`.",
+                example: "abbr add gco 'git checkout'",
+                result: None,
+            },
+            Example {
+                description: "Expand `gcm` and put the cursor between the quotes.",
+                example: r#"abbr add gcm 'git commit -m "%|"'"#,
+                result: None,
+            },
+        ]
+    }
+}
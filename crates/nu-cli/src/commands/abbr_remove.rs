@@ -0,0 +1,67 @@
+use super::abbr::update_abbreviations;
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+
+#[derive(Clone)]
+pub struct AbbreviationsRemove;
+
+impl Command for AbbreviationsRemove {
+    fn name(&self) -> &str {
+        "abbr remove"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required("name", SyntaxShape::String, "The abbreviation to remove.")
+            .category(Category::Platform)
+    }
+
+    fn description(&self) -> &str {
+        "Remove an abbreviation."
+    }
+
+    fn extra_description(&self) -> &str {
+        "This changes `$env.config.abbreviations` in the current scope."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["abbreviation", "erase", "delete", "unset"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+
+        update_abbreviations(
+            engine_state,
+            stack,
+            call.head,
+            |abbreviations| match abbreviations.remove(&name.item) {
+                Some(_) => Ok(()),
+                None => Err(ShellError::Generic(
+                    GenericError::new(
+                        "Abbreviation not found",
+                        format!("no abbreviation named '{}'", name.item),
+                        name.span,
+                    )
+                    .with_help("use `abbr list` to see the abbreviations"),
+                )),
+            },
+        )?;
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Remove the `gco` abbreviation.",
+            example: "abbr remove gco",
+            result: None,
+        }]
+    }
+}
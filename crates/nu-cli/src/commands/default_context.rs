@@ -13,7 +13,9 @@ pub fn add_cli_context(mut engine_state: EngineState) -> EngineState {
 
         bind_command! {
             Abbreviations,
+            AbbreviationsAdd,
             AbbreviationsList,
+            AbbreviationsRemove,
             Commandline,
             CommandlineComplete,
             CommandlineEdit,
@@ -26,12 +28,25 @@ pub fn add_cli_context(mut engine_state: EngineState) -> EngineState {
             KeybindingsListen,
             NuHighlight,
             Print,
+            Record,
+            RecordOutput,
+            RecordReplay,
+            RecordStart,
+            RecordStop,
         };
 
         #[cfg(feature = "sqlite")]
         bind_command! {
+            HistoryExport,
             HistoryImport,
-            HistorySession
+            HistorySearch,
+            HistorySession,
+            HistoryStats,
+        };
+
+        #[cfg(feature = "git-prompt")]
+        bind_command! {
+            PromptGitStatus,
         };
 
         working_set.render()
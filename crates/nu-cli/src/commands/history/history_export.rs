@@ -0,0 +1,94 @@
+use super::{
+    fields,
+    history_search::{since_millis, sqlite_history_path},
+};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct HistoryExport;
+
+impl Command for HistoryExport {
+    fn name(&self) -> &str {
+        "history export"
+    }
+
+    fn description(&self) -> &str {
+        "Export the command history as a table that `history import` accepts."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The table keeps the session ID of each item, so merging it with `history import --dedupe` on another machine, or importing it again, doesn't create duplicates. Requires `$env.config.history.file_format` to be `sqlite`."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("history export")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .named(
+                "since",
+                SyntaxShape::OneOf(vec![SyntaxShape::DateTime, SyntaxShape::Duration]),
+                "Only export commands started after this date, or within this duration from now.",
+                Some('s'),
+            )
+            .category(Category::History)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["sync", "merge", "backup", "dotfiles"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "history export --since 1wk | save history-laptop.nuon",
+                description: "Save the history of the last week to a file",
+                result: None,
+            },
+            Example {
+                example: "open history-laptop.nuon | history import --dedupe",
+                description: "Merge an exported history into the current one",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let since: Option<Value> = call.get_flag(engine_state, stack, "since")?;
+
+        let history_path = sqlite_history_path(engine_state, head)?;
+
+        // Column names match the ones accepted by `history import`
+        let mut table = nu_command::SQLiteQueryBuilder::new(
+            history_path,
+            "history".to_string(),
+            engine_state.signals().clone(),
+        )
+        .with_select(format!(
+            "command_line as {}, start_timestamp as {}, session_id as {}, hostname as {}, cwd as {}, duration_ms as {}, exit_status as {}",
+            fields::COMMAND_LINE,
+            fields::START_TIMESTAMP,
+            fields::SESSION_ID,
+            fields::HOSTNAME,
+            fields::CWD,
+            fields::DURATION,
+            fields::EXIT_STATUS,
+        ))
+        .with_order_by("rowid ASC".to_string())
+        .with_unix_millis_datetime_column(fields::START_TIMESTAMP.to_string())
+        .with_millis_duration_column(fields::DURATION.to_string());
+        if let Some(since) = since {
+            table = table.with_where(
+                "start_timestamp >= ?".to_string(),
+                vec![since_millis(&since)?.to_string()],
+            );
+        }
+
+        Ok(Value::custom(Box::new(table), head).into_pipeline_data())
+    }
+}
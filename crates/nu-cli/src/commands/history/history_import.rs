@@ -1,4 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
 use nu_engine::command_prelude::*;
 use nu_protocol::{
@@ -7,7 +10,8 @@ use nu_protocol::{
 };
 
 use reedline::{
-    FileBackedHistory, History, HistoryItem, ReedlineError, SearchQuery, SqliteBackedHistory,
+    FileBackedHistory, History, HistoryItem, HistorySessionId, ReedlineError, SearchQuery,
+    SqliteBackedHistory,
 };
 
 use super::fields;
@@ -30,7 +34,9 @@ impl Command for HistoryImport {
 
 If no input is provided, will import all history items from existing history in the other format: if current history is stored in sqlite, it will store it in plain text and vice versa.
 
-Note that history item IDs are ignored when importing from file."
+Note that history item IDs are ignored when importing from file.
+
+With `--dedupe`, items already in the history are skipped, so the output of `history export` from several machines can be merged repeatedly. Two items are the same if they have the same command, start timestamp and session ID."
     }
 
     fn signature(&self) -> nu_protocol::Signature {
@@ -42,6 +48,11 @@ Note that history item IDs are ignored when importing from file."
                 (Type::List(Box::new(Type::String)), Type::Nothing),
                 (Type::table(), Type::Nothing),
             ])
+            .switch(
+                "dedupe",
+                "Skip items that are already in the history.",
+                Some('d'),
+            )
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -61,17 +72,23 @@ Note that history item IDs are ignored when importing from file."
                 description: "Append `foo` ran from `/home` to the current history",
                 result: None,
             },
+            Example {
+                example: "open history-laptop.nuon | history import --dedupe",
+                description: "Merge the history exported on another machine with `history export`",
+                result: None,
+            },
         ]
     }
 
     fn run(
         &self,
         engine_state: &EngineState,
-        _stack: &mut Stack,
+        stack: &mut Stack,
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let span = call.head;
+        let dedupe = call.has_flag(engine_state, stack, "dedupe")?;
         let ok = Ok(Value::nothing(call.head).into_pipeline_data());
 
         let Some(history) = engine_state.history_config() else {
@@ -110,7 +127,7 @@ Note that history item IDs are ignored when importing from file."
                     .map_err(error_from_reedline)?
                     .into_iter()
                     .map(Ok);
-                import(dst.as_mut(), items)
+                import(dst.as_mut(), items, dedupe)
             }
             _ => {
                 let input = input.into_iter().map(item_from_value);
@@ -123,6 +140,7 @@ Note that history item IDs are ignored when importing from file."
                     )?
                     .as_mut(),
                     input,
+                    dedupe,
                 )
             }
         }?;
@@ -166,20 +184,64 @@ fn new_backend(
 fn import(
     dst: &mut dyn History,
     src: impl Iterator<Item = Result<HistoryItem, ShellError>>,
+    dedupe: bool,
 ) -> Result<(), ShellError> {
+    let mut known = if dedupe {
+        let mut known = KnownItems::default();
+        let existing = dst
+            .search(SearchQuery::everything(
+                reedline::SearchDirection::Forward,
+                None,
+            ))
+            .map_err(error_from_reedline)?;
+        for item in existing {
+            known.insert(&item);
+        }
+        Some(known)
+    } else {
+        None
+    };
+
     for item in src {
         let mut item = item?;
+        if let Some(known) = &mut known
+            && !known.insert(&item)
+        {
+            continue;
+        }
         item.id = None;
         dst.save(item).map_err(error_from_reedline)?;
     }
     Ok(())
 }
 
+/// History items seen so far, keyed by start timestamp, session ID and command, for `--dedupe`.
+#[derive(Default)]
+struct KnownItems(HashSet<(Option<i64>, Option<i64>, String)>);
+
+impl KnownItems {
+    /// Remember the item, returning whether it wasn't known yet.
+    fn insert(&mut self, item: &HistoryItem) -> bool {
+        self.0.insert((
+            item.start_timestamp.map(|time| time.timestamp_millis()),
+            item.session_id.map(i64::from),
+            item.command_line.clone(),
+        ))
+    }
+}
+
 fn error_from_reedline(e: ReedlineError) -> ShellError {
     // TODO: Should we add a new ShellError variant?
     ShellError::Generic(GenericError::new_internal("Reedline error", format!("{e}")))
 }
 
+/// The session ID of an imported record.
+///
+/// Reedline only creates session IDs for new sessions, but they're (de)serialized as their number.
+fn session_id_from_value(v: Value) -> Result<HistorySessionId, ShellError> {
+    Ok(HistorySessionId::new(v.as_int()?))
+}
+
 fn item_from_value(v: Value) -> Result<HistoryItem, ShellError> {
     let span = v.span();
     match v {
@@ -220,7 +282,11 @@ fn item_from_record(mut rec: Record, span: Span) -> Result<HistoryItem, ShellErr
         field: &'static str,
         f: impl FnOnce(Value) -> Result<T, ShellError>,
     ) -> Result<Option<T>, ShellError> {
-        rec.remove(field).map(f).transpose()
+        // Exported histories contain `null` for the fields that were never recorded
+        rec.remove(field)
+            .filter(|v| !v.is_nothing())
+            .map(f)
+            .transpose()
     }
 
     let rec = &mut rec;
@@ -233,8 +299,7 @@ fn item_from_record(mut rec: Record, span: Span) -> Result<HistoryItem, ShellErr
         exit_status: get(rec, fields::EXIT_STATUS, |v| v.as_int())?,
         duration: get(rec, fields::DURATION, |v| duration_from_value(v, span))?,
         more_info: None,
-        // TODO: Currently reedline doesn't let you create session IDs.
-        session_id: None,
+        session_id: get(rec, fields::SESSION_ID, session_id_from_value)?,
     };
 
     if !rec.is_empty() {
@@ -392,6 +457,45 @@ mod tests {
         assert!(item_from_value(rec).is_err());
     }
 
+    #[test]
+    fn test_item_from_value_record_session_id() {
+        let span = Span::test_data();
+        let rec = new_record(&[
+            ("command", Value::string("foo", span)),
+            ("session_id", Value::int(7, span)),
+        ]);
+        let item = item_from_value(rec).unwrap();
+        assert_eq!(item.session_id.map(i64::from), Some(7));
+    }
+
+    #[test]
+    fn test_known_items_compare_sessions() {
+        let span = Span::test_data();
+        let item = |session: Option<i64>| {
+            let mut fields = vec![
+                ("command", Value::string("foo", span)),
+                (
+                    "start_timestamp",
+                    Value::date(
+                        DateTime::parse_from_rfc3339("1996-12-19T16:39:57-08:00").unwrap(),
+                        span,
+                    ),
+                ),
+            ];
+            if let Some(session) = session {
+                fields.push(("session_id", Value::int(session, span)));
+            }
+            item_from_value(new_record(&fields)).unwrap()
+        };
+
+        let mut known = KnownItems::default();
+        assert!(known.insert(&item(Some(1))));
+        assert!(!known.insert(&item(Some(1))));
+        assert!(known.insert(&item(Some(2))));
+        assert!(known.insert(&item(None)));
+        assert!(!known.insert(&item(None)));
+    }
+
     fn new_record(rec: &[(&'static str, Value)]) -> Value {
         let span = Span::test_data();
         let rec = Record::from_raw_cols_vals(
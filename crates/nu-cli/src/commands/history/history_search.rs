@@ -0,0 +1,164 @@
+use super::fields;
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::{HistoryFileFormat, shell_error::generic::GenericError};
+use std::path::PathBuf;
+
+#[derive(Clone)]
+pub struct HistorySearch;
+
+impl Command for HistorySearch {
+    fn name(&self) -> &str {
+        "history search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the command history, filtering directly in the SQLite history database."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Unlike `history | where ...`, the filters are part of the query sent to the database, so only matching entries are read. Requires `$env.config.history.file_format` to be `sqlite`."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("history search")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .optional(
+                "term",
+                SyntaxShape::String,
+                "Text the command must contain.",
+            )
+            .named(
+                "cwd",
+                SyntaxShape::Directory,
+                "Only commands run in this directory.",
+                None,
+            )
+            .named(
+                "exit-code",
+                SyntaxShape::Int,
+                "Only commands that finished with this exit code.",
+                Some('e'),
+            )
+            .named(
+                "since",
+                SyntaxShape::OneOf(vec![SyntaxShape::DateTime, SyntaxShape::Duration]),
+                "Only commands started after this date, or within this duration from now.",
+                Some('s'),
+            )
+            .category(Category::History)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["find", "filter", "grep"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "history search cargo --cwd .",
+                description: "Find the `cargo` commands run in the current directory",
+                result: None,
+            },
+            Example {
+                example: "history search --exit-code 1 --since 1day",
+                description: "List the commands that failed during the last day",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let term: Option<String> = call.opt(engine_state, stack, 0)?;
+        let cwd: Option<String> = call.get_flag(engine_state, stack, "cwd")?;
+        let exit_code: Option<i64> = call.get_flag(engine_state, stack, "exit-code")?;
+        let since: Option<Value> = call.get_flag(engine_state, stack, "since")?;
+
+        let history_path = sqlite_history_path(engine_state, head)?;
+
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+        if let Some(term) = term {
+            conditions.push("instr(command_line, ?) > 0");
+            params.push(term);
+        }
+        if let Some(cwd) = cwd {
+            let cwd = expand_path_with(cwd, engine_state.cwd(Some(stack))?, true);
+            conditions.push("cwd = ?");
+            params.push(cwd.to_string_lossy().into_owned());
+        }
+        if let Some(exit_code) = exit_code {
+            conditions.push("exit_status = ?");
+            params.push(exit_code.to_string());
+        }
+        if let Some(since) = since {
+            conditions.push("start_timestamp >= ?");
+            params.push(since_millis(&since)?.to_string());
+        }
+
+        let mut table = nu_command::SQLiteQueryBuilder::new(
+            history_path,
+            "history".to_string(),
+            engine_state.signals().clone(),
+        )
+        .with_select(
+            "start_timestamp, command_line as command, cwd, duration_ms as duration, exit_status"
+                .to_string(),
+        )
+        .with_order_by("rowid ASC".to_string())
+        .with_unix_millis_datetime_column(fields::START_TIMESTAMP.to_string())
+        .with_millis_duration_column(fields::DURATION.to_string());
+        if !conditions.is_empty() {
+            table = table.with_where(conditions.join(" AND "), params);
+        }
+
+        Ok(PipelineData::Value(
+            Value::custom(Box::new(table), head),
+            Some(nu_protocol::PipelineMetadata::default().with_path_columns(vec!["cwd".into()])),
+        ))
+    }
+}
+
+/// Path of the SQLite history database, failing if the history isn't stored in SQLite.
+pub(super) fn sqlite_history_path(
+    engine_state: &EngineState,
+    span: Span,
+) -> Result<PathBuf, ShellError> {
+    let history = engine_state
+        .history_config()
+        .filter(|history| history.file_format == HistoryFileFormat::Sqlite)
+        .ok_or_else(|| {
+            ShellError::Generic(
+                GenericError::new(
+                    "SQLite history required",
+                    "this command only works with the SQLite history",
+                    span,
+                )
+                .with_help("set `$env.config.history.file_format = \"sqlite\"` in your config"),
+            )
+        })?;
+
+    history
+        .file_path(&engine_state.config_dirs.config_home)
+        .ok_or(ShellError::ConfigDirNotFound { span })
+}
+
+/// Convert a `--since` value, either a date or a duration before now, to unix milliseconds.
+pub(super) fn since_millis(since: &Value) -> Result<i64, ShellError> {
+    match since {
+        Value::Date { val, .. } => Ok(val.timestamp_millis()),
+        Value::Duration { val, .. } => Ok(chrono::Utc::now().timestamp_millis() - val / 1_000_000),
+        other => Err(ShellError::RuntimeTypeMismatch {
+            expected: Type::custom("datetime or duration"),
+            actual: other.get_type(),
+            span: other.span(),
+        }),
+    }
+}
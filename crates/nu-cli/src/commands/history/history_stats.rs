@@ -0,0 +1,100 @@
+use super::history_search::{since_millis, sqlite_history_path};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct HistoryStats;
+
+impl Command for HistoryStats {
+    fn name(&self) -> &str {
+        "history stats"
+    }
+
+    fn description(&self) -> &str {
+        "Summarize the most used commands of the command history."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The summary is computed by the SQLite history database. Requires `$env.config.history.file_format` to be `sqlite`."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("history stats")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .named(
+                "limit",
+                SyntaxShape::Int,
+                "Number of commands to show (default: 10).",
+                Some('n'),
+            )
+            .named(
+                "since",
+                SyntaxShape::OneOf(vec![SyntaxShape::DateTime, SyntaxShape::Duration]),
+                "Only count commands started after this date, or within this duration from now.",
+                Some('s'),
+            )
+            .category(Category::History)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["frequency", "top", "most used", "summary"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "history stats",
+                description: "Show the 10 most used commands",
+                result: None,
+            },
+            Example {
+                example: "history stats --limit 3 --since 1wk",
+                description: "Show the 3 most used commands of the last week",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let limit: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "limit")?;
+        let since: Option<Value> = call.get_flag(engine_state, stack, "since")?;
+
+        let limit = match limit {
+            Some(limit) if limit.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue { span: limit.span });
+            }
+            Some(limit) => limit.item,
+            None => 10,
+        };
+
+        let history_path = sqlite_history_path(engine_state, head)?;
+
+        let mut table = nu_command::SQLiteQueryBuilder::new(
+            history_path,
+            "history".to_string(),
+            engine_state.signals().clone(),
+        )
+        .with_select(
+            "command_line as command, count(*) as count, max(start_timestamp) as last_used"
+                .to_string(),
+        )
+        .with_group_by("command_line".to_string())
+        .with_order_by("count DESC, last_used DESC".to_string())
+        .with_limit(limit)
+        .with_unix_millis_datetime_column("last_used".to_string());
+        if let Some(since) = since {
+            table = table.with_where(
+                "start_timestamp >= ?".to_string(),
+                vec![since_millis(&since)?.to_string()],
+            );
+        }
+
+        Ok(Value::custom(Box::new(table), head).into_pipeline_data())
+    }
+}
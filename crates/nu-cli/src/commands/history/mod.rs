@@ -5,11 +5,23 @@ pub use history_::History;
 
 // if more history formats are added, will need to reconsider this
 #[cfg(feature = "sqlite")]
+mod history_export;
+#[cfg(feature = "sqlite")]
 mod history_import;
 #[cfg(feature = "sqlite")]
+mod history_search;
+#[cfg(feature = "sqlite")]
 mod history_session;
+#[cfg(feature = "sqlite")]
+mod history_stats;
 
+#[cfg(feature = "sqlite")]
+pub use history_export::HistoryExport;
 #[cfg(feature = "sqlite")]
 pub use history_import::HistoryImport;
 #[cfg(feature = "sqlite")]
+pub use history_search::HistorySearch;
+#[cfg(feature = "sqlite")]
 pub use history_session::HistorySession;
+#[cfg(feature = "sqlite")]
+pub use history_stats::HistoryStats;
@@ -1,5 +1,7 @@
 mod abbr;
+mod abbr_add;
 mod abbr_list;
+mod abbr_remove;
 mod commandline;
 mod default_context;
 mod history;
@@ -9,9 +11,14 @@ mod keybindings_list;
 mod keybindings_listen;
 mod nu_highlight;
 mod print;
+#[cfg(feature = "git-prompt")]
+mod prompt_git_status;
+mod record;
 
 pub use abbr::Abbreviations;
+pub use abbr_add::AbbreviationsAdd;
 pub use abbr_list::AbbreviationsList;
+pub use abbr_remove::AbbreviationsRemove;
 pub use commandline::{
     Commandline, CommandlineComplete, CommandlineEdit, CommandlineGetCursor, CommandlineSetCursor,
 };
@@ -22,5 +29,8 @@ pub use keybindings_list::KeybindingsList;
 pub use keybindings_listen::KeybindingsListen;
 pub use nu_highlight::NuHighlight;
 pub use print::Print;
+#[cfg(feature = "git-prompt")]
+pub use prompt_git_status::PromptGitStatus;
+pub use record::{Record, RecordOutput, RecordReplay, RecordStart, RecordStop};
 
 pub use default_context::add_cli_context;
@@ -0,0 +1,404 @@
+use git2::{Branch, ErrorCode, Repository, Status, StatusOptions};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::{generic::GenericError, io::IoError};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{LazyLock, Mutex, mpsc},
+    time::Duration,
+};
+
+/// Last status computed for each repository, keyed by the repository directory.
+static STATUS_CACHE: LazyLock<Mutex<HashMap<PathBuf, CachedStatus>>> =
+    LazyLock::new(Default::default);
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Clone)]
+pub struct PromptGitStatus;
+
+impl Command for PromptGitStatus {
+    fn name(&self) -> &str {
+        "prompt git-status"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![
+                (Type::Nothing, Type::record()),
+                (Type::Nothing, Type::Nothing),
+            ])
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "How long to wait for the status before using the last one computed (default: 100ms).",
+                Some('t'),
+            )
+            .category(Category::Platform)
+    }
+
+    fn description(&self) -> &str {
+        "Get the git status of the current directory, for use in prompts."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The status is computed without running `git`. When it takes longer than the timeout, as in big repositories, the last status of the repository is returned with `stale` set, and the computation finishes in the background for the next prompt. Returns null outside of a git repository."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["git", "branch", "dirty", "ahead", "behind", "gstat"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let timeout = match timeout {
+            Some(timeout) if timeout.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue { span: timeout.span });
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => DEFAULT_TIMEOUT,
+        };
+
+        let cwd = engine_state.cwd(Some(stack))?;
+        let repo = match Repository::discover(cwd.as_std_path()) {
+            Ok(repo) => repo,
+            Err(err) if err.code() == ErrorCode::NotFound => {
+                return Ok(Value::nothing(head).into_pipeline_data());
+            }
+            Err(err) => return Err(git_error(err, head)),
+        };
+        let repo_dir = repo.workdir().unwrap_or(repo.path()).to_path_buf();
+        drop(repo);
+
+        let (status, stale) = repo_status(repo_dir, timeout, GitStatus::compute, head)?;
+        Ok(status.into_value(stale, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Show the branch and whether the working tree has changes",
+                example: "let git = prompt git-status; if $git != null { $git.branch + (if $git.dirty { '*' } else { '' }) }",
+                result: None,
+            },
+            Example {
+                description: "Wait longer for the status of a big repository",
+                example: "prompt git-status --timeout 500ms",
+                result: None,
+            },
+        ]
+    }
+}
+
+struct CachedStatus {
+    status: GitStatus,
+    pending: bool,
+}
+
+/// The status of a repository, and whether it is the last one computed rather than a fresh one.
+fn repo_status(
+    repo_dir: PathBuf,
+    timeout: Duration,
+    compute: fn(&Path) -> Result<GitStatus, git2::Error>,
+    span: Span,
+) -> Result<(GitStatus, bool), ShellError> {
+    // Only one computation per repository at a time, so slow repositories don't pile up
+    // threads when prompts are drawn faster than the status is computed. Nothing will be sent
+    // for this prompt then, so the last status is returned without waiting.
+    let pending = STATUS_CACHE
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&repo_dir).map(|cached| cached.pending))
+        .unwrap_or(false);
+    if pending {
+        return Ok((last_status(&repo_dir), true));
+    }
+
+    set_pending(&repo_dir);
+    let (sender, receiver) = mpsc::channel();
+    let thread_dir = repo_dir.clone();
+    std::thread::Builder::new()
+        .name("prompt git-status".into())
+        .spawn(move || {
+            let _pending = PendingGuard(&thread_dir);
+            let status = compute(&thread_dir);
+            if let Ok(mut cache) = STATUS_CACHE.lock() {
+                match &status {
+                    Ok(status) => {
+                        cache.insert(
+                            thread_dir.clone(),
+                            CachedStatus {
+                                status: status.clone(),
+                                pending: false,
+                            },
+                        );
+                    }
+                    // Let the next prompt try again
+                    Err(_) => {
+                        cache.remove(&thread_dir);
+                    }
+                }
+            }
+            // The receiver is gone if the command already timed out
+            let _ = sender.send(status);
+        })
+        .map_err(|err| {
+            if let Ok(mut cache) = STATUS_CACHE.lock() {
+                cache.remove(&repo_dir);
+            }
+            IoError::new_internal(err, "Could not start computing the git status")
+        })?;
+
+    match receiver.recv_timeout(timeout) {
+        Ok(status) => Ok((status.map_err(|err| git_error(err, span))?, false)),
+        Err(_) => Ok((last_status(&repo_dir), true)),
+    }
+}
+
+fn last_status(repo_dir: &Path) -> GitStatus {
+    STATUS_CACHE
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(repo_dir).map(|cached| cached.status.clone()))
+        .unwrap_or_default()
+}
+
+/// Clears the pending computation of a repository, even when computing its status panicked.
+struct PendingGuard<'a>(&'a Path);
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut cache) = STATUS_CACHE.lock()
+            && let Some(cached) = cache.get_mut(self.0)
+        {
+            cached.pending = false;
+        }
+    }
+}
+
+fn set_pending(repo_dir: &Path) {
+    if let Ok(mut cache) = STATUS_CACHE.lock() {
+        cache
+            .entry(repo_dir.to_path_buf())
+            .or_insert_with(|| CachedStatus {
+                status: GitStatus::default(),
+                pending: true,
+            })
+            .pending = true;
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct GitStatus {
+    branch: Option<String>,
+    ahead: usize,
+    behind: usize,
+    staged: usize,
+    unstaged: usize,
+    untracked: usize,
+    conflicted: usize,
+}
+
+impl GitStatus {
+    fn compute(repo_dir: &Path) -> Result<Self, git2::Error> {
+        let repo = Repository::open(repo_dir)?;
+        let mut status = GitStatus::default();
+
+        match repo.head() {
+            Ok(head) if repo.head_detached()? => {
+                status.branch = head
+                    .target()
+                    .map(|oid| oid.to_string().chars().take(7).collect());
+            }
+            Ok(head) => {
+                status.branch = head.shorthand().map(str::to_string);
+                let local = head.target();
+                let upstream = Branch::wrap(head)
+                    .upstream()
+                    .ok()
+                    .and_then(|upstream| upstream.get().target());
+                if let (Some(local), Some(upstream)) = (local, upstream) {
+                    (status.ahead, status.behind) = repo.graph_ahead_behind(local, upstream)?;
+                }
+            }
+            // A branch without commits yet
+            Err(err) if err.code() == ErrorCode::UnbornBranch => {
+                status.branch = repo
+                    .find_reference("HEAD")?
+                    .symbolic_target()
+                    .map(|target| target.trim_start_matches("refs/heads/").to_string());
+            }
+            Err(err) => return Err(err),
+        }
+
+        let mut options = StatusOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(false)
+            .exclude_submodules(true);
+        for entry in repo.statuses(Some(&mut options))?.iter() {
+            let flags = entry.status();
+            if flags.intersects(
+                Status::INDEX_NEW
+                    | Status::INDEX_MODIFIED
+                    | Status::INDEX_DELETED
+                    | Status::INDEX_RENAMED
+                    | Status::INDEX_TYPECHANGE,
+            ) {
+                status.staged += 1;
+            }
+            if flags.intersects(
+                Status::WT_MODIFIED
+                    | Status::WT_DELETED
+                    | Status::WT_RENAMED
+                    | Status::WT_TYPECHANGE,
+            ) {
+                status.unstaged += 1;
+            }
+            if flags.contains(Status::WT_NEW) {
+                status.untracked += 1;
+            }
+            if flags.contains(Status::CONFLICTED) {
+                status.conflicted += 1;
+            }
+        }
+
+        Ok(status)
+    }
+
+    fn into_value(self, stale: bool, span: Span) -> Value {
+        let dirty = self.staged + self.unstaged + self.untracked + self.conflicted > 0;
+        Value::record(
+            record! {
+                "branch" => match self.branch {
+                    Some(branch) => Value::string(branch, span),
+                    None => Value::nothing(span),
+                },
+                "ahead" => Value::int(self.ahead as i64, span),
+                "behind" => Value::int(self.behind as i64, span),
+                "staged" => Value::int(self.staged as i64, span),
+                "unstaged" => Value::int(self.unstaged as i64, span),
+                "untracked" => Value::int(self.untracked as i64, span),
+                "conflicted" => Value::int(self.conflicted as i64, span),
+                "dirty" => Value::bool(dirty, span),
+                "stale" => Value::bool(stale, span),
+            },
+            span,
+        )
+    }
+}
+
+fn git_error(err: git2::Error, span: Span) -> ShellError {
+    ShellError::Generic(GenericError::new(
+        "Could not get the git status",
+        err.message().to_string(),
+        span,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn test_dir(name: &str) -> PathBuf {
+        PathBuf::from("prompt-git-status-test").join(name)
+    }
+
+    fn on_branch(_: &Path) -> Result<GitStatus, git2::Error> {
+        Ok(GitStatus {
+            branch: Some("main".into()),
+            ..Default::default()
+        })
+    }
+
+    fn slow(_: &Path) -> Result<GitStatus, git2::Error> {
+        std::thread::sleep(Duration::from_secs(2));
+        Ok(GitStatus::default())
+    }
+
+    fn panics(_: &Path) -> Result<GitStatus, git2::Error> {
+        panic!("computing the status failed")
+    }
+
+    fn is_pending(repo_dir: &Path) -> bool {
+        STATUS_CACHE
+            .lock()
+            .expect("status cache")
+            .get(repo_dir)
+            .is_some_and(|cached| cached.pending)
+    }
+
+    #[test]
+    fn returns_fresh_status() {
+        let dir = test_dir("fresh");
+        let (status, stale) = repo_status(
+            dir.clone(),
+            Duration::from_secs(5),
+            on_branch,
+            Span::test_data(),
+        )
+        .expect("status");
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert!(!stale);
+        assert!(!is_pending(&dir));
+    }
+
+    #[test]
+    fn pending_status_returns_without_waiting() {
+        let dir = test_dir("pending");
+        let (_, stale) =
+            repo_status(dir.clone(), Duration::ZERO, slow, Span::test_data()).expect("status");
+        assert!(stale);
+        assert!(is_pending(&dir));
+
+        let started = Instant::now();
+        let (_, stale) = repo_status(
+            dir.clone(),
+            Duration::from_secs(5),
+            on_branch,
+            Span::test_data(),
+        )
+        .expect("status");
+        assert!(stale);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn panic_clears_pending() {
+        let dir = test_dir("panic");
+        let result = repo_status(
+            dir.clone(),
+            Duration::from_secs(5),
+            panics,
+            Span::test_data(),
+        );
+        // The sender is dropped by the panic, so the last status is used
+        assert!(result.is_ok_and(|(_, stale)| stale));
+        assert!(!is_pending(&dir));
+
+        let (status, stale) =
+            repo_status(dir, Duration::from_secs(5), on_branch, Span::test_data()).expect("status");
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert!(!stale);
+    }
+
+    #[test]
+    fn counts_untracked_files() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        Repository::init(dir.path()).expect("init repository");
+        std::fs::write(dir.path().join("new.txt"), "new").expect("write file");
+
+        let status = GitStatus::compute(dir.path()).expect("status");
+        assert_eq!(status.untracked, 1);
+        assert_eq!(status.staged, 0);
+        assert!(status.branch.is_some());
+    }
+}
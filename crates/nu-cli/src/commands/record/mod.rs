@@ -0,0 +1,11 @@
+mod record_;
+mod record_output;
+mod record_replay;
+mod record_start;
+mod record_stop;
+
+pub use record_::Record;
+pub use record_output::RecordOutput;
+pub use record_replay::RecordReplay;
+pub use record_start::RecordStart;
+pub use record_stop::RecordStop;
@@ -0,0 +1,226 @@
+use crate::recording::recording_file;
+use nu_engine::command_prelude::*;
+use nu_protocol::{ByteStreamSource, engine::ReplState, process::ChildPipe};
+use std::{
+    io::{self, Read},
+    sync::{Arc, Mutex},
+};
+
+/// Lines of output kept in the recording.
+const SNIPPET_LINES: usize = 20;
+
+#[derive(Clone)]
+pub struct RecordOutput;
+
+impl Command for RecordOutput {
+    fn name(&self) -> &str {
+        "record output"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .category(Category::History)
+    }
+
+    fn description(&self) -> &str {
+        "Keep a snippet of the input in the session recording, and pass it through."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The first lines of the input, rendered as text, are stored with the command being recorded. Streams are passed through as they are read, and only their start is kept. Without an active recording, the input is passed through untouched."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["session", "snippet", "capture", "tee"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        if recording_file(engine_state, stack).is_none() {
+            return Ok(input);
+        }
+
+        let mut snippet = Snippet::new(engine_state.repl_state.clone());
+        let config = stack.get_config(engine_state);
+
+        match input {
+            PipelineData::Empty => Ok(PipelineData::Empty),
+            PipelineData::Value(value, metadata) => {
+                snippet.push_text(&value.to_expanded_string("\n", &config));
+                Ok(PipelineData::Value(value, metadata))
+            }
+            PipelineData::ListStream(stream, metadata) => {
+                let stream = stream.map(move |value| {
+                    snippet.push_text(&value.to_expanded_string("\n", &config));
+                    value
+                });
+                Ok(PipelineData::list_stream(stream, metadata))
+            }
+            PipelineData::ByteStream(mut stream, metadata) => {
+                let source = std::mem::replace(
+                    stream.source_mut(),
+                    ByteStreamSource::Read(Box::new(io::empty())),
+                );
+                *stream.source_mut() = match source {
+                    ByteStreamSource::Read(read) => {
+                        ByteStreamSource::Read(Box::new(SnippetReader { read, snippet }))
+                    }
+                    ByteStreamSource::File(file) => {
+                        ByteStreamSource::Read(Box::new(SnippetReader {
+                            read: file,
+                            snippet,
+                        }))
+                    }
+                    ByteStreamSource::Child(mut child) => {
+                        // Only stdout is kept, stderr is left as it is
+                        if let Some(stdout) = child.stdout.take() {
+                            child.stdout = Some(ChildPipe::Tee(Box::new(SnippetReader {
+                                read: stdout,
+                                snippet,
+                            })));
+                        }
+                        ByteStreamSource::Child(child)
+                    }
+                };
+                Ok(PipelineData::byte_stream(stream, metadata))
+            }
+        }
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Run a command and keep the start of its output in the recording",
+            example: "cargo test | record output",
+            result: None,
+        }]
+    }
+}
+
+/// The start of the output, kept in the REPL state as it streams by.
+struct Snippet {
+    repl_state: Arc<Mutex<ReplState>>,
+    buffer: Vec<u8>,
+    lines: usize,
+}
+
+impl Snippet {
+    fn new(repl_state: Arc<Mutex<ReplState>>) -> Self {
+        Self {
+            repl_state,
+            buffer: Vec::new(),
+            lines: 0,
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        self.push_bytes(text.as_bytes());
+        self.push_bytes(b"\n");
+    }
+
+    fn push_bytes(&mut self, mut bytes: &[u8]) {
+        if self.lines >= SNIPPET_LINES || bytes.is_empty() {
+            return;
+        }
+        while let Some(pos) = bytes.iter().position(|&b| b == b'\n') {
+            self.buffer.extend_from_slice(&bytes[..pos]);
+            self.lines += 1;
+            if self.lines >= SNIPPET_LINES {
+                bytes = &[];
+                break;
+            }
+            self.buffer.push(b'\n');
+            bytes = &bytes[pos + 1..];
+        }
+        self.buffer.extend_from_slice(bytes);
+
+        let output = String::from_utf8_lossy(&self.buffer)
+            .trim_end_matches('\n')
+            .to_string();
+        if let Ok(mut repl) = self.repl_state.lock() {
+            repl.recorded_output = Some(output);
+        }
+    }
+}
+
+/// Passes bytes through, keeping the first lines in the snippet.
+struct SnippetReader<R> {
+    read: R,
+    snippet: Snippet,
+}
+
+impl<R: Read> Read for SnippetReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.read.read(buf)?;
+        self.snippet.push_bytes(&buf[..len]);
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet() -> (Snippet, Arc<Mutex<ReplState>>) {
+        let repl_state = Arc::new(Mutex::new(ReplState {
+            buffer: String::new(),
+            cursor_pos: 0,
+            accept: false,
+            recorded_output: None,
+        }));
+        (Snippet::new(repl_state.clone()), repl_state)
+    }
+
+    fn output(repl_state: &Arc<Mutex<ReplState>>) -> Option<String> {
+        repl_state
+            .lock()
+            .expect("repl state mutex")
+            .recorded_output
+            .clone()
+    }
+
+    #[test]
+    fn keeps_lines_split_across_reads() {
+        let (mut snippet, repl_state) = snippet();
+        snippet.push_bytes(b"fir");
+        snippet.push_bytes(b"st\nsec");
+        snippet.push_bytes(b"ond\n");
+        assert_eq!(output(&repl_state).as_deref(), Some("first\nsecond"));
+    }
+
+    #[test]
+    fn stops_after_the_snippet_lines() {
+        let (mut snippet, repl_state) = snippet();
+        for n in 0..SNIPPET_LINES + 5 {
+            snippet.push_text(&n.to_string());
+        }
+        let output = output(&repl_state).expect("output kept");
+        assert_eq!(output.lines().count(), SNIPPET_LINES);
+        assert_eq!(
+            output.lines().last(),
+            Some((SNIPPET_LINES - 1).to_string().as_str())
+        );
+    }
+
+    #[test]
+    fn reader_passes_everything_through() {
+        let (snippet, repl_state) = snippet();
+        let text = (0..50).map(|n| format!("{n}\n")).collect::<String>();
+        let mut reader = SnippetReader {
+            read: text.as_bytes(),
+            snippet,
+        };
+        let mut read = String::new();
+        reader.read_to_string(&mut read).expect("read");
+        assert_eq!(read, text);
+        assert_eq!(
+            output(&repl_state).map(|output| output.lines().count()),
+            Some(SNIPPET_LINES)
+        );
+    }
+}
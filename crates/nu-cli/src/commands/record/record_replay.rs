@@ -0,0 +1,148 @@
+use crate::recording::{Entry, REPLAY_ENV, read_session};
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::shell_error::{generic::GenericError, io::IoError};
+use std::path::Path;
+
+/// Longest pause kept when playing a cast, in seconds.
+const CAST_IDLE_TIME_LIMIT: f64 = 2.0;
+
+#[derive(Clone)]
+pub struct RecordReplay;
+
+impl Command for RecordReplay {
+    fn name(&self) -> &str {
+        "record replay"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required("path", SyntaxShape::Filepath, "The session file to replay.")
+            .named(
+                "cast",
+                SyntaxShape::Filepath,
+                "Render the session to an asciinema cast file instead of replaying it.",
+                Some('c'),
+            )
+            .switch("force", "Overwrite an existing cast file.", Some('f'))
+            .category(Category::History)
+    }
+
+    fn description(&self) -> &str {
+        "Replay a recorded session step by step, or render it as an asciinema cast."
+    }
+
+    fn extra_description(&self) -> &str {
+        "When replaying, each recorded command is put on the command line at the next prompt, so it can be run with Enter, edited, or skipped with Ctrl+C. `record stop` ends the replay early.
+
+The cast is written in the asciicast v2 format, with the recorded timings and output snippets."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["session", "demo", "asciinema", "playback"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let path: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let cast: Option<Spanned<String>> = call.get_flag(engine_state, stack, "cast")?;
+        let force = call.has_flag(engine_state, stack, "force")?;
+
+        let cwd = engine_state.cwd(Some(stack))?;
+        let session = read_session(&expand_path_with(&path.item, &cwd, true), path.span)?;
+
+        if let Some(cast) = cast {
+            let file = expand_path_with(&cast.item, &cwd, true);
+            if file.exists() && !force {
+                return Err(ShellError::Generic(
+                    GenericError::new(
+                        "Cast file exists",
+                        format!("'{}' already exists", file.display()),
+                        cast.span,
+                    )
+                    .with_help("use --force to overwrite it"),
+                ));
+            }
+            return write_cast(&file, &session, cast.span).map(|()| PipelineData::empty());
+        }
+
+        if !engine_state.is_interactive {
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    "Replay needs an interactive session",
+                    "commands can only be replayed at the REPL prompt",
+                    call.head,
+                )
+                .with_help("use --cast to render the session instead"),
+            ));
+        }
+
+        let commands = session
+            .into_iter()
+            .map(|entry| Value::string(entry.command, call.head))
+            .collect();
+        stack.add_env_var(REPLAY_ENV.into(), Value::list(commands, call.head));
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Replay a session, one command at each prompt",
+                example: "record replay demo.jsonl",
+                result: None,
+            },
+            Example {
+                description: "Render a session for asciinema",
+                example: "record replay demo.jsonl --cast demo.cast",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Write the session as an asciicast v2 file: a header line, then one `[time, "o", text]` event
+/// per chunk of terminal output.
+fn write_cast(path: &Path, session: &[Entry], span: Span) -> Result<(), ShellError> {
+    let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+    let first_start = session.first().map(|entry| entry.start);
+
+    let mut header = serde_json::json!({
+        "version": 2,
+        "width": width,
+        "height": height,
+        "idle_time_limit": CAST_IDLE_TIME_LIMIT,
+        "env": { "SHELL": "nu" },
+    });
+    if let Some(first_start) = first_start {
+        header["timestamp"] = first_start.timestamp().into();
+    }
+
+    let mut lines = vec![header.to_string()];
+    let mut event = |time: f64, text: String| {
+        lines.push(serde_json::json!([time, "o", text]).to_string());
+    };
+    for entry in session {
+        let start = first_start
+            .map(|first_start| (entry.start - first_start).num_milliseconds() as f64 / 1000.0)
+            .unwrap_or_default();
+        event(start, format!("{}> {}\r\n", entry.cwd, entry.command));
+        if let Some(output) = &entry.output {
+            let end = start + entry.duration.as_secs_f64();
+            event(end, format!("{}\r\n", output.replace('\n', "\r\n")));
+        }
+    }
+
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    std::fs::write(path, contents).map_err(|err| IoError::new(err, span, path.to_path_buf()))?;
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::recording::{RECORDING_ENV, recording_file};
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::shell_error::{generic::GenericError, io::IoError};
+use std::fs::OpenOptions;
+
+#[derive(Clone)]
+pub struct RecordStart;
+
+impl Command for RecordStart {
+    fn name(&self) -> &str {
+        "record start"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required(
+                "path",
+                SyntaxShape::Filepath,
+                "The session file to record to.",
+            )
+            .switch(
+                "append",
+                "Add to an existing session file instead of failing.",
+                Some('a'),
+            )
+            .category(Category::History)
+    }
+
+    fn description(&self) -> &str {
+        "Start recording the commands run in the REPL to a session file."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Each command is written with its directory, start time, duration and exit code, one JSON object per line. Pipe into `record output` to also keep a snippet of a command's output. The recording lasts until `record stop`."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["session", "demo", "log", "transcript"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let path: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let append = call.has_flag(engine_state, stack, "append")?;
+
+        if let Some(active) = recording_file(engine_state, stack) {
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    "Already recording",
+                    format!("the session is already recorded to '{}'", active.display()),
+                    call.head,
+                )
+                .with_help("run `record stop` first"),
+            ));
+        }
+
+        let file = expand_path_with(&path.item, engine_state.cwd(Some(stack))?, true);
+        if file.exists() && !append {
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    "Session file exists",
+                    format!("'{}' already exists", file.display()),
+                    path.span,
+                )
+                .with_help("use --append to add to it"),
+            ));
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&file)
+            .map_err(|err| IoError::new(err, path.span, file.clone()))?;
+
+        stack.add_env_var(
+            RECORDING_ENV.into(),
+            Value::string(file.to_string_lossy(), call.head),
+        );
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Record the following commands to a session file",
+                example: "record start demo.jsonl",
+                result: None,
+            },
+            Example {
+                description: "Continue an earlier recording",
+                example: "record start --append demo.jsonl",
+                result: None,
+            },
+        ]
+    }
+}
@@ -0,0 +1,52 @@
+use crate::recording::{RECORDING_ENV, REPLAY_ENV};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct RecordStop;
+
+impl Command for RecordStop {
+    fn name(&self) -> &str {
+        "record stop"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .category(Category::History)
+    }
+
+    fn description(&self) -> &str {
+        "Stop the active session recording, and any step-by-step replay."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["session", "end", "cancel"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        _call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        for name in [RECORDING_ENV, REPLAY_ENV] {
+            stack.remove_env_var(engine_state, name);
+        }
+        engine_state
+            .repl_state
+            .lock()
+            .expect("repl state mutex")
+            .recorded_output = None;
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Stop recording",
+            example: "record stop",
+            result: None,
+        }]
+    }
+}
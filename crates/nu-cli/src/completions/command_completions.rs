@@ -1,9 +1,9 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, path::PathBuf, sync::Arc};
 
 use crate::completions::{Completer, CompletionOptions};
 use nu_protocol::{
     Category, DeclId, Span, SuggestionKind,
-    engine::{CommandType, Stack, StateWorkingSet},
+    engine::{CommandType, PathExecutable, Stack, StateWorkingSet},
 };
 use reedline::Suggestion;
 
@@ -34,33 +34,11 @@ impl CommandCompletion {
         working_set: &StateWorkingSet,
         internal_suggs: &HashSet<String>,
     ) -> HashSet<String> {
-        let mut collisions = HashSet::new();
-
-        let paths_val = working_set.permanent_state.get_env_var("path");
-
-        if let Some(paths_val) = paths_val
-            && let Ok(paths) = paths_val.as_list()
-        {
-            for path in paths {
-                let path = path.coerce_str().unwrap_or_default();
-
-                if let Ok(mut contents) = std::fs::read_dir(path.as_ref()) {
-                    while let Some(Ok(item)) = contents.next() {
-                        let Ok(name) = item.file_name().into_string() else {
-                            continue;
-                        };
-
-                        if internal_suggs.contains(&name)
-                            && Self::is_executable_command(item.path())
-                        {
-                            collisions.insert(name);
-                        }
-                    }
-                }
-            }
-        }
-
-        collisions
+        Self::path_executables(working_set)
+            .iter()
+            .filter(|executable| internal_suggs.contains(&executable.name))
+            .map(|executable| executable.name.clone())
+            .collect()
     }
 
     fn external_command_completion(
@@ -71,84 +49,63 @@ impl CommandCompletion {
         mut matcher: NuMatcher<SemanticSuggestion>,
     ) -> Vec<SemanticSuggestion> {
         let mut external_commands = HashSet::new();
-
-        let paths_val = working_set.permanent_state.get_env_var("path");
-
-        if let Some(paths_val) = paths_val
-            && let Ok(paths) = paths_val.as_list()
-        {
-            for path in paths {
-                let path = path.coerce_str().unwrap_or_default();
-
-                if let Ok(mut contents) = std::fs::read_dir(path.as_ref()) {
-                    while let Some(Ok(item)) = contents.next() {
-                        if working_set
-                            .permanent_state
-                            .config
-                            .completions
-                            .external
-                            .max_results
-                            <= external_commands.len() as i64
-                        {
-                            break;
-                        }
-                        let Ok(name) = item.file_name().into_string() else {
-                            continue;
-                        };
-                        // If there's an internal command with the same name, adds ^cmd to the
-                        // matcher so that both the internal and external command are included
-                        let value = if internal_suggs.contains(&name) {
-                            format!("^{name}")
-                        } else {
-                            name.clone()
-                        };
-                        if external_commands.contains(&value) {
-                            continue;
-                        }
-                        // TODO: check name matching before a relative heavy IO involved
-                        // `is_executable` for performance consideration, should avoid
-                        // duplicated `match_aux` call for matched items in the future
-                        if matcher.check_match(&name).is_some()
-                            && Self::is_executable_command(item.path())
-                        {
-                            external_commands.insert(value.clone());
-                            matcher.add(
-                                name,
-                                SemanticSuggestion {
-                                    suggestion: Suggestion {
-                                        value,
-                                        span: sugg_span,
-                                        append_whitespace: true,
-                                        ..Default::default()
-                                    },
-                                    kind: Some(SuggestionKind::Command(
-                                        CommandType::External,
-                                        None,
-                                    )),
-                                },
-                            );
-                        }
-                    }
-                }
+        let max_results = working_set
+            .permanent_state
+            .config
+            .completions
+            .external
+            .max_results;
+
+        for executable in Self::path_executables(working_set).iter() {
+            if max_results <= external_commands.len() as i64 {
+                break;
+            }
+            let name = &executable.name;
+            // If there's an internal command with the same name, adds ^cmd to the
+            // matcher so that both the internal and external command are included
+            let value = if internal_suggs.contains(name) {
+                format!("^{name}")
+            } else {
+                name.clone()
+            };
+            if external_commands.contains(&value) {
+                continue;
+            }
+            if matcher.check_match(name).is_some() {
+                external_commands.insert(value.clone());
+                matcher.add(
+                    name.clone(),
+                    SemanticSuggestion {
+                        suggestion: Suggestion {
+                            value,
+                            span: sugg_span,
+                            append_whitespace: true,
+                            ..Default::default()
+                        },
+                        kind: Some(SuggestionKind::Command(CommandType::External, None)),
+                    },
+                );
             }
         }
 
         matcher.suggestion_results()
     }
 
-    fn is_executable_command(path: impl AsRef<std::path::Path>) -> bool {
-        let path = path.as_ref();
-        if is_executable::is_executable(path) {
-            return true;
-        }
-
-        if cfg!(windows)
-            && let Some(ext) = path.extension()
-        {
-            return ext.eq_ignore_ascii_case("ps1") && path.is_file();
-        }
-
-        false
+    /// The executables in the directories of `$env.PATH`, from the engine's cache if they didn't
+    /// change since the last completion.
+    fn path_executables(working_set: &StateWorkingSet) -> Arc<[PathExecutable]> {
+        let dirs = working_set
+            .permanent_state
+            .get_env_var("path")
+            .and_then(|paths| paths.as_list().ok())
+            .map(|paths| {
+                paths
+                    .iter()
+                    .map(|path| PathBuf::from(path.coerce_str().unwrap_or_default().as_ref()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        nu_engine::executables_in(working_set.permanent_state, dirs)
     }
 }
 
@@ -787,6 +787,20 @@ impl NuCompleter {
                 let suggestions = self.process_completion(&mut completer, ctx);
                 (completer.need_fallback, suggestions)
             }
+            Completion::CommandName(name) => {
+                match ctx.working_set.find_decl(name.as_bytes()) {
+                    // Plugin completers are asked through the plugin's completion call, made by
+                    // the dynamic completion of the command being completed
+                    Some(decl_id) if ctx.working_set.get_decl(decl_id).is_plugin() => {
+                        (true, vec![])
+                    }
+                    Some(decl_id) => {
+                        self.custom_completion_helper(Completion::Command(decl_id), input, ctx, pos)
+                    }
+                    // The completer isn't in scope, e.g. its plugin was removed
+                    None => (true, vec![]),
+                }
+            }
             Completion::List(list) => {
                 let mut completer = StaticCompletion::new(list);
                 (false, self.process_completion(&mut completer, ctx))
@@ -0,0 +1,525 @@
+//! Bracket pairing and indentation while typing multi-line commands, see
+//! `$env.config.edit_assist`.
+//!
+//! Reedline doesn't show the buffer to the edit mode, so [`NuHighlighter`](crate::NuHighlighter)
+//! records the line and cursor each time it is drawn, and [`EditAssistMode`] follows its own
+//! edits on that copy until the next redraw, as several keys can be read before one.
+
+use nu_protocol::EditAssistConfig;
+use reedline::{EditCommand, EditMode, PromptEditMode, ReedlineEvent, ReedlineRawEvent};
+use std::sync::{Arc, Mutex};
+
+/// The command line as it was last drawn, shared between the highlighter and the edit mode.
+#[derive(Clone, Default)]
+pub(crate) struct SharedLine(Arc<Mutex<LineSnapshot>>);
+
+impl SharedLine {
+    pub(crate) fn update(&self, line: &str, cursor: usize) {
+        let mut snapshot = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        snapshot.line.clear();
+        snapshot.line.push_str(line);
+        snapshot.cursor = cursor.min(line.len());
+    }
+
+    /// The line and the cursor in it.
+    pub(crate) fn get(&self) -> (String, usize) {
+        let snapshot = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        (snapshot.line.clone(), snapshot.cursor)
+    }
+}
+
+#[derive(Default)]
+struct LineSnapshot {
+    line: String,
+    cursor: usize,
+}
+
+impl LineSnapshot {
+    fn before(&self) -> &str {
+        self.line.get(..self.cursor).unwrap_or(&self.line)
+    }
+
+    fn after(&self) -> &str {
+        self.line.get(self.cursor..).unwrap_or_default()
+    }
+
+    /// Follow the edits that are simple enough to predict, the others are picked up at the
+    /// next redraw.
+    fn apply(&mut self, commands: &[EditCommand]) {
+        for command in commands {
+            match command {
+                EditCommand::InsertChar(c) => {
+                    self.line.insert(self.cursor, *c);
+                    self.cursor += c.len_utf8();
+                }
+                EditCommand::InsertString(text) => {
+                    self.line.insert_str(self.cursor, text);
+                    self.cursor += text.len();
+                }
+                EditCommand::InsertNewline => {
+                    self.line.insert(self.cursor, '\n');
+                    self.cursor += 1;
+                }
+                EditCommand::MoveLeft { .. } => {
+                    self.cursor -= self.before().chars().next_back().map_or(0, char::len_utf8);
+                }
+                EditCommand::MoveRight { .. } => {
+                    self.cursor += self.after().chars().next().map_or(0, char::len_utf8);
+                }
+                EditCommand::Backspace => {
+                    if let Some(c) = self.before().chars().next_back() {
+                        self.cursor -= c.len_utf8();
+                        self.line.remove(self.cursor);
+                    }
+                }
+                EditCommand::Delete => {
+                    if !self.after().is_empty() {
+                        self.line.remove(self.cursor);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Wraps the configured edit mode to pair brackets and indent lines as they are typed.
+pub(crate) struct EditAssistMode {
+    inner: Box<dyn EditMode>,
+    line: SharedLine,
+    auto_pairs: bool,
+    smart_indent: bool,
+    indent: String,
+    /// Whether a menu may be open, Enter then accepts its selection instead of breaking the line
+    menu_open: bool,
+}
+
+impl EditAssistMode {
+    pub(crate) fn new(
+        inner: Box<dyn EditMode>,
+        line: SharedLine,
+        config: &EditAssistConfig,
+    ) -> Self {
+        Self {
+            inner,
+            line,
+            auto_pairs: config.auto_pairs,
+            smart_indent: config.smart_indent,
+            indent: " ".repeat(usize::try_from(config.indent_width).unwrap_or(4)),
+            menu_open: false,
+        }
+    }
+
+    fn assist(&self, command: &EditCommand, line: &LineSnapshot) -> Option<Vec<EditCommand>> {
+        match command {
+            EditCommand::InsertChar(c) => self.insert_char(*c, line),
+            EditCommand::Backspace if self.auto_pairs => delete_pair(line),
+            EditCommand::InsertNewline if self.smart_indent => {
+                let mut commands = vec![EditCommand::InsertNewline];
+                commands.extend(self.line_break_indent(line));
+                Some(commands)
+            }
+            EditCommand::InsertString(text) if self.smart_indent && text.contains('\n') => {
+                let reindented = self.reindent_paste(text, line);
+                (reindented != *text).then(|| vec![EditCommand::InsertString(reindented)])
+            }
+            _ => None,
+        }
+    }
+
+    fn insert_char(&self, c: char, line: &LineSnapshot) -> Option<Vec<EditCommand>> {
+        let nesting = Nesting::START.scan(line.before());
+        let next = line.after().chars().next();
+
+        if nesting.comment {
+            return None;
+        }
+        if let Some(quote) = nesting.string {
+            // Step over the quote inserted with the opening one
+            let closes = c == quote && !nesting.escaped && next == Some(c);
+            return (self.auto_pairs && c == '"' && closes).then(|| vec![move_right()]);
+        }
+
+        // Only pair in front of nothing, so typing before a word doesn't leave a stray bracket
+        let pairs_here = next.is_none_or(|next| next.is_whitespace() || is_closing(next));
+        match c {
+            '(' | '[' | '{' | '"' if self.auto_pairs && pairs_here => {
+                let quote_after_word = c == '"'
+                    && line
+                        .before()
+                        .chars()
+                        .next_back()
+                        .is_some_and(|prev| prev.is_alphanumeric());
+                (!quote_after_word).then(|| {
+                    vec![
+                        EditCommand::InsertChar(c),
+                        EditCommand::InsertChar(closing(c)),
+                        move_left(),
+                    ]
+                })
+            }
+            ')' | ']' | '}' if self.auto_pairs && next == Some(c) => Some(vec![move_right()]),
+            ')' | ']' | '}' if self.smart_indent => {
+                // Outdent a closing bracket typed at the start of a line
+                let current = line.before().rsplit('\n').next().unwrap_or_default();
+                if !current.chars().all(char::is_whitespace) {
+                    return None;
+                }
+                let target = self.indent.repeat(nesting.depth.saturating_sub(1));
+                (current != target).then(|| {
+                    let mut commands = vec![EditCommand::Backspace; current.chars().count()];
+                    commands.push(EditCommand::InsertString(format!("{target}{c}")));
+                    commands
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// What to insert after a new line at the cursor to indent it.
+    fn line_break_indent(&self, line: &LineSnapshot) -> Vec<EditCommand> {
+        let nesting = Nesting::START.scan(line.before());
+        // New lines are part of the text of multi-line strings
+        if nesting.string.is_some() || nesting.depth == 0 {
+            return vec![];
+        }
+
+        let mut commands = vec![EditCommand::InsertString(self.indent.repeat(nesting.depth))];
+        // Breaking the line between a pair of brackets puts the closing one on its own line
+        if line.after().starts_with(is_closing) {
+            let outer = self.indent.repeat(nesting.depth - 1);
+            commands.push(EditCommand::InsertNewline);
+            if !outer.is_empty() {
+                commands.push(EditCommand::InsertString(outer.clone()));
+            }
+            commands.extend(std::iter::repeat_n(move_left(), outer.len() + 1));
+        }
+        commands
+    }
+
+    fn reindent_paste(&self, text: &str, line: &LineSnapshot) -> String {
+        let before = line.before();
+        let at_line_start = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .all(char::is_whitespace);
+        reindent(
+            text,
+            Nesting::START.scan(before),
+            &self.indent,
+            at_line_start,
+        )
+    }
+}
+
+impl EditMode for EditAssistMode {
+    fn parse_event(&mut self, event: ReedlineRawEvent) -> ReedlineEvent {
+        let event = self.inner.parse_event(event);
+        let event = apply_mode_changes(self.inner.as_mut(), event);
+
+        let shared = self.line.clone();
+        let mut line = shared.0.lock().unwrap_or_else(|e| e.into_inner());
+        let event = match event {
+            ReedlineEvent::Edit(commands) => {
+                let assisted = match commands.as_slice() {
+                    [command] => self.assist(command, &line),
+                    _ => None,
+                };
+                let commands = assisted.unwrap_or(commands);
+                line.apply(&commands);
+                ReedlineEvent::Edit(commands)
+            }
+            // Reedline breaks the line on Enter when the command is incomplete, and submits it
+            // otherwise, in which case the rest is dropped
+            ReedlineEvent::Enter
+                if self.smart_indent
+                    && !self.menu_open
+                    && !Nesting::START.scan(&line.line).is_complete() =>
+            {
+                let indent = self.line_break_indent(&line);
+                line.apply(&[EditCommand::InsertNewline]);
+                line.apply(&indent);
+                ReedlineEvent::Multiple(vec![ReedlineEvent::Enter, ReedlineEvent::Edit(indent)])
+            }
+            event => event,
+        };
+
+        if opens_menu(&event) {
+            self.menu_open = true;
+        } else if matches!(
+            event,
+            ReedlineEvent::Enter
+                | ReedlineEvent::Submit
+                | ReedlineEvent::SubmitOrNewline
+                | ReedlineEvent::Esc
+                | ReedlineEvent::CtrlC
+        ) {
+            self.menu_open = false;
+        }
+        event
+    }
+
+    fn edit_mode(&self) -> PromptEditMode {
+        self.inner.edit_mode()
+    }
+}
+
+/// Run the mode changes of vi keybindings right away, as reedline hands them back to the
+/// outermost edit mode, which can't pass them on to the one it wraps.
+pub(crate) fn apply_mode_changes(inner: &mut dyn EditMode, event: ReedlineEvent) -> ReedlineEvent {
+    match event {
+        ReedlineEvent::ViChangeMode(_) => {
+            let _ = inner.handle_mode_specific_event(event);
+            ReedlineEvent::Repaint
+        }
+        ReedlineEvent::Multiple(events) => ReedlineEvent::Multiple(
+            events
+                .into_iter()
+                .map(|event| apply_mode_changes(inner, event))
+                .collect(),
+        ),
+        ReedlineEvent::UntilFound(events) => ReedlineEvent::UntilFound(
+            events
+                .into_iter()
+                .map(|event| apply_mode_changes(inner, event))
+                .collect(),
+        ),
+        event => event,
+    }
+}
+
+fn opens_menu(event: &ReedlineEvent) -> bool {
+    match event {
+        ReedlineEvent::Menu(_) => true,
+        ReedlineEvent::Multiple(events) | ReedlineEvent::UntilFound(events) => {
+            events.iter().any(opens_menu)
+        }
+        _ => false,
+    }
+}
+
+/// Delete both characters of an empty pair when deleting the opening one.
+fn delete_pair(line: &LineSnapshot) -> Option<Vec<EditCommand>> {
+    let before = line.before();
+    let open = before.chars().next_back()?;
+    let nesting = Nesting::START.scan(&before[..before.len() - open.len_utf8()]);
+    let pairs = matches!(open, '(' | '[' | '{' | '"')
+        && line.after().starts_with(closing(open))
+        && nesting.string.is_none()
+        && !nesting.comment;
+    pairs.then(|| vec![EditCommand::Backspace, EditCommand::Delete])
+}
+
+/// Indent each line of `text` by the depth of the brackets around it, starting from `nesting`.
+///
+/// Lines starting inside a string are kept as they are, and so is the first line unless it is
+/// inserted at the start of a line.
+fn reindent(text: &str, mut nesting: Nesting, indent: &str, at_line_start: bool) -> String {
+    let mut reindented = String::with_capacity(text.len());
+    for (index, text_line) in text.split('\n').enumerate() {
+        if index > 0 {
+            reindented.push('\n');
+            nesting = nesting.scan("\n");
+        }
+
+        let content = text_line.trim_start();
+        if nesting.string.is_some() || (index == 0 && !at_line_start) {
+            reindented.push_str(text_line);
+        } else if index == 0 {
+            // The line already has the indentation in front of the cursor
+            reindented.push_str(content);
+        } else if !content.is_empty() {
+            let depth = if content.starts_with(is_closing) {
+                nesting.depth.saturating_sub(1)
+            } else {
+                nesting.depth
+            };
+            reindented.push_str(&indent.repeat(depth));
+            reindented.push_str(content);
+        }
+        nesting = nesting.scan(text_line);
+    }
+    reindented
+}
+
+/// How deep some text is nested in brackets, strings and comments, following the rules of the
+/// lexer closely enough to indent it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Nesting {
+    depth: usize,
+    /// The quote of the string the text ends in
+    string: Option<char>,
+    /// Whether the next character of a double quoted string is escaped
+    escaped: bool,
+    comment: bool,
+    /// Whether a token can start at the next character, so that `#` starts a comment
+    token_start: bool,
+}
+
+impl Nesting {
+    const START: Self = Self {
+        depth: 0,
+        string: None,
+        escaped: false,
+        comment: false,
+        token_start: true,
+    };
+
+    fn scan(mut self, text: &str) -> Self {
+        for c in text.chars() {
+            if self.comment {
+                if c == '\n' {
+                    self.comment = false;
+                    self.token_start = true;
+                }
+                continue;
+            }
+            if let Some(quote) = self.string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if quote == '"' && c == '\\' {
+                    self.escaped = true;
+                } else if c == quote {
+                    self.string = None;
+                }
+                continue;
+            }
+            match c {
+                '"' | '\'' | '`' => self.string = Some(c),
+                '(' | '[' | '{' => self.depth += 1,
+                ')' | ']' | '}' => self.depth = self.depth.saturating_sub(1),
+                '#' if self.token_start => {
+                    self.comment = true;
+                    continue;
+                }
+                _ => {}
+            }
+            self.token_start = c.is_whitespace() || matches!(c, '(' | '[' | '{' | ';' | '|');
+        }
+        self
+    }
+
+    fn is_complete(&self) -> bool {
+        self.depth == 0 && self.string.is_none()
+    }
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(c, ')' | ']' | '}')
+}
+
+fn closing(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        other => other,
+    }
+}
+
+fn move_left() -> EditCommand {
+    EditCommand::MoveLeft { select: false }
+}
+
+fn move_right() -> EditCommand {
+    EditCommand::MoveRight { select: false }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reedline::Emacs;
+    use rstest::rstest;
+
+    fn assist_mode() -> EditAssistMode {
+        EditAssistMode::new(
+            Box::new(Emacs::default()),
+            SharedLine::default(),
+            &EditAssistConfig {
+                auto_pairs: true,
+                smart_indent: true,
+                indent_width: 4,
+            },
+        )
+    }
+
+    /// Run a single edit on a line where `|` marks the cursor, and show where it leaves it.
+    fn edit(line: &str, command: EditCommand) -> String {
+        let cursor = line.find('|').expect("cursor marker");
+        let mut snapshot = LineSnapshot {
+            line: line.replacen('|', "", 1),
+            cursor,
+        };
+        let commands = assist_mode()
+            .assist(&command, &snapshot)
+            .unwrap_or_else(|| vec![command]);
+        snapshot.apply(&commands);
+        snapshot.line.insert(snapshot.cursor, '|');
+        snapshot.line
+    }
+
+    #[rstest]
+    #[case("ls | each |", '{', "ls | each {|}")]
+    #[case("echo |", '"', "echo \"|\"")]
+    #[case("echo \"abc|\"", '"', "echo \"abc\"|")]
+    #[case("(1 + 2|)", ')', "(1 + 2)|")]
+    #[case("echo |abc", '(', "echo (|abc")]
+    #[case("echo abc|", '"', "echo abc\"|")]
+    #[case("echo 'it|'", '(', "echo 'it(|'")]
+    #[case("ls # comment |", '[', "ls # comment [|")]
+    fn pairs_brackets(#[case] line: &str, #[case] c: char, #[case] expected: &str) {
+        assert_eq!(edit(line, EditCommand::InsertChar(c)), expected);
+    }
+
+    #[rstest]
+    #[case("echo (|)", "echo |")]
+    #[case("echo \"|\"", "echo |")]
+    #[case("echo (|a)", "echo |a)")]
+    fn deletes_empty_pairs(#[case] line: &str, #[case] expected: &str) {
+        assert_eq!(edit(line, EditCommand::Backspace), expected);
+    }
+
+    #[rstest]
+    #[case("if true {|}", "if true {\n    |\n}")]
+    #[case(
+        "def f [] {\n    [1, 2] | each {|}\n}",
+        "def f [] {\n    [1, 2] | each {\n        |\n    }\n}"
+    )]
+    #[case("ls | where {|", "ls | where {\n    |")]
+    #[case("echo \"abc|", "echo \"abc\n|")]
+    #[case("ls|", "ls\n|")]
+    fn indents_new_lines(#[case] line: &str, #[case] expected: &str) {
+        assert_eq!(edit(line, EditCommand::InsertNewline), expected);
+    }
+
+    #[test]
+    fn outdents_closing_brackets() {
+        assert_eq!(
+            edit("if true {\n    ls\n    |", EditCommand::InsertChar('}')),
+            "if true {\n    ls\n}|"
+        );
+    }
+
+    #[test]
+    fn reindents_pasted_blocks() {
+        let pasted = "{\n  ls\n  | where size > 1kb\n        }";
+        assert_eq!(
+            edit(
+                "def f [] {\n    |",
+                EditCommand::InsertString(pasted.into())
+            ),
+            "def f [] {\n    {\n        ls\n        | where size > 1kb\n    }|"
+        );
+    }
+
+    #[test]
+    fn keeps_multi_line_strings() {
+        let pasted = "echo \"a\n  b\"\n  ls";
+        assert_eq!(
+            edit("|", EditCommand::InsertString(pasted.into())),
+            "echo \"a\n  b\"\nls|"
+        );
+    }
+}
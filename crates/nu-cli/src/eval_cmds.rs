@@ -1,24 +1,128 @@
 use log::info;
+use miette::Diagnostic;
 use nu_engine::eval_block;
 use nu_parser::parse;
 use nu_protocol::{
-    PipelineData, ShellError, Spanned, Value,
+    PipelineData, ShellError, Span, Spanned, Value, ast,
     debugger::WithoutDebug,
-    engine::{EngineState, Stack, StateWorkingSet},
+    engine::{Command, EngineState, Stack, StateWorkingSet},
     process::check_exit_status_future,
     report_error::report_compile_error,
     report_parse_error, report_parse_warning,
-    shell_error::generic::GenericError,
+    shell_error::{generic::GenericError, io::IoError},
 };
-use std::sync::Arc;
+use serde_json::json;
+use std::{io::Write, str::FromStr, sync::Arc};
 
 use crate::util::print_pipeline;
 
+/// A machine-readable format for the result of `nu -c`, set with `--output-format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Nuon,
+    Msgpack,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "nuon" => Ok(Self::Nuon),
+            "msgpack" => Ok(Self::Msgpack),
+            _ => Err(format!(
+                "'{s}' is not a valid output format, expected json, nuon or msgpack"
+            )),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct EvaluateCommandsOpts {
     pub table_mode: Option<Value>,
     pub error_style: Option<Value>,
     pub no_newline: bool,
+    /// Serialize the result in this format instead of printing it as a table, and report errors
+    /// as JSON
+    pub output_format: Option<OutputFormat>,
+}
+
+/// Prints an error to stderr as a single line of JSON, for programs running nushell with
+/// `--output-format`.
+pub fn report_error_as_json(working_set: &StateWorkingSet, error: &dyn Diagnostic) {
+    let labels = error
+        .labels()
+        .into_iter()
+        .flatten()
+        .map(|label| {
+            let span = Span::new(label.offset(), label.offset() + label.len());
+            json!({
+                "text": label.label(),
+                "span": { "start": span.start, "end": span.end },
+                "source": String::from_utf8_lossy(working_set.get_span_contents(span)),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    eprintln!(
+        "{}",
+        json!({
+            "error": {
+                "msg": error.to_string(),
+                "code": error.code().map(|code| code.to_string()),
+                "help": error.help().map(|help| help.to_string()),
+                "labels": labels,
+            }
+        })
+    );
+}
+
+/// Serializes the result of the commands with the `to` command of `format`, and writes it to stdout.
+fn print_formatted(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    pipeline_data: PipelineData,
+    format: OutputFormat,
+    no_newline: bool,
+) -> Result<(), ShellError> {
+    // No source span — the output format comes from a CLI flag, not from the commands
+    let span = Span::unknown();
+    let mut call = ast::Call::new(span);
+    let output = match format {
+        OutputFormat::Json => {
+            call.add_named((
+                Spanned {
+                    item: "raw".to_string(),
+                    span,
+                },
+                None,
+                None,
+            ));
+            nu_command::ToJson.run(engine_state, stack, &(&call).into(), pipeline_data)
+        }
+        OutputFormat::Nuon => {
+            nu_command::ToNuon.run(engine_state, stack, &(&call).into(), pipeline_data)
+        }
+        OutputFormat::Msgpack => {
+            nu_command::ToMsgpack.run(engine_state, stack, &(&call).into(), pipeline_data)
+        }
+    }?;
+
+    let mut bytes = match output.into_value(span)? {
+        Value::Binary { val, .. } => val,
+        value => value.coerce_into_string()?.into_bytes(),
+    };
+    if format != OutputFormat::Msgpack && !no_newline {
+        bytes.push(b'\n');
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    stdout
+        .write_all(&bytes)
+        .and_then(|_| stdout.flush())
+        .map_err(|err| ShellError::Io(IoError::new_internal(err, "Could not write to stdout")))
 }
 
 /// Run a command (or commands) given to us by the user
@@ -33,6 +137,7 @@ pub fn evaluate_commands(
         table_mode,
         error_style,
         no_newline,
+        output_format,
     } = opts;
 
     // Handle the configured error style early
@@ -66,12 +171,20 @@ pub fn evaluate_commands(
         }
 
         if let Some(err) = working_set.parse_errors.first() {
-            report_parse_error(Some(stack), &working_set, err);
+            if output_format.is_some() {
+                report_error_as_json(&working_set, err);
+            } else {
+                report_parse_error(Some(stack), &working_set, err);
+            }
             std::process::exit(1);
         }
 
         if let Some(err) = working_set.compile_errors.first() {
-            report_compile_error(Some(stack), &working_set, err);
+            if output_format.is_some() {
+                report_error_as_json(&working_set, err);
+            } else {
+                report_compile_error(Some(stack), &working_set, err);
+            }
             std::process::exit(1);
         }
 
@@ -94,7 +207,10 @@ pub fn evaluate_commands(
             t_mode.coerce_str()?.parse().unwrap_or_default();
     }
 
-    print_pipeline(engine_state, stack, pipeline_data, no_newline)?;
+    match output_format {
+        Some(format) => print_formatted(engine_state, stack, pipeline_data, format, no_newline)?,
+        None => print_pipeline(engine_state, stack, pipeline_data, no_newline)?,
+    }
     info!("evaluate {}:{}:{}", file!(), line!(), column!());
     let pipefail = nu_experimental::PIPE_FAIL.get();
     if !pipefail {
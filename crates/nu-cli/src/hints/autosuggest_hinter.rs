@@ -0,0 +1,162 @@
+use super::external_hinter::first_hint_token;
+use crate::NuCompleter;
+use nu_ansi_term::Style;
+use nu_protocol::{
+    AutosuggestConfig,
+    engine::{EngineState, Stack},
+};
+use nu_utils::time::Instant;
+use reedline::{
+    Completer, CompletionResult, CompletionStatus, CwdAwareHinter, Hinter, History, Suggestion,
+};
+use std::{sync::Arc, thread, time::Duration};
+
+/// How often to check on the completion worker while waiting for a suggestion.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Fish-style suggestions from the history, then optionally from the completions.
+///
+/// Completions are computed by the background worker of [`NuCompleter`]. When they take longer
+/// than `$env.config.autosuggest.timeout`, no suggestion is shown for that keystroke, and the
+/// result is picked up from the completer cache if the line is still the same at the next one.
+pub(crate) struct AutosuggestHinter {
+    history_hinter: Option<CwdAwareHinter>,
+    completer: Option<NuCompleter>,
+    min_chars: usize,
+    timeout: Duration,
+    style: Style,
+    current_hint: String,
+}
+
+impl AutosuggestHinter {
+    pub(crate) fn new(
+        engine_state: Arc<EngineState>,
+        stack: Arc<Stack>,
+        config: &AutosuggestConfig,
+        style: Style,
+    ) -> Self {
+        let min_chars = usize::try_from(config.min_chars).unwrap_or_default();
+        Self {
+            history_hinter: config.history.then(|| {
+                CwdAwareHinter::default()
+                    .with_style(style)
+                    .with_min_chars(min_chars)
+            }),
+            completer: config
+                .completions
+                .then(|| NuCompleter::new(engine_state, stack)),
+            min_chars,
+            timeout: Duration::from_nanos(u64::try_from(config.timeout).unwrap_or_default()),
+            style,
+            current_hint: String::new(),
+        }
+    }
+
+    fn suggest(&mut self, line: &str, pos: usize, history: &dyn History, cwd: &str) -> String {
+        if line.chars().count() < self.min_chars {
+            return String::new();
+        }
+
+        if let Some(history_hinter) = &mut self.history_hinter {
+            history_hinter.handle(line, pos, history, false, cwd);
+            let hint = history_hinter.complete_hint();
+            if !hint.is_empty() {
+                return hint;
+            }
+        }
+
+        // Suggestions only make sense as a continuation of the whole line
+        if pos != line.len() {
+            return String::new();
+        }
+        let Some(completer) = &mut self.completer else {
+            return String::new();
+        };
+
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            // Asking again for a pending query doesn't start a new computation
+            if let CompletionResult::Fresh(suggestions) = completer.complete(line, pos) {
+                return suggestions
+                    .first()
+                    .and_then(|suggestion| completion_remainder(line, suggestion))
+                    .unwrap_or_default();
+            }
+            if Instant::now() >= deadline {
+                return String::new();
+            }
+            if completer.poll_completion() != CompletionStatus::Ready {
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+impl Hinter for AutosuggestHinter {
+    fn handle(
+        &mut self,
+        line: &str,
+        pos: usize,
+        history: &dyn History,
+        use_ansi_coloring: bool,
+        cwd: &str,
+    ) -> String {
+        self.current_hint = self.suggest(line, pos, history, cwd);
+
+        if use_ansi_coloring && !self.current_hint.is_empty() {
+            self.style.paint(&self.current_hint).to_string()
+        } else {
+            self.current_hint.clone()
+        }
+    }
+
+    fn complete_hint(&self) -> String {
+        self.current_hint.clone()
+    }
+
+    fn next_hint_token(&self) -> String {
+        first_hint_token(&self.current_hint)
+    }
+}
+
+/// The part of a completion that comes after what is already typed.
+fn completion_remainder(line: &str, suggestion: &Suggestion) -> Option<String> {
+    let typed = line.get(suggestion.span.start..suggestion.span.end)?;
+    suggestion
+        .value
+        .strip_prefix(typed)
+        .filter(|remainder| !remainder.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestion(value: &str, start: usize, end: usize) -> Suggestion {
+        Suggestion {
+            value: value.to_string(),
+            span: reedline::Span::new(start, end),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn completion_remainder_continues_typed_text() {
+        let line = "open Car";
+        assert_eq!(
+            completion_remainder(line, &suggestion("Cargo.toml", 5, 8)),
+            Some("go.toml".to_string())
+        );
+    }
+
+    #[test]
+    fn completion_remainder_ignores_other_completions() {
+        let line = "open Car";
+        assert_eq!(
+            completion_remainder(line, &suggestion("crates", 5, 8)),
+            None
+        );
+        assert_eq!(completion_remainder(line, &suggestion("Car", 5, 8)), None);
+    }
+}
@@ -1,3 +1,5 @@
+mod autosuggest_hinter;
 mod external_hinter;
 
+pub(crate) use autosuggest_hinter::AutosuggestHinter;
 pub(crate) use external_hinter::ExternalHinter;
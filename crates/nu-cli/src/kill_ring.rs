@@ -0,0 +1,337 @@
+//! The kill ring: registers holding the last pieces of text cut from the command line, which
+//! keybindings can paste back:
+//!
+//! ```nushell
+//! { name: yank, modifier: control, keycode: char_y, mode: emacs, event: {register: paste} }
+//! { name: yank_pop, modifier: alt, keycode: char_y, mode: emacs, event: {register: cycle} }
+//! ```
+//!
+//! `{register: paste, index: 1}` pastes the second most recent cut, and `{register: cycle}` right
+//! after a paste replaces the pasted text with the next older cut, like yank-pop in Emacs.
+//!
+//! Reedline only keeps the last cut and doesn't tell what it was, so [`KillRingMode`] remembers
+//! the line before a cut and compares it with the line [`SharedLine`] has at the next key.
+
+use crate::{
+    edit_assist::{SharedLine, apply_mode_changes},
+    reedline_config::KeyTrigger,
+};
+use crossterm::event::Event;
+use nu_protocol::{Config, ParsedKeybinding, ShellError, Value};
+use reedline::{EditCommand, EditMode, PromptEditMode, ReedlineEvent, ReedlineRawEvent};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub(crate) fn is_register_keybinding(keybinding: &ParsedKeybinding) -> bool {
+    matches!(&keybinding.event, Value::Record { val, .. } if val.contains("register"))
+}
+
+/// The registers of the kill ring, most recent first. They're kept across prompts, so the REPL
+/// creates them once and hands them to the edit mode of each prompt.
+#[derive(Clone, Default)]
+pub(crate) struct KillRing(Arc<Mutex<VecDeque<String>>>);
+
+impl KillRing {
+    fn push(&self, text: String, size: usize) {
+        let mut ring = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        ring.push_front(text);
+        ring.truncate(size);
+    }
+
+    fn get(&self, index: usize) -> Option<String> {
+        let ring = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        ring.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RegisterAction {
+    /// Paste the register with this index, 0 being the most recent cut.
+    Paste(usize),
+    /// Replace the text that was just pasted with the next register.
+    Cycle,
+}
+
+/// A keybinding that uses the kill ring.
+pub(crate) struct RegisterKeybinding {
+    trigger: KeyTrigger,
+    action: RegisterAction,
+}
+
+/// The keybindings in the config that use the kill ring.
+pub(crate) fn create_register_keybindings(
+    config: &Config,
+) -> Result<Vec<RegisterKeybinding>, ShellError> {
+    config
+        .keybindings
+        .iter()
+        .filter(|keybinding| is_register_keybinding(keybinding))
+        .map(|keybinding| {
+            Ok(RegisterKeybinding {
+                trigger: KeyTrigger::from_keybinding(keybinding)?,
+                action: parse_register_action(&keybinding.event)?,
+            })
+        })
+        .collect()
+}
+
+fn parse_register_action(event: &Value) -> Result<RegisterAction, ShellError> {
+    let record = event.as_record()?;
+    let Some(register) = record.get("register") else {
+        return Err(ShellError::CantFindColumn {
+            col_name: "register".into(),
+            span: None,
+            src_span: event.span(),
+        });
+    };
+    match register.as_str()? {
+        "paste" => {
+            let index = match record.get("index") {
+                Some(index) => {
+                    let span = index.span();
+                    usize::try_from(index.as_int()?).map_err(|_| ShellError::IncorrectValue {
+                        msg: "the register index can't be negative".into(),
+                        val_span: span,
+                        call_span: event.span(),
+                    })?
+                }
+                None => 0,
+            };
+            Ok(RegisterAction::Paste(index))
+        }
+        "cycle" => Ok(RegisterAction::Cycle),
+        other => Err(ShellError::InvalidValue {
+            valid: "'paste' or 'cycle'".into(),
+            actual: format!("'{other}'"),
+            span: register.span(),
+        }),
+    }
+}
+
+/// Wraps the configured edit mode to fill the kill ring with what is cut from the line, and to
+/// run the keybindings that paste from it.
+pub(crate) struct KillRingMode {
+    inner: Box<dyn EditMode>,
+    keybindings: Vec<RegisterKeybinding>,
+    ring: KillRing,
+    size: usize,
+    line: SharedLine,
+    /// The line before the last event that cut something from it
+    line_before_cut: Option<String>,
+    /// The register that was pasted by the last event, and how many graphemes it had
+    last_paste: Option<(usize, usize)>,
+}
+
+impl KillRingMode {
+    pub(crate) fn new(
+        inner: Box<dyn EditMode>,
+        keybindings: Vec<RegisterKeybinding>,
+        ring: KillRing,
+        config: &Config,
+        line: SharedLine,
+    ) -> Self {
+        Self {
+            inner,
+            keybindings,
+            ring,
+            size: usize::try_from(config.kill_ring.size).unwrap_or(1),
+            line,
+            line_before_cut: None,
+            last_paste: None,
+        }
+    }
+
+    /// Put what the last cut removed into the ring, now that the line shows it.
+    fn record_cut(&mut self) {
+        if let Some(before) = self.line_before_cut.take()
+            && let Some(cut) = removed_text(&before, &self.line.get().0)
+        {
+            self.ring.push(cut.to_owned(), self.size);
+        }
+    }
+
+    fn run(&mut self, action: RegisterAction) -> ReedlineEvent {
+        let (index, mut edits) = match (action, self.last_paste) {
+            (RegisterAction::Paste(index), _) => (index, vec![]),
+            (RegisterAction::Cycle, Some((index, pasted))) if self.ring.len() > 0 => (
+                (index + 1) % self.ring.len(),
+                vec![EditCommand::Backspace; pasted],
+            ),
+            (RegisterAction::Cycle, _) => return ReedlineEvent::None,
+        };
+        let Some(text) = self.ring.get(index) else {
+            self.last_paste = None;
+            return ReedlineEvent::None;
+        };
+        self.last_paste = Some((index, text.graphemes(true).count()));
+        edits.push(EditCommand::InsertString(text));
+        ReedlineEvent::Edit(edits)
+    }
+}
+
+impl EditMode for KillRingMode {
+    fn parse_event(&mut self, event: ReedlineRawEvent) -> ReedlineEvent {
+        self.record_cut();
+
+        let event = Event::from(event);
+        let mode = self.inner.edit_mode();
+        if let Event::Key(key) = &event
+            && let Some(action) = self
+                .keybindings
+                .iter()
+                .find(|keybinding| keybinding.trigger.matches(key, &mode))
+                .map(|keybinding| keybinding.action)
+        {
+            return self.run(action);
+        }
+        self.last_paste = None;
+
+        // Only key releases are rejected, and those never made it here
+        let Ok(event) = ReedlineRawEvent::try_from(event) else {
+            return ReedlineEvent::None;
+        };
+        let event = self.inner.parse_event(event);
+        let event = apply_mode_changes(self.inner.as_mut(), event);
+        if cuts(&event) {
+            self.line_before_cut = Some(self.line.get().0);
+        }
+        event
+    }
+
+    fn edit_mode(&self) -> PromptEditMode {
+        self.inner.edit_mode()
+    }
+}
+
+fn cuts(event: &ReedlineEvent) -> bool {
+    match event {
+        ReedlineEvent::Edit(commands) => commands.iter().any(is_cut),
+        ReedlineEvent::Multiple(events) | ReedlineEvent::UntilFound(events) => {
+            events.iter().any(cuts)
+        }
+        _ => false,
+    }
+}
+
+/// Whether the command removes text from the line and keeps it to be pasted.
+fn is_cut(command: &EditCommand) -> bool {
+    match command {
+        EditCommand::CutChar
+        | EditCommand::CutCurrentLine
+        | EditCommand::CutFromStart
+        | EditCommand::CutFromLineStart
+        | EditCommand::CutToEnd
+        | EditCommand::CutToLineEnd
+        | EditCommand::KillLine
+        | EditCommand::CutWordLeft
+        | EditCommand::CutBigWordLeft
+        | EditCommand::CutWordRight
+        | EditCommand::CutBigWordRight
+        | EditCommand::CutWordRightToNext
+        | EditCommand::CutBigWordRightToNext
+        | EditCommand::CutRightUntil(_)
+        | EditCommand::CutRightBefore(_)
+        | EditCommand::CutLeftUntil(_)
+        | EditCommand::CutLeftBefore(_)
+        | EditCommand::CutSelection
+        | EditCommand::CutInsidePair { .. }
+        | EditCommand::CutAroundPair { .. }
+        | EditCommand::CutTextObject { .. } => true,
+        #[cfg(feature = "system-clipboard")]
+        EditCommand::CutSelectionSystem => true,
+        _ => false,
+    }
+}
+
+/// The text removed from `before` to get `after`, if that's all that happened to it.
+fn removed_text<'a>(before: &'a str, after: &str) -> Option<&'a str> {
+    if after.len() >= before.len() {
+        return None;
+    }
+    let prefix = before
+        .char_indices()
+        .zip(after.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(after.len(), |((index, _), _)| index);
+    let end = prefix + before.len() - after.len();
+    if before.get(end..)? == after.get(prefix..)? {
+        before.get(prefix..end)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::record;
+
+    #[test]
+    fn removed_text_of_a_cut() {
+        assert_eq!(removed_text("ls -la foo", "ls foo"), Some("-la "));
+        assert_eq!(removed_text("ls -la", "ls "), Some("-la"));
+        assert_eq!(removed_text("äöü", "ü"), Some("äö"));
+        assert_eq!(removed_text("ls", "ls"), None);
+        assert_eq!(removed_text("ls -la", "cd"), None);
+    }
+
+    #[test]
+    fn cuts_in_events() {
+        let edit = |command| ReedlineEvent::Edit(vec![command]);
+        assert!(cuts(&edit(EditCommand::CutWordLeft)));
+        assert!(cuts(&edit(EditCommand::KillLine)));
+        assert!(cuts(&ReedlineEvent::Multiple(vec![
+            ReedlineEvent::Esc,
+            edit(EditCommand::CutRightUntil('x')),
+        ])));
+        assert!(!cuts(&edit(EditCommand::CopyWordLeft)));
+        assert!(!cuts(&edit(EditCommand::Backspace)));
+    }
+
+    #[test]
+    fn ring_keeps_most_recent_cuts() {
+        let ring = KillRing::default();
+        for cut in ["a", "b", "c"] {
+            ring.push(cut.into(), 2);
+        }
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.get(0).as_deref(), Some("c"));
+        assert_eq!(ring.get(1).as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn register_actions() {
+        let action = |record| parse_register_action(&Value::test_record(record));
+        assert_eq!(
+            action(record! {"register" => Value::test_string("paste")}).unwrap(),
+            RegisterAction::Paste(0)
+        );
+        assert_eq!(
+            action(record! {
+                "register" => Value::test_string("paste"),
+                "index" => Value::test_int(2),
+            })
+            .unwrap(),
+            RegisterAction::Paste(2)
+        );
+        assert_eq!(
+            action(record! {"register" => Value::test_string("cycle")}).unwrap(),
+            RegisterAction::Cycle
+        );
+        assert!(
+            action(record! {
+                "register" => Value::test_string("paste"),
+                "index" => Value::test_int(-1),
+            })
+            .is_err()
+        );
+        assert!(action(record! {"register" => Value::test_string("x")}).is_err());
+    }
+}
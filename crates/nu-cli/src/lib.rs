@@ -1,25 +1,33 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(test, allow(clippy::unwrap_used))]
 
+mod abbreviations;
+mod closure_keybindings;
 mod commands;
 mod completions;
 mod config_files;
+mod edit_assist;
 mod eval_cmds;
 mod eval_file;
 mod hints;
+mod kill_ring;
+mod local_config;
 mod menus;
+mod paste_hook;
 mod prompt;
 mod prompt_update;
+mod recording;
 mod reedline_config;
 mod repl;
 mod syntax_highlight;
+mod undo_depth;
 mod util;
 mod validation;
 
 pub use commands::add_cli_context;
 pub use completions::{FileCompletion, NuCompleter, SemanticSuggestion, SuggestionKind};
 pub use config_files::eval_config_contents;
-pub use eval_cmds::{EvaluateCommandsOpts, evaluate_commands};
+pub use eval_cmds::{EvaluateCommandsOpts, OutputFormat, evaluate_commands, report_error_as_json};
 pub use eval_file::evaluate_file;
 pub use menus::NuHelpCompleter;
 pub use prompt::NushellPrompt;
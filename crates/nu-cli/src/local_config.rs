@@ -0,0 +1,87 @@
+use crate::util::eval_source;
+use nu_command::{
+    LOCAL_CONFIG_FILE, TrustStore, find_local_config, loaded_local_config, local_config_source,
+    unload_local_config_source,
+};
+use nu_protocol::{
+    PipelineData, Span,
+    engine::{EngineState, Stack},
+    report_shell_error,
+};
+use nu_utils::stderr_write_all_and_flush;
+use std::path::PathBuf;
+
+/// Load or unload the settings of the `.nu.toml` closest to the current directory.
+///
+/// `last_seen` remembers the file found on the previous call, so the notice about an untrusted
+/// file is only shown once when entering its directory.
+pub(crate) fn update_local_config(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    last_seen: &mut Option<PathBuf>,
+) {
+    let Ok(cwd) = engine_state.cwd(Some(stack)) else {
+        return;
+    };
+    let found = find_local_config(cwd.as_std_path());
+    let loaded = loaded_local_config(engine_state, stack);
+    let entered = found != *last_seen;
+    last_seen.clone_from(&found);
+
+    if found == loaded {
+        return;
+    }
+
+    if loaded.is_some() {
+        eval_source(
+            engine_state,
+            stack,
+            unload_local_config_source().as_bytes(),
+            "unload local config",
+            PipelineData::empty(),
+            false,
+        );
+    }
+
+    let Some(file) = found else {
+        return;
+    };
+    let Ok(contents) = std::fs::read(&file) else {
+        return;
+    };
+
+    let trusted = match TrustStore::load(engine_state, Span::unknown()) {
+        Ok(store) => store.is_trusted(&file, &contents),
+        Err(err) => {
+            report_shell_error(Some(stack), engine_state, &err);
+            false
+        }
+    };
+    if !trusted {
+        if entered {
+            let _ = stderr_write_all_and_flush(format!(
+                "{LOCAL_CONFIG_FILE} found in '{}' is not trusted, run `config trust` to load it\n",
+                file.parent().unwrap_or(&file).display()
+            ));
+        }
+        return;
+    }
+
+    match local_config_source(engine_state, &file, &contents, Span::unknown()) {
+        Ok(source) => {
+            eval_source(
+                engine_state,
+                stack,
+                source.as_bytes(),
+                &file.to_string_lossy(),
+                PipelineData::empty(),
+                false,
+            );
+        }
+        Err(err) => {
+            if entered {
+                report_shell_error(Some(stack), engine_state, &err);
+            }
+        }
+    }
+}
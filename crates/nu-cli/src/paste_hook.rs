@@ -0,0 +1,163 @@
+//! The `paste` hook, run on text pasted in the line editor before it is inserted.
+
+use crate::edit_assist::apply_mode_changes;
+use crossterm::event::Event;
+use nu_cmd_base::hook::eval_hook;
+use nu_protocol::{
+    IntoPipelineData, Span, Value,
+    engine::{EngineState, Stack},
+    report_shell_error,
+};
+use reedline::{EditMode, PromptEditMode, ReedlineEvent, ReedlineRawEvent};
+use std::sync::{Arc, Weak};
+
+/// What the `paste` hook runs with.
+pub(crate) struct PasteHook {
+    pub hook: Value,
+    pub engine_state: Arc<EngineState>,
+    /// Weak, so the REPL can take its stack back without cloning it once the line is read
+    /// (see STACK-REFERENCE)
+    pub stack: Weak<Stack>,
+}
+
+impl PasteHook {
+    /// The text to insert instead of `text`.
+    ///
+    /// The hooks of a list run in turn, each one getting the text returned by the one before.
+    fn transform(&self, text: String) -> String {
+        let Some(parent) = self.stack.upgrade() else {
+            return text;
+        };
+        let mut engine_state = (*self.engine_state).clone();
+        let mut stack = Stack::with_parent(parent);
+
+        match &self.hook {
+            Value::List { vals, .. } => vals.iter().fold(text, |text, hook| {
+                run_hook(&mut engine_state, &mut stack, hook, text)
+            }),
+            hook => run_hook(&mut engine_state, &mut stack, hook, text),
+        }
+    }
+}
+
+fn run_hook(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    hook: &Value,
+    text: String,
+) -> String {
+    let span = Span::unknown();
+    let output = eval_hook(
+        engine_state,
+        stack,
+        Some(Value::string(&text, span).into_pipeline_data()),
+        vec![("text".into(), Value::string(&text, span))],
+        hook,
+        "paste",
+    )
+    .and_then(|output| output.into_value(span));
+
+    match output {
+        Ok(Value::String { val, .. }) => val,
+        Ok(_) => text,
+        Err(err) => {
+            report_shell_error(Some(stack), engine_state, &err);
+            text
+        }
+    }
+}
+
+/// Wraps the configured edit mode to run the `paste` hook on bracketed pastes.
+pub(crate) struct PasteHookMode {
+    inner: Box<dyn EditMode>,
+    hook: PasteHook,
+}
+
+impl PasteHookMode {
+    pub(crate) fn new(inner: Box<dyn EditMode>, hook: PasteHook) -> Self {
+        Self { inner, hook }
+    }
+}
+
+impl EditMode for PasteHookMode {
+    fn parse_event(&mut self, event: ReedlineRawEvent) -> ReedlineEvent {
+        let event = match Event::from(event) {
+            Event::Paste(text) => Event::Paste(self.hook.transform(text)),
+            event => event,
+        };
+        // Only key releases are rejected, and those never made it here
+        let Ok(event) = ReedlineRawEvent::try_from(event) else {
+            return ReedlineEvent::None;
+        };
+
+        let event = self.inner.parse_event(event);
+        apply_mode_changes(self.inner.as_mut(), event)
+    }
+
+    fn edit_mode(&self) -> PromptEditMode {
+        self.inner.edit_mode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::engine::StateWorkingSet;
+
+    fn transform(hook: &str, text: &str) -> String {
+        let mut engine_state =
+            nu_command::add_shell_command_context(nu_cmd_lang::create_default_context());
+        let delta = StateWorkingSet::new(&engine_state).render();
+        engine_state.merge_delta(delta).expect("merge_delta");
+        let stack = Arc::new(Stack::new());
+        let hook = PasteHook {
+            hook: Value::test_string(hook),
+            engine_state: Arc::new(engine_state),
+            stack: Arc::downgrade(&stack),
+        };
+        hook.transform(text.into())
+    }
+
+    #[test]
+    fn inserts_hook_output() {
+        assert_eq!(transform("$text | str trim", "  ls -la\n"), "ls -la");
+        assert_eq!(transform("$in | str upcase", "ls"), "LS");
+    }
+
+    #[test]
+    fn runs_list_hooks_in_turn() {
+        let mut engine_state =
+            nu_command::add_shell_command_context(nu_cmd_lang::create_default_context());
+        let delta = StateWorkingSet::new(&engine_state).render();
+        engine_state.merge_delta(delta).expect("merge_delta");
+        let stack = Arc::new(Stack::new());
+        let hook = PasteHook {
+            hook: Value::test_list(vec![
+                Value::test_string("$text | str trim"),
+                Value::test_string("$in | str upcase"),
+            ]),
+            engine_state: Arc::new(engine_state),
+            stack: Arc::downgrade(&stack),
+        };
+        assert_eq!(hook.transform("  ls\n".into()), "LS");
+    }
+
+    #[test]
+    fn keeps_text_without_string_output() {
+        assert_eq!(transform("null", "ls"), "ls");
+        assert_eq!(transform("error make {msg: 'boom'}", "ls"), "ls");
+    }
+
+    #[test]
+    fn keeps_text_once_stack_is_gone() {
+        let engine_state = Arc::new(EngineState::new());
+        let stack = Arc::new(Stack::new());
+        let hook = PasteHook {
+            hook: Value::test_string("'replaced'"),
+            engine_state,
+            stack: Arc::downgrade(&stack),
+        };
+        drop(stack);
+        assert_eq!(hook.transform("ls".into()), "ls");
+    }
+}
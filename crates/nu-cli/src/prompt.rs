@@ -44,6 +44,10 @@ impl NushellPrompt {
         self.render_right_prompt_on_last_line = render_right_prompt_on_last_line;
     }
 
+    pub fn update_right_prompt_on_last_line(&mut self, render_right_prompt_on_last_line: bool) {
+        self.render_right_prompt_on_last_line = render_right_prompt_on_last_line;
+    }
+
     pub fn update_prompt_indicator(&mut self, prompt_indicator_string: Option<String>) {
         self.prompt_indicator = prompt_indicator_string;
     }
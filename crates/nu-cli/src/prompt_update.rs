@@ -3,10 +3,14 @@ use log::{info, trace};
 use nu_engine::ClosureEvalOnce;
 use nu_protocol::{
     Config, PipelineData, Value,
-    engine::{EngineState, Stack},
+    engine::{Closure, EngineState, Stack},
     report_shell_error,
 };
-use reedline::Prompt;
+use reedline::{Prompt, PromptEditMode, PromptHistorySearch};
+use std::{
+    borrow::Cow,
+    sync::{Arc, OnceLock},
+};
 
 // Name of environment variable where the prompt could be stored
 pub(crate) const PROMPT_COMMAND: &str = "PROMPT_COMMAND";
@@ -129,11 +133,19 @@ pub fn update_prompt(
 pub(crate) fn make_transient_prompt(
     config: &Config,
     engine_state: &EngineState,
-    stack: &mut Stack,
+    stack_arc: &Arc<Stack>,
     nu_prompt: &NushellPrompt,
 ) -> Box<dyn Prompt> {
     let mut nu_prompt = nu_prompt.clone();
 
+    // reedline always repaints submitted lines with the transient prompt, so a disabled
+    // transient prompt is the regular one
+    if !config.transient_prompt.enabled {
+        return Box::new(nu_prompt);
+    }
+
+    let stack = &mut Stack::with_parent(stack_arc.clone());
+
     if let Some(s) = get_prompt_string(TRANSIENT_PROMPT_COMMAND, config, engine_state, stack) {
         nu_prompt.update_prompt_left(Some(s))
     }
@@ -142,6 +154,9 @@ pub(crate) fn make_transient_prompt(
     {
         nu_prompt.update_prompt_right(Some(s), config.render_right_prompt_on_last_line)
     }
+    if let Some(on_last_line) = config.transient_prompt.render_right_prompt_on_last_line {
+        nu_prompt.update_right_prompt_on_last_line(on_last_line)
+    }
 
     if let Some(s) = get_prompt_string(TRANSIENT_PROMPT_INDICATOR, config, engine_state, stack) {
         nu_prompt.update_prompt_indicator(Some(s))
@@ -172,7 +187,69 @@ pub(crate) fn make_transient_prompt(
         nu_prompt.update_prompt_multiline(Some(s))
     }
 
-    Box::new(nu_prompt)
+    match &config.transient_prompt.closure {
+        Some(closure) => Box::new(ClosureTransientPrompt {
+            prompt: nu_prompt,
+            engine_state: engine_state.clone(),
+            stack: stack_arc.clone(),
+            closure: closure.clone(),
+            left_prompt: OnceLock::new(),
+        }),
+        None => Box::new(nu_prompt),
+    }
+}
+
+/// Transient prompt whose left part comes from `$env.config.transient_prompt.closure`.
+///
+/// The closure only runs when reedline renders the transient prompt, that is when the command
+/// is submitted, and its output is reused for any further repaint.
+struct ClosureTransientPrompt {
+    prompt: NushellPrompt,
+    engine_state: EngineState,
+    stack: Arc<Stack>,
+    closure: Closure,
+    left_prompt: OnceLock<String>,
+}
+
+impl Prompt for ClosureTransientPrompt {
+    fn render_prompt_left(&self) -> Cow<'_, str> {
+        let left_prompt = self.left_prompt.get_or_init(|| {
+            let stack = Stack::with_parent(self.stack.clone());
+            ClosureEvalOnce::new(&self.engine_state, &stack, self.closure.clone())
+                .run_with_input(PipelineData::empty())
+                .and_then(|pd| pd.collect_string("", self.engine_state.get_config()))
+                .unwrap_or_else(|err| {
+                    report_shell_error(None, &self.engine_state, &err);
+                    String::new()
+                })
+        });
+
+        left_prompt.replace('\n', "\r\n").into()
+    }
+
+    fn render_prompt_right(&self) -> Cow<'_, str> {
+        self.prompt.render_prompt_right()
+    }
+
+    fn render_prompt_indicator(&self, edit_mode: PromptEditMode) -> Cow<'_, str> {
+        self.prompt.render_prompt_indicator(edit_mode)
+    }
+
+    fn render_prompt_multiline_indicator(&self) -> Cow<'_, str> {
+        self.prompt.render_prompt_multiline_indicator()
+    }
+
+    fn render_prompt_history_search_indicator(
+        &self,
+        history_search: PromptHistorySearch,
+    ) -> Cow<'_, str> {
+        self.prompt
+            .render_prompt_history_search_indicator(history_search)
+    }
+
+    fn right_prompt_on_last_line(&self) -> bool {
+        self.prompt.right_prompt_on_last_line()
+    }
 }
 
 #[cfg(test)]
@@ -198,4 +275,29 @@ mod tests {
 
         assert_eq!(nu_prompt.render_prompt_left(), "test");
     }
+
+    #[test]
+    fn transient_prompt_follows_config() {
+        let mut config = Config::default();
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        stack.add_env_var(
+            TRANSIENT_PROMPT_COMMAND.into(),
+            Value::string("transient", Span::test_data()),
+        );
+        let stack = Arc::new(stack);
+
+        let mut nu_prompt = NushellPrompt::new();
+        nu_prompt.update_prompt_left(Some("regular".into()));
+
+        config.transient_prompt.render_right_prompt_on_last_line = Some(true);
+        let transient = make_transient_prompt(&config, &engine_state, &stack, &nu_prompt);
+        assert_eq!(transient.render_prompt_left(), "transient");
+        assert!(transient.right_prompt_on_last_line());
+
+        config.transient_prompt.enabled = false;
+        let transient = make_transient_prompt(&config, &engine_state, &stack, &nu_prompt);
+        assert_eq!(transient.render_prompt_left(), "regular");
+        assert!(!transient.right_prompt_on_last_line());
+    }
 }
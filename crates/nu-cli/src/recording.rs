@@ -0,0 +1,198 @@
+//! Session recordings written by `record start` and read back by `record replay`.
+//!
+//! A session file holds one JSON object per line, one line per command run in the REPL while
+//! the recording was active.
+
+use chrono::{DateTime, FixedOffset, Utc};
+use nu_protocol::{
+    ShellError, Span, Value,
+    engine::{EngineState, Stack},
+    shell_error::{generic::GenericError, io::IoError},
+};
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Path of the session file while a recording is active.
+pub(crate) const RECORDING_ENV: &str = "NU_RECORDING";
+/// Commands left to replay, one of them is put on the command line at each prompt.
+pub(crate) const REPLAY_ENV: &str = "NU_REPLAY";
+
+/// A recorded command.
+pub(crate) struct Entry {
+    pub command: String,
+    pub cwd: String,
+    pub start: DateTime<FixedOffset>,
+    pub duration: Duration,
+    pub exit_code: i64,
+    pub output: Option<String>,
+}
+
+impl Entry {
+    fn to_json(&self) -> String {
+        let mut entry = serde_json::json!({
+            "command": self.command,
+            "cwd": self.cwd,
+            "start": self.start.to_rfc3339(),
+            "duration_ms": self.duration.as_millis() as u64,
+            "exit_code": self.exit_code,
+        });
+        if let Some(output) = &self.output {
+            entry["output"] = output.as_str().into();
+        }
+        entry.to_string()
+    }
+
+    fn from_json(line: &str) -> Option<Self> {
+        let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+        Some(Self {
+            command: entry.get("command")?.as_str()?.to_string(),
+            cwd: entry.get("cwd")?.as_str()?.to_string(),
+            start: DateTime::parse_from_rfc3339(entry.get("start")?.as_str()?).ok()?,
+            duration: Duration::from_millis(entry.get("duration_ms")?.as_u64()?),
+            exit_code: entry.get("exit_code")?.as_i64()?,
+            output: entry
+                .get("output")
+                .and_then(|output| output.as_str())
+                .map(str::to_string),
+        })
+    }
+}
+
+/// Read all entries of a session file.
+pub(crate) fn read_session(path: &Path, span: Span) -> Result<Vec<Entry>, ShellError> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| IoError::new(err, span, PathBuf::from(path)))?;
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            Entry::from_json(line).ok_or_else(|| {
+                ShellError::Generic(GenericError::new(
+                    "Invalid session file",
+                    format!(
+                        "line {} of '{}' is not a recorded command",
+                        index + 1,
+                        path.display()
+                    ),
+                    span,
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Append an entry to a session file.
+pub(crate) fn append_entry(path: &Path, entry: &Entry) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", entry.to_json())
+}
+
+/// The session file of the active recording, if any.
+pub(crate) fn recording_file(engine_state: &EngineState, stack: &Stack) -> Option<PathBuf> {
+    stack
+        .get_env_var(engine_state, RECORDING_ENV)
+        .and_then(|path| path.as_str().ok())
+        .map(PathBuf::from)
+}
+
+/// The recording a command is about to run in.
+pub(crate) struct ActiveRecording {
+    path: PathBuf,
+    cwd: String,
+}
+
+/// The active recording, taken before the command runs so the command is recorded with the
+/// directory it ran in.
+pub(crate) fn active_recording(
+    engine_state: &EngineState,
+    stack: &Stack,
+) -> Option<ActiveRecording> {
+    let path = recording_file(engine_state, stack)?;
+    let cwd = engine_state
+        .cwd(Some(stack))
+        .map(|cwd| cwd.as_std_path().to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Some(ActiveRecording { path, cwd })
+}
+
+/// Append the command the REPL just ran to the active recording.
+///
+/// `recording` is the recording that was active before the command ran, so the commands
+/// starting or stopping a recording are never part of it.
+pub(crate) fn record_command(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    recording: Option<ActiveRecording>,
+    command: &str,
+    start: DateTime<Utc>,
+    duration: Duration,
+) {
+    // The output is always taken, so it can't end up attached to a later command
+    let output = engine_state
+        .repl_state
+        .lock()
+        .expect("repl state mutex")
+        .recorded_output
+        .take();
+
+    let Some(ActiveRecording { path, cwd }) = recording else {
+        return;
+    };
+    if recording_file(engine_state, stack).as_ref() != Some(&path) {
+        return;
+    }
+
+    let entry = Entry {
+        command: command.to_string(),
+        cwd,
+        start: start.fixed_offset(),
+        duration,
+        exit_code: stack
+            .get_env_var(engine_state, "LAST_EXIT_CODE")
+            .and_then(|code| code.as_int().ok())
+            .unwrap_or_default(),
+        output,
+    };
+    if let Err(err) = append_entry(&path, &entry) {
+        log::warn!("Could not record command to {}: {err}", path.display());
+    }
+}
+
+/// Put the next command of an active replay on the command line, unless the user already
+/// typed something.
+pub(crate) fn queue_next_replay_command(engine_state: &EngineState, stack: &mut Stack) {
+    let Some(Ok(vals)) = stack
+        .get_env_var(engine_state, REPLAY_ENV)
+        .map(|commands| commands.clone().into_list())
+    else {
+        return;
+    };
+
+    let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+    if !repl.buffer.is_empty() {
+        return;
+    }
+
+    let mut remaining = vals.into_iter();
+    if let Some(command) = remaining
+        .next()
+        .and_then(|command| command.into_string().ok())
+    {
+        repl.cursor_pos = command.len();
+        repl.buffer = command;
+    }
+    drop(repl);
+
+    let remaining: Vec<Value> = remaining.collect();
+    if remaining.is_empty() {
+        stack.remove_env_var(engine_state, REPLAY_ENV);
+    } else {
+        stack.add_env_var(REPLAY_ENV.into(), Value::list(remaining, Span::unknown()));
+    }
+}
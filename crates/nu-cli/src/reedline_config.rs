@@ -1,5 +1,8 @@
-use crate::{NuHelpCompleter, menus::NuMenuCompleter};
-use crossterm::event::{KeyCode, KeyModifiers};
+use crate::{
+    NuHelpCompleter, closure_keybindings::is_closure_keybinding, kill_ring::is_register_keybinding,
+    menus::NuMenuCompleter,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use nu_ansi_term::Style;
 use nu_color_config::{color_record_to_nustyle, lookup_ansi_color_style};
 use nu_engine::eval_block;
@@ -14,10 +17,10 @@ use nu_protocol::{
 use reedline::{
     ColumnarMenu, DescriptionMenu, DescriptionMode, DescriptionPosition, Direction, EditCommand,
     EditCommandDiscriminants, FindStop, Granularity, IdeMenu, InputMode, Keybindings, ListMenu,
-    MenuBuilder, MotionTarget, OutputMode, PromptEditModeDiscriminants, Reedline, ReedlineEvent,
-    ReedlineEventDiscriminants, ReedlineMenu, TextObject, TextObjectScope, TextObjectType,
-    TraversalDirection, WordEdge, WordKind, default_emacs_keybindings,
-    default_vi_insert_keybindings, default_vi_normal_keybindings,
+    MenuBuilder, MotionTarget, OutputMode, PromptEditMode, PromptEditModeDiscriminants,
+    PromptViMode, Reedline, ReedlineEvent, ReedlineEventDiscriminants, ReedlineMenu, TextObject,
+    TextObjectScope, TextObjectType, TraversalDirection, WordEdge, WordKind,
+    default_emacs_keybindings, default_vi_insert_keybindings, default_vi_normal_keybindings,
 };
 use std::{str::FromStr, sync::Arc};
 
@@ -829,7 +832,11 @@ pub(crate) fn create_keybindings(config: &Config) -> Result<KeybindingsMode, She
             add_menu_keybindings(&mut normal_keybindings);
         }
     }
-    for keybinding in parsed_keybindings {
+    // Keybindings running a closure or using the kill ring are handled by `ClosureKeybindingsMode`
+    // and `KillRingMode` instead
+    for keybinding in parsed_keybindings.iter().filter(|keybinding| {
+        !is_closure_keybinding(keybinding) && !is_register_keybinding(keybinding)
+    }) {
         add_keybinding(
             &keybinding.mode,
             keybinding,
@@ -857,14 +864,27 @@ fn add_keybinding(
     insert_keybindings: &mut Keybindings,
     normal_keybindings: &mut Keybindings,
 ) -> Result<(), ShellError> {
+    use PromptEditModeDiscriminants as PEMD;
+    for mode in parse_edit_modes(mode)? {
+        let keybindings = match mode {
+            PEMD::ViInsert => &mut *insert_keybindings,
+            PEMD::ViNormal => &mut *normal_keybindings,
+            _ => &mut *emacs_keybindings,
+        };
+        add_parsed_keybinding(keybindings, keybinding, config)?;
+    }
+    Ok(())
+}
+
+/// The edit modes a keybinding is for, from its `mode`: one of `emacs`, `vi_insert` or
+/// `vi_normal`, or a list of them.
+fn parse_edit_modes(mode: &Value) -> Result<Vec<PromptEditModeDiscriminants>, ShellError> {
     use PromptEditModeDiscriminants as PEMD;
     let span = mode.span();
     match &mode {
         // When updating this implementation, also update `display_edit_mode` function
         Value::String { val, .. } => match PEMD::from_str(val) {
-            Ok(PEMD::Emacs) => add_parsed_keybinding(emacs_keybindings, keybinding, config),
-            Ok(PEMD::ViInsert) => add_parsed_keybinding(insert_keybindings, keybinding, config),
-            Ok(PEMD::ViNormal) => add_parsed_keybinding(normal_keybindings, keybinding, config),
+            Ok(mode @ (PEMD::Emacs | PEMD::ViInsert | PEMD::ViNormal)) => Ok(vec![mode]),
             Ok(PEMD::Default | PEMD::Custom) | Err(_) => Err(ShellError::InvalidValue {
                 valid: "'emacs', 'vi_insert', or 'vi_normal'".into(),
                 actual: format!("'{val}'"),
@@ -872,18 +892,11 @@ fn add_keybinding(
             }),
         },
         Value::List { vals, .. } => {
+            let mut modes = Vec::new();
             for inner_mode in vals {
-                add_keybinding(
-                    inner_mode,
-                    keybinding,
-                    config,
-                    emacs_keybindings,
-                    insert_keybindings,
-                    normal_keybindings,
-                )?
+                modes.extend(parse_edit_modes(inner_mode)?);
             }
-
-            Ok(())
+            Ok(modes)
         }
         v => Err(ShellError::RuntimeTypeMismatch {
             expected: Type::custom("string or list<string>"),
@@ -908,6 +921,52 @@ fn add_parsed_keybinding(
     keybinding: &ParsedKeybinding,
     config: &Config,
 ) -> Result<(), ShellError> {
+    let (modifier, keycode) = parse_key_combination(keybinding)?;
+
+    if let Some(event) = parse_event(&keybinding.event, config)? {
+        keybindings.add_binding(modifier, keycode, event);
+    } else {
+        keybindings.remove_binding(modifier, keycode);
+    }
+
+    Ok(())
+}
+
+/// The keys and edit modes of a keybinding that is handled by nushell instead of reedline, like
+/// one running a closure.
+pub(crate) struct KeyTrigger {
+    modifiers: KeyModifiers,
+    code: KeyCode,
+    modes: Vec<PromptEditModeDiscriminants>,
+}
+
+impl KeyTrigger {
+    pub(crate) fn from_keybinding(keybinding: &ParsedKeybinding) -> Result<Self, ShellError> {
+        let (modifiers, code) = parse_key_combination(keybinding)?;
+        Ok(Self {
+            modifiers,
+            code,
+            modes: parse_edit_modes(&keybinding.mode)?,
+        })
+    }
+
+    /// Whether the key was pressed in one of the edit modes of the keybinding.
+    pub(crate) fn matches(&self, key: &KeyEvent, mode: &PromptEditMode) -> bool {
+        use PromptEditModeDiscriminants as PEMD;
+        let mode = match mode {
+            PromptEditMode::Emacs => PEMD::Emacs,
+            PromptEditMode::Vi(PromptViMode::Insert) => PEMD::ViInsert,
+            PromptEditMode::Vi(PromptViMode::Normal) => PEMD::ViNormal,
+            _ => return false,
+        };
+        key.modifiers == self.modifiers && key.code == self.code && self.modes.contains(&mode)
+    }
+}
+
+/// The key combination of a keybinding, from its `modifier` and `keycode`.
+fn parse_key_combination(
+    keybinding: &ParsedKeybinding,
+) -> Result<(KeyModifiers, KeyCode), ShellError> {
     let Ok(modifier_str) = keybinding.modifier.as_str() else {
         return Err(ShellError::RuntimeTypeMismatch {
             expected: Type::String,
@@ -1009,13 +1068,7 @@ fn add_parsed_keybinding(
         }
     };
 
-    if let Some(event) = parse_event(&keybinding.event, config)? {
-        keybindings.add_binding(modifier, keycode, event);
-    } else {
-        keybindings.remove_binding(modifier, keycode);
-    }
-
-    Ok(())
+    Ok((modifier, keycode))
 }
 
 enum EventType<'config> {
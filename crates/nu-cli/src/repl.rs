@@ -7,18 +7,27 @@ use crate::prompt_update::{
 };
 use crate::{
     NuHighlighter, NuValidator, NushellPrompt,
+    abbreviations::{AbbreviationMode, placeholder_abbreviations, plain_abbreviations},
+    closure_keybindings::{
+        ClosureKeybindingsMode, create_closure_keybindings, is_closure_keybinding,
+    },
     completions::NuCompleter,
-    hints::ExternalHinter,
-    prompt_update,
+    edit_assist::{EditAssistMode, SharedLine},
+    hints::{AutosuggestHinter, ExternalHinter},
+    kill_ring::{KillRing, KillRingMode, create_register_keybindings, is_register_keybinding},
+    local_config,
+    paste_hook::{PasteHook, PasteHookMode},
+    prompt_update, recording,
     reedline_config::{KeybindingsMode, add_menus, create_keybindings},
     syntax_highlight::NoOpHighlighter,
+    undo_depth::UndoDepthMode,
     util::{eval_source, evaluate_source},
 };
 use crossterm::cursor::SetCursorStyle;
 use log::{error, trace, warn};
 use miette::{ErrReport, IntoDiagnostic, Result};
 use nu_cmd_base::util::get_editor;
-use nu_color_config::StyleComputer;
+use nu_color_config::{StyleComputer, color_record_to_nustyle, lookup_ansi_color_style};
 use nu_engine::env_to_strings;
 use nu_engine::exit::cleanup_exit;
 use nu_parser::{lex, trim_quotes_str};
@@ -27,7 +36,7 @@ use nu_protocol::{BannerKind, ShellIntegrationConfig, shell_error};
 use nu_protocol::{
     Config, HistoryConfig, HistoryFileFormat, PipelineData, ShellError, Span, Spanned, Value,
     config::NuCursorShape,
-    engine::{EngineState, Stack},
+    engine::{EngineState, Stack, StateWorkingSet},
     report_shell_error,
 };
 use nu_utils::time::Instant;
@@ -38,7 +47,7 @@ use nu_utils::{
 #[cfg(feature = "sqlite")]
 use reedline::SqliteBackedHistory;
 use reedline::{
-    CursorConfig, CwdAwareHinter, DefaultCompleter, EditCommand, Emacs, FileBackedHistory,
+    CursorConfig, DefaultCompleter, EditCommand, EditMode, Emacs, FileBackedHistory,
     HistorySessionId, MouseClickMode, Osc133ClickEventsMarkers, Osc633Markers, Reedline,
     SemanticPromptMarkers, Vi,
 };
@@ -49,7 +58,7 @@ use std::{
     io::{self, IsTerminal, Write},
     panic::{AssertUnwindSafe, catch_unwind},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Weak},
     time::Duration,
 };
 use sysinfo::System;
@@ -86,6 +95,8 @@ pub fn evaluate_repl(
 
     let mut entry_num = 0;
     let mut is_hostcommand = false;
+    let mut last_local_config = None;
+    let kill_ring = KillRing::default();
 
     // Let's grab the shell_integration configs
     let shell_integration_osc2 = config.shell_integration.osc2;
@@ -208,6 +219,8 @@ pub fn evaluate_repl(
                 entry_num: &mut entry_num,
                 hostname: hostname.as_deref(),
                 is_hostcommand: &mut is_hostcommand,
+                last_local_config: &mut last_local_config,
+                kill_ring: &kill_ring,
             });
 
             // pass the most recent version of the line_editor back
@@ -323,6 +336,10 @@ struct LoopContext<'a> {
     entry_num: &'a mut usize,
     hostname: Option<&'a str>,
     is_hostcommand: &'a mut bool,
+    /// The `.nu.toml` found for the current directory during the previous iteration.
+    last_local_config: &'a mut Option<PathBuf>,
+    /// The registers of `$env.config.kill_ring`, kept across prompts.
+    kill_ring: &'a KillRing,
 }
 
 struct RunContext<'a> {
@@ -428,6 +445,11 @@ fn run_command(ctx: RunContext) -> Reedline {
         );
     }
 
+    // Remember the recording active before the command, so `record start` and `record stop`
+    // don't end up in it
+    let recording = recording::active_recording(engine_state, stack);
+    let cmd_started_at = chrono::Utc::now();
+
     // Actual command execution logic starts from here
     let cmd_execution_start_time = Instant::now();
 
@@ -474,6 +496,15 @@ fn run_command(ctx: RunContext) -> Reedline {
         Value::string(format!("{}", cmd_duration.as_millis()), Span::unknown()),
     );
 
+    recording::record_command(
+        engine_state,
+        stack,
+        recording,
+        &command,
+        cmd_started_at,
+        cmd_duration,
+    );
+
     if history_supports_meta
         && let Err(e) = fill_in_result_related_history_metadata(
             &command,
@@ -524,6 +555,8 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
         entry_num,
         hostname,
         is_hostcommand,
+        last_local_config,
+        kill_ring,
     } = ctx;
 
     let mut start_time = Instant::now();
@@ -546,6 +579,16 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
     repl.buffer = line_editor.current_buffer_contents().to_string();
     drop(repl);
 
+    // Leave the environment entered with `env activate` once the user moved out of its directory
+    nu_command::deactivate_env_outside_dir(engine_state, &mut stack);
+
+    // Load the settings of a trusted `.nu.toml` when entering its directory, and unload them
+    // when leaving it
+    local_config::update_local_config(engine_state, &mut stack, last_local_config);
+
+    // Put the next command of a `record replay` on the command line
+    recording::queue_next_replay_command(engine_state, &mut stack);
+
     // Check all the environment variables they ask for
     // fire the "env_change" hook
     if let Err(error) = hook::eval_env_change_hook(
@@ -570,6 +613,19 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
     }
     perf!("pre-prompt hook", start_time, use_color);
 
+    start_time = Instant::now();
+    // Last chance to change the environment the prompt is computed from, fire the "pre_render" hook
+    if let Err(err) = hook::eval_hooks(
+        engine_state,
+        &mut stack,
+        vec![],
+        &engine_state.get_config().hooks.pre_render.clone(),
+        "pre_render",
+    ) {
+        report_shell_error(None, engine_state, &err);
+    }
+    perf!("pre-render hook", start_time, use_color);
+
     let engine_reference = Arc::new(engine_state.clone());
     let config = stack.get_config(engine_state);
 
@@ -587,6 +643,17 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
     // until we drop those, we cannot use the stack in the REPL loop itself
     // See STACK-REFERENCE to see where we have taken a reference
     let stack_arc = Arc::new(stack);
+    // The line editor and the highlighter share the line for `$env.config.edit_assist`, for
+    // keybindings that run a closure, for the kill ring and for abbreviations with a cursor
+    // placeholder
+    let has_custom_keybindings = config
+        .keybindings
+        .iter()
+        .any(|keybinding| is_closure_keybinding(keybinding) || is_register_keybinding(keybinding));
+    let shared_line = (config.edit_assist.enabled()
+        || has_custom_keybindings
+        || !placeholder_abbreviations(&config).is_empty())
+    .then(SharedLine::default);
     let term_program_is_vscode = engine_state
         .get_env_var("TERM_PROGRAM")
         .and_then(|v| v.as_str().ok())
@@ -596,11 +663,14 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
         // try to enable bracketed paste
         // It doesn't work on windows system: https://github.com/crossterm-rs/crossterm/issues/737
         .use_bracketed_paste(cfg!(not(target_os = "windows")) && config.bracketed_paste)
-        .with_highlighter(Box::new(NuHighlighter::new(
-            engine_reference.clone(),
-            // STACK-REFERENCE 1
-            stack_arc.clone(),
-        )))
+        .with_highlighter(Box::new(
+            NuHighlighter::new(
+                engine_reference.clone(),
+                // STACK-REFERENCE 1
+                stack_arc.clone(),
+            )
+            .with_edit_assist_line(shared_line.clone()),
+        ))
         .with_validator(Box::new(NuValidator {
             engine_state: engine_reference.clone(),
         }))
@@ -621,7 +691,7 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
                 .to_string(),
         ))
         .with_cursor_config(cursor_config)
-        .with_abbreviations(config.abbreviations.clone())
+        .with_abbreviations(plain_abbreviations(&config))
         .with_visual_selection_style(nu_ansi_term::Style {
             is_reverse: true,
             ..Default::default()
@@ -653,7 +723,17 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
                 style,
             )))
         } else {
-            line_editor.with_hinter(Box::new(CwdAwareHinter::default().with_style(style)))
+            let style = match &config.autosuggest.style {
+                Value::String { val, .. } => lookup_ansi_color_style(val),
+                style @ Value::Record { .. } => color_record_to_nustyle(style),
+                _ => style,
+            };
+            line_editor.with_hinter(Box::new(AutosuggestHinter::new(
+                engine_reference.clone(),
+                stack_arc.clone(),
+                &config.autosuggest,
+                style,
+            )))
         }
     } else {
         line_editor.disable_hints()
@@ -663,8 +743,8 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
 
     start_time = Instant::now();
     trace!("adding menus");
-    line_editor =
-        add_menus(line_editor, engine_reference, &stack_arc, config).unwrap_or_else(|e| {
+    line_editor = add_menus(line_editor, engine_reference.clone(), &stack_arc, config)
+        .unwrap_or_else(|e| {
             report_shell_error(None, engine_state, &e);
             Reedline::create()
         });
@@ -706,7 +786,20 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
 
     start_time = Instant::now();
     // Changing the line editor based on the found keybindings
-    line_editor = setup_keybindings(engine_state, line_editor);
+    let paste_hook = config.hooks.paste.clone().map(|hook| PasteHook {
+        hook,
+        engine_state: engine_reference.clone(),
+        stack: Arc::downgrade(&stack_arc),
+    });
+    line_editor = setup_keybindings(
+        engine_state,
+        line_editor,
+        shared_line,
+        paste_hook,
+        engine_reference.clone(),
+        Arc::downgrade(&stack_arc),
+        kill_ring,
+    );
 
     perf!("keybindings", start_time, use_color);
 
@@ -718,12 +811,8 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
         &mut Stack::with_parent(stack_arc.clone()),
         nu_prompt,
     );
-    let transient_prompt = prompt_update::make_transient_prompt(
-        config,
-        engine_state,
-        &mut Stack::with_parent(stack_arc.clone()),
-        nu_prompt,
-    );
+    let transient_prompt =
+        prompt_update::make_transient_prompt(config, engine_state, &stack_arc, nu_prompt);
 
     perf!("update_prompt", start_time, use_color);
 
@@ -1052,6 +1141,7 @@ fn do_run_cmd(
     entry_num: usize,
     use_color: bool,
 ) -> Reedline {
+    use nu_cmd_base::hook;
     trace!("eval source: {s}");
 
     let had_warning_before = engine_state.exit_warning_given.load(Ordering::SeqCst);
@@ -1067,6 +1157,7 @@ fn do_run_cmd(
         &format!("repl_entry #{entry_num}"),
         PipelineData::empty(),
         false,
+        engine_state.get_config().recover_parse_errors,
     ) {
         Err(ShellError::Exit { code, .. }) => {
             return cleanup_exit(line_editor, engine_state, code);
@@ -1074,6 +1165,19 @@ fn do_run_cmd(
         Err(err) => {
             report_shell_error(Some(stack), engine_state, &err);
             stack.set_last_error(&err);
+
+            // Let the user react to the failure, e.g. to log it or suggest a fix
+            let error =
+                err.into_full_value(&StateWorkingSet::new(engine_state), stack, Span::unknown());
+            if let Err(err) = hook::eval_hooks(
+                engine_state,
+                stack,
+                vec![("$error".into(), error)],
+                &engine_state.get_config().hooks.post_command_error.clone(),
+                "post_command_error",
+            ) {
+                report_shell_error(None, engine_state, &err);
+            }
         }
         Ok(failed) => {
             let code: i32 = failed.into();
@@ -1291,21 +1395,84 @@ fn setup_history(
 ///
 /// Setup Reedline keybindingds based on the provided config
 ///
-fn setup_keybindings(engine_state: &EngineState, line_editor: Reedline) -> Reedline {
-    match create_keybindings(engine_state.get_config()) {
-        Ok(keybindings) => match keybindings {
-            KeybindingsMode::Emacs(keybindings) => {
-                let edit_mode = Box::new(Emacs::new(keybindings));
-                line_editor.with_edit_mode(edit_mode)
+fn setup_keybindings(
+    engine_state: &EngineState,
+    line_editor: Reedline,
+    shared_line: Option<SharedLine>,
+    paste_hook: Option<PasteHook>,
+    closure_engine_state: Arc<EngineState>,
+    closure_stack: Weak<Stack>,
+    kill_ring: &KillRing,
+) -> Reedline {
+    let config = engine_state.get_config();
+    let keybindings = create_keybindings(config).and_then(|keybindings| {
+        Ok((
+            keybindings,
+            create_closure_keybindings(config)?,
+            create_register_keybindings(config)?,
+        ))
+    });
+    match keybindings {
+        Ok((keybindings, closure_keybindings, register_keybindings)) => {
+            let mut edit_mode: Box<dyn EditMode> = match keybindings {
+                KeybindingsMode::Emacs(keybindings) => Box::new(Emacs::new(keybindings)),
+                KeybindingsMode::Vi {
+                    insert_keybindings,
+                    normal_keybindings,
+                } => Box::new(Vi::new(insert_keybindings, normal_keybindings)),
+            };
+            if let Some(line) = &shared_line
+                && config.edit_assist.enabled()
+            {
+                edit_mode = Box::new(EditAssistMode::new(
+                    edit_mode,
+                    line.clone(),
+                    &config.edit_assist,
+                ));
             }
-            KeybindingsMode::Vi {
-                insert_keybindings,
-                normal_keybindings,
-            } => {
-                let edit_mode = Box::new(Vi::new(insert_keybindings, normal_keybindings));
-                line_editor.with_edit_mode(edit_mode)
+            let abbreviations = placeholder_abbreviations(config);
+            if let Some(line) = &shared_line
+                && !abbreviations.is_empty()
+            {
+                edit_mode = Box::new(AbbreviationMode::new(
+                    edit_mode,
+                    abbreviations,
+                    line.clone(),
+                ));
             }
-        },
+            if let Some(line) = &shared_line
+                && !closure_keybindings.is_empty()
+            {
+                edit_mode = Box::new(ClosureKeybindingsMode::new(
+                    edit_mode,
+                    closure_keybindings,
+                    line.clone(),
+                    closure_engine_state,
+                    closure_stack,
+                ));
+            }
+            if let Some(line) = shared_line
+                && !register_keybindings.is_empty()
+            {
+                edit_mode = Box::new(KillRingMode::new(
+                    edit_mode,
+                    register_keybindings,
+                    kill_ring.clone(),
+                    config,
+                    line,
+                ));
+            }
+            if let Ok(depth) = usize::try_from(config.undo.depth)
+                && depth > 0
+            {
+                edit_mode = Box::new(UndoDepthMode::new(edit_mode, depth));
+            }
+            // Outermost, so pasted blocks are re-indented after the hook changed them
+            if let Some(hook) = paste_hook {
+                edit_mode = Box::new(PasteHookMode::new(edit_mode, hook));
+            }
+            line_editor.with_edit_mode(edit_mode)
+        }
         Err(e) => {
             report_shell_error(None, engine_state, &e);
             line_editor
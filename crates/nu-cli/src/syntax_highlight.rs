@@ -1,15 +1,22 @@
-use log::trace;
+use crate::edit_assist::SharedLine;
+use log::{error, trace};
 use nu_ansi_term::Style;
-use nu_color_config::{get_matching_brackets_style, get_shape_color};
-use nu_engine::env;
+use nu_color_config::{
+    color_record_to_nustyle, get_matching_brackets_style, get_shape_color, lookup_ansi_color_style,
+};
+use nu_engine::{ClosureEvalOnce, env};
 use nu_parser::{FlatShape, flatten_block, parse};
 use nu_protocol::{
-    Span,
-    ast::{Block, Expr, Expression, PipelineRedirection, RecordItem},
-    engine::{EngineState, Stack, StateWorkingSet},
+    PipelineData, Span, Value,
+    ast::{Block, Expr, Expression, PipelineRedirection, RecordItem, Traverse},
+    engine::{Closure, EngineState, Stack, StateWorkingSet},
+    record,
 };
 use reedline::{AbbrExpandContext, Highlighter, StyledText};
-use std::sync::{Arc, Mutex};
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+};
 
 /// A highlighter that does nothing
 ///
@@ -34,6 +41,7 @@ pub struct NuHighlighter {
     pub engine_state: Arc<EngineState>,
     pub stack: Arc<Stack>,
     cache: Mutex<Option<HighlightCache>>,
+    edit_assist_line: Option<SharedLine>,
 }
 
 impl NuHighlighter {
@@ -42,12 +50,22 @@ impl NuHighlighter {
             engine_state,
             stack,
             cache: Mutex::new(None),
+            edit_assist_line: None,
         }
     }
+
+    /// Share the line being edited with the edit mode of `$env.config.edit_assist`.
+    pub(crate) fn with_edit_assist_line(mut self, line: Option<SharedLine>) -> Self {
+        self.edit_assist_line = line;
+        self
+    }
 }
 
 impl Highlighter for NuHighlighter {
     fn highlight(&self, line: &str, cursor: usize) -> StyledText {
+        if let Some(edit_assist_line) = &self.edit_assist_line {
+            edit_assist_line.update(line, cursor);
+        }
         let result = highlight_syntax(&self.engine_state, &self.stack, line, cursor);
         *self.cache.lock().unwrap_or_else(|e| e.into_inner()) = Some(HighlightCache {
             line: line.to_string(),
@@ -229,10 +247,174 @@ pub(crate) fn highlight_syntax(
         result.text.push((Style::new(), remainder));
     }
 
+    if let Some(closure) = &config.highlighter.external_args {
+        let overrides = external_arg_styles(
+            engine_state,
+            stack,
+            closure,
+            &working_set,
+            &block,
+            &shapes,
+            line,
+        );
+        if !overrides.is_empty() {
+            result.text = apply_style_overrides(std::mem::take(&mut result.text), &overrides);
+        }
+    }
+
     result.shapes = shapes;
     result
 }
 
+/// Styles for parts of the line, computed by `$env.config.highlighter.external_args` for each
+/// external call.
+///
+/// The closure gets a record with the `command` name, the whole `line`, and the `args` of the
+/// call as `{text, start, end, shape}` records, and returns a list of `{start, end, style}`
+/// records. Offsets are byte offsets in the line.
+fn external_arg_styles(
+    engine_state: &EngineState,
+    stack: &Stack,
+    closure: &Closure,
+    working_set: &StateWorkingSet,
+    block: &Block,
+    shapes: &[(Span, FlatShape)],
+    line: &str,
+) -> Vec<(Range<usize>, Style)> {
+    let global_span_offset = engine_state.next_span_start();
+    let in_line = |span: Span| {
+        span.start >= global_span_offset && span.end <= global_span_offset + line.len()
+    };
+
+    let mut calls = vec![];
+    block.flat_map(
+        working_set,
+        &|expr| match &expr.expr {
+            Expr::ExternalCall(head, args) => {
+                vec![(
+                    head.span,
+                    args.iter().map(|arg| arg.expr().span).collect::<Vec<_>>(),
+                )]
+            }
+            _ => vec![],
+        },
+        &mut calls,
+    );
+
+    let span = Span::unknown();
+    let mut overrides = vec![];
+    for (head, args) in calls {
+        if !in_line(head) {
+            continue;
+        }
+        let args = args
+            .into_iter()
+            .filter(|arg| in_line(*arg))
+            .map(|arg| {
+                let shape = shapes
+                    .iter()
+                    .find(|(shape_span, _)| shape_span.start == arg.start)
+                    .map_or_else(
+                        || Value::nothing(span),
+                        |(_, shape)| Value::string(shape.as_str(), span),
+                    );
+                let text = String::from_utf8_lossy(working_set.get_span_contents(arg));
+                Value::record(
+                    record! {
+                        "text" => Value::string(text, span),
+                        "start" => Value::int((arg.start - global_span_offset) as i64, span),
+                        "end" => Value::int((arg.end - global_span_offset) as i64, span),
+                        "shape" => shape,
+                    },
+                    span,
+                )
+            })
+            .collect();
+        let command = String::from_utf8_lossy(working_set.get_span_contents(head));
+        let context = Value::record(
+            record! {
+                "command" => Value::string(command, span),
+                "args" => Value::list(args, span),
+                "line" => Value::string(line, span),
+            },
+            span,
+        );
+
+        let result = ClosureEvalOnce::new(engine_state, stack, closure.clone())
+            .add_arg(context)
+            .and_then(|closure| closure.run_with_input(PipelineData::empty()))
+            .and_then(|data| data.into_value(span));
+        let styles = match result {
+            Ok(Value::List { vals, .. }) => vals,
+            Ok(Value::Nothing { .. }) => continue,
+            Ok(value) => {
+                error!(
+                    "highlighter.external_args: expected a list of records, got {}",
+                    value.get_type()
+                );
+                continue;
+            }
+            Err(err) => {
+                error!("highlighter.external_args: closure evaluation failed: {err}");
+                continue;
+            }
+        };
+
+        for style in styles.iter() {
+            let Ok(style) = style.as_record() else {
+                continue;
+            };
+            let offset = |name| {
+                style
+                    .get(name)
+                    .and_then(|offset| offset.as_int().ok())
+                    .and_then(|offset| usize::try_from(offset).ok())
+                    .filter(|offset| line.is_char_boundary(*offset))
+            };
+            let (Some(start), Some(end)) = (offset("start"), offset("end")) else {
+                continue;
+            };
+            let style = match style.get("style") {
+                Some(Value::String { val, .. }) => lookup_ansi_color_style(val),
+                Some(value @ Value::Record { .. }) => color_record_to_nustyle(value),
+                _ => continue,
+            };
+            if start < end {
+                overrides.push((start..end, style));
+            }
+        }
+    }
+
+    overrides
+}
+
+/// Restyle the parts of `text` covered by `overrides`, later overrides winning.
+fn apply_style_overrides(text: StyledText, overrides: &[(Range<usize>, Style)]) -> StyledText {
+    let mut result = StyledText::new();
+    let mut offset = 0;
+    for (style, segment) in text.buffer {
+        let end = offset + segment.len();
+        let mut pos = offset;
+        while pos < end {
+            let next = overrides
+                .iter()
+                .flat_map(|(range, _)| [range.start, range.end])
+                .filter(|boundary| *boundary > pos && *boundary < end)
+                .min()
+                .unwrap_or(end);
+            let style = overrides
+                .iter()
+                .rev()
+                .find(|(range, _)| range.contains(&pos))
+                .map_or(style, |(_, style)| *style);
+            result.push((style, segment[pos - offset..next - offset].to_string()));
+            pos = next;
+        }
+        offset = end;
+    }
+    result
+}
+
 fn split_span_by_highlight_positions(
     line: &str,
     span: Span,
@@ -0,0 +1,196 @@
+//! `$env.config.undo.depth`: how many edits of the command line can be undone.
+//!
+//! Reedline keeps every edit of the line to undo it and has no setting for how many, so
+//! [`UndoDepthMode`] follows the edits like reedline groups them, and drops `Undo` commands once
+//! the last `depth` edits were undone. The count isn't reset when a new line starts: an `Undo`
+//! that's let through on an empty undo history does nothing.
+
+use crate::edit_assist::apply_mode_changes;
+use reedline::{
+    EditCommand, EditMode, PromptEditMode, ReedlineEvent, ReedlineRawEvent, UndoBehavior,
+};
+
+/// Wraps the configured edit mode to limit how many edits can be undone.
+pub(crate) struct UndoDepthMode {
+    inner: Box<dyn EditMode>,
+    depth: usize,
+    tracker: UndoTracker,
+}
+
+impl UndoDepthMode {
+    pub(crate) fn new(inner: Box<dyn EditMode>, depth: usize) -> Self {
+        Self {
+            inner,
+            depth,
+            tracker: UndoTracker::default(),
+        }
+    }
+
+    fn limit(&mut self, event: ReedlineEvent) -> ReedlineEvent {
+        match event {
+            ReedlineEvent::Edit(commands) => {
+                let commands: Vec<_> = commands
+                    .into_iter()
+                    .filter(|command| self.tracker.allow(command, self.depth))
+                    .collect();
+                if commands.is_empty() {
+                    ReedlineEvent::None
+                } else {
+                    ReedlineEvent::Edit(commands)
+                }
+            }
+            ReedlineEvent::Multiple(events) => {
+                ReedlineEvent::Multiple(events.into_iter().map(|event| self.limit(event)).collect())
+            }
+            event => event,
+        }
+    }
+}
+
+impl EditMode for UndoDepthMode {
+    fn parse_event(&mut self, event: ReedlineRawEvent) -> ReedlineEvent {
+        let event = self.inner.parse_event(event);
+        let event = apply_mode_changes(self.inner.as_mut(), event);
+        self.limit(event)
+    }
+
+    fn edit_mode(&self) -> PromptEditMode {
+        self.inner.edit_mode()
+    }
+}
+
+/// Counts the edits that can be undone and redone.
+#[derive(Default)]
+struct UndoTracker {
+    undos: usize,
+    redos: usize,
+    /// The last edit, to group the next one with it like reedline does
+    last: Option<UndoBehavior>,
+}
+
+impl UndoTracker {
+    /// Whether the command is let through, counting the edit it makes.
+    fn allow(&mut self, command: &EditCommand, depth: usize) -> bool {
+        match command {
+            EditCommand::Undo => {
+                if self.undos == 0 {
+                    return false;
+                }
+                self.undos -= 1;
+                self.redos += 1;
+                self.last = None;
+            }
+            EditCommand::Redo => {
+                if self.redos > 0 {
+                    self.redos -= 1;
+                    self.undos = (self.undos + 1).min(depth);
+                }
+                self.last = None;
+            }
+            command => {
+                if let Some(behavior) = undo_behavior(command) {
+                    let grouped = self
+                        .last
+                        .as_ref()
+                        .is_some_and(|last| !behavior.create_undo_point_after(last));
+                    if !grouped {
+                        self.undos = (self.undos + 1).min(depth);
+                    }
+                    self.redos = 0;
+                    self.last = Some(behavior);
+                }
+            }
+        }
+        true
+    }
+}
+
+/// How the command changes the line, or `None` if it doesn't.
+fn undo_behavior(command: &EditCommand) -> Option<UndoBehavior> {
+    match command {
+        EditCommand::MoveToStart { .. }
+        | EditCommand::MoveToEnd { .. }
+        | EditCommand::MoveToLineStart { .. }
+        | EditCommand::MoveToLineEnd { .. }
+        | EditCommand::MoveToPosition { .. }
+        | EditCommand::MoveLeft { .. }
+        | EditCommand::MoveRight { .. }
+        | EditCommand::MoveWordLeft { .. }
+        | EditCommand::MoveBigWordLeft { .. }
+        | EditCommand::MoveWordRight { .. }
+        | EditCommand::MoveWordRightStart { .. }
+        | EditCommand::MoveBigWordRightStart { .. }
+        | EditCommand::MoveWordRightEnd { .. }
+        | EditCommand::MoveBigWordRightEnd { .. }
+        | EditCommand::MoveRightUntil { .. }
+        | EditCommand::MoveRightBefore { .. }
+        | EditCommand::MoveLeftUntil { .. }
+        | EditCommand::MoveLeftBefore { .. }
+        | EditCommand::SwapCursorAndAnchor
+        | EditCommand::SelectAll
+        | EditCommand::CopySelection
+        | EditCommand::CopyFromStart
+        | EditCommand::CopyFromLineStart
+        | EditCommand::CopyToEnd
+        | EditCommand::CopyToLineEnd
+        | EditCommand::CopyCurrentLine
+        | EditCommand::CopyWordLeft
+        | EditCommand::CopyBigWordLeft
+        | EditCommand::CopyWordRight
+        | EditCommand::CopyBigWordRight
+        | EditCommand::CopyWordRightToNext
+        | EditCommand::CopyBigWordRightToNext
+        | EditCommand::CopyLeft
+        | EditCommand::CopyRight
+        | EditCommand::CopyRightUntil(_)
+        | EditCommand::CopyRightBefore(_)
+        | EditCommand::CopyLeftUntil(_)
+        | EditCommand::CopyLeftBefore(_)
+        | EditCommand::CopyInsidePair { .. }
+        | EditCommand::CopyAroundPair { .. }
+        | EditCommand::CopyTextObject { .. } => None,
+        #[cfg(feature = "system-clipboard")]
+        EditCommand::CopySelectionSystem => None,
+        EditCommand::InsertChar(c) => Some(UndoBehavior::InsertCharacter(*c)),
+        EditCommand::Backspace => Some(UndoBehavior::Backspace(None)),
+        EditCommand::Delete => Some(UndoBehavior::Delete(None)),
+        _ => Some(UndoBehavior::CreateUndoPoint),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_word(tracker: &mut UndoTracker, word: &str) {
+        for c in word.chars() {
+            assert!(tracker.allow(&EditCommand::InsertChar(c), 2));
+        }
+    }
+
+    #[test]
+    fn undo_stops_at_depth() {
+        let mut tracker = UndoTracker::default();
+        type_word(&mut tracker, "ls ");
+        assert!(tracker.allow(&EditCommand::CutWordLeft, 2));
+        type_word(&mut tracker, "cd ");
+        assert!(tracker.allow(&EditCommand::MoveLeft { select: false }, 2));
+
+        assert!(tracker.allow(&EditCommand::Undo, 2));
+        assert!(tracker.allow(&EditCommand::Undo, 2));
+        assert!(!tracker.allow(&EditCommand::Undo, 2));
+
+        assert!(tracker.allow(&EditCommand::Redo, 2));
+        assert!(tracker.allow(&EditCommand::Undo, 2));
+        assert!(!tracker.allow(&EditCommand::Undo, 2));
+    }
+
+    #[test]
+    fn typing_a_word_is_one_edit() {
+        let mut tracker = UndoTracker::default();
+        type_word(&mut tracker, "ls");
+        assert_eq!(tracker.undos, 1);
+        type_word(&mut tracker, " -la");
+        assert_eq!(tracker.undos, 2);
+    }
+}
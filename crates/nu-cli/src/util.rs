@@ -4,7 +4,8 @@ use nu_cmd_base::hook::eval_hook;
 use nu_engine::{eval_block, eval_block_with_early_return};
 use nu_parser::{Token, TokenContents, lex, parse, unescape_unquote_string};
 use nu_protocol::{
-    PipelineData, ShellError, Span, Value,
+    ParseWarning, PipelineData, ShellError, Span, Value,
+    ast::Block,
     debugger::WithoutDebug,
     engine::{EngineState, Stack, StateWorkingSet},
     process::check_exit_status_future,
@@ -246,7 +247,15 @@ pub fn eval_source(
 ) -> i32 {
     let start_time = Instant::now();
 
-    let exit_code = match evaluate_source(engine_state, stack, source, fname, input, allow_return) {
+    let exit_code = match evaluate_source(
+        engine_state,
+        stack,
+        source,
+        fname,
+        input,
+        allow_return,
+        false,
+    ) {
         Ok(failed) => {
             let code = failed.into();
             // No call span available in eval_source — this wraps generic source evaluation
@@ -289,9 +298,11 @@ pub(crate) fn evaluate_source(
     fname: &str,
     input: PipelineData,
     allow_return: bool,
+    recover_parse_errors: bool,
 ) -> Result<bool, ShellError> {
-    let (block, delta) = {
+    let parsed = {
         let mut working_set = StateWorkingSet::new(engine_state);
+        let start = working_set.next_span_start();
         let output = parse(
             &mut working_set,
             Some(fname), // format!("repl_entry #{}", entry_num)
@@ -304,15 +315,40 @@ pub(crate) fn evaluate_source(
 
         if let Some(err) = working_set.parse_errors.first() {
             report_parse_error(Some(stack), &working_set, err);
-            return Ok(true);
-        }
-
-        if let Some(err) = working_set.compile_errors.first() {
+            let Some(prefix_len) = recover_parse_errors
+                .then(|| runnable_prefix(&working_set, &output, start))
+                .flatten()
+            else {
+                return Ok(true);
+            };
+            let warning = ParseWarning::PartialRun {
+                span: Span::new(start + prefix_len, start + source.len()),
+            };
+            report_parse_warning(Some(stack), &working_set, &warning);
+            Err(prefix_len)
+        } else if let Some(err) = working_set.compile_errors.first() {
             report_compile_error(Some(stack), &working_set, err);
             return Ok(true);
+        } else {
+            Ok((output, working_set.render()))
         }
+    };
 
-        (output, working_set.render())
+    let (block, delta) = match parsed {
+        Ok(parsed) => parsed,
+        Err(prefix_len) => {
+            // The input as a whole still failed
+            return evaluate_source(
+                engine_state,
+                stack,
+                &source[..prefix_len],
+                fname,
+                input,
+                allow_return,
+                false,
+            )
+            .map(|_| true);
+        }
     };
 
     engine_state.merge_delta(delta)?;
@@ -345,6 +381,24 @@ pub(crate) fn evaluate_source(
     check_exit_status_future(pipeline.exit).map(|_| false)
 }
 
+/// The length of the source before its last statement, if that's the only one that failed to
+/// parse and there are others before it.
+fn runnable_prefix(working_set: &StateWorkingSet, block: &Block, start: usize) -> Option<usize> {
+    let [_, .., last] = block.pipelines.as_slice() else {
+        return None;
+    };
+    let last_start = last.elements.first()?.expr.span.start;
+    if working_set
+        .parse_errors
+        .iter()
+        .all(|err| err.span().start >= last_start)
+    {
+        last_start.checked_sub(start)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -373,4 +427,19 @@ mod test {
         assert!(env.contains_key("PWD"));
         assert_eq!(env.len(), 4);
     }
+
+    #[test]
+    fn runnable_prefix_only_before_failing_last_statement() {
+        let engine_state = EngineState::new();
+        let prefix = |source: &[u8]| {
+            let mut working_set = StateWorkingSet::new(&engine_state);
+            let start = working_set.next_span_start();
+            let block = parse(&mut working_set, None, source, false);
+            runnable_prefix(&working_set, &block, start)
+        };
+
+        assert_eq!(prefix(b"1; 2 +"), Some(3));
+        assert_eq!(prefix(b"1 +; 2"), None);
+        assert_eq!(prefix(b"1 +"), None);
+    }
 }
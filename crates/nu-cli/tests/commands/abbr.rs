@@ -0,0 +1,22 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn add_then_list() -> Result {
+    test()
+        .run("abbr add gco 'git checkout'; abbr list | where name == gco | get 0.expansion")
+        .expect_value_eq("git checkout")
+}
+
+#[test]
+fn add_replaces_and_remove_removes() -> Result {
+    test()
+        .run("abbr add gco 'git checkout'; abbr add gco 'git switch'; abbr remove gco; $env.config.abbreviations | get -o gco")
+        .expect_value_eq(Value::test_nothing())
+}
+
+#[test]
+fn remove_unknown_fails() -> Result {
+    let err = test().run("abbr remove nope").expect_shell_error()?;
+    assert!(matches!(err, ShellError::Generic(_)));
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use nu_test_support::{Outcome, nu};
+use tempfile::TempDir;
+
+const IMPORT_HISTORY_RECORDS: &str = "[[command start_timestamp duration exit_status cwd]; ['cargo build' ((date now) - 2day) 10ms 0 /tmp/a] ['cargo test' (date now) 20ms 1 /tmp/a] ['cargo build' (date now) 30ms 0 /tmp/b] ['ls' (date now) 5ms 0 /tmp/b]] | history import";
+
+struct Test {
+    cfg_dir: TempDir,
+}
+
+impl Test {
+    fn new() -> Self {
+        let cfg_dir = tempfile::Builder::new()
+            .prefix("history_search_test")
+            .tempdir()
+            .unwrap();
+        std::fs::write(
+            cfg_dir.path().join("env.nu"),
+            "$env.config.history.file_format = 'sqlite'",
+        )
+        .unwrap();
+        let test = Self { cfg_dir };
+        let import_result = test.nu(IMPORT_HISTORY_RECORDS);
+        assert!(import_result.status.success(), "{}", import_result.err);
+        test
+    }
+
+    fn nu(&self, cmd: impl AsRef<str>) -> Outcome {
+        let env = [(
+            "XDG_CONFIG_HOME".to_string(),
+            self.cfg_dir.path().to_str().unwrap().to_string(),
+        )];
+        let env_config = self.cfg_dir.path().join("env.nu");
+        nu!(envs: env, env_config: env_config, cmd.as_ref())
+    }
+}
+
+#[test]
+fn search_filters_by_term_and_cwd() {
+    let test = Test::new();
+
+    let actual = test.nu("history search cargo --cwd /tmp/a | get command | to nuon");
+    assert_eq!(actual.out, r#"["cargo build", "cargo test"]"#);
+}
+
+#[test]
+fn search_filters_by_exit_code() {
+    let test = Test::new();
+
+    let actual = test.nu("history search --exit-code 1 | get command | to nuon");
+    assert_eq!(actual.out, r#"["cargo test"]"#);
+}
+
+#[test]
+fn search_filters_by_since() {
+    let test = Test::new();
+
+    let actual = test.nu("history search cargo --since 1day | get command | to nuon");
+    assert_eq!(actual.out, r#"["cargo test", "cargo build"]"#);
+}
+
+#[test]
+fn stats_counts_commands() {
+    let test = Test::new();
+
+    let actual = test.nu("history stats --limit 1 | get 0 | $'($in.command):($in.count)'");
+    assert_eq!(actual.out, "cargo build:2");
+}
+
+#[test]
+fn export_then_import_dedupe_adds_nothing() {
+    let test = Test::new();
+
+    let actual = test.nu("history export | history import --dedupe; history | length");
+    assert_eq!(actual.out, "4");
+}
+
+#[test]
+fn import_dedupe_adds_new_items_only() {
+    let test = Test::new();
+
+    let actual = test.nu(
+        "history export --since 1day | append { command: 'cargo fmt' } | history import --dedupe; history | get command | to nuon",
+    );
+    assert_eq!(
+        actual.out,
+        r#"["cargo build", "cargo test", "cargo build", "ls", "cargo fmt"]"#
+    );
+}
+
+#[test]
+fn import_dedupe_keeps_sessions_apart() {
+    let test = Test::new();
+
+    let items = "[[command start_timestamp session_id]; [pwd 2024-01-01T00:00:00Z 1] [pwd 2024-01-01T00:00:00Z 2]]";
+    let actual = test.nu(format!(
+        "{items} | history import --dedupe; {items} | history import --dedupe; history --long | where command == pwd | get session_id | to nuon"
+    ));
+    assert_eq!(actual.out, "[1, 2]");
+}
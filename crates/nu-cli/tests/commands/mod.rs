@@ -1,8 +1,13 @@
+mod abbr;
 mod keybindings_list;
 mod nu_highlight;
+mod record;
 
 #[cfg(feature = "sqlite")]
 mod history_import;
 
 #[cfg(feature = "sqlite")]
 mod history_output;
+
+#[cfg(feature = "sqlite")]
+mod history_search;
@@ -86,3 +86,27 @@ fn nu_highlight_removes_content_type_metadata() -> Result {
     assert_eq!(out_meta, in_meta.map(|m| m.with_content_type(None)));
     Ok(())
 }
+
+#[test]
+fn nu_highlight_external_args_closure() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "$env.config.highlighter.external_args = {|ctx|
+            if $ctx.command != 'foo' { return null }
+            $ctx.args | where text == bar | each {|arg| {start: $arg.start, end: $arg.end, style: red_bold} }
+        }",
+    )?;
+    let () = tester.run("$env.config.color_config.shape_externalarg = blue")?;
+    tester
+        .run("'foo baz bar' | nu-highlight | [($in has $'(ansi red_bold)bar') ($in has $'(ansi red_bold)baz')]")
+        .expect_value_eq([true, false])
+}
+
+#[test]
+fn nu_highlight_external_args_closure_invalid_output() -> Result {
+    let mut tester = test();
+    let () = tester.run("$env.config.highlighter.external_args = {|ctx| 'not a list' }")?;
+    tester
+        .run("'^foo bar' | nu-highlight | ansi strip")
+        .expect_value_eq("^foo bar")
+}
@@ -0,0 +1,74 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+const SESSION: &str = r#"{"command":"ls","cwd":"/tmp/a","start":"2024-01-01T10:00:00+00:00","duration_ms":20,"exit_code":0}
+{"command":"cargo test","cwd":"/tmp/a","start":"2024-01-01T10:00:05+00:00","duration_ms":1500,"exit_code":1,"output":"error: 1 test failed"}
+"#;
+
+#[test]
+fn record_start_creates_session_file() {
+    Playground::setup("record_start_test_1", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "record start demo.jsonl; [($env.NU_RECORDING | path basename) ('demo.jsonl' | path exists)] | to nuon"
+        );
+
+        assert_eq!(actual.out, r#"["demo.jsonl", true]"#);
+    })
+}
+
+#[test]
+fn record_start_refuses_existing_file() {
+    Playground::setup("record_start_test_2", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("demo.jsonl", SESSION)]);
+
+        let actual = nu!(cwd: dirs.test(), "record start demo.jsonl");
+        assert!(actual.err.contains("Session file exists"));
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "record start --append demo.jsonl; record stop; $env.NU_RECORDING? | describe"
+        );
+        assert_eq!(actual.out, "nothing");
+    })
+}
+
+#[test]
+fn record_replay_renders_cast() {
+    Playground::setup("record_replay_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("demo.jsonl", SESSION)]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "record replay demo.jsonl --cast demo.cast; open demo.cast | lines | skip 1 | each { from json } | to nuon"
+        );
+
+        assert_eq!(
+            actual.out,
+            r#"[[0.0, o, "/tmp/a> ls\r\n"], [5.0, o, "/tmp/a> cargo test\r\n"], [6.5, o, "error: 1 test failed\r\n"]]"#
+        );
+    })
+}
+
+#[test]
+fn record_replay_rejects_invalid_session() {
+    Playground::setup("record_replay_test_2", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("demo.jsonl", "{\"command\": \"ls\"}\n")]);
+
+        let actual = nu!(cwd: dirs.test(), "record replay demo.jsonl --cast demo.cast");
+
+        assert!(actual.err.contains("line 1"));
+    })
+}
+
+#[test]
+fn record_replay_needs_interactive_session() {
+    Playground::setup("record_replay_test_3", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("demo.jsonl", SESSION)]);
+
+        let actual = nu!(cwd: dirs.test(), "record replay demo.jsonl");
+
+        assert!(actual.err.contains("interactive"));
+    })
+}
@@ -251,6 +251,7 @@ fn misc_command_argument_completions(
 #[case::command_argument("my-command ", None, vec!["cat", "dog", "eel"])]
 #[case::command_argument_after_cursor("my-command c", Some(11), vec!["cat", "dog", "eel"])]
 #[case::command_argument_after_cursor_piped("my-command c | ls", Some(11), vec!["cat", "dog", "eel"])]
+#[case::completer_by_name("fake-cmd --animal ", None, vec!["cat", "dog", "eel"])]
 fn misc_custom_completions(
     mut completer_strings: NuCompleter,
     #[case] input: &str,
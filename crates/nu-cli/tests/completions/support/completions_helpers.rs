@@ -2,8 +2,8 @@ use nu_engine::eval_block;
 use nu_parser::parse;
 use nu_path::{AbsolutePathBuf, PathBuf};
 use nu_protocol::{
-    DynamicCompletionCallRef, DynamicSuggestion, PipelineData, ShellError, Signature, Span,
-    SyntaxShape, Value,
+    Completion, DynamicCompletionCallRef, DynamicSuggestion, Flag, PipelineData, ShellError,
+    Signature, Span, SyntaxShape, Value,
     debugger::WithoutDebug,
     engine::{ArgType, Command, EngineState, Stack, StateWorkingSet},
 };
@@ -44,6 +44,12 @@ impl Command for FakeCmd {
                 "Example flag which support auto completion from plugin config.",
                 None,
             )
+            .param(
+                Flag::new("animal")
+                    .arg(SyntaxShape::String)
+                    .desc("Example flag completed by a command looked up by name.")
+                    .completion(Completion::CommandName("animals".into())),
+            )
     }
 
     #[expect(deprecated, reason = "example usage")]
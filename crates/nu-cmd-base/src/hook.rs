@@ -44,6 +44,10 @@ pub fn eval_hooks(
     hooks: &[Value],
     hook_name: &str,
 ) -> Result<(), ShellError> {
+    // Hooks with a higher `priority` run first, the others keep their order in the list
+    let mut hooks: Vec<&Value> = hooks.iter().collect();
+    hooks.sort_by_key(|hook| std::cmp::Reverse(hook_priority(hook)));
+
     for hook in hooks {
         eval_hook(
             engine_state,
@@ -57,6 +61,15 @@ pub fn eval_hooks(
     Ok(())
 }
 
+/// The `priority` of a hook given in record form, hooks without one have a priority of `0`.
+fn hook_priority(hook: &Value) -> i64 {
+    hook.as_record()
+        .ok()
+        .and_then(|record| record.get("priority"))
+        .and_then(|priority| priority.as_int().ok())
+        .unwrap_or(0)
+}
+
 pub fn eval_hook(
     engine_state: &mut EngineState,
     stack: &mut Stack,
@@ -139,6 +152,8 @@ pub fn eval_hook(
             // {
             //     condition: {|before, after| ... }  # block that evaluates to true/false
             //     code: # block or a string
+            //     name: # optional, used to remove the hook with `hook remove`
+            //     priority: # optional int, hooks with a higher priority run first
             // }
             // The condition block will be run to check whether the main hook (in `code`) should be run.
             // If it returns true (the default if a condition block is not specified), the hook should be run.
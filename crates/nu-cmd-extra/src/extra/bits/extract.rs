@@ -0,0 +1,169 @@
+use super::{check_field, field_endian_flag, read_bits, sign_extend};
+use nu_engine::command_prelude::*;
+use nu_heavy_utils::endian::Endian;
+
+#[derive(Clone)]
+pub struct BitsExtract;
+
+impl Command for BitsExtract {
+    fn name(&self) -> &str {
+        "bits extract"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("bits extract")
+            .input_output_types(vec![
+                (Type::Binary, Type::Int),
+                (
+                    Type::List(Box::new(Type::Binary)),
+                    Type::List(Box::new(Type::Int)),
+                ),
+            ])
+            .allow_variants_without_examples(true)
+            .required(
+                "offset",
+                SyntaxShape::Int,
+                "Bit the field starts at, counting from the most significant bit of the first byte.",
+            )
+            .required(
+                "length",
+                SyntaxShape::Int,
+                "Number of bits of the field, up to 64.",
+            )
+            .switch(
+                "signed",
+                "Read the field as a two's complement signed number.",
+                Some('s'),
+            )
+            .param(field_endian_flag())
+            .category(Category::Bits)
+    }
+
+    fn description(&self) -> &str {
+        "Extract a range of bits from binary data as an int."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Bits are numbered like in protocol diagrams: bit 0 is the most significant bit of the \
+first byte. Little endian fields must start on a byte and be made of whole bytes."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["field", "header", "protocol", "parse", "unpack"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let offset: Spanned<usize> = call.req(engine_state, stack, 0)?;
+        let length: Spanned<usize> = call.req(engine_state, stack, 1)?;
+        let signed = call.has_flag(engine_state, stack, "signed")?;
+        let endian = call
+            .get_flag::<Endian>(engine_state, stack, "endian")?
+            .unwrap_or(Endian::Big);
+
+        // This doesn't match explicit nulls
+        if let PipelineData::Empty = input {
+            return Err(ShellError::PipelineEmpty { dst_span: head });
+        }
+
+        input.map(
+            move |value| extract(value, offset, length, signed, endian, head),
+            engine_state.signals(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Get the version of an IPv4 header, its first 4 bits",
+                example: "0x[45 00 00 54] | bits extract 0 4",
+                result: Some(Value::test_int(4)),
+            },
+            Example {
+                description: "Get the total length of an IPv4 header, a 16 bit field",
+                example: "0x[45 00 00 54] | bits extract 16 16",
+                result: Some(Value::test_int(84)),
+            },
+            Example {
+                description: "Read a little endian field",
+                example: "0x[01 02] | bits extract 0 16 --endian little",
+                result: Some(Value::test_int(0x0201)),
+            },
+            Example {
+                description: "Read a signed 4 bit field",
+                example: "0x[f0] | bits extract 0 4 --signed",
+                result: Some(Value::test_int(-1)),
+            },
+        ]
+    }
+}
+
+fn extract(
+    value: Value,
+    offset: Spanned<usize>,
+    length: Spanned<usize>,
+    signed: bool,
+    endian: Endian,
+    head: Span,
+) -> Value {
+    let span = value.span();
+    let data = match value {
+        Value::Binary { val, .. } => val,
+        // Propagate errors by explicitly matching them before the final case.
+        Value::Error { .. } => return value,
+        other => {
+            return Value::error(
+                ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "binary".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: span,
+                },
+                span,
+            );
+        }
+    };
+
+    if let Err(msg) = check_field(offset.item, length.item, endian, data.len()) {
+        return Value::error(
+            ShellError::IncorrectValue {
+                msg,
+                val_span: offset.span.merge(length.span),
+                call_span: head,
+            },
+            span,
+        );
+    }
+
+    let raw = read_bits(&data, offset.item, length.item, endian);
+    if signed {
+        return Value::int(sign_extend(raw, length.item), span);
+    }
+    match i64::try_from(raw) {
+        Ok(int) => Value::int(int, span),
+        Err(_) => Value::error(
+            ShellError::IncorrectValue {
+                msg: format!("{raw} is too large for an int"),
+                val_span: length.span,
+                call_span: head,
+            },
+            span,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(BitsExtract)
+    }
+}
@@ -1,7 +1,9 @@
 mod and;
 mod bits_;
+mod extract;
 mod not;
 mod or;
+mod pack;
 mod rotate_left;
 mod rotate_right;
 mod shift_left;
@@ -10,8 +12,10 @@ mod xor;
 
 pub use and::BitsAnd;
 pub use bits_::Bits;
+pub use extract::BitsExtract;
 pub use not::BitsNot;
 pub use or::BitsOr;
+pub use pack::BitsPack;
 pub use rotate_left::BitsRol;
 pub use rotate_right::BitsRor;
 pub use shift_left::BitsShl;
@@ -19,7 +23,7 @@ pub use shift_right::BitsShr;
 pub use xor::BitsXor;
 
 use nu_heavy_utils::endian::Endian;
-use nu_protocol::{ShellError, Span, Spanned, Value};
+use nu_protocol::{Completion, Flag, ShellError, Span, Spanned, SyntaxShape, Value};
 use std::iter;
 
 #[derive(Clone, Copy)]
@@ -187,3 +191,116 @@ where
         ),
     }
 }
+
+/// The `--endian` flag of the commands reading and writing bit fields, which default to big
+/// endian like network protocols.
+fn field_endian_flag() -> Flag {
+    Flag::new("endian")
+        .short('e')
+        .arg(SyntaxShape::String)
+        .desc("Byte order of the fields, available options: big(default), little.")
+        .completion(Completion::new_list(&["big", "little"]))
+}
+
+/// Checks that a field of `length` bits at bit `offset` fits in `len_bytes` bytes.
+fn check_field(
+    offset: usize,
+    length: usize,
+    endian: Endian,
+    len_bytes: usize,
+) -> Result<(), String> {
+    if !(1..=64).contains(&length) {
+        return Err(format!(
+            "Fields must be 1 to 64 bits long, this one is {length}"
+        ));
+    }
+    if matches!(endian, Endian::Little) && (offset % 8 != 0 || length % 8 != 0) {
+        return Err("Little endian fields must be made of whole bytes".into());
+    }
+    if offset + length > len_bytes * 8 {
+        return Err(format!(
+            "The field ends at bit {}, past the end of the {len_bytes} bytes",
+            offset + length
+        ));
+    }
+    Ok(())
+}
+
+/// Reads `length` bits from bit `offset`, counting from the most significant bit of the first
+/// byte. With little endian, the field is made of whole bytes read least significant first.
+///
+/// The field must have been checked with [`check_field`].
+fn read_bits(data: &[u8], offset: usize, length: usize, endian: Endian) -> u64 {
+    match endian {
+        Endian::Big => (offset..offset + length).fold(0, |acc, bit| {
+            (acc << 1) | u64::from((data[bit / 8] >> (7 - bit % 8)) & 1)
+        }),
+        Endian::Little => data[offset / 8..(offset + length) / 8]
+            .iter()
+            .rev()
+            .fold(0, |acc, byte| (acc << 8) | u64::from(*byte)),
+    }
+}
+
+/// Writes the `length` low bits of `value` at bit `offset`, the opposite of [`read_bits`].
+///
+/// The field must have been checked with [`check_field`].
+fn write_bits(data: &mut [u8], offset: usize, length: usize, value: u64, endian: Endian) {
+    match endian {
+        Endian::Big => {
+            for i in 0..length {
+                if (value >> (length - 1 - i)) & 1 == 1 {
+                    let bit = offset + i;
+                    data[bit / 8] |= 0x80 >> (bit % 8);
+                }
+            }
+        }
+        Endian::Little => {
+            let bytes = &mut data[offset / 8..(offset + length) / 8];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = (value >> (8 * i)) as u8;
+            }
+        }
+    }
+}
+
+/// Interprets the `length` low bits of `raw` as a two's complement number.
+fn sign_extend(raw: u64, length: usize) -> i64 {
+    let shift = 64 - length as u32;
+    ((raw << shift) as i64) >> shift
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reads_and_writes_bit_fields() {
+        let mut data = vec![0; 3];
+        write_bits(&mut data, 0, 4, 0b0100, Endian::Big);
+        write_bits(&mut data, 4, 4, 0b0101, Endian::Big);
+        write_bits(&mut data, 8, 16, 0x1234, Endian::Little);
+        assert_eq!(data, [0x45, 0x34, 0x12]);
+
+        assert_eq!(read_bits(&data, 0, 4, Endian::Big), 4);
+        assert_eq!(read_bits(&data, 4, 4, Endian::Big), 5);
+        assert_eq!(read_bits(&data, 3, 6, Endian::Big), 0b001010);
+        assert_eq!(read_bits(&data, 8, 16, Endian::Little), 0x1234);
+        assert_eq!(read_bits(&data, 8, 16, Endian::Big), 0x3412);
+    }
+
+    #[test]
+    fn checks_fields() {
+        assert!(check_field(0, 64, Endian::Big, 8).is_ok());
+        assert!(check_field(0, 65, Endian::Big, 16).is_err());
+        assert!(check_field(4, 8, Endian::Little, 2).is_err());
+        assert!(check_field(12, 8, Endian::Big, 2).is_err());
+    }
+
+    #[test]
+    fn sign_extends() {
+        assert_eq!(sign_extend(0b1111, 4), -1);
+        assert_eq!(sign_extend(0b0111, 4), 7);
+        assert_eq!(sign_extend(u64::MAX, 64), -1);
+    }
+}
@@ -0,0 +1,218 @@
+use super::{check_field, field_endian_flag, write_bits};
+use nu_engine::command_prelude::*;
+use nu_heavy_utils::endian::Endian;
+use nu_protocol::FromValue;
+
+/// A field of the layout given to `bits pack`.
+#[derive(FromValue)]
+struct Field {
+    name: String,
+    bits: usize,
+    signed: Option<bool>,
+}
+
+#[derive(Clone)]
+pub struct BitsPack;
+
+impl Command for BitsPack {
+    fn name(&self) -> &str {
+        "bits pack"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("bits pack")
+            .input_output_types(vec![
+                (Type::record(), Type::Binary),
+                (Type::table(), Type::List(Box::new(Type::Binary))),
+            ])
+            .allow_variants_without_examples(true)
+            .required(
+                "layout",
+                SyntaxShape::Table(vec![]),
+                "The fields, in order, with their `name`, their number of `bits` and whether they're `signed`.",
+            )
+            .param(field_endian_flag())
+            .category(Category::Bits)
+    }
+
+    fn description(&self) -> &str {
+        "Pack the ints and bools of a record into binary data, following a layout."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Fields are written one after the other, starting with the most significant bit of the \
+first byte, and the last byte is padded with zeros. Every field of the layout must be in the \
+record, as an int or as a bool, which is written as 1 or 0. Little endian fields must start on a \
+byte and be made of whole bytes. `bits extract` reads the fields back."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["field", "header", "protocol", "serialize", "encode"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let layout: Value = call.req(engine_state, stack, 0)?;
+        let layout_span = layout.span();
+        let layout = Vec::<Field>::from_value(layout)?;
+        let endian = call
+            .get_flag::<Endian>(engine_state, stack, "endian")?
+            .unwrap_or(Endian::Big);
+
+        // Check the layout once, it's the same for every record
+        let total_bits: usize = layout.iter().map(|field| field.bits).sum();
+        let len_bytes = total_bits.div_ceil(8);
+        let mut offset = 0;
+        for field in &layout {
+            check_field(offset, field.bits, endian, len_bytes).map_err(|msg| {
+                ShellError::IncorrectValue {
+                    msg: format!("Field `{}`: {msg}", field.name),
+                    val_span: layout_span,
+                    call_span: head,
+                }
+            })?;
+            offset += field.bits;
+        }
+
+        // This doesn't match explicit nulls
+        if let PipelineData::Empty = input {
+            return Err(ShellError::PipelineEmpty { dst_span: head });
+        }
+
+        input.map(
+            move |value| match pack(&value, &layout, len_bytes, endian, head) {
+                Ok(bytes) => Value::binary(bytes, value.span()),
+                Err(err) => Value::error(err, value.span()),
+            },
+            engine_state.signals(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Pack the first byte of an IPv4 header",
+                example: "{version: 4, ihl: 5} | bits pack [[name bits]; [version 4] [ihl 4]]",
+                result: Some(Value::test_binary(vec![0x45])),
+            },
+            Example {
+                description: "Pack flags and a 16 bit length",
+                example: "{ack: true, syn: false, reserved: 0, len: 258} | bits pack [[name bits]; [ack 1] [syn 1] [reserved 6] [len 16]]",
+                result: Some(Value::test_binary(vec![0x80, 0x01, 0x02])),
+            },
+            Example {
+                description: "Pack a little endian field",
+                example: "{len: 258} | bits pack [[name bits]; [len 16]] --endian little",
+                result: Some(Value::test_binary(vec![0x02, 0x01])),
+            },
+            Example {
+                description: "Pack a signed field",
+                example: "{delta: -2} | bits pack [[name bits signed]; [delta 4 true]]",
+                result: Some(Value::test_binary(vec![0xe0])),
+            },
+        ]
+    }
+}
+
+fn pack(
+    value: &Value,
+    layout: &[Field],
+    len_bytes: usize,
+    endian: Endian,
+    head: Span,
+) -> Result<Vec<u8>, ShellError> {
+    let record = match value {
+        Value::Record { val, .. } => val,
+        // Propagate errors by explicitly matching them before the final case.
+        Value::Error { error, .. } => return Err(*error.clone()),
+        other => {
+            return Err(ShellError::OnlySupportsThisInputType {
+                exp_input_type: "record".into(),
+                wrong_type: other.get_type().to_string(),
+                dst_span: head,
+                src_span: other.span(),
+            });
+        }
+    };
+
+    let mut bytes = vec![0; len_bytes];
+    let mut offset = 0;
+    for field in layout {
+        let Some(cell) = record.get(&field.name) else {
+            return Err(ShellError::CantFindColumn {
+                col_name: field.name.clone(),
+                span: Some(head),
+                src_span: value.span(),
+            });
+        };
+        let number = match cell {
+            Value::Bool { val, .. } => i64::from(*val),
+            Value::Int { val, .. } => *val,
+            other => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "int or bool".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: other.span(),
+                });
+            }
+        };
+
+        let signed = field.signed.unwrap_or(false);
+        let (min, max) = field_range(field.bits, signed);
+        if !(min..=max).contains(&i128::from(number)) {
+            return Err(ShellError::IncorrectValue {
+                msg: format!(
+                    "`{}` must be from {min} to {max} to fit in {} {}bits",
+                    field.name,
+                    field.bits,
+                    if signed { "signed " } else { "" }
+                ),
+                val_span: cell.span(),
+                call_span: head,
+            });
+        }
+
+        write_bits(&mut bytes, offset, field.bits, number as u64, endian);
+        offset += field.bits;
+    }
+    Ok(bytes)
+}
+
+/// The smallest and largest ints that fit in a field.
+fn field_range(bits: usize, signed: bool) -> (i128, i128) {
+    if signed {
+        let half = 1i128 << (bits - 1);
+        (-half, half - 1)
+    } else {
+        // Ints are 64 bit signed, so a 64 bit field can't hold more than the largest int
+        (0, ((1i128 << bits) - 1).min(i128::from(i64::MAX)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(BitsPack)
+    }
+
+    #[test]
+    fn field_ranges() {
+        assert_eq!(field_range(4, false), (0, 15));
+        assert_eq!(field_range(4, true), (-8, 7));
+        assert_eq!(field_range(64, false), (0, i128::from(i64::MAX)));
+        assert_eq!(
+            field_range(64, true),
+            (i128::from(i64::MIN), i128::from(i64::MAX))
+        );
+    }
+}
@@ -5,7 +5,10 @@ mod math;
 mod platform;
 mod strings;
 
-pub use bits::{Bits, BitsAnd, BitsNot, BitsOr, BitsRol, BitsRor, BitsShl, BitsShr, BitsXor};
+pub use bits::{
+    Bits, BitsAnd, BitsExtract, BitsNot, BitsOr, BitsPack, BitsRol, BitsRor, BitsShl, BitsShr,
+    BitsXor,
+};
 pub use formats::ToHtml;
 pub use math::{MathArcCos, MathArcCosH, MathArcSin, MathArcSinH, MathArcTan, MathArcTanH};
 pub use math::{MathCos, MathCosH, MathSin, MathSinH, MathTan, MathTanH};
@@ -58,8 +61,10 @@ pub fn add_extra_command_context(mut engine_state: EngineState) -> EngineState {
         bind_command! {
             Bits,
             BitsAnd,
+            BitsExtract,
             BitsNot,
             BitsOr,
+            BitsPack,
             BitsRol,
             BitsRor,
             BitsShl,
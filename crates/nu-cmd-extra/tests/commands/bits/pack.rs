@@ -0,0 +1,28 @@
+use nu_test_support::nu;
+
+#[test]
+fn pack_and_extract_round_trip() {
+    let result = nu!(r#"
+        let layout = [[name bits signed]; [version 4 false] [ihl 4 false] [delta 8 true] [len 16 false]]
+        let packed = {version: 4, ihl: 5, delta: -3, len: 1500} | bits pack $layout
+        [
+            ($packed | bits extract 0 4)
+            ($packed | bits extract 4 4)
+            ($packed | bits extract 8 8 --signed)
+            ($packed | bits extract 16 16)
+        ] | str join ' '
+    "#);
+    assert_eq!("4 5 -3 1500", result.out);
+}
+
+#[test]
+fn pack_rejects_values_too_large_for_field() {
+    let result = nu!("{flag: 2} | bits pack [[name bits]; [flag 1]]");
+    assert!(result.err.contains("must be from 0 to 1"));
+}
+
+#[test]
+fn extract_rejects_range_past_end() {
+    let result = nu!("0x[ff] | bits extract 4 8");
+    assert!(result.err.contains("past the end"));
+}
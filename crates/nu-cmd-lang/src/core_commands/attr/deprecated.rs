@@ -35,6 +35,12 @@ impl Command for AttrDeprecated {
                 "Denote a version when this item will be removed.",
                 Some('r'),
             )
+            .named(
+                "replacement",
+                SyntaxShape::String,
+                "The command or flag to use instead.",
+                None,
+            )
             .param(
                 Flag::new("report")
                     .arg(SyntaxShape::String)
@@ -54,7 +60,9 @@ impl Command for AttrDeprecated {
             By default, only the first usage will trigger a deprecation warning.\n\
             \n\
             A help message can be included to provide more context for the deprecation, \
-            such as what to use as a replacement.\n\
+            and --replacement suggests what to use instead.\n\
+            \n\
+            Deprecations are listed in the help of the command.\n\
             \n\
             Also consider setting the category to deprecated with @category deprecated\
         "
@@ -103,6 +111,12 @@ impl Command for AttrDeprecated {
                     Span::test_data(),
                 )),
             },
+            Example {
+                description: "Deprecate a flag renamed in a later version.",
+                example: r###"@deprecated --flag all --since 1.2.0 --remove 2.0.0 --replacement "--every"
+    def my-command [--all, --every] {}"###,
+                result: None,
+            },
         ]
     }
 }
@@ -112,6 +126,7 @@ fn deprecated_record(call: WrapCall) -> Result<Value, ShellError> {
     let (call, flag): (_, Option<Spanned<String>>) = call.get_flag("flag")?;
     let (call, since): (_, Option<Spanned<String>>) = call.get_flag("since")?;
     let (call, remove): (_, Option<Spanned<String>>) = call.get_flag("remove")?;
+    let (call, replacement): (_, Option<Spanned<String>>) = call.get_flag("replacement")?;
     let (call, report): (_, Option<Spanned<String>>) = call.get_flag("report")?;
 
     let mut record = Record::new();
@@ -127,6 +142,12 @@ fn deprecated_record(call: WrapCall) -> Result<Value, ShellError> {
     if let Some(remove) = remove {
         record.push("expected_removal", Value::string(remove.item, remove.span))
     }
+    if let Some(replacement) = replacement {
+        record.push(
+            "replacement",
+            Value::string(replacement.item, replacement.span),
+        )
+    }
 
     let report = if let Some(Spanned { item, span }) = report {
         match item.as_str() {
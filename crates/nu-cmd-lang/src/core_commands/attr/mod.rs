@@ -4,6 +4,7 @@ mod complete;
 mod deprecated;
 mod example;
 mod search_terms;
+mod test;
 
 pub use attr_::Attr;
 pub use category::AttrCategory;
@@ -11,3 +12,4 @@ pub use complete::{AttrComplete, AttrCompleteExternal};
 pub use deprecated::AttrDeprecated;
 pub use example::AttrExample;
 pub use search_terms::AttrSearchTerms;
+pub use test::AttrTest;
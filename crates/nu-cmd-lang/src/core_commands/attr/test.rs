@@ -0,0 +1,57 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AttrTest;
+
+impl Command for AttrTest {
+    fn name(&self) -> &str {
+        "attr test"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("attr test")
+            .input_output_type(Type::Nothing, Type::Nothing)
+            .allow_variants_without_examples(true)
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Attribute for marking custom commands as tests."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Tests are found and run by the `test run` command."
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::nothing(call.head).into_pipeline_data())
+    }
+
+    fn run_const(
+        &self,
+        _working_set: &StateWorkingSet,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::nothing(call.head).into_pipeline_data())
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Mark a custom command as a test.",
+            example: "@test
+    def addition-works [] { if 1 + 1 != 2 { error make {msg: 'wrong sum'} } }",
+            result: None,
+        }]
+    }
+}
@@ -20,6 +20,7 @@ impl Command for Def {
             .required("params", SyntaxShape::Signature, "The command parameters, a comma-separated list inside [].")
             .required("block", SyntaxShape::Closure(None), "The body of the command, a list of instructions inside {}.")
             .switch("env", "Keep the environment defined inside the command.", None)
+            .switch("generator", "Stream the values the command yields, as it yields them.", None)
             .switch("wrapped", "Treat unknown flags and arguments as strings (requires ...rest-like parameter in signature).", None)
             .category(Category::Core)
     }
@@ -75,6 +76,15 @@ impl Command for Def {
                 example: "def only_int []: int -> int { $in }; 42 | only_int",
                 result: Some(Value::test_int(42)),
             },
+            Example {
+                description: "Define a generator, whose output streams the values it yields.",
+                example: "def --generator countdown [n: int] { for i in $n..1 { yield $i } }; countdown 3",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(3),
+                    Value::test_int(2),
+                    Value::test_int(1),
+                ])),
+            },
         ]
     }
 }
@@ -20,6 +20,7 @@ impl Command for ExportDef {
             .required("params", SyntaxShape::Signature, "Command parameters: comma-separated list inside [].")
             .required("block", SyntaxShape::Block, "Command body: list of instructions inside {}.")
             .switch("env", "Environment: defined inside the command.", None)
+            .switch("generator", "Stream the values the command yields, as it yields them.", None)
             .switch("wrapped", "Unknown flags and arguments: strings that require rest-like parameter in signature.", None)
             .category(Category::Core)
     }
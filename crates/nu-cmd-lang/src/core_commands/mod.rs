@@ -35,6 +35,7 @@ mod try_;
 mod use_;
 mod version;
 mod while_;
+mod yield_;
 
 pub use alias::Alias;
 pub use attr::*;
@@ -73,3 +74,4 @@ pub use try_::Try;
 pub use use_::Use;
 pub use version::{VERSION, VERSION_NU_FEATURES, Version};
 pub use while_::While;
+pub use yield_::Yield;
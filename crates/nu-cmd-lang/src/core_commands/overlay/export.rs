@@ -0,0 +1,164 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+use nuon::{ToNuonConfig, ToStyle, to_nuon};
+
+/// Environment variables that describe the running shell rather than the overlay itself.
+///
+/// Restoring them from a file would, for example, move the user into the directory the overlay
+/// happened to be exported from.
+const SKIPPED_ENV_VARS: &[&str] = &["PWD", "OLDPWD", "FILE_PWD", "CURRENT_FILE", "config"];
+
+#[derive(Clone)]
+pub struct OverlayExport;
+
+impl Command for OverlayExport {
+    fn name(&self) -> &str {
+        "overlay export"
+    }
+
+    fn description(&self) -> &str {
+        "Save the environment of an overlay into a module file that can be activated again later."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("overlay export")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required("name", SyntaxShape::String, "Overlay to export.")
+            .required_named(
+                "path",
+                SyntaxShape::Filepath,
+                "File the overlay module is written to.",
+                Some('p'),
+            )
+            .switch(
+                "force",
+                "Overwrite the file if it already exists.",
+                Some('f'),
+            )
+            .category(Category::Core)
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The written file is a regular module with an `export-env` block, so it can be activated with `overlay use <path>`. Naming the file after the overlay (e.g. `spam.nu` for `spam`) restores it under the same name.
+
+Environment variables that cannot be represented as nuon (e.g. closures) are skipped, as well as variables tied to the current session such as `PWD`.
+
+To re-activate exported overlays every time the shell starts, list their files in `$env.config.startup_overlays`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["save", "persist", "restore"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let Some(path): Option<Spanned<String>> = call.get_flag(engine_state, stack, "path")?
+        else {
+            return Err(ShellError::MissingParameter {
+                param_name: "path".into(),
+                span: call.head,
+            });
+        };
+        let force = call.has_flag(engine_state, stack, "force")?;
+
+        if !stack.has_env_overlay(&name.item, engine_state) {
+            return Err(ShellError::OverlayNotFoundAtRuntime {
+                overlay_name: name.item,
+                span: name.span,
+            });
+        }
+
+        let file_path = engine_state
+            .cwd(Some(stack))?
+            .join(&path.item)
+            .into_std_path_buf();
+        if !force && file_path.exists() {
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    "Destination file already exists",
+                    format!(
+                        "Destination file '{}' already exists",
+                        file_path.to_string_lossy()
+                    ),
+                    path.span,
+                )
+                .with_help("you can use -f, --force to force overwriting the destination"),
+            ));
+        }
+
+        // Start from the values merged into the engine state, then layer the stack on top
+        let mut env_vars: Vec<(String, Value)> = engine_state
+            .env_vars
+            .get(&name.item)
+            .into_iter()
+            .flatten()
+            .filter(|(env_name, _)| {
+                !stack
+                    .env_hidden
+                    .get(&name.item)
+                    .is_some_and(|hidden| hidden.contains(*env_name))
+            })
+            .map(|(env_name, value)| (env_name.as_str().to_string(), value.clone()))
+            .collect();
+
+        for (env_name, value) in stack.get_stack_overlay_env_vars(&name.item) {
+            match env_vars
+                .iter_mut()
+                .find(|(existing, _)| existing == &env_name)
+            {
+                Some((_, existing)) => *existing = value,
+                None => env_vars.push((env_name, value)),
+            }
+        }
+
+        env_vars.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let config = ToNuonConfig::default()
+            .style(ToStyle::Spaces(4))
+            .span(Some(call.head));
+        let mut record = Record::new();
+        for (env_name, value) in env_vars {
+            if SKIPPED_ENV_VARS.contains(&env_name.as_str()) {
+                continue;
+            }
+            // Values such as closures can't be written back as nuon, so they are left out
+            if to_nuon(engine_state, &value, config.clone()).is_ok() {
+                record.push(env_name, value);
+            }
+        }
+
+        let env_nuon = to_nuon(engine_state, &Value::record(record, call.head), config)?;
+
+        let contents = format!(
+            "# Overlay `{}` saved by `overlay export`\n\nexport-env {{\n    load-env {}\n}}\n",
+            name.item,
+            env_nuon.replace('\n', "\n    ")
+        );
+
+        std::fs::write(&file_path, contents)
+            .map_err(|err| IoError::new(err, path.span, file_path.clone()))?;
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Save the environment of the `spam` overlay into `spam.nu`.",
+                example: "overlay export spam --path spam.nu",
+                result: None,
+            },
+            Example {
+                description: "Replace a previously exported file for the current overlay.",
+                example: "overlay export (overlay list | last | get name) --path spam.nu --force",
+                result: None,
+            },
+        ]
+    }
+}
@@ -1,10 +1,12 @@
 mod command;
+mod export;
 mod hide;
 mod list;
 mod new;
 mod use_;
 
 pub use command::Overlay;
+pub use export::OverlayExport;
 pub use hide::OverlayHide;
 pub use list::OverlayList;
 pub use new::OverlayNew;
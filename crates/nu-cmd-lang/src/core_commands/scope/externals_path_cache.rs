@@ -0,0 +1,98 @@
+use nu_engine::{command_prelude::*, path_executables};
+
+#[derive(Clone)]
+pub struct ScopeExternalsPathCache;
+
+impl Command for ScopeExternalsPathCache {
+    fn name(&self) -> &str {
+        "scope externals-path-cache"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("scope externals-path-cache")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table(
+                    vec![
+                        ("name".to_string(), Type::String),
+                        ("path".to_string(), Type::String),
+                    ]
+                    .into(),
+                ),
+            )])
+            .switch(
+                "refresh",
+                "List the directories of PATH again, even if they didn't change.",
+                Some('r'),
+            )
+            .category(Category::Core)
+    }
+
+    fn description(&self) -> &str {
+        "Output the executables found in the directories of PATH."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The executables are listed in the order they're looked up, so the first one with a name \
+        is the one that runs. The listing is cached by the engine and shared with command \
+        completions, and is only read again when PATH or one of its directories changes, which \
+        makes this cheaper than listing the directories from a prompt or a completer."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["external", "executables", "path", "binaries"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        if call.has_flag(engine_state, stack, "refresh")? {
+            engine_state.externals_cache.clear();
+        }
+
+        let executables = path_executables(engine_state, stack);
+        let rows: Vec<Value> = executables
+            .iter()
+            .map(|executable| {
+                Value::record(
+                    record! {
+                        "name" => Value::string(&executable.name, head),
+                        "path" => Value::string(executable.path.to_string_lossy(), head),
+                    },
+                    head,
+                )
+            })
+            .collect();
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Count the executables in PATH.",
+                example: "scope externals-path-cache | length",
+                result: None,
+            },
+            Example {
+                description: "Find the executables shadowed by another one with the same name.",
+                example: "scope externals-path-cache | group-by name | values | where ($it | length) > 1",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ScopeExternalsPathCache)
+    }
+}
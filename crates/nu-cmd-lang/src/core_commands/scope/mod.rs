@@ -2,6 +2,7 @@ mod aliases;
 mod command;
 mod commands;
 mod engine_stats;
+mod externals_path_cache;
 mod externs;
 mod modules;
 mod variables;
@@ -10,6 +11,7 @@ pub use aliases::*;
 pub use command::*;
 pub use commands::*;
 pub use engine_stats::*;
+pub use externals_path_cache::*;
 pub use externs::*;
 pub use modules::*;
 pub use variables::*;
@@ -12,6 +12,11 @@ impl Command for ScopeModules {
         Signature::build("scope modules")
             .input_output_types(vec![(Type::Nothing, Type::Any)])
             .allow_variants_without_examples(true)
+            .switch(
+                "graph",
+                "List every module with the modules it imports instead.",
+                Some('g'),
+            )
             .category(Category::Core)
     }
 
@@ -28,16 +33,28 @@ impl Command for ScopeModules {
     ) -> Result<PipelineData, ShellError> {
         let head = call.head;
         let mut scope_data = ScopeData::new(engine_state, stack);
+        if call.has_flag(engine_state, stack, "graph")? {
+            return Ok(
+                Value::list(scope_data.collect_module_graph(head), head).into_pipeline_data()
+            );
+        }
         scope_data.populate_modules();
         Ok(Value::list(scope_data.collect_modules(head), head).into_pipeline_data())
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
-        vec![Example {
-            description: "Show the modules in the current scope.",
-            example: "scope modules",
-            result: None,
-        }]
+        vec![
+            Example {
+                description: "Show the modules in the current scope.",
+                example: "scope modules",
+                result: None,
+            },
+            Example {
+                description: "Show which modules import which.",
+                example: "scope modules --graph | select name imports.name",
+                result: None,
+            },
+        ]
     }
 }
 
@@ -2,10 +2,12 @@ use nu_engine::{
     command_prelude::*, find_in_dirs_env, get_dirs_var_from_call, get_eval_block, redirect_env,
 };
 use nu_protocol::{
+    BlockId, ModuleId, ParserPath,
     ast::{Expr, Expression},
     engine::CommandType,
     shell_error::generic::GenericError,
 };
+use std::{collections::HashSet, path::PathBuf};
 
 #[derive(Clone)]
 pub struct Use;
@@ -33,6 +35,11 @@ impl Command for Use {
                 SyntaxShape::Any,
                 "Which members of the module to import.",
             )
+            .switch(
+                "env-first",
+                "Set up the environment of the modules this module uses before its own.",
+                None,
+            )
             .category(Category::Core)
     }
 
@@ -44,6 +51,11 @@ impl Command for Use {
         "See `help std` for the standard library module.
 See `help modules` to list all available modules.
 
+The `export-env` block of a module runs when the module is used. With `--env-first`, the
+`export-env` blocks of the modules it uses, and of the modules those use, run before it, each
+one after the modules it uses and only once. This makes the order of environment setup follow
+the imports instead of the order of the `use` commands.
+
 This command is a parser keyword. For details, check:
   https://www.nushell.sh/book/thinking_in_nu.html"
     }
@@ -93,12 +105,30 @@ This command is a parser keyword. For details, check:
                 }
             }
 
+            if call.has_flag(engine_state, caller_stack, "env-first")? {
+                for dependency_id in env_dependencies(engine_state, module_id) {
+                    let dependency = engine_state.get_module(dependency_id);
+                    if let Some(block_id) = dependency.env_block {
+                        let file = match &dependency.file {
+                            Some((ParserPath::RealPath(path), _)) => Some(path.clone()),
+                            _ => None,
+                        };
+                        eval_env_block(
+                            engine_state,
+                            caller_stack,
+                            block_id,
+                            file,
+                            call.head,
+                            PipelineData::empty(),
+                        )?;
+                    }
+                }
+            }
+
             // Evaluate the export-env block if there is one
             let module = engine_state.get_module(module_id);
 
             if let Some(block_id) = module.env_block {
-                let block = engine_state.get_block(block_id);
-
                 // See if the module is a file
                 let module_arg_str = String::from_utf8_lossy(
                     engine_state.get_span_contents(import_pattern.head.span),
@@ -112,42 +142,24 @@ This command is a parser keyword. For details, check:
                 )?;
                 // module_arg_str maybe a directory, in this case
                 // find_in_dirs_env returns a directory.
-                let maybe_parent = maybe_file_path_or_dir.as_ref().and_then(|path| {
+                let module_file = maybe_file_path_or_dir.map(|path| {
                     if path.is_dir() {
-                        Some(path.to_path_buf())
-                    } else {
-                        path.parent().map(|p| p.to_path_buf())
-                    }
-                });
-
-                let mut callee_stack = caller_stack
-                    .gather_captures(engine_state, &block.captures)
-                    .reset_pipes();
-
-                // If so, set the currently evaluated directory (file-relative PWD)
-                if let Some(parent) = maybe_parent {
-                    let file_pwd = Value::string(parent.to_string_lossy(), call.head);
-                    callee_stack.add_env_var("FILE_PWD".to_string(), file_pwd);
-                }
-
-                if let Some(path) = maybe_file_path_or_dir {
-                    let module_file_path = if path.is_dir() {
                         // the existence of `mod.nu` is verified in parsing time
                         // so it's safe to use it here.
-                        Value::string(path.join("mod.nu").to_string_lossy(), call.head)
+                        path.join("mod.nu")
                     } else {
-                        Value::string(path.to_string_lossy(), call.head)
-                    };
-                    callee_stack.add_env_var("CURRENT_FILE".to_string(), module_file_path);
-                }
-
-                let eval_block = get_eval_block(engine_state);
-
-                // Run the block (discard the result)
-                let _ = eval_block(engine_state, &mut callee_stack, block, input)?;
+                        path
+                    }
+                });
 
-                // Merge the block's environment to the current stack
-                redirect_env(engine_state, caller_stack, &callee_stack);
+                eval_env_block(
+                    engine_state,
+                    caller_stack,
+                    block_id,
+                    module_file,
+                    call.head,
+                    input,
+                )?;
             }
         } else {
             return Err(ShellError::Generic(GenericError::new(
@@ -190,6 +202,11 @@ This command is a parser keyword. For details, check:
                 example: r#"module spam { export def 'foo bar' [] { "baz" } }; use spam 'foo bar'; foo bar"#,
                 result: Some(Value::test_string("baz")),
             },
+            Example {
+                description: "Set up the environment of the modules a module uses before its own.",
+                example: r#"module base { export-env { $env.PREFIX = "base" } }; module app { use base; export-env { $env.NAME = $env.PREFIX + "/app" } }; use --env-first app; $env.NAME"#,
+                result: Some(Value::test_string("base/app")),
+            },
             Example {
                 description: "To use multiple definitions from a module, wrap them in a list.",
                 example: r#"module spam { export def foo [] { "foo" }; export def 'foo bar' [] { "baz" } }; use spam ['foo', 'foo bar']; (foo) + (foo bar)"#,
@@ -207,3 +224,67 @@ mod test {
         nu_test_support::test().examples(Use)
     }
 }
+
+/// The modules that `module_id` uses, directly or through other modules, each after the modules
+/// it uses itself.
+fn env_dependencies(engine_state: &EngineState, module_id: ModuleId) -> Vec<ModuleId> {
+    fn visit(
+        engine_state: &EngineState,
+        module_id: ModuleId,
+        visited: &mut HashSet<ModuleId>,
+        order: &mut Vec<ModuleId>,
+    ) {
+        if !visited.insert(module_id) {
+            return;
+        }
+        for imported_id in &engine_state.get_module(module_id).imported_modules {
+            visit(engine_state, *imported_id, visited, order);
+        }
+        order.push(module_id);
+    }
+
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    visit(engine_state, module_id, &mut visited, &mut order);
+    // The module itself comes last
+    order.pop();
+    order
+}
+
+/// Run the `export-env` block of a module and keep the environment it sets up.
+///
+/// `module_file` is the file the module comes from, if any, which sets `$env.FILE_PWD` and
+/// `$env.CURRENT_FILE` in the block.
+fn eval_env_block(
+    engine_state: &EngineState,
+    caller_stack: &mut Stack,
+    block_id: BlockId,
+    module_file: Option<PathBuf>,
+    head: Span,
+    input: PipelineData,
+) -> Result<(), ShellError> {
+    let block = engine_state.get_block(block_id);
+
+    let mut callee_stack = caller_stack
+        .gather_captures(engine_state, &block.captures)
+        .reset_pipes();
+
+    if let Some(path) = module_file {
+        // Set the currently evaluated directory (file-relative PWD)
+        if let Some(parent) = path.parent() {
+            let file_pwd = Value::string(parent.to_string_lossy(), head);
+            callee_stack.add_env_var("FILE_PWD".to_string(), file_pwd);
+        }
+        let module_file_path = Value::string(path.to_string_lossy(), head);
+        callee_stack.add_env_var("CURRENT_FILE".to_string(), module_file_path);
+    }
+
+    let eval_block = get_eval_block(engine_state);
+
+    // Run the block (discard the result)
+    let _ = eval_block(engine_state, &mut callee_stack, block, input)?;
+
+    // Merge the block's environment to the current stack
+    redirect_env(engine_state, caller_stack, &callee_stack);
+    Ok(())
+}
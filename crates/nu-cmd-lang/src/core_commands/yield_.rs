@@ -0,0 +1,87 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+
+#[derive(Clone)]
+pub struct Yield;
+
+impl Command for Yield {
+    fn name(&self) -> &str {
+        "yield"
+    }
+
+    fn description(&self) -> &str {
+        "Add a value to the output of a generator."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Generators are custom commands defined with `def --generator`. Their output is a stream of \
+        the values they yield, and their body only runs as far as the stream is read: each `yield` \
+        waits for the previous value to be taken. `yield` can be used in the loops and conditionals \
+        of the body, but not in closures."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("yield")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required("value", SyntaxShape::Any, "The value to add to the output.")
+            .category(Category::Core)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["generator", "stream", "iterator"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let value: Value = call.req(engine_state, stack, 0)?;
+        let Some(sender) = &stack.yield_to else {
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    "`yield` outside of a generator",
+                    "can only be used in the body of a generator",
+                    call.head,
+                )
+                .with_help("define the command with `def --generator`"),
+            ));
+        };
+        // The stream was dropped, so nothing needs the next values
+        sender
+            .send(value)
+            .map_err(|_| ShellError::Interrupted { span: call.head })?;
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Stream the lines of a file that contain errors, as they are found.",
+                example: "def --generator errors [file: path] { for line in (open --raw $file | lines) { if ($line =~ "error") { yield $line } } }",
+                result: None,
+            },
+            Example {
+                description: "Generate an endless stream and take the first values from it.",
+                example: "def --generator naturals [] { mut n = 0; loop { yield $n; $n += 1 } }; naturals | first 3",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(0),
+                    Value::test_int(1),
+                    Value::test_int(2),
+                ])),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(Yield)
+    }
+}
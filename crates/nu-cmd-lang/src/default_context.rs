@@ -26,6 +26,7 @@ pub fn add_default_context(mut engine_state: EngineState) -> EngineState {
             AttrDeprecated,
             AttrExample,
             AttrSearchTerms,
+            AttrTest,
             Break,
             Collect,
             Const,
@@ -54,6 +55,7 @@ pub fn add_default_context(mut engine_state: EngineState) -> EngineState {
             OverlayList,
             OverlayNew,
             OverlayHide,
+            OverlayExport,
             Let,
             Loop,
             Match,
@@ -64,6 +66,7 @@ pub fn add_default_context(mut engine_state: EngineState) -> EngineState {
             ScopeAliases,
             ScopeCommands,
             ScopeEngineStats,
+            ScopeExternalsPathCache,
             ScopeExterns,
             ScopeModules,
             ScopeVariables,
@@ -71,6 +74,7 @@ pub fn add_default_context(mut engine_state: EngineState) -> EngineState {
             Use,
             Version,
             While,
+            Yield,
         };
 
         working_set.render()
@@ -97,3 +97,22 @@ pub fn test_deprecated_attribute_since_remove() {
     assert!(label.contains("0.10000.0"));
     assert!(label.contains("1.0"));
 }
+
+#[test]
+pub fn test_deprecated_attribute_replacement() {
+    let engine_state = nu_cmd_lang::create_default_context();
+    let mut working_set = StateWorkingSet::new(&engine_state);
+
+    let source = br#"
+    @deprecated "It was renamed." --flag all --replacement "--every"
+    def old-command [--all, --every] {}
+    old-command --all
+    "#;
+    let _ = parse(&mut working_set, None, source, false);
+
+    assert!(working_set.parse_errors.is_empty());
+    assert!(!working_set.parse_warnings.is_empty());
+
+    let help = &working_set.parse_warnings[0].help().unwrap().to_string();
+    assert_eq!(help, "Use `--every` instead.\nIt was renamed.");
+}
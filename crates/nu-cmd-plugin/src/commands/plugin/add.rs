@@ -1,11 +1,7 @@
-use crate::util::{get_plugin_dirs, modify_plugin_file};
+use crate::util::{add_plugin_to_registry, get_plugin_dirs};
 use nu_engine::command_prelude::*;
-use nu_plugin_engine::{GetPlugin, PersistentPlugin};
-use nu_protocol::{
-    PluginGcConfig, PluginIdentity, PluginRegistryItem, RegisteredPlugin,
-    shell_error::generic::GenericError, shell_error::io::IoError,
-};
-use std::{path::PathBuf, sync::Arc};
+use nu_protocol::{PluginIdentity, shell_error::generic::GenericError, shell_error::io::IoError};
+use std::path::PathBuf;
 
 #[derive(Clone)]
 pub struct PluginAdd;
@@ -115,25 +111,7 @@ apparent the next time `nu` is next launched with that plugin registry file.
 
         let custom_path = call.get_flag(engine_state, stack, "plugin-config")?;
 
-        // Start the plugin manually, to get the freshest signatures and to not affect engine
-        // state. Provide a GC config that will stop it ASAP
-        let plugin = Arc::new(PersistentPlugin::new(
-            identity,
-            PluginGcConfig {
-                enabled: true,
-                stop_after: 0,
-            },
-        ));
-        let interface = plugin.clone().get_plugin(Some((engine_state, stack)))?;
-        let metadata = interface.get_metadata()?;
-        let commands = interface.get_signature()?;
-
-        modify_plugin_file(engine_state, stack, call.head, &custom_path, |contents| {
-            // Update the file with the received metadata and signatures
-            let item = PluginRegistryItem::new(plugin.identity(), metadata, commands);
-            contents.upsert_plugin(item);
-            Ok(())
-        })?;
+        add_plugin_to_registry(engine_state, stack, call.head, identity, &custom_path)?;
 
         Ok(Value::nothing(call.head).into_pipeline_data())
     }
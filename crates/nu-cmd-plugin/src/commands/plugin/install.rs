@@ -0,0 +1,192 @@
+use crate::util::{add_plugin_to_registry, cargo_bin_dir, cargo_command, cargo_spawn_error};
+use nu_engine::command_prelude::*;
+use nu_protocol::{PluginIdentity, shell_error::generic::GenericError};
+
+#[derive(Clone)]
+pub struct PluginInstall;
+
+impl Command for PluginInstall {
+    fn name(&self) -> &str {
+        "plugin install"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_type(Type::Nothing, Type::Nothing)
+            // This matches the option to `nu`
+            .named(
+                "plugin-config",
+                SyntaxShape::Filepath,
+                "Use a plugin registry file other than the one set in `$nu.plugin-path`.",
+                None,
+            )
+            .named(
+                "version",
+                SyntaxShape::String,
+                "Install this version of the plugin instead of the latest one.",
+                Some('v'),
+            )
+            .switch(
+                "prebuilt",
+                "Download a prebuilt binary with `cargo binstall` instead of compiling the plugin.",
+                Some('p'),
+            )
+            .required(
+                "name",
+                SyntaxShape::String,
+                "The name of the plugin, with or without the `nu_plugin_` prefix.",
+            )
+            .category(Category::Plugin)
+    }
+
+    fn description(&self) -> &str {
+        "Install a plugin from crates.io and add it to the plugin registry file."
+    }
+
+    fn extra_description(&self) -> &str {
+        "
+This runs `cargo install` on the plugin's crate, or `cargo binstall` with
+`--prebuilt`, and then does the same thing as `plugin add` with the installed
+binary. `cargo` (and `cargo-binstall` for `--prebuilt`) must be in PATH.
+
+Like `plugin add`, this does not load the plugin commands into the scope - see
+`plugin use` for that.
+
+Use `plugin search` to find plugins to install.
+"
+        .trim()
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["cargo", "crates", "download", "package"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "plugin install gstat",
+                description: "Build and install the `nu_plugin_gstat` crate, and add the plugin to the registry.",
+                result: None,
+            },
+            Example {
+                example: "plugin install --prebuilt --version 0.100.0 nu_plugin_gstat",
+                description: "Install a prebuilt binary of a specific version of `nu_plugin_gstat`.",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let version: Option<String> = call.get_flag(engine_state, stack, "version")?;
+        let prebuilt = call.has_flag(engine_state, stack, "prebuilt")?;
+        let custom_path = call.get_flag(engine_state, stack, "plugin-config")?;
+
+        let crate_name = crate_name(&name.item);
+
+        let mut cargo = cargo_command(engine_state, stack)?;
+        cargo.args(install_args(&crate_name, version.as_deref(), prebuilt));
+
+        // Let cargo report its progress straight to the terminal
+        let status = cargo.status().map_err(|err| cargo_spawn_error(err, head))?;
+
+        if !status.success() {
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    format!("Failed to install `{crate_name}`"),
+                    format!("cargo {status}"),
+                    name.span,
+                )
+                .with_help("check the output of cargo above for details"),
+            ));
+        }
+
+        let filename = cargo_bin_dir(engine_state, stack)
+            .map(|dir| dir.join(format!("{crate_name}{}", std::env::consts::EXE_SUFFIX)))
+            .ok_or_else(|| {
+                ShellError::Generic(
+                    GenericError::new(
+                        "Could not find the installed plugin",
+                        "the cargo binary directory is unknown",
+                        head,
+                    )
+                    .with_help("set $env.CARGO_HOME, then run `plugin add` on the plugin"),
+                )
+            })?;
+
+        let identity = PluginIdentity::new(filename.clone(), None).map_err(|_| {
+            ShellError::Generic(GenericError::new(
+                "Plugin filename is invalid",
+                format!("installed as {}", filename.display()),
+                name.span,
+            ))
+        })?;
+
+        add_plugin_to_registry(engine_state, stack, head, identity, &custom_path)?;
+
+        Ok(PipelineData::empty())
+    }
+}
+
+/// The crate name for a plugin `name`, which may lack the `nu_plugin_` prefix.
+fn crate_name(name: &str) -> String {
+    if name.starts_with("nu_plugin_") {
+        name.to_owned()
+    } else {
+        format!("nu_plugin_{name}")
+    }
+}
+
+/// The arguments to pass to `cargo` to install `crate_name`.
+fn install_args<'a>(crate_name: &'a str, version: Option<&'a str>, prebuilt: bool) -> Vec<&'a str> {
+    let mut args = if prebuilt {
+        vec!["binstall", "--no-confirm"]
+    } else {
+        vec!["install"]
+    };
+    args.push(crate_name);
+    if let Some(version) = version {
+        args.extend(["--version", version]);
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crate_name_adds_prefix() {
+        assert_eq!(crate_name("gstat"), "nu_plugin_gstat");
+        assert_eq!(crate_name("nu_plugin_gstat"), "nu_plugin_gstat");
+    }
+
+    #[test]
+    fn install_args_compile() {
+        assert_eq!(
+            install_args("nu_plugin_gstat", None, false),
+            ["install", "nu_plugin_gstat"]
+        );
+    }
+
+    #[test]
+    fn install_args_prebuilt_version() {
+        assert_eq!(
+            install_args("nu_plugin_gstat", Some("0.100.0"), true),
+            [
+                "binstall",
+                "--no-confirm",
+                "nu_plugin_gstat",
+                "--version",
+                "0.100.0"
+            ]
+        );
+    }
+}
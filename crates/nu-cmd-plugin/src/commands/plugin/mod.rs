@@ -1,14 +1,20 @@
 use nu_engine::{command_prelude::*, get_full_help};
 
 mod add;
+mod install;
 mod list;
+mod reload;
 mod rm;
+mod search;
 mod stop;
 mod use_;
 
 pub use add::PluginAdd;
+pub use install::PluginInstall;
 pub use list::PluginList;
+pub use reload::PluginReload;
 pub use rm::PluginRm;
+pub use search::PluginSearch;
 pub use stop::PluginStop;
 pub use use_::PluginUse;
 
@@ -66,6 +72,11 @@ time.
                 description: "List installed plugins",
                 result: None,
             },
+            Example {
+                example: "plugin reload inc",
+                description: "Restart the `inc` plugin and load the commands it provides now.",
+                result: None,
+            },
             Example {
                 example: "plugin stop inc",
                 description: "Stop the plugin named `inc`.",
@@ -0,0 +1,197 @@
+use crate::util::{canonicalize_possible_filename_arg, modify_plugin_file, read_plugin_file};
+use nu_engine::command_prelude::*;
+use nu_plugin_engine::{PersistentPlugin, respawn_plugin};
+use nu_protocol::{
+    PluginIdentity, PluginRegistryItem, PluginSignature, RegisteredPlugin, engine::CommandType,
+    shell_error::generic::GenericError,
+};
+
+#[derive(Clone)]
+pub struct PluginReload;
+
+impl Command for PluginReload {
+    fn name(&self) -> &str {
+        "plugin reload"
+    }
+
+    fn description(&self) -> &str {
+        "Re-spawn a loaded plugin and replace its commands with the ones it provides now."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .named(
+                "plugin-config",
+                SyntaxShape::Filepath,
+                "Use a plugin registry file other than the one set in `$nu.plugin-path`.",
+                None,
+            )
+            .required(
+                "name",
+                SyntaxShape::String,
+                "The name, or filename, of the plugin to reload.",
+            )
+            .category(Category::Plugin)
+    }
+
+    fn extra_description(&self) -> &str {
+        "
+This command is a parser keyword. For details, check:
+  https://www.nushell.sh/book/thinking_in_nu.html
+
+The plugin must already be loaded. It is stopped and started again, and its
+signatures are requested anew, so a rebuilt plugin binary can be picked up
+without restarting the shell.
+
+If the plugin is in the plugin registry file, its entry is updated with the new
+signatures. Like `plugin use`, the commands are loaded from that entry when
+`plugin reload` is parsed, before the plugin is restarted. If the restarted
+plugin provides other commands than the ones in scope, `plugin reload` fails:
+run `plugin use` to load the updated entry, or `plugin add` a plugin that isn't
+in the registry file yet.
+"
+        .trim()
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["restart", "refresh", "signature", "update"]
+    }
+
+    fn command_type(&self) -> CommandType {
+        CommandType::Keyword
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        // The parser only loaded the signatures from the registry file, the plugin is restarted
+        // here so that parsing, e.g. for highlighting, never touches it
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let custom_path = call.get_flag(engine_state, stack, "plugin-config")?;
+
+        let filename = canonicalize_possible_filename_arg(engine_state, stack, &name.item);
+
+        let plugin = engine_state
+            .plugins()
+            .iter()
+            .find(|plugin| {
+                let id = plugin.identity();
+                id.name() == name.item || id.filename() == filename
+            })
+            .cloned()
+            .ok_or_else(|| {
+                ShellError::Generic(
+                    GenericError::new(
+                        format!("Failed to reload the `{}` plugin", name.item),
+                        "couldn't find a loaded plugin with this name",
+                        name.span,
+                    )
+                    .with_help("you may need to `plugin use` the plugin first"),
+                )
+            })?;
+
+        let plugin: std::sync::Arc<PersistentPlugin> =
+            plugin
+                .as_any()
+                .downcast()
+                .map_err(|_| ShellError::NushellFailed {
+                    msg: "encountered unexpected RegisteredPlugin type".into(),
+                })?;
+        let (metadata, commands) = respawn_plugin(plugin.clone(), engine_state, stack)?;
+        let changed = !same_commands(engine_state, plugin.identity(), &commands);
+
+        let registered = read_plugin_file(engine_state, stack, call.head, &custom_path)?
+            .plugins
+            .iter()
+            .any(|item| {
+                item.filename == plugin.identity().filename()
+                    && item.shell.as_deref() == plugin.identity().shell()
+            });
+
+        if registered {
+            modify_plugin_file(engine_state, stack, call.head, &custom_path, |contents| {
+                let item = PluginRegistryItem::new(plugin.identity(), metadata, commands);
+                contents.upsert_plugin(item);
+                Ok(())
+            })?;
+        }
+
+        if changed {
+            let plugin_name = plugin.identity().name();
+            let help = if registered {
+                format!(
+                    "the plugin registry file was updated, run `plugin use {plugin_name}` to load them"
+                )
+            } else {
+                format!(
+                    "`{plugin_name}` isn't in the plugin registry file, add it with `plugin add` to load them"
+                )
+            };
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    format!("The commands of the `{plugin_name}` plugin changed"),
+                    "the plugin was restarted, but the commands in scope are the old ones",
+                    call.head,
+                )
+                .with_help(help),
+            ));
+        }
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Reload the `query` plugin after rebuilding it",
+                example: "plugin reload query",
+                result: None,
+            },
+            Example {
+                description: "Reload the plugin with the filename `~/.cargo/bin/nu_plugin_query`",
+                example: "plugin reload ~/.cargo/bin/nu_plugin_query",
+                result: None,
+            },
+            Example {
+                description: "Reload the `query` plugin, updating its entry in a custom plugin registry file",
+                example: "plugin reload --plugin-config local-plugins.msgpackz query",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Whether the commands in scope for the plugin are the ones it provides now.
+fn same_commands(
+    engine_state: &EngineState,
+    identity: &PluginIdentity,
+    commands: &[PluginSignature],
+) -> bool {
+    let mut declared = engine_state
+        .get_decls_sorted(false)
+        .into_iter()
+        .map(|(_, decl_id)| engine_state.get_decl(decl_id))
+        .filter(|decl| decl.plugin_identity() == Some(identity))
+        .map(|decl| decl.signature())
+        .collect::<Vec<_>>();
+    declared.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut provided = commands
+        .iter()
+        .map(|command| &command.sig)
+        .collect::<Vec<_>>();
+    provided.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // The equality of signatures leaves out their flags and types
+    declared.len() == provided.len()
+        && declared.iter().zip(provided).all(|(declared, provided)| {
+            *declared == *provided
+                && declared.named == provided.named
+                && declared.input_output_types == provided.input_output_types
+        })
+}
@@ -0,0 +1,198 @@
+use crate::util::{cargo_command, cargo_spawn_error};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+
+/// The most results `cargo search` will return.
+const MAX_LIMIT: i64 = 100;
+
+#[derive(Clone)]
+pub struct PluginSearch;
+
+impl Command for PluginSearch {
+    fn name(&self) -> &str {
+        "plugin search"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_type(
+                Type::Nothing,
+                Type::Table(
+                    vec![
+                        ("name".into(), Type::String),
+                        ("version".into(), Type::String),
+                        ("description".into(), Type::String),
+                    ]
+                    .into(),
+                ),
+            )
+            .named(
+                "limit",
+                SyntaxShape::Int,
+                "Maximum number of results to show (at most 100, default 10).",
+                Some('l'),
+            )
+            .optional(
+                "query",
+                SyntaxShape::String,
+                "The plugin name to search for, with or without the `nu_plugin_` prefix.",
+            )
+            .category(Category::Plugin)
+    }
+
+    fn description(&self) -> &str {
+        "Search crates.io for plugins that can be installed."
+    }
+
+    fn extra_description(&self) -> &str {
+        "
+This runs `cargo search` for `nu_plugin_<query>`, so `cargo` must be in PATH.
+Only crates named `nu_plugin_*` are listed. Install a result with
+`plugin install`.
+"
+        .trim()
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["find", "crates", "registry", "discover"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "plugin search",
+                description: "List plugins published on crates.io.",
+                result: None,
+            },
+            Example {
+                example: "plugin search --limit 20 git",
+                description: "Find up to 20 plugins with `git` in their name.",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let query: Option<String> = call.opt(engine_state, stack, 0)?;
+        let limit: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "limit")?;
+
+        let limit = match limit {
+            Some(limit) if !(1..=MAX_LIMIT).contains(&limit.item) => {
+                return Err(ShellError::IncorrectValue {
+                    msg: format!("must be between 1 and {MAX_LIMIT}"),
+                    val_span: limit.span,
+                    call_span: head,
+                });
+            }
+            Some(limit) => limit.item,
+            None => 10,
+        };
+
+        let output = cargo_command(engine_state, stack)?
+            .args(["search", "--color", "never", "--limit"])
+            .arg(limit.to_string())
+            .arg(search_term(query.as_deref()))
+            .output()
+            .map_err(|err| cargo_spawn_error(err, head))?;
+
+        if !output.status.success() {
+            return Err(ShellError::Generic(
+                GenericError::new("`cargo search` failed", output.status.to_string(), head)
+                    .with_help(String::from_utf8_lossy(&output.stderr).trim().to_owned()),
+            ));
+        }
+
+        let results = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(parse_search_line)
+            .filter(|(name, _, _)| name.starts_with("nu_plugin_"))
+            .map(|(name, version, description)| {
+                Value::record(
+                    record! {
+                        "name" => Value::string(name, head),
+                        "version" => Value::string(version, head),
+                        "description" => Value::string(description, head),
+                    },
+                    head,
+                )
+            })
+            .collect();
+
+        Ok(Value::list(results, head).into_pipeline_data())
+    }
+}
+
+/// The term to pass to `cargo search` for the user's `query`.
+fn search_term(query: Option<&str>) -> String {
+    match query.map(|query| query.strip_prefix("nu_plugin_").unwrap_or(query)) {
+        Some(query) if !query.is_empty() => format!("nu_plugin_{query}"),
+        _ => "nu_plugin".into(),
+    }
+}
+
+/// Parse a line of `cargo search` output, which looks like `name = "version"    # description`.
+fn parse_search_line(line: &str) -> Option<(&str, &str, &str)> {
+    let (name, rest) = line.split_once(" = \"")?;
+    let (version, rest) = rest.split_once('"')?;
+    let description = rest
+        .trim_start()
+        .strip_prefix('#')
+        .map(str::trim)
+        .unwrap_or_default();
+    Some((name.trim(), version, description))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_term_adds_prefix() {
+        assert_eq!(search_term(Some("git")), "nu_plugin_git");
+        assert_eq!(search_term(Some("nu_plugin_git")), "nu_plugin_git");
+    }
+
+    #[test]
+    fn search_term_without_query() {
+        assert_eq!(search_term(None), "nu_plugin");
+        assert_eq!(search_term(Some("")), "nu_plugin");
+    }
+
+    #[test]
+    fn parse_search_line_with_description() {
+        assert_eq!(
+            parse_search_line(
+                r#"nu_plugin_gstat = "0.100.0"    # A git status plugin for Nushell"#
+            ),
+            Some((
+                "nu_plugin_gstat",
+                "0.100.0",
+                "A git status plugin for Nushell"
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_search_line_without_description() {
+        assert_eq!(
+            parse_search_line(r#"nu_plugin_foo = "1.2.3""#),
+            Some(("nu_plugin_foo", "1.2.3", ""))
+        );
+    }
+
+    #[test]
+    fn parse_search_line_ignores_other_output() {
+        assert_eq!(
+            parse_search_line("... and 42 crates more (use --limit N to see more)"),
+            None
+        );
+        assert_eq!(parse_search_line(""), None);
+    }
+}
@@ -14,8 +14,11 @@ pub fn add_plugin_command_context(mut engine_state: EngineState) -> EngineState
         bind_command!(
             PluginAdd,
             PluginCommand,
+            PluginInstall,
             PluginList,
+            PluginReload,
             PluginRm,
+            PluginSearch,
             PluginStop,
             PluginUse,
         );
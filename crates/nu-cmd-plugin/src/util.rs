@@ -1,6 +1,7 @@
 use nu_engine::command_prelude::*;
+use nu_plugin_engine::{GetPlugin, PersistentPlugin};
 use nu_protocol::{
-    PluginRegistryFile,
+    PluginGcConfig, PluginIdentity, PluginRegistryFile, PluginRegistryItem, RegisteredPlugin,
     engine::StateWorkingSet,
     shell_error::{
         self,
@@ -11,6 +12,7 @@ use nu_protocol::{
 use std::{
     fs::{self, File},
     path::PathBuf,
+    sync::Arc,
 };
 
 fn get_plugin_registry_file_path(
@@ -114,6 +116,37 @@ pub(crate) fn modify_plugin_file(
     Ok(())
 }
 
+/// Run the plugin to get its metadata and signatures, and add them to the plugin registry file.
+pub(crate) fn add_plugin_to_registry(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    span: Span,
+    identity: PluginIdentity,
+    custom_path: &Option<Spanned<String>>,
+) -> Result<(), ShellError> {
+    // Start the plugin manually, to get the freshest signatures and to not affect engine
+    // state. Provide a GC config that will stop it ASAP
+    let plugin = Arc::new(PersistentPlugin::new(
+        identity,
+        PluginGcConfig {
+            enabled: true,
+            stop_after: 0,
+        },
+    ));
+    let interface = plugin.clone().get_plugin(Some((engine_state, stack)))?;
+    let metadata = interface
+        .get_metadata()?
+        .with_protocol_version(interface.protocol_info()?.version.clone());
+    let commands = interface.get_signature()?;
+
+    modify_plugin_file(engine_state, stack, span, custom_path, |contents| {
+        // Update the file with the received metadata and signatures
+        let item = PluginRegistryItem::new(plugin.identity(), metadata, commands);
+        contents.upsert_plugin(item);
+        Ok(())
+    })
+}
+
 pub(crate) fn canonicalize_possible_filename_arg(
     engine_state: &EngineState,
     stack: &Stack,
@@ -156,3 +189,71 @@ pub(crate) fn get_plugin_dirs(
 
     dirs_from_const.chain(dirs_from_env)
 }
+
+/// Create a `cargo` command that runs with the environment and working directory of the `stack`.
+pub(crate) fn cargo_command(
+    engine_state: &EngineState,
+    stack: &Stack,
+) -> Result<std::process::Command, ShellError> {
+    let mut command = std::process::Command::new("cargo");
+    command
+        .envs(nu_engine::env::env_to_strings(engine_state, stack)?)
+        .current_dir(engine_state.cwd(Some(stack))?);
+    Ok(command)
+}
+
+/// Turn a failure to spawn `cargo` into a helpful error.
+pub(crate) fn cargo_spawn_error(err: std::io::Error, span: Span) -> ShellError {
+    if err.kind() == std::io::ErrorKind::NotFound {
+        ShellError::Generic(
+            GenericError::new(
+                "Could not find `cargo`",
+                "this command needs the Rust toolchain",
+                span,
+            )
+            .with_help("install Rust from https://rustup.rs and make sure `cargo` is in PATH"),
+        )
+    } else {
+        ShellError::Io(IoError::new(err, span, PathBuf::from("cargo")))
+    }
+}
+
+/// The directory that `cargo install` puts binaries into.
+pub(crate) fn cargo_bin_dir(engine_state: &EngineState, stack: &Stack) -> Option<PathBuf> {
+    let env_path = |name: &str| {
+        stack
+            .get_env_var(engine_state, name)
+            .and_then(|value| value.coerce_str().ok())
+            .map(|path| PathBuf::from(path.as_ref()))
+    };
+
+    env_path("CARGO_INSTALL_ROOT")
+        .or_else(|| env_path("CARGO_HOME"))
+        .or_else(|| nu_path::home_dir().map(|home| home.join(".cargo").into_std_path_buf()))
+        .map(|root| root.join("bin"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cargo_bin_dir_prefers_install_root() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        stack.add_env_var("CARGO_HOME".into(), Value::test_string("/cargo-home"));
+        assert_eq!(
+            cargo_bin_dir(&engine_state, &stack),
+            Some(PathBuf::from("/cargo-home/bin"))
+        );
+
+        stack.add_env_var(
+            "CARGO_INSTALL_ROOT".into(),
+            Value::test_string("/install-root"),
+        );
+        assert_eq!(
+            cargo_bin_dir(&engine_state, &stack),
+            Some(PathBuf::from("/install-root/bin"))
+        );
+    }
+}
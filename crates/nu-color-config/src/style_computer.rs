@@ -30,6 +30,12 @@ pub struct StyleComputer<'a> {
     engine_state: &'a EngineState,
     stack: &'a Stack,
     map: StyleMapping,
+    // The `table.row_style` closure, computed for every row of a table.
+    row_style: Option<(Closure, Span)>,
+    // The `table.summary` closure, computed for collapsed nested values.
+    summary: Option<(Closure, Span)>,
+    // The `table.columns.<name>.color` styles.
+    column_styles: HashMap<String, Style>,
 }
 
 impl<'a> StyleComputer<'a> {
@@ -44,6 +50,9 @@ impl<'a> StyleComputer<'a> {
             engine_state,
             stack,
             map,
+            row_style: None,
+            summary: None,
+            column_styles: HashMap::new(),
         }
     }
     // The main method. Takes a string name which maps to a color_config style name,
@@ -54,25 +63,7 @@ impl<'a> StyleComputer<'a> {
             Some(ComputableStyle::Static(s)) => *s,
             // Closures are run here.
             Some(ComputableStyle::Closure(closure, span)) => {
-                let result = ClosureEvalOnce::new(self.engine_state, self.stack, closure.clone())
-                    .debug(false)
-                    .run_with_value(value.clone())
-                    .and_then(|data| data.into_value(*span));
-
-                match result {
-                    Ok(value) => {
-                        // These should be the same color data forms supported by color_config.
-                        match value {
-                            Value::Record { .. } => color_record_to_nustyle(&value),
-                            Value::String { val, .. } => lookup_ansi_color_style(&val),
-                            _ => Style::default(),
-                        }
-                    }
-                    Err(err) => {
-                        report_shell_error(Some(self.stack), self.engine_state, &err);
-                        Style::default()
-                    }
-                }
+                self.run_closure(closure, *span, value)
             }
             // There should be no other kinds of values (due to create_map() in config.rs filtering them out)
             // so this is just a fallback.
@@ -80,6 +71,55 @@ impl<'a> StyleComputer<'a> {
         }
     }
 
+    // Runs a user closure, reporting any error it produces.
+    fn eval_closure(&self, closure: &Closure, span: Span, value: &Value) -> Option<Value> {
+        let result = ClosureEvalOnce::new(self.engine_state, self.stack, closure.clone())
+            .debug(false)
+            .run_with_value(value.clone())
+            .and_then(|data| data.into_value(span));
+
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                report_shell_error(Some(self.stack), self.engine_state, &err);
+                None
+            }
+        }
+    }
+
+    // Runs a style closure, turning its output into a style.
+    fn run_closure(&self, closure: &Closure, span: Span, value: &Value) -> Style {
+        // These should be the same color data forms supported by color_config.
+        match self.eval_closure(closure, span, value) {
+            Some(value @ Value::Record { .. }) => color_record_to_nustyle(&value),
+            Some(Value::String { val, .. }) => lookup_ansi_color_style(&val),
+            _ => Style::default(),
+        }
+    }
+
+    // The style of a whole table row, given by the `table.row_style` closure.
+    // Returns None when there's no closure or it produced no style for the row.
+    pub fn compute_row(&self, row: &Value) -> Option<Style> {
+        let (closure, span) = self.row_style.as_ref()?;
+        let style = self.run_closure(closure, *span, row);
+        (!style.is_plain()).then_some(style)
+    }
+
+    // The text shown for a nested value collapsed by `table.columns.<name>.expand`,
+    // given by the `table.summary` closure.
+    pub fn compute_summary(&self, value: &Value) -> Option<String> {
+        let (closure, span) = self.summary.as_ref()?;
+        match self.eval_closure(closure, *span, value)? {
+            Value::String { val, .. } => Some(val),
+            _ => None,
+        }
+    }
+
+    // The style of a table column, given by `table.columns.<name>.color`.
+    pub fn compute_column(&self, column: &str) -> Option<Style> {
+        self.column_styles.get(column).copied()
+    }
+
     // Used only by the `table` command.
     pub fn style_primitive(&self, value: &Value) -> TextStyle {
         use Alignment::*;
@@ -172,7 +212,24 @@ impl<'a> StyleComputer<'a> {
                 _ => (),
             }
         }
-        StyleComputer::new(engine_state, stack, map)
+
+        let mut computer = StyleComputer::new(engine_state, stack, map);
+        if let Some(value @ Value::Closure { val, .. }) = &config.table.row_style {
+            computer.row_style = Some((*val.clone(), value.span()));
+        }
+        if let Some(value @ Value::Closure { val, .. }) = &config.table.summary {
+            computer.summary = Some((*val.clone(), value.span()));
+        }
+        for (name, column) in &config.table.columns {
+            let style = match &column.color {
+                Some(value @ Value::Record { .. }) => color_record_to_nustyle(value),
+                Some(Value::String { val, .. }) => lookup_ansi_color_style(val),
+                _ => continue,
+            };
+            computer.column_styles.insert(name.clone(), style);
+        }
+
+        computer
     }
 }
 
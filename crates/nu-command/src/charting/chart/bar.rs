@@ -0,0 +1,112 @@
+use super::{chart_style, chart_width, collect_points, draw_bars};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct ChartBar;
+
+impl Command for ChartBar {
+    fn name(&self) -> &str {
+        "chart bar"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("chart bar")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Number)), Type::String),
+                (Type::List(Box::new(Type::Filesize)), Type::String),
+                (Type::List(Box::new(Type::Duration)), Type::String),
+                (Type::table(), Type::String),
+                (Type::record(), Type::String),
+            ])
+            .optional(
+                "column",
+                SyntaxShape::String,
+                "The column of a table to plot.",
+            )
+            .named(
+                "label",
+                SyntaxShape::String,
+                "The column of a table to label the bars with (defaults to the row index).",
+                Some('l'),
+            )
+            .named(
+                "width",
+                SyntaxShape::Int,
+                "Width of the chart (defaults to the terminal width).",
+                Some('w'),
+            )
+            .named(
+                "color",
+                SyntaxShape::String,
+                "Color of the bars, as a color name like `green`.",
+                Some('c'),
+            )
+            .category(Category::Chart)
+    }
+
+    fn description(&self) -> &str {
+        "Render numbers as a horizontal bar chart."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Lists of numbers are labelled by their index, records by their keys. Bars are scaled to the largest value, which must not be negative."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["graph", "plot"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Render a bar chart of a record",
+                example: "{apples: 4, pears: 2} | chart bar --width 18",
+                result: Some(Value::test_string("apples │████████ 4\npears  │████ 2")),
+            },
+            Example {
+                description: "Render the size of files as a bar chart",
+                example: "ls | chart bar size --label name --color cyan",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let column: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let label: Option<Spanned<String>> = call.get_flag(engine_state, stack, "label")?;
+        let width = chart_width(engine_state, stack, call)?;
+        let style = chart_style(engine_state, stack, call)?;
+        let config = stack.get_config(engine_state);
+
+        let points = collect_points(input, column.as_ref(), label.as_ref(), &config, head)?;
+        if let Some(point) = points.iter().find(|point| point.value < 0.0) {
+            return Err(ShellError::IncorrectValue {
+                msg: format!(
+                    "bars can't be negative, but `{}` is {}",
+                    point.label, point.text
+                ),
+                val_span: head,
+                call_span: head,
+            });
+        }
+
+        Ok(Value::string(draw_bars(&points, width, style), head).into_pipeline_data())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ChartBar)
+    }
+}
@@ -0,0 +1,174 @@
+use super::{Point, chart_style, chart_width, collect_points, draw_bars, positive_flag};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct ChartHistogram;
+
+impl Command for ChartHistogram {
+    fn name(&self) -> &str {
+        "chart histogram"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("chart histogram")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Number)), Type::String),
+                (Type::List(Box::new(Type::Filesize)), Type::String),
+                (Type::List(Box::new(Type::Duration)), Type::String),
+                (Type::table(), Type::String),
+            ])
+            .optional(
+                "column",
+                SyntaxShape::String,
+                "The column of a table to plot.",
+            )
+            .named(
+                "bins",
+                SyntaxShape::Int,
+                "Number of equally wide ranges the values are counted in (defaults to 10).",
+                Some('b'),
+            )
+            .named(
+                "width",
+                SyntaxShape::Int,
+                "Width of the chart (defaults to the terminal width).",
+                Some('w'),
+            )
+            .named(
+                "color",
+                SyntaxShape::String,
+                "Color of the bars, as a color name like `green`.",
+                Some('c'),
+            )
+            .category(Category::Chart)
+    }
+
+    fn description(&self) -> &str {
+        "Render the distribution of numbers as a histogram."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The values are counted in ranges of equal width between the smallest and the largest value, each drawn as a bar. Use `histogram` to count distinct values instead."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["graph", "plot", "distribution"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Render a histogram of a list of numbers in 3 ranges",
+                example: "[1 2 2 3 3 3 4] | chart histogram --bins 3 --width 20",
+                result: Some(Value::test_string(
+                    "1..2 │███ 1\n2..3 │██████ 2\n3..4 │████████████ 4",
+                )),
+            },
+            Example {
+                description: "Render the distribution of file sizes",
+                example: "ls | chart histogram size",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let column: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let bins = positive_flag(engine_state, stack, call, "bins")?.unwrap_or(10);
+        let width = chart_width(engine_state, stack, call)?;
+        let style = chart_style(engine_state, stack, call)?;
+        let config = stack.get_config(engine_state);
+
+        let points = collect_points(input, column.as_ref(), None, &config, head)?;
+        let values = points.iter().map(|point| point.value).collect::<Vec<_>>();
+        let bins = count_bins(&values, bins);
+
+        Ok(Value::string(draw_bars(&bins, width, style), head).into_pipeline_data())
+    }
+}
+
+/// Counts the values in `bins` ranges of equal width, as bars labelled by their range.
+fn count_bins(values: &[f64], bins: usize) -> Vec<Point> {
+    if values.is_empty() {
+        return Vec::new();
+    }
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    // All the values fall in a single range when they are equal
+    let bins = if max > min { bins } else { 1 };
+    let bin_width = (max - min) / bins as f64;
+
+    let mut counts = vec![0usize; bins];
+    for value in values {
+        let bin = if bin_width > 0.0 {
+            ((value - min) / bin_width) as usize
+        } else {
+            0
+        };
+        // The largest value closes the last range
+        counts[bin.min(bins - 1)] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(bin, count)| {
+            let start = min + bin as f64 * bin_width;
+            let end = if bin + 1 == bins {
+                max
+            } else {
+                start + bin_width
+            };
+            Point {
+                label: format!("{}..{}", format_bound(start), format_bound(end)),
+                value: count as f64,
+                text: count.to_string(),
+            }
+        })
+        .collect()
+}
+
+fn format_bound(bound: f64) -> String {
+    if bound.fract() == 0.0 {
+        format!("{bound:.0}")
+    } else {
+        let bound = format!("{bound:.2}");
+        bound
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ChartHistogram)
+    }
+
+    #[test]
+    fn equal_values_fall_in_one_bin() {
+        let bins = count_bins(&[2.0, 2.0, 2.0], 5);
+        assert_eq!(bins.len(), 1);
+        assert_eq!(bins[0].label, "2..2");
+        assert_eq!(bins[0].value, 3.0);
+    }
+
+    #[test]
+    fn bounds_are_trimmed() {
+        assert_eq!(format_bound(1.5), "1.5");
+        assert_eq!(format_bound(-3.0), "-3");
+    }
+}
@@ -0,0 +1,209 @@
+mod bar;
+mod chart_;
+mod histogram;
+mod sparkline;
+
+pub use bar::ChartBar;
+pub use chart_::Chart;
+pub use histogram::ChartHistogram;
+pub use sparkline::ChartSparkline;
+
+use itertools::Itertools;
+use nu_ansi_term::Style;
+use nu_color_config::lookup_ansi_color_style;
+use nu_engine::command_prelude::*;
+use nu_utils::terminal_size;
+use unicode_width::UnicodeWidthStr;
+
+/// The eighth blocks drawing the end of a horizontal bar, from 1/8 to 7/8.
+const PARTIAL_BARS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// A labelled value of a chart.
+struct Point {
+    label: String,
+    value: f64,
+    /// The value as it is displayed next to its bar.
+    text: String,
+}
+
+/// The number a chart plots for a value.
+fn value_to_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int { val, .. } => Some(*val as f64),
+        Value::Float { val, .. } => Some(*val),
+        Value::Filesize { val, .. } => Some(val.get() as f64),
+        Value::Duration { val, .. } => Some(*val as f64),
+        _ => None,
+    }
+}
+
+fn to_point(
+    label: String,
+    value: &Value,
+    config: &Config,
+    head: Span,
+) -> Result<Point, ShellError> {
+    if let Value::Error { error, .. } = value {
+        return Err(*error.clone());
+    }
+
+    match value_to_number(value) {
+        Some(number) if number.is_finite() => Ok(Point {
+            label,
+            value: number,
+            text: value.to_abbreviated_string(config),
+        }),
+        _ => Err(ShellError::UnsupportedInput {
+            msg: "Only numbers, file sizes and durations can be charted.".into(),
+            input: format!("input type: {}", value.get_type()),
+            msg_span: head,
+            input_span: value.span(),
+        }),
+    }
+}
+
+/// Collects the points to chart from the input.
+///
+/// Lists of numbers are labelled by their index, tables take their values from `column` and
+/// their labels from the `label` column, records are labelled by their keys.
+fn collect_points(
+    input: PipelineData,
+    column: Option<&Spanned<String>>,
+    label: Option<&Spanned<String>>,
+    config: &Config,
+    head: Span,
+) -> Result<Vec<Point>, ShellError> {
+    let value = input.into_value(head)?;
+    let span = value.span();
+    match value {
+        Value::Record { val, .. } => val
+            .iter()
+            .map(|(key, value)| to_point(key.clone(), value, config, head))
+            .collect(),
+        Value::List { vals, .. } => {
+            let mut points = Vec::with_capacity(vals.len());
+            for (index, item) in vals.iter().enumerate() {
+                let Some(column) = column else {
+                    points.push(to_point(index.to_string(), item, config, head)?);
+                    continue;
+                };
+
+                let value = item
+                    .as_record()
+                    .ok()
+                    .and_then(|record| record.get(&column.item));
+                let Some(value) = value else {
+                    return Err(ShellError::CantFindColumn {
+                        col_name: column.item.clone(),
+                        span: Some(column.span),
+                        src_span: item.span(),
+                    });
+                };
+                let label = label
+                    .and_then(|label| item.as_record().ok()?.get(&label.item))
+                    .map(|label| label.to_abbreviated_string(config))
+                    .unwrap_or_else(|| index.to_string());
+                points.push(to_point(label, value, config, head)?);
+            }
+            Ok(points)
+        }
+        Value::Error { error, .. } => Err(*error),
+        other => Err(ShellError::OnlySupportsThisInputType {
+            exp_input_type: "list, table or record".into(),
+            wrong_type: other.get_type().to_string(),
+            dst_span: head,
+            src_span: span,
+        }),
+    }
+}
+
+/// The largest `--bins`, `--width` or `--height`, so a mistyped one can't allocate without bound.
+const MAX_FLAG_VALUE: usize = 10_000;
+
+/// A flag that must be a positive number, up to [`MAX_FLAG_VALUE`].
+fn positive_flag(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    name: &str,
+) -> Result<Option<usize>, ShellError> {
+    let Some(value) = call.get_flag::<Spanned<i64>>(engine_state, stack, name)? else {
+        return Ok(None);
+    };
+
+    match usize::try_from(value.item) {
+        Ok(n) if n > 0 && n <= MAX_FLAG_VALUE => Ok(Some(n)),
+        _ => Err(ShellError::IncorrectValue {
+            msg: format!("--{name} must be a positive number up to {MAX_FLAG_VALUE}"),
+            val_span: value.span,
+            call_span: call.head,
+        }),
+    }
+}
+
+/// The `--width` of a chart, the terminal width by default.
+fn chart_width(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+) -> Result<usize, ShellError> {
+    let width = positive_flag(engine_state, stack, call, "width")?;
+    Ok(width.unwrap_or_else(|| terminal_size().map_or(80, |(w, _)| w as usize)))
+}
+
+/// The `--color` of the chart marks, unless ANSI coloring is disabled.
+fn chart_style(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+) -> Result<Option<Style>, ShellError> {
+    let color: Option<String> = call.get_flag(engine_state, stack, "color")?;
+    let use_color = stack
+        .get_config(engine_state)
+        .use_ansi_coloring
+        .get(engine_state);
+
+    Ok(color
+        .filter(|_| use_color)
+        .map(|color| lookup_ansi_color_style(&color)))
+}
+
+fn paint(text: String, style: Option<Style>) -> String {
+    match style {
+        Some(style) => style.paint(text).to_string(),
+        None => text,
+    }
+}
+
+/// A horizontal bar of `value` out of `max` in `width` columns.
+fn bar(value: f64, max: f64, width: usize) -> String {
+    if max <= 0.0 || value <= 0.0 {
+        return String::new();
+    }
+
+    let eighths = (value / max * (width * 8) as f64).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if eighths % 8 > 0 {
+        bar.push(PARTIAL_BARS[eighths % 8 - 1]);
+    }
+
+    bar
+}
+
+/// Draws one horizontal bar per point, as `label │████▌ value` lines fitting in `width`.
+fn draw_bars(points: &[Point], width: usize, style: Option<Style>) -> String {
+    let label_width = points.iter().map(|p| p.label.width()).max().unwrap_or(0);
+    let text_width = points.iter().map(|p| p.text.width()).max().unwrap_or(0);
+    // The label, the ` │` separator, then the bar and a space before the value
+    let bar_width = width.saturating_sub(label_width + text_width + 3).max(1);
+    let max = points.iter().map(|p| p.value).fold(0.0, f64::max);
+
+    points
+        .iter()
+        .map(|point| {
+            let padding = " ".repeat(label_width - point.label.width());
+            let bar = paint(bar(point.value, max, bar_width), style);
+            format!("{}{padding} │{bar} {}", point.label, point.text)
+        })
+        .join("\n")
+}
@@ -0,0 +1,166 @@
+use super::{chart_style, chart_width, collect_points, paint, positive_flag};
+use itertools::Itertools;
+use nu_engine::command_prelude::*;
+
+/// The blocks of a sparkline, from the lowest to the highest.
+const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Clone)]
+pub struct ChartSparkline;
+
+impl Command for ChartSparkline {
+    fn name(&self) -> &str {
+        "chart sparkline"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("chart sparkline")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Number)), Type::String),
+                (Type::List(Box::new(Type::Filesize)), Type::String),
+                (Type::List(Box::new(Type::Duration)), Type::String),
+                (Type::table(), Type::String),
+            ])
+            .optional(
+                "column",
+                SyntaxShape::String,
+                "The column of a table to plot.",
+            )
+            .named(
+                "width",
+                SyntaxShape::Int,
+                "Maximum width of the sparkline, values are averaged to fit (defaults to the terminal width).",
+                Some('w'),
+            )
+            .named(
+                "height",
+                SyntaxShape::Int,
+                "Number of lines of the sparkline (defaults to 1).",
+                Some('H'),
+            )
+            .named(
+                "color",
+                SyntaxShape::String,
+                "Color of the sparkline, as a color name like `green`.",
+                Some('c'),
+            )
+            .category(Category::Chart)
+    }
+
+    fn description(&self) -> &str {
+        "Render a list of numbers as a unicode sparkline."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["graph", "plot", "trend"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Render a sparkline of a list of numbers",
+                example: "[1 2 3 4 5 6 7 8] | chart sparkline",
+                result: Some(Value::test_string("▁▂▃▄▅▆▇█")),
+            },
+            Example {
+                description: "Render a sparkline on two lines",
+                example: "[0 4 8 12 16] | chart sparkline --height 2",
+                result: Some(Value::test_string("  ▁▄█\n▁▅███")),
+            },
+            Example {
+                description: "Render a sparkline of a table column, averaged to 4 characters",
+                example: "ls | chart sparkline size --width 4",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let column: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let width = chart_width(engine_state, stack, call)?;
+        let height = positive_flag(engine_state, stack, call, "height")?.unwrap_or(1);
+        let style = chart_style(engine_state, stack, call)?;
+        let config = stack.get_config(engine_state);
+
+        let points = collect_points(input, column.as_ref(), None, &config, head)?;
+        let values = points.iter().map(|point| point.value).collect::<Vec<_>>();
+        let lines = sparkline(&fit(&values, width), height);
+        let text = lines.into_iter().map(|line| paint(line, style)).join("\n");
+
+        Ok(Value::string(text, head).into_pipeline_data())
+    }
+}
+
+/// Averages consecutive values so at most `width` are left.
+fn fit(values: &[f64], width: usize) -> Vec<f64> {
+    if values.len() <= width {
+        return values.to_vec();
+    }
+
+    (0..width)
+        .map(|i| {
+            let bucket = &values[i * values.len() / width..(i + 1) * values.len() / width];
+            bucket.iter().sum::<f64>() / bucket.len() as f64
+        })
+        .collect()
+}
+
+/// The lines of a sparkline `height` lines tall, from the top one.
+fn sparkline(values: &[f64], height: usize) -> Vec<String> {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let levels = height * LEVELS.len();
+
+    // The number of eighths of a line each value is filled to, at least one so it shows up
+    let filled = values
+        .iter()
+        .map(|value| {
+            if max > min {
+                1 + ((value - min) / (max - min) * (levels - 1) as f64).round() as usize
+            } else {
+                levels.div_ceil(2)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    (0..height)
+        .rev()
+        .map(|line| {
+            filled
+                .iter()
+                .map(|filled| match filled.saturating_sub(line * LEVELS.len()) {
+                    0 => ' ',
+                    n => LEVELS[n.min(LEVELS.len()) - 1],
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ChartSparkline)
+    }
+
+    #[test]
+    fn fit_averages_values() {
+        assert_eq!(fit(&[1.0, 3.0, 5.0, 7.0], 2), vec![2.0, 6.0]);
+        assert_eq!(fit(&[1.0, 2.0], 5), vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn flat_sparkline() {
+        assert_eq!(sparkline(&[3.0, 3.0], 1), vec!["▄▄"]);
+    }
+}
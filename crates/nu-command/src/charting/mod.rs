@@ -1,4 +1,6 @@
+mod chart;
 mod hashable_value;
 mod histogram;
 
+pub use chart::{Chart, ChartBar, ChartHistogram, ChartSparkline};
 pub use histogram::Histogram;
@@ -2,6 +2,7 @@ use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::command_prelude::*;
 
 use nu_protocol::FromValue;
+use nu_table::string_width;
 use print_positions::print_positions;
 
 #[derive(Clone)]
@@ -11,6 +12,7 @@ struct Arguments {
     width: usize,
     alignment: FillAlignment,
     character: String,
+    display_width: bool,
     cell_paths: Option<Vec<CellPath>>,
 }
 
@@ -107,6 +109,11 @@ impl Command for Fill {
                 "The character to fill with. Defaults to ' ' (space).",
                 Some('c'),
             )
+            .switch(
+                "display-width",
+                "Measure the width as the columns the text takes on screen, so wide chars like CJK and emoji count as 2.",
+                None,
+            )
             .category(Category::Conversions)
     }
 
@@ -141,6 +148,11 @@ impl Command for Fill {
                 example: "1.1 | fill --alignment center --character '0' --width 5",
                 result: Some(Value::string("01.10", Span::test_data())),
             },
+            Example {
+                description: "Fill text with wide characters to the width it takes on screen.",
+                example: "'日本' | fill --display-width --character '.' --width 6",
+                result: Some(Value::string("日本..", Span::test_data())),
+            },
             Example {
                 description: "Fill a filesize on both sides to a width of 10 with the character '0'.",
                 example: "1kib | fill --alignment middle --character '0' --width 10",
@@ -171,6 +183,7 @@ fn fill(
         .get_flag::<FillAlignment>(engine_state, stack, "alignment")?
         .unwrap_or_default();
     let character_arg: Option<String> = call.get_flag(engine_state, stack, "character")?;
+    let display_width = call.has_flag(engine_state, stack, "display-width")?;
     let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
     let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
 
@@ -182,6 +195,7 @@ fn fill(
         width,
         alignment,
         character,
+        display_width,
         cell_paths,
     };
 
@@ -210,27 +224,33 @@ fn action(input: &Value, args: &Arguments, span: Span) -> Value {
 
 fn fill_float(num: f64, args: &Arguments, span: Span) -> Value {
     let s = num.to_string();
-    let out_str = pad(&s, args.width, &args.character, args.alignment, false);
+    let out_str = pad(&s, args, false);
 
     Value::string(out_str, span)
 }
 fn fill_int(num: i64, args: &Arguments, span: Span) -> Value {
     let s = num.to_string();
-    let out_str = pad(&s, args.width, &args.character, args.alignment, false);
+    let out_str = pad(&s, args, false);
 
     Value::string(out_str, span)
 }
 fn fill_string(s: &str, args: &Arguments, span: Span) -> Value {
-    let out_str = pad(s, args.width, &args.character, args.alignment, false);
+    let out_str = pad(s, args, false);
 
     Value::string(out_str, span)
 }
 
-fn pad(s: &str, width: usize, pad_char: &str, alignment: FillAlignment, truncate: bool) -> String {
+fn pad(s: &str, args: &Arguments, truncate: bool) -> String {
     // Attribution: Most of this function was taken from https://github.com/ogham/rust-pad and tweaked. Thank you!
     // Use width instead of len for graphical display
+    let width = args.width;
+    let pad_char = args.character.as_str();
 
-    let cols = print_positions(s).count();
+    let (cols, pad_width) = if args.display_width {
+        (string_width(s), string_width(pad_char).max(1))
+    } else {
+        (print_positions(s).count(), 1)
+    };
 
     if cols >= width {
         if truncate {
@@ -242,7 +262,7 @@ fn pad(s: &str, width: usize, pad_char: &str, alignment: FillAlignment, truncate
 
     let diff = width - cols;
 
-    let (left_pad, right_pad) = match alignment {
+    let (left_pad, right_pad) = match args.alignment {
         FillAlignment::Left => (0, diff),
         FillAlignment::Right => (diff, 0),
         FillAlignment::Middle => (diff / 2, diff - diff / 2),
@@ -250,16 +270,22 @@ fn pad(s: &str, width: usize, pad_char: &str, alignment: FillAlignment, truncate
     };
 
     let mut new_str = String::new();
-    for _ in 0..left_pad {
-        new_str.push_str(pad_char)
-    }
+    push_padding(&mut new_str, pad_char, pad_width, left_pad);
     new_str.push_str(s);
-    for _ in 0..right_pad {
-        new_str.push_str(pad_char)
-    }
+    push_padding(&mut new_str, pad_char, pad_width, right_pad);
     new_str
 }
 
+/// Fill `columns` with the padding, and spaces for what's left when the padding is wide.
+fn push_padding(out: &mut String, pad_char: &str, pad_width: usize, columns: usize) {
+    for _ in 0..columns / pad_width {
+        out.push_str(pad_char)
+    }
+    for _ in 0..columns % pad_width {
+        out.push(' ')
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -897,6 +897,8 @@ pub struct SQLiteQueryBuilder {
     pub sql_select: Option<String>, // e.g., "column1, column2" or "*" for all
     pub sql_where: Option<String>,  // e.g., "column = ?"
     pub sql_params: Vec<String>,    // parameters for the where clause
+    #[serde(default)]
+    pub sql_group_by: Option<String>, // e.g., "command_line"
     pub sql_order_by: Option<String>, // e.g., "id DESC"
     pub sql_limit: Option<i64>,
     pub sql_offset: Option<i64>,
@@ -915,6 +917,7 @@ impl SQLiteQueryBuilder {
             sql_select: None,
             sql_where: None,
             sql_params: Vec::new(),
+            sql_group_by: None,
             sql_order_by: None,
             sql_limit: None,
             sql_offset: None,
@@ -935,6 +938,11 @@ impl SQLiteQueryBuilder {
         self
     }
 
+    pub fn with_group_by(mut self, group_by: String) -> Self {
+        self.sql_group_by = Some(group_by);
+        self
+    }
+
     pub fn with_order_by(mut self, order_by: String) -> Self {
         self.sql_order_by = Some(order_by);
         self
@@ -1028,6 +1036,10 @@ impl SQLiteQueryBuilder {
             write!(sql, " WHERE {}", where_clause).expect("writing to a String is infallible");
         }
 
+        if let Some(group_by) = &self.sql_group_by {
+            write!(sql, " GROUP BY {}", group_by).expect("writing to a String is infallible");
+        }
+
         if let Some(order_by) = &self.sql_order_by {
             write!(sql, " ORDER BY {}", order_by).expect("writing to a String is infallible");
         }
@@ -1053,7 +1065,12 @@ impl SQLiteQueryBuilder {
     pub fn execute(&self, call_span: Span) -> Result<PipelineData, ShellError> {
         let conn = open_sqlite_db(&self.db_path, call_span)?;
         let sql = self.build_sql();
-        let params = NuSqlParams::List(Vec::new()); // FIXME: handle params properly
+        let params = NuSqlParams::List(
+            self.sql_params
+                .iter()
+                .map(|s| Box::new(s.clone()) as Box<dyn ToSql>)
+                .collect(),
+        );
         let query = Spanned {
             item: sql,
             span: call_span,
@@ -1075,6 +1092,11 @@ impl SQLiteQueryBuilder {
         if let Some(where_clause) = &self.sql_where {
             write!(sql, " WHERE {}", where_clause).expect("writing to a String is infallible");
         }
+        if let Some(group_by) = &self.sql_group_by {
+            // Count the groups rather than the rows of each group
+            write!(sql, " GROUP BY {}", group_by).expect("writing to a String is infallible");
+            sql = format!("SELECT COUNT(*) FROM ({sql})");
+        }
         let mut stmt = conn.prepare(&sql).map_err(|e| {
             ShellError::Generic(GenericError::new(
                 "Failed to prepare count query",
@@ -1575,6 +1597,22 @@ mod test {
         assert_eq!(table.build_sql(), "SELECT * FROM [test] WHERE col = ?");
     }
 
+    #[test]
+    fn sqlite_table_build_sql_with_group_by() {
+        let table = SQLiteQueryBuilder::new(
+            PathBuf::from(":memory:"),
+            "test".to_string(),
+            Signals::empty(),
+        )
+        .with_select("col, count(*) as count".to_string())
+        .with_where("id > ?".to_string(), vec!["1".to_string()])
+        .with_group_by("col".to_string());
+        assert_eq!(
+            table.build_sql(),
+            "SELECT col, count(*) as count FROM [test] WHERE id > ? GROUP BY col"
+        );
+    }
+
     #[test]
     fn sqlite_table_build_sql_with_order_by() {
         let table = SQLiteQueryBuilder::new(
@@ -1,6 +1,11 @@
 use nu_engine::command_prelude::*;
 use nu_parser::{flatten_block, parse};
-use nu_protocol::{engine::StateWorkingSet, record};
+use nu_protocol::{
+    BlockId,
+    ast::{Argument, Block, Expr, Expression, ExternalArgument, ListItem, Pipeline, RecordItem},
+    engine::StateWorkingSet,
+    record,
+};
 use serde_json::{Value as JsonValue, json};
 
 // Constants for JSON field names to avoid magic strings
@@ -39,6 +44,11 @@ impl Command for Ast {
                 "An easier to read version of the ast.",
                 Some('f'),
             )
+            .switch(
+                "tree",
+                "Return the ast as a tree of records with the kind, span, source and type of each node, with the compiled IR.",
+                Some('t'),
+            )
             .allow_variants_without_examples(true)
             .category(Category::Debug)
     }
@@ -70,6 +80,16 @@ impl Command for Ast {
                 example: "ast 'for x in 1..10 { echo $x ' --json --minify",
                 result: None,
             },
+            Example {
+                description: "Get the kind and type of the nodes of a pipeline as a tree.",
+                example: "ast 'ls | length' --tree | get ast.children.0.children",
+                result: None,
+            },
+            Example {
+                description: "Get the compiled IR instructions of a pipeline.",
+                example: "ast '1 + 2' --tree | get ir.instruction",
+                result: None,
+            },
             Example {
                 description: "Print the ast of a string flattened.",
                 example: r#"ast "'hello'" --flatten"#,
@@ -151,12 +171,45 @@ impl Command for Ast {
         let to_json = call.has_flag(engine_state, stack, "json")?;
         let minify = call.has_flag(engine_state, stack, "minify")?;
         let flatten = call.has_flag(engine_state, stack, "flatten")?;
+        let tree = call.has_flag(engine_state, stack, "tree")?;
+
+        if tree && (to_json || flatten) {
+            return Err(ShellError::IncompatibleParametersSingle {
+                msg: "--tree can't be used with --json or --flatten".into(),
+                span: call.get_flag_span(stack, "tree").unwrap_or(call.head),
+            });
+        }
 
         // Parse the pipeline into an AST
         let mut working_set = StateWorkingSet::new(engine_state);
         let offset = working_set.next_span_start();
         let parsed_block = parse(&mut working_set, None, pipeline.item.as_bytes(), false);
 
+        // Handle the structured tree, built once the parsed code is merged in a copy of the
+        // engine state so the IR can refer to the commands it defines
+        if tree {
+            let errors = working_set
+                .parse_errors
+                .iter()
+                .map(|error| (error.to_string(), Some(error.span())))
+                .chain(
+                    working_set
+                        .compile_errors
+                        .iter()
+                        .map(|error| (error.to_string(), None)),
+                )
+                .collect::<Vec<_>>();
+            let mut engine_state = engine_state.clone();
+            engine_state.merge_delta(working_set.render())?;
+
+            let builder = TreeBuilder {
+                engine_state: &engine_state,
+                offset,
+                head: call.head,
+            };
+            return Ok(builder.output(&parsed_block, errors).into_pipeline_data());
+        }
+
         // Handle flattened output (shows tokens with their shapes and spans)
         if flatten {
             let flat = flatten_block(&working_set, &parsed_block);
@@ -311,6 +364,318 @@ impl Command for Ast {
     }
 }
 
+/// Builds the structured tree returned by `ast --tree`.
+struct TreeBuilder<'a> {
+    engine_state: &'a EngineState,
+    /// The start of the parsed code, so spans are relative to it.
+    offset: usize,
+    head: Span,
+}
+
+impl TreeBuilder<'_> {
+    fn output(&self, block: &Block, errors: Vec<(String, Option<Span>)>) -> Value {
+        let errors = errors
+            .into_iter()
+            .map(|(message, span)| {
+                Value::record(
+                    record! {
+                        "message" => Value::string(message, self.head),
+                        "span" => span.map_or(Value::nothing(self.head), |span| self.span(span)),
+                    },
+                    self.head,
+                )
+            })
+            .collect();
+
+        Value::record(
+            record! {
+                "ast" => self.block(block),
+                "ir" => self.ir(block),
+                "errors" => Value::list(errors, self.head),
+            },
+            self.head,
+        )
+    }
+
+    fn span(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "start" => Value::int(span.start.saturating_sub(self.offset) as i64, self.head),
+                "end" => Value::int(span.end.saturating_sub(self.offset) as i64, self.head),
+            },
+            self.head,
+        )
+    }
+
+    fn node(
+        &self,
+        kind: &str,
+        span: Span,
+        ty: Option<&Type>,
+        detail: Option<String>,
+        children: Vec<Value>,
+    ) -> Value {
+        let source = String::from_utf8_lossy(self.engine_state.get_span_contents(span));
+        Value::record(
+            record! {
+                "kind" => Value::string(kind, self.head),
+                "source" => Value::string(source, self.head),
+                "span" => self.span(span),
+                "type" => ty.map_or(Value::nothing(self.head), |ty| Value::string(ty.to_string(), self.head)),
+                "detail" => detail.map_or(Value::nothing(self.head), |detail| Value::string(detail, self.head)),
+                "children" => Value::list(children, self.head),
+            },
+            self.head,
+        )
+    }
+
+    /// The compiled instructions of the block, if it compiled.
+    fn ir(&self, block: &Block) -> Value {
+        let Some(ir_block) = &block.ir_block else {
+            return Value::nothing(self.head);
+        };
+
+        let instructions = ir_block
+            .instructions
+            .iter()
+            .zip(&ir_block.spans)
+            .map(|(instruction, span)| {
+                Value::record(
+                    record! {
+                        "instruction" => Value::string(
+                            instruction.display(self.engine_state, &ir_block.data).to_string(),
+                            self.head,
+                        ),
+                        "span" => self.span(*span),
+                    },
+                    self.head,
+                )
+            })
+            .collect();
+        Value::list(instructions, self.head)
+    }
+
+    fn block(&self, block: &Block) -> Value {
+        let pipelines = block
+            .pipelines
+            .iter()
+            .map(|pipeline| self.pipeline(pipeline))
+            .collect();
+        let span = block.span.unwrap_or_else(|| {
+            Span::merge_many(
+                block
+                    .pipelines
+                    .iter()
+                    .flat_map(|pipeline| &pipeline.elements)
+                    .map(|element| element.expr.span),
+            )
+        });
+        self.node("block", span, Some(&block.output_type()), None, pipelines)
+    }
+
+    fn block_id(&self, block_id: BlockId) -> Value {
+        self.block(self.engine_state.get_block(block_id))
+    }
+
+    fn pipeline(&self, pipeline: &Pipeline) -> Value {
+        let elements = pipeline
+            .elements
+            .iter()
+            .map(|element| self.expression(&element.expr))
+            .collect();
+        let span = Span::merge_many(pipeline.elements.iter().map(|element| element.expr.span));
+        let ty = pipeline.elements.last().map(|element| &element.expr.ty);
+        self.node("pipeline", span, ty, None, elements)
+    }
+
+    fn expression(&self, expr: &Expression) -> Value {
+        let (kind, detail, children) = match &expr.expr {
+            Expr::AttributeBlock(block) => (
+                "attribute_block",
+                None,
+                block
+                    .attributes
+                    .iter()
+                    .map(|attribute| &attribute.expr)
+                    .chain([&*block.item])
+                    .map(|expr| self.expression(expr))
+                    .collect(),
+            ),
+            Expr::Bool(_) => ("bool", None, vec![]),
+            Expr::Int(_) => ("int", None, vec![]),
+            Expr::Float(_) => ("float", None, vec![]),
+            Expr::Binary(_) => ("binary", None, vec![]),
+            Expr::Range(range) => (
+                "range",
+                Some(range.operator.to_string()),
+                [&range.from, &range.next, &range.to]
+                    .into_iter()
+                    .flatten()
+                    .map(|expr| self.expression(expr))
+                    .collect(),
+            ),
+            Expr::Var(_) => ("var", None, vec![]),
+            Expr::VarDecl(_) => ("var_decl", None, vec![]),
+            Expr::Call(call) => (
+                "call",
+                Some(self.engine_state.get_decl(call.decl_id).name().to_string()),
+                call.arguments
+                    .iter()
+                    .map(|argument| self.argument(argument))
+                    .collect(),
+            ),
+            Expr::ExternalCall(head, args) => (
+                "external_call",
+                None,
+                std::iter::once(self.expression(head))
+                    .chain(args.iter().map(|arg| match arg {
+                        ExternalArgument::Regular(expr) => self.expression(expr),
+                        ExternalArgument::Spread(expr) => {
+                            self.node("spread", expr.span, None, None, vec![self.expression(expr)])
+                        }
+                    }))
+                    .collect(),
+            ),
+            Expr::Operator(operator) => ("operator", Some(operator.to_string()), vec![]),
+            Expr::RowCondition(block_id) => ("row_condition", None, vec![self.block_id(*block_id)]),
+            Expr::UnaryNot(expr) => ("unary_not", None, vec![self.expression(expr)]),
+            Expr::BinaryOp(lhs, op, rhs) => (
+                "binary_op",
+                None,
+                vec![
+                    self.expression(lhs),
+                    self.expression(op),
+                    self.expression(rhs),
+                ],
+            ),
+            Expr::Collect(_, expr) => ("collect", None, vec![self.expression(expr)]),
+            Expr::Subexpression(block_id) => {
+                ("subexpression", None, vec![self.block_id(*block_id)])
+            }
+            Expr::Block(block_id) => ("block", None, vec![self.block_id(*block_id)]),
+            Expr::Closure(block_id) => ("closure", None, vec![self.block_id(*block_id)]),
+            Expr::MatchBlock(arms) => (
+                "match_block",
+                None,
+                arms.iter()
+                    .map(|(pattern, expr)| {
+                        let guard = pattern.guard.iter().map(|guard| self.expression(guard));
+                        let children = guard.chain([self.expression(expr)]).collect();
+                        let span = Span::merge(pattern.span, expr.span);
+                        self.node("match_arm", span, Some(&expr.ty), None, children)
+                    })
+                    .collect(),
+            ),
+            Expr::List(items) => (
+                "list",
+                None,
+                items
+                    .iter()
+                    .map(|item| match item {
+                        ListItem::Item(expr) => self.expression(expr),
+                        ListItem::Spread(span, expr) => self.spread(*span, expr),
+                    })
+                    .collect(),
+            ),
+            Expr::Table(table) => (
+                "table",
+                None,
+                table
+                    .columns
+                    .iter()
+                    .map(|column| self.expression(column))
+                    .chain(table.rows.iter().map(|row| {
+                        let span = Span::merge_many(row.iter().map(|cell| cell.span));
+                        let cells = row.iter().map(|cell| self.expression(cell)).collect();
+                        self.node("row", span, None, None, cells)
+                    }))
+                    .collect(),
+            ),
+            Expr::Record(items) => (
+                "record",
+                None,
+                items
+                    .iter()
+                    .flat_map(|item| match item {
+                        RecordItem::Pair(key, value) => {
+                            vec![self.expression(key), self.expression(value)]
+                        }
+                        RecordItem::Spread(span, expr) => vec![self.spread(*span, expr)],
+                    })
+                    .collect(),
+            ),
+            Expr::Keyword(keyword) => (
+                "keyword",
+                Some(String::from_utf8_lossy(&keyword.keyword).into_owned()),
+                vec![self.expression(&keyword.expr)],
+            ),
+            Expr::ValueWithUnit(value) => (
+                "value_with_unit",
+                Some(
+                    String::from_utf8_lossy(self.engine_state.get_span_contents(value.unit.span))
+                        .into_owned(),
+                ),
+                vec![self.expression(&value.expr)],
+            ),
+            Expr::DateTime(_) => ("datetime", None, vec![]),
+            Expr::Filepath(..) => ("filepath", None, vec![]),
+            Expr::Directory(..) => ("directory", None, vec![]),
+            Expr::GlobPattern(..) => ("glob_pattern", None, vec![]),
+            Expr::String(_) => ("string", None, vec![]),
+            Expr::RawString(_) => ("raw_string", None, vec![]),
+            Expr::CellPath(_) => ("cell_path", None, vec![]),
+            Expr::FullCellPath(path) => ("full_cell_path", None, vec![self.expression(&path.head)]),
+            Expr::ImportPattern(_) => ("import_pattern", None, vec![]),
+            Expr::Overlay(_) => ("overlay", None, vec![]),
+            Expr::Signature(_) => ("signature", None, vec![]),
+            Expr::StringInterpolation(exprs) => (
+                "string_interpolation",
+                None,
+                exprs.iter().map(|expr| self.expression(expr)).collect(),
+            ),
+            Expr::GlobInterpolation(exprs, _) => (
+                "glob_interpolation",
+                None,
+                exprs.iter().map(|expr| self.expression(expr)).collect(),
+            ),
+            Expr::Nothing => ("nothing", None, vec![]),
+            Expr::Garbage => ("garbage", None, vec![]),
+        };
+
+        self.node(kind, expr.span, Some(&expr.ty), detail, children)
+    }
+
+    fn argument(&self, argument: &Argument) -> Value {
+        match argument {
+            Argument::Positional(expr) | Argument::Unknown(expr) => self.expression(expr),
+            Argument::Spread(expr) => self.node(
+                "spread",
+                argument.span(),
+                None,
+                None,
+                vec![self.expression(expr)],
+            ),
+            Argument::Named((long, _, value)) => self.node(
+                "flag",
+                argument.span(),
+                None,
+                Some(format!("--{}", long.item)),
+                value.iter().map(|expr| self.expression(expr)).collect(),
+            ),
+        }
+    }
+
+    fn spread(&self, span: Span, expr: &Expression) -> Value {
+        self.node(
+            "spread",
+            Span::merge(span, expr.span),
+            None,
+            None,
+            vec![self.expression(expr)],
+        )
+    }
+}
+
 fn json_merge(a: &mut JsonValue, b: &JsonValue) {
     match (a, b) {
         (JsonValue::Object(a), JsonValue::Object(b)) => {
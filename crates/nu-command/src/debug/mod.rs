@@ -10,6 +10,7 @@ mod metadata;
 mod metadata_access;
 mod metadata_set;
 mod profile;
+mod test_examples;
 mod timeit;
 mod util;
 mod view;
@@ -31,6 +32,7 @@ pub use metadata::Metadata;
 pub use metadata_access::MetadataAccess;
 pub use metadata_set::MetadataSet;
 pub use profile::DebugProfile;
+pub use test_examples::TestExamples;
 pub use timeit::TimeIt;
 pub use view::View;
 pub use view_blocks::ViewBlocks;
@@ -0,0 +1,152 @@
+use crate::help::run_example;
+use nu_engine::command_prelude::*;
+use nu_protocol::DeclId;
+
+#[derive(Clone)]
+pub struct TestExamples;
+
+impl Command for TestExamples {
+    fn name(&self) -> &str {
+        "test-examples"
+    }
+
+    fn description(&self) -> &str {
+        "Run the examples of commands and check their results."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Without arguments, the examples of all custom commands in scope are run. Examples come \
+        from `@example` attributes and from `Example:` sections in the doc comments of a command. \
+        An example passes when it gives the result it shows, or when it runs without an error if \
+        it shows none. An example whose result isn't a constant is `unchecked` when it runs \
+        without an error. Examples run in the current scope, so they must call the commands the way \
+        they're imported."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("test-examples")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .rest(
+                "command",
+                SyntaxShape::String,
+                "The names of the commands to run the examples of.",
+            )
+            .named(
+                "module",
+                SyntaxShape::String,
+                "Run the examples of the commands exported by this module.",
+                Some('m'),
+            )
+            .allow_variants_without_examples(true)
+            .category(Category::Debug)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["doctest", "check", "verify"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Check the examples of all custom commands.",
+                example: "test-examples",
+                result: None,
+            },
+            Example {
+                description: "Check the examples of a custom command.",
+                example: "test-examples my-command",
+                result: None,
+            },
+            Example {
+                description: "Show the examples of a module that don't pass.",
+                example: "test-examples --module my-module | where status != pass",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let names: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+        let module: Option<Spanned<String>> = call.get_flag(engine_state, stack, "module")?;
+
+        let mut decls: Vec<DeclId> = names
+            .iter()
+            .map(|name| {
+                engine_state
+                    .find_decl(name.item.as_bytes(), &[])
+                    .ok_or(ShellError::CommandNotFound { span: name.span })
+            })
+            .collect::<Result<_, _>>()?;
+
+        if let Some(module) = module {
+            let Some(module_id) = engine_state.find_module(module.item.as_bytes(), &[]) else {
+                return Err(ShellError::ModuleNotFoundAtRuntime {
+                    mod_name: module.item,
+                    span: module.span,
+                });
+            };
+            let mut module_decls = engine_state.get_module(module_id).decls();
+            module_decls.sort_by(|a, b| a.0.cmp(&b.0));
+            decls.extend(module_decls.into_iter().map(|(_, decl_id)| decl_id));
+        } else if names.is_empty() {
+            decls.extend(
+                engine_state
+                    .get_decls_sorted(false)
+                    .into_iter()
+                    .map(|(_, decl_id)| decl_id)
+                    .filter(|decl_id| engine_state.get_decl(*decl_id).is_custom()),
+            );
+        }
+
+        let mut rows = vec![];
+        for decl_id in decls {
+            let decl = engine_state.get_decl(decl_id);
+            for (index, example) in decl.examples().into_iter().enumerate() {
+                engine_state.signals().check(&head)?;
+
+                let (status, actual) =
+                    match run_example(engine_state, stack, example.example, None, head)
+                        .and_then(|data| data.into_value(head))
+                    {
+                        Ok(actual) => match &example.result {
+                            Some(expected) if *expected != actual => ("fail", actual),
+                            None if decl.example_result_unchecked(index) => ("unchecked", actual),
+                            _ => ("pass", actual),
+                        },
+                        Err(err) => ("error", Value::string(err.to_string(), head)),
+                    };
+
+                rows.push(Value::record(
+                    record! {
+                        "command" => Value::string(decl.name(), head),
+                        "description" => Value::string(example.description, head),
+                        "example" => Value::string(example.example, head),
+                        "status" => Value::string(status, head),
+                        "expected" => example.result.unwrap_or_else(|| Value::nothing(head)),
+                        "actual" => actual,
+                    },
+                    head,
+                ));
+            }
+        }
+
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(TestExamples)
+    }
+}
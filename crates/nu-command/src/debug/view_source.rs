@@ -1,5 +1,7 @@
 use nu_engine::command_prelude::*;
-use nu_protocol::{Config, PipelineMetadata, Span, shell_error::generic::GenericError};
+use nu_protocol::{
+    Config, PipelineMetadata, Span, engine::CommandType, shell_error::generic::GenericError,
+};
 
 use std::fmt::Write;
 
@@ -112,11 +114,6 @@ impl Command for ViewSource {
                     // arg is a command
                     let decl = engine_state.get_decl(decl_id);
                     let sig = decl.signature();
-                    let vec_of_required = &sig.required_positional;
-                    let vec_of_optional = &sig.optional_positional;
-                    let rest = &sig.rest_positional;
-                    let vec_of_flags = &sig.named;
-                    let type_signatures = &sig.input_output_types;
 
                     if decl.is_alias() {
                         if let Some(alias) = &decl.as_alias() {
@@ -150,6 +147,7 @@ impl Command for ViewSource {
                             let flags: Vec<&str> = [
                                 block.redirect_env.then_some("--env"),
                                 sig.allows_unknown_args.then_some("--wrapped"),
+                                block.generator.then_some("--generator"),
                             ]
                             .into_iter()
                             .flatten()
@@ -159,71 +157,7 @@ impl Command for ViewSource {
                             } else {
                                 format!("{} ", flags.join(" "))
                             };
-                            if val.contains(' ') {
-                                let _ = write!(&mut final_contents, "def {flags_str}\"{val}\" [");
-                            } else {
-                                let _ = write!(&mut final_contents, "def {flags_str}{val} [");
-                            };
-                            if !vec_of_required.is_empty()
-                                || !vec_of_optional.is_empty()
-                                || vec_of_flags.len() != 1
-                                || rest.is_some()
-                            {
-                                final_contents.push(' ');
-                            }
-                            for n in vec_of_required {
-                                let _ = write!(&mut final_contents, "{}: {} ", n.name, n.shape);
-                                // positional arguments
-                            }
-                            for n in vec_of_optional {
-                                if let Some(s) = n.default_value.clone() {
-                                    let _ = write!(
-                                        &mut final_contents,
-                                        "{}: {} = {} ",
-                                        n.name,
-                                        n.shape,
-                                        s.to_expanded_string(" ", &Config::default())
-                                    );
-                                } else {
-                                    let _ =
-                                        write!(&mut final_contents, "{}?: {} ", n.name, n.shape);
-                                }
-                            }
-                            for n in vec_of_flags {
-                                // skip adding the help flag
-                                if n.long == "help" {
-                                    continue;
-                                }
-                                let _ = write!(&mut final_contents, "--{}", n.long);
-                                if let Some(short) = n.short {
-                                    let _ = write!(&mut final_contents, "(-{short})");
-                                }
-                                if let Some(arg) = &n.arg {
-                                    let _ = write!(&mut final_contents, ": {arg}");
-                                }
-                                final_contents.push(' ');
-                            }
-                            if let Some(rest_arg) = rest {
-                                let _ = write!(
-                                    &mut final_contents,
-                                    "...{}:{}",
-                                    rest_arg.name, rest_arg.shape
-                                );
-                            }
-                            let len = type_signatures.len();
-                            if len != 0 {
-                                final_contents.push_str("]: [");
-                                let mut c = 0;
-                                for (insig, outsig) in type_signatures {
-                                    c += 1;
-                                    let s = format!("{insig} -> {outsig}");
-                                    final_contents.push_str(&s);
-                                    if c != len {
-                                        final_contents.push_str(", ")
-                                    }
-                                }
-                            }
-                            final_contents.push_str("] ");
+                            final_contents.push_str(&def_signature(&val, &flags_str, &sig));
                             final_contents.push_str(&String::from_utf8_lossy(contents));
 
                             Ok(make_output(
@@ -239,6 +173,48 @@ impl Command for ViewSource {
                                 arg_span,
                             )))
                         }
+                    } else if matches!(
+                        decl.command_type(),
+                        CommandType::Builtin | CommandType::Keyword | CommandType::Plugin
+                    ) {
+                        // A built-in command has no source to show, so show its documentation
+                        let mut final_contents = String::new();
+                        write_comment(&mut final_contents, decl.description());
+                        if !decl.extra_description().is_empty() {
+                            final_contents.push_str("#\n");
+                            write_comment(&mut final_contents, decl.extra_description());
+                        }
+
+                        let examples = decl.examples();
+                        if !examples.is_empty() {
+                            let config = stack.get_config(engine_state);
+                            let _ = writeln!(
+                                &mut final_contents,
+                                "#\n# Examples, run them with `help examples {val} --run <index>`:"
+                            );
+                            for (index, example) in examples.iter().enumerate() {
+                                final_contents.push_str("#\n");
+                                write_comment(
+                                    &mut final_contents,
+                                    &format!("{index}. {}", example.description),
+                                );
+                                write_comment(
+                                    &mut final_contents,
+                                    &format!("> {}", example.example),
+                                );
+                                if let Some(result) = &example.result {
+                                    write_comment(
+                                        &mut final_contents,
+                                        &result.to_expanded_string(", ", &config),
+                                    );
+                                }
+                            }
+                        }
+
+                        final_contents.push_str(&def_signature(&val, "", &sig));
+                        final_contents.push_str("{ <internal command> }");
+
+                        Ok(make_output(engine_state, final_contents, None, call.head))
                     } else {
                         Err(ShellError::Generic(GenericError::new(
                             "Cannot view string decl value",
@@ -306,6 +282,89 @@ impl Command for ViewSource {
     }
 }
 
+// Helper function to write the `def` line of a command, up to its body
+fn def_signature(name: &str, flags_str: &str, sig: &Signature) -> String {
+    let vec_of_required = &sig.required_positional;
+    let vec_of_optional = &sig.optional_positional;
+    let rest = &sig.rest_positional;
+    let vec_of_flags = &sig.named;
+    let type_signatures = &sig.input_output_types;
+
+    let mut final_contents = String::new();
+    if name.contains(' ') {
+        let _ = write!(&mut final_contents, "def {flags_str}\"{name}\" [");
+    } else {
+        let _ = write!(&mut final_contents, "def {flags_str}{name} [");
+    };
+    if !vec_of_required.is_empty()
+        || !vec_of_optional.is_empty()
+        || vec_of_flags.len() != 1
+        || rest.is_some()
+    {
+        final_contents.push(' ');
+    }
+    for n in vec_of_required {
+        let _ = write!(&mut final_contents, "{}: {} ", n.name, n.shape);
+        // positional arguments
+    }
+    for n in vec_of_optional {
+        if let Some(s) = n.default_value.clone() {
+            let _ = write!(
+                &mut final_contents,
+                "{}: {} = {} ",
+                n.name,
+                n.shape,
+                s.to_expanded_string(" ", &Config::default())
+            );
+        } else {
+            let _ = write!(&mut final_contents, "{}?: {} ", n.name, n.shape);
+        }
+    }
+    for n in vec_of_flags {
+        // skip adding the help flag
+        if n.long == "help" {
+            continue;
+        }
+        let _ = write!(&mut final_contents, "--{}", n.long);
+        if let Some(short) = n.short {
+            let _ = write!(&mut final_contents, "(-{short})");
+        }
+        if let Some(arg) = &n.arg {
+            let _ = write!(&mut final_contents, ": {arg}");
+        }
+        final_contents.push(' ');
+    }
+    if let Some(rest_arg) = rest {
+        let _ = write!(
+            &mut final_contents,
+            "...{}:{}",
+            rest_arg.name, rest_arg.shape
+        );
+    }
+    let len = type_signatures.len();
+    if len != 0 {
+        final_contents.push_str("]: [");
+        let mut c = 0;
+        for (insig, outsig) in type_signatures {
+            c += 1;
+            let s = format!("{insig} -> {outsig}");
+            final_contents.push_str(&s);
+            if c != len {
+                final_contents.push_str(", ")
+            }
+        }
+    }
+    final_contents.push_str("] ");
+    final_contents
+}
+
+// Helper function to write text as `#` comment lines
+fn write_comment(contents: &mut String, text: &str) {
+    for line in text.lines() {
+        let _ = writeln!(contents, "# {line}");
+    }
+}
+
 // Helper function to find the file path associated with a given span, if any.
 fn file_for_span(engine_state: &EngineState, span: Span) -> Option<String> {
     engine_state
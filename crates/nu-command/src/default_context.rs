@@ -23,6 +23,10 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
 
         // Charts
         bind_command! {
+            Chart,
+            ChartBar,
+            ChartHistogram,
+            ChartSparkline,
             Histogram
         }
 
@@ -103,24 +107,37 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
 
         // Misc
         bind_command! {
+            Assert,
+            AssertEqual,
+            AssertSnapshot,
             DeleteVar,
+            Lazy,
             Panic,
+            Ref,
+            RefNew,
+            RefGet,
+            RefUpdate,
             Run,
             Source,
+            TestRun,
             Tutor,
+            WithOverride,
         };
 
         // Path
         bind_command! {
             Path,
+            PathAdd,
             PathBasename,
             PathSelf,
+            PathDedupe,
             PathDirname,
             PathExists,
             PathExpand,
             PathJoin,
             PathParse,
             PathRelativeTo,
+            PathRemove,
             PathSplit,
             PathType,
         };
@@ -155,6 +172,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             HelpOperators,
             HelpPipeAndRedirect,
             HelpEscapes,
+            HelpExamples,
         };
 
         // Debug
@@ -170,6 +188,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Metadata,
             MetadataAccess,
             MetadataSet,
+            TestExamples,
             TimeIt,
             View,
             ViewBlocks,
@@ -180,7 +199,10 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
         };
 
         #[cfg(all(feature = "os", windows))]
-        bind_command! { Registry, RegistryQuery }
+        bind_command! { Registry, RegistryQuery, RegistrySet, RegistryDelete }
+
+        #[cfg(all(feature = "os", target_os = "linux"))]
+        bind_command! { Journal, JournalRead }
 
         #[cfg(all(
             feature = "os",
@@ -235,6 +257,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             StrIndexOf,
             StrLength,
             StrReverse,
+            StrSimilarity,
             StrStats,
             StrStartsWith,
             StrSubstring,
@@ -245,6 +268,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             FormatDate,
             FormatDuration,
             FormatFilesize,
+            FormatNu,
         };
 
         // FileSystem
@@ -253,6 +277,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Cd,
             Ls,
             UMkdir,
+            Mkpipe,
             Mktemp,
             UMv,
             UCp,
@@ -263,6 +288,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             UTouch,
             Glob,
             Watch,
+            WithLock,
             Idx,
             IdxInit,
             IdxStatus,
@@ -294,6 +320,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             IsRedirected,
             IsTerminal,
             Kill,
+            OnSignal,
             Sleep,
             Term,
             TermSize,
@@ -302,7 +329,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
         };
 
         #[cfg(all(unix, feature = "os"))]
-        bind_command! { ULimit };
+        bind_command! { ULimit, ULimitGet, ULimitSet };
 
         #[cfg(all(unix, feature = "os"))]
         bind_command! { UMask };
@@ -325,14 +352,20 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
         // Formats
         bind_command! {
             From,
+            FromCbor,
             FromCsv,
+            FromDotenv,
+            FromEdn,
             FromJson,
+            FromLogfmt,
             FromMd,
             FromMsgpack,
             FromMsgpackz,
             FromNuon,
             FromOds,
+            FromPlist,
             FromSsv,
+            FromSyslog,
             FromToml,
             FromTsv,
             FromXlsx,
@@ -341,12 +374,16 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             FROM_YML,
             FromKdl,
             To,
+            ToCbor,
             ToCsv,
+            ToEdn,
             ToJson,
+            ToLogfmt,
             ToMd,
             ToMsgpack,
             ToMsgpackz,
             ToNuon,
+            ToPlist,
             ToText,
             ToToml,
             ToTsv,
@@ -363,6 +400,10 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Griddle,
             Table,
         };
+        #[cfg(feature = "os")]
+        bind_command! {
+            ViewImage,
+        };
 
         // Conversions
         bind_command! {
@@ -421,12 +462,22 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             LoadEnv,
             SourceEnv,
             WithEnv,
+            EnvWatch,
             ConfigNu,
             ConfigEnv,
             ConfigFlatten,
             ConfigMeta,
             ConfigReset,
             ConfigUseColors,
+            HookRemove,
+        };
+
+        #[cfg(feature = "os")]
+        bind_command! {
+            EnvActivate,
+            EnvDeactivate,
+            ConfigTrust,
+            ConfigUntrust,
         };
 
         // Math
@@ -537,6 +588,8 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             JobSend,
             JobRecv,
             JobFlush,
+            JobSelect,
+            JobChannel,
         }
 
         #[cfg(all(unix, feature = "os"))]
@@ -550,6 +603,14 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             DateFormat,
         };
 
+        // Avro
+        #[cfg(feature = "avro")]
+        bind_command! { FromAvro };
+
+        // Protobuf
+        #[cfg(feature = "protobuf")]
+        bind_command! { FromProtobuf };
+
         // Stor
         #[cfg(feature = "sqlite")]
         bind_command! {
@@ -0,0 +1,404 @@
+use super::trust::TrustStore;
+use crate::filesystem::try_interaction;
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::shell_error::generic::GenericError;
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
+
+/// File names looked up, in order, when `env activate` is pointed at a directory.
+const ENV_FILE_NAMES: &[&str] = &["nuenv.nu", ".nu-env"];
+
+/// Record describing the active environment.
+///
+/// It is stored inside the environment's overlay, so it disappears together with it.
+const ACTIVE_ENV_VAR: &str = "NU_ACTIVE_ENV";
+
+/// Environment variables that keep their current value when an environment is deactivated.
+const KEPT_ENV_VARS: &[&str] = &["PWD", "OLDPWD"];
+
+#[derive(Clone)]
+pub struct EnvActivate;
+
+impl Command for EnvActivate {
+    fn name(&self) -> &str {
+        "env activate"
+    }
+
+    fn description(&self) -> &str {
+        "Activate a project environment described by a `nuenv.nu` or `.nu-env` file."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The environment file contains a single record (in nuon syntax) with the following optional keys:
+  - `name`: name of the environment, defaults to the name of its directory
+  - `env`: record of environment variables to set
+  - `path`: list of directories prepended to PATH, relative to the environment file
+
+The environment is activated in a new overlay named after the environment, so `env deactivate` restores the previous environment. In the REPL, the environment is also deactivated automatically once the current directory is no longer inside the environment's directory.
+
+Like direnv, an environment file must be trusted before it is loaded. In an interactive session, untrusted files must be accepted in a prompt, and trusting them is remembered until the file changes. Use `--trust` to trust a file without being prompted."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["virtualenv", "venv", "conda", "direnv", "project"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("env activate")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .optional(
+                "target",
+                SyntaxShape::String,
+                "Environment directory, environment file, or name of an environment stored in `$nu.data-dir/envs`. Defaults to the current directory.",
+            )
+            .switch(
+                "trust",
+                "Trust the environment file without prompting.",
+                None,
+            )
+            .category(Category::Env)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let target: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let trust = call.has_flag(engine_state, stack, "trust")?;
+        let span = target.as_ref().map_or(call.head, |target| target.span);
+
+        let cwd = engine_state.cwd(Some(stack))?;
+        let env_file = find_env_file(engine_state, cwd.as_std_path(), target.as_ref(), span)?;
+        let contents =
+            std::fs::read(&env_file).map_err(|err| IoError::new(err, span, env_file.clone()))?;
+
+        let mut store = TrustStore::load(engine_state, call.head)?;
+        if !store.is_trusted(&env_file, &contents) {
+            let prompt = format!(
+                "Environment file '{}' is not trusted. Trust it and continue?",
+                env_file.display()
+            );
+            let accepted = trust
+                || (engine_state.is_interactive
+                    && std::io::stdin().is_terminal()
+                    && try_interaction(true, prompt).1);
+            if !accepted {
+                return Err(ShellError::Generic(
+                    GenericError::new(
+                        "Untrusted environment file",
+                        format!("'{}' has not been trusted", env_file.display()),
+                        span,
+                    )
+                    .with_help("review the file, then run `env activate --trust` to trust it"),
+                ));
+            }
+            store.trust(&env_file, &contents);
+            store.save(call.head)?;
+        }
+
+        let env = ProjectEnv::parse(&env_file, &contents, span)?;
+
+        // Environments don't stack, entering one leaves the previous one
+        deactivate_env(engine_state, stack);
+
+        if stack.is_overlay_active(&env.name) {
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    "Environment name already in use",
+                    format!("an overlay named '{}' is already active", env.name),
+                    span,
+                )
+                .with_help("set a different `name` in the environment file"),
+            ));
+        }
+
+        env.apply(engine_state, stack, call.head);
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Activate the environment of the current directory.",
+                example: "env activate",
+                result: None,
+            },
+            Example {
+                description: "Activate an environment stored in `$nu.data-dir/envs/rust-nightly`.",
+                example: "env activate rust-nightly",
+                result: None,
+            },
+            Example {
+                description: "An environment file that sets a variable and extends PATH.",
+                example: r#"'{ name: "demo", env: { RUST_LOG: "debug" }, path: [".venv/bin"] }' | save nuenv.nu; env activate --trust"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+#[derive(Clone)]
+pub struct EnvDeactivate;
+
+impl Command for EnvDeactivate {
+    fn name(&self) -> &str {
+        "env deactivate"
+    }
+
+    fn description(&self) -> &str {
+        "Deactivate the environment entered with `env activate`."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Removes the environment's overlay. Only `PWD` and `OLDPWD` keep their current values, any other change made to the environment while it was active is dropped."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("env deactivate")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .category(Category::Env)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        match deactivate_env(engine_state, stack) {
+            Some(_) => Ok(PipelineData::empty()),
+            None => Err(ShellError::Generic(GenericError::new(
+                "No active environment",
+                "no environment was activated with `env activate`",
+                call.head,
+            ))),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Leave the active environment.",
+            example: "env deactivate",
+            result: None,
+        }]
+    }
+}
+
+/// Deactivate the environment entered with `env activate`, returning its name.
+///
+/// Returns `None` if no environment is active.
+pub fn deactivate_env(engine_state: &EngineState, stack: &mut Stack) -> Option<String> {
+    let name = active_env_field(engine_state, stack, "name")?;
+
+    let kept: Vec<(String, Value)> = KEPT_ENV_VARS
+        .iter()
+        .filter_map(|env_name| {
+            stack
+                .get_env_var(engine_state, env_name)
+                .map(|value| (env_name.to_string(), value.clone()))
+        })
+        .collect();
+
+    stack.remove_overlay(&name);
+
+    for (env_name, value) in kept {
+        stack.add_env_var(env_name, value);
+    }
+
+    Some(name)
+}
+
+/// Deactivate the active environment if the current directory is no longer inside of it.
+///
+/// This is run by the REPL after each command, so `cd`-ing out of a project leaves its
+/// environment.
+pub fn deactivate_env_outside_dir(engine_state: &EngineState, stack: &mut Stack) -> Option<String> {
+    let dir = active_env_field(engine_state, stack, "dir")?;
+    let cwd = engine_state.cwd(Some(stack)).ok()?;
+
+    if cwd.as_std_path().starts_with(dir) {
+        None
+    } else {
+        deactivate_env(engine_state, stack)
+    }
+}
+
+fn active_env_field(engine_state: &EngineState, stack: &Stack, field: &str) -> Option<String> {
+    stack
+        .get_env_var(engine_state, ACTIVE_ENV_VAR)?
+        .as_record()
+        .ok()?
+        .get(field)?
+        .coerce_string()
+        .ok()
+}
+
+/// Locate the environment file `target` refers to.
+///
+/// `target` may be an environment file, a directory containing one, or the name of a directory
+/// inside `$nu.data-dir/envs`.
+fn find_env_file(
+    engine_state: &EngineState,
+    cwd: &Path,
+    target: Option<&Spanned<String>>,
+    span: Span,
+) -> Result<PathBuf, ShellError> {
+    let candidate = match target {
+        Some(target) => expand_path_with(&target.item, cwd, true),
+        None => cwd.to_path_buf(),
+    };
+
+    if candidate.is_file() {
+        return Ok(candidate);
+    }
+
+    let dir = match target {
+        Some(target) if !candidate.exists() => engine_state
+            .config_dirs
+            .data_home
+            .join("envs")
+            .join(&target.item),
+        _ => candidate,
+    };
+
+    ENV_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            ShellError::Generic(
+                GenericError::new(
+                    "Environment file not found",
+                    format!("no environment file found in '{}'", dir.display()),
+                    span,
+                )
+                .with_help(format!(
+                    "create one of {} in the environment directory",
+                    ENV_FILE_NAMES.join(", ")
+                )),
+            )
+        })
+}
+
+/// The contents of an environment file.
+struct ProjectEnv {
+    name: String,
+    dir: PathBuf,
+    file: PathBuf,
+    vars: Record,
+    path: Vec<PathBuf>,
+}
+
+impl ProjectEnv {
+    fn parse(file: &Path, contents: &[u8], span: Span) -> Result<Self, ShellError> {
+        let dir = file.parent().map(Path::to_path_buf).unwrap_or_default();
+        let mut env = Self {
+            name: dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "env".into()),
+            dir,
+            file: file.to_path_buf(),
+            vars: Record::new(),
+            path: Vec::new(),
+        };
+
+        let contents = String::from_utf8_lossy(contents);
+        let value = nuon::from_nuon(&contents, Some(span))?;
+        let Value::Record { val: record, .. } = value else {
+            return Err(ShellError::RuntimeTypeMismatch {
+                expected: Type::record(),
+                actual: value.get_type(),
+                span,
+            });
+        };
+
+        for (key, value) in record.into_owned() {
+            match key.as_str() {
+                "name" => env.name = value.coerce_into_string()?,
+                "env" => env.vars = value.into_record()?,
+                "path" => {
+                    env.path = value
+                        .into_list()?
+                        .into_iter()
+                        .map(|dir| Ok(expand_path_with(dir.coerce_into_string()?, &env.dir, true)))
+                        .collect::<Result<_, ShellError>>()?;
+                }
+                _ => {
+                    return Err(ShellError::Generic(
+                        GenericError::new(
+                            "Invalid environment file",
+                            format!("unknown key '{key}' in '{}'", file.display()),
+                            span,
+                        )
+                        .with_help("supported keys are `name`, `env` and `path`"),
+                    ));
+                }
+            }
+        }
+
+        for prohibited in ["FILE_PWD", "CURRENT_FILE", "PWD", ACTIVE_ENV_VAR] {
+            if env.vars.contains(prohibited) {
+                return Err(ShellError::AutomaticEnvVarSetManually {
+                    envvar_name: prohibited.to_string(),
+                    span,
+                });
+            }
+        }
+
+        Ok(env)
+    }
+
+    /// Activate the environment in a new overlay on top of `stack`.
+    fn apply(self, engine_state: &EngineState, stack: &mut Stack, span: Span) {
+        let path = if self.path.is_empty() {
+            None
+        } else {
+            let current = match stack.get_env_var(engine_state, "path") {
+                Some(Value::List { vals, .. }) => vals.clone(),
+                Some(Value::String { val, .. }) => std::env::split_paths(val)
+                    .map(|dir| Value::string(dir.to_string_lossy(), span))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            let dirs = self
+                .path
+                .iter()
+                .map(|dir| Value::string(dir.to_string_lossy(), span))
+                .chain(current)
+                .collect();
+            Some(Value::list(dirs, span))
+        };
+
+        stack.add_overlay(self.name.clone());
+
+        for (env_name, value) in self.vars {
+            stack.add_env_var(env_name, value);
+        }
+
+        if let Some(path) = path {
+            let path_name = if cfg!(windows) { "Path" } else { "PATH" };
+            stack.add_env_var(path_name.into(), path);
+        }
+
+        stack.add_env_var(
+            ACTIVE_ENV_VAR.into(),
+            Value::record(
+                record! {
+                    "name" => Value::string(self.name, span),
+                    "dir" => Value::string(self.dir.to_string_lossy(), span),
+                    "file" => Value::string(self.file.to_string_lossy(), span),
+                },
+                span,
+            ),
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::env::{LOCAL_CONFIG_FILE, TrustStore, find_local_config};
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::shell_error::generic::GenericError;
+use std::path::PathBuf;
+
+#[derive(Clone)]
+pub struct ConfigTrust;
+
+impl Command for ConfigTrust {
+    fn name(&self) -> &str {
+        "config trust"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .optional(
+                "file",
+                SyntaxShape::Filepath,
+                "File to trust. Defaults to the closest `.nu.toml`.",
+            )
+            .category(Category::Env)
+    }
+
+    fn description(&self) -> &str {
+        "Allow a per-directory `.nu.toml` or an environment file to be loaded."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Trust is tied to the contents of the file: once it is modified, it has to be trusted again. Trusted files are listed in `$nu.data-dir/trusted_env_files.txt`."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["allow", "direnv", "local", "project"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let (file, span) = trust_target(engine_state, stack, call)?;
+        let contents = std::fs::read(&file).map_err(|err| IoError::new(err, span, file.clone()))?;
+
+        let mut store = TrustStore::load(engine_state, call.head)?;
+        store.trust(&file, &contents);
+        store.save(call.head)?;
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Trust the `.nu.toml` of the current project.",
+                example: "config trust",
+                result: None,
+            },
+            Example {
+                description: "Trust a specific file.",
+                example: "config trust ~/projects/demo/.nu.toml",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[derive(Clone)]
+pub struct ConfigUntrust;
+
+impl Command for ConfigUntrust {
+    fn name(&self) -> &str {
+        "config untrust"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .optional(
+                "file",
+                SyntaxShape::Filepath,
+                "File to stop trusting. Defaults to the closest `.nu.toml`.",
+            )
+            .category(Category::Env)
+    }
+
+    fn description(&self) -> &str {
+        "Revoke the trust given to a file with `config trust`."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["deny", "revoke", "direnv", "local", "project"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let (file, span) = trust_target(engine_state, stack, call)?;
+
+        let mut store = TrustStore::load(engine_state, call.head)?;
+        if !store.untrust(&file) {
+            return Err(ShellError::Generic(GenericError::new(
+                "File is not trusted",
+                format!("'{}' is not in the list of trusted files", file.display()),
+                span,
+            )));
+        }
+        store.save(call.head)?;
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Stop loading the `.nu.toml` of the current project.",
+            example: "config untrust",
+            result: None,
+        }]
+    }
+}
+
+/// Resolve the file given to `config trust`/`config untrust`, falling back to the closest
+/// `.nu.toml`.
+fn trust_target(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+) -> Result<(PathBuf, Span), ShellError> {
+    let file: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+    let cwd = engine_state.cwd(Some(stack))?;
+
+    match file {
+        Some(file) => Ok((expand_path_with(&file.item, cwd.as_std_path(), true), file.span)),
+        None => find_local_config(cwd.as_std_path())
+            .map(|file| (file, call.head))
+            .ok_or_else(|| {
+                ShellError::Generic(GenericError::new(
+                    format!("No {LOCAL_CONFIG_FILE} found"),
+                    format!(
+                        "neither the current directory nor its parents contain a {LOCAL_CONFIG_FILE}"
+                    ),
+                    call.head,
+                ))
+            }),
+    }
+}
@@ -3,6 +3,8 @@ mod config_env;
 mod config_flatten;
 mod config_nu;
 mod config_reset;
+#[cfg(feature = "os")]
+mod config_trust;
 mod config_use_colors;
 
 pub use config_::ConfigMeta;
@@ -10,4 +12,6 @@ pub use config_env::ConfigEnv;
 pub use config_flatten::ConfigFlatten;
 pub use config_nu::ConfigNu;
 pub use config_reset::ConfigReset;
+#[cfg(feature = "os")]
+pub use config_trust::{ConfigTrust, ConfigUntrust};
 pub use config_use_colors::ConfigUseColors;
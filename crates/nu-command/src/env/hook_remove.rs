@@ -0,0 +1,126 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+
+/// Hooks holding a list of hooks, besides the ones under `env_change`.
+const LIST_HOOKS: &[&str] = &[
+    "pre_prompt",
+    "pre_execution",
+    "pre_render",
+    "post_command_error",
+];
+
+#[derive(Clone)]
+pub struct HookRemove;
+
+impl Command for HookRemove {
+    fn name(&self) -> &str {
+        "hook remove"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required(
+                "hook",
+                SyntaxShape::String,
+                "Hook to remove from, e.g. `pre_prompt` or `env_change.PWD`.",
+            )
+            .required("name", SyntaxShape::String, "Name of the hook to remove.")
+            .category(Category::Env)
+    }
+
+    fn description(&self) -> &str {
+        "Remove a named hook from `$env.config.hooks`."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Only hooks given in record form with a `name` field can be removed, e.g. `{ name: greet, code: { print hello } }`. All hooks with that name are removed."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["hooks", "unregister", "delete"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let hook: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let name: Spanned<String> = call.req(engine_state, stack, 1)?;
+
+        let path: Vec<&str> = match hook.item.split_once('.') {
+            Some(("env_change", env_name)) => vec!["hooks", "env_change", env_name],
+            None if LIST_HOOKS.contains(&hook.item.as_str()) => vec!["hooks", &hook.item],
+            _ => {
+                return Err(ShellError::Generic(
+                    GenericError::new(
+                        "Invalid hook",
+                        format!("'{}' is not a list of hooks", hook.item),
+                        hook.span,
+                    )
+                    .with_help(format!(
+                        "expected one of {}, or env_change.<variable>",
+                        LIST_HOOKS.join(", ")
+                    )),
+                ));
+            }
+        };
+
+        let mut config = (*stack.get_config(engine_state))
+            .clone()
+            .into_value(call.head);
+
+        let mut removed = false;
+        if let Some(Value::List { vals, .. }) = lookup_mut(&mut config, &path) {
+            let vals = vals.to_mut();
+            let len = vals.len();
+            vals.retain(|hook| {
+                hook.as_record()
+                    .ok()
+                    .and_then(|record| record.get("name"))
+                    .and_then(|hook_name| hook_name.as_str().ok())
+                    != Some(name.item.as_str())
+            });
+            removed = vals.len() != len;
+        }
+
+        if !removed {
+            return Err(ShellError::Generic(GenericError::new(
+                "Hook not found",
+                format!("no hook named '{}' in {}", name.item, hook.item),
+                name.span,
+            )));
+        }
+
+        stack.add_env_var("config".into(), config);
+        stack.update_config(engine_state)?;
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Register a named hook, then remove it.",
+                example: "$env.config.hooks.pre_prompt ++= [{ name: greet, code: { print hello } }]; hook remove pre_prompt greet",
+                result: None,
+            },
+            Example {
+                description: "Remove a named `env_change` hook.",
+                example: "hook remove env_change.PWD auto-venv",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Follow `path` through nested records of `value`.
+fn lookup_mut<'a>(value: &'a mut Value, path: &[&str]) -> Option<&'a mut Value> {
+    path.iter().try_fold(value, |value, key| match value {
+        Value::Record { val, .. } => val.to_mut().get_mut(key),
+        _ => None,
+    })
+}
@@ -1,4 +1,7 @@
 use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+
+use crate::parse_dotenv;
 
 #[derive(Clone)]
 pub struct LoadEnv;
@@ -12,6 +15,12 @@ impl Command for LoadEnv {
         "Loads an environment update from a record."
     }
 
+    fn extra_description(&self) -> &str {
+        "With --file, the variables of a .env file are loaded first, as read by `from dotenv`. \
+References to other variables aren't expanded, use `open --raw | from dotenv --interpolate | \
+load-env` for that."
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("load-env")
             .input_output_types(vec![
@@ -27,6 +36,12 @@ impl Command for LoadEnv {
                 SyntaxShape::record(),
                 "The record to use for updates.",
             )
+            .named(
+                "file",
+                SyntaxShape::Filepath,
+                "A .env file to load the variables from, before the ones of the record.",
+                Some('f'),
+            )
             .category(Category::FileSystem)
     }
 
@@ -38,12 +53,22 @@ impl Command for LoadEnv {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let arg: Option<Record> = call.opt(engine_state, stack, 0)?;
+        let file: Option<Spanned<String>> = call.get_flag(engine_state, stack, "file")?;
         let span = call.head;
 
-        let record = match arg {
+        let mut record = match &file {
+            Some(file) => read_dotenv_file(engine_state, stack, file)?,
+            None => Record::new(),
+        };
+        let update = match arg {
             Some(record) => record,
             None => match input {
                 PipelineData::Value(Value::Record { val, .. }, ..) => val.into_owned(),
+                PipelineData::Empty | PipelineData::Value(Value::Nothing { .. }, ..)
+                    if file.is_some() =>
+                {
+                    Record::new()
+                }
                 _ => {
                     return Err(ShellError::UnsupportedInput {
                         msg: "'load-env' expects a single record".into(),
@@ -54,6 +79,7 @@ impl Command for LoadEnv {
                 }
             },
         };
+        record.extend(update);
 
         for prohibited in ["FILE_PWD", "CURRENT_FILE", "PWD"] {
             if record.contains(prohibited) {
@@ -82,10 +108,33 @@ impl Command for LoadEnv {
                 example: "load-env {NAME: ABE, AGE: UNKNOWN}; $env.NAME",
                 result: Some(Value::test_string("ABE")),
             },
+            Example {
+                description: "Load variables from a .env file.",
+                example: "load-env --file .env",
+                result: None,
+            },
+            Example {
+                description: "Load variables from a .env file, overriding some of them.",
+                example: "load-env --file .env {DEBUG: 'true'}",
+                result: None,
+            },
         ]
     }
 }
 
+/// Read the variables of a .env file, without interpolating them.
+fn read_dotenv_file(
+    engine_state: &EngineState,
+    stack: &Stack,
+    file: &Spanned<String>,
+) -> Result<Record, ShellError> {
+    let cwd = engine_state.cwd(Some(stack))?;
+    let path = nu_path::expand_path_with(&file.item, &cwd, true);
+    let text =
+        std::fs::read_to_string(&path).map_err(|err| IoError::new(err, file.span, path.clone()))?;
+    parse_dotenv(&text, false, |_| None, file.span)
+}
+
 #[cfg(test)]
 mod tests {
     use super::LoadEnv;
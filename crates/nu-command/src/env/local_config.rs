@@ -0,0 +1,197 @@
+//! Per-directory settings loaded from a trusted `.nu.toml` file.
+//!
+//! The file is plain data, so loading it can't run arbitrary code:
+//!
+//! ```toml
+//! [env]
+//! RUST_LOG = "debug"
+//!
+//! [aliases]
+//! t = "cargo test --workspace"
+//!
+//! [completions]
+//! just = ["build", "test", "release"]
+//! ```
+//!
+//! The REPL turns it into a small script that activates a dedicated overlay, so leaving the
+//! directory is a matter of hiding that overlay again.
+
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+use nuon::{ToNuonConfig, to_nuon};
+use std::path::{Path, PathBuf};
+
+/// Name of the per-directory settings file.
+pub const LOCAL_CONFIG_FILE: &str = ".nu.toml";
+
+/// Overlay the per-directory settings are activated in.
+pub const LOCAL_CONFIG_OVERLAY: &str = "local-config";
+
+/// Environment variable holding the path of the loaded `.nu.toml`.
+///
+/// It lives inside the overlay, so it is removed together with the settings.
+const LOCAL_CONFIG_VAR: &str = "NU_LOCAL_CONFIG";
+
+/// Find the `.nu.toml` closest to `cwd`, looking through its ancestors.
+pub fn find_local_config(cwd: &Path) -> Option<PathBuf> {
+    cwd.ancestors()
+        .map(|dir| dir.join(LOCAL_CONFIG_FILE))
+        .find(|file| file.is_file())
+}
+
+/// Path of the `.nu.toml` whose settings are currently active, if any.
+pub fn loaded_local_config(engine_state: &EngineState, stack: &Stack) -> Option<PathBuf> {
+    stack
+        .get_env_var(engine_state, LOCAL_CONFIG_VAR)?
+        .coerce_str()
+        .ok()
+        .map(|file| PathBuf::from(file.as_ref()))
+}
+
+/// Build the script activating the settings of `file`.
+///
+/// Only the `env`, `aliases` and `completions` tables are supported. Alias bodies must be a single
+/// command, so defining them can't sneak in extra statements that would run when the file is
+/// loaded. Completions are a list of words suggested for the first argument of an external
+/// command, and are declared with an `extern` that accepts any other arguments.
+pub fn local_config_source(
+    engine_state: &EngineState,
+    file: &Path,
+    contents: &[u8],
+    span: Span,
+) -> Result<String, ShellError> {
+    let invalid = |msg: String| {
+        ShellError::Generic(
+            GenericError::new(
+                format!("Invalid {LOCAL_CONFIG_FILE}"),
+                format!("{msg} in '{}'", file.display()),
+                span,
+            )
+            .with_help("supported tables are `env`, `aliases` and `completions`"),
+        )
+    };
+
+    let contents = String::from_utf8_lossy(contents).into_owned();
+    let Value::Record { val: record, .. } = crate::toml_str_to_value(contents, span)? else {
+        return Err(invalid("expected a table".into()));
+    };
+
+    let mut env = Record::new();
+    let mut aliases = Vec::new();
+    let mut completions = Vec::new();
+    for (key, value) in record.into_owned() {
+        match key.as_str() {
+            "env" => env = value.into_record()?,
+            "aliases" => {
+                for (name, body) in value.into_record()? {
+                    let body = body.coerce_into_string()?;
+                    if !is_valid_alias_name(&name) {
+                        return Err(invalid(format!("invalid alias name '{name}'")));
+                    }
+                    if body.contains([';', '|', '\n', '\r']) {
+                        return Err(invalid(format!("alias '{name}' must be a single command")));
+                    }
+                    aliases.push((name, body));
+                }
+            }
+            "completions" => {
+                for (name, words) in value.into_record()? {
+                    if !is_valid_alias_name(&name) {
+                        return Err(invalid(format!("invalid command name '{name}'")));
+                    }
+                    let words = words
+                        .into_list()?
+                        .into_iter()
+                        .map(|word| {
+                            word.coerce_into_string()
+                                .map(|word| Value::string(word, span))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    completions.push((name, Value::list(words, span)));
+                }
+            }
+            _ => return Err(invalid(format!("unknown table '{key}'"))),
+        }
+    }
+
+    for prohibited in ["FILE_PWD", "CURRENT_FILE", "PWD", LOCAL_CONFIG_VAR] {
+        if env.contains(prohibited) {
+            return Err(ShellError::AutomaticEnvVarSetManually {
+                envvar_name: prohibited.to_string(),
+                span,
+            });
+        }
+    }
+    env.push(
+        LOCAL_CONFIG_VAR,
+        Value::string(file.to_string_lossy(), span),
+    );
+
+    let env = to_nuon(
+        engine_state,
+        &Value::record(env, span),
+        ToNuonConfig::default().span(Some(span)),
+    )?;
+
+    let mut source = format!("overlay new {LOCAL_CONFIG_OVERLAY}\nload-env {env}\n");
+    for (name, body) in aliases {
+        source.push_str(&format!("alias {name} = {body}\n"));
+    }
+    for (name, words) in completions {
+        let words = to_nuon(
+            engine_state,
+            &words,
+            ToNuonConfig::default().span(Some(span)),
+        )?;
+        source.push_str(&format!(
+            "extern \"{name}\" [subcommand?: string@{words}, ...args]\n"
+        ));
+    }
+
+    Ok(source)
+}
+
+/// Script deactivating the settings loaded by [`local_config_source`].
+pub fn unload_local_config_source() -> String {
+    format!("overlay hide {LOCAL_CONFIG_OVERLAY} --keep-env [PWD OLDPWD]")
+}
+
+fn is_valid_alias_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completions_become_externs() {
+        let engine_state = EngineState::new();
+        let source = local_config_source(
+            &engine_state,
+            Path::new(".nu.toml"),
+            b"[completions]\njust = [\"build\", \"test\"]\n",
+            Span::test_data(),
+        )
+        .expect("valid local config");
+
+        assert!(source.contains("extern \"just\" [subcommand?: string@[build, test], ...args]"));
+    }
+
+    #[test]
+    fn completions_must_be_a_list() {
+        let engine_state = EngineState::new();
+        let result = local_config_source(
+            &engine_state,
+            Path::new(".nu.toml"),
+            b"[completions]\njust = \"build\"\n",
+            Span::test_data(),
+        );
+
+        assert!(result.is_err());
+    }
+}
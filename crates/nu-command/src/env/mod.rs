@@ -1,16 +1,37 @@
+#[cfg(feature = "os")]
+mod activate;
 mod config;
 mod export_env;
+mod hook_remove;
 mod load_env;
+#[cfg(feature = "os")]
+mod local_config;
 mod source_env;
+#[cfg(feature = "os")]
+mod trust;
+mod watch;
 mod with_env;
 
+#[cfg(feature = "os")]
+pub use activate::{EnvActivate, EnvDeactivate, deactivate_env, deactivate_env_outside_dir};
 pub use config::ConfigEnv;
 pub use config::ConfigFlatten;
 pub use config::ConfigMeta;
 pub use config::ConfigNu;
 pub use config::ConfigReset;
 pub use config::ConfigUseColors;
+#[cfg(feature = "os")]
+pub use config::{ConfigTrust, ConfigUntrust};
 pub use export_env::ExportEnv;
+pub use hook_remove::HookRemove;
 pub use load_env::LoadEnv;
+#[cfg(feature = "os")]
+pub use local_config::{
+    LOCAL_CONFIG_FILE, LOCAL_CONFIG_OVERLAY, find_local_config, loaded_local_config,
+    local_config_source, unload_local_config_source,
+};
 pub use source_env::SourceEnv;
+#[cfg(feature = "os")]
+pub use trust::TrustStore;
+pub use watch::EnvWatch;
 pub use with_env::WithEnv;
@@ -0,0 +1,90 @@
+//! Allow-list of files that may change the environment when they are loaded on the user's
+//! behalf, e.g. by `env activate`.
+//!
+//! A file is identified by its path together with a hash of its contents, so editing a trusted
+//! file revokes the trust until the user accepts it again, the same way direnv handles `.envrc`.
+
+use crate::hash::hex_encode;
+use nu_engine::command_prelude::*;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Name of the allow-list file inside `$nu.data-dir`.
+const TRUST_FILE: &str = "trusted_env_files.txt";
+
+pub struct TrustStore {
+    store_path: PathBuf,
+    /// `(path, content hash)` pairs, kept in the order they were trusted.
+    entries: Vec<(String, String)>,
+}
+
+impl TrustStore {
+    /// Read the allow-list from `$nu.data-dir`. A missing file is an empty allow-list.
+    pub fn load(engine_state: &EngineState, span: Span) -> Result<Self, ShellError> {
+        let store_path = engine_state.config_dirs.data_home.join(TRUST_FILE);
+
+        let contents = match std::fs::read_to_string(&store_path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(IoError::new(err, span, store_path).into()),
+        };
+
+        // Each line has the same shape as the output of `sha256sum`: `<hash>  <path>`
+        let entries = contents
+            .lines()
+            .filter_map(|line| line.split_once("  "))
+            .map(|(hash, path)| (path.to_string(), hash.to_string()))
+            .collect();
+
+        Ok(Self {
+            store_path,
+            entries,
+        })
+    }
+
+    /// Write the allow-list back to `$nu.data-dir`.
+    pub fn save(&self, span: Span) -> Result<(), ShellError> {
+        if let Some(parent) = self.store_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| IoError::new(err, span, parent.to_path_buf()))?;
+        }
+
+        let contents: String = self
+            .entries
+            .iter()
+            .map(|(path, hash)| format!("{hash}  {path}\n"))
+            .collect();
+
+        std::fs::write(&self.store_path, contents)
+            .map_err(|err| IoError::new(err, span, self.store_path.clone()).into())
+    }
+
+    /// Whether `file` was trusted with exactly these `contents`.
+    pub fn is_trusted(&self, file: &Path, contents: &[u8]) -> bool {
+        let path = file.to_string_lossy();
+        let hash = content_hash(contents);
+        self.entries
+            .iter()
+            .any(|(trusted_path, trusted_hash)| *trusted_path == path && *trusted_hash == hash)
+    }
+
+    /// Trust `file` with its current `contents`, replacing any previous entry for it.
+    pub fn trust(&mut self, file: &Path, contents: &[u8]) {
+        let path = file.to_string_lossy().into_owned();
+        self.untrust(file);
+        self.entries.push((path, content_hash(contents)));
+    }
+
+    /// Remove `file` from the allow-list, returning whether it was trusted before.
+    pub fn untrust(&mut self, file: &Path) -> bool {
+        let path = file.to_string_lossy();
+        let len = self.entries.len();
+        self.entries
+            .retain(|(trusted_path, _)| *trusted_path != path);
+        self.entries.len() != len
+    }
+}
+
+fn content_hash(contents: &[u8]) -> String {
+    hex_encode(&Sha256::digest(contents))
+}
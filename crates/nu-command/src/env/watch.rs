@@ -0,0 +1,91 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::engine::{Closure, EnvName, EnvWatcher};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct EnvWatch;
+
+impl Command for EnvWatch {
+    fn name(&self) -> &str {
+        "env watch"
+    }
+
+    fn description(&self) -> &str {
+        "Run a closure whenever an environment variable changes in the current scope."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The closure gets the old and the new value of the variable as arguments, or null when the variable
+didn't exist or was hidden. It runs right after the change, before the next command, and the changes it
+makes to the environment are kept.
+
+The closure runs for changes made in the scope calling `env watch` and in the closures and commands
+it calls. It runs in the scope where the variable changed, so the changes it makes in a closure or a
+command without `--env` are dropped with the rest of their environment. Changes the closure makes to
+its own variable don't run it again. In the REPL, the closure keeps watching for the rest of the
+session.
+
+Unlike `$env.config.hooks.env_change`, which runs before the prompt is shown, this also works in scripts."#
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("env watch")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required(
+                "name",
+                SyntaxShape::String,
+                "The name of the environment variable to watch.",
+            )
+            .required(
+                "closure",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any, SyntaxShape::Any])),
+                "The closure to run, with the old and the new value.",
+            )
+            .category(Category::Env)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["hook", "env_change", "observe", "trigger"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let name: String = call.req(engine_state, stack, 0)?;
+        let closure: Closure = call.req(engine_state, stack, 1)?;
+
+        let last_value = stack.get_env_var(engine_state, &name).cloned();
+        Arc::make_mut(&mut stack.env_watchers).push(EnvWatcher {
+            name: EnvName::from(name),
+            closure,
+            last_value,
+            running: false,
+        });
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Print the new value of a variable each time it changes.",
+                example: "env watch FOO {|before, after| print $'FOO: ($before) -> ($after)' }; $env.FOO = 1; $env.FOO = 2",
+                result: None,
+            },
+            Example {
+                description: "Switch the toolchain when the Kubernetes config changes.",
+                example: "env watch KUBECONFIG {|_, config| $env.KUBE_CONTEXT = (kubectl config current-context) }",
+                result: None,
+            },
+            Example {
+                description: "Keep track of how often the current directory changed.",
+                example: "$env.CD_COUNT = 0; env watch PWD { $env.CD_COUNT += 1 }; cd /; cd /; $env.CD_COUNT",
+                result: None,
+            },
+        ]
+    }
+}
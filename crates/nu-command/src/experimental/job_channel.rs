@@ -0,0 +1,87 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct JobChannel;
+
+impl Command for JobChannel {
+    fn name(&self) -> &str {
+        "job channel"
+    }
+
+    fn description(&self) -> &str {
+        "Set how many messages a channel of the current job can hold."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Channels are unbounded by default, so senders never wait. Once a channel is given a capacity,
+`job send --channel` blocks while the channel holds that many messages, until this job reads one of them
+with `job recv --channel` or `job select`. This keeps fast producers from running ahead of slow consumers.
+
+Without `--capacity`, the channel becomes unbounded again.
+Messages already in the channel are kept, even if there are more than the new capacity.
+"#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("job channel")
+            .category(Category::Experimental)
+            .required("name", SyntaxShape::String, "The name of the channel.")
+            .named(
+                "capacity",
+                SyntaxShape::Int,
+                "The maximum number of messages the channel holds.",
+                None,
+            )
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["bounded", "capacity", "backpressure"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let name: String = call.req(engine_state, stack, 0)?;
+        let capacity: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "capacity")?;
+
+        let capacity = match capacity {
+            Some(capacity) if capacity.item < 1 => {
+                return Err(ShellError::NeedsPositiveValue {
+                    span: capacity.span,
+                });
+            }
+            Some(capacity) => Some(capacity.item as usize),
+            None => None,
+        };
+
+        engine_state
+            .current_job
+            .channels
+            .set_capacity(&name, capacity);
+
+        Ok(Value::nothing(call.head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "job channel work --capacity 10",
+                description: "Let the `work` channel hold at most 10 messages.",
+                result: None,
+            },
+            Example {
+                example: r#"job channel work --capacity 2
+let consumer = job id
+job spawn { 1..100 | each { job send $consumer --channel work } }
+loop { print (job recv --channel work) }"#,
+                description: "Process items sent by a producer job, which waits while two of them are unprocessed.",
+                result: None,
+            },
+        ]
+    }
+}
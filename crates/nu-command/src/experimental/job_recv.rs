@@ -9,7 +9,7 @@ use nu_engine::command_prelude::*;
 
 use nu_protocol::{
     Signals,
-    engine::{FilterTag, Mailbox},
+    engine::{ChannelError, FilterTag, Mailbox},
 };
 
 #[derive(Clone)]
@@ -37,6 +37,9 @@ If no message with the specified tag (if any) is available in the mailbox, this
 By default this command block indefinitely until a matching message arrives, but a timeout duration can be specified.
 If a timeout duration of zero is specified, it will succeed only if there already is a message in the mailbox.
 
+With `--channel`, the message is read from a named channel of the job instead of its mailbox.
+Use `job select` to wait for messages on several channels at once.
+
 Note: When using par-each, only one thread at a time can utilize this command.
 In the case of two or more threads running this command, they will wait until other threads are done using it,
 in no particular order, regardless of the specified timeout parameter.
@@ -47,6 +50,12 @@ in no particular order, regardless of the specified timeout parameter.
         Signature::build("job recv")
             .category(Category::Experimental)
             .named("tag", SyntaxShape::Int, "A tag for the message.", None)
+            .named(
+                "channel",
+                SyntaxShape::String,
+                "The name of the channel to read from, instead of the mailbox.",
+                None,
+            )
             .named(
                 "timeout",
                 SyntaxShape::Duration,
@@ -82,6 +91,25 @@ in no particular order, regardless of the specified timeout parameter.
 
         let timeout: Option<Duration> = call.get_flag(engine_state, stack, "timeout")?;
 
+        let channel: Option<Spanned<String>> = call.get_flag(engine_state, stack, "channel")?;
+
+        if let Some(channel) = channel {
+            if let Some(tag) = tag_arg {
+                return Err(ShellError::IncompatibleParameters {
+                    left_message: "channels don't use tags".into(),
+                    left_span: tag.span,
+                    right_message: "reading from this channel".into(),
+                    right_span: channel.span,
+                });
+            }
+            return engine_state
+                .current_job
+                .channels
+                .recv(&[channel.item], timeout, engine_state.signals())
+                .map(|(_, message)| message)
+                .map_err(|err| channel_error(err, head));
+        }
+
         let mut mailbox = engine_state
             .current_job
             .mailbox
@@ -121,10 +149,23 @@ in no particular order, regardless of the specified timeout parameter.
                 description: "Receive a message from a newly-spawned job.",
                 result: None,
             },
+            Example {
+                example: "job spawn { 'done' | job send 0 --channel status }; job recv --channel status",
+                description: "Receive a message from a named channel.",
+                result: None,
+            },
         ]
     }
 }
 
+/// The error of a command waiting on channels.
+pub(super) fn channel_error(error: ChannelError, span: Span) -> ShellError {
+    match error {
+        ChannelError::Timeout => JobError::RecvTimeout { span }.into(),
+        ChannelError::Interrupted | ChannelError::Closed => ShellError::Interrupted { span },
+    }
+}
+
 fn recv_without_time_limit(
     mailbox: &mut Mailbox,
     tag: Option<FilterTag>,
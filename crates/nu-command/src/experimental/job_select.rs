@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use nu_engine::command_prelude::*;
+
+use super::job_recv::channel_error;
+
+#[derive(Clone)]
+pub struct JobSelect;
+
+impl Command for JobSelect {
+    fn name(&self) -> &str {
+        "job select"
+    }
+
+    fn description(&self) -> &str {
+        "Read a message from the first of several channels to have one."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"This command waits until one of the given channels of the current job has a message, then
+reads and returns it together with the name of its channel, as a record with `channel` and `message` columns.
+When several channels already have messages, the first one in the given list is read from.
+
+By default this command blocks indefinitely until a message arrives, but a timeout duration can be specified.
+If a timeout duration of zero is specified, it will succeed only if one of the channels already has a message.
+"#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("job select")
+            .category(Category::Experimental)
+            .required(
+                "channels",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "The names of the channels to read from.",
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "The maximum time duration to wait for.",
+                None,
+            )
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Record(
+                    [
+                        ("channel".into(), Type::String),
+                        ("message".into(), Type::Any),
+                    ]
+                    .into(),
+                ),
+            )])
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["receive", "wait", "channel", "multiplex"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+
+        let channels: Vec<String> = call.req(engine_state, stack, 0)?;
+        let timeout: Option<Duration> = call.get_flag(engine_state, stack, "timeout")?;
+
+        let (channel, message) = engine_state
+            .current_job
+            .channels
+            .recv(&channels, timeout, engine_state.signals())
+            .map_err(|err| channel_error(err, head))?;
+
+        Ok(Value::record(
+            record! {
+                "channel" => Value::string(channel, head),
+                "message" => message.into_value(head)?,
+            },
+            head,
+        )
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "job select [results errors]",
+                description: "Wait for a message on either the `results` or the `errors` channel.",
+                result: None,
+            },
+            Example {
+                example: "job select [results] --timeout 5sec",
+                description: "Wait for a message on the `results` channel for at most 5 seconds.",
+                result: None,
+            },
+            Example {
+                example: "'hi' | job send 0 --channel greetings; job select [results greetings]",
+                description: "Read a message that was sent to a channel of the main thread.",
+                result: Some(Value::test_record(record! {
+                    "channel" => Value::test_string("greetings"),
+                    "message" => Value::test_string("hi"),
+                })),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(JobSelect)
+    }
+}
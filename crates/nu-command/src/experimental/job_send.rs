@@ -1,5 +1,10 @@
 use nu_engine::command_prelude::*;
-use nu_protocol::{JobId, engine::FilterTag};
+use nu_protocol::{
+    JobId,
+    engine::{ChannelError, FilterTag},
+};
+
+use super::job_recv::channel_error;
 
 #[derive(Clone)]
 pub struct JobSend;
@@ -20,9 +25,13 @@ in a first-in-first-out fashion with `job recv`. When it does so, it may additio
 in which case it will only read messages sent with the exact same filter tag.
 In particular, the id 0 refers to the main/initial nushell thread.
 
+With `--channel`, the message is sent to a named channel of the job instead, which it reads with
+`job recv --channel` or `job select`. If the job bounded the channel with `job channel --capacity`
+and the channel is full, this command blocks until the job receives a message from it.
+
 A message can be any nushell value, and streams are always collected before being sent.
 
-This command never blocks.
+Without `--channel`, this command never blocks.
 "
     }
 
@@ -35,6 +44,12 @@ This command never blocks.
                 "The id of the job to send the message to.",
             )
             .named("tag", SyntaxShape::Int, "A tag for the message.", None)
+            .named(
+                "channel",
+                SyntaxShape::String,
+                "The name of the channel to send the message to.",
+                None,
+            )
             .input_output_types(vec![(Type::Any, Type::Nothing)])
             .allow_variants_without_examples(true)
     }
@@ -63,6 +78,20 @@ This command never blocks.
             return Err(ShellError::NeedsPositiveValue { span: tag.span });
         }
 
+        let channel: Option<Spanned<String>> = call.get_flag(engine_state, stack, "channel")?;
+
+        if let Some(channel) = channel {
+            if let Some(tag) = tag_arg {
+                return Err(ShellError::IncompatibleParameters {
+                    left_message: "channels don't use tags".into(),
+                    left_span: tag.span,
+                    right_message: "sending to this channel".into(),
+                    right_span: channel.span,
+                });
+            }
+            return send_to_channel(engine_state, id_arg, &channel.item, input, head);
+        }
+
         let tag = tag_arg.map(|it| it.item as FilterTag);
 
         if id == JobId::ZERO {
@@ -112,6 +141,56 @@ This command never blocks.
                 description: "Send a message from a newly-spawned job to the main thread (which always has an ID of 0).",
                 result: None,
             },
+            Example {
+                example: "job spawn { 1..10 | each { job send 0 --channel numbers } }; job recv --channel numbers",
+                description: "Send messages to a named channel of the main thread.",
+                result: None,
+            },
         ]
     }
 }
+
+fn send_to_channel(
+    engine_state: &EngineState,
+    id_arg: Spanned<usize>,
+    channel: &str,
+    input: PipelineData,
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    let id = JobId::new(id_arg.item);
+
+    let channels = if id == JobId::ZERO {
+        engine_state.root_job_channels.clone()
+    } else {
+        let jobs = engine_state.jobs.lock().expect("failed to acquire lock");
+
+        match jobs.lookup(id) {
+            Some(nu_protocol::engine::Job::Thread(thread_job)) => thread_job.channels.clone(),
+            Some(nu_protocol::engine::Job::Frozen(_)) => {
+                return Err(JobError::AlreadyFrozen {
+                    span: id_arg.span,
+                    id,
+                }
+                .into());
+            }
+            None => {
+                return Err(JobError::NotFound {
+                    span: id_arg.span,
+                    id,
+                }
+                .into());
+            }
+        }
+        // the jobs lock is released here, as sending to a full channel blocks
+    };
+
+    match channels.send(channel, input, engine_state.signals()) {
+        Ok(()) => Ok(Value::nothing(head).into_pipeline_data()),
+        Err(ChannelError::Closed) => Err(JobError::NotFound {
+            span: id_arg.span,
+            id,
+        }
+        .into()),
+        Err(err) => Err(channel_error(err, head)),
+    }
+}
@@ -87,6 +87,7 @@ impl Command for JobSpawn {
                 id,
                 background_thread_job: Some(thread_job),
                 mailbox: Arc::new(Mutex::new(Mailbox::new(recv))),
+                channels: thread_job.channels.clone(),
             };
 
             id
@@ -114,6 +115,9 @@ impl Command for JobSpawn {
 
                     jobs.remove_job(id);
                 }
+
+                // wake up the jobs waiting to send to a full channel of this job
+                job_state.current_job.channels.close();
             });
 
         match result {
@@ -9,11 +9,15 @@ mod job_spawn;
 #[cfg(all(unix, feature = "os"))]
 mod job_unfreeze;
 
+#[cfg(not(target_family = "wasm"))]
+mod job_channel;
 #[cfg(not(target_family = "wasm"))]
 mod job_flush;
 #[cfg(not(target_family = "wasm"))]
 mod job_recv;
 #[cfg(not(target_family = "wasm"))]
+mod job_select;
+#[cfg(not(target_family = "wasm"))]
 mod job_send;
 
 pub use is_admin::IsAdmin;
@@ -24,11 +28,15 @@ pub use job_kill::JobKill;
 pub use job_list::JobList;
 pub use job_spawn::JobSpawn;
 
+#[cfg(not(target_family = "wasm"))]
+pub use job_channel::JobChannel;
 #[cfg(not(target_family = "wasm"))]
 pub use job_flush::JobFlush;
 #[cfg(not(target_family = "wasm"))]
 pub use job_recv::JobRecv;
 #[cfg(not(target_family = "wasm"))]
+pub use job_select::JobSelect;
+#[cfg(not(target_family = "wasm"))]
 pub use job_send::JobSend;
 
 #[cfg(all(unix, feature = "os"))]
@@ -0,0 +1,266 @@
+use nu_engine::command_prelude::*;
+#[cfg(any(unix, windows))]
+use nu_protocol::shell_error::io::IoError;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone)]
+pub struct Mkpipe;
+
+impl Command for Mkpipe {
+    fn name(&self) -> &str {
+        "mkpipe"
+    }
+
+    fn description(&self) -> &str {
+        "Create named pipes (FIFOs)."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Data written to a named pipe with `save` can be read from it with `open --raw`, so separate
+jobs or processes can be connected through it. Opening one end of a pipe waits until the other end
+is opened too.
+
+On Unix this creates a FIFO like `mkfifo`, which stays until it is removed with `rm`.
+
+On Windows the path must start with `\\.\pipe\`. The named pipe exists only while the nushell
+process that created it is running, and connects one writer to one reader at a time."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["mkfifo", "fifo", "named pipe", "create", "ipc"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("mkpipe")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .rest(
+                "rest",
+                SyntaxShape::Filepath,
+                "The path(s) of the pipe(s) to create.",
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+        let paths: Vec<Spanned<PathBuf>> = call.rest(engine_state, stack, 0)?;
+
+        if paths.is_empty() {
+            return Err(ShellError::MissingParameter {
+                param_name: "requires pipe paths".to_string(),
+                span: call.head,
+            });
+        }
+
+        for path in paths {
+            let expanded = nu_path::expand_path_with(&path.item, &cwd, true);
+            create_pipe(&expanded, path.span)?;
+        }
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Make a FIFO named fifo.",
+                example: "mkpipe fifo",
+                result: None,
+            },
+            Example {
+                description: "Pass data from a background job through a FIFO.",
+                example: "mkpipe fifo; job spawn { ls | to json | save fifo }; open --raw fifo | from json",
+                result: None,
+            },
+            Example {
+                description: "Make a named pipe on Windows.",
+                example: r"mkpipe \\.\pipe\nu-example",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(unix)]
+fn create_pipe(path: &Path, span: Span) -> Result<(), ShellError> {
+    use nix::sys::stat::Mode;
+
+    // The umask is applied to the mode, like for regular files
+    nix::unistd::mkfifo(path, Mode::from_bits_truncate(0o666))
+        .map_err(|errno| IoError::new(std::io::Error::from(errno), span, PathBuf::from(path)))?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn create_pipe(path: &Path, span: Span) -> Result<(), ShellError> {
+    use nu_protocol::shell_error::generic::GenericError;
+    use std::{os::windows::ffi::OsStrExt, thread};
+
+    let is_pipe_path = path
+        .to_str()
+        .and_then(|path| path.get(..9))
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(r"\\.\pipe\"));
+    if !is_pipe_path {
+        return Err(ShellError::Generic(
+            GenericError::new(
+                "Invalid named pipe path",
+                "named pipes can only be created in the pipe namespace",
+                span,
+            )
+            .with_help(r"use a path like `\\.\pipe\name`"),
+        ));
+    }
+
+    let name: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+
+    // Create the first instance here, so an error is reported if the pipe already exists
+    let first = windows_pipe::create_instance(&name, true)
+        .map_err(|err| IoError::new(err, span, PathBuf::from(path)))?;
+
+    thread::Builder::new()
+        .name(format!("named pipe {}", path.display()))
+        .spawn(move || windows_pipe::relay(first, name))
+        .map_err(|err| IoError::new(err, span, PathBuf::from(path)))?;
+
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn create_pipe(_path: &Path, span: Span) -> Result<(), ShellError> {
+    use nu_protocol::shell_error::generic::GenericError;
+
+    Err(ShellError::Generic(GenericError::new(
+        "Named pipes are not supported on this platform",
+        "can't create a named pipe",
+        span,
+    )))
+}
+
+/// Named pipes on Windows have a server and clients, unlike a FIFO that is opened by a writer and
+/// a reader. To get FIFO semantics, the server side here accepts two clients at a time and copies
+/// whatever one of them writes to the other, until either of them disconnects.
+#[cfg(windows)]
+mod windows_pipe {
+    use std::{
+        fs::File,
+        io,
+        os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle},
+        sync::mpsc,
+        thread,
+    };
+    use windows::{
+        Win32::{
+            Foundation::{ERROR_PIPE_CONNECTED, HANDLE},
+            Storage::FileSystem::{
+                FILE_FLAG_FIRST_PIPE_INSTANCE, FlushFileBuffers, PIPE_ACCESS_DUPLEX,
+            },
+            System::Pipes::{
+                ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, PIPE_READMODE_BYTE,
+                PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+            },
+        },
+        core::PCWSTR,
+    };
+
+    const BUFFER_SIZE: u32 = 64 * 1024;
+
+    /// Create a new instance of the named pipe. `name` is NUL-terminated.
+    pub(super) fn create_instance(name: &[u16], first: bool) -> io::Result<File> {
+        let open_mode = if first {
+            PIPE_ACCESS_DUPLEX | FILE_FLAG_FIRST_PIPE_INSTANCE
+        } else {
+            PIPE_ACCESS_DUPLEX
+        };
+        // SAFETY: `name` is a valid NUL-terminated wide string
+        let handle = unsafe {
+            CreateNamedPipeW(
+                PCWSTR(name.as_ptr()),
+                open_mode,
+                PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT,
+                PIPE_UNLIMITED_INSTANCES,
+                BUFFER_SIZE,
+                BUFFER_SIZE,
+                0,
+                None,
+            )
+        };
+        if handle.is_invalid() {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the handle was just created and is owned by nobody else
+        Ok(File::from(unsafe {
+            OwnedHandle::from_raw_handle(handle.0)
+        }))
+    }
+
+    fn handle(pipe: &File) -> HANDLE {
+        HANDLE(pipe.as_raw_handle())
+    }
+
+    /// Wait for a client to connect to the instance.
+    fn connect(pipe: &File) -> io::Result<()> {
+        // SAFETY: the handle is a valid named pipe instance for as long as `pipe` lives
+        match unsafe { ConnectNamedPipe(handle(pipe), None) } {
+            Ok(()) => Ok(()),
+            // The client connected between creating the instance and waiting for it
+            Err(err) if err.code() == ERROR_PIPE_CONNECTED.to_hresult() => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Connect pairs of clients to each other, for as long as the process runs.
+    pub(super) fn relay(first: File, name: Vec<u16>) {
+        let mut next = Some(first);
+        loop {
+            let result = next
+                .take()
+                .map_or_else(|| create_instance(&name, false), Ok)
+                .and_then(|pipe| relay_pair(pipe, &name));
+            if result.is_err() {
+                // Something is wrong with the pipe itself, so give it up
+                return;
+            }
+        }
+    }
+
+    fn relay_pair(one: File, name: &[u16]) -> io::Result<()> {
+        connect(&one)?;
+        let other = create_instance(name, false)?;
+        connect(&other)?;
+
+        let (tx, rx) = mpsc::channel();
+        for (mut from, mut to) in [
+            (one.try_clone()?, other.try_clone()?),
+            (other.try_clone()?, one.try_clone()?),
+        ] {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let _ = io::copy(&mut from, &mut to);
+                let _ = tx.send(to);
+            });
+        }
+
+        // As soon as one client disconnects, make sure the other one has read everything that was
+        // copied to it, and then disconnect it too, which ends the copy in the other direction
+        if let Ok(to) = rx.recv() {
+            // SAFETY: the handles are valid named pipe instances
+            unsafe {
+                let _ = FlushFileBuffers(handle(&to));
+                let _ = DisconnectNamedPipe(handle(&one));
+                let _ = DisconnectNamedPipe(handle(&other));
+            }
+        }
+        let _ = rx.recv();
+        Ok(())
+    }
+}
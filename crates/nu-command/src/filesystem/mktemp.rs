@@ -1,6 +1,6 @@
-use nu_engine::command_prelude::*;
-use nu_protocol::shell_error::generic::GenericError;
-use std::path::PathBuf;
+use nu_engine::{ClosureEvalOnce, command_prelude::*};
+use nu_protocol::{engine::Closure, shell_error::generic::GenericError};
+use std::path::{Path, PathBuf};
 use uucore::{localized_help_template, translate};
 
 #[derive(Clone)]
@@ -28,7 +28,8 @@ impl Command for Mktemp {
 
     fn signature(&self) -> Signature {
         Signature::build("mktemp")
-            .input_output_types(vec![(Type::Nothing, Type::String)])
+            // The path, or the output of the `--scope` closure
+            .input_output_types(vec![(Type::Nothing, Type::Any)])
             .optional(
                 "template",
                 SyntaxShape::String,
@@ -39,6 +40,7 @@ impl Command for Mktemp {
             .switch("tmpdir", "Interpret TEMPLATE relative to the system temporary directory. It is implied if template is not provided.", Some('t'))
             .switch("directory", "Create a directory instead of a file.", Some('d'))
             .switch("dry", "Don't create a file and just return the path that would have been created.", None)
+            .named("scope", SyntaxShape::Closure(Some(vec![SyntaxShape::String])), "Run a closure with the path, remove the file or directory when it finishes (even if it fails), and return the closure's output.", Some('s'))
             .category(Category::FileSystem)
     }
 
@@ -64,6 +66,11 @@ impl Command for Mktemp {
                 example: "mktemp -d",
                 result: Some(Value::test_string("/tmp/tmp.NMw9fJr8K0")),
             },
+            Example {
+                description: "Work in a temporary directory that is removed afterwards.",
+                example: "mktemp -d --scope {|dir| cd $dir; ^git clone https://github.com/nushell/nushell.git; ls nushell | length }",
+                result: None,
+            },
         ]
     }
 
@@ -87,11 +94,19 @@ impl Command for Mktemp {
         let dry_run = call.has_flag(engine_state, stack, "dry")?;
         let suffix = call.get_flag(engine_state, stack, "suffix")?;
         let tmpdir = template.is_none() || call.has_flag(engine_state, stack, "tmpdir")?;
+        let scope: Option<Closure> = call.get_flag(engine_state, stack, "scope")?;
         let tmpdir_path = call
             .get_flag(engine_state, stack, "tmpdir-path")?
             .map(|i: Spanned<PathBuf>| i.item);
         let template = template.unwrap_or("tmp.XXXXXXXXXX".to_string()); // same as default in coreutils
 
+        if dry_run && scope.is_some() {
+            return Err(ShellError::IncompatibleParametersSingle {
+                msg: "--dry can't be used with --scope".into(),
+                span,
+            });
+        }
+
         let tmpdir = if tmpdir_path.is_some() {
             tmpdir_path
         } else if directory || tmpdir {
@@ -122,6 +137,32 @@ impl Command for Mktemp {
                 )));
             }
         };
+
+        if let Some(scope) = scope {
+            let path = PathBuf::from(&res);
+            // The output is collected so the closure is done with the path before it's removed
+            let result = ClosureEvalOnce::new(engine_state, stack, scope)
+                .run_with_value(Value::string(res, span))
+                .and_then(|output| output.into_value(span));
+            let removed = remove_temp(&path).map_err(|err| IoError::new(err, span, path));
+            let value = result?;
+            removed?;
+            return Ok(value.into_pipeline_data());
+        }
+
         Ok(PipelineData::value(Value::string(res, span), None))
     }
 }
+
+/// Remove a scoped temporary file or directory, unless the closure removed it already.
+fn remove_temp(path: &Path) -> std::io::Result<()> {
+    let removed = match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => std::fs::remove_dir_all(path),
+        Ok(_) => std::fs::remove_file(path),
+        Err(err) => Err(err),
+    };
+    match removed {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        removed => removed,
+    }
+}
@@ -3,6 +3,7 @@ mod du;
 mod glob;
 mod idx;
 mod ls;
+mod mkpipe;
 mod mktemp;
 mod open;
 mod rm;
@@ -14,6 +15,7 @@ mod umv;
 mod util;
 mod utouch;
 mod watch;
+mod with_lock;
 
 pub use self::open::Open;
 pub use cd::Cd;
@@ -21,6 +23,7 @@ pub use du::Du;
 pub use glob::Glob;
 pub use idx::{Idx, IdxDirs, IdxDrop, IdxFiles, IdxFind, IdxInit, IdxSearch, IdxStatus, IdxWatch};
 pub use ls::Ls;
+pub use mkpipe::Mkpipe;
 pub use mktemp::Mktemp;
 pub use rm::Rm;
 pub use save::Save;
@@ -28,5 +31,7 @@ pub use start::Start;
 pub use ucp::UCp;
 pub use umkdir::UMkdir;
 pub use umv::UMv;
+pub(crate) use util::try_interaction;
 pub use utouch::UTouch;
 pub use watch::Watch;
+pub use with_lock::WithLock;
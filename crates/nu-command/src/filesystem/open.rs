@@ -1,3 +1,4 @@
+use super::util::{is_special_file, open_special_file};
 use nu_engine::{command_prelude::*, eval_call};
 use nu_path::is_windows_device_path;
 use nu_protocol::{
@@ -29,7 +30,9 @@ impl Command for Open {
     }
 
     fn extra_description(&self) -> &str {
-        "Support to automatically parse files with an extension `.xyz` can be provided by a `from xyz` command in scope."
+        r#"Support to automatically parse files with an extension `.xyz` can be provided by a `from xyz` command in scope.
+
+FIFOs (named pipes) and character devices are streamed as they are read. Opening a FIFO waits until a writer opens it too, which can be interrupted with ctrl-c."#
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -153,8 +156,11 @@ impl Command for Open {
 
                     return Err(err.into());
                 } else {
+                    let special = is_special_file(path);
+
+                    // Probing a special file for SQLite would consume its data
                     #[cfg(feature = "sqlite")]
-                    if !raw {
+                    if !raw && !special {
                         let res = SQLiteDatabase::try_from_path(
                             path,
                             arg_span,
@@ -181,8 +187,12 @@ impl Command for Open {
                         )));
                     }
 
-                    let file = std::fs::File::open(path)
-                        .map_err(|err| IoError::new(err, arg_span, PathBuf::from(path)))?;
+                    let file = if special {
+                        open_special_file(path, false, engine_state.signals(), arg_span)?
+                    } else {
+                        std::fs::File::open(path)
+                            .map_err(|err| IoError::new(err, arg_span, PathBuf::from(path)))?
+                    };
 
                     // No content_type by default - Is added later if no converter is found
                     let stream = PipelineData::byte_stream(
@@ -1,3 +1,4 @@
+use super::util::{is_special_file, open_special_file};
 use crate::formats::{preserve_toml_document, read_toml_source_from_metadata};
 use crate::progress_bar;
 use nu_engine::{command_prelude::*, get_eval_block};
@@ -9,7 +10,7 @@ use nu_protocol::{
 };
 use std::{
     borrow::Cow,
-    fs::File,
+    fs::{File, OpenOptions, Permissions},
     io::{self, BufRead, BufReader, Read, Write},
     path::{Path, PathBuf},
     thread,
@@ -57,6 +58,23 @@ impl Command for Save {
             .switch("append", "Append input to the end of the file.", Some('a'))
             .switch("force", "Overwrite the destination.", Some('f'))
             .switch("progress", "Enable progress bar.", Some('p'))
+            .switch(
+                "atomic",
+                "Write to a temporary file and rename it over the destination, so the destination is never left half-written.",
+                None,
+            )
+            .named(
+                "backup",
+                SyntaxShape::String,
+                "Copy the destination before overwriting it: `simple` (file~), `numbered` (file.~1~), or `existing` (numbered if there are numbered backups already).",
+                Some('b'),
+            )
+            .named(
+                "backup-suffix",
+                SyntaxShape::String,
+                "The suffix of simple backups, `~` by default. Implies `--backup simple` if that isn't given.",
+                None,
+            )
             .category(Category::FileSystem)
     }
 
@@ -71,8 +89,20 @@ impl Command for Save {
         let append = call.has_flag(engine_state, stack, "append")?;
         let force = call.has_flag(engine_state, stack, "force")?;
         let progress = call.has_flag(engine_state, stack, "progress")?;
+        let atomic = call.has_flag(engine_state, stack, "atomic")?;
 
         let span = call.head;
+        let backup = Backup::from_flags(
+            call.get_flag(engine_state, stack, "backup")?,
+            call.get_flag(engine_state, stack, "backup-suffix")?,
+            span,
+        )?;
+        let options = WriteOptions {
+            append,
+            force,
+            atomic,
+            backup,
+        };
         let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
 
         let path = call
@@ -92,8 +122,7 @@ impl Command for Save {
                     path: &path,
                     stderr_path: stderr_path.as_ref(),
                     engine_state,
-                    append,
-                    force,
+                    options: &options,
                     span,
                     progress,
                 },
@@ -113,14 +142,17 @@ impl Command for Save {
                     stderr_path.as_ref(),
                 )?;
 
-                let (mut file, _) =
-                    get_files(engine_state, &path, stderr_path.as_ref(), append, force)?;
+                let SaveFiles {
+                    mut file, atomic, ..
+                } = get_files(engine_state, &path, stderr_path.as_ref(), &options)?;
                 for val in ls {
                     file.write_all(&value_to_bytes(val)?)
                         .map_err(&from_io_error)?;
                     file.write_all("\n".as_bytes()).map_err(&from_io_error)?;
                 }
                 file.flush().map_err(&from_io_error)?;
+                drop(file);
+                commit(atomic).map_err(&from_io_error)?;
 
                 Ok(PipelineData::empty())
             }
@@ -134,11 +166,14 @@ impl Command for Save {
                 if let Some(bytes) =
                     preserve_toml_output(engine_state, &input, &path.item, raw, append, span)?
                 {
-                    let (mut file, _) =
-                        get_files(engine_state, &path, stderr_path.as_ref(), append, force)?;
+                    let SaveFiles {
+                        mut file, atomic, ..
+                    } = get_files(engine_state, &path, stderr_path.as_ref(), &options)?;
 
                     file.write_all(&bytes).map_err(&from_io_error)?;
                     file.flush().map_err(&from_io_error)?;
+                    drop(file);
+                    commit(atomic).map_err(&from_io_error)?;
 
                     return Ok(PipelineData::empty());
                 }
@@ -168,11 +203,14 @@ impl Command for Save {
                 let bytes = value_to_bytes(converted.into_value(span)?)?;
 
                 // Only open file after successful conversion
-                let (mut file, _) =
-                    get_files(engine_state, &path, stderr_path.as_ref(), append, force)?;
+                let SaveFiles {
+                    mut file, atomic, ..
+                } = get_files(engine_state, &path, stderr_path.as_ref(), &options)?;
 
                 file.write_all(&bytes).map_err(&from_io_error)?;
                 file.flush().map_err(&from_io_error)?;
+                drop(file);
+                commit(atomic).map_err(&from_io_error)?;
 
                 Ok(PipelineData::empty())
             }
@@ -196,6 +234,11 @@ impl Command for Save {
                 example: "{ a: 1, b: 2 } | save foo.json",
                 result: None,
             },
+            Example {
+                description: "Save a config file atomically, keeping the previous version as config.toml~.",
+                example: "open config.toml | upsert theme dark | save --force --atomic --backup simple config.toml",
+                result: None,
+            },
             Example {
                 description: "Save a running program's stderr to foo.txt.",
                 example: "do -i {} | save foo.txt --stderr foo.txt",
@@ -361,7 +404,8 @@ fn prepare_path(
     let span = path.span;
     let path = &path.item;
 
-    if !(force || append) && path.exists() {
+    // Writing to a FIFO or a device doesn't overwrite anything
+    if !(force || append) && path.exists() && !is_special_file(path) {
         Err(ShellError::Generic(
             GenericError::new(
                 "Destination file already exists",
@@ -384,6 +428,10 @@ fn open_file(
     span: Span,
     append: bool,
 ) -> Result<File, ShellError> {
+    if is_special_file(path) {
+        return open_special_file(path, true, engine_state.signals(), span);
+    }
+
     let file: std::io::Result<File> = match (append, path.exists() || is_windows_device_path(path))
     {
         (true, true) => std::fs::OpenOptions::new().append(true).open(path),
@@ -437,14 +485,52 @@ fn open_file(
     }
 }
 
+/// Open the destination file, keeping a backup of it and writing it atomically if requested
+fn open_destination(
+    engine_state: &EngineState,
+    path: &Path,
+    span: Span,
+    options: &WriteOptions,
+) -> Result<(File, Option<AtomicWrite>), ShellError> {
+    // FIFOs and devices are written to directly
+    if is_special_file(path) {
+        return Ok((open_file(engine_state, path, span, options.append)?, None));
+    }
+
+    let from_io_error = IoError::factory(span, path);
+
+    if let Some(backup) = &options.backup
+        && path.is_file()
+    {
+        let backup_path = backup.path_for(path);
+        std::fs::copy(path, &backup_path).map_err(|err| IoError::new(err, span, backup_path))?;
+    }
+
+    if options.atomic {
+        let (atomic, file) = AtomicWrite::create(path, options.append).map_err(&from_io_error)?;
+        Ok((file, Some(atomic)))
+    } else {
+        Ok((open_file(engine_state, path, span, options.append)?, None))
+    }
+}
+
+/// The files that `save` writes to
+struct SaveFiles {
+    file: File,
+    stderr: Option<File>,
+    /// Set with `--atomic`, to move the written file into place once it's complete
+    atomic: Option<AtomicWrite>,
+}
+
 /// Get output file and optional stderr file
 fn get_files(
     engine_state: &EngineState,
     path: &Spanned<PathBuf>,
     stderr_path: Option<&Spanned<PathBuf>>,
-    append: bool,
-    force: bool,
-) -> Result<(File, Option<File>), ShellError> {
+    options: &WriteOptions,
+) -> Result<SaveFiles, ShellError> {
+    let WriteOptions { append, force, .. } = *options;
+
     // First check both paths
     let (path, path_span) = prepare_path(path, append, force)?;
     let stderr_path_and_span = stderr_path
@@ -453,7 +539,7 @@ fn get_files(
         .transpose()?;
 
     // Only if both files can be used open and possibly truncate them
-    let file = open_file(engine_state, path, path_span, append)?;
+    let (file, atomic) = open_destination(engine_state, path, path_span, options)?;
 
     let stderr_file = stderr_path_and_span
         .map(|(stderr_path, stderr_path_span)| {
@@ -472,7 +558,178 @@ fn get_files(
         })
         .transpose()?;
 
-    Ok((file, stderr_file))
+    Ok(SaveFiles {
+        file,
+        stderr: stderr_file,
+        atomic,
+    })
+}
+
+/// How `save` writes the destination file
+struct WriteOptions {
+    append: bool,
+    force: bool,
+    atomic: bool,
+    backup: Option<Backup>,
+}
+
+/// How to name the copy that `save --backup` makes of the file it overwrites
+enum Backup {
+    /// `file~`, or with another suffix
+    Simple(String),
+    /// `file.~1~`, `file.~2~`, ...
+    Numbered,
+    /// Numbered if the file has numbered backups already, simple otherwise
+    Existing(String),
+}
+
+impl Backup {
+    fn from_flags(
+        mode: Option<Spanned<String>>,
+        suffix: Option<Spanned<String>>,
+        span: Span,
+    ) -> Result<Option<Self>, ShellError> {
+        if let Some(suffix) = &suffix
+            && (suffix.item.is_empty() || suffix.item.contains(std::path::is_separator))
+        {
+            return Err(ShellError::IncorrectValue {
+                msg: "the backup suffix can't be empty or contain a path separator".into(),
+                val_span: suffix.span,
+                call_span: span,
+            });
+        }
+        let has_suffix = suffix.is_some();
+        let suffix = suffix.map_or_else(|| "~".into(), |suffix| suffix.item);
+
+        match mode {
+            None if has_suffix => Ok(Some(Backup::Simple(suffix))),
+            None => Ok(None),
+            Some(mode) => match mode.item.as_str() {
+                "simple" => Ok(Some(Backup::Simple(suffix))),
+                "numbered" => Ok(Some(Backup::Numbered)),
+                "existing" => Ok(Some(Backup::Existing(suffix))),
+                _ => Err(ShellError::IncorrectValue {
+                    msg: "expected `simple`, `numbered` or `existing`".into(),
+                    val_span: mode.span,
+                    call_span: span,
+                }),
+            },
+        }
+    }
+
+    /// The path to copy `path` to before it's overwritten
+    fn path_for(&self, path: &Path) -> PathBuf {
+        let with_suffix = |suffix: &str| {
+            let mut backup = path.as_os_str().to_owned();
+            backup.push(suffix);
+            PathBuf::from(backup)
+        };
+
+        match self {
+            Backup::Simple(suffix) => with_suffix(suffix),
+            Backup::Numbered => with_suffix(&format!(".~{}~", highest_numbered_backup(path) + 1)),
+            Backup::Existing(suffix) => match highest_numbered_backup(path) {
+                0 => with_suffix(suffix),
+                n => with_suffix(&format!(".~{}~", n + 1)),
+            },
+        }
+    }
+}
+
+/// The highest `n` of the existing `path.~n~` backups, or 0 if there are none
+fn highest_numbered_backup(path: &Path) -> u64 {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return 0;
+    };
+    let prefix = format!("{}.~", name.to_string_lossy());
+
+    std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .strip_prefix(&prefix)?
+                .strip_suffix('~')?
+                .parse()
+                .ok()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// A temporary file next to the destination, which replaces the destination once it's complete.
+///
+/// If it's dropped without being committed, e.g. because saving failed or was interrupted, the
+/// temporary file is removed and the destination is left as it was.
+struct AtomicWrite {
+    temp: PathBuf,
+    dest: PathBuf,
+    permissions: Option<Permissions>,
+    committed: bool,
+}
+
+impl AtomicWrite {
+    /// Create the temporary file, starting with the destination's contents if appending
+    fn create(dest: &Path, append: bool) -> io::Result<(Self, File)> {
+        // Replace the target of a symlink rather than the symlink itself
+        let dest = std::fs::canonicalize(dest).unwrap_or_else(|_| dest.to_path_buf());
+        let dir = dest.parent().unwrap_or(Path::new("."));
+        let name = dest.file_name().unwrap_or_default().to_string_lossy();
+
+        let mut attempt = 0;
+        let (temp, mut file) = loop {
+            let temp = dir.join(format!(".{name}.{}-{attempt}.tmp", std::process::id()));
+            match OpenOptions::new().write(true).create_new(true).open(&temp) {
+                Ok(file) => break (temp, file),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists && attempt < 100 => {
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        };
+
+        let metadata = std::fs::metadata(&dest).ok();
+        let atomic = AtomicWrite {
+            temp,
+            dest,
+            permissions: metadata.map(|metadata| metadata.permissions()),
+            committed: false,
+        };
+        if append && atomic.permissions.is_some() {
+            io::copy(&mut File::open(&atomic.dest)?, &mut file)?;
+        }
+        Ok((atomic, file))
+    }
+
+    /// Move the complete temporary file over the destination, with the destination's permissions
+    fn commit(mut self) -> io::Result<()> {
+        OpenOptions::new()
+            .write(true)
+            .open(&self.temp)?
+            .sync_all()?;
+        if let Some(permissions) = self.permissions.take() {
+            std::fs::set_permissions(&self.temp, permissions)?;
+        }
+        std::fs::rename(&self.temp, &self.dest)?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Drop for AtomicWrite {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = std::fs::remove_file(&self.temp);
+        }
+    }
+}
+
+/// Commit an atomic write, if there is one
+fn commit(atomic: Option<AtomicWrite>) -> io::Result<()> {
+    atomic.map_or(Ok(()), AtomicWrite::commit)
 }
 
 fn write_or_consume_stderr(
@@ -502,8 +759,7 @@ struct ByteStreamSaveContext<'a> {
     path: &'a Spanned<PathBuf>,
     stderr_path: Option<&'a Spanned<PathBuf>>,
     engine_state: &'a EngineState,
-    append: bool,
-    force: bool,
+    options: &'a WriteOptions,
     span: Span,
     progress: bool,
 }
@@ -518,12 +774,15 @@ fn stream_byte_stream_to_file(
 
     check_saving_to_source_file(context.metadata, context.path, context.stderr_path)?;
 
-    let (file, stderr_file) = get_files(
+    let SaveFiles {
+        file,
+        stderr: stderr_file,
+        atomic,
+    } = get_files(
         context.engine_state,
         context.path,
         context.stderr_path,
-        context.append,
-        context.force,
+        context.options,
     )?;
 
     let size = stream.known_size();
@@ -585,6 +844,8 @@ fn stream_byte_stream_to_file(
         }
     }
 
+    commit(atomic).map_err(&from_io_error)?;
+
     Ok(PipelineData::empty())
 }
 
@@ -4,9 +4,12 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode},
 };
+use nu_protocol::{ShellError, Signals, Span, shell_error::io::IoError};
 use std::{
     error::Error,
+    fs::File,
     io::{self, Write},
+    path::{Path, PathBuf},
 };
 
 pub fn try_interaction(
@@ -94,3 +97,109 @@ fn get_interactive_confirmation(prompt: String) -> Result<bool, Box<dyn Error>>
         }
     }
 }
+
+/// Whether `path` is a FIFO, a character device, or a Windows device path such as a named pipe.
+///
+/// Special files have no size, can't be probed without consuming their data, and opening them
+/// can block until the other end of a pipe is opened too.
+pub(crate) fn is_special_file(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        std::fs::metadata(path).is_ok_and(|metadata| {
+            metadata.file_type().is_fifo() || metadata.file_type().is_char_device()
+        })
+    }
+    #[cfg(not(unix))]
+    {
+        nu_path::is_windows_device_path(path)
+    }
+}
+
+/// Open a special file (see [`is_special_file`]) for reading or writing.
+///
+/// Opening a FIFO waits for the other end to be opened, so this waits in the background and stays
+/// responsive to interrupts instead of hanging the shell.
+pub(crate) fn open_special_file(
+    path: &Path,
+    write: bool,
+    signals: &Signals,
+    span: Span,
+) -> Result<File, ShellError> {
+    let from_io_error = |err| ShellError::Io(IoError::new(err, span, PathBuf::from(path)));
+
+    #[cfg(unix)]
+    {
+        use std::{
+            fs::OpenOptions,
+            os::unix::fs::OpenOptionsExt,
+            sync::mpsc::{self, RecvTimeoutError},
+            thread,
+            time::Duration,
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let thread_path = path.to_path_buf();
+        thread::Builder::new()
+            .name("special file opener".into())
+            .spawn(move || {
+                let file = OpenOptions::new()
+                    .read(!write)
+                    .write(write)
+                    .open(thread_path);
+                // If nobody is waiting anymore, the file is just closed again
+                let _ = tx.send(file);
+            })
+            .map_err(from_io_error)?;
+
+        loop {
+            match rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(file) => return file.map_err(from_io_error),
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Err(err) = signals.check(&span) {
+                        // Open the other end of the FIFO without blocking, so the pending open
+                        // returns and the thread can finish
+                        let _ = OpenOptions::new()
+                            .read(write)
+                            .write(!write)
+                            .custom_flags(nix::fcntl::OFlag::O_NONBLOCK.bits())
+                            .open(path);
+                        return Err(err);
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(from_io_error(io::ErrorKind::BrokenPipe.into()));
+                }
+            }
+        }
+    }
+    #[cfg(windows)]
+    {
+        use std::time::Duration;
+        use windows::Win32::Foundation::ERROR_PIPE_BUSY;
+
+        loop {
+            let file = std::fs::OpenOptions::new()
+                .read(!write)
+                .write(write)
+                .open(path);
+            match file {
+                // Every instance of the named pipe is in use, wait for one to become free
+                Err(err) if err.raw_os_error() == Some(ERROR_PIPE_BUSY.0 as i32) => {
+                    signals.check(&span)?;
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                file => return file.map_err(from_io_error),
+            }
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = signals;
+        std::fs::OpenOptions::new()
+            .read(!write)
+            .write(write)
+            .open(path)
+            .map_err(from_io_error)
+    }
+}
@@ -0,0 +1,174 @@
+use nu_engine::{ClosureEvalOnce, command_prelude::*};
+use nu_protocol::{
+    Signals,
+    engine::Closure,
+    shell_error::{generic::GenericError, io::IoError},
+};
+use std::{
+    fs::{File, OpenOptions, TryLockError},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use nu_utils::time::Instant;
+
+/// How often to retry taking a lock that is held by someone else.
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone)]
+pub struct WithLock;
+
+impl Command for WithLock {
+    fn name(&self) -> &str {
+        "with-lock"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("with-lock")
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .required(
+                "path",
+                SyntaxShape::Filepath,
+                "The file to lock, which is created if it doesn't exist.",
+            )
+            .required(
+                "closure",
+                SyntaxShape::Closure(None),
+                "The closure to run while holding the lock.",
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "Give up if the lock can't be taken within this duration.",
+                Some('t'),
+            )
+            .switch(
+                "shared",
+                "Take a shared lock, which can be held by several readers at once.",
+                Some('s'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn description(&self) -> &str {
+        "Run a closure while holding an advisory lock on a file."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The lock is exclusive unless `--shared` is given, and is released when the closure finishes,
+even if it fails. The closure's output is collected before the lock is released.
+
+Locks are advisory: they only keep out others that take a lock on the same file, like other
+`with-lock` calls or `flock` on Unix. Without `--timeout`, this waits until the lock is free or
+it's interrupted."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["flock", "lock", "mutex", "lockfile", "exclusive"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+        let path: Spanned<PathBuf> = call.req(engine_state, stack, 0)?;
+        let closure: Closure = call.req(engine_state, stack, 1)?;
+        let timeout: Option<Duration> = call.get_flag(engine_state, stack, "timeout")?;
+        let shared = call.has_flag(engine_state, stack, "shared")?;
+
+        let lock_path = nu_path::expand_path_with(&path.item, &cwd, true);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .map_err(|err| IoError::new(err, path.span, lock_path.clone()))?;
+
+        lock(
+            &file,
+            &lock_path,
+            shared,
+            timeout,
+            engine_state.signals(),
+            path.span,
+        )?;
+
+        let result = ClosureEvalOnce::new(engine_state, stack, closure)
+            .run_with_input(input)
+            .and_then(|output| output.into_value(head));
+
+        // Dropping the file would release the lock too, but this reports errors
+        file.unlock()
+            .map_err(|err| IoError::new(err, path.span, lock_path))?;
+
+        result.map(|value| value.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Make sure only one instance of a script updates a file at a time.",
+                example: "with-lock /tmp/backup.lock { backup-files }",
+                result: None,
+            },
+            Example {
+                description: "Give up if another instance holds the lock for more than 10 seconds.",
+                example: "with-lock --timeout 10sec /tmp/backup.lock { backup-files }",
+                result: None,
+            },
+            Example {
+                description: "Read a file while holding a shared lock, allowing other readers.",
+                example: "with-lock --shared data.json.lock { open data.json }",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Take the lock, retrying until `timeout` is reached or the shell is interrupted.
+fn lock(
+    file: &File,
+    path: &Path,
+    shared: bool,
+    timeout: Option<Duration>,
+    signals: &Signals,
+    span: Span,
+) -> Result<(), ShellError> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    loop {
+        let result = if shared {
+            file.try_lock_shared()
+        } else {
+            file.try_lock()
+        };
+
+        match result {
+            Ok(()) => return Ok(()),
+            Err(TryLockError::Error(err)) => {
+                return Err(IoError::new(err, span, PathBuf::from(path)).into());
+            }
+            Err(TryLockError::WouldBlock) => {
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    return Err(ShellError::Generic(
+                        GenericError::new(
+                            "Timed out waiting for the lock",
+                            format!("{} is locked by someone else", path.display()),
+                            span,
+                        )
+                        .with_help("try again later, or wait longer with a bigger --timeout"),
+                    ));
+                }
+                signals.check(&span)?;
+                thread::sleep(RETRY_INTERVAL);
+            }
+        }
+    }
+}
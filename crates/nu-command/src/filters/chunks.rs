@@ -22,9 +22,19 @@ impl Command for Chunks {
             ])
             .required(
                 "chunk_size",
-                SyntaxShape::OneOf(vec![SyntaxShape::Int, SyntaxShape::Filesize]),
+                SyntaxShape::OneOf(vec![
+                    SyntaxShape::Int,
+                    SyntaxShape::Filesize,
+                    SyntaxShape::Duration,
+                ]),
                 "The size of each chunk.",
             )
+            .named(
+                "by",
+                SyntaxShape::CellPath,
+                "Make chunks of a duration, by the time in this column of the rows.",
+                Some('b'),
+            )
             .category(Category::Filters)
     }
 
@@ -33,11 +43,15 @@ impl Command for Chunks {
     }
 
     fn extra_description(&self) -> &str {
-        "This command will error if `chunk_size` is negative or zero."
+        "This command will error if `chunk_size` is negative or zero.
+
+With --by, `chunk_size` is a duration, and each chunk has the rows whose time in the column is in \
+it. The chunks start at multiples of `chunk_size` since the Unix epoch, the rows must be sorted by \
+their time, and chunks without rows are skipped."
     }
 
     fn search_terms(&self) -> Vec<&str> {
-        vec!["batch", "group", "split", "bytes"]
+        vec!["batch", "group", "split", "bytes", "tumbling", "time"]
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -89,6 +103,14 @@ impl Command for Chunks {
                     Value::test_binary(vec![0x77, 0x88]),
                 ])),
             },
+            Example {
+                example: "[[time level]; [2024-01-01T10:02:00Z info] [2024-01-01T10:04:00Z warn] [2024-01-01T10:07:00Z info]] | chunks 5min --by time | each { get level }",
+                description: "Chunk log rows into windows of 5 minutes, by their time",
+                result: Some(Value::test_list(vec![
+                    Value::test_list(vec![Value::test_string("info"), Value::test_string("warn")]),
+                    Value::test_list(vec![Value::test_string("info")]),
+                ])),
+            },
             Example {
                 example: "open --raw foo.bin | chunks 8kib",
                 description: "Open a binary file and make 8 kibibyte chunks",
@@ -107,6 +129,16 @@ impl Command for Chunks {
         let input = input.into_stream_or_original(engine_state);
         let head = call.head;
         let chunk_size: Value = call.req(engine_state, stack, 0)?;
+        if let Some(column) = call.get_flag(engine_state, stack, "by")? {
+            return super::window::time_windows(
+                engine_state,
+                input,
+                column,
+                chunk_size,
+                None,
+                head,
+            );
+        }
 
         let size = match chunk_size {
             Value::Int { val, .. } => {
@@ -131,12 +131,13 @@ list of lists like `list<list<string>>` into a flat list like `list<string>`."#
         engine_state: &EngineState,
         stack: &mut Stack,
         call: &Call,
-        mut input: PipelineData,
+        input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let head = call.head;
         let closure: Closure = call.req(engine_state, stack, 0)?;
         let keep_empty = call.has_flag(engine_state, stack, "keep-empty")?;
         let flatten = call.has_flag(engine_state, stack, "flatten")?;
+        let mut input = input.stream_custom_value(engine_state.signals())?;
 
         let result = match input {
             PipelineData::Empty | PipelineData::Value(Value::Nothing { .. }, ..) => {
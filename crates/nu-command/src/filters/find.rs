@@ -2,7 +2,7 @@ use fancy_regex::{Regex, escape};
 use nu_ansi_term::Style;
 use nu_color_config::StyleComputer;
 use nu_engine::command_prelude::*;
-use nu_protocol::Config;
+use nu_protocol::{Config, RegexConfig};
 
 #[derive(Clone)]
 pub struct Find;
@@ -26,7 +26,7 @@ impl Command for Find {
             .named(
                 "regex",
                 SyntaxShape::String,
-                "Regex to match with.",
+                "Regex to match with, with the options of `$env.config.regex` turned on.",
                 Some('r'),
             )
             .switch(
@@ -56,6 +56,11 @@ impl Command for Find {
                 Some('n'),
             )
             .switch("invert", "Invert the match.", Some('v'))
+            .switch(
+                "detailed",
+                "Return records with the original values and where they matched, instead of highlighting them.",
+                Some('d'),
+            )
             .switch(
                 "rfind",
                 "Search from the end of the string and only return the first match.",
@@ -69,6 +74,12 @@ impl Command for Find {
         "Search for terms in the input data."
     }
 
+    fn extra_description(&self) -> &str {
+        "With --detailed, every value found becomes a record with the value as `item`, and its \
+`matches`: the column they're in, or null outside of records, their text, and the byte offsets of \
+their `start` and `end` in the text of the value."
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -238,6 +249,36 @@ impl Command for Find {
                     "\u{1b}[39mViolets are red\nAnd roses are bl\u{1b}[0m\u{1b}[41;39mue\u{1b}[0m\u{1b}[39m\nWhen metamaterials\nAlter their h\u{1b}[0m\u{1b}[41;39mue\u{1b}[0m\u{1b}[39m\u{1b}[0m",
                 )),
             },
+            Example {
+                description: "Find the rows with matches, and the columns and offsets of the matches.",
+                example: "[[name version]; [nushell 0.1] [fish 3.7]] | find --detailed --regex 'sh'",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "item" => Value::test_record(record! {
+                            "name" => Value::test_string("nushell"),
+                            "version" => Value::test_float(0.1),
+                        }),
+                        "matches" => Value::test_list(vec![Value::test_record(record! {
+                            "column" => Value::test_string("name"),
+                            "text" => Value::test_string("sh"),
+                            "start" => Value::test_int(2),
+                            "end" => Value::test_int(4),
+                        })]),
+                    }),
+                    Value::test_record(record! {
+                        "item" => Value::test_record(record! {
+                            "name" => Value::test_string("fish"),
+                            "version" => Value::test_float(3.7),
+                        }),
+                        "matches" => Value::test_list(vec![Value::test_record(record! {
+                            "column" => Value::test_string("name"),
+                            "text" => Value::test_string("sh"),
+                            "start" => Value::test_int(2),
+                            "end" => Value::test_int(4),
+                        })]),
+                    }),
+                ])),
+            },
             Example {
                 description: "Find and highlight the last occurrence in a string.",
                 example: "'hello world hello' | find --rfind hello",
@@ -305,6 +346,9 @@ struct MatchPattern {
     /// search from the end (find last occurrence)
     rfind: bool,
 
+    /// return records describing where the values matched instead of the values
+    detailed: bool,
+
     /// style of the non-highlighted string sections
     string_style: Style,
 
@@ -326,6 +370,13 @@ fn get_match_pattern_from_arguments(
     let invert = call.has_flag(engine_state, stack, "invert")?;
     let highlight = !call.has_flag(engine_state, stack, "no-highlight")?;
     let rfind = call.has_flag(engine_state, stack, "rfind")?;
+    let detailed = call.has_flag(engine_state, stack, "detailed")?;
+    if detailed && invert {
+        return Err(ShellError::IncompatibleParametersSingle {
+            msg: "Values that don't match have no matches to detail".into(),
+            span: call.get_flag_span(stack, "detailed").expect("has flag"),
+        });
+    }
 
     let ignore_case = call.has_flag(engine_state, stack, "ignore-case")?;
 
@@ -347,14 +398,16 @@ fn get_match_pattern_from_arguments(
             });
         }
         (Some(regex), []) => {
-            let flags = match (ignore_case, dotall) {
-                (false, false) => "",
-                (true, false) => "(?i)", // case insensitive
-                (false, true) => "(?s)", // allow . to match \n
-                (true, true) => "(?is)", // case insensitive and allow . to match \n
-            };
+            // `--multiline` is about splitting the input, so `(?m)` only comes from the config
+            let flags = RegexConfig {
+                case_insensitive: ignore_case,
+                multiline: false,
+                dotall,
+            }
+            .or(config.regex)
+            .inline_flags();
 
-            (flags.to_string() + regex.as_str(), Vec::new())
+            (flags + regex.as_str(), Vec::new())
         }
         (None, _) if dotall => {
             return Err(ShellError::IncompatibleParametersSingle {
@@ -407,6 +460,7 @@ fn get_match_pattern_from_arguments(
         invert,
         highlight,
         rfind,
+        detailed,
         string_style,
         highlight_style,
     })
@@ -519,6 +573,118 @@ fn highlight_matches_in_value(
     }
 }
 
+/// The record of a value found with --detailed, with the value and its matches.
+fn detail_matches_in_value(
+    pattern: &MatchPattern,
+    value: Value,
+    columns_to_search: &[String],
+    config: &Config,
+) -> Value {
+    if let Value::Error { .. } = value {
+        return value;
+    }
+    let span = value.span();
+    let mut matches = vec![];
+    collect_matches(
+        pattern,
+        &value,
+        None,
+        columns_to_search,
+        config,
+        &mut matches,
+    );
+    Value::record(
+        record! {
+            "item" => value,
+            "matches" => Value::list(matches, span),
+        },
+        span,
+    )
+}
+
+/// Collect the matches in a value, searching it like `value_should_be_printed`. The matches in
+/// nested values are in the column of the outermost record.
+fn collect_matches(
+    pattern: &MatchPattern,
+    value: &Value,
+    column: Option<&str>,
+    columns_to_search: &[String],
+    config: &Config,
+    matches: &mut Vec<Value>,
+) {
+    let span = value.span();
+    let mut push = |text: &str, start: usize, end: usize| {
+        matches.push(Value::record(
+            record! {
+                "column" => column.map_or(Value::nothing(span), |column| Value::string(column, span)),
+                "text" => Value::string(text, span),
+                "start" => Value::int(start as i64, span),
+                "end" => Value::int(end as i64, span),
+            },
+            span,
+        ))
+    };
+    let mut push_regex_matches = |text: &str| {
+        let found = pattern.regex.find_iter(text).filter_map(Result::ok);
+        if pattern.rfind {
+            if let Some(found) = found.last() {
+                push(found.as_str(), found.start(), found.end());
+            }
+        } else {
+            for found in found {
+                push(found.as_str(), found.start(), found.end());
+            }
+        }
+    };
+
+    match value {
+        Value::Bool { .. }
+        | Value::Int { .. }
+        | Value::Filesize { .. }
+        | Value::Duration { .. }
+        | Value::Date { .. }
+        | Value::Range { .. }
+        | Value::Float { .. }
+        | Value::Closure { .. }
+        | Value::Nothing { .. } => {
+            let text = value.to_expanded_string("", config);
+            if pattern.search_terms.is_empty() {
+                push_regex_matches(&text);
+            } else {
+                // Search terms match these values as a whole
+                let comparable = if pattern.ignore_case {
+                    text.to_lowercase()
+                } else {
+                    text.clone()
+                };
+                if pattern.search_terms.contains(&comparable) {
+                    push(&text, 0, text.len());
+                }
+            }
+        }
+        Value::Glob { .. } | Value::CellPath { .. } | Value::Custom { .. } => {
+            push_regex_matches(&value.to_expanded_string("", config))
+        }
+        Value::String { val, .. } => push_regex_matches(val),
+        Value::List { vals, .. } => {
+            for item in vals {
+                collect_matches(pattern, item, column, &[], config, matches);
+            }
+        }
+        Value::Record { val: record, .. } => {
+            let col_select = !columns_to_search.is_empty();
+            for (col, val) in record.iter() {
+                if col_select && !columns_to_search.contains(col) {
+                    continue;
+                }
+                let column = column.or(Some(col));
+                collect_matches(pattern, val, column, &[], config, matches);
+            }
+        }
+        Value::Binary { .. } | Value::Error { .. } => {}
+    }
+}
+
 fn find_in_pipelinedata(
     pattern: MatchPattern,
     columns_to_search: Vec<String>,
@@ -530,6 +696,14 @@ fn find_in_pipelinedata(
 
     let map_pattern = pattern.clone();
     let map_columns_to_search = columns_to_search.clone();
+    let map_config = config.clone();
+    let map_value = move |value: Value| {
+        if map_pattern.detailed {
+            detail_matches_in_value(&map_pattern, value, &map_columns_to_search, &map_config)
+        } else {
+            highlight_matches_in_value(&map_pattern, value, &map_columns_to_search)
+        }
+    };
 
     match input {
         PipelineData::Empty => Ok(PipelineData::empty()),
@@ -541,17 +715,14 @@ fn find_in_pipelinedata(
                 },
                 engine_state.signals(),
             )?
-            .map(
-                move |x| highlight_matches_in_value(&map_pattern, x, &map_columns_to_search),
-                engine_state.signals(),
-            ),
+            .map(map_value, engine_state.signals()),
         PipelineData::ListStream(stream, metadata) => {
             let stream = stream.modify(|iter| {
                 iter.filter(move |value| {
                     value_should_be_printed(&pattern, value, &columns_to_search, &config)
                         != pattern.invert
                 })
-                .map(move |x| highlight_matches_in_value(&map_pattern, x, &map_columns_to_search))
+                .map(map_value)
             });
 
             Ok(PipelineData::list_stream(stream, metadata))
@@ -563,7 +734,9 @@ fn find_in_pipelinedata(
                 for line in lines {
                     let line = line?;
                     if string_should_be_printed(&pattern, &line) != pattern.invert {
-                        if pattern.highlight && !pattern.invert {
+                        if pattern.detailed {
+                            output.push(map_value(line.into_value(span)))
+                        } else if pattern.highlight && !pattern.invert {
                             output
                                 .push(highlight_matches_in_string(&pattern, line).into_value(span))
                         } else {
@@ -687,6 +860,7 @@ pub fn find_internal(
         highlight,
         invert: false,
         rfind: false,
+        detailed: false,
         string_style,
         highlight_style,
     };
@@ -1,6 +1,6 @@
 use indexmap::IndexMap;
 use nu_engine::command_prelude::*;
-use nu_protocol::ast::PathMember;
+use nu_protocol::{FromValue, ast::PathMember, shell_error::generic::GenericError};
 
 #[derive(Clone)]
 pub struct Flatten;
@@ -25,6 +25,24 @@ impl Command for Flatten {
                 "Optionally flatten data by column.",
             )
             .switch("all", "Flatten inner table one level out.", Some('a'))
+            .named(
+                "depth",
+                SyntaxShape::Int,
+                "How many levels of nested records to flatten, 1 by default.",
+                Some('d'),
+            )
+            .named(
+                "separator",
+                SyntaxShape::String,
+                "Join the names of nested columns and their parent columns with this, `_` by default.",
+                Some('s'),
+            )
+            .param(
+                Flag::new("on-conflict")
+                    .arg(SyntaxShape::String)
+                    .desc("What to do with a nested column whose name is taken: error, suffix (default) or keep-first.")
+                    .completion(Completion::new_list(&["error", "suffix", "keep-first"])),
+            )
             .category(Category::Filters)
     }
 
@@ -32,6 +50,14 @@ impl Command for Flatten {
         "Flatten a table by extracting nested values."
     }
 
+    fn extra_description(&self) -> &str {
+        "The columns of nested records become columns of the outer record, and with --depth, so do \
+the columns of the records nested in those. The outer columns keep their names, and a nested column \
+whose name is already taken is renamed after its parent columns, like `parent_column`, with \
+--separator between the names. A number is added when that name is taken as well. \
+With --on-conflict error, such a column is an error instead, and with keep-first it's dropped."
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -109,6 +135,32 @@ impl Command for Flatten {
                     Span::test_data(),
                 )),
             },
+            Example {
+                description: "Flatten several levels of nested records at once.",
+                example: "{id: 1, user: {name: Ada, address: {city: London}}} | flatten --depth 2",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "id" => Value::test_int(1),
+                    "name" => Value::test_string("Ada"),
+                    "city" => Value::test_string("London"),
+                })])),
+            },
+            Example {
+                description: "Name the nested columns that conflict with others after their parent columns.",
+                example: "{id: 1, user: {id: 7, name: Ada}} | flatten --separator '.'",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "id" => Value::test_int(1),
+                    "user.id" => Value::test_int(7),
+                    "name" => Value::test_string("Ada"),
+                })])),
+            },
+            Example {
+                description: "Drop the nested columns that conflict with others.",
+                example: "{id: 1, user: {id: 7, name: Ada}} | flatten --on-conflict keep-first",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "id" => Value::test_int(1),
+                    "name" => Value::test_string("Ada"),
+                })])),
+            },
         ]
     }
 }
@@ -121,25 +173,152 @@ fn flatten(
 ) -> Result<PipelineData, ShellError> {
     let columns: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
     let metadata = input.take_metadata();
-    let flatten_all = call.has_flag(engine_state, stack, "all")?;
+    let depth = match call.get_flag::<Spanned<i64>>(engine_state, stack, "depth")? {
+        Some(depth) => usize::try_from(depth.item)
+            .ok()
+            .filter(|depth| *depth > 0)
+            .ok_or(ShellError::IncorrectValue {
+                msg: "the depth must be at least 1".into(),
+                val_span: depth.span,
+                call_span: call.head,
+            })?,
+        None => 1,
+    };
+    let options = FlattenOptions {
+        all: call.has_flag(engine_state, stack, "all")?,
+        depth,
+        separator: call
+            .get_flag(engine_state, stack, "separator")?
+            .unwrap_or_else(|| "_".into()),
+        on_conflict: call
+            .get_flag(engine_state, stack, "on-conflict")?
+            .unwrap_or_default(),
+    };
 
     input
         .flat_map(
-            move |item| flat_value(&columns, item, flatten_all),
+            move |item| flat_value(&columns, item, &options),
             engine_state.signals(),
         )
         .map(|x| x.set_metadata(metadata))
 }
 
+/// What to do with a nested column whose name is already taken.
+#[derive(Clone, Copy, Default)]
+enum OnConflict {
+    Error,
+    #[default]
+    Suffix,
+    KeepFirst,
+}
+
+impl FromValue for OnConflict {
+    fn from_value(v: Value) -> Result<Self, ShellError> {
+        let span = v.span();
+        let s = <String>::from_value(v)?;
+        match s.as_str() {
+            "error" => Ok(OnConflict::Error),
+            "suffix" => Ok(OnConflict::Suffix),
+            "keep-first" => Ok(OnConflict::KeepFirst),
+            _ => Err(ShellError::InvalidValue {
+                valid: "one of: error, suffix, keep-first".into(),
+                actual: s,
+                span,
+            }),
+        }
+    }
+}
+
+struct FlattenOptions {
+    all: bool,
+    // how many levels of nested records are flattened into a column
+    depth: usize,
+    // joins the names of nested columns to the names of the columns they come from
+    separator: String,
+    on_conflict: OnConflict,
+}
+
+impl FlattenOptions {
+    /// The name of a `column` flattened out of the `parent` column, or `None` when it's dropped,
+    /// given the names that are `taken` already.
+    fn column_name(
+        &self,
+        parent: &str,
+        column: String,
+        taken: impl Fn(&str) -> bool,
+        span: Span,
+    ) -> Result<Option<String>, ShellError> {
+        if !taken(&column) {
+            return Ok(Some(column));
+        }
+        match self.on_conflict {
+            OnConflict::Error => Err(ShellError::Generic(
+                GenericError::new(
+                    "Column name conflict",
+                    format!("`{column}` of `{parent}` is already a column"),
+                    span,
+                )
+                .with_help("use --on-conflict suffix or keep-first to flatten it anyway"),
+            )),
+            OnConflict::KeepFirst => Ok(None),
+            OnConflict::Suffix => {
+                let name = format!("{parent}{}{column}", self.separator);
+                if !taken(&name) {
+                    return Ok(Some(name));
+                }
+                Ok((2u64..)
+                    .map(|n| format!("{name}{}{n}", self.separator))
+                    .find(|name| !taken(name)))
+            }
+        }
+    }
+}
+
+/// The columns of a nested record, with the records nested in it flattened as well while `depth`
+/// allows, along with the path of the column each one comes from.
+fn nested_columns(
+    parent: String,
+    record: Record,
+    depth: usize,
+    separator: &str,
+) -> Vec<(String, String, Value)> {
+    let mut columns = vec![];
+    for (column, value) in record {
+        match value {
+            Value::Record { val, .. } if depth > 1 => {
+                let path = format!("{parent}{separator}{column}");
+                columns.extend(nested_columns(path, val.into_owned(), depth - 1, separator));
+            }
+            value => columns.push((parent.clone(), column, value)),
+        }
+    }
+    columns
+}
+
+/// The outer columns, with the `flattened` ones at the `index` of the column they come from.
+fn splice_columns(
+    out: &IndexMap<String, Value>,
+    index: usize,
+    flattened: impl IntoIterator<Item = (String, Value)>,
+) -> Record {
+    let mut columns: Vec<(String, Value)> = out
+        .iter()
+        .map(|(column, value)| (column.clone(), value.clone()))
+        .collect();
+    let index = index.min(columns.len());
+    columns.splice(index..index, flattened);
+    columns.into_iter().collect()
+}
+
 enum TableInside {
     // handle for a column which contains a single list(but not list of records)
-    // it contains (column, span, values in the column, column index).
+    // it contains (column, values in the column, position of the column in the output).
     Entries(String, Vec<Value>, usize),
     // handle for a column which contains a table, we can flatten the inner column to outer level
     // `records` is the nested/inner table to flatten to the outer level
     // `parent_column_name` is handled for conflicting column name, the nested table may contains columns which has the same name
-    // to outer level, for that case, the output column name should be f"{parent_column_name}_{inner_column_name}".
-    // `parent_column_index` is the column index in original table.
+    // to outer level, for that case, the output column name is named after the `--on-conflict` policy.
+    // `parent_column_index` is the position of the column in the output.
     FlattenedRows {
         records: Vec<Record>,
         parent_column_name: String,
@@ -147,210 +326,153 @@ enum TableInside {
     },
 }
 
-fn flat_value(columns: &[CellPath], item: Value, all: bool) -> Vec<Value> {
+fn flat_value(columns: &[CellPath], item: Value, options: &FlattenOptions) -> Vec<Value> {
     let tag = item.span();
 
     match item {
-        Value::Record { val, .. } => {
-            let val = val.into_owned();
-            let retained_outer_columns: Vec<String> = if columns.is_empty() {
-                vec![]
-            } else {
-                val.iter()
-                    .filter_map(|(column, value)| {
-                        let column_requested =
-                            columns.iter().find(|c| c.to_column_name() == *column);
-                        let need_flatten = column_requested.is_some();
+        Value::Record { val, .. } => match flat_record(columns, val.into_owned(), options, tag) {
+            Ok(expanded) => expanded,
+            Err(err) => vec![Value::error(err, tag)],
+        },
+        Value::List { vals, .. } => vals.into_owned(),
+        item => vec![item],
+    }
+}
 
-                        let will_flatten = match value {
-                            Value::Record { .. } => need_flatten,
-                            Value::List { vals, .. } => {
-                                if all && vals.iter().all(|value| value.as_record().is_ok()) {
-                                    need_flatten
-                                } else {
-                                    matches!(
-                                        column_requested
-                                            .and_then(|cell_path| cell_path.members.first()),
-                                        Some(PathMember::String { .. })
-                                    )
-                                }
-                            }
-                            _ => false,
-                        };
+fn flat_record(
+    columns: &[CellPath],
+    val: Record,
+    options: &FlattenOptions,
+    tag: Span,
+) -> Result<Vec<Value>, ShellError> {
+    let requested = |column: &str| columns.iter().find(|c| c.to_column_name() == column);
+    let need_flatten = |column: &str| columns.is_empty() || requested(column).is_some();
+    let is_table =
+        |vals: &[Value]| options.all && vals.iter().all(|value| value.as_record().is_ok());
 
-                        (!will_flatten).then_some(column.clone())
-                    })
-                    .collect()
+    // the outer columns keep their names, the nested columns give way to them
+    let retained_outer_columns: Vec<String> = val
+        .iter()
+        .filter_map(|(column, value)| {
+            let will_flatten = match value {
+                Value::Record { .. } => need_flatten(column),
+                Value::List { vals, .. } => is_table(vals) && need_flatten(column),
+                _ => false,
             };
-            let mut out = IndexMap::<String, Value>::new();
-            let mut inner_table = None;
+            (!will_flatten).then_some(column.clone())
+        })
+        .collect();
+    let mut out = IndexMap::<String, Value>::new();
+    let mut inner_table = None;
 
-            for (column_index, (column, value)) in val.into_iter().enumerate() {
-                let column_requested = columns.iter().find(|c| c.to_column_name() == column);
-                let need_flatten = { columns.is_empty() || column_requested.is_some() };
-                let span = value.span();
-
-                match value {
-                    Value::Record { ref val, .. } => {
-                        if need_flatten {
-                            for (col, val) in val.clone().into_owned() {
-                                if out.contains_key(&col)
-                                    || retained_outer_columns.iter().any(|column| column == &col)
-                                {
-                                    out.insert(format!("{column}_{col}"), val);
-                                } else {
-                                    out.insert(col, val);
-                                }
-                            }
-                        } else if out.contains_key(&column) {
-                            out.insert(format!("{column}_{column}"), value);
-                        } else {
-                            out.insert(column, value);
-                        }
-                    }
-                    Value::List { vals, .. } => {
-                        if need_flatten && inner_table.is_some() {
-                            return vec![Value::error(
-                                ShellError::UnsupportedInput {
-                                    msg: "can only flatten one inner list at a time. tried flattening more than one column with inner lists... but is flattened already".into(),
-                                    input: "value originates from here".into(),
-                                    msg_span: tag,
-                                    input_span: span
-                                },
-                                span,
-                            )];
-                        }
+    for (column, value) in val {
+        let column_requested = requested(&column);
+        let span = value.span();
 
-                        if all && vals.iter().all(|f| f.as_record().is_ok()) {
-                            // it's a table (a list of record, we can flatten inner record)
-                            if need_flatten {
-                                let records = vals
-                                    .into_iter()
-                                    .filter_map(|v| v.into_record().ok())
-                                    .collect();
-
-                                inner_table = Some(TableInside::FlattenedRows {
-                                    records,
-                                    parent_column_name: column,
-                                    parent_column_index: column_index,
-                                });
-                            } else if out.contains_key(&column) {
-                                out.insert(
-                                    format!("{column}_{column}"),
-                                    Value::list_shared(vals, span),
-                                );
-                            } else {
-                                out.insert(column, Value::list_shared(vals, span));
-                            }
-                        } else if !columns.is_empty() {
-                            let cell_path =
-                                column_requested.and_then(|x| match x.members.first() {
-                                    Some(PathMember::String { val, .. }) => Some(val),
-                                    _ => None,
-                                });
-
-                            if let Some(r) = cell_path {
-                                inner_table = Some(TableInside::Entries(
-                                    r.clone(),
-                                    vals.into_owned(),
-                                    column_index,
-                                ));
-                            } else {
-                                out.insert(column, Value::list_shared(vals, span));
-                            }
-                        } else {
-                            inner_table = Some(TableInside::Entries(
-                                column,
-                                vals.into_owned(),
-                                column_index,
-                            ));
-                        }
-                    }
-                    _ => {
-                        out.insert(column, value);
+        match value {
+            Value::Record { val, .. } if need_flatten(&column) => {
+                for (parent, col, val) in
+                    nested_columns(column, val.into_owned(), options.depth, &options.separator)
+                {
+                    let taken = |name: &str| {
+                        out.contains_key(name)
+                            || retained_outer_columns.iter().any(|column| column == name)
+                    };
+                    if let Some(name) = options.column_name(&parent, col, taken, span)? {
+                        out.insert(name, val);
                     }
                 }
             }
-
-            let mut expanded = vec![];
-            match inner_table {
-                Some(TableInside::Entries(column, entries, parent_column_index)) => {
-                    for entry in entries {
-                        let base = out.clone();
-                        let mut record = Record::new();
-                        let mut index = 0;
-                        for (col, val) in base.into_iter() {
-                            // meet the flattened column, push them to result record first
-                            // this can avoid output column order changed.
-                            if index == parent_column_index {
-                                record.push(column.clone(), entry.clone());
-                            }
-                            record.push(col, val);
-                            index += 1;
-                        }
-                        // the flattened column may be the last column in the original table.
-                        if index == parent_column_index {
-                            record.push(column.clone(), entry);
-                        }
-                        expanded.push(Value::record(record, tag));
-                    }
+            Value::List { vals, .. } => {
+                if need_flatten(&column) && inner_table.is_some() {
+                    return Err(ShellError::UnsupportedInput {
+                        msg: "can only flatten one inner list at a time. tried flattening more than one column with inner lists... but is flattened already".into(),
+                        input: "value originates from here".into(),
+                        msg_span: tag,
+                        input_span: span,
+                    });
                 }
-                Some(TableInside::FlattenedRows {
-                    records,
-                    parent_column_name,
-                    parent_column_index,
-                }) => {
-                    for inner_record in records {
-                        let base = out.clone();
-                        let mut record = Record::new();
-                        let mut index = 0;
 
-                        for (base_col, base_val) in base {
-                            // meet the flattened column, push them to result record first
-                            // this can avoid output column order changed.
-                            if index == parent_column_index {
-                                for (col, val) in &inner_record {
-                                    if record.contains(col)
-                                        || (!columns.is_empty() && out.contains_key(col))
-                                    {
-                                        record.push(
-                                            format!("{parent_column_name}_{col}"),
-                                            val.clone(),
-                                        );
-                                    } else {
-                                        record.push(col, val.clone());
-                                    };
-                                }
-                            }
+                if is_table(&vals) {
+                    // it's a table (a list of record, we can flatten inner record)
+                    if need_flatten(&column) {
+                        let records = vals
+                            .into_iter()
+                            .filter_map(|v| v.into_record().ok())
+                            .collect();
 
-                            record.push(base_col, base_val);
-                            index += 1;
-                        }
+                        inner_table = Some(TableInside::FlattenedRows {
+                            records,
+                            parent_column_name: column,
+                            parent_column_index: out.len(),
+                        });
+                    } else {
+                        out.insert(column, Value::list_shared(vals, span));
+                    }
+                } else if !columns.is_empty() {
+                    let cell_path = column_requested.and_then(|x| match x.members.first() {
+                        Some(PathMember::String { val, .. }) => Some(val),
+                        _ => None,
+                    });
 
-                        // the flattened column may be the last column in the original table.
-                        if index == parent_column_index {
-                            for (col, val) in inner_record {
-                                if record.contains(&col)
-                                    || (!columns.is_empty() && out.contains_key(&col))
-                                {
-                                    record.push(format!("{parent_column_name}_{col}"), val);
-                                } else {
-                                    record.push(col, val);
-                                }
-                            }
-                        }
-                        expanded.push(Value::record(record, tag));
+                    if let Some(r) = cell_path {
+                        inner_table = Some(TableInside::Entries(
+                            r.clone(),
+                            vals.into_owned(),
+                            out.len(),
+                        ));
+                    } else {
+                        out.insert(column, Value::list_shared(vals, span));
                     }
+                } else {
+                    inner_table = Some(TableInside::Entries(column, vals.into_owned(), out.len()));
                 }
-                None => {
-                    expanded.push(Value::record(out.into_iter().collect(), tag));
+            }
+            value => {
+                out.insert(column, value);
+            }
+        }
+    }
+
+    let mut expanded = vec![];
+    match inner_table {
+        Some(TableInside::Entries(column, entries, parent_column_index)) => {
+            for entry in entries {
+                // push the entry at the position of its column, to keep the column order
+                let record = splice_columns(&out, parent_column_index, [(column.clone(), entry)]);
+                expanded.push(Value::record(record, tag));
+            }
+        }
+        Some(TableInside::FlattenedRows {
+            records,
+            parent_column_name,
+            parent_column_index,
+        }) => {
+            for inner_record in records {
+                let mut flattened: Vec<(String, Value)> = vec![];
+                for (parent, col, val) in nested_columns(
+                    parent_column_name.clone(),
+                    inner_record,
+                    options.depth,
+                    &options.separator,
+                ) {
+                    let taken = |name: &str| {
+                        out.contains_key(name) || flattened.iter().any(|(column, _)| column == name)
+                    };
+                    if let Some(name) = options.column_name(&parent, col, taken, tag)? {
+                        flattened.push((name, val));
+                    }
                 }
+                // push the flattened columns at the position of their column, to keep the column order
+                let record = splice_columns(&out, parent_column_index, flattened);
+                expanded.push(Value::record(record, tag));
             }
-            expanded
         }
-        Value::List { vals, .. } => vals.into_owned(),
-        item => vec![item],
+        None => {
+            expanded.push(Value::record(out.into_iter().collect(), tag));
+        }
     }
+    Ok(expanded)
 }
 
 #[cfg(test)]
@@ -192,6 +192,7 @@ If multiple cell paths are given, this will produce a list of values."
                 report_mode: ReportMode::FirstUse,
                 since: Some("0.105.0".into()),
                 expected_removal: None,
+                replacement: None,
                 help: Some("Cell-paths are now case-sensitive by default.\nTo access fields case-insensitively, add `!` after the relevant path member.".into())
             },
             DeprecationEntry {
@@ -199,7 +200,8 @@ If multiple cell paths are given, this will produce a list of values."
                 report_mode: ReportMode::FirstUse,
                 since: Some("0.106.0".into()),
                 expected_removal: None,
-                help: Some("This flag has been renamed to `--optional (-o)` to better reflect its behavior.".into())
+                replacement: Some("--optional".into()),
+                help: Some("This flag has been renamed to better reflect its behavior.".into())
             }
         ]
     }
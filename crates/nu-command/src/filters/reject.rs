@@ -1,3 +1,4 @@
+use super::utils::{ColumnSelection, PatternOptions};
 use nu_engine::command_prelude::*;
 use nu_protocol::{
     DeprecationEntry, DeprecationType, ReportMode, ast::PathMember, casing::Casing,
@@ -36,6 +37,11 @@ impl Command for Reject {
                 "Ignore missing data (make all cell path members optional) (deprecated).",
                 Some('i'),
             )
+            .switch(
+                "regex",
+                "Match the names of columns with regular expressions.",
+                Some('r'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
@@ -49,7 +55,9 @@ impl Command for Reject {
     }
 
     fn extra_description(&self) -> &str {
-        "To remove a quantity of rows or columns, use `skip`, `drop`, or `drop column`. To keep/retain only specific columns, use `select`."
+        "To remove a quantity of rows or columns, use `skip`, `drop`, or `drop column`. To keep/retain only specific columns, use `select`.
+
+Column names with `*`, `?` or `[` are globs removing all the columns they match, unless a column has that name as is, and with --regex all the column names are regular expressions. Patterns that match no column of a row are an error, unless --optional is used."
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -115,6 +123,7 @@ impl Command for Reject {
         let optional = call.has_flag(engine_state, stack, "optional")?
             || call.has_flag(engine_state, stack, "ignore-errors")?;
         let ignore_case = call.has_flag(engine_state, stack, "ignore-case")?;
+        let regex = call.has_flag(engine_state, stack, "regex")?;
 
         if optional {
             for cell_path in &mut new_columns {
@@ -128,7 +137,12 @@ impl Command for Reject {
             }
         }
 
-        reject(engine_state, span, input, new_columns)
+        let pattern_options = PatternOptions {
+            regex,
+            ignore_case,
+            optional,
+        };
+        reject(engine_state, span, input, new_columns, pattern_options)
     }
 
     fn deprecation_info(&self) -> Vec<DeprecationEntry> {
@@ -137,10 +151,8 @@ impl Command for Reject {
             report_mode: ReportMode::FirstUse,
             since: Some("0.106.0".into()),
             expected_removal: None,
-            help: Some(
-                "This flag has been renamed to `--optional (-o)` to better reflect its behavior."
-                    .into(),
-            ),
+            replacement: Some("--optional".into()),
+            help: Some("This flag has been renamed to better reflect its behavior.".into()),
         }]
     }
 
@@ -203,6 +215,13 @@ impl Command for Reject {
                     Value::test_record(record! { "name" => Value::test_string("Cargo.lock") }),
                 ])),
             },
+            Example {
+                description: "Reject the columns matching a regular expression.",
+                example: "{tmp_a: 1, tmp_b: 2, name: nu} | reject --regex '^tmp_'",
+                result: Some(Value::test_record(record! {
+                    "name" => Value::test_string("nu"),
+                })),
+            },
             Example {
                 description: "Reject item in list.",
                 example: "[1 2 3] | reject 1",
@@ -220,6 +239,7 @@ fn reject(
     span: Span,
     input: PipelineData,
     cell_paths: Vec<CellPath>,
+    pattern_options: PatternOptions,
 ) -> Result<PipelineData, ShellError> {
     let mut input = input.into_stream_or_original(engine_state);
     let mut unique_rows: HashSet<usize> = HashSet::new();
@@ -250,18 +270,22 @@ fn reject(
         };
     }
 
+    let columns = ColumnSelection::new(new_columns, pattern_options)?;
+
     // remove path_columns that are available in new_columns
     if let Some(metadata) = &mut metadata {
         metadata.path_columns.retain(|column| {
-            !new_columns
-                .iter()
-                .any(|cell_path| match cell_path.members.as_slice() {
-                    [PathMember::String { val, casing, .. }] => match casing {
-                        Casing::Sensitive => val == column,
-                        Casing::Insensitive => val.eq_ignore_case(column),
-                    },
-                    _ => false,
-                })
+            !columns.pattern_matches(column)
+                && !columns
+                    .paths()
+                    .iter()
+                    .any(|cell_path| match cell_path.members.as_slice() {
+                        [PathMember::String { val, casing, .. }] => match casing {
+                            Casing::Sensitive => val == column,
+                            Casing::Insensitive => val.eq_ignore_case(column),
+                        },
+                        _ => false,
+                    })
         });
     }
 
@@ -274,9 +298,7 @@ fn reject(
         })
     });
 
-    new_columns.append(&mut new_rows);
-
-    let has_integer_path_member = new_columns.iter().any(|path| {
+    let has_integer_path_member = columns.paths().iter().chain(&new_rows).any(|path| {
         path.members
             .iter()
             .any(|member| matches!(member, PathMember::Int { .. }))
@@ -292,10 +314,8 @@ fn reject(
                     }
 
                     let span = value.span();
-                    for cell_path in new_columns.iter() {
-                        if let Err(error) = value.remove_data_at_cell_path(&cell_path.members) {
-                            return Value::error(error, span);
-                        }
+                    if let Err(error) = remove_columns(&mut value, &columns) {
+                        return Value::error(error, span);
                     }
 
                     value
@@ -308,7 +328,16 @@ fn reject(
         input => {
             let mut val = input.into_value(span)?;
 
-            for cell_path in new_columns {
+            match &mut val {
+                // The patterns can match other columns in every row
+                Value::List { vals, .. } if columns.has_patterns() => {
+                    for row in vals.to_mut() {
+                        remove_columns(row, &columns)?;
+                    }
+                }
+                val => remove_columns(val, &columns)?,
+            }
+            for cell_path in new_rows {
                 val.remove_data_at_cell_path(&cell_path.members)?;
             }
 
@@ -317,6 +346,13 @@ fn reject(
     }
 }
 
+fn remove_columns(value: &mut Value, columns: &ColumnSelection) -> Result<(), ShellError> {
+    for cell_path in columns.paths_for(value)?.iter() {
+        value.remove_data_at_cell_path(&cell_path.members)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     #[test]
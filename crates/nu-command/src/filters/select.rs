@@ -1,3 +1,4 @@
+use super::utils::{ColumnSelection, PatternOptions};
 #[cfg(feature = "sqlite")]
 use crate::database::QueryPlan;
 use nu_engine::command_prelude::*;
@@ -43,6 +44,11 @@ impl Command for Select {
                 "Ignore missing data (make all cell path members optional) (deprecated).",
                 Some('i'),
             )
+            .switch(
+                "regex",
+                "Match the names of columns with regular expressions.",
+                Some('r'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
@@ -59,7 +65,11 @@ impl Command for Select {
     fn extra_description(&self) -> &str {
         "This differs from `get` in that, rather than accessing the given value in the data structure,
 it removes all non-selected values from the structure. Hence, using `select` on a table will
-produce a table, a list will produce a list, and a record will produce a record."
+produce a table, a list will produce a list, and a record will produce a record.
+
+Column names with `*`, `?` or `[` are globs selecting all the columns they match, unless a column
+has that name as is, and with --regex all the column names are regular expressions. Patterns that
+match no column of a row are an error, unless --optional is used."
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -123,6 +133,7 @@ produce a table, a list will produce a list, and a record will produce a record.
         let optional = call.has_flag(engine_state, stack, "optional")?
             || call.has_flag(engine_state, stack, "ignore-errors")?;
         let ignore_case = call.has_flag(engine_state, stack, "ignore-case")?;
+        let regex = call.has_flag(engine_state, stack, "regex")?;
         let span = call.head;
 
         if optional {
@@ -137,7 +148,12 @@ produce a table, a list will produce a list, and a record will produce a record.
             }
         }
 
-        select(engine_state, span, new_columns, input)
+        let pattern_options = PatternOptions {
+            regex,
+            ignore_case,
+            optional,
+        };
+        select(engine_state, span, new_columns, pattern_options, input)
     }
 
     fn deprecation_info(&self) -> Vec<DeprecationEntry> {
@@ -146,10 +162,8 @@ produce a table, a list will produce a list, and a record will produce a record.
             report_mode: ReportMode::FirstUse,
             since: Some("0.106.0".into()),
             expected_removal: None,
-            help: Some(
-                "This flag has been renamed to `--optional (-o)` to better reflect its behavior."
-                    .into(),
-            ),
+            replacement: Some("--optional".into()),
+            help: Some("This flag has been renamed to better reflect its behavior.".into()),
         }]
     }
 
@@ -205,6 +219,23 @@ produce a table, a list will produce a list, and a record will produce a record.
                     }),
                 ])),
             },
+            Example {
+                description: "Select the columns matching a glob.",
+                example: "{http_host: example.com, http_port: 80, user: ada} | select 'http_*'",
+                result: Some(Value::test_record(record! {
+                    "http_host" => Value::test_string("example.com"),
+                    "http_port" => Value::test_int(80),
+                })),
+            },
+            Example {
+                description: "Select the columns matching regular expressions.",
+                example: "[[id tmp_a tmp_b name]; [1 x y nu]] | select --regex '^tmp_' '^name$'",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "tmp_a" => Value::test_string("x"),
+                    "tmp_b" => Value::test_string("y"),
+                    "name" => Value::test_string("nu"),
+                })])),
+            },
             Example {
                 description: "Select multiple columns by spreading a list.",
                 example: "let cols = [name type]; [[name type size]; [Cargo.toml toml 1kb] [Cargo.lock toml 2kb]] | select ...$cols",
@@ -227,6 +258,7 @@ fn select(
     engine_state: &EngineState,
     call_span: Span,
     columns: Vec<CellPath>,
+    pattern_options: PatternOptions,
     mut input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let mut unique_rows: BTreeSet<usize> = BTreeSet::new();
@@ -253,7 +285,7 @@ fn select(
             }
         };
     }
-    let columns = new_columns;
+    let columns = ColumnSelection::new(new_columns, pattern_options)?;
 
     let input = if !unique_rows.is_empty() {
         let metadata = input.take_metadata();
@@ -276,11 +308,13 @@ fn select(
     #[cfg(feature = "sqlite")]
     // Pushdown optimization: handle 'select' via QueryPlan for lazy column selection
     if let PipelineData::Value(Value::Custom { val, .. }, ..) = &input
+        && !columns.has_patterns()
         && let Some(plan) = QueryPlan::try_from_any(val.as_any())
     {
         // Push down only simple single-segment string paths; everything else
         // falls back to the generic in-memory selection path below.
         let select_columns: Option<Vec<String>> = columns
+            .paths()
             .iter()
             .map(|column| match column.members.as_slice() {
                 [PathMember::String { val, .. }] => Some(val.clone()),
@@ -304,9 +338,13 @@ fn select(
                 } => Ok(input_vals
                     .into_iter()
                     .map(move |input_val| {
-                        if !columns.is_empty() {
+                        if !columns.paths().is_empty() {
+                            let paths = match columns.paths_for(&input_val) {
+                                Ok(paths) => paths,
+                                Err(e) => return Value::error(e, call_span),
+                            };
                             let mut record = Record::new();
-                            for path in &columns {
+                            for path in paths.iter() {
                                 match input_val.follow_cell_path(&path.members) {
                                     Ok(fetcher) => {
                                         record.push(path.to_column_name(), fetcher.into_owned());
@@ -326,10 +364,10 @@ fn select(
                         metadata,
                     )),
                 _ => {
-                    if !columns.is_empty() {
+                    if !columns.paths().is_empty() {
                         let mut record = Record::new();
 
-                        for cell_path in columns {
+                        for cell_path in columns.paths_for(&v)?.iter() {
                             let result = v.follow_cell_path(&cell_path.members)?;
                             record.push(cell_path.to_column_name(), result.into_owned());
                         }
@@ -344,9 +382,13 @@ fn select(
         }
         PipelineData::ListStream(stream, metadata, ..) => Ok(stream
             .map(move |x| {
-                if !columns.is_empty() {
+                if !columns.paths().is_empty() {
+                    let paths = match columns.paths_for(&x) {
+                        Ok(paths) => paths,
+                        Err(e) => return Value::error(e, call_span),
+                    };
                     let mut record = Record::new();
-                    for path in &columns {
+                    for path in paths.iter() {
                         match x.follow_cell_path(&path.members) {
                             Ok(value) => {
                                 record.push(path.to_column_name(), value.into_owned());
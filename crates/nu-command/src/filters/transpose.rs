@@ -1,4 +1,7 @@
-use nu_engine::{column::get_columns, command_prelude::*};
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+use nu_engine::command_prelude::*;
 use nu_protocol::shell_error::generic::GenericError;
 
 #[derive(Clone)]
@@ -11,6 +14,8 @@ pub struct TransposeArgs {
     as_record: bool,
     keep_last: bool,
     keep_all: bool,
+    slugify: bool,
+    dedupe: bool,
 }
 
 impl Command for Transpose {
@@ -49,6 +54,16 @@ impl Command for Transpose {
                 "On repetition of record fields due to `header-row`, keep all the values obtained.",
                 Some('a'),
             )
+            .switch(
+                "slugify",
+                "Lowercase the headers of `header-row`, with `_` in place of spaces and symbols.",
+                Some('s'),
+            )
+            .switch(
+                "dedupe",
+                "On repetition of record fields due to `header-row`, keep all the fields, numbering the repeated names.",
+                Some('u'),
+            )
             .allow_variants_without_examples(true)
             .rest(
                 "rest",
@@ -62,8 +77,14 @@ impl Command for Transpose {
         "Transposes the table contents so rows become columns and columns become rows."
     }
 
+    fn extra_description(&self) -> &str {
+        "The input is read one row at a time, and only the transposed table is kept, so that long \
+streams, like the key and value rows read with `transpose --header-row --as-record`, aren't \
+collected first."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["pivot"]
+        vec!["pivot", "rotate"]
     }
 
     fn run(
@@ -126,6 +147,15 @@ impl Command for Transpose {
                     "c2" =>  Value::test_int(2),
                 })),
             },
+            Example {
+                description: "Clean up the headers of a header row.",
+                example: "[[key value]; ['First Name' Ada] ['E-mail' ada@example.com] ['First Name' Grace]] | transpose --header-row --as-record --slugify --dedupe",
+                result: Some(Value::test_record(record! {
+                    "first_name" => Value::test_string("Ada"),
+                    "e_mail" => Value::test_string("ada@example.com"),
+                    "first_name_2" => Value::test_string("Grace"),
+                })),
+            },
         ]
     }
 }
@@ -143,6 +173,8 @@ pub fn transpose(
         as_record: call.has_flag(engine_state, stack, "as-record")?,
         keep_last: call.has_flag(engine_state, stack, "keep-last")?,
         keep_all: call.has_flag(engine_state, stack, "keep-all")?,
+        slugify: call.has_flag(engine_state, stack, "slugify")?,
+        dedupe: call.has_flag(engine_state, stack, "dedupe")?,
         rest: call.rest(engine_state, stack, 0)?,
     };
 
@@ -164,6 +196,31 @@ pub fn transpose(
             span: call.get_flag_span(stack, "keep-last").expect("has flag"),
         });
     }
+    if !args.header_row && args.slugify {
+        return Err(ShellError::IncompatibleParametersSingle {
+            msg: "Can only be used with `--header-row`(`-r`)".into(),
+            span: call.get_flag_span(stack, "slugify").expect("has flag"),
+        });
+    }
+    if !args.header_row && args.dedupe {
+        return Err(ShellError::IncompatibleParametersSingle {
+            msg: "Can only be used with `--header-row`(`-r`)".into(),
+            span: call.get_flag_span(stack, "dedupe").expect("has flag"),
+        });
+    }
+    if args.dedupe && (args.keep_all || args.keep_last) {
+        let other = if args.keep_all {
+            "keep-all"
+        } else {
+            "keep-last"
+        };
+        return Err(ShellError::IncompatibleParameters {
+            left_message: format!("can't use `--{other}` at the same time"),
+            left_span: call.get_flag_span(stack, other).expect("has flag"),
+            right_message: "because of `--dedupe`".into(),
+            right_span: call.get_flag_span(stack, "dedupe").expect("has flag"),
+        });
+    }
     if args.keep_all && args.keep_last {
         return Err(ShellError::IncompatibleParameters {
             left_message: "can't use `--keep-last` at the same time".into(),
@@ -174,15 +231,23 @@ pub fn transpose(
     }
 
     let metadata = input.take_metadata();
-    let input: Vec<_> = input.into_iter().collect();
+    // the input is read one row at a time, and its values are moved into the transposed records,
+    // so that long streams don't need to be collected first
+    let mut transposed = IndexMap::<String, Record>::new();
+    let mut headers: Vec<String> = vec![];
+    let mut used_headers = HashSet::new();
+    let mut header_column: Option<String> = None;
+    let title_header = args
+        .rest
+        .first()
+        .map(|title| title.item.clone())
+        .unwrap_or_else(|| "column0".into());
 
-    // Ensure error values are propagated and non-record values are rejected
-    for value in input.iter() {
-        match value {
-            Value::Error { .. } => {
-                return Ok(value.clone().into_pipeline_data_with_metadata(metadata));
-            }
-            Value::Record { .. } => {} // go on, this is what we're looking for
+    for value in input {
+        let mut row = match value {
+            Value::Record { val, .. } => val.into_owned(),
+            // Ensure error values are propagated and non-record values are rejected
+            Value::Error { .. } => return Ok(value.into_pipeline_data_with_metadata(metadata)),
             _ => {
                 return Err(ShellError::OnlySupportsThisInputType {
                     exp_input_type: "table or record".into(),
@@ -191,101 +256,57 @@ pub fn transpose(
                     src_span: value.span(),
                 });
             }
-        }
-    }
-
-    let descs = get_columns(&input);
-
-    let mut headers: Vec<String> = Vec::with_capacity(input.len());
+        };
 
-    if args.header_row {
-        for i in input.iter() {
-            if let Some(desc) = descs.first() {
-                match &i.get_data_by_key(desc) {
-                    Some(x) => {
-                        if let Ok(s) = x.coerce_string() {
-                            headers.push(s);
-                        } else {
-                            return Err(ShellError::Generic(GenericError::new(
-                                "Header row needs string headers",
-                                "used non-string headers",
-                                name,
-                            )));
-                        }
-                    }
-                    _ => {
-                        return Err(ShellError::Generic(GenericError::new(
-                            "Header row is incomplete and can't be used",
-                            "using incomplete header row",
-                            name,
-                        )));
-                    }
-                }
-            } else {
+        let header = if args.header_row {
+            if header_column.is_none() {
+                header_column = row.columns().next().cloned();
+            }
+            let Some(header) = header_column.as_ref().and_then(|column| row.remove(column)) else {
                 return Err(ShellError::Generic(GenericError::new(
                     "Header row is incomplete and can't be used",
                     "using incomplete header row",
                     name,
                 )));
-            }
-        }
-    } else {
-        for i in 0..=input.len() {
-            if let Some(name) = args.rest.get(i) {
-                headers.push(name.item.clone())
-            } else {
-                headers.push(format!("column{i}"));
-            }
-        }
-    }
+            };
+            let Ok(header) = header.coerce_string() else {
+                return Err(ShellError::Generic(GenericError::new(
+                    "Header row needs string headers",
+                    "used non-string headers",
+                    name,
+                )));
+            };
+            args.clean_header(header, headers.len(), &mut used_headers)
+        } else {
+            let index = headers.len() + usize::from(!args.ignore_titles);
+            args.rest
+                .get(index)
+                .map(|header| header.item.clone())
+                .unwrap_or_else(|| format!("column{index}"))
+        };
 
-    let mut descs = descs.into_iter();
-    if args.header_row {
-        descs.next();
-    }
-    let mut result_data = descs
-        .map(|desc| {
-            let mut column_num: usize = 0;
+        for (column, record) in transposed.iter_mut() {
+            let value = row.remove(column).unwrap_or_else(|| Value::nothing(name));
+            args.push_value(record, &header, value);
+        }
+        // columns that are new in this row are missing in the rows before
+        for (column, value) in row {
             let mut record = Record::new();
-
             if !args.ignore_titles && !args.header_row {
-                record.push(
-                    headers[column_num].clone(),
-                    Value::string(desc.clone(), name),
-                );
-                column_num += 1
+                record.push(title_header.clone(), Value::string(column.clone(), name));
             }
-
-            for i in input.iter() {
-                let x = i
-                    .get_data_by_key(&desc)
-                    .unwrap_or_else(|| Value::nothing(name));
-                match record.get_mut(&headers[column_num]) {
-                    None => {
-                        record.push(headers[column_num].clone(), x);
-                    }
-                    Some(val) => {
-                        if args.keep_all {
-                            let current_span = val.span();
-                            match val {
-                                Value::List { vals, .. } => {
-                                    vals.to_mut().push(x);
-                                }
-                                v => {
-                                    *v = Value::list(vec![std::mem::take(v), x], current_span);
-                                }
-                            };
-                        } else if args.keep_last {
-                            *val = x;
-                        }
-                    }
-                }
-
-                column_num += 1;
+            for earlier in &headers {
+                args.push_value(&mut record, earlier, Value::nothing(name));
             }
+            args.push_value(&mut record, &header, value);
+            transposed.insert(column, record);
+        }
+        headers.push(header);
+    }
 
-            Value::record(record, name)
-        })
+    let mut result_data = transposed
+        .into_values()
+        .map(|record| Value::record(record, name))
         .collect::<Vec<Value>>();
     if result_data.len() == 1 && args.as_record {
         Ok(PipelineData::value(
@@ -303,6 +324,73 @@ pub fn transpose(
     }
 }
 
+impl TransposeArgs {
+    /// Put the value of an input row in a transposed record, under the header of that row.
+    fn push_value(&self, record: &mut Record, header: &str, value: Value) {
+        match record.get_mut(header) {
+            None => {
+                record.push(header, value);
+            }
+            Some(val) => {
+                if self.keep_all {
+                    let current_span = val.span();
+                    match val {
+                        Value::List { vals, .. } => {
+                            vals.to_mut().push(value);
+                        }
+                        v => {
+                            *v = Value::list(vec![std::mem::take(v), value], current_span);
+                        }
+                    };
+                } else if self.keep_last {
+                    *val = value;
+                }
+            }
+        }
+    }
+
+    /// The header of the input row at `index`, cleaned up with `--slugify` and `--dedupe`.
+    fn clean_header(&self, header: String, index: usize, used: &mut HashSet<String>) -> String {
+        let header = if self.slugify {
+            let slug = slugify(&header);
+            if slug.is_empty() {
+                format!("column{index}")
+            } else {
+                slug
+            }
+        } else {
+            header
+        };
+        let mut unique = header.clone();
+        if self.dedupe {
+            let mut number = 2;
+            while used.contains(&unique) {
+                unique = format!("{header}_{number}");
+                number += 1;
+            }
+        }
+        used.insert(unique.clone());
+        unique
+    }
+}
+
+/// Lowercase the header, with a `_` in place of each run of characters that aren't letters or
+/// digits.
+fn slugify(header: &str) -> String {
+    let mut slug = String::with_capacity(header.len());
+    for c in header.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    if slug.ends_with('_') {
+        slug.pop();
+    }
+    slug
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
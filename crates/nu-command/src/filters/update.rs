@@ -1,5 +1,6 @@
+use super::utils::{ColumnReplacement, column_replacements};
 use nu_engine::{ClosureEval, ClosureEvalOnce, command_prelude::*};
-use nu_protocol::ast::PathMember;
+use nu_protocol::{FromValue, ast::PathMember};
 
 #[derive(Clone)]
 pub struct Update;
@@ -22,10 +23,10 @@ impl Command for Update {
             ])
             .required(
                 "field",
-                SyntaxShape::CellPath,
-                "The name of the column to update.",
+                SyntaxShape::OneOf(vec![SyntaxShape::CellPath, SyntaxShape::Record(vec![])]),
+                "The name of the column to update, or a record of columns and their replacement values.",
             )
-            .required(
+            .optional(
                 "replacement value",
                 SyntaxShape::Any,
                 "The new value to give the cell(s), or a closure to create the value.",
@@ -42,7 +43,9 @@ impl Command for Update {
         "When updating a column, the closure will be run for each row, and the current row will be passed as the first argument. \
 Referencing `$in` inside the closure will provide the value at the column for the current row.
 
-When updating a specific index, the closure will instead be run once. The first argument to the closure and the `$in` value will both be the current value at the index."
+When updating a specific index, the closure will instead be run once. The first argument to the closure and the `$in` value will both be the current value at the index.
+
+To update several columns in a single pass over the rows, give a record of the columns and their replacement values or closures instead. The columns are updated in the order of the record, like with chained `update` calls."
     }
 
     fn run(
@@ -81,6 +84,14 @@ When updating a specific index, the closure will instead be run once. The first
                     "authors" => Value::test_string("Andrés,JT,Yehuda"),
                 })])),
             },
+            Example {
+                description: "Update several columns at once.",
+                example: "[[name stars]; [nu 5]] | update {name: {str upcase}, stars: {|row| $row.stars * 2 }}",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "name" => Value::test_string("NU"),
+                    "stars" => Value::test_int(10),
+                })])),
+            },
             Example {
                 description: "Update a value at an index in a list.",
                 example: "[1 2 3] | update 1 4",
@@ -249,8 +260,8 @@ fn update(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let head = call.head;
-    let cell_path: CellPath = call.req(engine_state, stack, 0)?;
-    let replacement: Value = call.req(engine_state, stack, 1)?;
+    let field: Value = call.req(engine_state, stack, 0)?;
+    let replacement: Option<Value> = call.opt(engine_state, stack, 1)?;
     let is_custom = matches!(&input, PipelineData::Value(Value::Custom { .. }, _));
     let input = if is_custom {
         input
@@ -258,14 +269,78 @@ fn update(
         input.into_stream_or_original(engine_state)
     };
 
-    update_recursive(
-        engine_state,
-        stack,
-        head,
-        replacement,
-        input,
-        &cell_path.members,
-    )
+    match (field, replacement) {
+        (Value::Record { val, .. }, None) => {
+            update_columns(engine_state, stack, head, val.into_owned(), input)
+        }
+        (Value::Record { .. }, Some(replacement)) => {
+            Err(ShellError::IncompatibleParametersSingle {
+                msg: "The replacement values of a record of columns are in the record".into(),
+                span: replacement.span(),
+            })
+        }
+        (field, Some(replacement)) => {
+            let cell_path = CellPath::from_value(field)?;
+            update_recursive(
+                engine_state,
+                stack,
+                head,
+                replacement,
+                input,
+                &cell_path.members,
+            )
+        }
+        (field, None) => Err(ShellError::MissingParameter {
+            param_name: "replacement value".into(),
+            span: field.span(),
+        }),
+    }
+}
+
+/// Update the columns of a record of replacements in a single pass over the rows.
+fn update_columns(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    head_span: Span,
+    replacements: Record,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let mut replacements = column_replacements(engine_state, stack, replacements);
+    let mut update_row = move |value: &mut Value| -> Result<(), ShellError> {
+        for (path, replacement) in &mut replacements {
+            match replacement {
+                ColumnReplacement::Closure(closure) => {
+                    update_value_by_closure(value, closure, head_span, path)?
+                }
+                ColumnReplacement::Value(new_value) => {
+                    value.update_data_at_cell_path(path, new_value.clone())?
+                }
+            }
+        }
+        Ok(())
+    };
+
+    match input {
+        PipelineData::Value(mut value, metadata) => {
+            update_row(&mut value)?;
+            Ok(value.into_pipeline_data_with_metadata(metadata))
+        }
+        PipelineData::ListStream(stream, metadata) => {
+            let stream = stream.map(move |mut value| match update_row(&mut value) {
+                Ok(()) => value,
+                Err(e) => Value::error(e, head_span),
+            });
+            Ok(PipelineData::list_stream(stream, metadata))
+        }
+        PipelineData::Empty => Err(ShellError::IncompatiblePathAccess {
+            type_name: "empty pipeline".to_string(),
+            span: head_span,
+        }),
+        PipelineData::ByteStream(stream, ..) => Err(ShellError::IncompatiblePathAccess {
+            type_name: stream.type_().describe().into(),
+            span: head_span,
+        }),
+    }
 }
 
 /// Applies `closure` to every leaf cell reached by following `cell_path` inside `value`.
@@ -1,7 +1,8 @@
 use std::borrow::Cow;
 
+use super::utils::{ColumnReplacement, column_replacements};
 use nu_engine::{ClosureEval, ClosureEvalOnce, command_prelude::*};
-use nu_protocol::ast::PathMember;
+use nu_protocol::{FromValue, ast::PathMember};
 
 #[derive(Clone)]
 pub struct Upsert;
@@ -23,10 +24,10 @@ impl Command for Upsert {
             ])
             .required(
                 "field",
-                SyntaxShape::CellPath,
-                "The name of the column to update or insert.",
+                SyntaxShape::OneOf(vec![SyntaxShape::CellPath, SyntaxShape::Record(vec![])]),
+                "The name of the column to update or insert, or a record of columns and their replacement values.",
             )
-            .required(
+            .optional(
                 "replacement value",
                 SyntaxShape::Any,
                 "The new value to give the cell(s), or a closure to create the value.",
@@ -44,7 +45,9 @@ impl Command for Upsert {
 Referencing `$in` inside the closure will provide the value at the column for the current row or null if the column does not exist.
 
 When updating a specific index, the closure will instead be run once. The first argument to the closure and the `$in` value will both be the current value at the index. \
-If the command is inserting at the end of a list or table, then both of these values will be null."
+If the command is inserting at the end of a list or table, then both of these values will be null.
+
+To update or insert several columns in a single pass over the rows, give a record of the columns and their replacement values or closures instead. The columns are upserted in the order of the record, like with chained `upsert` calls."
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -71,6 +74,15 @@ If the command is inserting at the end of a list or table, then both of these va
                     "stars" => Value::test_int(5),
                 })),
             },
+            Example {
+                description: "Update a column and insert another one at once.",
+                example: "[[name stars]; [nu 5]] | upsert {stars: {$in + 1}, rating: {|row| $row.stars * 10 }}",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "name" => Value::test_string("nu"),
+                    "stars" => Value::test_int(6),
+                    "rating" => Value::test_int(60),
+                })])),
+            },
             Example {
                 description: "Insert a new entry into a record.",
                 example: "{'name': 'nu', 'stars': 5} | upsert language 'Rust'",
@@ -309,18 +321,82 @@ fn upsert(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let head = call.head;
-    let cell_path: CellPath = call.req(engine_state, stack, 0)?;
-    let replacement: Value = call.req(engine_state, stack, 1)?;
+    let field: Value = call.req(engine_state, stack, 0)?;
+    let replacement: Option<Value> = call.opt(engine_state, stack, 1)?;
     let input = input.into_stream_or_original(engine_state);
 
-    upsert_recursive(
-        engine_state,
-        stack,
-        head,
-        replacement,
-        input,
-        &cell_path.members,
-    )
+    match (field, replacement) {
+        (Value::Record { val, .. }, None) => {
+            upsert_columns(engine_state, stack, head, val.into_owned(), input)
+        }
+        (Value::Record { .. }, Some(replacement)) => {
+            Err(ShellError::IncompatibleParametersSingle {
+                msg: "The replacement values of a record of columns are in the record".into(),
+                span: replacement.span(),
+            })
+        }
+        (field, Some(replacement)) => {
+            let cell_path = CellPath::from_value(field)?;
+            upsert_recursive(
+                engine_state,
+                stack,
+                head,
+                replacement,
+                input,
+                &cell_path.members,
+            )
+        }
+        (field, None) => Err(ShellError::MissingParameter {
+            param_name: "replacement value".into(),
+            span: field.span(),
+        }),
+    }
+}
+
+/// Update or insert the columns of a record of replacements in a single pass over the rows.
+fn upsert_columns(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    head_span: Span,
+    replacements: Record,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let mut replacements = column_replacements(engine_state, stack, replacements);
+    let mut upsert_row = move |value: &mut Value| -> Result<(), ShellError> {
+        for (path, replacement) in &mut replacements {
+            match replacement {
+                ColumnReplacement::Closure(closure) => {
+                    upsert_value_by_closure(value, closure, head_span, path)?
+                }
+                ColumnReplacement::Value(new_value) => {
+                    value.upsert_data_at_cell_path(path, new_value.clone())?
+                }
+            }
+        }
+        Ok(())
+    };
+
+    match input {
+        PipelineData::Value(mut value, metadata) => {
+            upsert_row(&mut value)?;
+            Ok(value.into_pipeline_data_with_metadata(metadata))
+        }
+        PipelineData::ListStream(stream, metadata) => {
+            let stream = stream.map(move |mut value| match upsert_row(&mut value) {
+                Ok(()) => value,
+                Err(e) => Value::error(e, head_span),
+            });
+            Ok(PipelineData::list_stream(stream, metadata))
+        }
+        PipelineData::Empty => Err(ShellError::IncompatiblePathAccess {
+            type_name: "empty pipeline".to_string(),
+            span: head_span,
+        }),
+        PipelineData::ByteStream(stream, ..) => Err(ShellError::IncompatiblePathAccess {
+            type_name: stream.type_().describe().into(),
+            span: head_span,
+        }),
+    }
 }
 
 fn upsert_value_by_closure(
@@ -1,9 +1,15 @@
+use fancy_regex::Regex;
 use itertools::Itertools;
 use nu_engine::{CallExt, ClosureEval};
+use nu_glob::MatchOptions;
 use nu_protocol::{
-    IntoPipelineData, PipelineData, ShellError, Span, Value,
+    IntoPipelineData, PipelineData, Record, ShellError, Span, Value,
+    ast::{CellPath, PathMember},
+    casing::Casing,
     engine::{Call, Closure, EngineState, Stack},
+    shell_error::generic::GenericError,
 };
+use std::borrow::Cow;
 
 pub fn chain_error_with_input(
     error_source: ShellError,
@@ -111,3 +117,195 @@ pub fn boolean_fold(
 
     Ok(Value::bool(!accumulator, head).into_pipeline_data())
 }
+
+/// How `select` and `reject` match the names of columns with patterns.
+#[derive(Clone, Copy)]
+pub struct PatternOptions {
+    /// Every column name is a regex, instead of only the names with glob characters being globs
+    pub regex: bool,
+    pub ignore_case: bool,
+    /// Patterns that match no column are ignored instead of being an error
+    pub optional: bool,
+}
+
+enum ColumnPattern {
+    Glob(nu_glob::Pattern, MatchOptions),
+    Regex(Regex),
+}
+
+impl ColumnPattern {
+    fn matches(&self, column: &str) -> bool {
+        match self {
+            ColumnPattern::Glob(pattern, options) => pattern.matches_with(column, *options),
+            ColumnPattern::Regex(regex) => regex.is_match(column).unwrap_or(false),
+        }
+    }
+}
+
+/// The cell paths given to `select` or `reject`, where the column names that are patterns are
+/// replaced with the columns they match in every row.
+pub struct ColumnSelection {
+    paths: Vec<CellPath>,
+    /// The patterns, with the index of the path they're the name of
+    patterns: Vec<(usize, ColumnPattern)>,
+    optional: bool,
+}
+
+impl ColumnSelection {
+    pub fn new(paths: Vec<CellPath>, options: PatternOptions) -> Result<Self, ShellError> {
+        let invalid = |msg: String, span: Span| {
+            ShellError::Generic(GenericError::new("Invalid column pattern", msg, span))
+        };
+
+        let mut patterns = vec![];
+        for (index, path) in paths.iter().enumerate() {
+            let [PathMember::String { val, span, .. }] = path.members.as_slice() else {
+                continue;
+            };
+            let pattern = if options.regex {
+                let flags = if options.ignore_case { "(?i)" } else { "" };
+                Regex::new(&format!("{flags}{val}"))
+                    .map(ColumnPattern::Regex)
+                    .map_err(|err| invalid(err.to_string(), *span))?
+            } else if val.contains(['*', '?', '[']) {
+                let glob =
+                    nu_glob::Pattern::new(val).map_err(|err| invalid(err.msg.into(), *span))?;
+                let options = MatchOptions {
+                    case_sensitive: !options.ignore_case,
+                    ..MatchOptions::default()
+                };
+                ColumnPattern::Glob(glob, options)
+            } else {
+                continue;
+            };
+            patterns.push((index, pattern));
+        }
+
+        Ok(Self {
+            paths,
+            patterns,
+            optional: options.optional,
+        })
+    }
+
+    /// The cell paths, as given.
+    pub fn paths(&self) -> &[CellPath] {
+        &self.paths
+    }
+
+    pub fn has_patterns(&self) -> bool {
+        !self.patterns.is_empty()
+    }
+
+    /// Whether a pattern matches the name of this column.
+    pub fn pattern_matches(&self, column: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|(_, pattern)| pattern.matches(column))
+    }
+
+    /// The cell paths for a row, with the patterns replaced with the columns of the row that they
+    /// match. Globs matching the name of a column as is stay that column.
+    pub fn paths_for(&self, row: &Value) -> Result<Cow<'_, [CellPath]>, ShellError> {
+        if self.patterns.is_empty() {
+            return Ok(Cow::Borrowed(&self.paths));
+        }
+
+        let record = match row {
+            Value::Record { val, .. } => Some(val),
+            _ => None,
+        };
+        let mut paths: Vec<CellPath> = Vec::with_capacity(self.paths.len());
+        let mut push = |path: CellPath| {
+            if !paths.contains(&path) {
+                paths.push(path)
+            }
+        };
+        let mut patterns = self.patterns.iter().peekable();
+        for (index, path) in self.paths.iter().enumerate() {
+            let Some((_, pattern)) = patterns.next_if(|(i, _)| *i == index) else {
+                push(path.clone());
+                continue;
+            };
+            let [
+                PathMember::String {
+                    val: name, span, ..
+                },
+            ] = path.members.as_slice()
+            else {
+                continue;
+            };
+
+            let Some(record) = record else {
+                if self.optional {
+                    continue;
+                }
+                return Err(ShellError::Generic(GenericError::new(
+                    "Column patterns only match the columns of records",
+                    format!(
+                        "can't match `{name}` with the columns of {}",
+                        row.get_type()
+                    ),
+                    *span,
+                )));
+            };
+            if matches!(pattern, ColumnPattern::Glob(..)) && record.contains(name) {
+                push(path.clone());
+                continue;
+            }
+
+            let mut matched = false;
+            for column in record.columns().filter(|column| pattern.matches(column)) {
+                matched = true;
+                push(CellPath {
+                    members: vec![PathMember::String {
+                        val: column.clone(),
+                        span: *span,
+                        optional: false,
+                        casing: Casing::Sensitive,
+                    }],
+                });
+            }
+            if !matched && !self.optional {
+                return Err(ShellError::Generic(
+                    GenericError::new(
+                        "No column matches the pattern",
+                        format!("no column matches `{name}`"),
+                        *span,
+                    )
+                    .with_help("use --optional to ignore patterns that match no column"),
+                ));
+            }
+        }
+
+        Ok(Cow::Owned(paths))
+    }
+}
+
+/// The new value of a column in the record given to `update` or `upsert`.
+pub enum ColumnReplacement {
+    Closure(ClosureEval),
+    Value(Value),
+}
+
+/// The columns of the record given to `update` or `upsert`, with their new values.
+pub fn column_replacements(
+    engine_state: &EngineState,
+    stack: &Stack,
+    replacements: Record,
+) -> Vec<(Vec<PathMember>, ColumnReplacement)> {
+    replacements
+        .into_iter()
+        .map(|(column, replacement)| {
+            let span = replacement.span();
+            let path = vec![PathMember::string(column, false, Casing::Sensitive, span)];
+            let replacement = match replacement {
+                Value::Closure { val, .. } => {
+                    ColumnReplacement::Closure(ClosureEval::new(engine_state, stack, *val))
+                }
+                value => ColumnReplacement::Value(value),
+            };
+            (path, replacement)
+        })
+        .collect()
+}
@@ -1,6 +1,6 @@
 use nu_engine::command_prelude::*;
-use nu_protocol::ListStream;
-use std::num::NonZeroUsize;
+use nu_protocol::{FromValue, ListStream, shell_error::generic::GenericError};
+use std::{collections::VecDeque, iter::Fuse, num::NonZeroUsize};
 
 #[derive(Clone)]
 pub struct Window;
@@ -16,13 +16,23 @@ impl Command for Window {
                 Type::list(Type::Any),
                 Type::list(Type::list(Type::Any)),
             )])
-            .required("window_size", SyntaxShape::Int, "The size of each window.")
+            .required(
+                "window_size",
+                SyntaxShape::OneOf(vec![SyntaxShape::Int, SyntaxShape::Duration]),
+                "The size of each window.",
+            )
             .named(
                 "stride",
-                SyntaxShape::Int,
-                "The number of rows to slide over between windows.",
+                SyntaxShape::OneOf(vec![SyntaxShape::Int, SyntaxShape::Duration]),
+                "The number of rows, or the time with --by, to slide over between windows.",
                 Some('s'),
             )
+            .named(
+                "by",
+                SyntaxShape::CellPath,
+                "Make windows of a duration, by the time in this column of the rows.",
+                Some('b'),
+            )
             .switch(
                 "remainder",
                 "Yield last chunks even if they have fewer elements than size.",
@@ -36,7 +46,12 @@ impl Command for Window {
     }
 
     fn extra_description(&self) -> &str {
-        "This command will error if `window_size` or `stride` are negative or zero."
+        "This command will error if `window_size` or `stride` are negative or zero.
+
+With --by, `window_size` and `stride` are durations, and each window has the rows whose time in \
+the column is in it. The windows start at multiples of `stride` since the Unix epoch, and the \
+stride is the size of the windows by default, so that they don't overlap. The rows must be sorted \
+by their time, and windows without rows are skipped."
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -71,6 +86,16 @@ impl Command for Window {
                     Value::test_list(vec![Value::test_int(4), Value::test_int(5)]),
                 ])),
             },
+            Example {
+                example: "[[time value]; [2024-01-01T00:00:10Z 1] [2024-01-01T00:00:40Z 2] [2024-01-01T00:01:20Z 3]] | window 1min --stride 30sec --by time | each { get value }",
+                description: "Sliding windows of a minute, every 30 seconds, by the time of the rows.",
+                result: Some(Value::test_list(vec![
+                    Value::test_list(vec![Value::test_int(1)]),
+                    Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+                    Value::test_list(vec![Value::test_int(2), Value::test_int(3)]),
+                    Value::test_list(vec![Value::test_int(3)]),
+                ])),
+            },
         ]
     }
 
@@ -93,9 +118,16 @@ impl Command for Window {
             _ => err,
         };
 
-        let size: NonZeroUsize = call.req(engine_state, stack, 0).map_err(fix_call_span)?;
-        let stride: NonZeroUsize = call
-            .get_flag(engine_state, stack, "stride")
+        let size: Value = call.req(engine_state, stack, 0)?;
+        let stride: Option<Value> = call.get_flag(engine_state, stack, "stride")?;
+        if let Some(column) = call.get_flag(engine_state, stack, "by")? {
+            return time_windows(engine_state, input, column, size, stride, head);
+        }
+
+        let size = NonZeroUsize::from_value(size).map_err(fix_call_span)?;
+        let stride = stride
+            .map(NonZeroUsize::from_value)
+            .transpose()
             .map_err(fix_call_span)?
             .unwrap_or(NonZeroUsize::MIN);
         let remainder = call.has_flag(engine_state, stack, "remainder")?;
@@ -235,6 +267,196 @@ impl<I: Iterator<Item = Value>> Iterator for WindowGapIter<I> {
     }
 }
 
+/// Make the windows of `size` by the time in the `column` of the rows, every `stride`, or without
+/// overlap when there's no stride.
+pub fn time_windows(
+    engine_state: &EngineState,
+    input: PipelineData,
+    column: CellPath,
+    size: Value,
+    stride: Option<Value>,
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    let size = window_duration(size, head)?;
+    let stride = match stride {
+        Some(stride) => window_duration(stride, head)?,
+        None => size,
+    };
+
+    match input {
+        PipelineData::Value(Value::List { vals, .. }, metadata) => {
+            let windows = TimeWindowIter::new(vals, column, size, stride, head);
+            let stream = ListStream::new(windows, head, engine_state.signals().clone());
+            Ok(PipelineData::list_stream(stream, metadata))
+        }
+        PipelineData::ListStream(stream, metadata) => {
+            let stream =
+                stream.modify(|iter| TimeWindowIter::new(iter, column, size, stride, head));
+            Ok(PipelineData::list_stream(stream, metadata))
+        }
+        input => Err(input.unsupported_input_error("list", head)),
+    }
+}
+
+/// The nanoseconds of the duration of time windows, which must be positive.
+fn window_duration(value: Value, call_span: Span) -> Result<i64, ShellError> {
+    match value {
+        Value::Duration { val, .. } if val > 0 => Ok(val),
+        Value::Duration { .. } => Err(ShellError::IncorrectValue {
+            msg: "time windows need a positive duration".into(),
+            val_span: value.span(),
+            call_span,
+        }),
+        value => Err(ShellError::RuntimeTypeMismatch {
+            expected: Type::Duration,
+            actual: value.get_type(),
+            span: value.span(),
+        }),
+    }
+}
+
+struct TimeWindowIter<I: Iterator<Item = Value>> {
+    iter: Fuse<I>,
+    column: CellPath,
+    size: i64,
+    stride: i64,
+    // the rows that may still be in a window, with their time in nanoseconds
+    rows: VecDeque<(i64, Value)>,
+    // the start of the next window, once there's been one
+    start: Option<i64>,
+    last_time: Option<i64>,
+    done: bool,
+    span: Span,
+}
+
+impl<I: Iterator<Item = Value>> TimeWindowIter<I> {
+    fn new(
+        iter: impl IntoIterator<IntoIter = I>,
+        column: CellPath,
+        size: i64,
+        stride: i64,
+        span: Span,
+    ) -> Self {
+        Self {
+            iter: iter.into_iter().fuse(),
+            column,
+            size,
+            stride,
+            rows: VecDeque::new(),
+            start: None,
+            last_time: None,
+            done: false,
+            span,
+        }
+    }
+
+    /// The start of the first window that ends after `time`.
+    fn first_start(&self, time: i64) -> i64 {
+        time.saturating_sub(self.size)
+            .div_euclid(self.stride)
+            .saturating_add(1)
+            .saturating_mul(self.stride)
+    }
+
+    /// Read rows until there's one, or one from `until` on.
+    fn fill(&mut self, until: Option<i64>) -> Result<(), ShellError> {
+        while self
+            .rows
+            .back()
+            .is_none_or(|(time, _)| until.is_some_and(|until| *time < until))
+        {
+            let Some(row) = self.iter.next() else {
+                return Ok(());
+            };
+            if let Value::Error { error, .. } = row {
+                return Err(*error);
+            }
+            let time = match row.follow_cell_path(&self.column.members)?.as_ref() {
+                Value::Date { val, .. } => val.timestamp_nanos_opt().ok_or_else(|| {
+                    ShellError::Generic(GenericError::new(
+                        "Time out of range",
+                        "time windows only support times between the years 1677 and 2262",
+                        row.span(),
+                    ))
+                })?,
+                value => {
+                    return Err(ShellError::RuntimeTypeMismatch {
+                        expected: Type::Date,
+                        actual: value.get_type(),
+                        span: value.span(),
+                    });
+                }
+            };
+            if self.last_time.is_some_and(|last| time < last) {
+                return Err(ShellError::Generic(
+                    GenericError::new(
+                        "Rows out of order",
+                        "this row is earlier than the one before it",
+                        row.span(),
+                    )
+                    .with_help("sort the rows by their time first"),
+                ));
+            }
+            self.last_time = Some(time);
+            self.rows.push_back((time, row));
+        }
+        Ok(())
+    }
+
+    fn next_window(&mut self) -> Result<Option<Value>, ShellError> {
+        loop {
+            self.fill(None)?;
+            let Some((time, _)) = self.rows.front() else {
+                return Ok(None);
+            };
+            let start = self.first_start(*time).max(self.start.unwrap_or(i64::MIN));
+            let end = start.saturating_add(self.size);
+            // the rows before the window aren't in any window, when there's a gap between them
+            while self.rows.front().is_some_and(|(time, _)| *time < start) {
+                self.rows.pop_front();
+            }
+            self.fill(Some(end))?;
+
+            // a window cut short by the latest time has the rows at that time too
+            let window: Vec<Value> = self
+                .rows
+                .iter()
+                .take_while(|(time, _)| *time < end || end == i64::MAX)
+                .map(|(_, row)| row.clone())
+                .collect();
+            let Some(next) = start.checked_add(self.stride) else {
+                // no window starts after this one, so it's the last
+                self.done = true;
+                return Ok((!window.is_empty()).then(|| Value::list(window, self.span)));
+            };
+            self.start = Some(next);
+            while self.rows.front().is_some_and(|(time, _)| *time < next) {
+                self.rows.pop_front();
+            }
+            if !window.is_empty() {
+                return Ok(Some(Value::list(window, self.span)));
+            }
+        }
+    }
+}
+
+impl<I: Iterator<Item = Value>> Iterator for TimeWindowIter<I> {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_window() {
+            Ok(window) => window,
+            Err(err) => {
+                self.done = true;
+                Some(Value::error(err, self.span))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,283 @@
+use apache_avro::{Reader, types::Value as AvroValue};
+use chrono::{DateTime, NaiveDate, Utc};
+use nu_engine::command_prelude::*;
+use nu_protocol::{Signals, shell_error::generic::GenericError};
+use std::io::{Cursor, Read};
+
+use super::try_json_str_to_value;
+
+#[derive(Clone)]
+pub struct FromAvro;
+
+impl Command for FromAvro {
+    fn name(&self) -> &str {
+        "from avro"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from avro")
+            .input_output_types(vec![(Type::Binary, Type::Any)])
+            .switch(
+                "schema",
+                "Output the schema embedded in the file instead of its records.",
+                Some('s'),
+            )
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Convert an Avro object container file into a table."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The records are decoded with the schema embedded in the file, and read block by block \
+as the table is consumed. Unions become their value, enums their symbol, maps and records become \
+records, and fixed and bytes become binary. Timestamps and dates become datetimes in UTC, times \
+become durations since midnight, and uuids and big decimals become strings. Decimals stored as \
+bytes or fixed can't be read, as their scale is only in the schema."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["apache", "kafka", "schema", "binary"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Read the records of an Avro file.",
+                example: "open --raw events.avro | from avro",
+                result: None,
+            },
+            Example {
+                description: "Show the schema of an Avro file.",
+                example: "open --raw events.avro | from avro --schema",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let schema = call.has_flag(engine_state, stack, "schema")?;
+        let signals = engine_state.signals().clone();
+        let metadata = input.take_metadata().map(|md| md.with_content_type(None));
+
+        let out = match input {
+            PipelineData::Value(Value::Binary { val, .. }, _) => {
+                read_avro(Cursor::new(val), schema, head, signals)
+            }
+            // Read the blocks from the stream as they're needed, without collecting it
+            PipelineData::ByteStream(stream, ..) => {
+                let span = stream.span();
+                match stream.reader() {
+                    Some(reader) => read_avro(reader, schema, head, signals),
+                    None => Err(ShellError::PipelineMismatch {
+                        exp_input_type: "binary or byte stream".into(),
+                        dst_span: head,
+                        src_span: span,
+                    }),
+                }
+            }
+            input => Err(ShellError::PipelineMismatch {
+                exp_input_type: "binary or byte stream".into(),
+                dst_span: head,
+                src_span: input.span().unwrap_or(head),
+            }),
+        };
+        out.map(|pd| pd.set_metadata(metadata))
+    }
+}
+
+fn read_avro(
+    input: impl Read + Send + 'static,
+    schema: bool,
+    span: Span,
+    signals: Signals,
+) -> Result<PipelineData, ShellError> {
+    let avro_error = |err: apache_avro::Error| {
+        ShellError::Generic(GenericError::new(
+            "Could not read Avro data",
+            err.to_string(),
+            span,
+        ))
+    };
+
+    // The header with the schema is read right away, and the blocks when they're iterated
+    let reader = Reader::new(input).map_err(avro_error)?;
+    if schema {
+        let json = reader.writer_schema().canonical_form();
+        return Ok(try_json_str_to_value(&json, span, true, &signals)?.into_pipeline_data());
+    }
+
+    Ok(reader
+        .map(move |value| match value {
+            Ok(value) => convert_avro_value(value, span),
+            Err(err) => Value::error(avro_error(err), span),
+        })
+        .into_pipeline_data(span, signals))
+}
+
+fn convert_avro_value(value: AvroValue, span: Span) -> Value {
+    let date = |date: Option<DateTime<Utc>>, raw: i64| match date {
+        Some(date) => Value::date(date.fixed_offset(), span),
+        None => Value::error(
+            ShellError::Generic(GenericError::new(
+                "Could not read Avro data",
+                format!("{raw} is out of the range of datetimes"),
+                span,
+            )),
+            span,
+        ),
+    };
+
+    match value {
+        AvroValue::Null => Value::nothing(span),
+        AvroValue::Boolean(bool) => Value::bool(bool, span),
+        AvroValue::Int(int) => Value::int(int.into(), span),
+        AvroValue::Long(long) => Value::int(long, span),
+        AvroValue::Float(float) => Value::float(float.into(), span),
+        AvroValue::Double(double) => Value::float(double, span),
+        AvroValue::Bytes(bytes) | AvroValue::Fixed(_, bytes) => Value::binary(bytes, span),
+        AvroValue::String(string) | AvroValue::Enum(_, string) => Value::string(string, span),
+        AvroValue::Union(_, value) => convert_avro_value(*value, span),
+        AvroValue::Array(values) => Value::list(
+            values
+                .into_iter()
+                .map(|value| convert_avro_value(value, span))
+                .collect(),
+            span,
+        ),
+        AvroValue::Map(map) => {
+            // Maps have no order, so sort them to get the same record every time
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::record(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, convert_avro_value(value, span)))
+                    .collect(),
+                span,
+            )
+        }
+        AvroValue::Record(fields) => Value::record(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, convert_avro_value(value, span)))
+                .collect(),
+            span,
+        ),
+        AvroValue::Date(days) => date(
+            UNIX_EPOCH_DAYS_FROM_CE
+                .checked_add(days)
+                .and_then(NaiveDate::from_num_days_from_ce_opt)
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date| date.and_utc()),
+            days.into(),
+        ),
+        AvroValue::TimeMillis(millis) => Value::duration(i64::from(millis) * 1_000_000, span),
+        AvroValue::TimeMicros(micros) => Value::duration(micros.saturating_mul(1_000), span),
+        AvroValue::TimestampMillis(millis) | AvroValue::LocalTimestampMillis(millis) => {
+            date(DateTime::from_timestamp_millis(millis), millis)
+        }
+        AvroValue::TimestampMicros(micros) | AvroValue::LocalTimestampMicros(micros) => {
+            date(DateTime::from_timestamp_micros(micros), micros)
+        }
+        AvroValue::TimestampNanos(nanos) | AvroValue::LocalTimestampNanos(nanos) => {
+            Value::date(DateTime::from_timestamp_nanos(nanos).fixed_offset(), span)
+        }
+        AvroValue::Uuid(uuid) => Value::string(uuid.to_string(), span),
+        AvroValue::BigDecimal(decimal) => Value::string(decimal.to_string(), span),
+        AvroValue::Duration(duration) => Value::record(
+            record! {
+                "months" => Value::int(u32::from(duration.months()).into(), span),
+                "days" => Value::int(u32::from(duration.days()).into(), span),
+                "millis" => Value::int(u32::from(duration.millis()).into(), span),
+            },
+            span,
+        ),
+        // The scale of these decimals is only in the schema, so their value is unknown
+        AvroValue::Decimal(_) => Value::error(
+            ShellError::Generic(
+                GenericError::new(
+                    "Could not read Avro data",
+                    "decimals stored as bytes or fixed aren't supported",
+                    span,
+                )
+                .with_help("store the decimal as a `big-decimal` to read it"),
+            ),
+            span,
+        ),
+    }
+}
+
+/// The number of days from the first day of the common era to 1970-01-01.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use apache_avro::{Schema, Writer, types::Record};
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(FromAvro)
+    }
+
+    #[test]
+    fn reads_container_files() -> Result<(), apache_avro::Error> {
+        let schema = Schema::parse_str(
+            r#"{"type": "record", "name": "event", "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "kind", "type": {"type": "enum", "name": "kind", "symbols": ["click", "view"]}},
+                {"name": "user", "type": ["null", "string"]}
+            ]}"#,
+        )?;
+        let mut writer = Writer::new(&schema, Vec::new());
+        let mut record = Record::new(writer.schema()).expect("schema is a record");
+        record.put("id", 1i64);
+        record.put("kind", AvroValue::Enum(0, "click".into()));
+        record.put("user", AvroValue::Union(1, Box::new("ada".into())));
+        writer.append(record)?;
+        let bytes = writer.into_inner()?;
+
+        let values = read_avro(
+            Cursor::new(bytes),
+            false,
+            Span::test_data(),
+            Signals::empty(),
+        )
+        .and_then(|data| data.into_value(Span::test_data()))
+        .expect("valid avro");
+        assert_eq!(
+            values,
+            Value::test_list(vec![Value::test_record(record! {
+                "id" => Value::test_int(1),
+                "kind" => Value::test_string("click"),
+                "user" => Value::test_string("ada"),
+            })])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn converts_dates() {
+        let value = convert_avro_value(AvroValue::Date(1), Span::test_data());
+        assert_eq!(
+            value.as_date().map(|date| date.timestamp()),
+            Ok(24 * 60 * 60)
+        );
+    }
+
+    #[test]
+    fn decimals_without_scale_are_errors() {
+        let decimal = apache_avro::Decimal::from(vec![1u8, 0]);
+        let value = convert_avro_value(AvroValue::Decimal(decimal), Span::test_data());
+        assert!(matches!(value, Value::Error { .. }));
+    }
+}
@@ -0,0 +1,561 @@
+use std::io::{self, Cursor, ErrorKind, Read};
+
+use byteorder::{BigEndian, ReadBytesExt};
+use chrono::{DateTime, Utc};
+use nu_engine::command_prelude::*;
+use nu_protocol::{Signals, shell_error::generic::GenericError};
+
+/// Max recursion depth
+const MAX_DEPTH: usize = 50;
+
+#[derive(Clone)]
+pub struct FromCbor;
+
+impl Command for FromCbor {
+    fn name(&self) -> &str {
+        "from cbor"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_type(Type::Binary, Type::Any)
+            .switch(
+                "objects",
+                "Read a sequence of multiple values from input.",
+                None,
+            )
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Convert CBOR data into Nu values."
+    }
+
+    fn extra_description(&self) -> &str {
+        "
+Maps become records: their text keys are used as is and their integer keys, like the
+ones of COSE, become strings. Undefined becomes null.
+
+Dates, tagged with 0 or 1, become datetimes and big numbers, tagged with 2 or 3, become
+ints when they fit and binary otherwise. The self-described CBOR tag is skipped, and the
+other tagged values become records with the `tag` and the `value`.
+
+With --objects, a CBOR sequence is read one value at a time, as the values are used.
+
+CBOR: https://cbor.io/
+"
+        .trim()
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Read a list of values from CBOR.",
+                example: "0x[83 63 666F6F 18 2A F4] | from cbor",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("foo"),
+                    Value::test_int(42),
+                    Value::test_bool(false),
+                ])),
+            },
+            Example {
+                description: "Read a map with integer keys, like a COSE key.",
+                example: "0x[A2 01 02 20 01] | from cbor",
+                result: Some(Value::test_record(record! {
+                    "1" => Value::test_int(2),
+                    "-1" => Value::test_int(1),
+                })),
+            },
+            Example {
+                description: "Read a sequence of values, one after the other.",
+                example: "0x[01 C1 1A 5CD5ADE0] | from cbor --objects",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(1),
+                    Value::test_date(
+                        DateTime::from_timestamp(1_557_507_552, 0)
+                            .unwrap_or_default()
+                            .fixed_offset(),
+                    ),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let objects = call.has_flag(engine_state, stack, "objects")?;
+        let signals = engine_state.signals().clone();
+        let metadata = input.take_metadata().map(|md| md.with_content_type(None));
+        let out = match input {
+            // Deserialize from a byte buffer
+            PipelineData::Value(Value::Binary { val: bytes, .. }, _) => {
+                read_cbor(Cursor::new(bytes), objects, span, signals)
+            }
+            // Deserialize from a raw stream directly without having to collect it
+            PipelineData::ByteStream(stream, ..) => {
+                let stream_span = stream.span();
+                if let Some(reader) = stream.reader() {
+                    read_cbor(reader, objects, span, signals)
+                } else {
+                    Err(ShellError::PipelineMismatch {
+                        exp_input_type: "binary or byte stream".into(),
+                        dst_span: span,
+                        src_span: stream_span,
+                    })
+                }
+            }
+            input => Err(ShellError::PipelineMismatch {
+                exp_input_type: "binary or byte stream".into(),
+                dst_span: span,
+                src_span: input.span().unwrap_or(span),
+            }),
+        };
+        out.map(|pd| pd.set_metadata(metadata))
+    }
+}
+
+/// Read a single value, or a sequence of values, into PipelineData.
+fn read_cbor(
+    mut input: impl Read + Send + 'static,
+    objects: bool,
+    span: Span,
+    signals: Signals,
+) -> Result<PipelineData, ShellError> {
+    if objects {
+        let mut done = false;
+        Ok(std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let initial = match input.read_u8() {
+                Ok(initial) => initial,
+                // The sequence ends between two values
+                Err(err) if err.kind() == ErrorKind::UnexpectedEof => {
+                    done = true;
+                    return None;
+                }
+                Err(err) => {
+                    done = true;
+                    return Some(Value::error(io_error(err, span), span));
+                }
+            };
+            match read_item(&mut input, initial, span, 0).and_then(|item| item.value(span)) {
+                Ok(value) => Some(value),
+                // Any error should cause us to not read anymore
+                Err(err) => {
+                    done = true;
+                    Some(Value::error(err, span))
+                }
+            }
+        })
+        .into_pipeline_data(span, signals))
+    } else {
+        let value = read_value(&mut input, span, 0)?;
+        // Make sure this is the end of the data, which can detect corrupted data
+        let mut buf = [0u8];
+        match input.read_exact(&mut buf) {
+            Err(_) => Ok(value.into_pipeline_data()),
+            Ok(()) => Err(ShellError::Generic(
+                GenericError::new(
+                    "Additional data after end of CBOR value",
+                    "there was more data available after parsing",
+                    span,
+                )
+                .with_help("use `from cbor --objects` to read a sequence of values"),
+            )),
+        }
+    }
+}
+
+/// A data item, or the break that ends an item of indefinite length.
+enum Item {
+    Value(Value),
+    Break,
+}
+
+impl Item {
+    fn value(self, span: Span) -> Result<Value, ShellError> {
+        match self {
+            Item::Value(value) => Ok(value),
+            Item::Break => Err(invalid(
+                "unexpected break outside of an indefinite length item",
+                span,
+            )),
+        }
+    }
+}
+
+fn read_value(input: &mut impl Read, span: Span, depth: usize) -> Result<Value, ShellError> {
+    let initial = input.read_u8().map_err(|err| io_error(err, span))?;
+    read_item(input, initial, span, depth)?.value(span)
+}
+
+fn read_item(
+    input: &mut impl Read,
+    initial: u8,
+    span: Span,
+    depth: usize,
+) -> Result<Item, ShellError> {
+    // Prevent stack overflow
+    if depth >= MAX_DEPTH {
+        return Err(ShellError::Generic(GenericError::new(
+            "CBOR data is nested too deeply",
+            format!("exceeded depth limit ({MAX_DEPTH})"),
+            span,
+        )));
+    }
+
+    let major = initial >> 5;
+    let info = initial & 0x1f;
+    if major == 7 {
+        return read_simple(input, info, span);
+    }
+    let argument = read_argument(input, info, span)?;
+
+    let value = match (major, argument) {
+        (0, Some(n)) => Value::int(to_int(n, span)?, span),
+        (1, Some(n)) => Value::int(-1 - to_int(n, span)?, span),
+        (2, _) => Value::binary(read_bytes(input, major, argument, span)?, span),
+        (3, _) => {
+            let bytes = read_bytes(input, major, argument, span)?;
+            let string = String::from_utf8(bytes).map_err(|err| ShellError::NonUtf8Custom {
+                msg: format!("in CBOR data: {err}"),
+                span,
+            })?;
+            Value::string(string, span)
+        }
+        (4, _) => {
+            let mut vals = vec![];
+            read_items(input, argument, span, depth, |value| {
+                vals.push(value);
+                Ok(())
+            })?;
+            Value::list(vals, span)
+        }
+        (5, _) => {
+            let mut record = Record::new();
+            let mut key = None;
+            read_items(
+                input,
+                argument.map(|len| len.saturating_mul(2)),
+                span,
+                depth,
+                |value| {
+                    match key.take() {
+                        None => key = Some(map_key(value, span)?),
+                        Some(key) => {
+                            record.insert(key, value);
+                        }
+                    }
+                    Ok(())
+                },
+            )?;
+            if key.is_some() {
+                return Err(invalid("map with a key but no value", span));
+            }
+            Value::record(record, span)
+        }
+        (6, Some(tag)) => read_tagged(input, tag, span, depth)?,
+        _ => return Err(invalid("indefinite length for an integer or a tag", span)),
+    };
+    Ok(Item::Value(value))
+}
+
+/// Read the argument that follows the initial byte, or `None` for an indefinite length.
+fn read_argument(input: &mut impl Read, info: u8, span: Span) -> Result<Option<u64>, ShellError> {
+    let argument = match info {
+        0..=23 => info.into(),
+        24 => input.read_u8().map(u64::from),
+        25 => input.read_u16::<BigEndian>().map(u64::from),
+        26 => input.read_u32::<BigEndian>().map(u64::from),
+        27 => input.read_u64::<BigEndian>(),
+        31 => return Ok(None),
+        _ => {
+            return Err(invalid(
+                &format!("reserved additional information {info}"),
+                span,
+            ));
+        }
+    }
+    .map_err(|err| io_error(err, span))?;
+    Ok(Some(argument))
+}
+
+/// Read the items of an array or a map, until their count or the break.
+fn read_items(
+    input: &mut impl Read,
+    count: Option<u64>,
+    span: Span,
+    depth: usize,
+    mut push: impl FnMut(Value) -> Result<(), ShellError>,
+) -> Result<(), ShellError> {
+    match count {
+        Some(count) => {
+            for _ in 0..count {
+                push(read_value(input, span, depth + 1)?)?;
+            }
+        }
+        None => loop {
+            let initial = input.read_u8().map_err(|err| io_error(err, span))?;
+            match read_item(input, initial, span, depth + 1)? {
+                Item::Value(value) => push(value)?,
+                Item::Break => break,
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Read the contents of a byte or text string, which can be split into chunks.
+fn read_bytes(
+    input: &mut impl Read,
+    major: u8,
+    length: Option<u64>,
+    span: Span,
+) -> Result<Vec<u8>, ShellError> {
+    match length {
+        Some(length) => {
+            let mut bytes = vec![];
+            input
+                .take(length)
+                .read_to_end(&mut bytes)
+                .map_err(|err| io_error(err, span))?;
+            if (bytes.len() as u64) < length {
+                return Err(io_error(ErrorKind::UnexpectedEof.into(), span));
+            }
+            Ok(bytes)
+        }
+        None => {
+            let mut bytes = vec![];
+            loop {
+                let initial = input.read_u8().map_err(|err| io_error(err, span))?;
+                if initial == 0xff {
+                    return Ok(bytes);
+                }
+                // The chunks must be strings of the same type, of definite length
+                if initial >> 5 != major || initial & 0x1f == 31 {
+                    return Err(invalid(
+                        "invalid chunk in an indefinite length string",
+                        span,
+                    ));
+                }
+                let length = read_argument(input, initial & 0x1f, span)?;
+                bytes.extend(read_bytes(input, major, length, span)?);
+            }
+        }
+    }
+}
+
+fn read_simple(input: &mut impl Read, info: u8, span: Span) -> Result<Item, ShellError> {
+    let value = match info {
+        20 => Value::bool(false, span),
+        21 => Value::bool(true, span),
+        22 | 23 => Value::nothing(span),
+        25 => {
+            let half = input
+                .read_u16::<BigEndian>()
+                .map_err(|err| io_error(err, span))?;
+            Value::float(half_to_f64(half), span)
+        }
+        26 => Value::float(
+            input
+                .read_f32::<BigEndian>()
+                .map_err(|err| io_error(err, span))?
+                .into(),
+            span,
+        ),
+        27 => Value::float(
+            input
+                .read_f64::<BigEndian>()
+                .map_err(|err| io_error(err, span))?,
+            span,
+        ),
+        31 => return Ok(Item::Break),
+        _ => {
+            return Err(ShellError::Generic(GenericError::new(
+                "Unknown CBOR simple value",
+                format!("encountered simple value {info}"),
+                span,
+            )));
+        }
+    };
+    Ok(Item::Value(value))
+}
+
+fn read_tagged(
+    input: &mut impl Read,
+    tag: u64,
+    span: Span,
+    depth: usize,
+) -> Result<Value, ShellError> {
+    let value = read_value(input, span, depth + 1)?;
+    let invalid_tag = || invalid(&format!("invalid content for tag {tag}"), span);
+
+    Ok(match (tag, value) {
+        // Date and time string
+        (0, Value::String { val, .. }) => {
+            let date = DateTime::parse_from_rfc3339(&val).map_err(|_| invalid_tag())?;
+            Value::date(date, span)
+        }
+        // Seconds since the epoch
+        (1, Value::Int { val, .. }) => make_date(val, 0, span)?,
+        (1, Value::Float { val, .. }) if val.is_finite() => {
+            let seconds = val.floor();
+            let nanos = ((val - seconds) * 1e9) as u32;
+            make_date(seconds as i64, nanos, span)?
+        }
+        // Big numbers, which can't always be ints
+        (2 | 3, Value::Binary { val, .. }) => {
+            let number = val
+                .iter()
+                .skip_while(|&&byte| byte == 0)
+                .try_fold(0i64, |number, &byte| {
+                    number.checked_mul(256)?.checked_add(byte.into())
+                });
+            match number {
+                Some(number) if tag == 2 => Value::int(number, span),
+                Some(number) => Value::int(-1 - number, span),
+                None => Value::binary(val, span),
+            }
+        }
+        (0..=3, _) => return Err(invalid_tag()),
+        // Self-described CBOR
+        (55799, value) => value,
+        (tag, value) => Value::record(
+            record! {
+                "tag" => Value::int(to_int(tag, span)?, span),
+                "value" => value,
+            },
+            span,
+        ),
+    })
+}
+
+fn map_key(key: Value, span: Span) -> Result<String, ShellError> {
+    match key {
+        Value::String { val, .. } => Ok(val),
+        Value::Int { val, .. } => Ok(val.to_string()),
+        other => Err(ShellError::Generic(
+            GenericError::new(
+                "Invalid key in CBOR map",
+                format!("{} keys are not supported", other.get_type()),
+                span,
+            )
+            .with_help("only maps with text or integer keys are supported"),
+        )),
+    }
+}
+
+fn make_date(seconds: i64, nanos: u32, span: Span) -> Result<Value, ShellError> {
+    DateTime::<Utc>::from_timestamp(seconds, nanos)
+        .map(|date| Value::date(date.fixed_offset(), span))
+        .ok_or_else(|| {
+            ShellError::Generic(GenericError::new(
+                "Invalid CBOR date",
+                "datetime is out of supported range",
+                span,
+            ))
+        })
+}
+
+/// Convert a half precision float, which Rust has no stable type for.
+fn half_to_f64(half: u16) -> f64 {
+    let sign = if half & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((half >> 10) & 0x1f);
+    let mantissa = f64::from(half & 0x3ff);
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f64.powi(exponent - 15),
+    }
+}
+
+fn to_int(number: u64, span: Span) -> Result<i64, ShellError> {
+    number.try_into().map_err(|_| {
+        ShellError::Generic(GenericError::new(
+            "CBOR integer too big for Nushell",
+            format!("{number} is larger than the largest int"),
+            span,
+        ))
+    })
+}
+
+fn invalid(msg: &str, span: Span) -> ShellError {
+    ShellError::Generic(GenericError::new("Invalid CBOR data", msg, span))
+}
+
+fn io_error(err: io::Error, span: Span) -> ShellError {
+    ShellError::Generic(GenericError::new(
+        "Error while reading CBOR data",
+        err.to_string(),
+        span,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(FromCbor)
+    }
+
+    fn read(bytes: &[u8]) -> Result<Value, ShellError> {
+        read_value(&mut Cursor::new(bytes), Span::test_data(), 0)
+    }
+
+    #[test]
+    fn reads_indefinite_lengths() {
+        // ["a", "bc"] as an indefinite array, with "bc" in two chunks
+        assert_eq!(
+            read(&[0x9f, 0x61, b'a', 0x7f, 0x61, b'b', 0x61, b'c', 0xff, 0xff]),
+            Ok(Value::test_list(vec![
+                Value::test_string("a"),
+                Value::test_string("bc"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn reads_half_floats() {
+        assert_eq!(read(&[0xf9, 0x3e, 0x00]), Ok(Value::test_float(1.5)));
+        assert_eq!(
+            read(&[0xf9, 0x7c, 0x00]),
+            Ok(Value::test_float(f64::INFINITY))
+        );
+    }
+
+    #[test]
+    fn reads_big_numbers() {
+        assert_eq!(read(&[0xc2, 0x42, 0x01, 0x00]), Ok(Value::test_int(256)));
+        assert_eq!(
+            read(&[0xc2, 0x49, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Ok(Value::test_binary(vec![1, 0, 0, 0, 0, 0, 0, 0, 0]))
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_tags() {
+        assert_eq!(
+            read(&[0xd8, 0x20, 0x61, b'x']),
+            Ok(Value::test_record(record! {
+                "tag" => Value::test_int(32),
+                "value" => Value::test_string("x"),
+            }))
+        );
+    }
+
+    #[test]
+    fn rejects_stray_breaks() {
+        assert!(read(&[0xff]).is_err());
+        assert!(read(&[0x82, 0x01]).is_err());
+    }
+}
@@ -0,0 +1,348 @@
+use std::{iter::Peekable, str::Chars};
+
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+
+#[derive(Clone)]
+pub struct FromDotenv;
+
+impl Command for FromDotenv {
+    fn name(&self) -> &str {
+        "from dotenv"
+    }
+
+    fn description(&self) -> &str {
+        "Parse text as .env and create a record."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Every `NAME=value` line becomes a field of the record, and `export` before the name is \
+ignored. Lines starting with `#` are comments, like the text after ` #` in unquoted values. Values \
+in single quotes are taken literally, values in double quotes support `\\n`, `\\r`, `\\t`, `\\\"`, \
+`\\\\` and `\\$` escapes, and both can span multiple lines.
+
+With --interpolate, `$NAME` and `${NAME}` in unquoted and double quoted values are replaced with the \
+value of an earlier variable of the file, or else of the environment."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("from dotenv")
+            .input_output_types(vec![(Type::String, Type::record())])
+            .switch(
+                "interpolate",
+                "Expand `$NAME` and `${NAME}` references in the values.",
+                Some('i'),
+            )
+            .category(Category::Formats)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["env", "environment", ".env", "variables"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r##""# Database
+export DB_HOST=localhost
+DB_PORT=5432 # default port
+DB_PASSWORD='pa$$ word'" | from dotenv"##,
+                description: "Converts .env text to a record.",
+                result: Some(Value::test_record(record! {
+                    "DB_HOST" => Value::test_string("localhost"),
+                    "DB_PORT" => Value::test_string("5432"),
+                    "DB_PASSWORD" => Value::test_string("pa$$ word"),
+                })),
+            },
+            Example {
+                example: r#""HOST=example.com
+URL=\"https://${HOST}/api\"" | from dotenv --interpolate"#,
+                description: "Converts .env text, expanding the references to other variables.",
+                result: Some(Value::test_record(record! {
+                    "HOST" => Value::test_string("example.com"),
+                    "URL" => Value::test_string("https://example.com/api"),
+                })),
+            },
+            Example {
+                example: "open .env --raw | from dotenv | load-env",
+                description: "Loads the variables of a .env file into the environment, like `load-env --file .env`.",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let interpolate = call.has_flag(engine_state, stack, "interpolate")?;
+        let (text, span, metadata) = input.collect_string_strict(head)?;
+        let env = |name: &str| {
+            stack
+                .get_env_var(engine_state, name)
+                .and_then(|value| value.coerce_string().ok())
+        };
+        let record = parse_dotenv(&text, interpolate, env, span)?;
+        Ok(Value::record(record, head)
+            .into_pipeline_data_with_metadata(metadata.map(|md| md.with_content_type(None))))
+    }
+}
+
+/// Parse the variables of a .env file. With `interpolate`, references to variables that aren't
+/// defined earlier in the file are looked up with `env`, and are empty when it returns nothing.
+pub(crate) fn parse_dotenv(
+    text: &str,
+    interpolate: bool,
+    env: impl Fn(&str) -> Option<String>,
+    span: Span,
+) -> Result<Record, ShellError> {
+    let mut record = Record::new();
+    let mut lines = text.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let error = |msg: String| {
+            ShellError::Generic(GenericError::new(
+                "Error while parsing .env",
+                format!("{msg} on line {}", index + 1),
+                span,
+            ))
+        };
+
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line
+            .strip_prefix("export ")
+            .map(str::trim_start)
+            .unwrap_or(line);
+        let Some((name, value)) = line.split_once('=') else {
+            return Err(error(format!("expected `=` after `{}`", line.trim_end())));
+        };
+        let name = name.trim_end();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+        {
+            return Err(error(format!("invalid variable name `{name}`")));
+        }
+
+        let lookup = |reference: &str| {
+            record
+                .get(reference)
+                .and_then(|value| value.as_str().ok())
+                .map(str::to_string)
+                .or_else(|| env(reference))
+                .unwrap_or_default()
+        };
+        let raw_value = value;
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('\'' | '"')) => {
+                // Quoted values go on until their closing quote, even over several lines
+                let mut raw = value[1..].to_string();
+                let end = loop {
+                    if let Some(end) = closing_quote(&raw, quote) {
+                        break end;
+                    }
+                    let Some((_, next)) = lines.next() else {
+                        return Err(error(format!(
+                            "unterminated quote in the value of `{name}`"
+                        )));
+                    };
+                    raw.push('\n');
+                    raw.push_str(next);
+                };
+                let rest = raw[end + 1..].trim_start();
+                if !rest.is_empty() && !rest.starts_with('#') {
+                    return Err(error(format!(
+                        "unexpected `{rest}` after the value of `{name}`"
+                    )));
+                }
+                let content = &raw[..end];
+                if quote == '"' {
+                    unescape(content, interpolate, lookup)
+                } else {
+                    content.to_string()
+                }
+            }
+            _ => {
+                let end = raw_value
+                    .char_indices()
+                    .find(|&(i, c)| {
+                        c == '#' && raw_value[..i].ends_with(|c: char| c.is_ascii_whitespace())
+                    })
+                    .map_or(raw_value.len(), |(i, _)| i);
+                let value = raw_value[..end].trim();
+                if interpolate {
+                    expand(value, lookup)
+                } else {
+                    value.to_string()
+                }
+            }
+        };
+
+        record.insert(name, Value::string(value, span));
+    }
+
+    Ok(record)
+}
+
+/// The position of the quote that closes a value, skipping the escaped ones in double quotes.
+fn closing_quote(raw: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in raw.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quote == '"' => escaped = true,
+            c if c == quote => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Replace the escapes of a double quoted value, and its references when interpolating.
+fn unescape(content: &str, interpolate: bool, lookup: impl Fn(&str) -> String) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('t') => out.push('\t'),
+                Some(escaped @ ('"' | '\\' | '$')) => out.push(escaped),
+                Some(other) => {
+                    out.push('\\');
+                    out.push(other);
+                }
+                None => out.push('\\'),
+            },
+            '$' if interpolate => out.push_str(&reference(&mut chars, &lookup)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Replace the references of an unquoted value.
+fn expand(value: &str, lookup: impl Fn(&str) -> String) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '$' => out.push_str(&reference(&mut chars, &lookup)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// The value of the `$NAME` or `${NAME}` reference after a `$`. Anything else is kept as is.
+fn reference(chars: &mut Peekable<Chars<'_>>, lookup: impl Fn(&str) -> String) -> String {
+    let is_name = |c: &char| c.is_ascii_alphanumeric() || *c == '_';
+    if chars.next_if_eq(&'{').is_some() {
+        let mut name = String::new();
+        for c in chars.by_ref() {
+            if c == '}' {
+                return lookup(&name);
+            }
+            name.push(c);
+        }
+        // Without a closing brace it's not a reference
+        return format!("${{{name}");
+    }
+
+    let mut name = String::new();
+    while let Some(c) = chars.next_if(is_name) {
+        name.push(c);
+    }
+    if name.is_empty() {
+        "$".into()
+    } else {
+        lookup(&name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(text: &str, interpolate: bool) -> Result<Record, ShellError> {
+        parse_dotenv(
+            text,
+            interpolate,
+            |name| (name == "HOME").then(|| "/home/ada".into()),
+            Span::test_data(),
+        )
+    }
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(FromDotenv)
+    }
+
+    #[test]
+    fn parses_quotes_and_comments() {
+        let text = r#"
+            # comment
+            export A = plain value # comment
+            B=no#comment
+            C='single $HOME \n'
+            D="double \"quoted\"\n\$HOME"
+            E="multi
+line" # comment
+            F=
+            G= # comment
+        "#;
+        assert_eq!(
+            parse(text, false).expect("valid .env"),
+            record! {
+                "A" => Value::test_string("plain value"),
+                "B" => Value::test_string("no#comment"),
+                "C" => Value::test_string(r"single $HOME \n"),
+                "D" => Value::test_string("double \"quoted\"\n$HOME"),
+                "E" => Value::test_string("multi\nline"),
+                "F" => Value::test_string(""),
+                "G" => Value::test_string(""),
+            }
+        );
+    }
+
+    #[test]
+    fn interpolates_only_when_asked() {
+        let text = r#"
+            DIR=$HOME/app
+            LOG="${DIR}/log \$DIR"
+            RAW='$DIR'
+            MISSING=${NOPE}-$
+        "#;
+        assert_eq!(
+            parse(text, true).expect("valid .env"),
+            record! {
+                "DIR" => Value::test_string("/home/ada/app"),
+                "LOG" => Value::test_string("/home/ada/app/log $DIR"),
+                "RAW" => Value::test_string("$DIR"),
+                "MISSING" => Value::test_string("-$"),
+            }
+        );
+        assert_eq!(
+            parse("DIR=$HOME/app", false).expect("valid .env"),
+            record! { "DIR" => Value::test_string("$HOME/app") }
+        );
+    }
+
+    #[test]
+    fn reports_invalid_lines() {
+        assert!(parse("JUST_A_NAME", false).is_err());
+        assert!(parse("BAD NAME=1", false).is_err());
+        assert!(parse("A=\"unterminated\nB=2", false).is_err());
+        assert!(parse("A='x' y", false).is_err());
+    }
+}
@@ -0,0 +1,501 @@
+use chrono::DateTime;
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+
+#[derive(Clone)]
+pub struct FromEdn;
+
+impl Command for FromEdn {
+    fn name(&self) -> &str {
+        "from edn"
+    }
+
+    fn description(&self) -> &str {
+        "Convert EDN (extensible data notation) text into structured data."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Maps become records and vectors, lists and sets become lists. Keywords and symbols become \
+strings, without the colon of keywords, and map keys that aren't strings use their EDN text. \
+Characters become strings too. `#inst` literals become datetimes and `#uuid` literals strings, \
+and the other tagged literals become records with the `tag` and the `value`."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from edn")
+            .input_output_types(vec![(Type::String, Type::Any)])
+            .category(Category::Formats)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["clojure", "datomic", "extensible data notation"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"'{:name "nu" :tags #{:shell} :version 1}' | from edn"#,
+                description: "Converts an EDN map to a record.",
+                result: Some(Value::test_record(record! {
+                    "name" => Value::test_string("nu"),
+                    "tags" => Value::test_list(vec![Value::test_string("shell")]),
+                    "version" => Value::test_int(1),
+                })),
+            },
+            Example {
+                example: r#"'[1 2.5 nil true \a]' | from edn"#,
+                description: "Converts an EDN vector to a list.",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(1),
+                    Value::test_float(2.5),
+                    Value::test_nothing(),
+                    Value::test_bool(true),
+                    Value::test_string("a"),
+                ])),
+            },
+            Example {
+                example: r#"'#myapp/Point [1 2]' | from edn"#,
+                description: "Tagged literals become records.",
+                result: Some(Value::test_record(record! {
+                    "tag" => Value::test_string("myapp/Point"),
+                    "value" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+                })),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let (string_input, span, metadata) = input.collect_string_strict(head)?;
+        let metadata = metadata.map(|md| md.with_content_type(None));
+
+        let value = convert_string_to_value(&string_input, span)?;
+        Ok(value.into_pipeline_data_with_metadata(metadata))
+    }
+}
+
+/// Parse a single EDN value.
+pub(crate) fn convert_string_to_value(text: &str, span: Span) -> Result<Value, ShellError> {
+    let mut parser = Parser {
+        text,
+        pos: 0,
+        span,
+        depth: 0,
+    };
+    let value = parser.parse_value().and_then(|value| {
+        parser.skip_whitespace()?;
+        match parser.peek() {
+            None => Ok(value),
+            Some(_) => Err(parser.error("expected a single value")),
+        }
+    });
+
+    value.map_err(|ParseError { msg, pos }| {
+        let before = &text[..pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        ShellError::Generic(GenericError::new(
+            "Could not parse EDN",
+            format!("{msg} at line {line}, column {column}"),
+            span,
+        ))
+    })
+}
+
+/// How deep values can be nested, so that deeply nested input can't overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+struct ParseError {
+    msg: String,
+    pos: usize,
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    span: Span,
+    /// The number of values being parsed, one in the other
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, msg: impl Into<String>) -> ParseError {
+        ParseError {
+            msg: msg.into(),
+            pos: self.pos,
+        }
+    }
+
+    /// Skip whitespace, commas, comments and discarded values like `#_ value`.
+    fn skip_whitespace(&mut self) -> Result<(), ParseError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() || c == ',' => {
+                    self.next();
+                }
+                Some(';') => while self.next().is_some_and(|c| c != '\n') {},
+                Some('#') if self.text[self.pos..].starts_with("#_") => {
+                    self.pos += 2;
+                    self.parse_value()?;
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Read the text of a symbol, a keyword or a number.
+    fn token(&mut self) -> &'a str {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| !c.is_whitespace() && !"\",;()[]{}".contains(c))
+        {
+            self.next();
+        }
+        &self.text[start..self.pos]
+    }
+
+    fn parse_value(&mut self) -> Result<Value, ParseError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error(format!(
+                "values are nested more than {MAX_DEPTH} levels deep"
+            )));
+        }
+        self.depth += 1;
+        let value = self.parse_nested_value();
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_nested_value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace()?;
+        let span = self.span;
+        let Some(c) = self.peek() else {
+            return Err(self.error("unexpected end of input"));
+        };
+
+        match c {
+            '(' | '[' => {
+                self.next();
+                let close = if c == '(' { ')' } else { ']' };
+                Ok(Value::list(self.parse_items(close)?, span))
+            }
+            '{' => {
+                self.next();
+                self.parse_map()
+            }
+            '"' => {
+                self.next();
+                Ok(Value::string(self.parse_string()?, span))
+            }
+            '\\' => {
+                self.next();
+                Ok(Value::string(self.parse_char()?, span))
+            }
+            ':' => {
+                self.next();
+                let name = self.token();
+                if name.is_empty() {
+                    return Err(self.error("expected a keyword name after `:`"));
+                }
+                Ok(Value::string(name, span))
+            }
+            '#' => {
+                self.next();
+                self.parse_dispatch()
+            }
+            ')' | ']' | '}' => Err(self.error(format!("unexpected `{c}`"))),
+            _ => {
+                let start = self.pos;
+                let token = self.token();
+                match token {
+                    "nil" => Ok(Value::nothing(span)),
+                    "true" => Ok(Value::bool(true, span)),
+                    "false" => Ok(Value::bool(false, span)),
+                    _ if is_number(token) => parse_number(token, span).ok_or_else(|| ParseError {
+                        msg: format!("invalid number `{token}`"),
+                        pos: start,
+                    }),
+                    _ => Ok(Value::string(token, span)),
+                }
+            }
+        }
+    }
+
+    /// Parse what follows a `#`: a set, a symbolic value like `##Inf`, or a tagged literal.
+    fn parse_dispatch(&mut self) -> Result<Value, ParseError> {
+        let span = self.span;
+        match self.peek() {
+            Some('{') => {
+                self.next();
+                Ok(Value::list(self.parse_items('}')?, span))
+            }
+            Some('#') => {
+                self.next();
+                match self.token() {
+                    "Inf" => Ok(Value::float(f64::INFINITY, span)),
+                    "-Inf" => Ok(Value::float(f64::NEG_INFINITY, span)),
+                    "NaN" => Ok(Value::float(f64::NAN, span)),
+                    other => Err(self.error(format!("unknown symbolic value `##{other}`"))),
+                }
+            }
+            _ => {
+                let start = self.pos;
+                let tag = self.token().to_string();
+                if tag.is_empty() {
+                    return Err(self.error("expected a tag after `#`"));
+                }
+                let value = self.parse_value()?;
+                match (tag.as_str(), value) {
+                    ("inst", Value::String { val, .. }) => DateTime::parse_from_rfc3339(&val)
+                        .map(|date| Value::date(date, span))
+                        .map_err(|err| ParseError {
+                            msg: format!("invalid #inst `{val}`: {err}"),
+                            pos: start,
+                        }),
+                    ("uuid", value @ Value::String { .. }) => Ok(value),
+                    ("inst" | "uuid", _) => Err(ParseError {
+                        msg: format!("#{tag} must be followed by a string"),
+                        pos: start,
+                    }),
+                    (_, value) => Ok(Value::record(
+                        record! {
+                            "tag" => Value::string(tag, span),
+                            "value" => value,
+                        },
+                        span,
+                    )),
+                }
+            }
+        }
+    }
+
+    fn parse_items(&mut self, close: char) -> Result<Vec<Value>, ParseError> {
+        let mut items = vec![];
+        loop {
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(c) if c == close => {
+                    self.next();
+                    return Ok(items);
+                }
+                None => return Err(self.error(format!("expected `{close}`"))),
+                Some(_) => items.push(self.parse_value()?),
+            }
+        }
+    }
+
+    fn parse_map(&mut self) -> Result<Value, ParseError> {
+        let mut record = Record::new();
+        loop {
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some('}') => {
+                    self.next();
+                    return Ok(Value::record(record, self.span));
+                }
+                None => return Err(self.error("expected `}`")),
+                Some(_) => {
+                    let start = self.pos;
+                    let key = match self.parse_value()? {
+                        // Keywords, symbols and strings
+                        Value::String { val, .. } => val,
+                        _ => self.text[start..self.pos].trim().to_string(),
+                    };
+                    self.skip_whitespace()?;
+                    if self.peek() == Some('}') {
+                        return Err(self.error(format!("missing value for key `{key}`")));
+                    }
+                    let value = self.parse_value()?;
+                    record.insert(key, value);
+                }
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, ParseError> {
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error("unterminated string")),
+                Some('"') => return Ok(string),
+                Some('\\') => {
+                    let c = match self.next() {
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('n') => '\n',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => self.parse_unicode()?,
+                        Some(c @ ('\\' | '"')) => c,
+                        Some(c) => return Err(self.error(format!("invalid escape `\\{c}`"))),
+                        None => return Err(self.error("unterminated string")),
+                    };
+                    string.push(c);
+                }
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    fn parse_unicode(&mut self) -> Result<char, ParseError> {
+        let hex = self.text.get(self.pos..self.pos + 4).unwrap_or_default();
+        let c = u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(format!("invalid unicode escape `\\u{hex}`")))?;
+        self.pos += 4;
+        Ok(c)
+    }
+
+    fn parse_char(&mut self) -> Result<String, ParseError> {
+        // The first character is always part of the literal, even if it's a delimiter
+        let Some(first) = self.next() else {
+            return Err(self.error("expected a character after `\\`"));
+        };
+        let start = self.pos - first.len_utf8();
+        let rest = self.token();
+        if rest.is_empty() {
+            return Ok(first.to_string());
+        }
+        let c = match &self.text[start..self.pos] {
+            "newline" => '\n',
+            "return" => '\r',
+            "space" => ' ',
+            "tab" => '\t',
+            name => match name.strip_prefix('u') {
+                Some(hex) if hex.len() == 4 => u32::from_str_radix(hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error(format!("invalid character `\\{name}`")))?,
+                _ => return Err(self.error(format!("invalid character `\\{name}`"))),
+            },
+        };
+        Ok(c.to_string())
+    }
+}
+
+fn is_number(token: &str) -> bool {
+    let digits = token.strip_prefix(['+', '-']).unwrap_or(token);
+    digits.starts_with(|c: char| c.is_ascii_digit())
+}
+
+fn parse_number(token: &str, span: Span) -> Option<Value> {
+    // `N` marks arbitrary precision integers and `M` exact decimals, which must fit ints and floats
+    if let Some(int) = token.strip_suffix('N') {
+        return int.parse().ok().map(|int| Value::int(int, span));
+    }
+    if let Some(float) = token.strip_suffix('M') {
+        return float.parse().ok().map(|float| Value::float(float, span));
+    }
+    if token.contains(['.', 'e', 'E']) {
+        return token.parse().ok().map(|float| Value::float(float, span));
+    }
+    token.parse().ok().map(|int| Value::int(int, span))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(FromEdn)
+    }
+
+    fn parse(text: &str) -> Result<Value, ShellError> {
+        convert_string_to_value(text, Span::test_data())
+    }
+
+    #[test]
+    fn parses_comments_and_discards() {
+        assert_eq!(
+            parse("; a comment\n[1, #_ 2 3] ; another"),
+            Ok(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(3)
+            ]))
+        );
+    }
+
+    #[test]
+    fn parses_strings_and_characters() {
+        assert_eq!(
+            parse(r#"["a\tbé" \newline é \(]"#),
+            Ok(Value::test_list(vec![
+                Value::test_string("a\tbé"),
+                Value::test_string("\n"),
+                Value::test_string("é"),
+                Value::test_string("("),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parses_special_numbers() {
+        assert_eq!(
+            parse("[-7N 1.5M 2e3 ##Inf]"),
+            Ok(Value::test_list(vec![
+                Value::test_int(-7),
+                Value::test_float(1.5),
+                Value::test_float(2000.0),
+                Value::test_float(f64::INFINITY),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parses_inst() {
+        let value = parse(r#"#inst "2024-01-15T09:00:00Z""#).expect("valid inst");
+        assert_eq!(
+            value.as_date().map(|date| date.timestamp()),
+            Ok(1_705_309_200)
+        );
+    }
+
+    #[test]
+    fn uses_edn_text_of_other_keys() {
+        assert_eq!(
+            parse("{1 :a, [x] :b}"),
+            Ok(Value::test_record(record! {
+                "1" => Value::test_string("a"),
+                "[x]" => Value::test_string("b"),
+            }))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(parse("[1 2").is_err());
+        assert!(parse("{:a}").is_err());
+        assert!(parse("1 2").is_err());
+        assert!(parse("99999999999999999999").is_err());
+        assert!(parse(r#"#inst 5"#).is_err());
+    }
+
+    #[test]
+    fn rejects_deep_nesting() {
+        let nested = format!("{}{}", "[".repeat(10_000), "]".repeat(10_000));
+        assert!(parse(&nested).is_err());
+        assert!(parse(&format!("{}1", "#_ 0 ".repeat(10_000))).is_ok());
+        assert!(parse(&format!("{}1", "#_ ".repeat(10_000))).is_err());
+        let allowed = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        assert!(parse(&allowed).is_ok());
+    }
+}
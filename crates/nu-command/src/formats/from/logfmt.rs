@@ -0,0 +1,266 @@
+use std::io::{BufRead, Cursor};
+
+use nu_engine::command_prelude::*;
+use nu_protocol::{
+    ListStream, Signals,
+    shell_error::{generic::GenericError, io::IoError},
+};
+
+#[derive(Clone)]
+pub struct FromLogfmt;
+
+impl Command for FromLogfmt {
+    fn name(&self) -> &str {
+        "from logfmt"
+    }
+
+    fn description(&self) -> &str {
+        "Convert logfmt lines into a table."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Every line of `key=value` pairs becomes a record, and the lines are read one at a time as \
+the table is consumed. Values can be quoted with `\"`, with `\\` escapes inside the quotes. A key \
+without a value becomes `true`. Unquoted values that look like ints, floats or bools are converted, \
+unless --no-infer is given."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("from logfmt")
+            .input_output_types(vec![(Type::String, Type::table())])
+            .switch("no-infer", "No field type inferencing.", None)
+            .category(Category::Formats)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["log", "key", "value", "heroku", "loki"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""at=info method=GET path=\"/a b\" status=200 cached" | from logfmt"#,
+                description: "Converts a logfmt line to a table.",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "at" => Value::test_string("info"),
+                    "method" => Value::test_string("GET"),
+                    "path" => Value::test_string("/a b"),
+                    "status" => Value::test_int(200),
+                    "cached" => Value::test_bool(true),
+                })])),
+            },
+            Example {
+                example: "'level=warn took=1.5
+level=error code=42' | from logfmt --no-infer",
+                description: "Converts logfmt lines without converting the values.",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "level" => Value::test_string("warn"),
+                        "took" => Value::test_string("1.5"),
+                    }),
+                    Value::test_record(record! {
+                        "level" => Value::test_string("error"),
+                        "code" => Value::test_string("42"),
+                    }),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let infer = !call.has_flag(engine_state, stack, "no-infer")?;
+        let signals = engine_state.signals().clone();
+        let metadata = input.take_metadata().map(|md| md.with_content_type(None));
+
+        match input {
+            PipelineData::Value(Value::String { val, .. }, ..) => Ok(PipelineData::list_stream(
+                read_logfmt_lines(Cursor::new(val), span, infer, signals),
+                metadata,
+            )),
+            // Read the lines as they're needed, so that long logs can be streamed
+            PipelineData::ByteStream(stream, ..) if stream.type_() != ByteStreamType::Binary => {
+                match stream.reader() {
+                    Some(reader) => Ok(PipelineData::list_stream(
+                        read_logfmt_lines(reader, span, infer, signals),
+                        metadata,
+                    )),
+                    None => Ok(PipelineData::empty()),
+                }
+            }
+            _ => Err(ShellError::OnlySupportsThisInputType {
+                exp_input_type: "string".into(),
+                wrong_type: input.get_type().to_string(),
+                dst_span: call.head,
+                src_span: input.span().unwrap_or(call.head),
+            }),
+        }
+    }
+}
+
+/// Create a stream of records from a reader that produces logfmt lines
+fn read_logfmt_lines(
+    input: impl BufRead + Send + 'static,
+    span: Span,
+    infer: bool,
+    signals: Signals,
+) -> ListStream {
+    let iter = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            line.as_ref().is_ok_and(|line| !line.trim().is_empty()) || line.is_err()
+        })
+        .map(move |(index, line)| -> Result<Value, ShellError> {
+            let line = line.map_err(|err| IoError::new(err, span, None))?;
+            let record = parse_line(&line, infer, span).map_err(|msg| {
+                GenericError::new(
+                    "Error while parsing logfmt",
+                    format!("{msg} on line {}", index + 1),
+                    span,
+                )
+            })?;
+            Ok(Value::record(record, span))
+        })
+        .map(move |result| result.unwrap_or_else(|err| Value::error(err, span)));
+
+    ListStream::new(iter, span, signals)
+}
+
+/// Parse the `key=value` pairs of a single line.
+fn parse_line(line: &str, infer: bool, span: Span) -> Result<Record, String> {
+    let mut record = Record::new();
+    let mut rest = line.trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = &rest[..key_end];
+        if key.is_empty() {
+            return Err("expected a key before `=`".into());
+        }
+        if key.contains('"') {
+            return Err(format!("unexpected quote in the key `{key}`"));
+        }
+        rest = &rest[key_end..];
+
+        let value = match rest.strip_prefix('=') {
+            // A key without a value is a flag
+            None => Value::bool(true, span),
+            Some(after) => match after.strip_prefix('"') {
+                Some(quoted) => {
+                    let (text, after) = parse_quoted(quoted)
+                        .ok_or_else(|| format!("unterminated quote in the value of `{key}`"))?;
+                    rest = after;
+                    Value::string(text, span)
+                }
+                None => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    rest = &after[end..];
+                    infer_value(&after[..end], infer, span)
+                }
+            },
+        };
+
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return Err(format!("expected a space after the value of `{key}`"));
+        }
+        record.insert(key, value);
+        rest = rest.trim_start();
+    }
+
+    Ok(record)
+}
+
+/// Read a quoted value up to its closing quote, returning the text after it.
+fn parse_quoted(input: &str) -> Option<(String, &str)> {
+    let mut out = String::new();
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, &input[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                    match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        Some(c) => out.push(c),
+                        None => {
+                            out.push_str("\\u");
+                            out.push_str(&hex);
+                        }
+                    }
+                }
+                escaped @ ('"' | '\\') => out.push(escaped),
+                other => {
+                    out.push('\\');
+                    out.push(other);
+                }
+            },
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+fn infer_value(raw: &str, infer: bool, span: Span) -> Value {
+    if infer {
+        if let Ok(bool) = raw.parse::<bool>() {
+            return Value::bool(bool, span);
+        }
+        if let Ok(int) = raw.parse::<i64>() {
+            return Value::int(int, span);
+        }
+        // Only plain numbers, not words like `inf` or `NaN`
+        let looks_numeric = raw
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'));
+        if looks_numeric && let Ok(float) = raw.parse::<f64>() {
+            return Value::float(float, span);
+        }
+    }
+    Value::string(raw, span)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(FromLogfmt)
+    }
+
+    #[test]
+    fn parses_escapes_in_quotes() {
+        let record = parse_line(
+            r#"msg="say \"hi\"\n\u00e9" empty= path=C:\dir"#,
+            true,
+            Span::test_data(),
+        );
+        assert_eq!(
+            record,
+            Ok(record! {
+                "msg" => Value::test_string("say \"hi\"\né"),
+                "empty" => Value::test_string(""),
+                "path" => Value::test_string(r"C:\dir"),
+            })
+        );
+    }
+
+    #[test]
+    fn reports_invalid_lines() {
+        assert!(parse_line(r#"msg="unterminated"#, true, Span::test_data()).is_err());
+        assert!(parse_line("=value", true, Span::test_data()).is_err());
+        assert!(parse_line(r#"a="x"b=1"#, true, Span::test_data()).is_err());
+    }
+}
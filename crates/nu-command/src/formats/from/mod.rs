@@ -1,15 +1,25 @@
+#[cfg(feature = "avro")]
+mod avro;
+mod cbor;
 mod command;
 mod csv;
 mod delimited;
+mod dotenv;
+mod edn;
 mod json;
 mod kdl;
+mod logfmt;
 mod md;
 mod msgpack;
 mod msgpackz;
 mod nuon;
 mod ods;
+mod plist;
+#[cfg(feature = "protobuf")]
+mod protobuf;
 mod sheets;
 mod ssv;
+mod syslog;
 mod toml;
 mod tsv;
 mod xlsx;
@@ -17,20 +27,32 @@ mod xml;
 mod yaml;
 
 pub use self::csv::FromCsv;
+#[cfg(feature = "avro")]
+pub use avro::FromAvro;
 pub use self::toml::FromToml;
+pub use cbor::FromCbor;
 pub use command::From;
+pub use dotenv::FromDotenv;
+pub use edn::FromEdn;
 pub use json::FromJson;
 pub use kdl::FromKdl;
+pub use logfmt::FromLogfmt;
 pub use md::FromMd;
 pub use msgpack::FromMsgpack;
 pub use msgpackz::FromMsgpackz;
 pub use nuon::FromNuon;
 pub use ods::FromOds;
+pub use plist::FromPlist;
+#[cfg(feature = "protobuf")]
+pub use protobuf::FromProtobuf;
 pub use ssv::FromSsv;
+pub use syslog::FromSyslog;
 pub use tsv::FromTsv;
 pub use xlsx::FromXlsx;
 pub use xml::FromXml;
 pub use yaml::{FROM_YAML, FROM_YML, FromYamlLike};
 
+pub(crate) use dotenv::parse_dotenv;
 pub(crate) use json::try_str_to_value as try_json_str_to_value;
+pub(crate) use toml::convert_string_to_value as toml_str_to_value;
 pub(crate) use toml::convert_toml_datetime_to_value as toml_datetime_to_value;
@@ -0,0 +1,182 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+use plist::{Date as PlistDate, Value as PlistValue};
+use std::time::SystemTime;
+
+#[derive(Clone)]
+pub struct FromPlist;
+
+impl Command for FromPlist {
+    fn name(&self) -> &str {
+        "from plist"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from plist")
+            .input_output_types(vec![(Type::String, Type::Any), (Type::Binary, Type::Any)])
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Parse an XML or binary property list (.plist)."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Dictionaries become records and arrays become lists. Dates become datetimes, data becomes \
+binary, and the UIDs of keyed archives become ints."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["property list", "macos", "apple", "defaults"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"'<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>autohide</key>
+    <true/>
+    <key>tilesize</key>
+    <integer>48</integer>
+</dict>
+</plist>' | from plist"#,
+                description: "Converts an XML plist to a record.",
+                result: Some(Value::test_record(record! {
+                    "autohide" => Value::test_bool(true),
+                    "tilesize" => Value::test_int(48),
+                })),
+            },
+            Example {
+                example: "defaults export com.apple.dock - | from plist | get tilesize",
+                description: "Read a setting from the preferences of a macOS app.",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let metadata = input.take_metadata().map(|md| md.with_content_type(None));
+        let value = input.into_value(head)?;
+        let span = value.span();
+
+        let plist = match &value {
+            Value::String { val, .. } => plist::from_bytes(val.as_bytes()),
+            Value::Binary { val, .. } => plist::from_bytes(val),
+            other => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "string or binary".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: span,
+                });
+            }
+        }
+        .map_err(|err| GenericError::new("Could not parse plist", err.to_string(), span))?;
+
+        Ok(convert_plist_value(&plist, span)?.into_pipeline_data_with_metadata(metadata))
+    }
+}
+
+fn convert_plist_value(plist: &PlistValue, span: Span) -> Result<Value, ShellError> {
+    let too_large = |number: String| {
+        ShellError::Generic(GenericError::new(
+            "Could not convert plist",
+            format!("{number} is too large for an int"),
+            span,
+        ))
+    };
+
+    Ok(match plist {
+        PlistValue::Dictionary(dict) => Value::record(
+            dict.iter()
+                .map(|(key, value)| Ok((key.clone(), convert_plist_value(value, span)?)))
+                .collect::<Result<Record, ShellError>>()?,
+            span,
+        ),
+        PlistValue::Array(array) => Value::list(
+            array
+                .iter()
+                .map(|value| convert_plist_value(value, span))
+                .collect::<Result<_, _>>()?,
+            span,
+        ),
+        PlistValue::String(string) => Value::string(string.clone(), span),
+        PlistValue::Boolean(bool) => Value::bool(*bool, span),
+        PlistValue::Real(float) => Value::float(*float, span),
+        PlistValue::Integer(int) => match int.as_signed() {
+            Some(int) => Value::int(int, span),
+            None => return Err(too_large(int.to_string())),
+        },
+        PlistValue::Date(date) => Value::date(convert_date(date), span),
+        PlistValue::Data(data) => Value::binary(data.clone(), span),
+        PlistValue::Uid(uid) => match i64::try_from(uid.get()) {
+            Ok(int) => Value::int(int, span),
+            Err(_) => return Err(too_large(uid.get().to_string())),
+        },
+        // The value type is non-exhaustive
+        _ => Value::nothing(span),
+    })
+}
+
+/// Plist dates are UTC timestamps.
+fn convert_date(date: &PlistDate) -> DateTime<FixedOffset> {
+    let time: SystemTime = (*date).into();
+    DateTime::<Utc>::from(time).fixed_offset()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use plist::{Dictionary, Uid};
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(FromPlist)
+    }
+
+    #[test]
+    fn converts_values() {
+        let mut dict = Dictionary::new();
+        dict.insert("name".into(), PlistValue::String("dock".into()));
+        dict.insert("data".into(), PlistValue::Data(vec![1, 2]));
+        dict.insert("uid".into(), PlistValue::Uid(Uid::new(7)));
+        dict.insert(
+            "list".into(),
+            PlistValue::Array(vec![PlistValue::Real(0.5), PlistValue::Boolean(false)]),
+        );
+
+        let value = convert_plist_value(&PlistValue::Dictionary(dict), Span::test_data());
+        assert_eq!(
+            value,
+            Ok(Value::test_record(record! {
+                "name" => Value::test_string("dock"),
+                "data" => Value::test_binary(vec![1, 2]),
+                "uid" => Value::test_int(7),
+                "list" => Value::test_list(vec![Value::test_float(0.5), Value::test_bool(false)]),
+            }))
+        );
+    }
+
+    #[test]
+    fn converts_dates() {
+        let date = PlistDate::from(SystemTime::UNIX_EPOCH);
+        assert_eq!(convert_date(&date).timestamp(), 0);
+    }
+
+    #[test]
+    fn rejects_large_integers() {
+        let plist = PlistValue::Integer(u64::MAX.into());
+        assert!(convert_plist_value(&plist, Span::test_data()).is_err());
+    }
+}
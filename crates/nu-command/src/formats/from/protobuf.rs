@@ -0,0 +1,268 @@
+use chrono::DateTime;
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::{generic::GenericError, io::IoError};
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MapKey, Value as ProtoValue,
+};
+
+#[derive(Clone)]
+pub struct FromProtobuf;
+
+impl Command for FromProtobuf {
+    fn name(&self) -> &str {
+        "from protobuf"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from protobuf")
+            .input_output_types(vec![(Type::Binary, Type::record())])
+            .required(
+                "message",
+                SyntaxShape::String,
+                "The full name of the message type, like `my.package.Event`.",
+            )
+            .required_named(
+                "descriptor",
+                SyntaxShape::Filepath,
+                "A file descriptor set with the message type, as made by `protoc --descriptor_set_out`.",
+                Some('d'),
+            )
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Decode a binary protobuf message into a record, with the types of a descriptor set."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Messages become records with all their fields, repeated fields become lists and maps \
+become records. Fields that aren't set have their default value, or are null if they're messages \
+or track their presence. Enums become the names of their values. `google.protobuf.Timestamp` and \
+`google.protobuf.Duration` become datetimes and durations when they're in the descriptor set.
+
+The descriptor set is made with `protoc --include_imports --descriptor_set_out=types.pb \
+types.proto`."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["proto", "grpc", "decode", "binary"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Decode a message captured from a service.",
+                example: "open --raw event.bin | from protobuf --descriptor types.pb my.package.Event",
+                result: None,
+            },
+            Example {
+                description: "Decode a message from hexadecimal text.",
+                example: "'0801' | decode hex | from protobuf -d types.pb my.package.Flag",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let Some(descriptor) =
+            call.get_flag::<Spanned<String>>(engine_state, stack, "descriptor")?
+        else {
+            return Err(ShellError::MissingParameter {
+                param_name: "descriptor".into(),
+                span: head,
+            });
+        };
+
+        let cwd = engine_state.cwd(Some(stack))?;
+        let path = nu_path::expand_path_with(&descriptor.item, &cwd, true);
+        let bytes =
+            std::fs::read(&path).map_err(|err| IoError::new(err, descriptor.span, path.clone()))?;
+        let pool = DescriptorPool::decode(bytes.as_slice()).map_err(|err| {
+            GenericError::new(
+                "Could not read the descriptor set",
+                err.to_string(),
+                descriptor.span,
+            )
+            .with_help("make it with `protoc --include_imports --descriptor_set_out=<file>`")
+        })?;
+        let Some(message_type) = pool.get_message_by_name(&name.item) else {
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    "Message type not found",
+                    format!("there's no `{}` message in the descriptor set", name.item),
+                    name.span,
+                )
+                .with_help("use the full name of the message, with its package"),
+            ));
+        };
+
+        let metadata = input.take_metadata().map(|md| md.with_content_type(None));
+        let span = input.span().unwrap_or(head);
+        let payload = input.into_value(head)?;
+        let Value::Binary { val, .. } = &payload else {
+            return Err(ShellError::OnlySupportsThisInputType {
+                exp_input_type: "binary".into(),
+                wrong_type: payload.get_type().to_string(),
+                dst_span: head,
+                src_span: span,
+            });
+        };
+        let message = DynamicMessage::decode(message_type, val.as_slice()).map_err(|err| {
+            GenericError::new(
+                "Could not decode protobuf",
+                format!("not a valid `{}` message: {err}", name.item),
+                span,
+            )
+        })?;
+
+        Ok(convert_message(&message, span).into_pipeline_data_with_metadata(metadata))
+    }
+}
+
+fn convert_message(message: &DynamicMessage, span: Span) -> Value {
+    let descriptor = message.descriptor();
+    match descriptor.full_name() {
+        "google.protobuf.Timestamp" => {
+            let (seconds, nanos) = seconds_and_nanos(message);
+            if let Some(date) = DateTime::from_timestamp(seconds, nanos.try_into().unwrap_or(0)) {
+                return Value::date(date.fixed_offset(), span);
+            }
+        }
+        "google.protobuf.Duration" => {
+            let (seconds, nanos) = seconds_and_nanos(message);
+            if let Some(duration) = seconds
+                .checked_mul(1_000_000_000)
+                .and_then(|duration| duration.checked_add(nanos.into()))
+            {
+                return Value::duration(duration, span);
+            }
+        }
+        _ => (),
+    }
+
+    Value::record(
+        descriptor
+            .fields()
+            .map(|field| {
+                let value = if is_unset_optional(message, &field) {
+                    Value::nothing(span)
+                } else {
+                    convert_value(&message.get_field(&field), &field.kind(), span)
+                };
+                (field.name().to_string(), value)
+            })
+            .collect(),
+        span,
+    )
+}
+
+/// Whether a field isn't set and can tell it apart from its default value.
+fn is_unset_optional(message: &DynamicMessage, field: &FieldDescriptor) -> bool {
+    field.supports_presence() && !message.has_field(field)
+}
+
+fn seconds_and_nanos(message: &DynamicMessage) -> (i64, i32) {
+    let seconds = message
+        .get_field_by_name("seconds")
+        .and_then(|value| value.as_i64());
+    let nanos = message
+        .get_field_by_name("nanos")
+        .and_then(|value| value.as_i32());
+    (seconds.unwrap_or(0), nanos.unwrap_or(0))
+}
+
+fn convert_value(value: &ProtoValue, kind: &Kind, span: Span) -> Value {
+    match value {
+        ProtoValue::Bool(bool) => Value::bool(*bool, span),
+        ProtoValue::I32(int) => Value::int((*int).into(), span),
+        ProtoValue::I64(int) => Value::int(*int, span),
+        ProtoValue::U32(int) => Value::int((*int).into(), span),
+        ProtoValue::U64(int) => match i64::try_from(*int) {
+            Ok(int) => Value::int(int, span),
+            Err(_) => Value::error(
+                ShellError::Generic(GenericError::new(
+                    "Could not decode protobuf",
+                    format!("{int} is too large for an int"),
+                    span,
+                )),
+                span,
+            ),
+        },
+        ProtoValue::F32(float) => Value::float((*float).into(), span),
+        ProtoValue::F64(float) => Value::float(*float, span),
+        ProtoValue::String(string) => Value::string(string, span),
+        ProtoValue::Bytes(bytes) => Value::binary(bytes.to_vec(), span),
+        ProtoValue::EnumNumber(number) => match kind {
+            Kind::Enum(enum_type) => match enum_type.get_value(*number) {
+                Some(enum_value) => Value::string(enum_value.name(), span),
+                // Values added after the descriptor was made
+                None => Value::int((*number).into(), span),
+            },
+            _ => Value::int((*number).into(), span),
+        },
+        ProtoValue::Message(message) => convert_message(message, span),
+        ProtoValue::List(values) => Value::list(
+            values
+                .iter()
+                .map(|value| convert_value(value, kind, span))
+                .collect(),
+            span,
+        ),
+        ProtoValue::Map(map) => {
+            // Maps are repeated entry messages, with the type of their values in the `value` field
+            let value_kind = match kind {
+                Kind::Message(entry) if entry.is_map_entry() => {
+                    entry.map_entry_value_field().kind()
+                }
+                kind => kind.clone(),
+            };
+            let mut entries: Vec<_> = map
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        map_key_to_string(key),
+                        convert_value(value, &value_kind, span),
+                    )
+                })
+                .collect();
+            // Maps have no order, so sort them to get the same record every time
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::record(entries.into_iter().collect(), span)
+        }
+    }
+}
+
+fn map_key_to_string(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(bool) => bool.to_string(),
+        MapKey::I32(int) => int.to_string(),
+        MapKey::I64(int) => int.to_string(),
+        MapKey::U32(int) => int.to_string(),
+        MapKey::U64(int) => int.to_string(),
+        MapKey::String(string) => string.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(FromProtobuf)
+    }
+
+    #[test]
+    fn converts_map_keys() {
+        assert_eq!(map_key_to_string(&MapKey::I32(-3)), "-3");
+        assert_eq!(map_key_to_string(&MapKey::String("a".into())), "a");
+    }
+}
@@ -0,0 +1,466 @@
+use std::io::{BufRead, Cursor};
+
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveTime, TimeDelta, TimeZone};
+use nu_engine::command_prelude::*;
+use nu_protocol::{
+    ListStream, Signals,
+    shell_error::{generic::GenericError, io::IoError},
+};
+
+#[derive(Clone)]
+pub struct FromSyslog;
+
+const FACILITIES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+impl Command for FromSyslog {
+    fn name(&self) -> &str {
+        "from syslog"
+    }
+
+    fn description(&self) -> &str {
+        "Parse syslog messages into a table."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Every line becomes a record, read one at a time as the table is consumed. Both RFC 5424 \
+messages and the older BSD format of RFC 3164 are read, with or without their `<PRI>` part, so the \
+output of `journalctl -o short` can be parsed too.
+
+BSD timestamps have no year, so the current one is used, or the previous one for dates in the \
+future. Lines that aren't syslog messages become records with only their message."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("from syslog")
+            .input_output_types(vec![(Type::String, Type::table())])
+            .category(Category::Formats)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["log", "journal", "rsyslog", "rfc5424", "rfc3164"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"'<165>1 2003-10-11T22:14:15.003Z host.example.com evntslog - ID47 [origin ip="192.0.2.1"] Started' | from syslog"#,
+                description: "Parse an RFC 5424 message.",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "facility" => Value::test_string("local4"),
+                    "severity" => Value::test_string("notice"),
+                    "timestamp" => Value::test_date(
+                        DateTime::parse_from_rfc3339("2003-10-11T22:14:15.003Z").unwrap_or_default(),
+                    ),
+                    "host" => Value::test_string("host.example.com"),
+                    "app" => Value::test_string("evntslog"),
+                    "pid" => Value::test_nothing(),
+                    "msgid" => Value::test_string("ID47"),
+                    "structured_data" => Value::test_record(record! {
+                        "origin" => Value::test_record(record! {
+                            "ip" => Value::test_string("192.0.2.1"),
+                        }),
+                    }),
+                    "message" => Value::test_string("Started"),
+                })])),
+            },
+            Example {
+                example: "journalctl -o short -n 100 | from syslog | where app == sshd",
+                description: "Parse the recent journal entries and keep the ones of a service.",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let signals = engine_state.signals().clone();
+        let metadata = input.take_metadata().map(|md| md.with_content_type(None));
+
+        match input {
+            PipelineData::Value(Value::String { val, .. }, ..) => Ok(PipelineData::list_stream(
+                read_syslog_lines(Cursor::new(val), span, signals),
+                metadata,
+            )),
+            // Read the lines as they're needed, so that captures can be streamed
+            PipelineData::ByteStream(stream, ..) if stream.type_() != ByteStreamType::Binary => {
+                match stream.reader() {
+                    Some(reader) => Ok(PipelineData::list_stream(
+                        read_syslog_lines(reader, span, signals),
+                        metadata,
+                    )),
+                    None => Ok(PipelineData::empty()),
+                }
+            }
+            _ => Err(ShellError::OnlySupportsThisInputType {
+                exp_input_type: "string".into(),
+                wrong_type: input.get_type().to_string(),
+                dst_span: call.head,
+                src_span: input.span().unwrap_or(call.head),
+            }),
+        }
+    }
+}
+
+/// Create a stream of records from a reader that produces syslog lines
+fn read_syslog_lines(
+    input: impl BufRead + Send + 'static,
+    span: Span,
+    signals: Signals,
+) -> ListStream {
+    let iter = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            line.as_ref().is_ok_and(|line| !line.trim().is_empty()) || line.is_err()
+        })
+        .map(move |(index, line)| -> Result<Value, ShellError> {
+            let line = line.map_err(|err| IoError::new(err, span, None))?;
+            let message = parse_message(&line, span).map_err(|msg| {
+                GenericError::new(
+                    "Error while parsing syslog",
+                    format!("{msg} on line {}", index + 1),
+                    span,
+                )
+            })?;
+            Ok(message.into_value(span))
+        })
+        .map(move |result| result.unwrap_or_else(|err| Value::error(err, span)));
+
+    ListStream::new(iter, span, signals)
+}
+
+#[derive(Default)]
+struct Message {
+    priority: Option<u8>,
+    timestamp: Option<DateTime<FixedOffset>>,
+    host: Option<String>,
+    app: Option<String>,
+    pid: Option<String>,
+    msgid: Option<String>,
+    structured_data: Option<Record>,
+    message: String,
+}
+
+impl Message {
+    fn into_value(self, span: Span) -> Value {
+        let text = |text: Option<String>| match text {
+            Some(text) => Value::string(text, span),
+            None => Value::nothing(span),
+        };
+        let (facility, severity) = match self.priority {
+            Some(priority) => (
+                Value::string(FACILITIES[usize::from(priority / 8)], span),
+                Value::string(SEVERITIES[usize::from(priority % 8)], span),
+            ),
+            None => (Value::nothing(span), Value::nothing(span)),
+        };
+        Value::record(
+            record! {
+                "facility" => facility,
+                "severity" => severity,
+                "timestamp" => match self.timestamp {
+                    Some(timestamp) => Value::date(timestamp, span),
+                    None => Value::nothing(span),
+                },
+                "host" => text(self.host),
+                "app" => text(self.app),
+                "pid" => text(self.pid),
+                "msgid" => text(self.msgid),
+                "structured_data" => match self.structured_data {
+                    Some(data) => Value::record(data, span),
+                    None => Value::nothing(span),
+                },
+                "message" => Value::string(self.message, span),
+            },
+            span,
+        )
+    }
+}
+
+fn parse_message(line: &str, span: Span) -> Result<Message, String> {
+    let (priority, rest) = match line.strip_prefix('<') {
+        Some(after) => {
+            let (priority, rest) = after
+                .split_once('>')
+                .ok_or("expected `>` after the priority")?;
+            let priority = priority
+                .parse::<u8>()
+                .ok()
+                .filter(|priority| *priority < 192)
+                .ok_or_else(|| format!("invalid priority `{priority}`"))?;
+            (Some(priority), rest)
+        }
+        None => (None, line),
+    };
+
+    // RFC 5424 messages have a version right after the priority
+    if priority.is_some()
+        && let Some(rest) = rest.strip_prefix("1 ")
+    {
+        let mut message = parse_rfc5424(rest, span)?;
+        message.priority = priority;
+        return Ok(message);
+    }
+
+    let mut message = parse_rfc3164(rest);
+    message.priority = priority;
+    Ok(message)
+}
+
+fn parse_rfc5424(line: &str, span: Span) -> Result<Message, String> {
+    let nil = |field: &str| (field != "-").then(|| field.to_string());
+
+    let (timestamp, rest) = next_token(line);
+    let timestamp = match timestamp {
+        "-" => None,
+        timestamp => Some(
+            DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|_| format!("invalid timestamp `{timestamp}`"))?,
+        ),
+    };
+    let (host, rest) = next_token(rest);
+    let (app, rest) = next_token(rest);
+    let (pid, rest) = next_token(rest);
+    let (msgid, rest) = next_token(rest);
+    let (structured_data, rest) = parse_structured_data(rest.trim_start(), span)?;
+    let message = rest.strip_prefix(' ').unwrap_or(rest);
+
+    Ok(Message {
+        priority: None,
+        timestamp,
+        host: nil(host),
+        app: nil(app),
+        pid: nil(pid),
+        msgid: nil(msgid),
+        structured_data,
+        message: message.trim_start_matches('\u{feff}').to_string(),
+    })
+}
+
+/// Parse the structured data elements, like `[id key="value"]`, into a record of records.
+fn parse_structured_data(input: &str, span: Span) -> Result<(Option<Record>, &str), String> {
+    if let Some(rest) = input.strip_prefix('-') {
+        return Ok((None, rest));
+    }
+
+    let mut data = Record::new();
+    let mut rest = input;
+    while let Some(element) = rest.strip_prefix('[') {
+        let id_end = element
+            .find([' ', ']'])
+            .ok_or("unterminated structured data")?;
+        let id = &element[..id_end];
+        let mut params = Record::new();
+        rest = &element[id_end..];
+        loop {
+            rest = rest.trim_start_matches(' ');
+            if let Some(after) = rest.strip_prefix(']') {
+                rest = after;
+                break;
+            }
+            let (name, after) = rest
+                .split_once("=\"")
+                .ok_or_else(|| format!("invalid parameter in the structured data `{id}`"))?;
+            let (value, after) = parse_param_value(after)
+                .ok_or_else(|| format!("unterminated parameter value in `{id}`"))?;
+            params.insert(name, Value::string(value, span));
+            rest = after;
+        }
+        data.insert(id, Value::record(params, span));
+    }
+
+    if data.is_empty() {
+        return Err("expected structured data or `-`".into());
+    }
+    Ok((Some(data), rest))
+}
+
+/// Read a parameter value up to its closing quote, with `\"`, `\\` and `\]` escapes.
+fn parse_param_value(input: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &input[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                escaped @ ('"' | '\\' | ']') => value.push(escaped),
+                other => {
+                    value.push('\\');
+                    value.push(other);
+                }
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+fn parse_rfc3164(line: &str) -> Message {
+    let Some((timestamp, rest)) = parse_bsd_timestamp(line).or_else(|| {
+        // Some relays use RFC 3339 timestamps with the BSD format
+        let (timestamp, rest) = next_token(line);
+        Some((DateTime::parse_from_rfc3339(timestamp).ok()?, rest))
+    }) else {
+        return Message {
+            message: line.to_string(),
+            ..Default::default()
+        };
+    };
+
+    let (host, rest) = next_token(rest);
+    let rest = rest.trim_start();
+
+    // The tag is the name of the program, with an optional pid, followed by a colon
+    let tag_end = rest.find([':', ' ']).unwrap_or(rest.len());
+    let (app, pid, message) = match rest[tag_end..].strip_prefix(':') {
+        Some(message) => {
+            let tag = &rest[..tag_end];
+            let (app, pid) = match tag.split_once('[') {
+                Some((app, pid)) => (app, pid.strip_suffix(']').map(str::to_string)),
+                None => (tag, None),
+            };
+            (Some(app.to_string()), pid, message.trim_start())
+        }
+        None => (None, None, rest),
+    };
+
+    Message {
+        timestamp: Some(timestamp),
+        host: Some(host.to_string()),
+        app,
+        pid,
+        message: message.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Parse a timestamp like `Oct 11 22:14:15`, in the local time zone.
+fn parse_bsd_timestamp(line: &str) -> Option<(DateTime<FixedOffset>, &str)> {
+    let (month, rest) = next_token(line);
+    let (day, rest) = next_token(rest);
+    let (time, rest) = next_token(rest);
+
+    let month = MONTHS.iter().position(|name| *name == month)? as u32 + 1;
+    let day = day.parse::<u32>().ok()?;
+    let time = NaiveTime::parse_from_str(time, "%H:%M:%S%.f").ok()?;
+
+    let now = Local::now();
+    let date_in = |year: i32| {
+        let date = NaiveDate::from_ymd_opt(year, month, day)?.and_time(time);
+        Local.from_local_datetime(&date).earliest()
+    };
+    let timestamp = match date_in(now.year()) {
+        // A date in the future is from the end of last year
+        Some(timestamp) if timestamp <= now + TimeDelta::days(1) => timestamp,
+        _ => date_in(now.year() - 1)?,
+    };
+    Some((timestamp.fixed_offset(), rest))
+}
+
+/// Split off the next word, skipping the spaces before it.
+fn next_token(input: &str) -> (&str, &str) {
+    let input = input.trim_start_matches(' ');
+    let end = input.find(' ').unwrap_or(input.len());
+    (&input[..end], &input[end..])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(FromSyslog)
+    }
+
+    #[test]
+    fn parses_rfc5424_without_structured_data() {
+        let message = parse_message(
+            "<34>1 2003-10-11T22:14:15.003Z mymachine su - ID47 - \u{feff}'su root' failed",
+            Span::test_data(),
+        )
+        .expect("valid message");
+        assert_eq!(message.priority, Some(34));
+        assert_eq!(message.app.as_deref(), Some("su"));
+        assert_eq!(message.pid, None);
+        assert!(message.structured_data.is_none());
+        assert_eq!(message.message, "'su root' failed");
+    }
+
+    #[test]
+    fn parses_escapes_in_structured_data() {
+        let (data, rest) =
+            parse_structured_data(r#"[a x="1 \"2\" \]"][b] rest"#, Span::test_data())
+                .expect("valid structured data");
+        assert_eq!(
+            data,
+            Some(record! {
+                "a" => Value::test_record(record! { "x" => Value::test_string(r#"1 "2" ]"#) }),
+                "b" => Value::test_record(Record::new()),
+            })
+        );
+        assert_eq!(rest, " rest");
+    }
+
+    #[test]
+    fn parses_journalctl_short_lines() {
+        let message = parse_message(
+            "Oct  6 10:00:00 myhost sshd[811]: Accepted publickey for ada",
+            Span::test_data(),
+        )
+        .expect("valid message");
+        assert_eq!(message.priority, None);
+        assert_eq!(message.host.as_deref(), Some("myhost"));
+        assert_eq!(message.app.as_deref(), Some("sshd"));
+        assert_eq!(message.pid.as_deref(), Some("811"));
+        assert_eq!(message.message, "Accepted publickey for ada");
+        assert!(message.timestamp.is_some());
+    }
+
+    #[test]
+    fn keeps_other_lines_as_messages() {
+        let message = parse_message("-- No entries --", Span::test_data()).expect("any line");
+        assert_eq!(message.message, "-- No entries --");
+        assert!(message.timestamp.is_none());
+        assert!(parse_message("<999>1 -", Span::test_data()).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::{Signals, ast::PathMember, shell_error::generic::GenericError};
+
+/// Max recursion depth
+const MAX_DEPTH: usize = 50;
+
+#[derive(Clone)]
+pub struct ToCbor;
+
+impl Command for ToCbor {
+    fn name(&self) -> &str {
+        "to cbor"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_type(Type::Any, Type::Binary)
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Convert Nu values into CBOR."
+    }
+
+    fn extra_description(&self) -> &str {
+        "
+Not all values are representable as CBOR.
+
+Datetimes become epoch-based dates with tag 1, as a number of seconds, and binaries
+become byte strings. Floats always use double precision. Most other types are
+represented in an analogous way to `to msgpack`, and may not convert to the exact same
+type when deserialized with `from cbor`.
+
+CBOR: https://cbor.io/
+"
+        .trim()
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Convert a list of values to CBOR.",
+                example: "[foo, 42, false] | to cbor",
+                result: Some(Value::test_binary(b"\x83\x63\x66\x6F\x6F\x18\x2A\xF4")),
+            },
+            Example {
+                description: "Convert a record with a datetime and a binary to CBOR.",
+                example: "{time: 2019-05-10T09:59:12-07:00, id: 0x[01 02]} | to cbor",
+                result: Some(Value::test_binary(
+                    b"\xA2\x64\x74\x69\x6D\x65\xC1\x1A\x5C\xD5\xAD\xE0\x62\x69\x64\x42\x01\x02",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let metadata = input
+            .take_metadata()
+            .unwrap_or_default()
+            .with_content_type(Some("application/cbor".into()));
+
+        let value_span = input.span().unwrap_or(call.head);
+        let value = input.into_value(value_span)?;
+        let mut out = vec![];
+
+        write_value(&mut out, &value, 0, engine_state, call.head)?;
+
+        Ok(Value::binary(out, call.head).into_pipeline_data_with_metadata(Some(metadata)))
+    }
+}
+
+fn write_value(
+    out: &mut Vec<u8>,
+    value: &Value,
+    depth: usize,
+    engine_state: &EngineState,
+    call_span: Span,
+) -> Result<(), ShellError> {
+    let span = value.span();
+    // Prevent stack overflow
+    if depth >= MAX_DEPTH {
+        return Err(ShellError::Generic(GenericError::new(
+            "CBOR data is nested too deeply",
+            format!("exceeded depth limit ({MAX_DEPTH})"),
+            span,
+        )));
+    }
+    match value {
+        Value::Bool { val, .. } => out.push(if *val { 0xf5 } else { 0xf4 }),
+        Value::Int { val, .. } => write_int(out, *val),
+        Value::Float { val, .. } => {
+            out.push(0xfb);
+            out.extend(val.to_be_bytes());
+        }
+        Value::Filesize { val, .. } => write_int(out, val.get()),
+        Value::Duration { val, .. } => write_int(out, *val),
+        Value::Date { val, .. } => {
+            // Epoch-based date, in seconds
+            write_head(out, 6, 1);
+            if val.timestamp_subsec_nanos() == 0 {
+                write_int(out, val.timestamp());
+            } else {
+                let seconds =
+                    val.timestamp() as f64 + f64::from(val.timestamp_subsec_nanos()) / 1e9;
+                out.push(0xfb);
+                out.extend(seconds.to_be_bytes());
+            }
+        }
+        Value::Range { val, .. } => {
+            // Convert range to list
+            write_value(
+                out,
+                &Value::list(val.into_range_iter(span, Signals::empty()).collect(), span),
+                depth,
+                engine_state,
+                call_span,
+            )?;
+        }
+        Value::String { val, .. } | Value::Glob { val, .. } => write_text(out, val),
+        Value::Record { val, .. } => {
+            write_head(out, 5, val.len() as u64);
+            for (k, v) in val.iter() {
+                write_text(out, k);
+                write_value(out, v, depth + 1, engine_state, call_span)?;
+            }
+        }
+        Value::List { vals, .. } => {
+            write_head(out, 4, vals.len() as u64);
+            for val in vals {
+                write_value(out, val, depth + 1, engine_state, call_span)?;
+            }
+        }
+        Value::Nothing { .. } => out.push(0xf6),
+        Value::Closure { .. } => {
+            return Err(ShellError::UnsupportedInput {
+                msg: "closures can't be converted to CBOR".into(),
+                input: "value originates from here".into(),
+                msg_span: call_span,
+                input_span: span,
+            });
+        }
+        Value::Error { error, .. } => {
+            return Err(*error.clone());
+        }
+        Value::CellPath { val, .. } => {
+            // Write as a list of strings/ints
+            write_head(out, 4, val.members.len() as u64);
+            for member in &val.members {
+                match member {
+                    PathMember::String { val, .. } => write_text(out, val),
+                    PathMember::Int { val, .. } => write_head(out, 0, *val as u64),
+                }
+            }
+        }
+        Value::Binary { val, .. } => {
+            write_head(out, 2, val.len() as u64);
+            out.extend(val);
+        }
+        Value::Custom { val, .. } => {
+            write_value(
+                out,
+                &val.to_base_value(span)?,
+                depth,
+                engine_state,
+                call_span,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the initial byte of a data item, with its argument in the fewest bytes.
+fn write_head(out: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    if argument < 24 {
+        out.push(major | argument as u8);
+    } else if let Ok(argument) = u8::try_from(argument) {
+        out.extend([major | 24, argument]);
+    } else if let Ok(argument) = u16::try_from(argument) {
+        out.push(major | 25);
+        out.extend(argument.to_be_bytes());
+    } else if let Ok(argument) = u32::try_from(argument) {
+        out.push(major | 26);
+        out.extend(argument.to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend(argument.to_be_bytes());
+    }
+}
+
+fn write_int(out: &mut Vec<u8>, int: i64) {
+    if int >= 0 {
+        write_head(out, 0, int as u64);
+    } else {
+        // Negative integers are stored as -1 - n
+        write_head(out, 1, !int as u64);
+    }
+}
+
+fn write_text(out: &mut Vec<u8>, text: &str) {
+    write_head(out, 3, text.len() as u64);
+    out.extend(text.as_bytes());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ToCbor)
+    }
+
+    #[test]
+    fn writes_shortest_arguments() {
+        let mut out = vec![];
+        write_int(&mut out, 23);
+        write_int(&mut out, 500);
+        write_int(&mut out, -1);
+        write_int(&mut out, i64::MIN);
+        assert_eq!(
+            out,
+            [
+                &[0x17, 0x19, 0x01, 0xf4, 0x20, 0x3b][..],
+                &i64::MAX.to_be_bytes()
+            ]
+            .concat()
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use nu_engine::command_prelude::*;
+use std::fmt::Write;
+
+#[derive(Clone)]
+pub struct ToEdn;
+
+impl Command for ToEdn {
+    fn name(&self) -> &str {
+        "to edn"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("to edn")
+            .input_output_types(vec![(Type::Any, Type::String)])
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Convert a value into EDN (extensible data notation) text."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Records become maps, with keyword keys when the names are valid keywords, and lists become \
+vectors. Datetimes become `#inst` literals, filesizes become their number of bytes and durations \
+their number of nanoseconds."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["clojure", "datomic", "extensible data notation"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Outputs an EDN map representing the contents of this record.",
+                example: "{name: nu, tags: [shell], 'has space': null} | to edn",
+                result: Some(Value::test_string(
+                    r#"{:name "nu", :tags ["shell"], "has space" nil}"#,
+                )),
+            },
+            Example {
+                description: "Outputs an `#inst` literal for a datetime.",
+                example: "2024-01-15T09:00:00Z | to edn",
+                result: Some(Value::test_string(r#"#inst "2024-01-15T09:00:00+00:00""#)),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let metadata = input
+            .take_metadata()
+            .unwrap_or_default()
+            .with_content_type(Some("application/edn".into()));
+        let value = input.into_value(head)?;
+
+        let mut out = String::new();
+        write_value(&mut out, &value)?;
+        Ok(Value::string(out, head).into_pipeline_data_with_metadata(Some(metadata)))
+    }
+}
+
+fn write_value(out: &mut String, value: &Value) -> Result<(), ShellError> {
+    match value {
+        Value::Nothing { .. } => out.push_str("nil"),
+        Value::Bool { val, .. } => out.push_str(if *val { "true" } else { "false" }),
+        Value::Int { val, .. } => out.push_str(&val.to_string()),
+        Value::Filesize { val, .. } => out.push_str(&val.get().to_string()),
+        Value::Duration { val, .. } => out.push_str(&val.to_string()),
+        Value::Float { val, .. } => write_float(out, *val),
+        Value::String { val, .. } | Value::Glob { val, .. } => write_string(out, val),
+        Value::Date { val, .. } => {
+            out.push_str("#inst ");
+            write_string(out, &val.to_rfc3339());
+        }
+        Value::Record { val, .. } => {
+            out.push('{');
+            for (i, (key, value)) in val.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                if is_keyword(key) {
+                    out.push(':');
+                    out.push_str(key);
+                } else {
+                    write_string(out, key);
+                }
+                out.push(' ');
+                write_value(out, value)?;
+            }
+            out.push('}');
+        }
+        Value::List { vals, .. } => {
+            out.push('[');
+            for (i, value) in vals.iter().enumerate() {
+                if i > 0 {
+                    out.push(' ');
+                }
+                write_value(out, value)?;
+            }
+            out.push(']');
+        }
+        Value::Error { error, .. } => return Err(*error.clone()),
+        other => {
+            return Err(ShellError::CantConvert {
+                to_type: "EDN".into(),
+                from_type: other.get_type().to_string(),
+                span: other.span(),
+                help: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn write_float(out: &mut String, float: f64) {
+    if float.is_nan() {
+        out.push_str("##NaN");
+    } else if float.is_infinite() {
+        out.push_str(if float > 0.0 { "##Inf" } else { "##-Inf" });
+    } else {
+        let _ = write!(out, "{float}");
+        // EDN reads numbers without a decimal point as integers
+        if float.fract() == 0.0 {
+            out.push_str(".0");
+        }
+    }
+}
+
+fn write_string(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Whether a record key can be written as a keyword, like `:name` or `:person/name`.
+fn is_keyword(key: &str) -> bool {
+    let Some(first) = key.chars().next() else {
+        return false;
+    };
+    let starts_like_number = first.is_ascii_digit()
+        || (matches!(first, '+' | '-' | '.') && key[1..].starts_with(|c: char| c.is_ascii_digit()));
+    !starts_like_number
+        && !matches!(first, ':' | '#')
+        && !key.starts_with('/')
+        && !key.ends_with('/')
+        && key.matches('/').count() <= 1
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || ".*+!-_?$%&=<>/:#'".contains(c))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ToEdn)
+    }
+
+    #[test]
+    fn writes_floats_with_a_decimal_point() {
+        let mut out = String::new();
+        write_float(&mut out, 2.0);
+        assert_eq!(out, "2.0");
+    }
+
+    #[test]
+    fn checks_keywords() {
+        assert!(is_keyword("person/name"));
+        assert!(is_keyword("valid?"));
+        assert!(!is_keyword("1st"));
+        assert!(!is_keyword("-1"));
+        assert!(!is_keyword("has space"));
+        assert!(!is_keyword(""));
+    }
+}
@@ -0,0 +1,190 @@
+use nu_engine::command_prelude::*;
+use nu_utils::ObviousFloat;
+
+#[derive(Clone)]
+pub struct ToLogfmt;
+
+impl Command for ToLogfmt {
+    fn name(&self) -> &str {
+        "to logfmt"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("to logfmt")
+            .input_output_types(vec![
+                (Type::record(), Type::String),
+                (Type::table(), Type::String),
+            ])
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Convert records into logfmt lines."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Every record becomes a line of `key=value` pairs. Values with spaces, quotes, `=` or \
+control characters are quoted and escaped. Null becomes an empty value, datetimes become RFC 3339 \
+text, filesizes become their number of bytes and durations their number of nanoseconds."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["log", "key", "value", "heroku", "loki"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Outputs a logfmt line representing the contents of this record.",
+                example: "{level: info, msg: 'user logged in', user: \"ada\", ok: true} | to logfmt",
+                result: Some(Value::test_string(
+                    r#"level=info msg="user logged in" user=ada ok=true"#,
+                )),
+            },
+            Example {
+                description: "Outputs a line for each row of a table.",
+                example: "[[at status]; [info 200] [error null]] | to logfmt",
+                result: Some(Value::test_string("at=info status=200\nat=error status=")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let metadata = input
+            .take_metadata()
+            .unwrap_or_default()
+            .with_content_type(Some("text/plain".into()));
+
+        let mut lines = vec![];
+        for value in input {
+            lines.push(record_to_line(&value, head)?);
+        }
+        Ok(Value::string(lines.join("\n"), head).into_pipeline_data_with_metadata(Some(metadata)))
+    }
+}
+
+fn record_to_line(value: &Value, head: Span) -> Result<String, ShellError> {
+    let record = match value {
+        Value::Record { val, .. } => val,
+        Value::Error { error, .. } => return Err(*error.clone()),
+        other => {
+            return Err(ShellError::OnlySupportsThisInputType {
+                exp_input_type: "record or table".into(),
+                wrong_type: other.get_type().to_string(),
+                dst_span: head,
+                src_span: other.span(),
+            });
+        }
+    };
+
+    let mut pairs = vec![];
+    for (key, value) in record.iter() {
+        if key.is_empty() || key.contains(|c: char| c == '=' || c == '"' || c <= ' ') {
+            return Err(ShellError::CantConvert {
+                to_type: "logfmt".into(),
+                from_type: format!("the key `{key}`"),
+                span: value.span(),
+                help: Some("logfmt keys can't be empty, or have spaces, quotes or `=`".into()),
+            });
+        }
+        pairs.push(format!("{key}={}", value_to_text(value)?));
+    }
+    Ok(pairs.join(" "))
+}
+
+fn value_to_text(value: &Value) -> Result<String, ShellError> {
+    let text = match value {
+        Value::Nothing { .. } => return Ok(String::new()),
+        Value::Bool { val, .. } => val.to_string(),
+        Value::Int { val, .. } => val.to_string(),
+        // Whole floats keep their decimal point, and `NaN` or `inf` are quoted since they aren't
+        // read back as floats
+        Value::Float { val, .. } if val.is_finite() => ObviousFloat(*val).to_string(),
+        Value::Float { val, .. } => return Ok(quote_if_needed(val.to_string(), true)),
+        Value::Filesize { val, .. } => val.get().to_string(),
+        Value::Duration { val, .. } => val.to_string(),
+        Value::Date { val, .. } => val.to_rfc3339(),
+        // Text that reads like another type is quoted, so `from logfmt` keeps it as text
+        Value::String { val, .. } | Value::Glob { val, .. } => {
+            let force = val.parse::<f64>().is_ok() || val.parse::<bool>().is_ok();
+            return Ok(quote_if_needed(val.clone(), force));
+        }
+        Value::Error { error, .. } => return Err(*error.clone()),
+        other => {
+            return Err(ShellError::CantConvert {
+                to_type: "logfmt".into(),
+                from_type: other.get_type().to_string(),
+                span: other.span(),
+                help: Some("logfmt values are flat, use `flatten` or `to json` first".into()),
+            });
+        }
+    };
+    Ok(quote_if_needed(text, false))
+}
+
+/// Quote the text when it wouldn't be read back as the same value.
+fn quote_if_needed(text: String, force: bool) -> String {
+    let needs_quotes = force
+        || text.is_empty()
+        || text
+            .chars()
+            .any(|c| c <= ' ' || c == '=' || c == '"' || c == '\\' || c.is_control());
+    if !needs_quotes {
+        return text;
+    }
+
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ToLogfmt)
+    }
+
+    #[test]
+    fn quotes_and_escapes() {
+        assert_eq!(quote_if_needed("plain".into(), false), "plain");
+        assert_eq!(quote_if_needed("42".into(), true), r#""42""#);
+        assert_eq!(quote_if_needed("".into(), false), r#""""#);
+        assert_eq!(quote_if_needed("a=b".into(), false), r#""a=b""#);
+        assert_eq!(
+            quote_if_needed("say \"hi\"\n\\".into(), false),
+            r#""say \"hi\"\n\\""#
+        );
+    }
+
+    #[test]
+    fn writes_floats() {
+        let text = |val| value_to_text(&Value::test_float(val)).expect("floats convert");
+        assert_eq!(text(1.0), "1.0");
+        assert_eq!(text(-2.5), "-2.5");
+        assert_eq!(text(f64::NAN), r#""NaN""#);
+        assert_eq!(text(f64::INFINITY), r#""inf""#);
+        assert_eq!(text(f64::NEG_INFINITY), r#""-inf""#);
+    }
+}
@@ -2,7 +2,7 @@ use indexmap::IndexMap;
 use nu_cmd_base::formats::to::delimited::merge_descriptors;
 use nu_engine::command_prelude::*;
 use nu_protocol::{Config, ast::PathMember};
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 #[derive(Clone)]
 pub struct ToMd;
@@ -32,10 +32,35 @@ impl ListStyle {
     }
 }
 
+/// Defines how a column of a Markdown table is aligned
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Alignment {
+    /// Left aligned, with ":---"
+    Left,
+    /// Centered, with ":---:"
+    Center,
+    /// Right aligned, with "---:"
+    Right,
+}
+
+impl Alignment {
+    const OPTIONS: &[&'static str] = &["left", "center", "right"];
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "left" => Some(Self::Left),
+            "center" => Some(Self::Center),
+            "right" => Some(Self::Right),
+            _ => None,
+        }
+    }
+}
+
 struct ToMdOptions {
     pretty: bool,
     per_element: bool,
     center: Option<Vec<CellPath>>,
+    align: Vec<(String, Alignment)>,
     escape_md: bool,
     escape_html: bool,
     list_style: ListStyle,
@@ -76,6 +101,12 @@ impl Command for ToMd {
                 "Formats the Markdown table to center given columns.",
                 Some('c'),
             )
+            .named(
+                "align",
+                SyntaxShape::Record(vec![]),
+                "Aligns the given columns of the Markdown table, like {name: left, price: right}.",
+                None,
+            )
             .switch(
                 "escape-md",
                 "Escapes Markdown special characters.",
@@ -100,6 +131,12 @@ impl Command for ToMd {
         "Convert table into simple Markdown."
     }
 
+    fn extra_description(&self) -> &str {
+        "Lists and records nested in a table are written as inline code, so they stay in their \
+cell. Lists that aren't tables are written as Markdown lists, with their nested lists and records \
+as indented sub-items."
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -142,6 +179,27 @@ impl Command for ToMd {
                     "| foo | bar |\n| --- |:---:|\n| 1   |  2  |\n| 3   |  4  |",
                 )),
             },
+            Example {
+                description: "Align columns of a markdown table.",
+                example: "[[item price]; [tea 3.5] [cake 12]] | to md --pretty --align {price: right}",
+                result: Some(Value::test_string(
+                    "| item | price |\n| ---- | -----:|\n| tea  |   3.5 |\n| cake |    12 |",
+                )),
+            },
+            Example {
+                description: "Write nested values of a table as inline code.",
+                example: "[[name tags]; [nu [shell data]]] | to md",
+                result: Some(Value::test_string(
+                    "| name | tags |\n| --- | --- |\n| nu | `[shell, data]` |",
+                )),
+            },
+            Example {
+                description: "Render nested lists and records as sub-items.",
+                example: "[one [two {three: 3, four: [4 5]}]] | to md",
+                result: Some(Value::test_string(
+                    "* one\n* * two\n  * * three: 3\n    * four:\n      * 4\n      * 5",
+                )),
+            },
             Example {
                 description: "Escape markdown special characters.",
                 example: r#"[ {foo: "_1_", bar: "\# 2"} {foo: "[3]", bar: "4|5"}] | to md --escape-md"#,
@@ -184,6 +242,7 @@ impl Command for ToMd {
         let escape_html = call.has_flag(engine_state, stack, "escape-html")?;
         let escape_both = call.has_flag(engine_state, stack, "escape-all")?;
         let center: Option<Vec<CellPath>> = call.get_flag(engine_state, stack, "center")?;
+        let align: Option<Record> = call.get_flag(engine_state, stack, "align")?;
         let list_style_str: Option<Spanned<String>> = call.get_flag(engine_state, stack, "list")?;
 
         let list_style = match &list_style_str {
@@ -197,6 +256,23 @@ impl Command for ToMd {
             None => ListStyle::default(),
         };
 
+        let align = align
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(column, value)| {
+                let span = value.span();
+                let alignment = value.coerce_into_string()?;
+                match Alignment::from_str(&alignment) {
+                    Some(alignment) => Ok((column, alignment)),
+                    None => Err(ShellError::InvalidValue {
+                        valid: format!("one of {}", Alignment::OPTIONS.join(", ")),
+                        actual: alignment,
+                        span,
+                    }),
+                }
+            })
+            .collect::<Result<_, ShellError>>()?;
+
         let config = stack.get_config(engine_state);
 
         to_md(
@@ -205,6 +281,7 @@ impl Command for ToMd {
                 pretty,
                 per_element,
                 center,
+                align,
                 escape_md: escape_md || escape_both,
                 escape_html: escape_html || escape_both,
                 list_style,
@@ -232,11 +309,14 @@ fn to_md(
     // Collect input to check if it's a simple list (no records/tables)
     let values: Vec<Value> = input.into_iter().collect();
 
-    // Check if input is a simple list (no records, lists, or tables)
-    // Tables in nushell can be represented as List of Records or List of Lists
-    let is_simple_list = !values
-        .iter()
-        .any(|v| matches!(v, Value::Record { .. } | Value::List { .. }));
+    // Check if input is a simple list (no records or tables), whose nested lists become sub-items
+    let is_simple_list = !values.iter().any(|v| match v {
+        Value::Record { .. } => true,
+        Value::List { vals, .. } => {
+            !vals.is_empty() && vals.iter().all(|v| matches!(v, Value::Record { .. }))
+        }
+        _ => false,
+    });
 
     // For simple lists, use list_style formatting
     if is_simple_list {
@@ -276,6 +356,7 @@ fn to_md(
                                     val.into_pipeline_data(),
                                     options.pretty,
                                     &options.center,
+                                    &options.align,
                                     options.escape_md,
                                     options.escape_html,
                                     config
@@ -290,6 +371,7 @@ fn to_md(
                                     val,
                                     options.pretty,
                                     &options.center,
+                                    &options.align,
                                     options.escape_md,
                                     options.escape_html,
                                     config,
@@ -302,6 +384,7 @@ fn to_md(
                                         val,
                                         options.pretty,
                                         &options.center,
+                                        &options.align,
                                         options.escape_md,
                                         options.escape_html,
                                         config
@@ -335,6 +418,7 @@ fn to_md(
             grouped_input,
             options.pretty,
             &options.center,
+            &options.align,
             options.escape_md,
             options.escape_html,
             config,
@@ -353,13 +437,93 @@ fn format_list_item(
     escape_html: bool,
     config: &Config,
 ) -> String {
-    let value_string = input.to_expanded_string("|", config);
-    let escaped = escape_value(value_string, escape_md, escape_html, false);
+    format_list_entry(
+        None,
+        input,
+        index,
+        list_style,
+        escape_md,
+        escape_html,
+        config,
+    )
+}
 
-    match list_style {
-        ListStyle::Ordered => format!("{}. {}\n", index + 1, escaped),
-        ListStyle::Unordered => format!("* {}\n", escaped),
-        ListStyle::None => format!("{}\n", escaped),
+/// Formats a list item, with a label for the fields of records, and its nested lists and
+/// records as sub-items indented under it
+fn format_list_entry(
+    label: Option<String>,
+    input: Value,
+    index: usize,
+    list_style: ListStyle,
+    escape_md: bool,
+    escape_html: bool,
+    config: &Config,
+) -> String {
+    let marker = match list_style {
+        ListStyle::Ordered => format!("{}. ", index + 1),
+        ListStyle::Unordered => String::from("* "),
+        ListStyle::None => String::new(),
+    };
+    let label = label.map(|label| escape_value(label, escape_md, escape_html, false));
+
+    let children: Vec<(Option<String>, Value)> = match input {
+        Value::List { vals, .. } if !vals.is_empty() => {
+            vals.into_owned().into_iter().map(|v| (None, v)).collect()
+        }
+        Value::Record { val, .. } if !val.is_empty() => val
+            .into_owned()
+            .into_iter()
+            .map(|(k, v)| (Some(k), v))
+            .collect(),
+        input => {
+            let value_string = input.to_expanded_string("|", config);
+            let escaped = escape_value(value_string, escape_md, escape_html, false);
+            return match label {
+                Some(label) => format!("{marker}{label}: {escaped}\n"),
+                None => format!("{marker}{escaped}\n"),
+            };
+        }
+    };
+
+    // Sub-items line up with the text of their parent item
+    let indent = " ".repeat(marker.len());
+    let sub_items = children
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, value))| {
+            format_list_entry(label, value, i, list_style, escape_md, escape_html, config)
+        })
+        .collect::<String>()
+        .lines()
+        .map(|line| format!("{indent}{line}\n"))
+        .collect::<String>();
+
+    match label {
+        Some(label) => format!("{marker}{label}:\n{sub_items}"),
+        // An item can start with a nested list, like "* * a"
+        None => format!("{marker}{}", &sub_items[indent.len()..]),
+    }
+}
+
+/// Formats a table cell, with nested lists and records as inline code so they stay in their cell
+fn table_cell(value: &Value, escape_md: bool, escape_html: bool, config: &Config) -> String {
+    let value_string = value.to_expanded_string(", ", config);
+    match value {
+        Value::List { .. } | Value::Record { .. } => inline_code(&value_string),
+        _ => escape_value(value_string, escape_md, escape_html, true),
+    }
+}
+
+/// Wraps text in a code span, with a fence longer than any run of backticks in it
+fn inline_code(text: &str) -> String {
+    // Pipes still end table cells inside code spans, and line breaks would end the row
+    let text = text.replace('|', "\\|").replace(['\r', '\n'], " ");
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{fence} {text} {fence}")
+    } else {
+        format!("{fence}{text}{fence}")
     }
 }
 
@@ -403,6 +567,7 @@ fn fragment(
     input: Value,
     pretty: bool,
     center: &Option<Vec<CellPath>>,
+    align: &[(String, Alignment)],
     escape_md: bool,
     escape_html: bool,
     config: &Config,
@@ -430,6 +595,7 @@ fn fragment(
                     input.into_pipeline_data(),
                     pretty,
                     center,
+                    align,
                     escape_md,
                     escape_html,
                     config,
@@ -470,6 +636,7 @@ fn table(
     input: PipelineData,
     pretty: bool,
     center: &Option<Vec<CellPath>>,
+    align: &[(String, Alignment)],
     escape_md: bool,
     escape_html: bool,
     config: &Config,
@@ -508,20 +675,10 @@ fn table(
         match row.to_owned() {
             Value::Record { val: row, .. } => {
                 for i in 0..headers.len() {
-                    let value_string = row
-                        .get(&headers[i])
-                        .cloned()
-                        .unwrap_or_else(|| Value::nothing(span))
-                        .to_expanded_string(", ", config);
-                    let escaped_string = escape_markdown_characters(
-                        if escape_html {
-                            v_htmlescape::escape_fmt(&value_string).to_string()
-                        } else {
-                            value_string
-                        },
-                        escape_md,
-                        true,
-                    );
+                    let escaped_string = match row.get(&headers[i]) {
+                        Some(value) => table_cell(value, escape_md, escape_html, config),
+                        None => table_cell(&Value::nothing(span), escape_md, escape_html, config),
+                    };
 
                     let new_column_width = escaped_string.len();
                     escaped_row.push(escaped_string);
@@ -555,6 +712,7 @@ fn table(
             &column_widths,
             pretty,
             center,
+            align,
         )
         .trim()
         .to_string()
@@ -600,10 +758,11 @@ fn get_output_string(
     column_widths: &[usize],
     pretty: bool,
     center: &Option<Vec<CellPath>>,
+    align: &[(String, Alignment)],
 ) -> String {
     let mut output_string = String::new();
 
-    let mut to_center: HashSet<String> = HashSet::new();
+    let mut alignments: HashMap<&str, Alignment> = HashMap::new();
     if let Some(center_vec) = center.as_ref() {
         for cell_path in center_vec {
             if let Some(PathMember::String { val, .. }) = cell_path
@@ -611,10 +770,18 @@ fn get_output_string(
                 .iter()
                 .find(|member| matches!(member, PathMember::String { .. }))
             {
-                to_center.insert(val.clone());
+                alignments.insert(val, Alignment::Center);
             }
         }
     }
+    for (column, alignment) in align {
+        alignments.insert(column, *alignment);
+    }
+    let alignment = |i: usize| {
+        headers
+            .get(i)
+            .and_then(|header| alignments.get(header.as_str()).copied())
+    };
 
     if !headers.is_empty() {
         output_string.push('|');
@@ -622,19 +789,11 @@ fn get_output_string(
         for i in 0..headers.len() {
             output_string.push(' ');
             if pretty {
-                if center.is_some() && to_center.contains(&headers[i]) {
-                    output_string.push_str(&get_centered_string(
-                        headers[i].clone(),
-                        column_widths[i],
-                        ' ',
-                    ));
-                } else {
-                    output_string.push_str(&get_padded_string(
-                        headers[i].clone(),
-                        column_widths[i],
-                        ' ',
-                    ));
-                }
+                output_string.push_str(&get_aligned_string(
+                    headers[i].clone(),
+                    column_widths[i],
+                    alignment(i),
+                ));
             } else {
                 output_string.push_str(&headers[i]);
             }
@@ -645,21 +804,23 @@ fn get_output_string(
         output_string.push_str("\n|");
 
         for i in 0..headers.len() {
-            let centered_column = center.is_some() && to_center.contains(&headers[i]);
-            let border_char = if centered_column { ':' } else { ' ' };
+            let (left_char, right_char) = match alignment(i) {
+                Some(Alignment::Left) => (':', ' '),
+                Some(Alignment::Center) => (':', ':'),
+                Some(Alignment::Right) => (' ', ':'),
+                None => (' ', ' '),
+            };
+            output_string.push(left_char);
             if pretty {
-                output_string.push(border_char);
                 output_string.push_str(&get_padded_string(
                     String::from("-"),
                     column_widths[i],
                     '-',
                 ));
-                output_string.push(border_char);
-            } else if centered_column {
-                output_string.push_str(":---:");
             } else {
-                output_string.push_str(" --- ");
+                output_string.push_str("---");
             }
+            output_string.push(right_char);
 
             output_string.push('|');
         }
@@ -678,19 +839,11 @@ fn get_output_string(
             }
 
             if pretty && column_widths.get(i).is_some() {
-                if center.is_some() && to_center.contains(&headers[i]) {
-                    output_string.push_str(&get_centered_string(
-                        row[i].clone(),
-                        column_widths[i],
-                        ' ',
-                    ));
-                } else {
-                    output_string.push_str(&get_padded_string(
-                        row[i].clone(),
-                        column_widths[i],
-                        ' ',
-                    ));
-                }
+                output_string.push_str(&get_aligned_string(
+                    row[i].clone(),
+                    column_widths[i],
+                    alignment(i),
+                ));
             } else {
                 output_string.push_str(&row[i]);
             }
@@ -706,6 +859,18 @@ fn get_output_string(
     output_string
 }
 
+fn get_aligned_string(text: String, desired_length: usize, alignment: Option<Alignment>) -> String {
+    match alignment {
+        Some(Alignment::Center) => get_centered_string(text, desired_length, ' '),
+        Some(Alignment::Right) => format!(
+            "{}{}",
+            " ".repeat(desired_length.saturating_sub(text.len())),
+            text
+        ),
+        Some(Alignment::Left) | None => get_padded_string(text, desired_length, ' '),
+    }
+}
+
 fn get_centered_string(text: String, desired_length: usize, padding_character: char) -> String {
     let total_padding = if text.len() > desired_length {
         0
@@ -769,7 +934,7 @@ mod tests {
         });
 
         assert_eq!(
-            fragment(value, false, &None, false, false, &Config::default()),
+            fragment(value, false, &None, &[], false, false, &Config::default()),
             "# Ecuador\n"
         );
     }
@@ -781,7 +946,7 @@ mod tests {
         });
 
         assert_eq!(
-            fragment(value, false, &None, false, false, &Config::default()),
+            fragment(value, false, &None, &[], false, false, &Config::default()),
             "## Ecuador\n"
         );
     }
@@ -793,7 +958,7 @@ mod tests {
         });
 
         assert_eq!(
-            fragment(value, false, &None, false, false, &Config::default()),
+            fragment(value, false, &None, &[], false, false, &Config::default()),
             "### Ecuador\n"
         );
     }
@@ -805,7 +970,7 @@ mod tests {
         });
 
         assert_eq!(
-            fragment(value, false, &None, false, false, &Config::default()),
+            fragment(value, false, &None, &[], false, false, &Config::default()),
             "> Ecuador\n"
         );
     }
@@ -829,6 +994,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 false,
                 &None,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -847,6 +1013,7 @@ mod tests {
                 value.into_pipeline_data(),
                 true,
                 &None,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -879,6 +1046,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 false,
                 &None,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -914,6 +1082,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 false,
                 &None,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -964,6 +1133,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 true,
                 &center,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -983,6 +1153,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 false,
                 &center,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -1021,6 +1192,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 true,
                 &center,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -1078,6 +1250,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 true,
                 &center,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -1127,6 +1300,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 true,
                 &center,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -1175,6 +1349,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 true,
                 &center,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -1242,6 +1417,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 false,
                 &None,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -1260,6 +1436,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 false,
                 &None,
+                &[],
                 true,
                 false,
                 &Config::default()
@@ -1278,6 +1455,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 true,
                 &None,
+                &[],
                 false,
                 false,
                 &Config::default()
@@ -1296,6 +1474,7 @@ mod tests {
                 value.into_pipeline_data(),
                 true,
                 &None,
+                &[],
                 true,
                 false,
                 &Config::default()
@@ -1322,6 +1501,7 @@ mod tests {
                 value.clone().into_pipeline_data(),
                 false,
                 &None,
+                &[],
                 false,
                 true,
                 &Config::default()
@@ -1338,6 +1518,7 @@ mod tests {
                 value.into_pipeline_data(),
                 true,
                 &None,
+                &[],
                 false,
                 true,
                 &Config::default()
@@ -1364,6 +1545,7 @@ mod tests {
                 pretty: false,
                 per_element: false,
                 center: None,
+                align: vec![],
                 escape_md: false,
                 escape_html: false,
                 list_style: ListStyle::Ordered,
@@ -1394,6 +1576,7 @@ mod tests {
                 pretty: false,
                 per_element: false,
                 center: None,
+                align: vec![],
                 escape_md: false,
                 escape_html: false,
                 list_style: ListStyle::Unordered,
@@ -1423,6 +1606,7 @@ mod tests {
                 pretty: false,
                 per_element: false,
                 center: None,
+                align: vec![],
                 escape_md: true,
                 escape_html: false,
                 list_style: ListStyle::Unordered,
@@ -1453,6 +1637,7 @@ mod tests {
                 pretty: false,
                 per_element: false,
                 center: None,
+                align: vec![],
                 escape_md: false,
                 escape_html: false,
                 list_style: ListStyle::None,
@@ -1479,6 +1664,7 @@ mod tests {
                 pretty: false,
                 per_element: false,
                 center: None,
+                align: vec![],
                 escape_md: false,
                 escape_html: false,
                 list_style: ListStyle::Unordered,
@@ -1495,6 +1681,97 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn test_align_columns() {
+        let value = Value::test_list(vec![Value::test_record(record! {
+            "foo" => Value::test_string("1"),
+            "bar" => Value::test_string("2"),
+            "baz" => Value::test_string("3"),
+        })]);
+
+        let center = Some(vec![CellPath {
+            members: vec![PathMember::test_string("baz", false, Casing::Sensitive)],
+        }]);
+        let align = [
+            ("foo".to_string(), Alignment::Left),
+            ("bar".to_string(), Alignment::Right),
+        ];
+
+        assert_eq!(
+            table(
+                value.into_pipeline_data(),
+                false,
+                &center,
+                &align,
+                false,
+                false,
+                &Config::default()
+            ),
+            one("
+            | foo | bar | baz |
+            |:--- | ---:|:---:|
+            | 1 | 2 | 3 |
+            ")
+        );
+    }
+
+    #[test]
+    fn test_nested_cells_as_inline_code() {
+        let value = Value::test_record(record! {
+            "list" => Value::test_list(vec![Value::test_string("a|b")]),
+            "record" => Value::test_record(record! {
+                "code" => Value::test_string("`x`"),
+            }),
+        });
+
+        assert_eq!(
+            table(
+                value.into_pipeline_data(),
+                false,
+                &None,
+                &[],
+                true,
+                false,
+                &Config::default()
+            ),
+            one("
+            | list | record |
+            | --- | --- |
+            | `[a\\|b]` | ``{code: `x`}`` |
+            ")
+        );
+    }
+
+    #[test]
+    fn test_nested_list_items() {
+        let value = Value::test_list(vec![
+            Value::test_string("a"),
+            Value::test_list(vec![Value::test_string("b"), Value::test_string("c")]),
+        ]);
+
+        let result = to_md(
+            value.into_pipeline_data(),
+            ToMdOptions {
+                pretty: false,
+                per_element: false,
+                center: None,
+                align: vec![],
+                escape_md: false,
+                escape_html: false,
+                list_style: ListStyle::Ordered,
+            },
+            &Config::default(),
+            Span::test_data(),
+        )
+        .unwrap()
+        .into_value(Span::test_data())
+        .unwrap()
+        .into_string()
+        .unwrap();
+
+        assert_eq!(result, "1. a\n2. 1. b\n   2. c");
+    }
+
     #[test]
     fn test_mixed_input_ordered() {
         // Test that list numbering is continuous even with h1/tables mixed in
@@ -1512,6 +1789,7 @@ mod tests {
                 pretty: false,
                 per_element: true,
                 center: None,
+                align: vec![],
                 escape_md: false,
                 escape_html: false,
                 list_style: ListStyle::Ordered,
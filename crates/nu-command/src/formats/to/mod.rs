@@ -1,12 +1,16 @@
+mod cbor;
 mod command;
 mod csv;
 mod delimited;
+mod edn;
 mod json;
 mod kdl;
+mod logfmt;
 mod md;
 mod msgpack;
 mod msgpackz;
 mod nuon;
+mod plist;
 mod text;
 mod toml;
 mod tsv;
@@ -15,13 +19,17 @@ mod yaml;
 
 pub use self::csv::ToCsv;
 pub use self::toml::ToToml;
+pub use cbor::ToCbor;
 pub use command::To;
+pub use edn::ToEdn;
 pub use json::ToJson;
 pub use kdl::ToKdl;
+pub use logfmt::ToLogfmt;
 pub use md::ToMd;
 pub use msgpack::ToMsgpack;
 pub use msgpackz::ToMsgpackz;
 pub use nuon::ToNuon;
+pub use plist::ToPlist;
 pub use text::ToText;
 pub use tsv::ToTsv;
 pub use xml::ToXml;
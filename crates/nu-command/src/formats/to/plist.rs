@@ -0,0 +1,123 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+use plist::Value as PlistValue;
+use std::time::SystemTime;
+
+#[derive(Clone)]
+pub struct ToPlist;
+
+impl Command for ToPlist {
+    fn name(&self) -> &str {
+        "to plist"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("to plist")
+            .input_output_types(vec![(Type::Any, Type::String), (Type::Any, Type::Binary)])
+            .switch("binary", "Output a binary plist.", Some('b'))
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Convert a value into an XML or binary property list (.plist)."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Records become dictionaries and lists become arrays. Datetimes become dates, binary \
+becomes data, and filesizes become integers. Plists have no null, so nothing can't be converted."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["property list", "macos", "apple", "defaults"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Outputs an XML plist representing the contents of this record.",
+                example: "{tilesize: 48} | to plist",
+                result: None,
+            },
+            Example {
+                description: "Change a setting in a binary plist file.",
+                example: "open --raw settings.plist | from plist | update tilesize 64 | to plist --binary | save --force settings.plist",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        mut input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let binary = call.has_flag(engine_state, stack, "binary")?;
+        let metadata = input
+            .take_metadata()
+            .unwrap_or_default()
+            .with_content_type(Some("application/x-plist".into()));
+        let value = input.into_value(head)?;
+        let plist = convert_nu_value(&value)?;
+
+        let write_error =
+            |err: plist::Error| GenericError::new("Could not write plist", err.to_string(), head);
+        let mut out = vec![];
+        let output = if binary {
+            plist::to_writer_binary(&mut out, &plist).map_err(write_error)?;
+            Value::binary(out, head)
+        } else {
+            plist::to_writer_xml(&mut out, &plist).map_err(write_error)?;
+            Value::string(String::from_utf8_lossy(&out), head)
+        };
+        Ok(output.into_pipeline_data_with_metadata(Some(metadata)))
+    }
+}
+
+fn convert_nu_value(value: &Value) -> Result<PlistValue, ShellError> {
+    Ok(match value {
+        Value::Record { val, .. } => PlistValue::Dictionary(
+            val.iter()
+                .map(|(key, value)| Ok((key.clone(), convert_nu_value(value)?)))
+                .collect::<Result<_, ShellError>>()?,
+        ),
+        Value::List { vals, .. } => PlistValue::Array(
+            vals.iter()
+                .map(convert_nu_value)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::String { val, .. } | Value::Glob { val, .. } => PlistValue::String(val.clone()),
+        Value::Bool { val, .. } => PlistValue::Boolean(*val),
+        Value::Int { val, .. } => PlistValue::Integer((*val).into()),
+        Value::Filesize { val, .. } => PlistValue::Integer(val.get().into()),
+        Value::Float { val, .. } => PlistValue::Real(*val),
+        Value::Date { val, .. } => PlistValue::Date(SystemTime::from(*val).into()),
+        Value::Binary { val, .. } => PlistValue::Data(val.clone()),
+        Value::Error { error, .. } => return Err(*error.clone()),
+        other => {
+            return Err(ShellError::CantConvert {
+                to_type: "plist".into(),
+                from_type: other.get_type().to_string(),
+                span: other.span(),
+                help: None,
+            });
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ToPlist)
+    }
+
+    #[test]
+    fn rejects_nothing() {
+        assert!(convert_nu_value(&Value::test_nothing()).is_err());
+    }
+}
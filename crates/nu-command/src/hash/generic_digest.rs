@@ -35,7 +35,7 @@ impl CmdArgument for Arguments {
     }
 }
 
-fn hex_encode(bytes: &[u8]) -> String {
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
     let mut s = String::with_capacity(bytes.len() * 2);
     for &b in bytes {
         let _ = write!(s, "{b:02x}");
@@ -3,6 +3,7 @@ mod hash_;
 mod md5;
 mod sha256;
 
+pub(crate) use self::generic_digest::hex_encode;
 pub use self::hash_::Hash;
 pub use self::md5::HashMd5;
 pub use self::sha256::HashSha256;
@@ -21,7 +21,7 @@ impl Command for Help {
             .named(
                 "find",
                 SyntaxShape::String,
-                "String to find in command names, descriptions, and search terms.",
+                "Words to search for in command names, descriptions, parameters and examples.",
                 Some('f'),
             )
             .category(Category::Core)
@@ -87,7 +87,7 @@ Here are some tips to help you get started.
   * help -h or help help - show available `help` subcommands and examples
   * help commands - list all available commands
   * help <name> - display help about a particular command, alias, or module
-  * help --find <text to search> - search through all help commands table
+  * help --find <words to search> - search commands by what they do
 
 Nushell works on the idea of a "pipeline". Pipelines are commands connected with the '|' character.
 Each stage in the pipeline works together to load, parse, and display information to you.
@@ -154,6 +154,11 @@ You can also learn more at https://www.nushell.sh/book/"#;
                 example: "help --find char",
                 result: None,
             },
+            Example {
+                description: "find commands by what they do, ranked by relevance.",
+                example: "help --find 'merge two tables'",
+                result: None,
+            },
         ]
     }
 }
@@ -1,4 +1,4 @@
-use crate::filters::find_internal;
+use crate::help::help_search::{Highlight, SearchIndex};
 use nu_engine::{command_prelude::*, get_full_help};
 use nu_protocol::DeclId;
 
@@ -25,7 +25,7 @@ impl Command for HelpCommands {
             .named(
                 "find",
                 SyntaxShape::String,
-                "String to find in command names, descriptions, and search terms.",
+                "Words to search for in command names, descriptions, parameters and examples.",
                 Some('f'),
             )
             .input_output_types(vec![(Type::Nothing, Type::table())])
@@ -53,16 +53,7 @@ pub fn help_commands(
     let rest: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
 
     if let Some(f) = find {
-        let all_cmds_vec = build_help_commands(engine_state, head);
-        return find_internal(
-            all_cmds_vec,
-            engine_state,
-            stack,
-            &f.item,
-            &["name", "description", "search_terms"],
-            true,
-            head,
-        );
+        return Ok(find_commands(engine_state, stack, &f.item, head));
     }
 
     if rest.is_empty() {
@@ -147,125 +138,158 @@ fn find_decl_with_alias_resolution(engine_state: &EngineState, name: &[u8]) -> O
     None
 }
 
+/// The commands matching `query`, ranked by relevance, with a snippet of the text that matched.
+fn find_commands(
+    engine_state: &EngineState,
+    stack: &Stack,
+    query: &str,
+    span: Span,
+) -> PipelineData {
+    let highlight = Highlight::from_config(engine_state, stack, span);
+    let hits = SearchIndex::new(engine_state).search(query, highlight.as_ref());
+
+    let rows = hits
+        .into_iter()
+        .map(|hit| {
+            let mut record = help_command_record(engine_state, hit.name, hit.decl_id, span);
+            record.insert("description", Value::string(hit.description, span));
+            record.push("matched", Value::string(hit.field, span));
+            record.push("snippet", Value::string(hit.snippet, span));
+            record.push("score", Value::float(hit.score, span));
+            Value::record(record, span)
+        })
+        .collect();
+
+    Value::list(rows, span).into_pipeline_data()
+}
+
 fn build_help_commands(engine_state: &EngineState, span: Span) -> PipelineData {
     let commands = engine_state.get_decls_sorted(false);
     let mut found_cmds_vec = Vec::new();
 
     for (decl_name_bytes, decl_id) in commands {
-        let decl = engine_state.get_decl(decl_id);
-        let sig = decl.signature().update_from_command(decl);
-
         // Use the overlay-visible name (decl_name_bytes) as the help `name` so module-qualified
         // names (e.g. "clip prefix") are shown instead of the bare signature name.
         let key = String::from_utf8_lossy(&decl_name_bytes).to_string();
-        let description = sig.description;
-        let search_terms = sig.search_terms;
-
-        let command_type = decl.command_type().to_string();
-
-        // Build table of parameters
-        let param_table = {
-            let mut vals = vec![];
-
-            for required_param in &sig.required_positional {
-                vals.push(Value::record(
-                    record! {
-                        "name" => Value::string(&required_param.name, span),
-                        "type" => Value::string(required_param.shape.to_string(), span),
-                        "required" => Value::bool(true, span),
-                        "description" => Value::string(&required_param.desc, span),
-                    },
-                    span,
-                ));
-            }
+        let record = help_command_record(engine_state, key, decl_id, span);
+        found_cmds_vec.push(Value::record(record, span));
+    }
 
-            for optional_param in &sig.optional_positional {
-                vals.push(Value::record(
-                    record! {
-                        "name" => Value::string(&optional_param.name, span),
-                        "type" => Value::string(optional_param.shape.to_string(), span),
-                        "required" => Value::bool(false, span),
-                        "description" => Value::string(&optional_param.desc, span),
-                    },
-                    span,
-                ));
-            }
+    Value::list(found_cmds_vec, span).into_pipeline_data()
+}
 
-            if let Some(rest_positional) = &sig.rest_positional {
-                vals.push(Value::record(
-                    record! {
-                        "name" => Value::string(format!("...{}", rest_positional.name), span),
-                        "type" => Value::string(rest_positional.shape.to_string(), span),
-                        "required" => Value::bool(false, span),
-                        "description" => Value::string(&rest_positional.desc, span),
-                    },
-                    span,
-                ));
-            }
+fn help_command_record(
+    engine_state: &EngineState,
+    key: String,
+    decl_id: DeclId,
+    span: Span,
+) -> Record {
+    let decl = engine_state.get_decl(decl_id);
+    let sig = decl.signature().update_from_command(decl);
+
+    let description = sig.description;
+    let search_terms = sig.search_terms;
+
+    let command_type = decl.command_type().to_string();
+
+    // Build table of parameters
+    let param_table = {
+        let mut vals = vec![];
+
+        for required_param in &sig.required_positional {
+            vals.push(Value::record(
+                record! {
+                    "name" => Value::string(&required_param.name, span),
+                    "type" => Value::string(required_param.shape.to_string(), span),
+                    "required" => Value::bool(true, span),
+                    "description" => Value::string(&required_param.desc, span),
+                },
+                span,
+            ));
+        }
 
-            for named_param in &sig.named {
-                let name = if let Some(short) = named_param.short {
-                    if named_param.long.is_empty() {
-                        format!("-{short}")
-                    } else {
-                        format!("--{}(-{})", named_param.long, short)
-                    }
-                } else {
-                    format!("--{}", named_param.long)
-                };
+        for optional_param in &sig.optional_positional {
+            vals.push(Value::record(
+                record! {
+                    "name" => Value::string(&optional_param.name, span),
+                    "type" => Value::string(optional_param.shape.to_string(), span),
+                    "required" => Value::bool(false, span),
+                    "description" => Value::string(&optional_param.desc, span),
+                },
+                span,
+            ));
+        }
 
-                let typ = if let Some(arg) = &named_param.arg {
-                    arg.to_string()
-                } else {
-                    "switch".to_string()
-                };
-
-                vals.push(Value::record(
-                    record! {
-                        "name" => Value::string(name, span),
-                        "type" => Value::string(typ, span),
-                        "required" => Value::bool(named_param.required, span),
-                        "description" => Value::string(&named_param.desc, span),
-                    },
-                    span,
-                ));
-            }
+        if let Some(rest_positional) = &sig.rest_positional {
+            vals.push(Value::record(
+                record! {
+                    "name" => Value::string(format!("...{}", rest_positional.name), span),
+                    "type" => Value::string(rest_positional.shape.to_string(), span),
+                    "required" => Value::bool(false, span),
+                    "description" => Value::string(&rest_positional.desc, span),
+                },
+                span,
+            ));
+        }
 
-            Value::list(vals, span)
-        };
-
-        // Build the signature input/output table
-        let input_output_table = {
-            let mut vals = vec![];
-
-            for (input_type, output_type) in sig.input_output_types {
-                vals.push(Value::record(
-                    record! {
-                        "input" => Value::string(input_type.to_string(), span),
-                        "output" => Value::string(output_type.to_string(), span),
-                    },
-                    span,
-                ));
-            }
+        for named_param in &sig.named {
+            let name = if let Some(short) = named_param.short {
+                if named_param.long.is_empty() {
+                    format!("-{short}")
+                } else {
+                    format!("--{}(-{})", named_param.long, short)
+                }
+            } else {
+                format!("--{}", named_param.long)
+            };
 
-            Value::list(vals, span)
-        };
+            let typ = if let Some(arg) = &named_param.arg {
+                arg.to_string()
+            } else {
+                "switch".to_string()
+            };
+
+            vals.push(Value::record(
+                record! {
+                    "name" => Value::string(name, span),
+                    "type" => Value::string(typ, span),
+                    "required" => Value::bool(named_param.required, span),
+                    "description" => Value::string(&named_param.desc, span),
+                },
+                span,
+            ));
+        }
 
-        let record = record! {
-            "name" => Value::string(key, span),
-            "category" => Value::string(sig.category.to_string(), span),
-            "command_type" => Value::string(command_type, span),
-            "description" => Value::string(description, span),
-            "params" => param_table,
-            "input_output" => input_output_table,
-            "search_terms" => Value::string(search_terms.join(", "), span),
-            "is_const" => Value::bool(decl.is_const(), span),
-        };
+        Value::list(vals, span)
+    };
+
+    // Build the signature input/output table
+    let input_output_table = {
+        let mut vals = vec![];
+
+        for (input_type, output_type) in sig.input_output_types {
+            vals.push(Value::record(
+                record! {
+                    "input" => Value::string(input_type.to_string(), span),
+                    "output" => Value::string(output_type.to_string(), span),
+                },
+                span,
+            ));
+        }
 
-        found_cmds_vec.push(Value::record(record, span));
+        Value::list(vals, span)
+    };
+
+    record! {
+        "name" => Value::string(key, span),
+        "category" => Value::string(sig.category.to_string(), span),
+        "command_type" => Value::string(command_type, span),
+        "description" => Value::string(description, span),
+        "params" => param_table,
+        "input_output" => input_output_table,
+        "search_terms" => Value::string(search_terms.join(", "), span),
+        "is_const" => Value::bool(decl.is_const(), span),
     }
-
-    Value::list(found_cmds_vec, span).into_pipeline_data()
 }
 
 #[cfg(test)]
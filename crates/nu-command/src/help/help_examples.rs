@@ -0,0 +1,286 @@
+use itertools::Itertools;
+use nu_engine::{command_prelude::*, get_eval_block};
+use nu_parser::parse;
+use nu_protocol::{eval_const::eval_constant, shell_error::generic::GenericError};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct HelpExamples;
+
+impl Command for HelpExamples {
+    fn name(&self) -> &str {
+        "help examples"
+    }
+
+    fn description(&self) -> &str {
+        "Show the examples of a command, or run one of them."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Commands don't declare sample inputs for their examples, so the `input` column is read from the example code: it is the constant value an example starts with, like `[1 2]` in `[1 2] | math sum`, and is empty for other examples. Run an example with `--run`, and use `--input` to run it against your own data instead."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("help examples")
+            .category(Category::Core)
+            .rest(
+                "rest",
+                SyntaxShape::String,
+                "The name of the command to show the examples of.",
+            )
+            .named(
+                "run",
+                SyntaxShape::Int,
+                "Index of the example to run.",
+                Some('r'),
+            )
+            .named(
+                "input",
+                SyntaxShape::Any,
+                "Data to run the example against, instead of its sample input.",
+                Some('i'),
+            )
+            .switch("pick", "Pick the example to run from a list.", Some('p'))
+            .input_output_types(vec![(Type::Nothing, Type::Any)])
+            .allow_variants_without_examples(true)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["example", "sample", "try"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Show the examples of a command with their sample inputs.",
+                example: "help examples str join",
+                result: None,
+            },
+            Example {
+                description: "Run the first example of a command.",
+                example: "help examples str join --run 0",
+                result: None,
+            },
+            Example {
+                description: "Run an example against other data.",
+                example: "help examples str join --run 0 --input [a b c]",
+                result: None,
+            },
+            Example {
+                description: "Pick the example to run from a list.",
+                example: "help examples str join --pick",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let rest: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+        let run: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "run")?;
+        let input: Option<Value> = call.get_flag(engine_state, stack, "input")?;
+        let pick = call.has_flag(engine_state, stack, "pick")?;
+
+        if rest.is_empty() {
+            return Err(ShellError::MissingParameter {
+                param_name: "rest".into(),
+                span: head,
+            });
+        }
+
+        let name = rest.iter().map(|r| r.item.as_str()).join(" ");
+        let Some(decl_id) = engine_state.find_decl(name.as_bytes(), &[]) else {
+            return Err(ShellError::CommandNotFound {
+                span: Span::merge_many(rest.iter().map(|s| s.span)),
+            });
+        };
+        let examples = engine_state.get_decl(decl_id).examples();
+
+        let index = match run {
+            Some(run) => match usize::try_from(run.item) {
+                Ok(index) if index < examples.len() => Some(index),
+                _ => {
+                    return Err(ShellError::IncorrectValue {
+                        msg: format!("`{name}` has {} examples", examples.len()),
+                        val_span: run.span,
+                        call_span: head,
+                    });
+                }
+            },
+            None if pick => pick_example(engine_state, stack, &examples, head)?,
+            None => {
+                let rows = examples
+                    .iter()
+                    .map(|example| example_row(engine_state, example, head))
+                    .collect();
+                return Ok(Value::list(rows, head).into_pipeline_data());
+            }
+        };
+
+        match index {
+            Some(index) => run_example(engine_state, stack, examples[index].example, input, head),
+            // Nothing was picked
+            None => Ok(PipelineData::empty()),
+        }
+    }
+}
+
+fn example_row(engine_state: &EngineState, example: &Example<'_>, head: Span) -> Value {
+    let input = split_example(engine_state, example.example).map(|(input, _)| input);
+
+    Value::record(
+        record! {
+            "description" => Value::string(example.description, head),
+            "example" => Value::string(example.example, head),
+            "input" => input.unwrap_or_else(|| Value::nothing(head)),
+            "result" => example.result.clone().unwrap_or_else(|| Value::nothing(head)),
+        },
+        head,
+    )
+}
+
+/// Splits an example like `[1 2] | math sum` into its sample input and the pipeline it is piped
+/// into, when the example is a single pipeline starting with a constant value.
+fn split_example(engine_state: &EngineState, example: &str) -> Option<(Value, String)> {
+    let mut working_set = StateWorkingSet::new(engine_state);
+    let block = parse(&mut working_set, None, example.as_bytes(), false);
+    if !working_set.parse_errors.is_empty() {
+        return None;
+    }
+
+    let [pipeline] = block.pipelines.as_slice() else {
+        return None;
+    };
+    let (first, rest) = pipeline.elements.split_first()?;
+    let span = Span::new(rest.first()?.expr.span.start, rest.last()?.expr.span.end);
+
+    let input = eval_constant(&working_set, &first.expr).ok()?;
+    let pipeline = String::from_utf8_lossy(working_set.get_span_contents(span)).into_owned();
+
+    Some((input, pipeline))
+}
+
+/// Runs an example, against `input` instead of its sample input when given.
+pub(crate) fn run_example(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    example: &str,
+    input: Option<Value>,
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    let (source, input) = match input {
+        Some(input) => {
+            let source = split_example(engine_state, example)
+                .map(|(_, pipeline)| pipeline)
+                .unwrap_or_else(|| example.to_string());
+            (source, input.into_pipeline_data())
+        }
+        None => (example.to_string(), PipelineData::empty()),
+    };
+
+    let mut engine_state = engine_state.clone();
+    let mut working_set = StateWorkingSet::new(&engine_state);
+    let block = parse(&mut working_set, None, source.as_bytes(), false);
+    if let Some(err) = working_set.parse_errors.first() {
+        return Err(ShellError::Generic(GenericError::new(
+            "Failed to parse the example",
+            err.to_string(),
+            head,
+        )));
+    }
+    let delta = working_set.render();
+    engine_state.merge_delta(delta)?;
+
+    // The example runs in a child stack so its definitions don't leak to the caller
+    let mut callee_stack = Stack::with_parent(Arc::new(stack.clone()));
+    let eval_block = get_eval_block(&engine_state);
+    eval_block(&engine_state, &mut callee_stack, &block, input).map(|data| data.body)
+}
+
+/// Lets the user pick an example with `input list`, returning its index.
+#[cfg(feature = "os")]
+fn pick_example(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    examples: &[Example<'_>],
+    head: Span,
+) -> Result<Option<usize>, ShellError> {
+    use nu_protocol::ast;
+
+    let descriptions = examples
+        .iter()
+        .map(|example| Value::string(example.description, head))
+        .collect();
+
+    let mut call = ast::Call::new(head);
+    call.add_named((
+        Spanned {
+            item: "index".to_string(),
+            span: head,
+        },
+        None,
+        None,
+    ));
+
+    let picked = crate::InputList
+        .run(
+            engine_state,
+            stack,
+            &(&call).into(),
+            Value::list(descriptions, head).into_pipeline_data(),
+        )?
+        .into_value(head)?;
+
+    match picked {
+        Value::Int { val, .. } => Ok(usize::try_from(val).ok()),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(not(feature = "os"))]
+fn pick_example(
+    _engine_state: &EngineState,
+    _stack: &mut Stack,
+    _examples: &[Example<'_>],
+    head: Span,
+) -> Result<Option<usize>, ShellError> {
+    Err(ShellError::Generic(
+        GenericError::new(
+            "Can't pick an example",
+            "picking from a list isn't available in this build",
+            head,
+        )
+        .with_help("run an example by its index with `--run`"),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(HelpExamples)
+    }
+
+    #[test]
+    fn splits_constant_input() {
+        let engine_state = EngineState::new();
+        let (input, pipeline) =
+            split_example(&engine_state, "[1 2] | length").expect("example should split");
+        assert_eq!(input.as_list().map(|list| list.len()).ok(), Some(2));
+        assert_eq!(pipeline, "length");
+    }
+
+    #[test]
+    fn keeps_examples_without_input() {
+        let engine_state = EngineState::new();
+        assert!(split_example(&engine_state, "ls").is_none());
+    }
+}
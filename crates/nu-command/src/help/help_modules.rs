@@ -1,5 +1,5 @@
 use crate::filters::find_internal;
-use nu_engine::{command_prelude::*, scope::ScopeData};
+use nu_engine::{command_prelude::*, get_full_help, scope::ScopeData};
 use nu_protocol::DeclId;
 use std::fmt::Write;
 
@@ -18,7 +18,10 @@ impl Command for HelpModules {
     fn extra_description(&self) -> &str {
         "When requesting help for a single module, its commands and aliases will be highlighted if they
 are also available in the current scope. Commands/aliases that were imported under a different name
-(such as with a prefix after `use some-module`) will be highlighted in parentheses."
+(such as with a prefix after `use some-module`) will be highlighted in parentheses.
+
+With `--full`, the full help of each exported command follows, with the examples from its
+`@example` attributes and the `Example:` sections of its doc comments."
     }
 
     fn signature(&self) -> Signature {
@@ -35,6 +38,11 @@ are also available in the current scope. Commands/aliases that were imported und
                 "String to find in module names and descriptions.",
                 Some('f'),
             )
+            .switch(
+                "full",
+                "Also show the full help of each exported command.",
+                None,
+            )
             .input_output_types(vec![(Type::Nothing, Type::table())])
             .allow_variants_without_examples(true)
     }
@@ -51,6 +59,11 @@ are also available in the current scope. Commands/aliases that were imported und
                 example: "help modules my-module",
                 result: None,
             },
+            Example {
+                description: "Show help for a module and all of its commands.",
+                example: "help modules my-module --full",
+                result: None,
+            },
             Example {
                 description: "Search for string in module names and descriptions.",
                 example: "help modules --find my-module",
@@ -78,6 +91,7 @@ pub fn help_modules(
     let head = call.head;
     let find: Option<Spanned<String>> = call.get_flag(engine_state, stack, "find")?;
     let rest: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+    let full = call.has_flag(engine_state, stack, "full")?;
 
     if let Some(f) = find {
         let all_cmds_vec = build_help_modules(engine_state, stack, head);
@@ -122,6 +136,7 @@ pub fn help_modules(
         const RESET: &str = "\x1b[0m"; // reset
 
         let mut long_desc = String::new();
+        let mut commands_help = String::new();
 
         if let Some((desc, extra_desc)) = module_desc {
             long_desc.push_str(&desc);
@@ -174,6 +189,18 @@ pub fn help_modules(
             write!(long_desc, "{G}Exported commands{RESET}:\n  {commands_str}")
                 .expect("writing to a String is infallible");
             long_desc.push_str("\n\n");
+
+            if full {
+                for (name_bytes, id) in &module_commands {
+                    let name = String::from_utf8_lossy(name_bytes);
+                    let help = get_full_help(engine_state.get_decl(*id), engine_state, stack, head);
+                    write!(
+                        commands_help,
+                        "\n\n{G}Command{RESET}: {CB}{name}{RESET}\n\n{help}"
+                    )
+                    .expect("writing to a String is infallible");
+                }
+            }
         }
 
         if !module.decls.is_empty() {
@@ -225,6 +252,7 @@ pub fn help_modules(
             )
             .expect("writing to a String is infallible");
         }
+        long_desc.push_str(commands_help.trim_end());
 
         let config = stack.get_config(engine_state);
         if !config.use_ansi_coloring.get(engine_state) {
@@ -0,0 +1,429 @@
+//! Ranked full-text search over the documentation of commands, used by `help --find`.
+
+use itertools::Itertools;
+use nu_ansi_term::Style;
+use nu_color_config::StyleComputer;
+use nu_engine::command_prelude::*;
+use nu_protocol::DeclId;
+use std::collections::HashMap;
+
+/// Words too common to tell commands apart, ignored in queries.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "how", "i", "in", "into", "is",
+    "it", "of", "on", "or", "the", "to", "with",
+];
+
+/// How quickly repeated matches of a term stop raising the rank of a command, as in BM25.
+const SATURATION: f64 = 4.0;
+
+/// The score of a command whose name or description only contains the query as part of a word,
+/// below that of commands matching whole words.
+const PARTIAL_MATCH_SCORE: f64 = 0.01;
+
+/// About how many bytes of text a snippet shows.
+const SNIPPET_WIDTH: usize = 80;
+
+/// The parts of the documentation of a command that are searched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Name,
+    SearchTerms,
+    Description,
+    ExtraDescription,
+    Parameters,
+    Examples,
+}
+
+impl Field {
+    /// How much a match in this field counts towards the rank of a command.
+    fn weight(self) -> f64 {
+        match self {
+            Field::Name => 8.0,
+            Field::SearchTerms => 6.0,
+            Field::Description => 4.0,
+            Field::ExtraDescription => 2.0,
+            Field::Parameters => 1.5,
+            Field::Examples => 1.0,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Field::Name => "name",
+            Field::SearchTerms => "search terms",
+            Field::Description => "description",
+            Field::ExtraDescription => "extra description",
+            Field::Parameters => "parameters",
+            Field::Examples => "examples",
+        }
+    }
+}
+
+/// The styles of highlighted search results.
+pub(crate) struct Highlight {
+    text: Style,
+    matched: Style,
+}
+
+impl Highlight {
+    /// The `string` and `search_result` styles of the config, unless ANSI coloring is disabled.
+    pub(crate) fn from_config(
+        engine_state: &EngineState,
+        stack: &Stack,
+        span: Span,
+    ) -> Option<Self> {
+        let config = stack.get_config(engine_state);
+        if !config.use_ansi_coloring.get(engine_state) {
+            return None;
+        }
+
+        let style_computer = StyleComputer::from_config(engine_state, stack);
+        let sample = Value::string("search result", span);
+        Some(Self {
+            text: style_computer.compute("string", &sample),
+            matched: style_computer.compute("search_result", &sample),
+        })
+    }
+}
+
+/// A command matching a search.
+pub(crate) struct SearchHit {
+    pub decl_id: DeclId,
+    /// The name of the command in scope.
+    pub name: String,
+    pub score: f64,
+    /// The description, with the matched words highlighted.
+    pub description: String,
+    /// The field that matched the query best.
+    pub field: &'static str,
+    /// An excerpt of that field around the first match, with the matched words highlighted.
+    pub snippet: String,
+}
+
+/// The searchable documentation of a command.
+struct Document {
+    decl_id: DeclId,
+    name: String,
+    fields: Vec<(Field, String)>,
+    /// The number of occurrences of each term, weighted by the fields they are in.
+    terms: HashMap<String, f64>,
+}
+
+impl Document {
+    fn new(name: String, decl_id: DeclId, decl: &dyn Command) -> Self {
+        let sig = decl.signature();
+        let parameters = sig
+            .required_positional
+            .iter()
+            .chain(&sig.optional_positional)
+            .chain(&sig.rest_positional)
+            .map(|param| format!("{}: {}", param.name, param.desc))
+            .chain(
+                sig.named
+                    .iter()
+                    .filter(|flag| flag.long != "help")
+                    .map(|flag| format!("--{}: {}", flag.long, flag.desc)),
+            )
+            .join("; ");
+        let examples = decl
+            .examples()
+            .iter()
+            .map(|example| format!("{}: {}", example.description, example.example))
+            .join("; ");
+
+        let fields = [
+            (Field::Name, name.clone()),
+            (Field::SearchTerms, decl.search_terms().join(", ")),
+            (Field::Description, decl.description().to_string()),
+            (
+                Field::ExtraDescription,
+                decl.extra_description().to_string(),
+            ),
+            (Field::Parameters, parameters),
+            (Field::Examples, examples),
+        ]
+        .into_iter()
+        .map(|(field, text)| (field, text.split_whitespace().join(" ")))
+        .collect::<Vec<_>>();
+
+        let mut terms = HashMap::new();
+        for (field, text) in &fields {
+            for (_, word) in words(text) {
+                *terms.entry(normalize(word)).or_insert(0.0) += field.weight();
+            }
+        }
+
+        Self {
+            decl_id,
+            name,
+            fields,
+            terms,
+        }
+    }
+
+    fn text(&self, field: Field) -> &str {
+        self.fields
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map_or("", |(_, text)| text.as_str())
+    }
+
+    /// The field with the most distinct query terms, the name only when nothing else matches.
+    fn best_field(&self, terms: &[String]) -> Field {
+        self.fields
+            .iter()
+            .filter(|(field, _)| *field != Field::Name)
+            .map(|(field, text)| {
+                let matched = words(text)
+                    .map(|(_, word)| normalize(word))
+                    .filter(|word| terms.contains(word))
+                    .unique()
+                    .count();
+                (*field, matched)
+            })
+            // The first field wins a tie, as the fields are ordered by weight
+            .fold((Field::Name, 0), |best, (field, matched)| {
+                if matched > best.1 {
+                    (field, matched)
+                } else {
+                    best
+                }
+            })
+            .0
+    }
+}
+
+/// A full-text index over the documentation of every command in scope.
+pub(crate) struct SearchIndex {
+    documents: Vec<Document>,
+    /// The number of documents each term appears in.
+    document_frequency: HashMap<String, usize>,
+}
+
+impl SearchIndex {
+    pub(crate) fn new(engine_state: &EngineState) -> Self {
+        let documents = engine_state
+            .get_decls_sorted(false)
+            .into_iter()
+            .map(|(name, decl_id)| {
+                let name = String::from_utf8_lossy(&name).into_owned();
+                Document::new(name, decl_id, engine_state.get_decl(decl_id))
+            })
+            .collect::<Vec<_>>();
+
+        let mut document_frequency = HashMap::new();
+        for document in &documents {
+            for term in document.terms.keys() {
+                *document_frequency.entry(term.clone()).or_insert(0) += 1;
+            }
+        }
+
+        Self {
+            documents,
+            document_frequency,
+        }
+    }
+
+    /// The commands matching the words of `query`, from the most relevant.
+    pub(crate) fn search(&self, query: &str, highlight: Option<&Highlight>) -> Vec<SearchHit> {
+        let terms = query_terms(query);
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let phrase = query.split_whitespace().join(" ").to_lowercase();
+        let total = self.documents.len() as f64;
+
+        let mut hits = self
+            .documents
+            .iter()
+            .filter_map(|document| {
+                let mut score = 0.0;
+                let mut matched = 0;
+                for term in &terms {
+                    let Some(tf) = document.terms.get(term) else {
+                        continue;
+                    };
+                    let df = self.document_frequency.get(term).copied().unwrap_or(1) as f64;
+                    let idf = (1.0 + (total - df + 0.5) / (df + 0.5)).ln();
+                    score += idf * tf * (SATURATION + 1.0) / (tf + SATURATION);
+                    matched += 1;
+                }
+                let name = document.name.to_lowercase();
+                let description = document.text(Field::Description).to_lowercase();
+                if matched > 0 {
+                    // Commands matching every word of the query come first
+                    score *= (matched as f64 / terms.len() as f64).powi(2);
+                } else if name.contains(&phrase) || description.contains(&phrase) {
+                    // Part of a word, like `stor` for `stor open`, still finds the command
+                    score = PARTIAL_MATCH_SCORE;
+                } else {
+                    return None;
+                }
+
+                // And even more so when they contain the query as it was written
+                if name == phrase {
+                    score *= 4.0;
+                } else if name.contains(&phrase) {
+                    score *= 2.0;
+                } else if description.contains(&phrase) {
+                    score *= 1.5;
+                }
+
+                let field = document.best_field(&terms);
+                let text = document.text(match field {
+                    Field::Name => Field::Description,
+                    field => field,
+                });
+                Some(SearchHit {
+                    decl_id: document.decl_id,
+                    name: document.name.clone(),
+                    score,
+                    description: highlight_words(
+                        document.text(Field::Description),
+                        &terms,
+                        highlight,
+                    ),
+                    field: field.name(),
+                    snippet: snippet(text, &terms, highlight),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        hits
+    }
+}
+
+/// The words of a text, with their byte offset.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = None;
+    text.char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+        .filter_map(move |(index, c)| {
+            if c.is_alphanumeric() {
+                start.get_or_insert(index);
+                None
+            } else {
+                start.take().map(|start| (start, &text[start..index]))
+            }
+        })
+}
+
+/// Lowercases a word and strips its plural, so `Tables` matches `table`.
+fn normalize(word: &str) -> String {
+    let word = word.to_lowercase();
+    if let Some(stem) = word.strip_suffix("ies")
+        && stem.len() > 1
+    {
+        return format!("{stem}y");
+    }
+    if let Some(stem) = word.strip_suffix('s')
+        && stem.len() > 2
+        && !stem.ends_with('s')
+    {
+        return stem.to_string();
+    }
+    word
+}
+
+/// The distinct normalized words of a query, without the stop words unless there is nothing else.
+fn query_terms(query: &str) -> Vec<String> {
+    let terms = words(query)
+        .map(|(_, word)| normalize(word))
+        .unique()
+        .collect::<Vec<_>>();
+    let meaningful = terms
+        .iter()
+        .filter(|term| !STOP_WORDS.contains(&term.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if meaningful.is_empty() {
+        terms
+    } else {
+        meaningful
+    }
+}
+
+/// An excerpt of `text` of about [`SNIPPET_WIDTH`] bytes around the first matched word.
+fn snippet(text: &str, terms: &[String], highlight: Option<&Highlight>) -> String {
+    if text.len() <= SNIPPET_WIDTH {
+        return highlight_words(text, terms, highlight);
+    }
+
+    let first_match = words(text)
+        .find(|(_, word)| terms.contains(&normalize(word)))
+        .map_or(0, |(start, _)| start);
+    // Show some context before the match, starting at a word
+    let start = words(text)
+        .map(|(start, _)| start)
+        .take_while(|start| *start + SNIPPET_WIDTH / 4 <= first_match)
+        .last()
+        .unwrap_or(0);
+    let end = words(text)
+        .map(|(start, word)| start + word.len())
+        .find(|end| *end >= start + SNIPPET_WIDTH)
+        .unwrap_or(text.len());
+
+    let mut snippet = highlight_words(&text[start..end], terms, highlight);
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+/// Paints the words of `text` matching the query with the highlight styles.
+fn highlight_words(text: &str, terms: &[String], highlight: Option<&Highlight>) -> String {
+    let Some(highlight) = highlight else {
+        return text.to_string();
+    };
+
+    let mut highlighted = String::new();
+    let mut last_end = 0;
+    for (start, word) in words(text) {
+        if terms.contains(&normalize(word)) {
+            let end = start + word.len();
+            highlighted.push_str(&highlight.text.paint(&text[last_end..start]).to_string());
+            highlighted.push_str(&highlight.matched.paint(word).to_string());
+            last_end = end;
+        }
+    }
+    highlighted.push_str(&highlight.text.paint(&text[last_end..]).to_string());
+    highlighted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_plurals() {
+        assert_eq!(normalize("Tables"), "table");
+        assert_eq!(normalize("entries"), "entry");
+        assert_eq!(normalize("process"), "process");
+    }
+
+    #[test]
+    fn query_terms_skip_stop_words() {
+        assert_eq!(query_terms("merge two tables"), ["merge", "two", "table"]);
+        assert_eq!(query_terms("convert to a string"), ["convert", "string"]);
+        assert_eq!(query_terms("to"), ["to"]);
+    }
+
+    #[test]
+    fn snippet_is_cut_around_the_match() {
+        let text = "word ".repeat(30) + "needle " + &"word ".repeat(30);
+        let snippet = snippet(text.trim_end(), &["needle".into()], None);
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.contains("needle"));
+    }
+}
@@ -2,15 +2,18 @@ mod help_;
 mod help_aliases;
 mod help_commands;
 mod help_escapes;
+mod help_examples;
 mod help_externs;
 mod help_modules;
 mod help_operators;
 mod help_pipe_and_redirect;
+mod help_search;
 
 pub use help_::Help;
 pub use help_aliases::HelpAliases;
 pub use help_commands::HelpCommands;
 pub use help_escapes::HelpEscapes;
+pub use help_examples::HelpExamples;
 pub use help_externs::HelpExterns;
 pub use help_modules::HelpModules;
 pub use help_operators::HelpOperators;
@@ -18,4 +21,5 @@ pub use help_pipe_and_redirect::HelpPipeAndRedirect;
 
 pub(crate) use help_aliases::help_aliases;
 pub(crate) use help_commands::help_commands;
+pub(crate) use help_examples::run_example;
 pub(crate) use help_modules::help_modules;
@@ -24,6 +24,7 @@ mod platform;
 mod progress_bar;
 #[cfg(feature = "rand")]
 mod random;
+mod reference;
 mod removed;
 mod semver;
 mod shells;
@@ -33,6 +34,7 @@ mod stor;
 mod strings;
 #[cfg(feature = "os")]
 mod system;
+mod testing;
 mod viewers;
 
 pub(crate) mod formats;
@@ -61,6 +63,7 @@ pub use path::*;
 pub use platform::*;
 #[cfg(feature = "rand")]
 pub use random::*;
+pub use reference::*;
 pub use removed::*;
 pub use semver::*;
 pub use shells::*;
@@ -70,6 +73,7 @@ pub use stor::*;
 pub use strings::*;
 #[cfg(feature = "os")]
 pub use system::*;
+pub use testing::*;
 pub use viewers::*;
 
 #[cfg(feature = "sqlite")]
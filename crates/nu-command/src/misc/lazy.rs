@@ -0,0 +1,437 @@
+use nu_engine::{ClosureEvalOnce, command_prelude::*};
+use nu_protocol::{
+    CustomValue, ListStream, Signals,
+    ast::{Bits, Boolean, Comparison, Math, Operator, PathMember},
+    casing::Casing,
+    engine::Closure,
+    shell_error::generic::GenericError,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    any::Any,
+    cmp::Ordering,
+    fmt,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread::{self, ThreadId},
+};
+
+#[derive(Clone)]
+pub struct Lazy;
+
+impl Command for Lazy {
+    fn name(&self) -> &str {
+        "lazy"
+    }
+
+    fn description(&self) -> &str {
+        "Create a value that is only computed by a closure once it's used."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The closure runs the first time the value is used: when following a cell path into it, \
+        in an operation, or when it's passed to a command that takes a specific type, like \
+        `str length`. Its result is kept, so the closure runs at most once, even for copies of \
+        the value: `let name = lazy { ... }` is a binding that is computed on first use. \
+        Commands that take any value, like `describe`, get the lazy value itself, so `describe` \
+        reports `lazy` without running the closure. Use `into value` to compute it explicitly."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("lazy")
+            // A lazy value stands for whatever the closure returns, so it can be passed to commands
+            // that take a specific type
+            .input_output_types(vec![(Type::Nothing, Type::Any)])
+            .required(
+                "closure",
+                SyntaxShape::Closure(Some(vec![])),
+                "The closure computing the value.",
+            )
+            .category(Category::Misc)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["thunk", "deferred", "memoize", "cache"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let closure: Closure = call.req(engine_state, stack, 0)?;
+        let lazy = LazyValue::new(Thunk {
+            engine_state: engine_state.clone(),
+            // Only the environment is needed, the closure brings its captures
+            stack: stack.captures_to_stack(vec![]),
+            closure,
+        });
+        Ok(Value::custom(Box::new(lazy), call.head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Compute a value only if it's needed.",
+                example: "let size = lazy { ls | length }; if false { $size + 1 }",
+                result: None,
+            },
+            Example {
+                description: "Follow a cell path into a lazy value.",
+                example: "let user = lazy { {name: nushell} }; $user.name",
+                result: Some(Value::test_string("nushell")),
+            },
+            Example {
+                description: "Bind a value that is computed once, on first use.",
+                example: "let id = lazy { random uuid }; $id == $id",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                description: "Pass a lazy value to a command that takes a specific type.",
+                example: "lazy { 'nushell' } | str length",
+                result: Some(Value::test_int(7)),
+            },
+            Example {
+                description: "Use a lazy value in an operation.",
+                example: "(lazy { 1 + 2 }) * 2",
+                result: Some(Value::test_int(6)),
+            },
+            Example {
+                description: "Iterate over a lazy list.",
+                example: "lazy { [1 2 3] } | each { $in * 2 }",
+                result: Some(Value::test_list(vec![
+                    Value::test_int(2),
+                    Value::test_int(4),
+                    Value::test_int(6),
+                ])),
+            },
+            Example {
+                description: "Describe a lazy value without computing it.",
+                example: "lazy { sleep 1day } | describe",
+                result: Some(Value::test_string("lazy")),
+            },
+        ]
+    }
+}
+
+/// What's needed to run the closure of a lazy value.
+struct Thunk {
+    engine_state: EngineState,
+    stack: Stack,
+    closure: Closure,
+}
+
+impl Thunk {
+    fn run(self, span: Span) -> Result<Value, ShellError> {
+        ClosureEvalOnce::new(&self.engine_state, &self.stack, self.closure)
+            .run_with_input(PipelineData::empty())?
+            .into_value(span)
+    }
+}
+
+enum State {
+    Pending(Thunk),
+    /// The closure is running on the given thread.
+    Running(ThreadId),
+    Done(Result<Value, ShellError>),
+}
+
+struct Shared {
+    state: Mutex<State>,
+    done: Condvar,
+}
+
+/// A value computed by a closure when it's first used, with the result shared by all copies.
+#[derive(Clone)]
+pub struct LazyValue(Arc<Shared>);
+
+impl LazyValue {
+    fn new(thunk: Thunk) -> Self {
+        Self::with_state(State::Pending(thunk))
+    }
+
+    fn with_state(state: State) -> Self {
+        Self(Arc::new(Shared {
+            state: Mutex::new(state),
+            done: Condvar::new(),
+        }))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.0.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The value, computing it if no copy did yet.
+    fn force(&self, span: Span) -> Result<Value, ShellError> {
+        let current = thread::current().id();
+        let mut state = self.lock();
+        let thunk = loop {
+            match std::mem::replace(&mut *state, State::Running(current)) {
+                State::Pending(thunk) => break thunk,
+                State::Done(result) => {
+                    *state = State::Done(result.clone());
+                    return result;
+                }
+                State::Running(thread) => {
+                    *state = State::Running(thread);
+                    if thread == current {
+                        return Err(ShellError::Generic(
+                            GenericError::new(
+                                "Lazy value depends on itself",
+                                "computing this value needs the value itself",
+                                span,
+                            )
+                            .with_help("the closure of `lazy` uses the value it computes"),
+                        ));
+                    }
+                    // Another thread is computing it
+                    state = self.0.done.wait(state).unwrap_or_else(|e| e.into_inner());
+                }
+            }
+        };
+        drop(state);
+
+        let mut finish = Finish {
+            lazy: self,
+            result: None,
+            span,
+        };
+        let result = thunk.run(span);
+        finish.result = Some(result.clone());
+        result
+    }
+}
+
+/// Stores the result of the closure once it's done. If the closure panicked, an error is stored
+/// instead, so that the value isn't left running and threads waiting for it wake up.
+struct Finish<'a> {
+    lazy: &'a LazyValue,
+    result: Option<Result<Value, ShellError>>,
+    span: Span,
+}
+
+impl Drop for Finish<'_> {
+    fn drop(&mut self) {
+        let result = self.result.take().unwrap_or_else(|| {
+            Err(ShellError::Generic(GenericError::new(
+                "Lazy value failed",
+                "the closure computing this value panicked",
+                self.span,
+            )))
+        });
+        *self.lazy.lock() = State::Done(result);
+        self.lazy.0.done.notify_all();
+    }
+}
+
+/// The value of a lazy value, and lazy values themselves as they are.
+fn force_value(value: &Value) -> Result<Value, ShellError> {
+    match value {
+        Value::Custom { val, internal_span } => match val.as_any().downcast_ref::<LazyValue>() {
+            Some(lazy) => lazy.force(*internal_span),
+            None => Ok(value.clone()),
+        },
+        _ => Ok(value.clone()),
+    }
+}
+
+impl fmt::Debug for LazyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &*self.lock() {
+            State::Done(result) => f.debug_tuple("LazyValue").field(result).finish(),
+            _ => f.write_str("LazyValue(<not computed>)"),
+        }
+    }
+}
+
+/// Lazy values are sent as their value, computing it if needed.
+impl Serialize for LazyValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.force(Span::unknown())
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LazyValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(|value| Self::with_state(State::Done(Ok(value))))
+    }
+}
+
+#[typetag::serde]
+impl CustomValue for LazyValue {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::custom(Box::new(self.clone()), span)
+    }
+
+    fn type_name(&self) -> String {
+        "lazy".into()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        self.force(span)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn follow_path_int(
+        &self,
+        self_span: Span,
+        index: usize,
+        path_span: Span,
+        optional: bool,
+    ) -> Result<Value, ShellError> {
+        let value = self.force(self_span)?;
+        value
+            .follow_cell_path(&[PathMember::int(index, optional, path_span)])
+            .map(|value| value.into_owned())
+    }
+
+    fn follow_path_string(
+        &self,
+        self_span: Span,
+        column_name: String,
+        path_span: Span,
+        optional: bool,
+        casing: Casing,
+    ) -> Result<Value, ShellError> {
+        let value = self.force(self_span)?;
+        value
+            .follow_cell_path(&[PathMember::string(column_name, optional, casing, path_span)])
+            .map(|value| value.into_owned())
+    }
+
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        let value = self.force(Span::unknown()).ok()?;
+        value.partial_cmp(&force_value(other).ok()?)
+    }
+
+    fn operation(
+        &self,
+        lhs_span: Span,
+        operator: Operator,
+        op: Span,
+        right: &Value,
+    ) -> Result<Value, ShellError> {
+        let lhs = self.force(lhs_span)?;
+        let rhs = force_value(right)?;
+        let span = lhs_span;
+        match operator {
+            Operator::Math(math) => match math {
+                Math::Add => lhs.add(op, &rhs, span),
+                Math::Subtract => lhs.sub(op, &rhs, span),
+                Math::Multiply => lhs.mul(op, &rhs, span),
+                Math::Divide => lhs.div(op, &rhs, span),
+                Math::FloorDivide => lhs.floor_div(op, &rhs, span),
+                Math::Modulo => lhs.modulo(op, &rhs, span),
+                Math::Pow => lhs.pow(op, &rhs, span),
+                Math::Concatenate => lhs.concat(op, &rhs, span),
+            },
+            Operator::Comparison(comparison) => match comparison {
+                Comparison::Equal => lhs.eq(op, &rhs, span),
+                Comparison::NotEqual => lhs.ne(op, &rhs, span),
+                Comparison::LessThan => lhs.lt(op, &rhs, span),
+                Comparison::GreaterThan => lhs.gt(op, &rhs, span),
+                Comparison::LessThanOrEqual => lhs.lte(op, &rhs, span),
+                Comparison::GreaterThanOrEqual => lhs.gte(op, &rhs, span),
+                Comparison::In => lhs.r#in(op, &rhs, span),
+                Comparison::NotIn => lhs.not_in(op, &rhs, span),
+                Comparison::Has => lhs.has(op, &rhs, span),
+                Comparison::NotHas => lhs.not_has(op, &rhs, span),
+                Comparison::StartsWith => lhs.starts_with(op, &rhs, span),
+                Comparison::NotStartsWith => lhs.not_starts_with(op, &rhs, span),
+                Comparison::EndsWith => lhs.ends_with(op, &rhs, span),
+                Comparison::NotEndsWith => lhs.not_ends_with(op, &rhs, span),
+                Comparison::RegexMatch | Comparison::NotRegexMatch => {
+                    Err(ShellError::OperatorUnsupportedType {
+                        op: operator,
+                        unsupported: Type::Custom(self.type_name().into()),
+                        op_span: op,
+                        unsupported_span: lhs_span,
+                        help: Some("convert the value with `into value` first"),
+                    })
+                }
+            },
+            Operator::Bits(bits) => match bits {
+                Bits::BitAnd => lhs.bit_and(op, &rhs, span),
+                Bits::BitOr => lhs.bit_or(op, &rhs, span),
+                Bits::BitXor => lhs.bit_xor(op, &rhs, span),
+                Bits::ShiftLeft => lhs.bit_shl(op, &rhs, span),
+                Bits::ShiftRight => lhs.bit_shr(op, &rhs, span),
+            },
+            Operator::Boolean(boolean) => match boolean {
+                Boolean::And => lhs.and(op, &rhs, span),
+                Boolean::Or => lhs.or(op, &rhs, span),
+                Boolean::Xor => lhs.xor(op, &rhs, span),
+            },
+            Operator::Assignment(_) => Err(ShellError::OperatorUnsupportedType {
+                op: operator,
+                unsupported: Type::Custom(self.type_name().into()),
+                op_span: op,
+                unsupported_span: lhs_span,
+                help: None,
+            }),
+        }
+    }
+
+    fn is_transparent(&self) -> bool {
+        true
+    }
+
+    fn is_streamable(&self) -> bool {
+        true
+    }
+
+    /// Lists and ranges are iterated over, other values are a stream of just that value, like
+    /// they are when they're the input of `each`.
+    fn iterate(&self, span: Span, signals: Signals) -> Result<ListStream, ShellError> {
+        Ok(match self.force(span)? {
+            Value::List { vals, .. } => ListStream::new(vals.into_iter(), span, signals),
+            Value::Range { val, .. } => {
+                ListStream::new(val.into_range_iter(span, signals.clone()), span, signals)
+            }
+            value => ListStream::new(std::iter::once(value), span, signals),
+        })
+    }
+
+    fn memory_size(&self) -> usize {
+        match &*self.lock() {
+            State::Done(Ok(value)) => std::mem::size_of::<Self>() + value.memory_size(),
+            _ => std::mem::size_of::<Self>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(Lazy)
+    }
+
+    #[test]
+    fn panic_in_closure_fails_the_value() {
+        let lazy = LazyValue::with_state(State::Running(thread::current().id()));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _finish = Finish {
+                lazy: &lazy,
+                result: None,
+                span: Span::test_data(),
+            };
+            panic!("closure panicked");
+        }));
+        assert!(result.is_err());
+        assert!(matches!(*lazy.lock(), State::Done(Err(_))));
+        assert!(lazy.force(Span::test_data()).is_err());
+    }
+}
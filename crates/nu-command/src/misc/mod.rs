@@ -1,9 +1,11 @@
+mod lazy;
 mod panic;
 mod run;
 mod source;
 mod tutor;
 mod unlet;
 
+pub use lazy::Lazy;
 pub use panic::Panic;
 pub use run::Run;
 pub use source::Source;
@@ -0,0 +1,132 @@
+use super::env_path::{dedupe, get_path_entries, path_arguments, path_var_name, set_path_entries};
+use nu_engine::command_prelude::*;
+use std::path::Path;
+
+#[derive(Clone)]
+pub struct PathAdd;
+
+impl Command for PathAdd {
+    fn name(&self) -> &str {
+        "path add"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("path add")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::Nothing, Type::List(Box::new(Type::String))),
+            ])
+            .rest(
+                "paths",
+                SyntaxShape::Any,
+                "The paths to add, or records with a path for each OS.",
+            )
+            .switch(
+                "prepend",
+                "Add the paths before the other entries (the default).",
+                Some('p'),
+            )
+            .switch(
+                "append",
+                "Add the paths after the other entries.",
+                Some('a'),
+            )
+            .switch(
+                "if-exists",
+                "Only add the paths of existing directories.",
+                None,
+            )
+            .switch("ret", "Return the new PATH.", Some('r'))
+            .category(Category::Path)
+    }
+
+    fn description(&self) -> &str {
+        "Add directories to the PATH environment variable."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The paths are expanded, without resolving symlinks. A record gives the path to add under the name of
+the current OS, like `$nu.os-info.name`, and adds nothing on other systems. Paths which are already in PATH
+are moved to the front, or kept where they are with `--append`.
+
+PATH is updated whether it's a list or a string, and whatever its casing is, like `Path` on Windows."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["env", "bin", "prepend", "append"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let args: Vec<Value> = call.rest(engine_state, stack, 0)?;
+        let prepend = call.has_flag(engine_state, stack, "prepend")?;
+        let append = call.has_flag(engine_state, stack, "append")?;
+        let if_exists = call.has_flag(engine_state, stack, "if-exists")?;
+        let ret = call.has_flag(engine_state, stack, "ret")?;
+
+        if prepend && append {
+            return Err(ShellError::IncompatibleParameters {
+                left_message: "can't prepend".into(),
+                left_span: call.get_flag_span(stack, "prepend").unwrap_or(head),
+                right_message: "and append at the same time".into(),
+                right_span: call.get_flag_span(stack, "append").unwrap_or(head),
+            });
+        }
+
+        let mut paths = path_arguments(engine_state, stack, args, head)?;
+        if if_exists {
+            paths.retain(|path| Path::new(path).is_dir());
+        }
+
+        let name = path_var_name(engine_state, stack);
+        let entries = get_path_entries(engine_state, stack, &name)?;
+        let entries = if append {
+            entries.into_iter().chain(paths).collect()
+        } else {
+            paths.into_iter().chain(entries).collect()
+        };
+        let entries = dedupe(entries);
+        Ok(set_path_entries(stack, name, entries, ret, head))
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Add a directory at the front of PATH.",
+                example: "path add ~/.local/bin",
+                result: None,
+            },
+            Example {
+                description: "Add directories at the end of PATH, if they exist.",
+                example: "path add --append --if-exists /opt/homebrew/bin /usr/local/go/bin",
+                result: None,
+            },
+            Example {
+                description: "Add a different directory depending on the OS.",
+                example: "path add {linux: ~/.cargo/bin, windows: ~/scoop/shims}",
+                result: None,
+            },
+            Example {
+                description: "Add a directory and return the new PATH.",
+                example: "path add ~/bin --ret",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(PathAdd)
+    }
+}
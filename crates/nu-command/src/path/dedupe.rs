@@ -0,0 +1,78 @@
+use super::env_path::{dedupe, get_path_entries, path_var_name, set_path_entries};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct PathDedupe;
+
+impl Command for PathDedupe {
+    fn name(&self) -> &str {
+        "path dedupe"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("path dedupe")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::Nothing, Type::List(Box::new(Type::String))),
+            ])
+            .switch("ret", "Return the new PATH.", Some('r'))
+            .category(Category::Path)
+    }
+
+    fn description(&self) -> &str {
+        "Remove the duplicate entries of the PATH environment variable."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The first entry for each directory is kept, so the order in which directories are searched doesn't
+change. Trailing separators are ignored, and so is the case on Windows."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["env", "uniq", "duplicates", "clean"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let ret = call.has_flag(engine_state, stack, "ret")?;
+
+        let name = path_var_name(engine_state, stack);
+        let entries = dedupe(get_path_entries(engine_state, stack, &name)?);
+        Ok(set_path_entries(stack, name, entries, ret, head))
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Remove the duplicate entries of PATH.",
+                example: "path dedupe",
+                result: None,
+            },
+            Example {
+                description: "Remove duplicates from a PATH and return it.",
+                example: "with-env {PATH: [/a /b /a/ /c /b]} { path dedupe --ret }",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("/a"),
+                    Value::test_string("/b"),
+                    Value::test_string("/c"),
+                ])),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(PathDedupe)
+    }
+}
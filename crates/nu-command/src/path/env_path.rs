@@ -0,0 +1,177 @@
+//! Helpers shared by `path add`, `path remove` and `path dedupe`, which edit the PATH variable.
+
+use nu_engine::command_prelude::*;
+use nu_path::expand_path_with;
+use nu_protocol::shell_error::generic::GenericError;
+use std::path::Path;
+
+/// The name of the PATH variable as it's spelled in the environment, which is usually `Path` on
+/// Windows.
+pub(super) fn path_var_name(engine_state: &EngineState, stack: &Stack) -> String {
+    stack
+        .get_env_var_names(engine_state)
+        .into_iter()
+        .find(|name| name.eq_ignore_ascii_case("PATH"))
+        .unwrap_or_else(|| if cfg!(windows) { "Path" } else { "PATH" }.into())
+}
+
+/// The entries of PATH, splitting it with the platform's separator if it's a string, or has
+/// entries with several paths.
+pub(super) fn get_path_entries(
+    engine_state: &EngineState,
+    stack: &Stack,
+    name: &str,
+) -> Result<Vec<String>, ShellError> {
+    let joined = match stack.get_env_var(engine_state, name) {
+        None => return Ok(Vec::new()),
+        Some(Value::String { val, .. }) => vec![val.clone()],
+        Some(Value::List { vals, .. }) => vals
+            .iter()
+            .map(|entry| entry.coerce_string())
+            .collect::<Result<_, _>>()?,
+        Some(other) => {
+            return Err(ShellError::CantConvert {
+                to_type: "list<string>".into(),
+                from_type: other.get_type().to_string(),
+                span: other.span(),
+                help: Some(format!("`$env.{name}` should be a list or a string")),
+            });
+        }
+    };
+    Ok(joined
+        .iter()
+        .flat_map(std::env::split_paths)
+        .map(|path| path.to_string_lossy().into_owned())
+        .filter(|path| !path.is_empty())
+        .collect())
+}
+
+/// Sets PATH to the entries, and returns them if `ret` is set, for the `--ret` flags.
+pub(super) fn set_path_entries(
+    stack: &mut Stack,
+    name: String,
+    entries: Vec<String>,
+    ret: bool,
+    span: Span,
+) -> PipelineData {
+    let entries = Value::list(
+        entries
+            .into_iter()
+            .map(|entry| Value::string(entry, span))
+            .collect(),
+        span,
+    );
+    stack.add_env_var(name, entries.clone());
+    if ret {
+        entries.into_pipeline_data()
+    } else {
+        PipelineData::empty()
+    }
+}
+
+/// The paths given as arguments, expanded without resolving symlinks.
+///
+/// Lists are flattened, and records give the path for the current OS under its name, like
+/// `{linux: ~/bin, windows: ~/scoop/shims}`. Records without the current OS give no path.
+pub(super) fn path_arguments(
+    engine_state: &EngineState,
+    stack: &Stack,
+    args: Vec<Value>,
+    span: Span,
+) -> Result<Vec<String>, ShellError> {
+    let cwd = engine_state.cwd(Some(stack))?;
+    let mut values = Vec::new();
+    flatten_into(args, &mut values);
+    if values.is_empty() {
+        return Err(ShellError::Generic(
+            GenericError::new("Empty input", "provide at least one path or record", span)
+                .with_help("e.g. `path add ~/.local/bin`"),
+        ));
+    }
+
+    let mut paths = Vec::new();
+    for value in values {
+        let value_span = value.span();
+        let path = match value {
+            Value::String { val, .. } => Some(val),
+            Value::Record { val, .. } => val
+                .get(std::env::consts::OS)
+                .map(|path| path.coerce_string())
+                .transpose()?,
+            other => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "string, record or list".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: span,
+                    src_span: value_span,
+                });
+            }
+        };
+        if let Some(path) = path {
+            paths.push(expand(&path, cwd.as_std_path()));
+        }
+    }
+    Ok(paths)
+}
+
+fn flatten_into(values: Vec<Value>, flat: &mut Vec<Value>) {
+    for value in values {
+        match value {
+            Value::List { vals, .. } => flatten_into(vals, flat),
+            value => flat.push(value),
+        }
+    }
+}
+
+fn expand(path: &str, cwd: &Path) -> String {
+    expand_path_with(path, cwd, true)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Whether two PATH entries are the same directory, ignoring case on Windows and trailing
+/// separators.
+pub(super) fn same_entry(a: &str, b: &str) -> bool {
+    let (a, b) = (trim_separators(a), trim_separators(b));
+    if cfg!(windows) {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+fn trim_separators(entry: &str) -> &str {
+    match entry.trim_end_matches(['/', std::path::MAIN_SEPARATOR]) {
+        // The root directory is only separators
+        "" => entry,
+        trimmed => trimmed,
+    }
+}
+
+/// Removes the entries that are the same as an earlier one.
+pub(super) fn dedupe(entries: Vec<String>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if !unique.iter().any(|seen| same_entry(seen, &entry)) {
+            unique.push(entry);
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedupe_keeps_first_occurrence() {
+        let entries = ["/a", "/b/", "/a/", "/c", "/b"].map(String::from).to_vec();
+        assert_eq!(dedupe(entries), ["/a", "/b/", "/c"]);
+    }
+
+    #[test]
+    fn root_is_not_trimmed_away() {
+        assert!(same_entry("/", "/"));
+        assert!(!same_entry("/", "/a"));
+    }
+}
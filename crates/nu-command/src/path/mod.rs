@@ -1,16 +1,22 @@
+mod add;
 mod basename;
+mod dedupe;
 mod dirname;
+mod env_path;
 mod exists;
 mod expand;
 mod join;
 mod parse;
 pub mod path_;
 mod relative_to;
+mod remove;
 mod self_;
 mod split;
 mod r#type;
 
+pub use add::PathAdd;
 pub use basename::PathBasename;
+pub use dedupe::PathDedupe;
 pub use dirname::PathDirname;
 pub use exists::PathExists;
 pub use expand::PathExpand;
@@ -18,6 +24,7 @@ pub use join::PathJoin;
 pub use parse::PathParse;
 pub use path_::Path;
 pub use relative_to::PathRelativeTo;
+pub use remove::PathRemove;
 pub use self_::PathSelf;
 pub use split::PathSplit;
 pub use r#type::PathType;
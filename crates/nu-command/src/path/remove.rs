@@ -0,0 +1,84 @@
+use super::env_path::{
+    get_path_entries, path_arguments, path_var_name, same_entry, set_path_entries,
+};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct PathRemove;
+
+impl Command for PathRemove {
+    fn name(&self) -> &str {
+        "path remove"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("path remove")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::Nothing, Type::List(Box::new(Type::String))),
+            ])
+            .rest(
+                "paths",
+                SyntaxShape::Any,
+                "The paths to remove, or records with a path for each OS.",
+            )
+            .switch("ret", "Return the new PATH.", Some('r'))
+            .category(Category::Path)
+    }
+
+    fn description(&self) -> &str {
+        "Remove directories from the PATH environment variable."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The paths are expanded like with `path add`, and all the entries for them are removed. Trailing
+separators are ignored, and so is the case on Windows. Paths which aren't in PATH are ignored."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["env", "bin", "delete"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let args: Vec<Value> = call.rest(engine_state, stack, 0)?;
+        let ret = call.has_flag(engine_state, stack, "ret")?;
+
+        let paths = path_arguments(engine_state, stack, args, head)?;
+        let name = path_var_name(engine_state, stack);
+        let mut entries = get_path_entries(engine_state, stack, &name)?;
+        entries.retain(|entry| !paths.iter().any(|path| same_entry(entry, path)));
+        Ok(set_path_entries(stack, name, entries, ret, head))
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Remove a directory from PATH.",
+                example: "path remove ~/.local/bin",
+                result: None,
+            },
+            Example {
+                description: "Remove a different directory depending on the OS, and return the new PATH.",
+                example: "path remove {linux: /usr/games, windows: ~/scoop/shims} --ret",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(PathRemove)
+    }
+}
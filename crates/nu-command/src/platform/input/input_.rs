@@ -1,7 +1,11 @@
 use crate::platform::input::legacy_input::LegacyInput;
+use crate::platform::input::list::select_one;
 use crate::platform::input::reedline_prompt::ReedlinePrompt;
-use nu_engine::command_prelude::*;
-use nu_protocol::shell_error::{self, io::IoError};
+use nu_engine::{ClosureEval, command_prelude::*};
+use nu_protocol::{
+    engine::Closure,
+    shell_error::{self, generic::GenericError, io::IoError},
+};
 use reedline::{
     EditCommand, FileBackedHistory, HISTORY_SIZE, History, HistoryItem, Reedline, Signal,
 };
@@ -67,6 +71,18 @@ impl Command for Input {
                 None,
             )
             .switch("suppress-output", "Don't print keystroke values.", Some('s'))
+            .named(
+                "validate",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any])),
+                "Check the input with a closure, and ask again until it returns true. If it returns a string or fails, that message is shown first.",
+                Some('v'),
+            )
+            .named(
+                "choices",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "Let the user pick one of these values with a selector, like `input list`.",
+                Some('c'),
+            )
             .category(Category::Platform)
     }
 
@@ -77,6 +93,92 @@ impl Command for Input {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
+        let validate: Option<Closure> = call.get_flag(engine_state, stack, "validate")?;
+        let choices: Option<Vec<String>> = call.get_flag(engine_state, stack, "choices")?;
+
+        let history_entries = match input {
+            PipelineData::Value(Value::List { vals, .. }, ..) => Some(vals),
+            _ => None,
+        };
+
+        let mut validate = validate.map(|closure| ClosureEval::new(engine_state, stack, closure));
+
+        loop {
+            let answer = match &choices {
+                Some(choices) => select_choice(engine_state, stack, call, choices)?,
+                None => self.read_input(engine_state, stack, call, history_entries.clone())?,
+            };
+
+            // Nothing means the user gave up, e.g. with ctrl-d, so there's nothing to check
+            let Some(validate) = validate.as_mut().filter(|_| !answer.is_nothing()) else {
+                return Ok(answer.into_pipeline_data());
+            };
+
+            let result = validate
+                .run_with_value(answer.clone())
+                .and_then(|result| result.into_value(call.head));
+            match validation_message(result)? {
+                None => return Ok(answer.into_pipeline_data()),
+                Some(message) => println!("{message}"),
+            }
+        }
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Get input from the user, and assign to a variable.",
+                example: "let user_input = (input)",
+                result: None,
+            },
+            Example {
+                description: "Get two characters from the user, and assign to a variable.",
+                example: "let user_input = (input --numchar 2)",
+                result: None,
+            },
+            Example {
+                description: "Get input from the user with default value, and assign to a variable.",
+                example: "let user_input = (input --default 10)",
+                result: None,
+            },
+            Example {
+                description: "Get multiple lines of input from the user (newlines can be entered using `Alt` + `Enter` or `Ctrl` + `Enter`), and assign to a variable.",
+                example: "let multiline_input = (input --reedline)",
+                result: None,
+            },
+            Example {
+                description: "Get input from the user with history, and assign to a variable.",
+                example: "let user_input = ([past,command,entries] | input --reedline)",
+                result: None,
+            },
+            Example {
+                description: "Get input from the user with history backed by a file, and assign to a variable.",
+                example: "let user_input = (input --reedline --history-file ./history.txt)",
+                result: None,
+            },
+            Example {
+                description: "Ask for a number until the user enters one.",
+                example: r#"let age = (input 'Age: ' --validate {|s| if $s =~ '^\d+$' { true } else { 'please enter a number' } })"#,
+                result: None,
+            },
+            Example {
+                description: "Let the user pick one of a few values, defaulting to the first.",
+                example: "let color = (input 'Color: ' --choices [red green blue] --default red)",
+                result: None,
+            },
+        ]
+    }
+}
+
+impl Input {
+    /// Read one answer from the terminal, with reedline or the legacy implementation.
+    fn read_input(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        history_entries: Option<Vec<Value>>,
+    ) -> Result<Value, ShellError> {
         // Check if we should use the legacy implementation or the reedline implementation
         let use_reedline = [
             // reedline is not set - use legacy implementation
@@ -91,7 +193,9 @@ impl Command for Input {
         .any(|x| *x);
 
         if !use_reedline {
-            return self.legacy_input(engine_state, stack, call, input);
+            return self
+                .legacy_input(engine_state, stack, call, PipelineData::empty())?
+                .into_value(call.head);
         }
 
         let prompt_str: Option<String> = call.opt(engine_state, stack, 0)?;
@@ -110,11 +214,6 @@ impl Command for Input {
             _ => "".to_string(),
         };
 
-        let history_entries = match input {
-            PipelineData::Value(Value::List { vals, .. }, ..) => Some(vals),
-            _ => None,
-        };
-
         // If we either have history entries or history file, we create an history
         let history = match (history_entries.is_some(), history_file_val.is_some()) {
             (false, false) => None, // Neither are set, no need for history support
@@ -196,7 +295,7 @@ impl Command for Input {
             }
             Ok(Signal::CtrlD) => {
                 // Do nothing on ctrl-d
-                return Ok(Value::nothing(call.head).into_pipeline_data());
+                return Ok(Value::nothing(call.head));
             }
             // TODO: handle other signals like Signal::ExternalBreak
             Ok(_) => {}
@@ -206,44 +305,64 @@ impl Command for Input {
             }
         }
         match default_val {
-            Some(val) if buf.is_empty() => Ok(Value::string(val, call.head).into_pipeline_data()),
-            _ => Ok(Value::string(buf, call.head).into_pipeline_data()),
+            Some(val) if buf.is_empty() => Ok(Value::string(val, call.head)),
+            _ => Ok(Value::string(buf, call.head)),
         }
     }
+}
 
-    fn examples(&self) -> Vec<Example<'_>> {
-        vec![
-            Example {
-                description: "Get input from the user, and assign to a variable.",
-                example: "let user_input = (input)",
-                result: None,
-            },
-            Example {
-                description: "Get two characters from the user, and assign to a variable.",
-                example: "let user_input = (input --numchar 2)",
-                result: None,
-            },
-            Example {
-                description: "Get input from the user with default value, and assign to a variable.",
-                example: "let user_input = (input --default 10)",
-                result: None,
-            },
-            Example {
-                description: "Get multiple lines of input from the user (newlines can be entered using `Alt` + `Enter` or `Ctrl` + `Enter`), and assign to a variable.",
-                example: "let multiline_input = (input --reedline)",
-                result: None,
-            },
-            Example {
-                description: "Get input from the user with history, and assign to a variable.",
-                example: "let user_input = ([past,command,entries] | input --reedline)",
-                result: None,
-            },
-            Example {
-                description: "Get input from the user with history backed by a file, and assign to a variable.",
-                example: "let user_input = (input --reedline --history-file ./history.txt)",
-                result: None,
-            },
-        ]
+/// Let the user pick one of the choices with the selector of `input list`.
+fn select_choice(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    choices: &[String],
+) -> Result<Value, ShellError> {
+    let prompt: Option<String> = call.opt(engine_state, stack, 0)?;
+    let default_val: Option<String> = call.get_flag(engine_state, stack, "default")?;
+
+    if choices.is_empty() {
+        return Err(ShellError::Generic(GenericError::new(
+            "No choices to pick from",
+            "--choices needs at least one value",
+            call.head,
+        )));
+    }
+
+    let selected = select_one(engine_state, stack, prompt.as_deref(), choices, call.head)?;
+    Ok(chosen_value(choices, selected, default_val, call.head))
+}
+
+/// The value for the choice the user `selected`, or the default if they picked none.
+fn chosen_value(
+    choices: &[String],
+    selected: Option<usize>,
+    default_val: Option<String>,
+    span: Span,
+) -> Value {
+    match (selected.and_then(|index| choices.get(index)), default_val) {
+        (Some(choice), _) => Value::string(choice, span),
+        (None, Some(default_val)) => Value::string(default_val, span),
+        (None, None) => Value::nothing(span),
+    }
+}
+
+/// Interpret the `result` of the `--validate` closure: `None` accepts the answer, otherwise the
+/// message is shown before asking again.
+fn validation_message(result: Result<Value, ShellError>) -> Result<Option<String>, ShellError> {
+    match result {
+        Ok(Value::Bool { val: true, .. }) => Ok(None),
+        Ok(Value::Bool { val: false, .. }) => Ok(Some("Invalid input, please try again.".into())),
+        Ok(Value::String { val, .. }) => Ok(Some(val)),
+        Ok(other) => Err(ShellError::TypeMismatch {
+            err_message: format!(
+                "the --validate closure must return a bool or a string, not {}",
+                other.get_type()
+            ),
+            span: other.span(),
+        }),
+        Err(err @ ShellError::Interrupted { .. }) => Err(err),
+        Err(err) => Ok(Some(err.to_string())),
     }
 }
 
@@ -262,7 +381,8 @@ fn prefill_reedline_buffer(line_editor: &mut Reedline, default_val: &str) {
 #[cfg(test)]
 mod tests {
     use super::Input;
-    use super::prefill_reedline_buffer;
+    use super::{chosen_value, prefill_reedline_buffer, validation_message};
+    use nu_protocol::{ShellError, Span, Value};
     use reedline::Reedline;
 
     #[test]
@@ -287,4 +407,69 @@ mod tests {
         assert_eq!(line_editor.current_buffer_contents(), "");
         assert_eq!(line_editor.current_insertion_point(), 0);
     }
+
+    #[test]
+    fn chosen_value_picks_selected_choice() {
+        let choices = ["red".to_string(), "green".to_string()];
+        assert_eq!(
+            chosen_value(&choices, Some(1), Some("red".into()), Span::test_data()),
+            Value::test_string("green")
+        );
+    }
+
+    #[test]
+    fn chosen_value_falls_back_to_default() {
+        let choices = ["red".to_string(), "green".to_string()];
+        assert_eq!(
+            chosen_value(&choices, None, Some("red".into()), Span::test_data()),
+            Value::test_string("red")
+        );
+        assert_eq!(
+            chosen_value(&choices, None, None, Span::test_data()),
+            Value::test_nothing()
+        );
+    }
+
+    #[test]
+    fn validation_accepts_true() {
+        assert!(matches!(
+            validation_message(Ok(Value::test_bool(true))),
+            Ok(None)
+        ));
+    }
+
+    #[test]
+    fn validation_retries_with_message() {
+        assert!(matches!(
+            validation_message(Ok(Value::test_bool(false))),
+            Ok(Some(_))
+        ));
+        assert!(matches!(
+            validation_message(Ok(Value::test_string("please enter a number"))),
+            Ok(Some(message)) if message == "please enter a number"
+        ));
+        let error = ShellError::NushellFailed {
+            msg: "closure failed".into(),
+        };
+        assert!(matches!(validation_message(Err(error)), Ok(Some(_))));
+    }
+
+    #[test]
+    fn validation_rejects_other_types() {
+        assert!(matches!(
+            validation_message(Ok(Value::test_int(1))),
+            Err(ShellError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn validation_stops_on_interrupt() {
+        let interrupted = ShellError::Interrupted {
+            span: Span::test_data(),
+        };
+        assert!(matches!(
+            validation_message(Err(interrupted)),
+            Err(ShellError::Interrupted { .. })
+        ));
+    }
 }
@@ -44,6 +44,12 @@ impl Command for InputListen {
                 "How long to wait for input before returning.",
                 Some('o')
             )
+            .named(
+                "chord-timeout",
+                SyntaxShape::Duration,
+                "After a key press, wait this long for more keys and return them together as a chord.",
+                Some('c')
+            )
             .input_output_types(vec![(
                 Type::Nothing,
                 Type::Record(vec![
@@ -65,10 +71,18 @@ corresponding record, distinguished by type field:
 ```
     { type: focus event: (gained|lost) }
     { type: key key_type: <key_type> code: <string> modifiers: [ <modifier> ... ] }
-    { type: mouse col: <int> row: <int> kind: <string> modifiers: [ <modifier> ... ] }
+    { type: mouse col: <int> row: <int> kind: <string> button: <button> modifiers: [ <modifier> ... ] }
     { type: paste content: <string> }
     { type: resize col: <int> row: <int> }
 ```
+With `--chord-timeout`, key presses that follow each other within the timeout are returned as one
+event, where each key is a record like the key event above:
+```
+    { type: chord keys: [ { type: key ... } ... ] }
+```
+The mouse `kind` is one of <Button>_down, <Button>_up, <Button>_drag, moved, scroll_down, scroll_up,
+scroll_left and scroll_right, where <Button> is Left, Right or Middle. The `button` is left, right or
+middle, or null for moves and scrolling.
 There are 6 `modifier` variants: shift, control, alt, super, hyper, meta.
 There are 4 `key_type` variants:
     f - f1, f2, f3 ... keys
@@ -77,11 +91,23 @@ There are 4 `key_type` variants:
     other - keys not falling under previous categories (up, down, backspace, enter ...)"
     }
     fn examples(&self) -> Vec<Example<'_>> {
-        vec![Example {
-            description: "Listen for a keyboard shortcut and find out how nu receives it.",
-            example: "input listen --types [key]",
-            result: None,
-        }]
+        vec![
+            Example {
+                description: "Listen for a keyboard shortcut and find out how nu receives it.",
+                example: "input listen --types [key]",
+                result: None,
+            },
+            Example {
+                description: "Wait for a key sequence like `g g`, with up to half a second between the keys.",
+                example: "input listen --types [key] --chord-timeout 500ms | get keys.code",
+                result: None,
+            },
+            Example {
+                description: "Find out where the user clicks.",
+                example: "input listen --types [mouse] | where kind == Left_down | select col row",
+                result: None,
+            },
+        ]
     }
     fn run(
         &self,
@@ -93,6 +119,8 @@ There are 4 `key_type` variants:
         let head = call.head;
         let event_type_filter = get_event_type_filter(engine_state, stack, call, head)?;
         let timeout: Option<Duration> = call.get_flag(engine_state, stack, "timeout")?;
+        let chord_timeout: Option<Duration> =
+            call.get_flag(engine_state, stack, "chord-timeout")?;
         let add_raw = call.has_flag(engine_state, stack, "raw")?;
         let config = stack.get_config(engine_state);
 
@@ -130,9 +158,7 @@ There are 4 `key_type` variants:
 
         loop {
             if let Some(t) = remaining_time
-                && !crossterm::event::poll(t).map_err(|_| {
-                    ShellError::Generic(GenericError::new("Error with user input", "", head))
-                })?
+                && !crossterm::event::poll(t).map_err(|_| input_error(head))?
             {
                 terminal::disable_raw_mode().map_err(|err| IoError::new(err, head, None))?;
                 return Err(ShellError::Generic(GenericError::new(
@@ -141,11 +167,15 @@ There are 4 `key_type` variants:
                     head,
                 )));
             }
-            let event = crossterm::event::read().map_err(|_| {
-                ShellError::Generic(GenericError::new("Error with user input", "", head))
-            })?;
-            let event = parse_event(head, &event, &event_type_filter, add_raw);
+            let raw_event = crossterm::event::read().map_err(|_| input_error(head))?;
+            let event = parse_event(head, &raw_event, &event_type_filter, add_raw);
             if let Some(event) = event {
+                let event = match chord_timeout {
+                    Some(chord_timeout) if matches!(raw_event, crossterm::event::Event::Key(_)) => {
+                        read_chord(head, event, chord_timeout, &event_type_filter, add_raw)?
+                    }
+                    _ => event,
+                };
                 terminal::disable_raw_mode().map_err(|err| IoError::new(err, head, None))?;
                 if config.use_kitty_protocol {
                     let _ = execute!(
@@ -163,6 +193,43 @@ There are 4 `key_type` variants:
     }
 }
 
+fn input_error(head: Span) -> ShellError {
+    ShellError::Generic(GenericError::new("Error with user input", "", head))
+}
+
+/// Keep reading key presses until none follows within `timeout`, and return them as a chord.
+fn read_chord(
+    head: Span,
+    first: Value,
+    timeout: Duration,
+    filter: &EventTypeFilter,
+    add_raw: bool,
+) -> Result<Value, ShellError> {
+    let mut keys = vec![first];
+    let mut deadline = Instant::now() + timeout;
+
+    // Other events, like mouse moves, don't extend the wait
+    while crossterm::event::poll(deadline.saturating_duration_since(Instant::now()))
+        .map_err(|_| input_error(head))?
+    {
+        if let crossterm::event::Event::Key(event) =
+            crossterm::event::read().map_err(|_| input_error(head))?
+            && let Some(key) = create_key_event(head, filter, &event, add_raw)
+        {
+            keys.push(key);
+            deadline = Instant::now() + timeout;
+        }
+    }
+
+    Ok(Value::record(
+        record! {
+            "type" => Value::string("chord", head),
+            "keys" => Value::list(keys, head),
+        },
+        head,
+    ))
+}
+
 fn get_event_type_filter(
     engine_state: &EngineState,
     stack: &mut Stack,
@@ -436,6 +503,12 @@ fn create_mouse_event(
     add_raw: bool,
 ) -> Option<Value> {
     if filter.listen_mouse {
+        let button = match event.kind {
+            MouseEventKind::Down(btn) | MouseEventKind::Up(btn) | MouseEventKind::Drag(btn) => {
+                Value::string(format!("{btn:?}").to_ascii_lowercase(), head)
+            }
+            _ => Value::nothing(head),
+        };
         let kind = match event.kind {
             MouseEventKind::Down(btn) => format!("{btn:?}_down"),
             MouseEventKind::Up(btn) => format!("{btn:?}_up"),
@@ -452,6 +525,7 @@ fn create_mouse_event(
             "col" => Value::int(event.column as i64, head),
             "row" => Value::int(event.row as i64, head),
             "kind" => Value::string(kind, head),
+            "button" => button,
             "modifiers" => parse_modifiers(head, &event.modifiers),
         };
 
@@ -948,6 +948,56 @@ impl InputList {
     }
 }
 
+/// Let the user pick one of `choices` with the selector of `input list`, in its single selection
+/// mode. Returns the index of the picked choice, or `None` if the user cancelled.
+pub(super) fn select_one(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    prompt: Option<&str>,
+    choices: &[String],
+    span: Span,
+) -> Result<Option<usize>, ShellError> {
+    let config = stack.get_config(engine_state);
+    let style_computer = StyleComputer::from_config(engine_state, stack);
+    let input_list_config = InputListConfig::from_nu_config(&config, &style_computer, span);
+
+    let options = choices
+        .iter()
+        .map(|choice| {
+            InputList::make_select_item(
+                Value::string(choice, span),
+                &[],
+                &DisplayMode::Default,
+                &config,
+                engine_state,
+                stack,
+                span,
+            )
+        })
+        .collect();
+
+    let mut widget = SelectWidget::new(
+        SelectMode::Single,
+        prompt,
+        options,
+        input_list_config,
+        None,
+        false,
+        StreamState {
+            stream_reader: None,
+            item_generator: None,
+        },
+    );
+    let answer = widget
+        .run()
+        .map_err(|err| IoError::new_with_additional_context(err, span, None, INTERACT_ERROR))?;
+
+    Ok(match answer {
+        InteractMode::Single(selected) => selected,
+        InteractMode::Multi(selected) => selected.and_then(|selected| selected.first().copied()),
+    })
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum SelectMode {
     Single,
@@ -1,3 +1,4 @@
+use crate::filesystem::try_interaction;
 use nu_engine::command_prelude::*;
 use nu_protocol::shell_error::generic::GenericError;
 use nu_system::build_kill_command;
@@ -12,20 +13,38 @@ impl Command for Kill {
     }
 
     fn description(&self) -> &str {
-        "Kill a process using its process ID."
+        "Kill processes using their process IDs or names."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Processes can be given as ids, as names, or as a table with a `pid` column like the one \
+        `ps` returns. Names are glob patterns matched against the process names, so `kill 'node*'` \
+        kills every process whose name starts with `node`. The running shell itself is never \
+        matched by name.
+
+On Unix, the signal can be given as a number or as a name like `TERM` or `SIGHUP`."
     }
 
     fn signature(&self) -> Signature {
         let signature = Signature::build("kill")
-            .input_output_types(vec![(Type::Nothing, Type::Any)])
+            .input_output_types(vec![
+                (Type::Nothing, Type::Any),
+                (Type::table(), Type::Any),
+                (Type::List(Box::new(Type::Int)), Type::Any),
+            ])
             .allow_variants_without_examples(true)
             .rest(
                 "pid",
-                SyntaxShape::Int,
-                "Process ids of processes that are to be killed.",
+                SyntaxShape::OneOf(vec![SyntaxShape::Int, SyntaxShape::String]),
+                "Process ids or names of processes that are to be killed.",
             )
             .switch("force", "Forcefully kill the process.", Some('f'))
             .switch("quiet", "Won't print anything to the console.", Some('q'))
+            .switch(
+                "ask",
+                "List the matched processes and ask before killing them.",
+                Some('a'),
+            )
             .category(Category::Platform);
 
         if cfg!(windows) {
@@ -34,14 +53,14 @@ impl Command for Kill {
 
         signature.named(
             "signal",
-            SyntaxShape::Int,
-            "Signal decimal number to be sent instead of the default 15 (unsupported on Windows).",
+            SyntaxShape::OneOf(vec![SyntaxShape::Int, SyntaxShape::String]),
+            "Signal number or name to be sent instead of the default TERM (unsupported on Windows).",
             Some('s'),
         )
     }
 
     fn search_terms(&self) -> Vec<&str> {
-        vec!["stop", "end", "close", "taskkill"]
+        vec!["stop", "end", "close", "taskkill", "pkill", "killall"]
     }
 
     fn run(
@@ -49,12 +68,32 @@ impl Command for Kill {
         engine_state: &EngineState,
         stack: &mut Stack,
         call: &Call,
-        _input: PipelineData,
+        input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let pids: Vec<Spanned<i64>> = call.rest(engine_state, stack, 0)?;
+        let args: Vec<Value> = call.rest(engine_state, stack, 0)?;
         let force: bool = call.has_flag(engine_state, stack, "force")?;
-        let signal: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "signal")?;
+        let signal: Option<Value> = call.get_flag(engine_state, stack, "signal")?;
         let quiet: bool = call.has_flag(engine_state, stack, "quiet")?;
+        let ask: bool = call.has_flag(engine_state, stack, "ask")?;
+
+        let mut pids = Vec::new();
+        let mut names = Vec::new();
+        for arg in args {
+            let span = arg.span();
+            match arg {
+                Value::Int { val, .. } => pids.push(val.into_spanned(span)),
+                Value::String { val, .. } => names.push(val.into_spanned(span)),
+                other => {
+                    return Err(ShellError::CantConvert {
+                        to_type: "process id or name".into(),
+                        from_type: other.get_type().to_string(),
+                        span,
+                        help: None,
+                    });
+                }
+            }
+        }
+        let piped = input_pids(input, call.head)?;
 
         if cfg!(unix)
             && signal.is_none()
@@ -72,13 +111,22 @@ impl Command for Kill {
             });
         }
 
-        if pids.is_empty() {
+        pids.extend(piped);
+
+        if pids.is_empty() && names.is_empty() {
             return Err(ShellError::MissingParameter {
                 param_name: "pid".to_string(),
                 span: call.arguments_span(),
             });
         }
 
+        let signal = signal
+            .map(|signal| {
+                let span = signal.span();
+                signal_number(signal).map(|number| number.into_spanned(span))
+            })
+            .transpose()?;
+
         if cfg!(unix)
             && let (
                 true,
@@ -102,10 +150,73 @@ impl Command for Kill {
             });
         };
 
+        // Processes are only listed when they're needed
+        let processes = if names.is_empty() && !ask {
+            Vec::new()
+        } else {
+            running_processes(call.head)?
+        };
+
+        let mut targets: Vec<i64> = pids.iter().map(|pid| pid.item).collect();
+        for name in &names {
+            let pattern = nu_glob::Pattern::new(&name.item).map_err(|err| {
+                ShellError::Generic(GenericError::new(
+                    "Invalid process name pattern",
+                    err.msg,
+                    name.span,
+                ))
+            })?;
+            let own_pid = i64::from(std::process::id());
+            let matched: Vec<i64> = processes
+                .iter()
+                .filter(|(pid, process)| *pid != own_pid && name_matches(&pattern, process))
+                .map(|(pid, _)| *pid)
+                .collect();
+            if matched.is_empty() {
+                return Err(ShellError::Generic(
+                    GenericError::new(
+                        "No matching process",
+                        format!("no running process is named `{}`", name.item),
+                        name.span,
+                    )
+                    .with_help("use `ps` to list the running processes"),
+                ));
+            }
+            targets.extend(matched);
+        }
+        let mut seen = std::collections::HashSet::new();
+        targets.retain(|pid| seen.insert(*pid));
+
+        if ask {
+            eprintln!("The following processes will be killed:");
+            for pid in &targets {
+                let name = processes
+                    .iter()
+                    .find(|(process_pid, _)| process_pid == pid)
+                    .map_or("", |(_, name)| name.as_str());
+                eprintln!("{pid:>8}  {name}");
+            }
+            let prompt = match targets.len() {
+                1 => "Kill this process?".to_string(),
+                count => format!("Kill these {count} processes?"),
+            };
+            let (interaction, confirmed) = try_interaction(true, prompt);
+            if let Err(e) = interaction {
+                return Err(ShellError::Generic(GenericError::new(
+                    "Could not ask for confirmation",
+                    e.to_string(),
+                    call.head,
+                )));
+            }
+            if !confirmed {
+                return Ok(PipelineData::empty());
+            }
+        }
+
         let mut cmd = build_kill_command(
             force,
-            pids.iter().copied().map(|spanned| spanned.item),
-            signal.map(|spanned| spanned.item as u32),
+            targets.into_iter(),
+            signal.map(|spanned| spanned.item),
         );
 
         // pipe everything to null
@@ -160,16 +271,156 @@ impl Command for Kill {
                 example: "kill --force 12345",
                 result: None,
             },
+            Example {
+                description: "Kill every process whose name starts with `node`.",
+                example: "kill 'node*'",
+                result: None,
+            },
+            Example {
+                description: "Kill the processes in a table from `ps`, after confirming.",
+                example: "ps | where name =~ chrome | kill --ask",
+                result: None,
+            },
             #[cfg(not(target_os = "windows"))]
             Example {
                 description: "Send INT signal.",
                 example: "kill -s 2 12345",
                 result: None,
             },
+            #[cfg(not(target_os = "windows"))]
+            Example {
+                description: "Send HUP signal by name.",
+                example: "kill -s HUP 12345",
+                result: None,
+            },
         ]
     }
 }
 
+/// The process ids in the input, which can be ids or records with a `pid` column.
+fn input_pids(input: PipelineData, head: Span) -> Result<Vec<Spanned<i64>>, ShellError> {
+    let pid_of = |value: Value| {
+        let span = value.span();
+        match value {
+            Value::Int { val, .. } => Ok(val.into_spanned(span)),
+            Value::Record { val, .. } => match val.get("pid") {
+                Some(pid) => Ok(pid.as_int()?.into_spanned(pid.span())),
+                None => Err(ShellError::CantFindColumn {
+                    col_name: "pid".into(),
+                    span: None,
+                    src_span: span,
+                }),
+            },
+            other => Err(ShellError::OnlySupportsThisInputType {
+                exp_input_type: "int or record with a pid column".into(),
+                wrong_type: other.get_type().to_string(),
+                dst_span: head,
+                src_span: span,
+            }),
+        }
+    };
+    match input {
+        PipelineData::Empty => Ok(Vec::new()),
+        PipelineData::Value(Value::Nothing { .. }, ..) => Ok(Vec::new()),
+        PipelineData::Value(value @ (Value::Int { .. } | Value::Record { .. }), ..) => {
+            Ok(vec![pid_of(value)?])
+        }
+        input => input.into_iter().map(pid_of).collect(),
+    }
+}
+
+/// The number of a signal given as a number or a name, like `TERM` or `SIGTERM`.
+fn signal_number(signal: Value) -> Result<u32, ShellError> {
+    let span = signal.span();
+    match signal {
+        Value::Int { val, .. } => u32::try_from(val).map_err(|_| ShellError::IncorrectValue {
+            msg: "signal numbers can't be negative".into(),
+            val_span: span,
+            call_span: span,
+        }),
+        Value::String { val, .. } => signal_from_name(&val, span),
+        other => Err(ShellError::CantConvert {
+            to_type: "signal".into(),
+            from_type: other.get_type().to_string(),
+            span,
+            help: None,
+        }),
+    }
+}
+
+#[cfg(unix)]
+fn signal_from_name(name: &str, span: Span) -> Result<u32, ShellError> {
+    use nix::sys::signal::Signal;
+    use std::str::FromStr;
+
+    let name = name.to_ascii_uppercase();
+    let name = if name.starts_with("SIG") {
+        name
+    } else {
+        format!("SIG{name}")
+    };
+    Signal::from_str(&name)
+        .map(|signal| signal as u32)
+        .map_err(|_| ShellError::InvalidValue {
+            valid: "a signal number or name, like TERM, HUP or KILL".into(),
+            actual: format!("'{name}'"),
+            span,
+        })
+}
+
+#[cfg(not(unix))]
+fn signal_from_name(name: &str, span: Span) -> Result<u32, ShellError> {
+    Err(ShellError::InvalidValue {
+        valid: "a signal number".into(),
+        actual: format!("'{name}'"),
+        span,
+    })
+}
+
+/// Whether a process name matches, ignoring the `.exe` extension on Windows.
+fn name_matches(pattern: &nu_glob::Pattern, name: &str) -> bool {
+    pattern.matches(name)
+        || (cfg!(windows)
+            && name
+                .get(name.len().saturating_sub(4)..)
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(".exe"))
+            && pattern.matches(&name[..name.len() - 4]))
+}
+
+/// The ids and names of the running processes.
+#[cfg(any(
+    target_os = "android",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "windows"
+))]
+fn running_processes(_span: Span) -> Result<Vec<(i64, String)>, ShellError> {
+    Ok(nu_system::collect_proc(std::time::Duration::ZERO, false)
+        .into_iter()
+        .map(|process| (i64::from(process.pid()), process.name()))
+        .collect())
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "windows"
+)))]
+fn running_processes(span: Span) -> Result<Vec<(i64, String)>, ShellError> {
+    Err(ShellError::Generic(GenericError::new(
+        "Processes can't be listed on this platform",
+        "only process ids are supported here",
+        span,
+    )))
+}
+
 fn inferred_signal_number(raw: &[u8], pid: i64) -> Option<u64> {
     if pid < 0 {
         return Some(pid.unsigned_abs());
@@ -201,4 +452,22 @@ mod tests {
         assert_eq!(inferred_signal_number(b"0", 0), None);
         assert_eq!(inferred_signal_number(b"9", 9), None);
     }
+
+    #[test]
+    fn process_names_are_glob_patterns() {
+        let pattern = nu_glob::Pattern::new("node*").unwrap();
+        assert!(name_matches(&pattern, "node"));
+        assert!(name_matches(&pattern, "nodemon"));
+        assert!(!name_matches(&pattern, "deno"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signals_by_name() {
+        let span = Span::test_data();
+        assert_eq!(signal_from_name("TERM", span).unwrap(), 15);
+        assert_eq!(signal_from_name("sigkill", span).unwrap(), 9);
+        assert_eq!(signal_from_name("HUP", span).unwrap(), 1);
+        assert!(signal_from_name("NOPE", span).is_err());
+    }
 }
@@ -6,11 +6,16 @@ mod input;
 mod is_redirected;
 mod is_terminal;
 mod kill;
+mod on_signal;
 mod sleep;
 mod term;
 #[cfg(unix)]
 mod ulimit;
 #[cfg(unix)]
+mod ulimit_get;
+#[cfg(unix)]
+mod ulimit_set;
+#[cfg(unix)]
 mod umask_;
 mod whoami;
 
@@ -24,10 +29,15 @@ pub use input::InputListen;
 pub use is_redirected::IsRedirected;
 pub use is_terminal::IsTerminal;
 pub use kill::Kill;
+pub use on_signal::OnSignal;
 pub use sleep::Sleep;
 pub use term::{Term, TermQuery, TermSize};
 #[cfg(unix)]
 pub use ulimit::ULimit;
 #[cfg(unix)]
+pub use ulimit_get::ULimitGet;
+#[cfg(unix)]
+pub use ulimit_set::ULimitSet;
+#[cfg(unix)]
 pub use umask_::UMask;
 pub use whoami::Whoami;
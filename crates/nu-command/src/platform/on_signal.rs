@@ -0,0 +1,136 @@
+use nu_engine::{ClosureEvalOnce, command_prelude::*};
+use nu_protocol::{HookedSignal, Signals, engine::Closure, report_shell_error};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct OnSignal;
+
+impl Command for OnSignal {
+    fn name(&self) -> &str {
+        "on-signal"
+    }
+
+    fn description(&self) -> &str {
+        "Run a closure when nushell gets a signal."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The signal is `interrupt` (SIGINT or Ctrl-C), `terminate` (SIGTERM) or `hangup` (SIGHUP, \
+        or the console window being closed on Windows). Unix names like `SIGTERM` work too.
+
+The closure gets the name of the signal, and runs on its own with the environment of the \
+        place `on-signal` was called from, so it can't change variables or the environment. It \
+        isn't interrupted itself, and nushell waits for it before exiting: after an interrupt, \
+        nushell stops what it was doing as usual, and after `terminate` or `hangup`, it exits \
+        once the closures ran.
+
+Closures stay registered for the rest of the session, and run in the order they were \
+        registered."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("on-signal")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required(
+                "signal",
+                SyntaxShape::String,
+                "The signal to run the closure for.",
+            )
+            .required(
+                "closure",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::String])),
+                "The closure to run, getting the name of the signal.",
+            )
+            .category(Category::Platform)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["trap", "signal", "cleanup", "sigterm", "sigint", "sighup"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let signal: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let closure: Closure = call.req(engine_state, stack, 1)?;
+
+        let Some(hooked) = HookedSignal::from_name(&signal.item) else {
+            return Err(ShellError::InvalidValue {
+                valid: "interrupt, terminate or hangup".into(),
+                actual: signal.item,
+                span: signal.span,
+            });
+        };
+
+        if hooked != HookedSignal::Interrupt {
+            let hooks = engine_state.signal_hooks.clone();
+            nu_system::set_termination_handler(move |signal| {
+                hooks.run(match signal {
+                    nu_system::TerminationSignal::Terminate => HookedSignal::Terminate,
+                    nu_system::TerminationSignal::Hangup => HookedSignal::Hangup,
+                })
+            })
+            .map_err(|err| {
+                IoError::new_with_additional_context(
+                    err,
+                    signal.span,
+                    None,
+                    "Could not handle the signal",
+                )
+            })?;
+        }
+
+        let mut hook_engine_state = engine_state.clone();
+        // The closure cleans up after an interrupt, so it mustn't be interrupted by it
+        hook_engine_state.set_signals(Signals::empty());
+        let hook_stack = stack.captures_to_stack_preserve_out_dest(vec![]);
+        let span = call.head;
+        engine_state.signal_hooks.add(
+            hooked,
+            Arc::new(move |signal| {
+                let result = ClosureEvalOnce::new(&hook_engine_state, &hook_stack, closure.clone())
+                    .run_with_value(Value::string(signal.name(), span))
+                    .and_then(|output| output.drain());
+                if let Err(err) = result {
+                    report_shell_error(Some(&hook_stack), &hook_engine_state, &err);
+                }
+            }),
+        );
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Save the progress of a long script when it's stopped",
+                example: "let log = 'progress.log'; on-signal terminate {|| 'stopped early' | save -a $log }",
+                result: None,
+            },
+            Example {
+                description: "Clean up a temporary directory after Ctrl-C",
+                example: "let dir = mktemp -d; on-signal interrupt {|| rm -r $dir }",
+                result: None,
+            },
+            Example {
+                description: "Log every signal that has a hook",
+                example: "[interrupt terminate hangup] | each {|s| on-signal $s {|signal| print -e $'got ($signal)' } }",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(OnSignal)
+    }
+}
@@ -1,16 +1,41 @@
 use nix::sys::resource::{RLIM_INFINITY, Resource, rlim_t};
 use nu_engine::command_prelude::*;
-use nu_protocol::shell_error::generic::GenericError;
+use nu_protocol::{did_you_mean, shell_error::generic::GenericError};
 
 use std::sync::LazyLock;
 
+/// The names of all the limits `ulimit` knows, including those that aren't supported on this
+/// platform.
+pub(super) const LIMIT_NAMES: [&str; 20] = [
+    "socket-buffers",
+    "core-size",
+    "data-size",
+    "nice",
+    "file-size",
+    "pending-signals",
+    "lock-size",
+    "resident-set-size",
+    "file-descriptor-count",
+    "queue-size",
+    "realtime-priority",
+    "stack-size",
+    "cpu-time",
+    "process-count",
+    "virtual-memory-size",
+    "swap-size",
+    "file-locks",
+    "realtime-maxtime",
+    "kernel-queues",
+    "ptys",
+];
+
 /// An object contains resource related parameters
-struct ResourceInfo<'a> {
-    name: &'a str,
-    desc: &'a str,
+pub(super) struct ResourceInfo<'a> {
+    pub(super) name: &'a str,
+    pub(super) desc: &'a str,
     flag: char,
     multiplier: rlim_t,
-    resource: Resource,
+    pub(super) resource: Resource,
 }
 
 impl<'a> ResourceInfo<'a> {
@@ -55,7 +80,7 @@ impl Default for ResourceInfo<'_> {
     }
 }
 
-static RESOURCE_ARRAY: LazyLock<Vec<ResourceInfo>> = LazyLock::new(|| {
+pub(super) static RESOURCE_ARRAY: LazyLock<Vec<ResourceInfo>> = LazyLock::new(|| {
     let resources = [
         #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
         (
@@ -265,6 +290,50 @@ fn limit_to_value(limit: rlim_t, multiplier: rlim_t, span: Span) -> Result<Value
     Ok(Value::int(val, span))
 }
 
+/// The limit as a filesize, duration or count, depending on the resource
+pub(super) fn limit_to_structured_value(limit: rlim_t, res: &ResourceInfo, span: Span) -> Value {
+    if limit == RLIM_INFINITY {
+        return Value::string("unlimited", span);
+    }
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    if res.multiplier != 1 {
+        return Value::filesize(limit, span);
+    }
+    match res.resource {
+        Resource::RLIMIT_CPU => Value::duration(limit.saturating_mul(1_000_000_000), span),
+        #[cfg(target_os = "linux")]
+        Resource::RLIMIT_RTTIME => Value::duration(limit.saturating_mul(1_000), span),
+        _ => Value::int(limit, span),
+    }
+}
+
+/// A resource by name, or an error telling whether it's unknown or only unsupported here
+pub(super) fn find_resource(
+    name: &str,
+    span: Span,
+) -> Result<&'static ResourceInfo<'static>, ShellError> {
+    if let Some(res) = RESOURCE_ARRAY.iter().find(|res| res.name == name) {
+        return Ok(res);
+    }
+    let error = if LIMIT_NAMES.contains(&name) {
+        GenericError::new(
+            format!(
+                "The {name} limit isn't supported on {}",
+                std::env::consts::OS
+            ),
+            "unsupported limit",
+            span,
+        )
+    } else {
+        let error = GenericError::new(format!("Unknown limit {name}"), "unknown limit", span);
+        match did_you_mean(LIMIT_NAMES, name) {
+            Some(suggestion) => error.with_help(format!("did you mean {suggestion}?")),
+            None => error.with_help(format!("the limits are {}", LIMIT_NAMES.join(", "))),
+        }
+    };
+    Err(ShellError::Generic(error))
+}
+
 /// Get maximum length of all flag descriptions
 fn max_desc_len(
     call: &Call,
@@ -334,7 +403,7 @@ fn fill_record(
 }
 
 /// Set limits
-fn set_limits(
+pub(super) fn set_limits(
     limit_value: &Value,
     res: &ResourceInfo,
     soft: bool,
@@ -357,7 +426,13 @@ fn set_limits(
         }
     }
 
-    setrlimit(res.resource, soft_limit, hard_limit)
+    nix::sys::resource::setrlimit(res.resource, soft_limit, hard_limit).map_err(|e| {
+        ShellError::Generic(GenericError::new(
+            format!("Could not set the {} limit", res.name),
+            e.to_string(),
+            limit_value.span(),
+        ))
+    })
 }
 
 /// Print limits
@@ -400,19 +475,13 @@ fn print_limits(
 }
 
 /// Wrap `nix::sys::resource::getrlimit`
-fn setrlimit(res: Resource, soft_limit: rlim_t, hard_limit: rlim_t) -> Result<(), ShellError> {
-    nix::sys::resource::setrlimit(res, soft_limit, hard_limit)
-        .map_err(|e| ShellError::Generic(GenericError::new_internal(e.to_string(), "")))
-}
-
-/// Wrap `nix::sys::resource::setrlimit`
-fn getrlimit(res: Resource) -> Result<(rlim_t, rlim_t), ShellError> {
+pub(super) fn getrlimit(res: Resource) -> Result<(rlim_t, rlim_t), ShellError> {
     nix::sys::resource::getrlimit(res)
         .map_err(|e| ShellError::Generic(GenericError::new_internal(e.to_string(), "")))
 }
 
 /// Parse user input
-fn parse_limit(
+pub(super) fn parse_limit(
     limit_value: &Value,
     res: &ResourceInfo,
     soft: bool,
@@ -455,6 +524,29 @@ fn parse_limit(
                 help: Some(e.to_string()),
             })
         }
+        Value::Duration { val, .. } => {
+            let nanos_per_unit = match res.resource {
+                Resource::RLIMIT_CPU => 1_000_000_000,
+                #[cfg(target_os = "linux")]
+                Resource::RLIMIT_RTTIME => 1_000,
+                _ => {
+                    return Err(ShellError::TypeMismatch {
+                        err_message: format!(
+                            "duration is not compatible with resource {:?}",
+                            res.resource
+                        ),
+                        span: val_span,
+                    });
+                }
+            };
+
+            rlim_t::try_from(*val / nanos_per_unit).map_err(|e| ShellError::CantConvert {
+                to_type: "rlim_t".into(),
+                from_type: "duration".into(),
+                span: val_span,
+                help: Some(e.to_string()),
+            })
+        }
         Value::String { val, .. } => {
             if val == "unlimited" {
                 Ok(RLIM_INFINITY)
@@ -472,7 +564,7 @@ fn parse_limit(
         }
         _ => Err(ShellError::TypeMismatch {
             err_message: format!(
-                "string, int or filesize required, you provide {}",
+                "string, int, filesize or duration required, you provide {}",
                 limit_value.get_type()
             ),
             span: limit_value.span(),
@@ -585,6 +677,10 @@ impl Command for ULimit {
         ]
     }
 
+    fn extra_description(&self) -> &str {
+        "Use `ulimit get` and `ulimit set` to work with several limits at once, as structured data."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["resource", "limits"]
     }
@@ -0,0 +1,118 @@
+use super::ulimit::{
+    LIMIT_NAMES, RESOURCE_ARRAY, find_resource, getrlimit, limit_to_structured_value,
+};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct ULimitGet;
+
+impl Command for ULimitGet {
+    fn name(&self) -> &str {
+        "ulimit get"
+    }
+
+    fn description(&self) -> &str {
+        "Get resource usage limits as a table."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Sizes are given as filesizes and times as durations. Limits that aren't supported on \
+        this platform are listed too, with `supported` set to false."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ulimit get")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .rest(
+                "names",
+                SyntaxShape::String,
+                "The limits to get, like `stack-size`. All of them if none are given.",
+            )
+            .category(Category::Platform)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["resource", "limits", "rlimit"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let names: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+
+        let rows = if names.is_empty() {
+            LIMIT_NAMES
+                .iter()
+                .map(|name| limit_row(name, head))
+                .collect::<Result<_, _>>()?
+        } else {
+            names
+                .iter()
+                .map(|name| {
+                    find_resource(&name.item, name.span)?;
+                    limit_row(&name.item, head)
+                })
+                .collect::<Result<_, _>>()?
+        };
+
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Get all limits",
+                example: "ulimit get",
+                result: None,
+            },
+            Example {
+                description: "Get the soft limit of the number of open files",
+                example: "ulimit get file-descriptor-count | first | get soft",
+                result: None,
+            },
+            Example {
+                description: "Get the soft limits as a record",
+                example: "ulimit get | where supported | select name soft | transpose -r -d",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn limit_row(name: &str, span: Span) -> Result<Value, ShellError> {
+    let record = match RESOURCE_ARRAY.iter().find(|res| res.name == name) {
+        Some(res) => {
+            let (soft, hard) = getrlimit(res.resource)?;
+            record! {
+                "name" => Value::string(name, span),
+                "description" => Value::string(res.desc, span),
+                "supported" => Value::bool(true, span),
+                "soft" => limit_to_structured_value(soft, res, span),
+                "hard" => limit_to_structured_value(hard, res, span),
+            }
+        }
+        None => record! {
+            "name" => Value::string(name, span),
+            "description" => Value::nothing(span),
+            "supported" => Value::bool(false, span),
+            "soft" => Value::nothing(span),
+            "hard" => Value::nothing(span),
+        },
+    };
+    Ok(Value::record(record, span))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ULimitGet)
+    }
+}
@@ -0,0 +1,128 @@
+use super::ulimit::{find_resource, getrlimit, parse_limit, set_limits};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct ULimitSet;
+
+impl Command for ULimitSet {
+    fn name(&self) -> &str {
+        "ulimit set"
+    }
+
+    fn description(&self) -> &str {
+        "Set several resource usage limits at once from a record."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The record maps limit names, as listed by `ulimit get`, to new limits. Limits can be \
+        given like with `ulimit`: as integers in the unit `ulimit` shows, as `unlimited`, `soft` \
+        or `hard`, and also as filesizes for sizes and durations for times.
+
+All the limits are checked before any is set, so an unknown or unsupported limit or an \
+        invalid value changes nothing."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ulimit set")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::record(), Type::Nothing),
+            ])
+            .optional(
+                "limits",
+                SyntaxShape::Record(vec![]),
+                "The limits to set, if not given as input.",
+            )
+            .switch("soft", "Only set the soft limits.", Some('S'))
+            .switch("hard", "Only set the hard limits.", Some('H'))
+            .category(Category::Platform)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["resource", "limits", "rlimit"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let mut soft = call.has_flag(engine_state, stack, "soft")?;
+        let mut hard = call.has_flag(engine_state, stack, "hard")?;
+        if !soft && !hard {
+            soft = true;
+            hard = true;
+        }
+
+        let limits = match call.opt::<Value>(engine_state, stack, 0)? {
+            Some(limits) => limits,
+            None => input.into_value(head)?,
+        };
+        let span = limits.span();
+        let limits = match limits {
+            Value::Record { val, .. } => val.into_owned(),
+            Value::Nothing { .. } => {
+                return Err(ShellError::MissingParameter {
+                    param_name: "limits".into(),
+                    span: head,
+                });
+            }
+            other => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "record".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: span,
+                });
+            }
+        };
+
+        // Check every limit before changing any of them
+        let mut resources = Vec::with_capacity(limits.len());
+        for (name, value) in &limits {
+            let res = find_resource(name, value.span())?;
+            let (soft_limit, hard_limit) = getrlimit(res.resource)?;
+            parse_limit(value, res, soft, soft_limit, hard_limit, head)?;
+            resources.push((res, value));
+        }
+
+        for (res, value) in resources {
+            set_limits(value, res, soft, hard, head)?;
+        }
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Allow more open files and unlimited core dumps",
+                example: "ulimit set {file-descriptor-count: 4096, core-size: unlimited}",
+                result: None,
+            },
+            Example {
+                description: "Only lower the soft limit of the stack size",
+                example: "ulimit set --soft {stack-size: 4MiB}",
+                result: None,
+            },
+            Example {
+                description: "Restore limits saved earlier",
+                example: "let saved = ulimit get | where supported | select name soft | transpose -r -d; ulimit set --soft $saved",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ULimitSet)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::reference::RefValue;
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct RefGet;
+
+impl Command for RefGet {
+    fn name(&self) -> &str {
+        "ref get"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ref get")
+            .input_output_types(vec![(Type::Custom("ref".into()), Type::Any)])
+            .category(Category::Misc)
+    }
+
+    fn description(&self) -> &str {
+        "Get the current value of a reference."
+    }
+
+    fn extra_description(&self) -> &str {
+        "While another thread updates the reference, this returns the value from before that update."
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let reference = RefValue::from_value(&input.into_value(call.head)?)?;
+        Ok(reference.get().into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Get the value of a reference.",
+            example: "ref new {name: nushell} | ref get",
+            result: Some(Value::test_record(record! {
+                "name" => Value::test_string("nushell"),
+            })),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(RefGet)
+    }
+}
@@ -0,0 +1,12 @@
+pub(crate) mod value;
+
+mod get;
+mod new;
+mod ref_;
+mod update;
+
+pub use get::RefGet;
+pub use new::RefNew;
+pub use ref_::Ref;
+pub use update::RefUpdate;
+pub use value::RefValue;
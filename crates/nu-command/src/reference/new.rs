@@ -0,0 +1,73 @@
+use crate::reference::RefValue;
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct RefNew;
+
+impl Command for RefNew {
+    fn name(&self) -> &str {
+        "ref new"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ref new")
+            .input_output_types(vec![(Type::Nothing, Type::Custom("ref".into()))])
+            .required(
+                "value",
+                SyntaxShape::Any,
+                "The initial value of the reference.",
+            )
+            .category(Category::Misc)
+    }
+
+    fn description(&self) -> &str {
+        "Create a reference holding a value that all of its copies share."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Copies of a reference, like the ones captured by `par-each` closures or background jobs, all \
+        see the value set by `ref update`."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["mutable", "shared", "atomic", "cell", "counter"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let value: Value = call.req(engine_state, stack, 0)?;
+        Ok(RefValue::new(value)
+            .into_value(call.head)
+            .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Count the items processed by `par-each`.",
+                example: "let count = ref new 0; 1..100 | par-each { $count | ref update { $in + 1 } } | ignore; $count | ref get",
+                result: Some(Value::test_int(100)),
+            },
+            Example {
+                description: "Describe a reference.",
+                example: "ref new [] | describe",
+                result: Some(Value::test_string("ref")),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(RefNew)
+    }
+}
@@ -0,0 +1,42 @@
+use nu_engine::{command_prelude::*, get_full_help};
+
+#[derive(Clone)]
+pub struct Ref;
+
+impl Command for Ref {
+    fn name(&self) -> &str {
+        "ref"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ref")
+            .category(Category::Misc)
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+    }
+
+    fn description(&self) -> &str {
+        "Various commands for working with references, values shared and updated across threads."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"A reference holds a value which all of its copies share, so `par-each` closures and background jobs
+can update a counter or an accumulator together. Closures can't capture `mut` variables, and references are
+the way to share mutable state instead.
+
+You must use one of the following subcommands. Using this command as-is will only produce this help message."#
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::string(
+            get_full_help(self, engine_state, stack, call.head),
+            call.head,
+        )
+        .into_pipeline_data())
+    }
+}
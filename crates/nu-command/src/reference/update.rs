@@ -0,0 +1,95 @@
+use crate::reference::{RefValue, value::UpdateId};
+use nu_engine::{ClosureEvalOnce, command_prelude::*};
+use nu_protocol::engine::Closure;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct RefUpdate;
+
+impl Command for RefUpdate {
+    fn name(&self) -> &str {
+        "ref update"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ref update")
+            .input_output_types(vec![(Type::Custom("ref".into()), Type::Any)])
+            .required(
+                "closure",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any])),
+                "The closure computing the new value from the current one.",
+            )
+            .category(Category::Misc)
+    }
+
+    fn description(&self) -> &str {
+        "Replace the value of a reference with the result of a closure, and return the new value."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The closure gets the current value as its parameter and as its input. Updates of the same \
+        reference from several threads run one after the other, so none of them is lost. If the \
+        closure fails, the reference keeps its value. Updating the same reference from the \
+        closure, including in `par-each`, is an error."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["set", "swap", "atomic", "increment"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let closure: Closure = call.req(engine_state, stack, 0)?;
+        let reference = RefValue::from_value(&input.into_value(head)?)?;
+
+        // Background jobs can wait for an enclosing update, since the update doesn't wait for them
+        let job = engine_state.current_job.id.get();
+        let enclosing: Vec<UpdateId> = stack
+            .ref_updates
+            .iter()
+            .filter(|(update_job, _)| *update_job == job)
+            .map(|(_, id)| UpdateId::from_int(*id))
+            .collect();
+
+        reference
+            .update(head, engine_state.signals(), &enclosing, |value, id| {
+                let mut stack = stack.clone();
+                Arc::make_mut(&mut stack.ref_updates).push((job, id.as_int()));
+                ClosureEvalOnce::new(engine_state, &stack, closure)
+                    .run_with_value(value)?
+                    .into_value(head)
+            })
+            .map(|value| value.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Increment a counter.",
+                example: "let counter = ref new 0; $counter | ref update {|n| $n + 1 }",
+                result: Some(Value::test_int(1)),
+            },
+            Example {
+                description: "Collect the results of background jobs.",
+                example: "let results = ref new []; 1..3 | each {|i| job spawn { $results | ref update { append ($i * 2) } } } | ignore",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(RefUpdate)
+    }
+}
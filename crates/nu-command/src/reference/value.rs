@@ -0,0 +1,275 @@
+use nu_protocol::{
+    CustomValue, ShellError, Signals, Span, Value, shell_error::generic::GenericError,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    any::Any,
+    fmt,
+    sync::{
+        Arc, Condvar, Mutex, MutexGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+/// How often a waiting update checks for ctrl-c.
+const CTRL_C_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+static NEXT_UPDATE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Identifies one run of [`RefValue::update`], so that updates started by its closure, on any
+/// thread, can be told apart from unrelated ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateId(u64);
+
+impl UpdateId {
+    fn next() -> Self {
+        Self(NEXT_UPDATE_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn from_int(id: i64) -> Self {
+        Self(id as u64)
+    }
+
+    pub fn as_int(self) -> i64 {
+        self.0 as i64
+    }
+}
+
+struct State {
+    value: Value,
+    /// The update that's running, which the other updates wait for.
+    updating: Option<UpdateId>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    updated: Condvar,
+}
+
+/// A value shared by all copies of the reference, which updates replace atomically.
+#[derive(Clone)]
+pub struct RefValue(Arc<Shared>);
+
+impl RefValue {
+    pub fn new(value: Value) -> Self {
+        Self(Arc::new(Shared {
+            state: Mutex::new(State {
+                value,
+                updating: None,
+            }),
+            updated: Condvar::new(),
+        }))
+    }
+
+    pub fn into_value(self, span: Span) -> Value {
+        Value::custom(Box::new(self), span)
+    }
+
+    pub fn from_value(value: &Value) -> Result<Self, ShellError> {
+        let span = value.span();
+        match value {
+            Value::Custom { val, .. } => {
+                val.as_any().downcast_ref::<Self>().cloned().ok_or_else(|| {
+                    ShellError::CantConvert {
+                        to_type: "ref".into(),
+                        from_type: val.type_name(),
+                        span,
+                        help: Some("create a reference with `ref new`".into()),
+                    }
+                })
+            }
+            x => Err(ShellError::CantConvert {
+                to_type: "ref".into(),
+                from_type: x.get_type().to_string(),
+                span,
+                help: Some("create a reference with `ref new`".into()),
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.0.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The current value. While an update runs, this is the value before it.
+    pub fn get(&self) -> Value {
+        self.lock().value.clone()
+    }
+
+    /// Replaces the value with the result of `update`, returning the new value.
+    ///
+    /// Updates of the same reference run one at a time, so no update is lost. If `update` fails,
+    /// the value is kept as it was. `update` gets the id of this update, and `enclosing` are the
+    /// ids of the updates the caller runs in: waiting for one of those would never end, so that's
+    /// an error. Waiting stops when `signals` are interrupted.
+    pub fn update(
+        &self,
+        span: Span,
+        signals: &Signals,
+        enclosing: &[UpdateId],
+        update: impl FnOnce(Value, UpdateId) -> Result<Value, ShellError>,
+    ) -> Result<Value, ShellError> {
+        let mut state = self.lock();
+        loop {
+            match state.updating {
+                None => break,
+                Some(running) if enclosing.contains(&running) => {
+                    return Err(ShellError::Generic(
+                        GenericError::new(
+                            "Reference is already being updated",
+                            "this update runs inside another update of the same reference",
+                            span,
+                        )
+                        .with_help("return the new value from the outer update instead"),
+                    ));
+                }
+                Some(_) => {
+                    if signals.interrupted() {
+                        return Err(ShellError::Interrupted { span });
+                    }
+                    state = match self.0.updated.wait_timeout(state, CTRL_C_CHECK_INTERVAL) {
+                        Ok((state, _)) => state,
+                        Err(e) => e.into_inner().0,
+                    };
+                }
+            }
+        }
+        let id = UpdateId::next();
+        state.updating = Some(id);
+        let value = state.value.clone();
+        drop(state);
+
+        let result = update(value, id);
+
+        let mut state = self.lock();
+        if let Ok(value) = &result {
+            state.value = value.clone();
+        }
+        state.updating = None;
+        drop(state);
+        self.0.updated.notify_all();
+        result
+    }
+}
+
+impl fmt::Debug for RefValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RefValue").field(&self.lock().value).finish()
+    }
+}
+
+/// References are sent as their current value, so a plugin gets a reference of its own.
+impl Serialize for RefValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RefValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[typetag::serde]
+impl CustomValue for RefValue {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::custom(Box::new(self.clone()), span)
+    }
+
+    fn type_name(&self) -> String {
+        "ref".into()
+    }
+
+    fn to_base_value(&self, _span: Span) -> Result<Value, ShellError> {
+        Ok(self.get())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.lock().value.memory_size()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{sync::atomic::AtomicBool, thread};
+
+    fn increment(reference: &RefValue, enclosing: &[UpdateId]) -> Result<Value, ShellError> {
+        reference.update(
+            Span::test_data(),
+            &Signals::empty(),
+            enclosing,
+            |value, _| Ok(Value::test_int(value.as_int()? + 1)),
+        )
+    }
+
+    #[test]
+    fn nested_update_fails() {
+        let reference = RefValue::new(Value::test_int(0));
+        let result = reference.update(Span::test_data(), &Signals::empty(), &[], |value, id| {
+            assert!(increment(&reference, &[id]).is_err());
+            Ok(value)
+        });
+        assert_eq!(result, Ok(Value::test_int(0)));
+    }
+
+    #[test]
+    fn nested_update_on_other_thread_fails() {
+        let reference = RefValue::new(Value::test_int(0));
+        let result = reference.update(Span::test_data(), &Signals::empty(), &[], |value, id| {
+            let nested = reference.clone();
+            let result = thread::spawn(move || increment(&nested, &[id]))
+                .join()
+                .expect("nested update panicked");
+            assert!(result.is_err());
+            Ok(value)
+        });
+        assert_eq!(result, Ok(Value::test_int(0)));
+    }
+
+    #[test]
+    fn unrelated_update_waits() {
+        let reference = RefValue::new(Value::test_int(0));
+        let mut waiting = None;
+        let result = reference.update(Span::test_data(), &Signals::empty(), &[], |value, _| {
+            let other = reference.clone();
+            waiting = Some(thread::spawn(move || increment(&other, &[])));
+            Ok(Value::test_int(value.as_int()? + 10))
+        });
+        assert_eq!(result, Ok(Value::test_int(10)));
+
+        let waiting = waiting.expect("update should have run");
+        assert_eq!(
+            waiting.join().expect("waiting update panicked"),
+            Ok(Value::test_int(11))
+        );
+    }
+
+    #[test]
+    fn waiting_update_is_interrupted() {
+        let reference = RefValue::new(Value::test_int(0));
+        let interrupt = Arc::new(AtomicBool::new(false));
+        let signals = Signals::new(interrupt.clone());
+        let result = reference.update(Span::test_data(), &Signals::empty(), &[], |value, _| {
+            let other = reference.clone();
+            let waiting = thread::spawn(move || {
+                other.update(Span::test_data(), &signals, &[], |value, _| Ok(value))
+            });
+            interrupt.store(true, Ordering::Relaxed);
+            let result = waiting.join().expect("waiting update panicked");
+            assert!(matches!(result, Err(ShellError::Interrupted { .. })));
+            Ok(value)
+        });
+        assert_eq!(result, Ok(Value::test_int(0)));
+    }
+}
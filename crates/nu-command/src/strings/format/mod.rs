@@ -2,8 +2,10 @@ mod date;
 mod duration;
 mod filesize;
 mod format_;
+mod nu;
 
 pub use date::FormatDate;
 pub use duration::FormatDuration;
 pub use filesize::FormatFilesize;
 pub use format_::Format;
+pub use nu::FormatNu;
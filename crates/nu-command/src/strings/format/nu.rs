@@ -0,0 +1,121 @@
+use nu_engine::command_prelude::*;
+use nu_parser::format_source;
+use nu_protocol::shell_error::generic::GenericError;
+
+#[derive(Clone)]
+pub struct FormatNu;
+
+impl Command for FormatNu {
+    fn name(&self) -> &str {
+        "format nu"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("format nu")
+            .input_output_types(vec![(Type::String, Type::String)])
+            .named(
+                "indent-width",
+                SyntaxShape::Int,
+                "Number of spaces per indentation level (defaults to `$env.config.formatter.indent_width`).",
+                Some('i'),
+            )
+            .named(
+                "max-line-length",
+                SyntaxShape::Int,
+                "Length above which pipelines are split over several lines (defaults to `$env.config.formatter.max_line_length`).",
+                Some('l'),
+            )
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Format nushell code."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Spaces between the parts of a command are normalized, the contents of brackets spanning several lines are indented, and runs of blank lines are collapsed. Line breaks, comments and strings are kept as written. Use `nu --format` to format a script file in place."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["formatter", "prettify", "indent", "fmt", "style"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Normalize the spaces in a pipeline.",
+                example: "'ls   |   where size > 1kb' | format nu",
+                result: Some(Value::test_string("ls | where size > 1kb\n")),
+            },
+            Example {
+                description: "Indent a block by two spaces.",
+                example: "\"if true {\\nprint 'yes'\\n}\" | format nu --indent-width 2",
+                result: Some(Value::test_string("if true {\n  print 'yes'\n}\n")),
+            },
+            Example {
+                description: "Split a long pipeline over several lines.",
+                example: "'[1 2 3] | each {|x|$x * 2} | math sum' | format nu --max-line-length 30",
+                result: Some(Value::test_string(
+                    "[1 2 3]\n| each {|x| $x * 2 }\n| math sum\n",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let indent_width: Option<Spanned<i64>> =
+            call.get_flag(engine_state, stack, "indent-width")?;
+        let max_line_length: Option<Spanned<i64>> =
+            call.get_flag(engine_state, stack, "max-line-length")?;
+
+        let mut config = stack.get_config(engine_state).formatter;
+        if let Some(width) = indent_width {
+            if !(1..=16).contains(&width.item) {
+                return Err(ShellError::IncorrectValue {
+                    msg: "the indent width must be between 1 and 16".into(),
+                    val_span: width.span,
+                    call_span: head,
+                });
+            }
+            config.indent_width = width.item;
+        }
+        if let Some(length) = max_line_length {
+            if length.item < 20 {
+                return Err(ShellError::IncorrectValue {
+                    msg: "the maximum line length must be at least 20".into(),
+                    val_span: length.span,
+                    call_span: head,
+                });
+            }
+            config.max_line_length = length.item;
+        }
+
+        let (source, ..) = input.collect_string_strict(head)?;
+        let formatted = format_source(engine_state, source.as_bytes(), &config).map_err(|err| {
+            ShellError::Generic(GenericError::new(
+                "Can't format the code",
+                err.to_string(),
+                head,
+            ))
+        })?;
+
+        Ok(Value::string(formatted, head).into_pipeline_data())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(FormatNu)
+    }
+}
@@ -1,7 +1,7 @@
 use fancy_regex::{Captures, Regex, RegexBuilder};
 use nu_engine::command_prelude::*;
 use nu_protocol::shell_error::generic::GenericError;
-use nu_protocol::{ListStream, Signals, engine::StateWorkingSet};
+use nu_protocol::{ListStream, RegexConfig, Signals, engine::StateWorkingSet};
 use std::collections::VecDeque;
 
 #[derive(Clone)]
@@ -21,12 +21,25 @@ impl Command for Parse {
     }
 
     fn extra_description(&self) -> &str {
-        "The parse command always uses regular expressions even when you use a simple pattern. If a simple pattern is supplied, parse will transform that pattern into a regular expression."
+        "The parse command always uses regular expressions even when you use a simple pattern. If a simple pattern is supplied, parse will transform that pattern into a regular expression.
+
+The columns of a simple pattern can have a type, like `{pid:int}`, to only match text of that type and convert it. The types are `int`, `float`, `bool` and `string`, the default.
+
+With a list of patterns, they're tried in order for each string, and the first one that matches gives the columns of its rows.
+
+The --ignore-case, --multiline and --dotall flags turn on the options of the regular expressions, like the `(?i)`, `(?m)` and `(?s)` inline flags, on top of the defaults of `$env.config.regex`."
     }
 
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("parse")
-            .required("pattern", SyntaxShape::String, "The pattern to match.")
+            .required(
+                "pattern",
+                SyntaxShape::OneOf(vec![
+                    SyntaxShape::String,
+                    SyntaxShape::List(Box::new(SyntaxShape::String)),
+                ]),
+                "The pattern to match, or a list of patterns to try in order.",
+            )
             .input_output_types(vec![
                 (Type::String, Type::table()),
                 (Type::List(Box::new(Type::Any)), Type::table()),
@@ -38,6 +51,17 @@ impl Command for Parse {
                 "Set the max backtrack limit for regex.",
                 Some('b'),
             )
+            .switch(
+                "ignore-case",
+                "Match letters regardless of their case.",
+                Some('i'),
+            )
+            .switch(
+                "multiline",
+                "Make `^` and `$` match at the start and end of every line.",
+                Some('m'),
+            )
+            .switch("dotall", "Make `.` also match newlines.", Some('s'))
             .allow_variants_without_examples(true)
             .category(Category::Strings)
     }
@@ -103,6 +127,40 @@ impl Command for Parse {
                     "capture0" => Value::test_string("b"),
                 })])),
             },
+            Example {
+                description: "Parse a string into typed columns.",
+                example: r#""pid 42 took 1.5s" | parse "pid {pid:int} took {time:float}s""#,
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "pid" => Value::test_int(42),
+                    "time" => Value::test_float(1.5),
+                })])),
+            },
+            Example {
+                description: "Parse log lines of different shapes, trying the patterns in order.",
+                example: r#"["GET /index 200" "error: timeout"] | parse ["{method} {path} {status:int}" "error: {message}"]"#,
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "method" => Value::test_string("GET"),
+                        "path" => Value::test_string("/index"),
+                        "status" => Value::test_int(200),
+                    }),
+                    Value::test_record(record! {
+                        "message" => Value::test_string("timeout"),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Parse the lines of a string, whatever the case of their keys.",
+                example: r#""Name: nu\nVERSION: 0.1" | parse --regex --ignore-case --multiline '^(?:name|version): (?<value>.*)$'"#,
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "value" => Value::test_string("nu"),
+                    }),
+                    Value::test_record(record! {
+                        "value" => Value::test_string("0.1"),
+                    }),
+                ])),
+            },
             Example {
                 description: "Parse a string with a manually set fancy-regex backtrack limit.",
                 example: r#""hi there" | parse --backtrack 1500000 "{foo} {bar}""#,
@@ -125,12 +183,26 @@ impl Command for Parse {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let pattern: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let pattern: Value = call.req(engine_state, stack, 0)?;
         let regex: bool = call.has_flag(engine_state, stack, "regex")?;
         let backtrack_limit: usize = call
             .get_flag(engine_state, stack, "backtrack")?
             .unwrap_or(1_000_000); // 1_000_000 is fancy_regex default
-        operate(engine_state, pattern, regex, backtrack_limit, call, input)
+        let options = RegexConfig {
+            case_insensitive: call.has_flag(engine_state, stack, "ignore-case")?,
+            multiline: call.has_flag(engine_state, stack, "multiline")?,
+            dotall: call.has_flag(engine_state, stack, "dotall")?,
+        }
+        .or(stack.get_config(engine_state).regex);
+        operate(
+            engine_state,
+            pattern,
+            regex,
+            backtrack_limit,
+            options,
+            call,
+            input,
+        )
     }
 
     fn run_const(
@@ -139,70 +211,175 @@ impl Command for Parse {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let pattern: Spanned<String> = call.req_const(working_set, 0)?;
+        let pattern: Value = call.req_const(working_set, 0)?;
         let regex: bool = call.has_flag_const(working_set, "regex")?;
         let backtrack_limit: usize = call
             .get_flag_const(working_set, "backtrack")?
             .unwrap_or(1_000_000);
+        let options = RegexConfig {
+            case_insensitive: call.has_flag_const(working_set, "ignore-case")?,
+            multiline: call.has_flag_const(working_set, "multiline")?,
+            dotall: call.has_flag_const(working_set, "dotall")?,
+        }
+        .or(working_set.permanent().get_config().regex);
         operate(
             working_set.permanent(),
             pattern,
             regex,
             backtrack_limit,
+            options,
             call,
             input,
         )
     }
 }
 
+/// A compiled pattern, with the names of its columns and the types to convert them to.
+struct Pattern {
+    regex: Regex,
+    columns: Vec<(String, CaptureType)>,
+}
+
+impl Pattern {
+    fn new(
+        pattern: Spanned<String>,
+        regex: bool,
+        backtrack_limit: usize,
+        options: RegexConfig,
+    ) -> Result<Self, ShellError> {
+        let (item_to_parse, types) = if regex {
+            (pattern.item, vec![])
+        } else {
+            build_regex(&pattern.item, pattern.span)?
+        };
+        let item_to_parse = format!("{}{item_to_parse}", options.inline_flags());
+
+        let regex = RegexBuilder::new(&item_to_parse)
+            .backtrack_limit(backtrack_limit)
+            .build()
+            .map_err(|e| {
+                ShellError::Generic(GenericError::new(
+                    "Error with regular expression",
+                    e.to_string(),
+                    pattern.span,
+                ))
+            })?;
+
+        let columns = regex
+            .capture_names()
+            .skip(1)
+            .enumerate()
+            .map(|(i, name)| {
+                let name = name
+                    .map(String::from)
+                    .unwrap_or_else(|| format!("capture{i}"));
+                (name, types.get(i).copied().unwrap_or(CaptureType::String))
+            })
+            .collect();
+
+        Ok(Pattern { regex, columns })
+    }
+}
+
+/// The type of a column of a simple pattern, like `int` in `{pid:int}`.
+#[derive(Clone, Copy)]
+enum CaptureType {
+    String,
+    Int,
+    Float,
+    Bool,
+}
+
+impl CaptureType {
+    fn from_name(name: &str, span: Span) -> Result<Self, ShellError> {
+        match name {
+            "string" => Ok(CaptureType::String),
+            "int" => Ok(CaptureType::Int),
+            "float" => Ok(CaptureType::Float),
+            "bool" => Ok(CaptureType::Bool),
+            _ => Err(ShellError::Generic(
+                GenericError::new(
+                    "Unknown capture type",
+                    format!("`{name}` isn't a type of capture"),
+                    span,
+                )
+                .with_help("use one of string, int, float or bool"),
+            )),
+        }
+    }
+
+    /// The regular expression matching the text of this type.
+    fn regex(self) -> &'static str {
+        match self {
+            CaptureType::String => ".*?",
+            CaptureType::Int => r"[+-]?\d+",
+            CaptureType::Float => r"[+-]?(?:\d+(?:\.\d*)?|\.\d+)(?:[eE][+-]?\d+)?",
+            CaptureType::Bool => "(?:true|false)",
+        }
+    }
+
+    fn convert(self, text: &str, span: Span) -> Result<Value, ShellError> {
+        let cant_convert = || ShellError::CantConvert {
+            to_type: self.name().into(),
+            from_type: "string".into(),
+            span,
+            help: Some(format!("`{text}` can't be converted to {}", self.name())),
+        };
+        Ok(match self {
+            CaptureType::String => Value::string(text, span),
+            CaptureType::Int => Value::int(text.parse().map_err(|_| cant_convert())?, span),
+            CaptureType::Float => Value::float(text.parse().map_err(|_| cant_convert())?, span),
+            CaptureType::Bool => Value::bool(text.parse().map_err(|_| cant_convert())?, span),
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            CaptureType::String => "string",
+            CaptureType::Int => "int",
+            CaptureType::Float => "float",
+            CaptureType::Bool => "bool",
+        }
+    }
+}
+
 fn operate(
     engine_state: &EngineState,
-    pattern: Spanned<String>,
+    pattern: Value,
     regex: bool,
     backtrack_limit: usize,
+    options: RegexConfig,
     call: &Call,
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let head = call.head;
 
-    let pattern_item = pattern.item;
-    let pattern_span = pattern.span;
-
-    let item_to_parse = if regex {
-        pattern_item
-    } else {
-        build_regex(&pattern_item, pattern_span)?
+    let pattern_span = pattern.span();
+    let patterns = match pattern {
+        Value::List { vals, .. } => vals,
+        pattern => vec![pattern],
     };
-
-    let regex = RegexBuilder::new(&item_to_parse)
-        .backtrack_limit(backtrack_limit)
-        .build()
-        .map_err(|e| {
-            ShellError::Generic(GenericError::new(
-                "Error with regular expression",
-                e.to_string(),
-                pattern_span,
-            ))
-        })?;
-
-    let columns = regex
-        .capture_names()
-        .skip(1)
-        .enumerate()
-        .map(|(i, name)| {
-            name.map(String::from)
-                .unwrap_or_else(|| format!("capture{i}"))
+    if patterns.is_empty() {
+        return Err(ShellError::Generic(GenericError::new(
+            "No pattern to match",
+            "expected at least one pattern",
+            pattern_span,
+        )));
+    }
+    let patterns = patterns
+        .into_iter()
+        .map(|pattern| {
+            let span = pattern.span();
+            let item = pattern.coerce_into_string()?;
+            Pattern::new(Spanned { item, span }, regex, backtrack_limit, options)
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
 
     match input {
         PipelineData::Empty => Ok(PipelineData::empty()),
         PipelineData::Value(value, ..) => match value {
             Value::String { val, .. } => {
-                let captures = regex
-                    .captures_iter(&val)
-                    .map(|captures| captures_to_value(captures, &columns, head))
-                    .collect::<Result<_, _>>()?;
+                let captures = match_patterns(&patterns, &val, head)?;
 
                 Ok(Value::list(captures, head).into_pipeline_data())
             }
@@ -221,8 +398,7 @@ fn operate(
 
                 let iter = ParseIter {
                     captures: VecDeque::new(),
-                    regex,
-                    columns,
+                    patterns,
                     iter,
                     span: head,
                     signals: engine_state.signals().clone(),
@@ -250,8 +426,7 @@ fn operate(
 
                 ParseIter {
                     captures: VecDeque::new(),
-                    regex,
-                    columns,
+                    patterns,
                     iter,
                     span: head,
                     signals: engine_state.signals().clone(),
@@ -261,18 +436,17 @@ fn operate(
         PipelineData::ByteStream(stream, ..) => {
             let val = stream.into_string()?;
 
-            let captures = regex
-                .captures_iter(&val)
-                .map(|captures| captures_to_value(captures, &columns, head))
-                .collect::<Result<_, _>>()?;
+            let captures = match_patterns(&patterns, &val, head)?;
 
             Ok(Value::list(captures, head).into_pipeline_data())
         }
     }
 }
 
-fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
+/// Build the regular expression of a simple pattern, and the types of its columns.
+fn build_regex(input: &str, span: Span) -> Result<(String, Vec<CaptureType>), ShellError> {
     let mut output = r#"(?s)\A"#.to_string();
+    let mut types = vec![];
 
     // Single-pass scanner keeps parsing state explicit and avoids byte-offset bookkeeping.
     let mut loop_input = input.char_indices().peekable();
@@ -315,17 +489,23 @@ fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
 
         if c == '}' {
             if !column.is_empty() {
+                let (name, type_) = match column.split_once(':') {
+                    Some((name, type_)) => (name, CaptureType::from_name(type_.trim(), span)?),
+                    None => (column.as_str(), CaptureType::String),
+                };
                 output.push_str("(?");
-                if column == "_" {
+                if name == "_" {
                     // discard placeholder column(s)
                     output.push(':');
                 } else {
                     // create capture group for column
                     output.push_str("P<");
-                    output.push_str(&column);
+                    output.push_str(name);
                     output.push('>');
+                    types.push(type_);
                 }
-                output.push_str(".*?)");
+                output.push_str(type_.regex());
+                output.push(')');
                 column.clear();
             }
 
@@ -347,7 +527,7 @@ fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
     }
 
     output.push_str(r#"\z"#);
-    Ok(output)
+    Ok((output, types))
 }
 
 /// Returns true when the remainder after the second `{` in `{{` forms a trailing capture.
@@ -361,8 +541,7 @@ fn is_trailing_capture(after: &str) -> bool {
 
 struct ParseIter<I: Iterator<Item = Result<String, ShellError>>> {
     captures: VecDeque<Value>,
-    regex: Regex,
-    columns: Vec<String>,
+    patterns: Vec<Pattern>,
     iter: I,
     span: Span,
     signals: Signals,
@@ -370,10 +549,8 @@ struct ParseIter<I: Iterator<Item = Result<String, ShellError>>> {
 
 impl<I: Iterator<Item = Result<String, ShellError>>> ParseIter<I> {
     fn populate_captures(&mut self, str: &str) -> Result<(), ShellError> {
-        for captures in self.regex.captures_iter(str) {
-            self.captures
-                .push_back(captures_to_value(captures, &self.columns, self.span)?);
-        }
+        self.captures
+            .extend(match_patterns(&self.patterns, str, self.span)?);
         Ok(())
     }
 }
@@ -403,9 +580,24 @@ impl<I: Iterator<Item = Result<String, ShellError>>> Iterator for ParseIter<I> {
     }
 }
 
+/// The rows of the first pattern that matches the text.
+fn match_patterns(patterns: &[Pattern], text: &str, span: Span) -> Result<Vec<Value>, ShellError> {
+    for pattern in patterns {
+        let rows = pattern
+            .regex
+            .captures_iter(text)
+            .map(|captures| captures_to_value(captures, &pattern.columns, span))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !rows.is_empty() {
+            return Ok(rows);
+        }
+    }
+    Ok(vec![])
+}
+
 fn captures_to_value(
     captures: Result<Captures, fancy_regex::Error>,
-    columns: &[String],
+    columns: &[(String, CaptureType)],
     span: Span,
 ) -> Result<Value, ShellError> {
     let captures = captures.map_err(|err| {
@@ -419,13 +611,14 @@ fn captures_to_value(
     let record = columns
         .iter()
         .zip(captures.iter().skip(1))
-        .map(|(column, match_)| {
-            let match_value = match_
-                .map(|m| Value::string(m.as_str(), span))
-                .unwrap_or(Value::nothing(span));
-            (column.clone(), match_value)
+        .map(|((column, type_), match_)| {
+            let match_value = match match_ {
+                Some(m) => type_.convert(m.as_str(), span)?,
+                None => Value::nothing(span),
+            };
+            Ok((column.clone(), match_value))
         })
-        .collect();
+        .collect::<Result<_, ShellError>>()?;
 
     Ok(Value::record(record, span))
 }
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use fancy_regex::{Regex, escape};
 use nu_engine::command_prelude::*;
-use nu_protocol::shell_error::generic::GenericError;
+use nu_protocol::{FromValue, shell_error::generic::GenericError};
 
 use super::split;
 
@@ -40,6 +42,12 @@ impl Command for SplitColumn {
                 None,
             )
             .switch("regex", "Separator is a regular expression.", Some('r'))
+            .named(
+                "trim",
+                SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::record()]),
+                "Trim the whitespace of the columns: left, right, both or none, or a record of these for each column.",
+                Some('t'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::String,
@@ -52,8 +60,13 @@ impl Command for SplitColumn {
         "Split a string into multiple columns using a separator."
     }
 
+    fn extra_description(&self) -> &str {
+        "With --regex, the separator can use the whole regular expression syntax, including look \
+around, like `(?<=\\d),(?=\\d)` to only split on commas between digits."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["separate", "divide", "regex"]
+        vec!["separate", "divide", "regex", "trim"]
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -104,6 +117,23 @@ impl Command for SplitColumn {
                     }),
                 ])),
             },
+            Example {
+                description: "Split on commas between digits only, trimming the columns.",
+                example: r"'1,2 ,  x, y' | split column --regex '(?<=\d)\s*,' --trim both",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                        "column0" => Value::test_string("1"),
+                        "column1" => Value::test_string("2"),
+                        "column2" => Value::test_string("x, y"),
+                })])),
+            },
+            Example {
+                description: "Trim only some of the columns.",
+                example: "'  key =  value  ' | split column '=' key value --trim {key: both, value: left}",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                        "key" => Value::test_string("key"),
+                        "value" => Value::test_string("value  "),
+                })])),
+            },
             Example {
                 description: "Split into columns, last column may contain the delimiter.",
                 example: "['author: Salina Yoon' r#'title: Where's Ellie?: A Hide-and-Seek Book'#] | split column --number 2 ': ' key value",
@@ -156,6 +186,9 @@ impl Command for SplitColumn {
         let max_split: Option<usize> = call.get_flag(engine_state, stack, "number")?;
         let split_from_right = call.has_flag(engine_state, stack, "right")?;
         let has_regex = call.has_flag(engine_state, stack, "regex")?;
+        let trim = call
+            .get_flag(engine_state, stack, "trim")?
+            .unwrap_or_default();
 
         let args = Arguments {
             separator,
@@ -164,6 +197,7 @@ impl Command for SplitColumn {
             max_split,
             split_from_right,
             has_regex,
+            trim,
         };
         split_column(engine_state, call, input, args)
     }
@@ -180,6 +214,9 @@ impl Command for SplitColumn {
         let max_split: Option<usize> = call.get_flag_const(working_set, "number")?;
         let split_from_right = call.has_flag_const(working_set, "right")?;
         let has_regex = call.has_flag_const(working_set, "regex")?;
+        let trim = call
+            .get_flag_const(working_set, "trim")?
+            .unwrap_or_default();
 
         let args = Arguments {
             separator,
@@ -188,6 +225,7 @@ impl Command for SplitColumn {
             max_split,
             split_from_right,
             has_regex,
+            trim,
         };
         split_column(working_set.permanent(), call, input, args)
     }
@@ -200,6 +238,85 @@ struct Arguments {
     max_split: Option<usize>,
     split_from_right: bool,
     has_regex: bool,
+    trim: Trimming,
+}
+
+/// Which whitespace to trim from a column.
+#[derive(Clone, Copy, Default)]
+enum Trim {
+    Left,
+    Right,
+    Both,
+    #[default]
+    None,
+}
+
+impl Trim {
+    fn apply(self, s: &str) -> &str {
+        match self {
+            Trim::Left => s.trim_start(),
+            Trim::Right => s.trim_end(),
+            Trim::Both => s.trim(),
+            Trim::None => s,
+        }
+    }
+}
+
+impl FromValue for Trim {
+    fn from_value(v: Value) -> Result<Self, ShellError> {
+        let span = v.span();
+        let s = <String>::from_value(v)?;
+        match s.as_str() {
+            "left" => Ok(Trim::Left),
+            "right" => Ok(Trim::Right),
+            "both" => Ok(Trim::Both),
+            "none" => Ok(Trim::None),
+            _ => Err(ShellError::InvalidValue {
+                valid: "one of: left, right, both, none".into(),
+                actual: s,
+                span,
+            }),
+        }
+    }
+}
+
+/// How to trim the columns, either all of them the same way or each by its name.
+#[derive(Default)]
+struct Trimming {
+    all: Trim,
+    columns: HashMap<String, Trim>,
+}
+
+impl Trimming {
+    fn apply<'a>(&self, column: &str, s: &'a str) -> &'a str {
+        self.columns
+            .get(column)
+            .copied()
+            .unwrap_or(self.all)
+            .apply(s)
+    }
+}
+
+impl FromValue for Trimming {
+    fn from_value(v: Value) -> Result<Self, ShellError> {
+        match v {
+            Value::Record { val, .. } => {
+                let columns = val
+                    .into_owned()
+                    .into_iter()
+                    .map(|(column, trim)| Ok((column, Trim::from_value(trim)?)))
+                    .collect::<Result<_, ShellError>>()?;
+                Ok(Trimming {
+                    all: Trim::None,
+                    columns,
+                })
+            }
+            v => Ok(Trimming {
+                all: Trim::from_value(v)?,
+                columns: HashMap::new(),
+            }),
+        }
+    }
 }
 
 fn split_column(
@@ -232,6 +349,7 @@ fn split_column(
                 args.collapse_empty,
                 args.max_split,
                 args.split_from_right,
+                &args.trim,
                 name_span,
             )
         },
@@ -246,6 +364,7 @@ fn split_column_helper(
     collapse_empty: bool,
     max_split: Option<usize>,
     split_from_right: bool,
+    trim: &Trimming,
     head: Span,
 ) -> Vec<Value> {
     if let Ok(s) = v.as_str() {
@@ -282,11 +401,11 @@ fn split_column_helper(
             }
 
             for (&k, v) in split_result.iter().zip(&gen_columns) {
-                record.push(v, Value::string(k, head));
+                record.push(v, Value::string(trim.apply(v, k), head));
             }
         } else {
             for (&k, v) in split_result.iter().zip(&positional) {
-                record.push(v, Value::string(k, head));
+                record.push(v, Value::string(trim.apply(v, k), head));
             }
         }
         vec![Value::record(record, head)]
@@ -99,7 +99,8 @@ impl Command for StrDowncase {
             report_mode: ReportMode::FirstUse,
             since: Some("0.114.0".into()),
             expected_removal: None,
-            help: Some("Use `str lowercase` instead.".into()),
+            replacement: Some("str lowercase".into()),
+            help: None,
         }]
     }
 }
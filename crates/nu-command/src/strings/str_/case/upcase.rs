@@ -76,7 +76,8 @@ impl Command for StrUpcase {
             report_mode: ReportMode::FirstUse,
             since: Some("0.114.0".into()),
             expected_removal: None,
-            help: Some("Use `str uppercase` instead.".into()),
+            replacement: Some("str uppercase".into()),
+            help: None,
         }]
     }
 }
@@ -14,7 +14,9 @@ impl Command for StrExpand {
     }
 
     fn extra_description(&self) -> &str {
-        "This syntax may seem familiar with `glob {A,B}.C`. The difference is glob relies on filesystem, but str expand is not. Inside braces, we put variants. Then basically we're creating all possible outcomes."
+        "This syntax may seem familiar with `glob {A,B}.C`. The difference is glob relies on filesystem, but str expand is not. Inside braces, we put variants. Then basically we're creating all possible outcomes.
+
+Numeric ranges can have a step, like `{1..10..2}`, and are zero padded when one of their limits starts with a zero, like `{01..12}`."
     }
 
     fn signature(&self) -> Signature {
@@ -179,6 +181,18 @@ impl Command for StrExpand {
                     Span::test_data(),
                 )),
             },
+            Example {
+                description: "Use a step in numeric ranges, which can be zero padded too.",
+                example: "\"img{01..12..4}.png\" | str expand",
+                result: Some(Value::list(
+                    vec![
+                        Value::test_string("img01.png"),
+                        Value::test_string("img05.png"),
+                        Value::test_string("img09.png"),
+                    ],
+                    Span::test_data(),
+                )),
+            },
         ]
     }
 
@@ -248,7 +262,23 @@ fn str_expand(contents: &str, span: Span, value_span: Span) -> Value {
         parser::{ParsingError, parse},
         tokenizer::{TokenizationError, tokenize},
     };
-    match tokenize(contents) {
+    let contents = match expand_stepped_ranges(contents) {
+        Ok(contents) => contents,
+        Err(range) => {
+            return Value::error(
+                ShellError::Generic(
+                    GenericError::new(
+                        "Invalid Range Step",
+                        format!("The step of `{{{range}}}` is zero."),
+                        value_span,
+                    )
+                    .with_help("Range with a step are defined like `{M..N..S}`, where S is the non zero number to count by."),
+                ),
+                span,
+            );
+        }
+    };
+    match tokenize(&contents) {
         Ok(tokens) => {
             match parse(&tokens) {
                 Ok(node) => {
@@ -401,10 +431,124 @@ fn str_expand(contents: &str, span: Span, value_span: Span) -> Value {
     }
 }
 
+/// Rewrite the numeric ranges with a step, like `{1..10..3}`, into collections, like
+/// `{1,4,7,10}`, which brace expansion supports. Returns the range when its step is zero.
+fn expand_stepped_ranges(contents: &str) -> Result<String, String> {
+    let mut output = String::with_capacity(contents.len());
+    let mut rest = contents;
+    while let Some(c) = rest.chars().next() {
+        let len = c.len_utf8();
+        match c {
+            '\\' => {
+                // Escaped characters are left for the brace expansion
+                let escaped = rest[len..].chars().next().map_or(0, char::len_utf8);
+                output.push_str(&rest[..len + escaped]);
+                rest = &rest[len + escaped..];
+            }
+            '{' => {
+                let range = rest[len..]
+                    .find(['{', '}', ',', '\\'])
+                    .filter(|&end| rest[len + end..].starts_with('}'))
+                    .map(|end| &rest[len..len + end]);
+                if let Some(range) = range
+                    && let Some(values) = stepped_range(range)?
+                {
+                    output.push('{');
+                    output.push_str(&values.join(","));
+                    output.push('}');
+                    rest = &rest[len + range.len() + 1..];
+                } else {
+                    output.push(c);
+                    rest = &rest[len..];
+                }
+            }
+            c => {
+                output.push(c);
+                rest = &rest[len..];
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// The values of a `M..N..S` range, or `None` when it's not a range with a step. Returns the
+/// range as the error when its step is zero.
+fn stepped_range(range: &str) -> Result<Option<Vec<String>>, String> {
+    let limits = range.split("..").collect::<Vec<_>>();
+    let [start, end, step] = limits[..] else {
+        return Ok(None);
+    };
+    let (Ok(first), Ok(last), Ok(step)) = (
+        start.parse::<i64>(),
+        end.parse::<i64>(),
+        step.parse::<i64>(),
+    ) else {
+        return Ok(None);
+    };
+    // The direction comes from the limits, so the sign of the step doesn't matter
+    let step = step.checked_abs().unwrap_or(i64::MAX);
+    if step == 0 {
+        return Err(range.to_string());
+    }
+
+    // Like in bash, a limit starting with a zero pads the values to the widest limit
+    let padded = |limit: &str| {
+        let digits = limit.trim_start_matches(['-', '+']);
+        digits.len() > 1 && digits.starts_with('0')
+    };
+    let width = if padded(start) || padded(end) {
+        start.len().max(end.len())
+    } else {
+        0
+    };
+
+    let mut values = vec![];
+    let mut value = Some(first);
+    while let Some(current) = value.filter(|current| {
+        if first <= last {
+            *current <= last
+        } else {
+            *current >= last
+        }
+    }) {
+        values.push(format!("{current:0width$}"));
+        value = if first <= last {
+            current.checked_add(step)
+        } else {
+            current.checked_sub(step)
+        };
+    }
+    Ok(Some(values))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_stepped_ranges() {
+        let expand = |contents| {
+            let Value::List { vals, .. } =
+                str_expand(contents, Span::test_data(), Span::test_data())
+            else {
+                panic!("expected a list for {contents}");
+            };
+            vals.into_iter()
+                .map(|val| val.into_string().unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(expand("{1..10..3}"), ["1", "4", "7", "10"]);
+        assert_eq!(expand("{10..1..-4}"), ["10", "6", "2"]);
+        assert_eq!(expand("{-3..03..3}"), ["-3", "00", "03"]);
+        assert_eq!(expand("a{x,{1..5..2}}"), ["ax", "a1", "a3", "a5"]);
+        assert_eq!(expand(r"\{1..3..2\}{a,b}"), ["{1..3..2}a", "{1..3..2}b"]);
+        assert!(matches!(
+            str_expand("{1..5..0}", Span::test_data(), Span::test_data()),
+            Value::Error { .. }
+        ));
+    }
+
     #[test]
     fn test_zero_padding_actual_zero() {
         assert_eq!(
@@ -9,6 +9,7 @@ mod join;
 mod length;
 mod replace;
 mod reverse;
+mod similarity;
 mod starts_with;
 mod stats;
 mod substring;
@@ -25,6 +26,7 @@ pub use join::*;
 pub use length::StrLength;
 pub use replace::StrReplace;
 pub use reverse::StrReverse;
+pub use similarity::StrSimilarity;
 pub use starts_with::StrStartsWith;
 pub use stats::StrStats;
 pub use substring::StrSubstring;
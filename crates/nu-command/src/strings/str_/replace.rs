@@ -1,6 +1,7 @@
 use fancy_regex::{Captures, NoExpand, Regex};
 use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::{ClosureEval, command_prelude::*};
+use nu_protocol::RegexConfig;
 use std::sync::Arc;
 
 enum ReplacementValue {
@@ -28,20 +29,21 @@ enum Matcher {
 }
 
 impl Matcher {
+    /// A literal substring is only matched as a regex when it ignores case.
     fn new(
         find: Spanned<String>,
         regex: bool,
-        multiline: bool,
+        options: RegexConfig,
         head: Span,
     ) -> Result<Self, ShellError> {
-        if !regex && !multiline {
+        if !regex && !options.case_insensitive {
             Ok(Self::Literal(find))
         } else {
             let Spanned { item, span } = find;
-            let pattern = if multiline {
-                format!("(?m){item}")
+            let pattern = if regex {
+                format!("{}{item}", options.inline_flags())
             } else {
-                item
+                format!("(?i){}", fancy_regex::escape(&item))
             };
             Regex::new(&pattern)
                 .map(Self::Regex)
@@ -100,6 +102,16 @@ impl Command for StrReplace {
                 "Multi-line regex mode (implies --regex): ^ and $ match begin/end of line; equivalent to (?m).",
                 Some('m'),
             )
+            .switch(
+                "dotall",
+                "Dot matches newlines (implies --regex); equivalent to (?s).",
+                Some('s'),
+            )
+            .switch(
+                "ignore-case",
+                "Match the pattern regardless of case; equivalent to (?i) with --regex.",
+                Some('i'),
+            )
             .allow_variants_without_examples(true)
             .category(Category::Strings)
     }
@@ -115,6 +127,9 @@ The replacement can be a string, possibly containing references to numbered (`$1
 named capture groups (`$name`), or it can be a closure that is invoked for each match.
 In the latter case, the closure is invoked with the entire match as its input and any capture
 groups as its argument. It must return a string that will be used as a replacement for the match.
+
+Regular expressions also get the options of `$env.config.regex` turned on, which the flags and the
+inline flags of the pattern, like `(?-i)`, can add to or override.
 "
     }
 
@@ -152,13 +167,24 @@ groups as its argument. It must return a string that will be used as a replaceme
         }?;
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 2)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
-        let multiline = call.has_flag(engine_state, stack, "multiline")?;
-        let regex = call.has_flag(engine_state, stack, "regex")?;
-        let literal_replace = call.has_flag(engine_state, stack, "no-expand")?;
+        let options = RegexConfig {
+            case_insensitive: call.has_flag(engine_state, stack, "ignore-case")?,
+            multiline: call.has_flag(engine_state, stack, "multiline")?,
+            dotall: call.has_flag(engine_state, stack, "dotall")?,
+        };
+        let regex =
+            call.has_flag(engine_state, stack, "regex")? || options.multiline || options.dotall;
+        let options = if regex {
+            options.or(stack.get_config(engine_state).regex)
+        } else {
+            options
+        };
+        // A literal pattern has no captures to expand, even when it's matched as a regex
+        let literal_replace = call.has_flag(engine_state, stack, "no-expand")? || !regex;
 
         let args = Arguments {
             all: call.has_flag(engine_state, stack, "all")?,
-            matcher: Matcher::new(find, regex, multiline, call.head)?,
+            matcher: Matcher::new(find, regex, options, call.head)?,
             replace,
             cell_paths,
             literal_replace,
@@ -176,13 +202,23 @@ groups as its argument. It must return a string that will be used as a replaceme
         let replace: Spanned<String> = call.req_const(working_set, 1)?;
         let cell_paths: Vec<CellPath> = call.rest_const(working_set, 2)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
-        let multiline = call.has_flag_const(working_set, "multiline")?;
-        let regex = call.has_flag_const(working_set, "regex")?;
-        let literal_replace = call.has_flag_const(working_set, "no-expand")?;
+        let options = RegexConfig {
+            case_insensitive: call.has_flag_const(working_set, "ignore-case")?,
+            multiline: call.has_flag_const(working_set, "multiline")?,
+            dotall: call.has_flag_const(working_set, "dotall")?,
+        };
+        let regex =
+            call.has_flag_const(working_set, "regex")? || options.multiline || options.dotall;
+        let options = if regex {
+            options.or(working_set.permanent().get_config().regex)
+        } else {
+            options
+        };
+        let literal_replace = call.has_flag_const(working_set, "no-expand")? || !regex;
 
         let args = Arguments {
             all: call.has_flag_const(working_set, "all")?,
-            matcher: Matcher::new(find, regex, multiline, call.head)?,
+            matcher: Matcher::new(find, regex, options, call.head)?,
             replace: ReplacementValue::String(Arc::new(replace)),
             cell_paths,
             literal_replace,
@@ -268,6 +304,16 @@ groups as its argument. It must return a string that will be used as a replaceme
                     "non-matching line\none line\nanother line\n",
                 )),
             },
+            Example {
+                description: "Find and replace a substring regardless of its case.",
+                example: "'Nu nu NU' | str replace --all --ignore-case 'nu' 'shell'",
+                result: Some(Value::test_string("shell shell shell")),
+            },
+            Example {
+                description: "Find and replace across lines, letting `.` match newlines.",
+                example: r#""start\nfirst\nsecond\nend" | str replace --dotall 'start.*end' 'done'"#,
+                result: Some(Value::test_string("done")),
+            },
             Example {
                 description: "Find and replace backslash escape sequences using a closure.",
                 example: r#"'string: \"abc\" backslash: \\ newline:\nend' | str replace -a -r '\\(.)' {|char| if $char == "n" { "\n" } else { $char } }"#,
@@ -446,7 +492,7 @@ mod tests {
             matcher: Matcher::new(
                 test_spanned_string("Cargo.(.+)"),
                 true,
-                false,
+                RegexConfig::default(),
                 Span::test_data(),
             )
             .unwrap(),
@@ -0,0 +1,352 @@
+use std::sync::Mutex;
+
+use nu_cmd_base::input_handler::{CmdArgument, operate};
+use nu_engine::command_prelude::*;
+use nu_protocol::{FromValue, engine::StateWorkingSet, levenshtein_distance};
+use nucleo_matcher::{
+    Config as NucleoConfig, Matcher as NucleoMatcher, Utf32Str,
+    pattern::{Atom, AtomKind, CaseMatching, Normalization},
+};
+
+#[derive(Clone)]
+pub struct StrSimilarity;
+
+struct Arguments {
+    compare_string: String,
+    algorithm: Algorithm,
+    ignore_case: bool,
+    matcher: Mutex<NucleoMatcher>,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+enum Algorithm {
+    #[default]
+    Levenshtein,
+    JaroWinkler,
+    Fuzzy,
+}
+
+impl FromValue for Algorithm {
+    fn from_value(v: Value) -> Result<Self, ShellError> {
+        let span = v.span();
+        let s = <String>::from_value(v)?;
+        match s.as_str() {
+            "levenshtein" => Ok(Algorithm::Levenshtein),
+            "jaro-winkler" => Ok(Algorithm::JaroWinkler),
+            "fuzzy" => Ok(Algorithm::Fuzzy),
+            _ => Err(ShellError::InvalidValue {
+                valid: "one of: levenshtein, jaro-winkler, fuzzy".into(),
+                actual: s,
+                span,
+            }),
+        }
+    }
+}
+
+impl Command for StrSimilarity {
+    fn name(&self) -> &str {
+        "str similarity"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str similarity")
+            .input_output_types(vec![
+                (Type::String, Type::Number),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::Number)),
+                ),
+                (Type::table(), Type::table()),
+                (Type::record(), Type::record()),
+            ])
+            .allow_variants_without_examples(true)
+            .required(
+                "compare-string",
+                SyntaxShape::String,
+                "The string to compare with.",
+            )
+            .param(
+                Flag::new("algorithm")
+                    .short('a')
+                    .arg(SyntaxShape::String)
+                    .desc("The algorithm to score with: levenshtein (default), jaro-winkler or fuzzy.")
+                    .completion(Completion::new_list(&["levenshtein", "jaro-winkler", "fuzzy"])),
+            )
+            .switch("ignore-case", "Compare the strings case insensitively.", Some('i'))
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, score strings at the given cell paths, and replace with result.",
+            )
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Score how similar strings are to another string."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The levenshtein and jaro-winkler scores are floats from 0, for nothing in common, to 1, for \
+equal strings. The levenshtein score is the edit distance relative to the length of the longest \
+string, and jaro-winkler favors strings with a common prefix, which suits short strings like names.
+
+The fuzzy score is an int, as used by fuzzy finders to match the compare string as a pattern in the \
+input: the higher, the better the match, and 0 when it doesn't match at all."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "levenshtein",
+            "jaro",
+            "winkler",
+            "fuzzy",
+            "match",
+            "distance",
+            "dedup",
+        ]
+    }
+
+    fn is_const(&self) -> bool {
+        true
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let compare_string: String = call.req(engine_state, stack, 0)?;
+        let algorithm = call
+            .get_flag(engine_state, stack, "algorithm")?
+            .unwrap_or_default();
+        let ignore_case = call.has_flag(engine_state, stack, "ignore-case")?;
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            compare_string,
+            algorithm,
+            ignore_case,
+            matcher: Mutex::new(NucleoMatcher::new(NucleoConfig::DEFAULT)),
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.signals())
+    }
+
+    fn run_const(
+        &self,
+        working_set: &StateWorkingSet,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let compare_string: String = call.req_const(working_set, 0)?;
+        let algorithm = call
+            .get_flag_const(working_set, "algorithm")?
+            .unwrap_or_default();
+        let ignore_case = call.has_flag_const(working_set, "ignore-case")?;
+        let cell_paths: Vec<CellPath> = call.rest_const(working_set, 1)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            compare_string,
+            algorithm,
+            ignore_case,
+            matcher: Mutex::new(NucleoMatcher::new(NucleoConfig::DEFAULT)),
+            cell_paths,
+        };
+        operate(
+            action,
+            args,
+            input,
+            call.head,
+            working_set.permanent().signals(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Score the similarity of two strings by their edit distance.",
+                example: "'nushell' | str similarity 'nutshell'",
+                result: Some(Value::test_float(0.875)),
+            },
+            Example {
+                description: "Score the similarity of names with jaro-winkler.",
+                example: "'martha' | str similarity --algorithm jaro-winkler 'marhta' | math round --precision 2",
+                result: Some(Value::test_float(0.96)),
+            },
+            Example {
+                description: "Score strings in a table, ignoring case.",
+                example: "[{a: 'NuShell' b: 'xyz'}] | str similarity --ignore-case 'nushell' a b",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "a" => Value::test_float(1.0),
+                    "b" => Value::test_float(0.0),
+                })])),
+            },
+            Example {
+                description: "Find the files best matching a fuzzy pattern.",
+                example: "ls | insert score {|file| $file.name | str similarity --algorithm fuzzy 'cfgnu' } | where score > 0 | sort-by score --reverse",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => {
+            let (val, compare_string) = if args.ignore_case {
+                (val.to_lowercase(), args.compare_string.to_lowercase())
+            } else {
+                (val.clone(), args.compare_string.clone())
+            };
+            match args.algorithm {
+                Algorithm::Levenshtein => {
+                    Value::float(levenshtein_similarity(&val, &compare_string), head)
+                }
+                Algorithm::JaroWinkler => {
+                    Value::float(jaro_winkler_similarity(&val, &compare_string), head)
+                }
+                Algorithm::Fuzzy => {
+                    let mut matcher = args
+                        .matcher
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    let score = fuzzy_score(&mut matcher, &val, &compare_string, args.ignore_case);
+                    Value::int(score, head)
+                }
+            }
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::error(
+            ShellError::OnlySupportsThisInputType {
+                exp_input_type: "string".into(),
+                wrong_type: input.get_type().to_string(),
+                dst_span: head,
+                src_span: input.span(),
+            },
+            head,
+        ),
+    }
+}
+
+/// The edit distance relative to the length of the longest string, turned into a similarity.
+fn levenshtein_similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein_distance(a, b) as f64 / longest as f64
+}
+
+fn jaro_winkler_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // Chars only match when they're at most this far apart
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, c) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        for j in start..end {
+            if !b_matched[j] && b[j] == *c {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let matched = |chars: &[char], matched: &[bool]| {
+        chars
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| **matched)
+            .map(|(c, _)| *c)
+            .collect::<Vec<_>>()
+    };
+    let out_of_order = matched(&a, &a_matched)
+        .into_iter()
+        .zip(matched(&b, &b_matched))
+        .filter(|(a, b)| a != b)
+        .count();
+
+    let matches = matches as f64;
+    let transpositions = out_of_order as f64 / 2.0;
+    let jaro = (matches / a.len() as f64
+        + matches / b.len() as f64
+        + (matches - transpositions) / matches)
+        / 3.0;
+
+    // Strings with a common prefix of up to 4 chars are closer
+    let prefix = a.iter().zip(&b).take(4).take_while(|(a, b)| a == b).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// The score of the pattern in the text, like in fuzzy finders, or 0 when it doesn't match.
+fn fuzzy_score(matcher: &mut NucleoMatcher, text: &str, pattern: &str, ignore_case: bool) -> i64 {
+    let case_matching = if ignore_case {
+        CaseMatching::Ignore
+    } else {
+        CaseMatching::Smart
+    };
+    let atom = Atom::new(
+        pattern,
+        case_matching,
+        Normalization::Smart,
+        AtomKind::Fuzzy,
+        false,
+    );
+    let mut buf = Vec::new();
+    atom.score(Utf32Str::new(text, &mut buf), matcher)
+        .map_or(0, i64::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(StrSimilarity)
+    }
+
+    #[test]
+    fn jaro_winkler_scores() {
+        let round = |score: f64| (score * 1000.0).round() / 1000.0;
+        assert_eq!(round(jaro_winkler_similarity("martha", "marhta")), 0.961);
+        assert_eq!(round(jaro_winkler_similarity("dwayne", "duane")), 0.84);
+        assert_eq!(round(jaro_winkler_similarity("dixon", "dicksonx")), 0.813);
+        assert_eq!(jaro_winkler_similarity("same", "same"), 1.0);
+        assert_eq!(jaro_winkler_similarity("", "abc"), 0.0);
+    }
+
+    #[test]
+    fn fuzzy_scores_matches_only() {
+        let mut matcher = NucleoMatcher::new(NucleoConfig::DEFAULT);
+        let exact = fuzzy_score(&mut matcher, "config.nu", "config", false);
+        let scattered = fuzzy_score(&mut matcher, "conf-big.nu", "config", false);
+        assert!(exact > scattered, "{exact} <= {scattered}");
+        assert_eq!(fuzzy_score(&mut matcher, "notes.md", "xyz", false), 0);
+    }
+}
@@ -4,6 +4,7 @@ use crate::{grapheme_flags, grapheme_flags_const};
 use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::command_prelude::*;
 use nu_protocol::{IntRange, engine::StateWorkingSet};
+use nu_table::string_width;
 use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Clone)]
@@ -13,6 +14,7 @@ struct Arguments {
     range: IntRange,
     cell_paths: Option<Vec<CellPath>>,
     graphemes: bool,
+    display_width: bool,
 }
 
 impl CmdArgument for Arguments {
@@ -45,6 +47,11 @@ impl Command for StrSubstring {
                 "Count indexes and split using UTF-8 bytes (default; non-ASCII chars have length 2+).",
                 Some('b'),
             )
+            .switch(
+                "display-width",
+                "Count indexes using the columns the text takes on screen (wide chars like CJK and emoji have length 2), leaving out chars cut by the range.",
+                None,
+            )
             .required(
                 "range",
                 SyntaxShape::Any,
@@ -81,10 +88,18 @@ impl Command for StrSubstring {
 
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let graphemes = grapheme_flags(engine_state, stack, call)?;
+        let display_width = call.has_flag(engine_state, stack, "display-width")?;
+        check_display_width(
+            display_width,
+            graphemes || call.has_flag(engine_state, stack, "utf-8-bytes")?,
+            call.head,
+        )?;
         let args = Arguments {
             range,
             cell_paths,
-            graphemes: grapheme_flags(engine_state, stack, call)?,
+            graphemes,
+            display_width,
         };
         operate(action, args, input, call.head, engine_state.signals()).map(|mut pipeline| {
             if let Some(metadata) = pipeline.metadata_mut() {
@@ -105,10 +120,18 @@ impl Command for StrSubstring {
 
         let cell_paths: Vec<CellPath> = call.rest_const(working_set, 1)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let graphemes = grapheme_flags_const(working_set, call)?;
+        let display_width = call.has_flag_const(working_set, "display-width")?;
+        check_display_width(
+            display_width,
+            graphemes || call.has_flag_const(working_set, "utf-8-bytes")?,
+            call.head,
+        )?;
         let args = Arguments {
             range,
             cell_paths,
-            graphemes: grapheme_flags_const(working_set, call)?,
+            graphemes,
+            display_width,
         };
         operate(
             action,
@@ -138,6 +161,11 @@ impl Command for StrSubstring {
                 example: " '🇯🇵ほげ ふが ぴよ' | str substring --grapheme-clusters 4..5",
                 result: Some(Value::test_string("ふが")),
             },
+            Example {
+                description: "Get the text in the first 4 columns of the screen.",
+                example: " '日本語 text' | str substring --display-width 0..3",
+                result: Some(Value::test_string("日本")),
+            },
             Example {
                 description: "sub string by negative index.",
                 example: " 'good nushell' | str substring 5..-2",
@@ -147,10 +175,26 @@ impl Command for StrSubstring {
     }
 }
 
+fn check_display_width(
+    display_width: bool,
+    other_flags: bool,
+    head: Span,
+) -> Result<(), ShellError> {
+    if display_width && other_flags {
+        return Err(ShellError::IncompatibleParametersSingle {
+            msg: "Incompatible flags: --display-width can't be used with --grapheme-clusters (-g) or --utf-8-bytes (-b)".to_string(),
+            span: head,
+        });
+    }
+    Ok(())
+}
+
 fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     match input {
         Value::String { val: s, .. } => {
-            let s = if args.graphemes {
+            let s = if args.display_width {
+                substring_by_width(s, &args.range)
+            } else if args.graphemes {
                 let indices = s
                     .grapheme_indices(true)
                     .map(|(idx, s)| (idx, s.len()))
@@ -195,6 +239,30 @@ fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     }
 }
 
+/// The graphemes between the display columns of the range, without the wide ones it cuts.
+fn substring_by_width(s: &str, range: &IntRange) -> String {
+    let (start, end) = range.absolute_bounds(string_width(s));
+    let end = match end {
+        Bound::Excluded(end) => end,
+        Bound::Included(end) => end + 1,
+        Bound::Unbounded => usize::MAX,
+    };
+
+    let mut column = 0;
+    let mut out = String::new();
+    for grapheme in s.graphemes(true) {
+        if column >= end {
+            break;
+        }
+        let width = string_width(grapheme);
+        if column >= start && column + width <= end {
+            out.push_str(grapheme);
+        }
+        column += width;
+    }
+    out
+}
+
 #[cfg(test)]
 #[allow(clippy::reversed_empty_ranges)]
 mod tests {
@@ -311,6 +379,7 @@ mod tests {
                     range: expectation.range(),
                     cell_paths: None,
                     graphemes: false,
+                    display_width: false,
                 },
                 Span::test_data(),
             );
@@ -328,9 +397,41 @@ mod tests {
             cell_paths: None,
             range: range.into(),
             graphemes: false,
+            display_width: false,
         };
 
         let actual = action(&word, &options, Span::test_data());
         assert_eq!(actual, Value::test_string("�"));
     }
+
+    #[test]
+    fn use_display_width() {
+        let word = Value::test_string("ab日本語");
+
+        let cases = vec![
+            expectation("ab日", 0..=3),
+            expectation("b", 1..=2),
+            expectation("本語", 3..=7),
+            expectation("語", -2..=-1),
+        ];
+
+        for expectation in &cases {
+            let actual = action(
+                &word,
+                &Arguments {
+                    range: expectation.range(),
+                    cell_paths: None,
+                    graphemes: false,
+                    display_width: true,
+                },
+                Span::test_data(),
+            );
+
+            assert_eq!(
+                actual,
+                Value::test_string(expectation.expected),
+                "{expectation:?}"
+            );
+        }
+    }
 }
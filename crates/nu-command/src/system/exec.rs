@@ -1,6 +1,11 @@
 use std::borrow::Cow;
 
 use nu_engine::{command_prelude::*, env_to_strings};
+use nu_protocol::shell_error::generic::GenericError;
+
+/// The flags of `exec` itself. Known flags are recognized anywhere, but after the command name
+/// they belong to the command, like in `exec su --login`.
+const EXEC_FLAGS: [&str; 3] = ["login", "new-group", "new-session"];
 
 #[derive(Clone)]
 pub struct Exec;
@@ -18,6 +23,21 @@ impl Command for Exec {
                 SyntaxShape::OneOf(vec![SyntaxShape::GlobPattern, SyntaxShape::Any]),
                 "External command to run, with arguments.",
             )
+            .switch(
+                "login",
+                "Run the command as a login shell, with a dash before its name (Unix only).",
+                None,
+            )
+            .switch(
+                "new-group",
+                "Run the command in a new process group, which takes over the terminal (Unix only).",
+                None,
+            )
+            .switch(
+                "new-session",
+                "Run the command in a new session, detached from the terminal (Unix only).",
+                None,
+            )
             .allows_unknown_args()
             .category(Category::System)
     }
@@ -28,7 +48,11 @@ impl Command for Exec {
 
     fn extra_description(&self) -> &str {
         "On Unix-based systems, the current process is replaced with the command.
-On Windows based systems, Nushell will wait for the command to finish and then exit with the command's exit code."
+On Windows based systems, Nushell will wait for the command to finish and then exit with the command's exit code.
+With `--new-group` or `--new-session`, the command runs in a child process on Unix too, as the current process can't leave its process group or session: Nushell waits for it and exits with its exit code.
+
+With `--login`, the command is started the way login(1) starts shells: its name is prefixed with a dash, which shells like bash, zsh, fish and nu take as being a login shell, and HOME, USER, LOGNAME and SHELL are set for the current user.
+The flags of `exec` only apply when they come before the command name. After it, they are passed to the command, so `exec su --login` runs `su --login`."
     }
 
     fn run(
@@ -49,6 +73,44 @@ On Windows based systems, Nushell will wait for the command to finish and then e
             });
         };
 
+        // Flags after the command name are passed on to it
+        let (exec_flags, command_flags): (Vec<_>, Vec<_>) = EXEC_FLAGS
+            .into_iter()
+            .filter_map(|flag| Some((flag, call.get_flag_span(stack, flag)?)))
+            .partition(|(_, span)| span.start < name.span().start);
+        let mut exec_flag = |flag: &str| -> Result<bool, ShellError> {
+            Ok(exec_flags.iter().any(|(exec_flag, _)| *exec_flag == flag)
+                && call.has_flag(engine_state, stack, flag)?)
+        };
+        let login = exec_flag("login")?;
+        let new_group = exec_flag("new-group")?;
+        let new_session = exec_flag("new-session")?;
+
+        if new_group
+            && new_session
+            && let (Some(left_span), Some(right_span)) = (
+                call.get_flag_span(stack, "new-group"),
+                call.get_flag_span(stack, "new-session"),
+            )
+        {
+            return Err(ShellError::IncompatibleParameters {
+                left_message: "can't start a new process group...".into(),
+                left_span,
+                right_message: "...and a new session at the same time".into(),
+                right_span,
+            });
+        }
+        if cfg!(windows)
+            && (login || new_group || new_session)
+            && let Some((_, span)) = exec_flags.first()
+        {
+            return Err(ShellError::Generic(GenericError::new(
+                "Unsupported flag",
+                "this flag is only supported on Unix",
+                *span,
+            )));
+        }
+
         let name_str: Cow<str> = match &name {
             Value::Glob { val, .. } => Cow::Borrowed(val),
             Value::String { val, .. } => Cow::Borrowed(val),
@@ -72,7 +134,7 @@ On Windows based systems, Nushell will wait for the command to finish and then e
         };
 
         // Create the command.
-        let mut command = std::process::Command::new(executable);
+        let mut command = std::process::Command::new(&executable);
 
         // Configure PWD.
         command.current_dir(cwd);
@@ -93,21 +155,63 @@ On Windows based systems, Nushell will wait for the command to finish and then e
         }
 
         // Configure args.
-        let args = crate::eval_external_arguments(engine_state, stack, call_args.to_vec())?;
+        let mut args = crate::eval_external_arguments(engine_state, stack, call_args.to_vec())?;
+        for (flag, span) in command_flags {
+            // Keep the value given to the flag, like in `--login=false`
+            let arg = match call.get_flag::<Value>(engine_state, stack, flag)? {
+                Some(value) => format!(
+                    "--{flag}={}",
+                    value.to_expanded_string("", &stack.get_config(engine_state))
+                ),
+                None => format!("--{flag}"),
+            };
+            let index = args.partition_point(|arg| arg.span.start < span.start);
+            args.insert(index, arg.into_spanned(span).map(Into::into));
+        }
         command.args(args.into_iter().map(|s| s.item));
 
         // Execute the child process, replacing/terminating the current process
         // depending on platform.
         #[cfg(unix)]
         {
+            use nu_system::ExecGroup;
             use std::os::unix::process::CommandExt;
 
-            let err = command.exec();
-            Err(ShellError::ExternalCommand {
-                label: "Failed to exec into new process".into(),
-                help: err.to_string(),
-                span: call.head,
-            })
+            if login {
+                set_login_env(&mut command, &executable, call.head)?;
+            }
+            let group = if new_group {
+                ExecGroup::NewGroup
+            } else if new_session {
+                ExecGroup::NewSession
+            } else {
+                ExecGroup::Inherit
+            };
+            nu_system::prepare_exec_command(&mut command, group);
+
+            if group == ExecGroup::Inherit {
+                let err = command.exec();
+                return Err(ShellError::ExternalCommand {
+                    label: "Failed to exec into new process".into(),
+                    help: err.to_string(),
+                    span: call.head,
+                });
+            }
+
+            // Our process may already lead its process group and session, so the command can't
+            // take the current process over: it runs in a child, and we exit with its exit code
+            let status = command
+                .spawn()
+                .and_then(|mut child| child.wait())
+                .map_err(|err| ShellError::ExternalCommand {
+                    label: "Failed to exec into new process".into(),
+                    help: err.to_string(),
+                    span: call.head,
+                })?;
+            std::process::exit(status.code().unwrap_or_else(|| {
+                use std::os::unix::process::ExitStatusExt;
+                128 + status.signal().unwrap_or(0)
+            }))
         }
         #[cfg(windows)]
         {
@@ -137,6 +241,42 @@ On Windows based systems, Nushell will wait for the command to finish and then e
                 example: "exec nautilus",
                 result: None,
             },
+            Example {
+                description: "Replace nushell with zsh as a login shell",
+                example: "exec --login zsh",
+                result: None,
+            },
         ]
     }
 }
+
+/// Start the command like login(1) does: as `-name`, and with the user's basic environment.
+#[cfg(unix)]
+fn set_login_env(
+    command: &mut std::process::Command,
+    executable: &std::path::Path,
+    span: Span,
+) -> Result<(), ShellError> {
+    use nix::unistd::{Uid, User};
+    use std::os::unix::process::CommandExt;
+
+    let user = User::from_uid(Uid::current())
+        .ok()
+        .flatten()
+        .ok_or_else(|| {
+            ShellError::Generic(GenericError::new(
+                "Could not set up the login environment",
+                "the current user has no entry in the user database",
+                span,
+            ))
+        })?;
+
+    let mut arg0 = std::ffi::OsString::from("-");
+    arg0.push(executable.file_name().unwrap_or(executable.as_os_str()));
+    command.arg0(arg0);
+    command.env("HOME", &user.dir);
+    command.env("USER", &user.name);
+    command.env("LOGNAME", &user.name);
+    command.env("SHELL", executable);
+    Ok(())
+}
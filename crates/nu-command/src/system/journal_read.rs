@@ -0,0 +1,392 @@
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Read},
+    process::{Child, ChildStdout, Command as ProcessCommand, Stdio},
+    thread::{self, JoinHandle},
+};
+
+use chrono::{DateTime, FixedOffset};
+use nu_engine::{command_prelude::*, env_to_strings};
+use nu_protocol::shell_error::{generic::GenericError, io::IoError};
+
+/// The names of the `PRIORITY` field values, like in syslog.
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+#[derive(Clone)]
+pub struct JournalRead;
+
+impl Command for JournalRead {
+    fn name(&self) -> &str {
+        "journal read"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("journal read")
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .named(
+                "unit",
+                SyntaxShape::String,
+                "Only read the entries of this systemd unit.",
+                Some('u'),
+            )
+            .named(
+                "lines",
+                SyntaxShape::Int,
+                "Only read this many of the most recent entries.",
+                Some('n'),
+            )
+            .named(
+                "since",
+                SyntaxShape::DateTime,
+                "Only read the entries from this time on.",
+                Some('s'),
+            )
+            .named(
+                "after-cursor",
+                SyntaxShape::String,
+                "Only read the entries after the one with this cursor.",
+                Some('c'),
+            )
+            .switch(
+                "boot",
+                "Only read the entries of the current boot.",
+                Some('b'),
+            )
+            .switch(
+                "follow",
+                "Keep reading new entries as they're added to the journal.",
+                Some('f'),
+            )
+            .category(Category::System)
+    }
+
+    fn description(&self) -> &str {
+        "Read the entries of the systemd journal."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The entries are read with the export format of `journalctl`, so that no field is lost, \
+and become records with their time, host, unit, app, pid, severity, message and cursor, and all \
+their fields. Fields that aren't valid UTF-8 are kept as binary.
+
+Save the cursor of the last entry to resume from it later with --after-cursor.
+
+Currently supported only on Linux systems."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["journalctl", "systemd", "log", "syslog"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Read the 20 most recent entries of a unit",
+                example: "journal read --unit sshd.service --lines 20",
+                result: None,
+            },
+            Example {
+                description: "Show the errors of the current boot",
+                example: "journal read --boot | where severity in [emerg alert crit err]",
+                result: None,
+            },
+            Example {
+                description: "Follow the journal from where an earlier read stopped",
+                example: "journal read --follow --after-cursor (open last-cursor.txt)",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let mut args = vec!["--output=export".to_string(), "--no-pager".to_string()];
+        if let Some(unit) = call.get_flag::<String>(engine_state, stack, "unit")? {
+            args.push(format!("--unit={unit}"));
+        }
+        if let Some(lines) = call.get_flag::<Spanned<i64>>(engine_state, stack, "lines")? {
+            if lines.item < 0 {
+                return Err(ShellError::NeedsPositiveValue { span: lines.span });
+            }
+            args.push(format!("--lines={}", lines.item));
+        }
+        if let Some(since) = call.get_flag::<DateTime<FixedOffset>>(engine_state, stack, "since")? {
+            args.push(format!("--since=@{}", since.timestamp()));
+        }
+        if let Some(cursor) = call.get_flag::<String>(engine_state, stack, "after-cursor")? {
+            args.push(format!("--after-cursor={cursor}"));
+        }
+        if call.has_flag(engine_state, stack, "boot")? {
+            args.push("--boot".into());
+        }
+        if call.has_flag(engine_state, stack, "follow")? {
+            args.push("--follow".into());
+        }
+
+        let paths = nu_engine::env::path_str(engine_state, stack, head)?;
+        let cwd = engine_state.cwd(Some(stack))?;
+        let executable = crate::which("journalctl", &paths, cwd.as_ref()).ok_or(
+            ShellError::ExternalCommand {
+                label: "`journalctl` not found".into(),
+                help: "reading the journal needs journalctl, which comes with systemd".into(),
+                span: head,
+            },
+        )?;
+
+        let mut command = ProcessCommand::new(executable);
+        command
+            .current_dir(cwd)
+            .env_clear()
+            .envs(env_to_strings(engine_state, stack)?)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let mut child = command.spawn().map_err(|err| {
+            IoError::new_with_additional_context(err, head, None, "Could not run journalctl")
+        })?;
+        let Some(stdout) = child.stdout.take() else {
+            return Err(ShellError::Generic(GenericError::new(
+                "Could not read the journal",
+                "journalctl has no output",
+                head,
+            )));
+        };
+
+        // Read the errors while the entries are read, so journalctl doesn't block on a full pipe
+        let stderr = child
+            .stderr
+            .take()
+            .map(|mut pipe| {
+                thread::Builder::new()
+                    .name("journalctl stderr".into())
+                    .spawn(move || {
+                        let mut stderr = String::new();
+                        let _ = pipe.read_to_string(&mut stderr);
+                        stderr
+                    })
+            })
+            .transpose()
+            .map_err(|err| {
+                IoError::new_with_additional_context(
+                    err,
+                    head,
+                    None,
+                    "Could not read the errors of journalctl",
+                )
+            })?;
+
+        let entries = JournalEntries {
+            child,
+            reader: BufReader::new(stdout),
+            stderr,
+            span: head,
+            done: false,
+        };
+        Ok(entries.into_pipeline_data(head, engine_state.signals().clone()))
+    }
+}
+
+/// The entries read from a running `journalctl`, which is stopped when they're dropped.
+struct JournalEntries {
+    child: Child,
+    reader: BufReader<ChildStdout>,
+    stderr: Option<JoinHandle<String>>,
+    span: Span,
+    done: bool,
+}
+
+impl Iterator for JournalEntries {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        if self.done {
+            return None;
+        }
+        match read_entry(&mut self.reader) {
+            Ok(Some(fields)) => Some(entry_to_value(fields, self.span)),
+            Ok(None) => {
+                self.done = true;
+                self.exit_error().map(|err| Value::error(err, self.span))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Value::error(
+                    IoError::new(err, self.span, None).into(),
+                    self.span,
+                ))
+            }
+        }
+    }
+}
+
+impl JournalEntries {
+    /// The error of `journalctl`, when it didn't succeed.
+    fn exit_error(&mut self) -> Option<ShellError> {
+        let status = self.child.wait().ok()?;
+        if status.success() {
+            return None;
+        }
+        let stderr = self
+            .stderr
+            .take()
+            .and_then(|thread| thread.join().ok())
+            .unwrap_or_default();
+        Some(ShellError::ExternalCommand {
+            label: format!("journalctl failed: {}", stderr.trim()),
+            help: "check the flags, and that you're allowed to read the journal".into(),
+            span: self.span,
+        })
+    }
+}
+
+impl Drop for JournalEntries {
+    fn drop(&mut self) {
+        // Followed journals never end, so stop journalctl when the entries aren't needed anymore
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Read the fields of the next entry of the journal export format, which are either lines like
+/// `NAME=value`, or a `NAME` line followed by the little endian length of the data and the data.
+fn read_entry(reader: &mut impl BufRead) -> std::io::Result<Option<Vec<(String, Vec<u8>)>>> {
+    let mut fields = vec![];
+    loop {
+        let mut line = vec![];
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            // The last entry may not be followed by an empty line
+            return Ok((!fields.is_empty()).then_some(fields));
+        }
+        if line.pop() != Some(b'\n') {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+        if line.is_empty() {
+            if fields.is_empty() {
+                continue;
+            }
+            return Ok(Some(fields));
+        }
+
+        match line.iter().position(|&byte| byte == b'=') {
+            Some(equals) => {
+                let data = line.split_off(equals + 1);
+                line.pop();
+                fields.push((String::from_utf8_lossy(&line).into_owned(), data));
+            }
+            None => {
+                let mut length = [0u8; 8];
+                reader.read_exact(&mut length)?;
+                let length = u64::from_le_bytes(length);
+                let mut data = vec![];
+                reader.by_ref().take(length).read_to_end(&mut data)?;
+                let mut newline = [0u8];
+                reader.read_exact(&mut newline)?;
+                if (data.len() as u64) < length || newline != [b'\n'] {
+                    return Err(ErrorKind::InvalidData.into());
+                }
+                fields.push((String::from_utf8_lossy(&line).into_owned(), data));
+            }
+        }
+    }
+}
+
+fn entry_to_value(fields: Vec<(String, Vec<u8>)>, span: Span) -> Value {
+    let mut all = Record::new();
+    for (name, data) in fields {
+        let value = match String::from_utf8(data) {
+            Ok(text) => Value::string(text, span),
+            Err(err) => Value::binary(err.into_bytes(), span),
+        };
+        all.insert(name, value);
+    }
+
+    let text = |name: &str| all.get(name).cloned().unwrap_or(Value::nothing(span));
+    let number = |name: &str| {
+        all.get(name)
+            .and_then(|value| value.as_str().ok())
+            .and_then(|text| text.parse::<i64>().ok())
+    };
+
+    let timestamp = number("__REALTIME_TIMESTAMP")
+        .and_then(DateTime::from_timestamp_micros)
+        .map(|date| Value::date(date.fixed_offset(), span))
+        .unwrap_or(Value::nothing(span));
+    let severity = number("PRIORITY")
+        .and_then(|priority| SEVERITIES.get(usize::try_from(priority).ok()?))
+        .map(|severity| Value::string(*severity, span))
+        .unwrap_or(Value::nothing(span));
+    let pid = number("_PID")
+        .map(|pid| Value::int(pid, span))
+        .unwrap_or(Value::nothing(span));
+
+    Value::record(
+        record! {
+            "timestamp" => timestamp,
+            "host" => text("_HOSTNAME"),
+            "unit" => text("_SYSTEMD_UNIT"),
+            "app" => text("SYSLOG_IDENTIFIER"),
+            "pid" => pid,
+            "severity" => severity,
+            "message" => text("MESSAGE"),
+            "cursor" => text("__CURSOR"),
+            "fields" => Value::record(all, span),
+        },
+        span,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn reads_export_format() {
+        let mut export =
+            b"__CURSOR=s=1\n__REALTIME_TIMESTAMP=1700000000000000\nPRIORITY=3\n".to_vec();
+        export.extend(b"MESSAGE\n");
+        export.extend(5u64.to_le_bytes());
+        export.extend(b"a\nb\xff\x00\n\n__CURSOR=s=2\nMESSAGE=second\n");
+        let mut reader = Cursor::new(export);
+
+        let first = read_entry(&mut reader)
+            .expect("valid export")
+            .expect("an entry");
+        let first = entry_to_value(first, Span::test_data());
+        assert_eq!(
+            first.get_data_by_key("severity"),
+            Some(Value::test_string("err"))
+        );
+        assert_eq!(
+            first.get_data_by_key("message"),
+            Some(Value::test_binary(b"a\nb\xff\x00".to_vec()))
+        );
+        assert_eq!(
+            first
+                .get_data_by_key("timestamp")
+                .and_then(|date| date.as_date().ok())
+                .map(|date| date.timestamp()),
+            Some(1_700_000_000)
+        );
+
+        let second = read_entry(&mut reader)
+            .expect("valid export")
+            .expect("an entry");
+        assert_eq!(
+            second,
+            vec![
+                ("__CURSOR".to_string(), b"s=2".to_vec()),
+                ("MESSAGE".to_string(), b"second".to_vec()),
+            ]
+        );
+        assert!(read_entry(&mut reader).expect("valid export").is_none());
+    }
+}
@@ -1,6 +1,11 @@
 mod complete;
 mod exec;
+#[cfg(target_os = "linux")]
+mod journal;
+#[cfg(target_os = "linux")]
+mod journal_read;
 mod nu_check;
+mod package_providers;
 #[cfg(any(
     target_os = "android",
     target_os = "linux",
@@ -14,7 +19,11 @@ mod ps;
 #[cfg(windows)]
 mod registry;
 #[cfg(windows)]
+mod registry_delete;
+#[cfg(windows)]
 mod registry_query;
+#[cfg(windows)]
+mod registry_set;
 mod run_external;
 mod run_internal;
 mod sys;
@@ -23,7 +32,12 @@ mod which_;
 
 pub use complete::Complete;
 pub use exec::Exec;
+#[cfg(target_os = "linux")]
+pub use journal::Journal;
+#[cfg(target_os = "linux")]
+pub use journal_read::JournalRead;
 pub use nu_check::NuCheck;
+pub use package_providers::{PackageProvider, register_package_provider};
 #[cfg(any(
     target_os = "android",
     target_os = "linux",
@@ -37,7 +51,11 @@ pub use ps::Ps;
 #[cfg(windows)]
 pub use registry::Registry;
 #[cfg(windows)]
+pub use registry_delete::RegistryDelete;
+#[cfg(windows)]
 pub use registry_query::RegistryQuery;
+#[cfg(windows)]
+pub use registry_set::RegistrySet;
 pub use run_external::{External, command_not_found, eval_external_arguments, which};
 pub use run_internal::RunInternal;
 pub use sys::*;
@@ -0,0 +1,378 @@
+//! Suggestions of packages to install when a command isn't found, like "`rg` is provided by the
+//! `ripgrep` package".
+//!
+//! This is only done when `$env.config.suggest_packages` is enabled, and nothing else, like a
+//! similar command name, could be suggested.
+//!
+//! Lookups run on a background thread, and `command_not_found` only waits [`LOOKUP_TIMEOUT`] for
+//! them. Results are cached for the whole session, so a slow lookup still helps the next time the
+//! command is mistyped.
+
+use std::{
+    collections::HashMap,
+    io,
+    process::{Command, Stdio},
+    sync::{Arc, LazyLock, Mutex, MutexGuard, mpsc},
+    thread,
+    time::Duration,
+};
+
+/// How long `command_not_found` waits for the providers.
+const LOOKUP_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Finds the packages that provide a command, using a package manager's file index or catalog.
+///
+/// Providers are tried in the order they were registered, and the first one that finds packages
+/// wins. The built-in ones use `pkgfile` and `apt-file` on Unix and `winget` and `scoop` on
+/// Windows, when they're installed. Others can be added with [`register_package_provider`].
+pub trait PackageProvider: Send + Sync {
+    /// A short name for the provider, shown in the suggestion.
+    fn name(&self) -> &str;
+
+    /// The packages that provide `command`. Errors, like the package manager not being installed,
+    /// are treated as not finding any.
+    fn lookup(&self, command: &str) -> io::Result<Vec<String>>;
+
+    /// The command line that installs `package`.
+    fn install_command(&self, package: &str) -> String;
+}
+
+/// Add a provider, which is tried before the ones already registered.
+pub fn register_package_provider(provider: impl PackageProvider + 'static) {
+    lock(&PROVIDERS).insert(0, Arc::new(provider));
+    // Commands that weren't found before may be found now
+    lock(&CACHE).clear();
+}
+
+enum Lookup {
+    Pending,
+    Done(Option<String>),
+}
+
+static PROVIDERS: LazyLock<Mutex<Vec<Arc<dyn PackageProvider>>>> =
+    LazyLock::new(|| Mutex::new(default_providers()));
+
+static CACHE: LazyLock<Mutex<HashMap<String, Lookup>>> = LazyLock::new(Default::default);
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn default_providers() -> Vec<Arc<dyn PackageProvider>> {
+    if cfg!(windows) {
+        vec![Arc::new(Winget), Arc::new(Scoop)]
+    } else {
+        vec![Arc::new(Pkgfile), Arc::new(AptFile)]
+    }
+}
+
+/// A hint about which package to install to get `command`, if a provider knows one by now.
+pub(crate) fn package_hint(command: &str) -> Option<String> {
+    let receiver = {
+        let mut cache = lock(&CACHE);
+        match cache.get(command) {
+            Some(Lookup::Done(hint)) => return hint.clone(),
+            // Still looking from a previous attempt
+            Some(Lookup::Pending) => return None,
+            None => {}
+        }
+
+        let providers = lock(&PROVIDERS).clone();
+        let (sender, receiver) = mpsc::channel();
+        let name = command.to_owned();
+        let spawned = thread::Builder::new()
+            .name("package lookup".into())
+            .spawn(move || {
+                let hint = providers
+                    .iter()
+                    .find_map(|provider| provider_hint(provider.as_ref(), &name));
+                lock(&CACHE).insert(name, Lookup::Done(hint.clone()));
+                let _ = sender.send(hint);
+            });
+        if spawned.is_err() {
+            return None;
+        }
+        cache.insert(command.to_owned(), Lookup::Pending);
+        receiver
+    };
+    receiver.recv_timeout(LOOKUP_TIMEOUT).ok().flatten()
+}
+
+fn provider_hint(provider: &dyn PackageProvider, command: &str) -> Option<String> {
+    let packages = provider.lookup(command).ok()?;
+    let (first, others) = packages.split_first()?;
+    let install = provider.install_command(first);
+    let provider = provider.name();
+    Some(if others.is_empty() {
+        format!(
+            "`{command}` is provided by the `{first}` package ({provider}), install it with `{install}`"
+        )
+    } else {
+        let packages = packages
+            .iter()
+            .map(|package| format!("`{package}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "`{command}` is provided by the packages {packages} ({provider}), install one of them, e.g. with `{install}`"
+        )
+    })
+}
+
+/// The standard output of a program, whatever its exit status.
+fn run(program: &str, args: &[&str]) -> io::Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Arch Linux's `pkgfile`, which prints `repo/package` for each package with the binary.
+struct Pkgfile;
+
+impl PackageProvider for Pkgfile {
+    fn name(&self) -> &str {
+        "pkgfile"
+    }
+
+    fn lookup(&self, command: &str) -> io::Result<Vec<String>> {
+        run("pkgfile", &["--binaries", "--", command]).map(|output| lines(&output))
+    }
+
+    fn install_command(&self, package: &str) -> String {
+        format!("sudo pacman -S {package}")
+    }
+}
+
+/// Debian's `apt-file`, searching for the command in the usual binary directories.
+struct AptFile;
+
+impl PackageProvider for AptFile {
+    fn name(&self) -> &str {
+        "apt-file"
+    }
+
+    fn lookup(&self, command: &str) -> io::Result<Vec<String>> {
+        let pattern = format!("^/(usr/)?s?bin/{}$", escape_regex(command));
+        run(
+            "apt-file",
+            &["search", "--package-only", "--regexp", &pattern],
+        )
+        .map(|output| lines(&output))
+    }
+
+    fn install_command(&self, package: &str) -> String {
+        format!("sudo apt install {package}")
+    }
+}
+
+fn escape_regex(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| {
+            let escape = r"\.+*?()|[]{}^$".contains(c).then_some('\\');
+            escape.into_iter().chain([c])
+        })
+        .collect()
+}
+
+/// The `winget` catalogs, which know which packages provide which commands.
+struct Winget;
+
+impl PackageProvider for Winget {
+    fn name(&self) -> &str {
+        "winget"
+    }
+
+    fn lookup(&self, command: &str) -> io::Result<Vec<String>> {
+        let output = run(
+            "winget",
+            &[
+                "search",
+                "--command",
+                command,
+                "--exact",
+                "--disable-interactivity",
+                "--accept-source-agreements",
+            ],
+        )?;
+        Ok(table_rows(&output, &["Id"])
+            .into_iter()
+            .filter_map(|mut row| row.pop())
+            .collect())
+    }
+
+    fn install_command(&self, package: &str) -> String {
+        format!("winget install --id {package}")
+    }
+}
+
+/// The buckets known to `scoop`, whose manifests list the binaries of each app.
+struct Scoop;
+
+impl PackageProvider for Scoop {
+    fn name(&self) -> &str {
+        "scoop"
+    }
+
+    fn lookup(&self, command: &str) -> io::Result<Vec<String>> {
+        // Scoop is a PowerShell script with a batch file shim, which needs its extension
+        let output = run("scoop.cmd", &["search", command])?;
+        let exe = format!("{command}.exe");
+        Ok(table_rows(&output, &["Name", "Binaries"])
+            .into_iter()
+            .filter(|row| {
+                row[1]
+                    .split(" | ")
+                    .any(|binary| binary.eq_ignore_ascii_case(&exe) || binary == command)
+            })
+            .map(|mut row| row.swap_remove(0))
+            .collect())
+    }
+
+    fn install_command(&self, package: &str) -> String {
+        format!("scoop install {package}")
+    }
+}
+
+/// The given columns of a table printed with a header, a line of dashes, and columns aligned with
+/// spaces, like `winget` and `scoop` do.
+fn table_rows(output: &str, columns: &[&str]) -> Vec<Vec<String>> {
+    // Progress spinners are overwritten with carriage returns
+    let mut lines = output
+        .lines()
+        .map(|line| line.rsplit('\r').next().unwrap_or(line));
+    let Some(header) = lines.by_ref().find(|line| {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        columns.iter().all(|column| words.contains(column))
+    }) else {
+        return Vec::new();
+    };
+    let header: Vec<char> = header.chars().collect();
+
+    // Where each column of the header starts and ends, in characters
+    let mut starts = Vec::new();
+    for (index, c) in header.iter().enumerate() {
+        if !c.is_whitespace() && (index == 0 || header[index - 1].is_whitespace()) {
+            starts.push(index);
+        }
+    }
+    let bounds: Vec<(String, usize, Option<usize>)> = starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied();
+            let name: String = header[start..end.unwrap_or(header.len())].iter().collect();
+            (name.trim().to_owned(), start, end)
+        })
+        .collect();
+
+    lines
+        .skip_while(|line| line.trim().chars().all(|c| c == '-' || c == ' '))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            columns
+                .iter()
+                .map(|column| {
+                    bounds
+                        .iter()
+                        .find(|(name, ..)| name == column)
+                        .map(|&(_, start, end)| {
+                            let start = start.min(chars.len());
+                            let end = end.unwrap_or(chars.len()).min(chars.len());
+                            chars[start..end]
+                                .iter()
+                                .collect::<String>()
+                                .trim()
+                                .to_owned()
+                        })
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_winget_table() {
+        let output = "\r   - \r\
+Name    Id                      Version Match       Source
+----------------------------------------------------------
+ripgrep BurntSushi.ripgrep.MSVC 14.1.0  Command: rg winget
+";
+        assert_eq!(
+            table_rows(output, &["Id"]),
+            vec![vec!["BurntSushi.ripgrep.MSVC".to_string()]]
+        );
+    }
+
+    #[test]
+    fn parse_scoop_table() {
+        let output = "Results from local buckets...
+
+Name    Version Source Binaries
+----    ------- ------ --------
+ripgrep 14.1.0  main   rg.exe
+rga     0.10.6  extras rga.exe | rga-preproc.exe
+";
+        assert_eq!(
+            table_rows(output, &["Name", "Binaries"]),
+            vec![
+                vec!["ripgrep".to_string(), "rg.exe".to_string()],
+                vec!["rga".to_string(), "rga.exe | rga-preproc.exe".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn escapes_regex() {
+        assert_eq!(escape_regex("g++"), r"g\+\+");
+    }
+
+    struct Fake;
+
+    impl PackageProvider for Fake {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        fn lookup(&self, command: &str) -> io::Result<Vec<String>> {
+            Ok(match command {
+                "rg" => vec!["ripgrep".into()],
+                "fd" => vec!["fd".into(), "fd-find".into()],
+                _ => vec![],
+            })
+        }
+
+        fn install_command(&self, package: &str) -> String {
+            format!("fake install {package}")
+        }
+    }
+
+    #[test]
+    fn hints_from_provider() {
+        assert_eq!(
+            provider_hint(&Fake, "rg").as_deref(),
+            Some(
+                "`rg` is provided by the `ripgrep` package (fake), install it with `fake install ripgrep`"
+            )
+        );
+        assert!(provider_hint(&Fake, "fd").is_some_and(|hint| hint.contains("`fd`, `fd-find`")));
+        assert_eq!(provider_hint(&Fake, "nope"), None);
+    }
+}
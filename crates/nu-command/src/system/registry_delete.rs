@@ -0,0 +1,124 @@
+use super::registry_query::{get_reg_hive, hive_switches};
+use nu_engine::command_prelude::*;
+use winreg::enums::KEY_SET_VALUE;
+
+#[derive(Clone)]
+pub struct RegistryDelete;
+
+impl Command for RegistryDelete {
+    fn name(&self) -> &str {
+        "registry delete"
+    }
+
+    fn signature(&self) -> Signature {
+        hive_switches(Signature::build("registry delete"), "Delete from")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::Nothing, Type::record()),
+            ])
+            .required("key", SyntaxShape::String, "Registry key to delete from.")
+            .optional(
+                "value",
+                SyntaxShape::String,
+                "Name of the registry value to delete. Without it, the key itself is deleted.",
+            )
+            .switch(
+                "recursive",
+                "Delete the key together with its subkeys.",
+                Some('r'),
+            )
+            .switch(
+                "dry-run",
+                "Return what would be deleted, without deleting it.",
+                Some('n'),
+            )
+            .category(Category::System)
+    }
+
+    fn description(&self) -> &str {
+        "Delete a value or a key from the Windows registry."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"A key with subkeys is only deleted with `--recursive`.
+
+Currently supported only on Windows systems."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["regedit", "reg delete", "remove"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let key: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let value: Option<Spanned<String>> = call.opt(engine_state, stack, 1)?;
+        let recursive = call.has_flag(engine_state, stack, "recursive")?;
+        let dry_run = call.has_flag(engine_state, stack, "dry-run")?;
+
+        let (hive_name, hive) = get_reg_hive(engine_state, stack, call)?;
+
+        if dry_run {
+            // Still check that there's something to delete
+            let reg_key = hive
+                .open_subkey(&key.item)
+                .map_err(|err| IoError::new(err, key.span, None))?;
+            if let Some(value) = &value {
+                reg_key
+                    .get_raw_value(&value.item)
+                    .map_err(|err| IoError::new(err, value.span, None))?;
+            }
+            return Ok(Value::record(
+                record! {
+                    "hive" => Value::string(hive_name, head),
+                    "key" => Value::string(key.item, key.span),
+                    "name" => value.map_or(Value::nothing(head), |value| Value::string(value.item, value.span)),
+                },
+                head,
+            )
+            .into_pipeline_data());
+        }
+
+        match value {
+            Some(value) => hive
+                .open_subkey_with_flags(&key.item, KEY_SET_VALUE)
+                .map_err(|err| IoError::new(err, key.span, None))?
+                .delete_value(&value.item)
+                .map_err(|err| IoError::new(err, value.span, None))?,
+            None if recursive => hive
+                .delete_subkey_all(&key.item)
+                .map_err(|err| IoError::new(err, key.span, None))?,
+            None => hive
+                .delete_subkey(&key.item)
+                .map_err(|err| IoError::new(err, key.span, None))?,
+        }
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Delete a value from the HKEY_CURRENT_USER hive",
+                example: r"registry delete --hkcu 'Software\MyApp' Greeting",
+                result: None,
+            },
+            Example {
+                description: "Delete a key and all of its subkeys",
+                example: r"registry delete --hkcu 'Software\MyApp' --recursive",
+                result: None,
+            },
+            Example {
+                description: "Show what would be deleted, without deleting it",
+                example: r"registry delete --hkcu 'Software\MyApp' Enabled --dry-run",
+                result: None,
+            },
+        ]
+    }
+}
@@ -14,22 +14,8 @@ impl Command for RegistryQuery {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("registry query")
+        hive_switches(Signature::build("registry query"), "Query")
             .input_output_types(vec![(Type::Nothing, Type::Any)])
-            .switch("hkcr", "Query the hkey_classes_root hive.", None)
-            .switch("hkcu", "Query the hkey_current_user hive.", None)
-            .switch("hklm", "Query the hkey_local_machine hive.", None)
-            .switch("hku", "Query the hkey_users hive.", None)
-            .switch("hkpd", "Query the hkey_performance_data hive.", None)
-            .switch("hkpt", "Query the hkey_performance_text hive.", None)
-            .switch("hkpnls", "Query the hkey_performance_nls_text hive.", None)
-            .switch("hkcc", "Query the hkey_current_config hive.", None)
-            .switch("hkdd", "Query the hkey_dyn_data hive.", None)
-            .switch(
-                "hkculs",
-                "Query the hkey_current_user_local_settings hive.",
-                None,
-            )
             .switch(
                 "no-expand",
                 "Do not expand %ENV% placeholders in REG_EXPAND_SZ.",
@@ -91,7 +77,7 @@ fn registry_query(
     let registry_key_span = &registry_key.clone().span;
     let registry_value: Option<Spanned<String>> = call.opt(engine_state, stack, 1)?;
 
-    let reg_hive = get_reg_hive(engine_state, stack, call)?;
+    let (_, reg_hive) = get_reg_hive(engine_state, stack, call)?;
     let reg_key = reg_hive
         .open_subkey(registry_key.item)
         .map_err(|err| IoError::new(err, *registry_key_span, None))?;
@@ -141,22 +127,41 @@ fn registry_query(
     }
 }
 
-fn get_reg_hive(
+/// The switches selecting a registry hive, named after the hive, and described with `verb`.
+pub(super) fn hive_switches(signature: Signature, verb: &str) -> Signature {
+    HIVES.iter().fold(signature, |signature, (flag, hive)| {
+        signature.switch(*flag, format!("{verb} the {hive} hive."), None)
+    })
+}
+
+const HIVES: [(&str, &str); 10] = [
+    ("hkcr", "hkey_classes_root"),
+    ("hkcu", "hkey_current_user"),
+    ("hklm", "hkey_local_machine"),
+    ("hku", "hkey_users"),
+    ("hkpd", "hkey_performance_data"),
+    ("hkpt", "hkey_performance_text"),
+    ("hkpnls", "hkey_performance_nls_text"),
+    ("hkcc", "hkey_current_config"),
+    ("hkdd", "hkey_dyn_data"),
+    ("hkculs", "hkey_current_user_local_settings"),
+];
+
+/// The hive selected by the hive switches, `hkcu` if there are none.
+pub(super) fn get_reg_hive(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
-) -> Result<RegKey, ShellError> {
-    let flags = [
-        "hkcr", "hkcu", "hklm", "hku", "hkpd", "hkpt", "hkpnls", "hkcc", "hkdd", "hkculs",
-    ]
-    .iter()
-    .copied()
-    .filter_map(|flag| match call.has_flag(engine_state, stack, flag) {
-        Ok(true) => Some(Ok(flag)),
-        Ok(false) => None,
-        Err(e) => Some(Err(e)),
-    })
-    .collect::<Result<Vec<_>, ShellError>>()?;
+) -> Result<(&'static str, RegKey), ShellError> {
+    let flags = HIVES
+        .iter()
+        .map(|(flag, _)| *flag)
+        .filter_map(|flag| match call.has_flag(engine_state, stack, flag) {
+            Ok(true) => Some(Ok(flag)),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect::<Result<Vec<_>, ShellError>>()?;
     if flags.len() > 1 {
         return Err(ShellError::Generic(GenericError::new(
             "Only one registry key can be specified",
@@ -184,7 +189,7 @@ fn get_reg_hive(
             });
         }
     };
-    Ok(RegKey::predef(hkey))
+    Ok((hive, RegKey::predef(hkey)))
 }
 
 fn reg_value_to_nu_value(
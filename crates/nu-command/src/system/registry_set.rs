@@ -0,0 +1,236 @@
+use super::registry_query::{get_reg_hive, hive_switches};
+use nu_engine::command_prelude::*;
+use winreg::{RegValue, enums::*, types::ToRegValue};
+
+#[derive(Clone)]
+pub struct RegistrySet;
+
+impl Command for RegistrySet {
+    fn name(&self) -> &str {
+        "registry set"
+    }
+
+    fn signature(&self) -> Signature {
+        hive_switches(Signature::build("registry set"), "Write to")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::Nothing, Type::record()),
+            ])
+            .required(
+                "key",
+                SyntaxShape::String,
+                "Registry key to write to, created if it doesn't exist.",
+            )
+            .required(
+                "value",
+                SyntaxShape::String,
+                "Name of the registry value to set, or an empty string for the default value.",
+            )
+            .required("data", SyntaxShape::Any, "The data to write.")
+            .named(
+                "type",
+                SyntaxShape::String,
+                "The type of the value: sz, expand_sz, multi_sz, dword, qword or binary.",
+                Some('t'),
+            )
+            .switch(
+                "dry-run",
+                "Return what would be written, without writing it.",
+                Some('n'),
+            )
+            .category(Category::System)
+    }
+
+    fn description(&self) -> &str {
+        "Set a value in the Windows registry."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Without `--type`, the type follows the data: strings are written as REG_SZ, integers as REG_DWORD
+(or REG_QWORD if they don't fit), lists of strings as REG_MULTI_SZ, and binary data as REG_BINARY.
+Use `--type expand_sz` for strings with %ENV% placeholders. Types may also be written like `REG_DWORD`.
+
+Currently supported only on Windows systems."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["regedit", "reg add", "write"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let key: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let name: String = call.req(engine_state, stack, 1)?;
+        let data: Value = call.req(engine_state, stack, 2)?;
+        let reg_type: Option<Spanned<String>> = call.get_flag(engine_state, stack, "type")?;
+        let dry_run = call.has_flag(engine_state, stack, "dry-run")?;
+
+        let (hive_name, hive) = get_reg_hive(engine_state, stack, call)?;
+        let reg_type = match reg_type {
+            Some(reg_type) => parse_reg_type(&reg_type)?,
+            None => infer_reg_type(&data)?,
+        };
+        let reg_value = to_reg_value(&data, reg_type)?;
+
+        if dry_run {
+            return Ok(Value::record(
+                record! {
+                    "hive" => Value::string(hive_name, head),
+                    "key" => Value::string(key.item, key.span),
+                    "name" => Value::string(name, head),
+                    "value" => data,
+                    "type" => Value::string(format!("{:?}", reg_value.vtype), head),
+                },
+                head,
+            )
+            .into_pipeline_data());
+        }
+
+        let (reg_key, _) = hive
+            .create_subkey(&key.item)
+            .map_err(|err| IoError::new(err, key.span, None))?;
+        reg_key
+            .set_raw_value(&name, &reg_value)
+            .map_err(|err| IoError::new(err, head, None))?;
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Set a string value in the HKEY_CURRENT_USER hive",
+                example: r"registry set --hkcu 'Software\MyApp' Greeting 'hello'",
+                result: None,
+            },
+            Example {
+                description: "Set a REG_DWORD value",
+                example: r"registry set --hkcu 'Software\MyApp' Enabled 1",
+                result: None,
+            },
+            Example {
+                description: "Set an expandable string, which is expanded when it is read",
+                example: r"registry set --hkcu Environment MyTools '%USERPROFILE%\tools' --type expand_sz",
+                result: None,
+            },
+            Example {
+                description: "Show what would be written, without writing it",
+                example: r"registry set --hklm 'SOFTWARE\MyApp' Paths [C:\a C:\b] --dry-run",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn parse_reg_type(reg_type: &Spanned<String>) -> Result<RegType, ShellError> {
+    let name = reg_type.item.to_ascii_lowercase();
+    match name.strip_prefix("reg_").unwrap_or(&name) {
+        "sz" => Ok(REG_SZ),
+        "expand_sz" => Ok(REG_EXPAND_SZ),
+        "multi_sz" => Ok(REG_MULTI_SZ),
+        "dword" => Ok(REG_DWORD),
+        "qword" => Ok(REG_QWORD),
+        "binary" => Ok(REG_BINARY),
+        _ => Err(ShellError::InvalidValue {
+            valid: "sz, expand_sz, multi_sz, dword, qword or binary".into(),
+            actual: reg_type.item.clone(),
+            span: reg_type.span,
+        }),
+    }
+}
+
+fn infer_reg_type(data: &Value) -> Result<RegType, ShellError> {
+    match data {
+        Value::String { .. } => Ok(REG_SZ),
+        Value::Int { val, .. } if u32::try_from(*val).is_ok() => Ok(REG_DWORD),
+        Value::Int { .. } => Ok(REG_QWORD),
+        Value::List { .. } => Ok(REG_MULTI_SZ),
+        Value::Binary { .. } => Ok(REG_BINARY),
+        other => Err(unsupported_data(
+            other,
+            "string, int, list<string> or binary",
+        )),
+    }
+}
+
+fn to_reg_value(data: &Value, reg_type: RegType) -> Result<RegValue, ShellError> {
+    let span = data.span();
+    let type_name = format!("{reg_type:?}");
+    let out_of_range = |max: &str| ShellError::IncorrectValue {
+        msg: format!("{type_name} values must be between 0 and {max}"),
+        val_span: span,
+        call_span: span,
+    };
+    Ok(match (reg_type, data) {
+        (REG_SZ, Value::String { val, .. }) => val.to_reg_value(),
+        (REG_EXPAND_SZ, Value::String { val, .. }) => RegValue {
+            vtype: REG_EXPAND_SZ,
+            ..val.to_reg_value()
+        },
+        (REG_MULTI_SZ, Value::List { vals, .. }) => vals
+            .iter()
+            .map(|val| val.as_str().map(str::to_owned))
+            .collect::<Result<Vec<String>, _>>()?
+            .to_reg_value(),
+        (REG_DWORD, Value::Int { val, .. }) => u32::try_from(*val)
+            .map_err(|_| out_of_range(&u32::MAX.to_string()))?
+            .to_reg_value(),
+        (REG_QWORD, Value::Int { val, .. }) => u64::try_from(*val)
+            .map_err(|_| out_of_range(&i64::MAX.to_string()))?
+            .to_reg_value(),
+        (REG_BINARY, Value::Binary { val, .. }) => RegValue {
+            bytes: val.clone().into(),
+            vtype: REG_BINARY,
+        },
+        (REG_SZ | REG_EXPAND_SZ, other) => return Err(unsupported_data(other, "string")),
+        (REG_MULTI_SZ, other) => return Err(unsupported_data(other, "list<string>")),
+        (REG_DWORD | REG_QWORD, other) => return Err(unsupported_data(other, "int")),
+        (_, other) => return Err(unsupported_data(other, "binary")),
+    })
+}
+
+fn unsupported_data(data: &Value, expected: &str) -> ShellError {
+    ShellError::CantConvert {
+        to_type: expected.into(),
+        from_type: data.get_type().to_string(),
+        span: data.span(),
+        help: Some("use `--type` to choose the type of the registry value".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_types_from_data() {
+        let reg_type = |value| infer_reg_type(&value).ok();
+        assert_eq!(reg_type(Value::test_string("a")), Some(REG_SZ));
+        assert_eq!(reg_type(Value::test_int(1)), Some(REG_DWORD));
+        assert_eq!(reg_type(Value::test_int(1 << 40)), Some(REG_QWORD));
+        assert_eq!(reg_type(Value::test_list(vec![])), Some(REG_MULTI_SZ));
+        assert_eq!(reg_type(Value::test_float(1.0)), None);
+    }
+
+    #[test]
+    fn parses_type_names() {
+        let reg_type =
+            |name: &str| parse_reg_type(&name.to_string().into_spanned(Span::test_data())).ok();
+        assert_eq!(reg_type("dword"), Some(REG_DWORD));
+        assert_eq!(reg_type("REG_EXPAND_SZ"), Some(REG_EXPAND_SZ));
+        assert_eq!(reg_type("float"), None);
+    }
+
+    #[test]
+    fn checks_dword_range() {
+        assert!(to_reg_value(&Value::test_int(-1), REG_DWORD).is_err());
+        assert!(to_reg_value(&Value::test_int(1 << 32), REG_DWORD).is_err());
+        assert!(to_reg_value(&Value::test_int(1 << 32), REG_QWORD).is_ok());
+    }
+}
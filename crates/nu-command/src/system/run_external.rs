@@ -1,3 +1,4 @@
+use super::package_providers;
 use itertools::Itertools;
 use nu_cmd_base::hook::eval_hook;
 use nu_engine::{command_prelude::*, env_to_strings};
@@ -5,7 +6,7 @@ use nu_path::{AbsolutePath, dots::expand_ndots_safe, expand_tilde};
 use nu_protocol::{
     ByteStream, NuGlob, OutDest, Signals, UseAnsiColoring, did_you_mean,
     process::{ChildProcess, PostWaitCallback},
-    shell_error::io::IoError,
+    shell_error::{generic::GenericError, io::IoError},
 };
 use nu_system::{ForegroundChild, kill_by_pid, prepare_background_command};
 use nu_utils::IgnoreCaseExt;
@@ -36,7 +37,9 @@ impl Command for External {
 
     fn extra_description(&self) -> &str {
         "All externals are run with this command, whether you call it directly with `run-external external` or use `external` or `^external`.
-If you create a custom command with this name, that will be used instead."
+If you create a custom command with this name, that will be used instead.
+
+With `--elevated`, the command runs in its own console window, so its input and output can't be redirected. It gets the current environment, and is run by an elevated nu. On Windows, the processes started by an external command are ended together with it by Ctrl-C or `job kill`."
     }
 
     fn signature(&self) -> nu_protocol::Signature {
@@ -47,6 +50,11 @@ If you create a custom command with this name, that will be used instead."
                 SyntaxShape::OneOf(vec![SyntaxShape::GlobPattern, SyntaxShape::Any]),
                 "External command to run, with arguments.",
             )
+            .switch(
+                "elevated",
+                "Run the command as administrator, after a UAC prompt (Windows only).",
+                None,
+            )
             .category(Category::System)
     }
 
@@ -59,6 +67,7 @@ If you create a custom command with this name, that will be used instead."
     ) -> Result<PipelineData, ShellError> {
         let cwd = engine_state.cwd(Some(stack))?;
         let rest = call.rest::<Value>(engine_state, stack, 0)?;
+        let elevated = call.has_flag(engine_state, stack, "elevated")?;
         let name_args = rest.split_first().map(|(x, y)| (x, y.to_vec()));
 
         let Some((name, mut call_args)) = name_args else {
@@ -169,6 +178,21 @@ If you create a custom command with this name, that will be used instead."
             executable
         };
 
+        let envs = env_to_strings(engine_state, stack)?;
+        let args = eval_external_arguments(engine_state, stack, call_args)?;
+
+        if elevated {
+            return run_elevated(
+                engine_state,
+                stack,
+                call,
+                &expanded_name,
+                cwd.as_ref(),
+                envs,
+                args,
+            );
+        }
+
         // Create the command.
         let mut command = std::process::Command::new(&executable);
 
@@ -176,12 +200,10 @@ If you create a custom command with this name, that will be used instead."
         command.current_dir(cwd);
 
         // Configure environment variables.
-        let envs = env_to_strings(engine_state, stack)?;
         command.env_clear();
         command.envs(envs);
 
         // Configure args.
-        let args = eval_external_arguments(engine_state, stack, call_args)?;
         #[cfg(windows)]
         if is_cmd_internal_command(&name_str) || pathext_script_in_windows {
             // The /D flag disables execution of AutoRun commands from registry.
@@ -289,8 +311,19 @@ If you create a custom command with this name, that will be used instead."
         );
 
         let mut child = child.map_err(|err| {
+            #[cfg(windows)]
+            if err.raw_os_error() == Some(ERROR_ELEVATION_REQUIRED) {
+                return ShellError::Generic(
+                    GenericError::new(
+                        "Command requires elevation",
+                        "this command must be run as administrator",
+                        call.head,
+                    )
+                    .with_help("run it with `run-external --elevated`"),
+                );
+            }
             let context = format!("Could not spawn foreground child: {err}");
-            IoError::new_internal(err, context)
+            ShellError::Io(IoError::new_internal(err, context))
         })?;
 
         if let Some(thread_job) = engine_state.current_thread_job()
@@ -361,6 +394,11 @@ If you create a custom command with this name, that will be used instead."
                 example: r#"run-external "echo" "-n" "hello" | split chars"#,
                 result: None,
             },
+            Example {
+                description: "Run an external command as administrator (Windows only)",
+                example: r#"run-external --elevated "net" "start" "w32time""#,
+                result: None,
+            },
             Example {
                 description: "Redirect stderr from an external command into the pipeline",
                 example: r#"run-external "nu" "-c" "print -e hello" e>| split chars"#,
@@ -370,6 +408,140 @@ If you create a custom command with this name, that will be used instead."
     }
 }
 
+/// The error of `CreateProcess` for programs whose manifest asks to run as administrator.
+#[cfg(windows)]
+const ERROR_ELEVATION_REQUIRED: i32 = 740;
+
+/// Environment variables that are set by nu itself, and that `load-env` can't set.
+#[cfg(windows)]
+const AUTOMATIC_ENV_VARS: [&str; 3] = ["PWD", "FILE_PWD", "CURRENT_FILE"];
+
+/// Tells apart the files passing commands to elevated processes of the same nu process.
+#[cfg(windows)]
+static NEXT_ELEVATED_JOB: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Run the command as administrator, waiting for it to exit.
+///
+/// An elevated process doesn't inherit the environment of the process starting it, so this starts
+/// an elevated nu that reads the command, its arguments and the environment from a file, and runs
+/// it with `run-external`. Going through nu also runs cmd.exe built-ins, PowerShell scripts and the
+/// app execution aliases of Store apps the same way as without `--elevated`.
+#[cfg(windows)]
+fn run_elevated(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    name: &Path,
+    cwd: &Path,
+    envs: std::collections::HashMap<String, String>,
+    args: Vec<Spanned<OsString>>,
+) -> Result<PipelineData, ShellError> {
+    let head = call.head;
+    let env = envs
+        .into_iter()
+        .filter(|(name, _)| {
+            !AUTOMATIC_ENV_VARS
+                .iter()
+                .any(|var| var.eq_ignore_case(name))
+        })
+        .map(|(name, value)| (name, Value::string(value, head)))
+        .collect();
+    let args = args
+        .into_iter()
+        .map(|arg| Value::string(arg.item.to_string_lossy(), arg.span))
+        .collect();
+    let job = Value::record(
+        record! {
+            "cwd" => Value::string(cwd.to_string_lossy(), head),
+            "name" => Value::string(name.to_string_lossy(), head),
+            "args" => Value::list(args, head),
+            "env" => Value::record(env, head),
+        },
+        head,
+    );
+    let job = nuon::to_nuon(
+        engine_state,
+        &job,
+        nuon::ToNuonConfig::default().span(Some(head)),
+    )?;
+
+    let id = NEXT_ELEVATED_JOB.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!("nu-elevated-{}-{id}.nuon", std::process::id()));
+    std::fs::write(&path, job).map_err(|err| IoError::new(err, head, path.clone()))?;
+    let path_literal = nuon::to_nuon(
+        engine_state,
+        &Value::string(path.to_string_lossy(), head),
+        nuon::ToNuonConfig::default().span(Some(head)),
+    )?;
+    // The elevated nu removes the file once it read it
+    let script = format!(
+        "let job = open --raw {path_literal} | from nuon; rm {path_literal}; cd $job.cwd; \
+        load-env $job.env; run-external $job.name ...$job.args"
+    );
+    let nu = std::env::current_exe()
+        .map_err(|err| IoError::new_internal(err, "Could not find the nu executable"))?;
+
+    let signals = engine_state.signals();
+    let exit_code = nu_system::run_elevated(
+        nu.as_os_str(),
+        [
+            "--no-config-file",
+            "--no-std-lib",
+            "--commands",
+            script.as_str(),
+        ]
+        .map(OsStr::new),
+        Some(cwd),
+        || signals.interrupted(),
+    )
+    .map_err(|err| {
+        let _ = std::fs::remove_file(&path);
+        if err.kind() == std::io::ErrorKind::PermissionDenied {
+            ShellError::Generic(GenericError::new(
+                "Elevation was declined",
+                "the UAC prompt was cancelled",
+                call.head,
+            ))
+        } else {
+            ShellError::Io(IoError::new_internal(
+                err,
+                "Could not run the command as administrator",
+            ))
+        }
+    })?;
+    let Some(exit_code) = exit_code else {
+        return Err(ShellError::Interrupted { span: call.head });
+    };
+    stack.set_last_exit_code(exit_code, call.head);
+    match std::num::NonZeroI32::new(exit_code) {
+        Some(exit_code) => Err(ShellError::NonZeroExitCode {
+            exit_code,
+            span: call.head,
+        }),
+        None => Ok(PipelineData::empty()),
+    }
+}
+
+#[cfg(not(windows))]
+fn run_elevated(
+    _engine_state: &EngineState,
+    _stack: &mut Stack,
+    call: &Call,
+    _name: &Path,
+    _cwd: &Path,
+    _envs: std::collections::HashMap<String, String>,
+    _args: Vec<Spanned<OsString>>,
+) -> Result<PipelineData, ShellError> {
+    Err(ShellError::Generic(
+        GenericError::new(
+            "`--elevated` is only supported on Windows",
+            "not supported on this platform",
+            call.head,
+        )
+        .with_help("use `sudo` or `doas` to run commands as another user"),
+    ))
+}
+
 /// Evaluate all arguments, performing expansions when necessary.
 pub fn eval_external_arguments(
     engine_state: &EngineState,
@@ -584,6 +756,8 @@ pub fn command_not_found(
         };
     }
 
+    let suggest_packages = stack.get_config(engine_state).suggest_packages;
+
     // Making this a closure allows using return inside instead of nesting if-else's
     let help = (|| {
         // The command might be from another module. Try to find it.
@@ -652,6 +826,11 @@ pub fn command_not_found(
             );
         }
 
+        // Maybe a package provides it. This runs the package manager, so it's opt-in.
+        if suggest_packages && let Some(hint) = package_providers::package_hint(name) {
+            return hint;
+        }
+
         // We found nothing useful. Give up and return a generic error message.
         format!("`{name}` is neither a Nushell built-in or a known external command")
     })();
@@ -780,6 +959,56 @@ mod test {
         })
     }
 
+    struct FakeProvider;
+
+    impl package_providers::PackageProvider for FakeProvider {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        fn lookup(&self, command: &str) -> std::io::Result<Vec<String>> {
+            Ok(match command {
+                "nu-test-packaged-command" => vec!["nu-test-package".into()],
+                _ => vec![],
+            })
+        }
+
+        fn install_command(&self, package: &str) -> String {
+            format!("fake install {package}")
+        }
+    }
+
+    #[test]
+    fn command_not_found_suggests_packages_when_enabled() {
+        package_providers::register_package_provider(FakeProvider);
+
+        let mut engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        let cwd = nu_path::AbsolutePathBuf::try_from(std::env::temp_dir())
+            .expect("temp dir should be absolute");
+        let name = "nu-test-packaged-command";
+
+        let help = |engine_state: &EngineState, stack: &mut Stack| match command_not_found(
+            name,
+            Span::test_data(),
+            engine_state,
+            stack,
+            &cwd,
+        ) {
+            ShellError::ExternalCommand { help, .. } => help,
+            err => panic!("unexpected error: {err:?}"),
+        };
+
+        assert!(!help(&engine_state, &mut stack).contains("nu-test-package"));
+
+        let config = nu_protocol::Config {
+            suggest_packages: true,
+            ..Default::default()
+        };
+        engine_state.set_config(config);
+        assert!(help(&engine_state, &mut stack).contains("`nu-test-package` package (fake)"));
+    }
+
     #[test]
     fn test_write_pipeline_data() {
         let mut engine_state = EngineState::new();
@@ -1,6 +1,6 @@
 use nu_engine::{command_prelude::*, env};
-use nu_protocol::PipelineMetadata;
-use nu_protocol::engine::CommandType;
+use nu_protocol::engine::{CommandType, Visibility};
+use nu_protocol::{DeclId, ModuleId, OverlayId, PipelineMetadata};
 use std::collections::HashSet;
 use std::ffi::{OsStr, OsString};
 use std::fs;
@@ -21,7 +21,16 @@ impl Command for Which {
             .input_output_types(vec![(Type::Nothing, Type::table())])
             .allow_variants_without_examples(true)
             .rest("applications", SyntaxShape::String, "Application(s).")
-            .switch("all", "List all executables.", Some('a'))
+            .switch(
+                "all",
+                "List all executables, and where each command is defined.",
+                Some('a'),
+            )
+            .switch(
+                "follow-symlinks",
+                "Report the resolved path of externals that are symbolic links.",
+                None,
+            )
             .category(Category::System)
     }
 
@@ -29,6 +38,12 @@ impl Command for Which {
         "Finds a program file, alias or custom command. If `application` is not provided, all deduplicated commands will be returned."
     }
 
+    fn extra_description(&self) -> &str {
+        "With `--all`, every definition with the name is listed, including the ones the first \
+        is shadowing, and the rows get the columns `module`, `overlay`, `line`, `column` and \
+        `shadowed`, telling where the command is defined and whether it's hidden by another one."
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec![
             "find",
@@ -62,6 +77,16 @@ impl Command for Which {
                 example: "which -a",
                 result: None,
             },
+            Example {
+                description: "Find out which definition of `ls` shadows the others, and where each one comes from",
+                example: "which --all ls | select command type module overlay shadowed",
+                result: None,
+            },
+            Example {
+                description: "Find the file a symbolic link in PATH points to",
+                example: "which --follow-symlinks python3",
+                result: None,
+            },
         ]
     }
 }
@@ -74,32 +99,129 @@ fn file_for_span(engine_state: &EngineState, span: Span) -> Option<String> {
         .map(|f| f.name.to_string())
 }
 
-/// Returns the source file path for a declaration, if it can be determined.
+/// Returns the line and column (both counted from 1) where `span` starts in its file.
+fn position_of_span(engine_state: &EngineState, span: Span) -> Option<(usize, usize)> {
+    let file = engine_state
+        .files()
+        .find(|f| f.covered_span.contains_span(span))?;
+    let before = file
+        .content
+        .get(..span.start.checked_sub(file.covered_span.start)?)?;
+    let line = before.iter().filter(|&&byte| byte == b'\n').count() + 1;
+    let line_start = before
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |index| index + 1);
+    let column = String::from_utf8_lossy(&before[line_start..])
+        .chars()
+        .count()
+        + 1;
+    Some((line, column))
+}
+
+/// Returns the span a declaration is defined at, if it can be determined.
 ///
-/// - Aliases: resolved via `decl_span()` (the alias expansion span)
-/// - Custom commands: resolved from the block's span via `block_id()`
-/// - Plugins: resolved from the plugin identity's filename
-/// - Known externals (`extern` declarations): resolved via `decl_span()`
-fn file_for_decl(
-    engine_state: &EngineState,
-    decl: &dyn nu_protocol::engine::Command,
-) -> Option<String> {
-    if let Some(block_id) = decl.block_id() {
-        return engine_state
-            .get_block(block_id)
-            .span
-            .and_then(|sp| file_for_span(engine_state, sp));
+/// - Custom commands: the block's span via `block_id()`
+/// - Aliases and known externals (`extern` declarations): `decl_span()`
+fn definition_span(engine_state: &EngineState, decl: &dyn Command) -> Option<Span> {
+    match decl.block_id() {
+        Some(block_id) => engine_state.get_block(block_id).span,
+        None => decl.decl_span(),
     }
+}
+
+/// Returns the source file path for a declaration, if it can be determined.
+///
+/// Plugins are resolved from the plugin identity's filename, everything else from the
+/// [`definition_span`].
+fn file_for_decl(engine_state: &EngineState, decl: &dyn Command) -> Option<String> {
     #[cfg(feature = "plugin")]
     if decl.is_plugin() {
         return decl
             .plugin_identity()
             .map(|id| id.filename().to_string_lossy().to_string());
     }
-    if let Some(span) = decl.decl_span() {
-        return file_for_span(engine_state, span);
+    definition_span(engine_state, decl).and_then(|span| file_for_span(engine_state, span))
+}
+
+/// Returns the name of the module defining a declaration.
+///
+/// Submodules are added to the engine before their parents, so the first module exporting the
+/// declaration is the one defining it, not one re-exporting it.
+fn module_of_decl(engine_state: &EngineState, decl_id: DeclId) -> Option<String> {
+    (0..engine_state.num_modules())
+        .map(|index| engine_state.get_module(ModuleId::new(index)))
+        .find(|module| {
+            module.main == Some(decl_id) || module.decls.values().any(|id| *id == decl_id)
+        })
+        .map(|module| String::from_utf8_lossy(&module.name).to_string())
+}
+
+/// Returns the declarations named `name` in the active overlays, the one in use first, followed
+/// by the ones it shadows.
+fn find_decls(engine_state: &EngineState, name: &str) -> Vec<(OverlayId, DeclId)> {
+    let mut visibility = Visibility::new();
+    let mut found: Vec<(OverlayId, DeclId)> = vec![];
+
+    for overlay_id in engine_state.active_overlay_ids(&[]).rev() {
+        let overlay_frame = engine_state.get_overlay(*overlay_id);
+        visibility.append(&overlay_frame.visibility);
+
+        if let Some(decl_id) = overlay_frame.get_decl(name.as_bytes())
+            && visibility.is_decl_id_visible(&decl_id)
+            && !found.iter().any(|(_, id)| *id == decl_id)
+        {
+            found.push((*overlay_id, decl_id));
+        }
+    }
+
+    found
+}
+
+/// Where a command is defined, reported by `which --all`.
+#[derive(Debug, Default)]
+struct Origin {
+    module: Option<String>,
+    overlay: Option<String>,
+    position: Option<(usize, usize)>,
+    /// Whether another command with the same name is run instead of this one
+    shadowed: bool,
+}
+
+impl Origin {
+    fn of_decl(
+        engine_state: &EngineState,
+        overlay_id: OverlayId,
+        decl_id: DeclId,
+        shadowed: bool,
+    ) -> Self {
+        let decl = engine_state.get_decl(decl_id);
+        Origin {
+            module: module_of_decl(engine_state, decl_id),
+            overlay: Some(
+                String::from_utf8_lossy(engine_state.get_overlay_name(overlay_id)).to_string(),
+            ),
+            position: definition_span(engine_state, decl)
+                .and_then(|span| position_of_span(engine_state, span)),
+            shadowed,
+        }
+    }
+
+    fn external(shadowed: bool) -> Self {
+        Origin {
+            shadowed,
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns `path`, resolving symbolic links when asked to.
+fn resolve_path(path: PathBuf, cwd: &Path, follow_symlinks: bool) -> PathBuf {
+    if follow_symlinks {
+        nu_path::canonicalize_with(&path, cwd).unwrap_or(path)
+    } else {
+        path
     }
-    None
 }
 
 // Shortcut for creating an entry to the output table.
@@ -109,6 +231,7 @@ fn entry(
     cmd_type: CommandType,
     definition: Option<String>,
     file: Option<String>,
+    origin: Option<Origin>,
     span: Span,
 ) -> Value {
     let arg = arg.into();
@@ -129,11 +252,34 @@ fn entry(
         record.insert("definition", Value::string(def, span));
     }
 
+    if let Some(origin) = origin {
+        let optional_string = |value: Option<String>| {
+            value.map_or(Value::nothing(span), |value| Value::string(value, span))
+        };
+        let (line, column) = match origin.position {
+            Some((line, column)) => (
+                Value::int(line as i64, span),
+                Value::int(column as i64, span),
+            ),
+            None => (Value::nothing(span), Value::nothing(span)),
+        };
+        record.insert("module", optional_string(origin.module));
+        record.insert("overlay", optional_string(origin.overlay));
+        record.insert("line", line);
+        record.insert("column", column);
+        record.insert("shadowed", Value::bool(origin.shadowed, span));
+    }
+
     Value::record(record, span)
 }
 
-fn get_entry_in_commands(engine_state: &EngineState, name: &str, span: Span) -> Option<Value> {
-    let decl_id = engine_state.find_decl(name.as_bytes(), &[])?;
+fn decl_entry(
+    engine_state: &EngineState,
+    name: impl Into<String>,
+    decl_id: DeclId,
+    origin: Option<Origin>,
+    span: Span,
+) -> Value {
     let decl = engine_state.get_decl(decl_id);
     let definition = if decl.command_type() == CommandType::Alias {
         decl.as_alias().map(|alias| {
@@ -144,7 +290,31 @@ fn get_entry_in_commands(engine_state: &EngineState, name: &str, span: Span) ->
         None
     };
     let file = file_for_decl(engine_state, decl);
-    Some(entry(name, "", decl.command_type(), definition, file, span))
+    entry(
+        name,
+        "",
+        decl.command_type(),
+        definition,
+        file,
+        origin,
+        span,
+    )
+}
+
+fn get_entry_in_commands(engine_state: &EngineState, name: &str, span: Span) -> Option<Value> {
+    let decl_id = engine_state.find_decl(name.as_bytes(), &[])?;
+    Some(decl_entry(engine_state, name, decl_id, None, span))
+}
+
+fn get_all_entries_in_commands(engine_state: &EngineState, name: &str, span: Span) -> Vec<Value> {
+    find_decls(engine_state, name)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (overlay_id, decl_id))| {
+            let origin = Origin::of_decl(engine_state, overlay_id, decl_id, index > 0);
+            decl_entry(engine_state, name, decl_id, Some(origin), span)
+        })
+        .collect()
 }
 
 /// Reads `$env.PATHEXT` from the shell environment as an `OsString`, mirroring
@@ -239,22 +409,27 @@ fn get_first_entry_in_path(
     cwd: impl AsRef<Path>,
     paths: impl AsRef<OsStr>,
     path_ext: &Option<OsString>,
+    follow_symlinks: bool,
 ) -> Option<Value> {
+    let cwd = cwd.as_ref();
     WhichConfig::new_with_sys(NuWhichSys {
         path_ext: path_ext.clone(),
     })
     .binary_name(item.into())
-    .custom_cwd(cwd.as_ref().to_path_buf())
+    .custom_cwd(cwd.to_path_buf())
     .custom_path_list(paths.as_ref().to_os_string())
     .first_result()
     .map(|path| {
-        let full_path = path.to_string_lossy().to_string();
+        let full_path = resolve_path(path, cwd, follow_symlinks)
+            .to_string_lossy()
+            .to_string();
         entry(
             item,
             full_path.clone(),
             CommandType::External,
             None,
             Some(full_path),
+            None,
             span,
         )
     })
@@ -267,23 +442,28 @@ fn get_all_entries_in_path(
     cwd: impl AsRef<Path>,
     paths: impl AsRef<OsStr>,
     path_ext: &Option<OsString>,
+    follow_symlinks: bool,
+    shadowed: bool,
 ) -> Vec<Value> {
     // The results may contain the same canonical path more than once. On systems
     // where PATH contains both a real directory and a symlink pointing to the same
     // place (e.g. `/usr/bin` and `/bin -> /usr/bin` on WSL/Debian), the same path
     // would appear multiple times. The HashSet deduplicates those before we build
     // the output rows.
+    let cwd = cwd.as_ref();
     let mut seen = HashSet::new();
     WhichConfig::new_with_sys(NuWhichSys {
         path_ext: path_ext.clone(),
     })
     .binary_name(item.into())
-    .custom_cwd(cwd.as_ref().to_path_buf())
+    .custom_cwd(cwd.to_path_buf())
     .custom_path_list(paths.as_ref().to_os_string())
     .all_results()
     .map(|iter| {
-        iter.filter(|path| seen.insert(path.clone()))
-            .map(|path| {
+        iter.map(|path| resolve_path(path, cwd, follow_symlinks))
+            .filter(|path| seen.insert(path.clone()))
+            .enumerate()
+            .map(|(index, path)| {
                 let full_path = path.to_string_lossy().to_string();
                 entry(
                     item,
@@ -291,6 +471,7 @@ fn get_all_entries_in_path(
                     CommandType::External,
                     None,
                     Some(full_path),
+                    Some(Origin::external(shadowed || index > 0)),
                     span,
                 )
             })
@@ -301,9 +482,11 @@ fn get_all_entries_in_path(
 
 fn list_all_executables(
     engine_state: &EngineState,
+    cwd: &Path,
     paths: impl AsRef<OsStr>,
     path_ext: &Option<OsString>,
     all: bool,
+    follow_symlinks: bool,
     span: Span,
 ) -> Vec<Value> {
     let decls = engine_state.get_decls_sorted(false);
@@ -314,25 +497,18 @@ fn list_all_executables(
     for (name_bytes, decl_id) in decls {
         let name = String::from_utf8_lossy(&name_bytes).to_string();
         seen_commands.insert(name.clone());
-        let decl = engine_state.get_decl(decl_id);
-        let definition = if decl.command_type() == CommandType::Alias {
-            decl.as_alias().map(|alias| {
-                String::from_utf8_lossy(engine_state.get_span_contents(alias.wrapped_call.span))
-                    .to_string()
+        let origin = all
+            .then(|| {
+                find_decls(engine_state, &name)
+                    .into_iter()
+                    .find(|(_, id)| *id == decl_id)
+                    .map(|(overlay_id, _)| {
+                        Origin::of_decl(engine_state, overlay_id, decl_id, false)
+                    })
             })
-        } else {
-            None
-        };
-        let file = file_for_decl(engine_state, decl);
-
-        results.push(entry(
-            name,
-            String::new(),
-            decl.command_type(),
-            definition,
-            file,
-            span,
-        ));
+            .flatten();
+
+        results.push(decl_entry(engine_state, name, decl_id, origin, span));
     }
 
     // Add PATH executables
@@ -348,17 +524,21 @@ fn list_all_executables(
             }
             let filename = path.file_name()?.to_string_lossy().to_string();
 
-            if !all && !seen_commands.insert(filename.clone()) {
+            let shadowed = !seen_commands.insert(filename.clone());
+            if !all && shadowed {
                 return None;
             }
 
-            let full_path = path.to_string_lossy().to_string();
+            let full_path = resolve_path(path, cwd, follow_symlinks)
+                .to_string_lossy()
+                .to_string();
             Some(entry(
                 filename,
                 full_path.clone(),
                 CommandType::External,
                 None,
                 Some(full_path),
+                all.then(|| Origin::external(shadowed)),
                 span,
             ))
         });
@@ -371,16 +551,22 @@ fn list_all_executables(
 struct WhichArgs {
     applications: Vec<Spanned<String>>,
     all: bool,
+    follow_symlinks: bool,
 }
 
 fn which_single(
     application: Spanned<String>,
-    all: bool,
+    which_args: &WhichArgs,
     engine_state: &EngineState,
     cwd: impl AsRef<Path>,
     paths: impl AsRef<OsStr>,
     path_ext: &Option<OsString>,
 ) -> Vec<Value> {
+    let WhichArgs {
+        all,
+        follow_symlinks,
+        ..
+    } = *which_args;
     let cwd = cwd.as_ref();
     let paths = paths.as_ref();
     let (external, prog_name) = if application.item.starts_with('^') {
@@ -392,28 +578,51 @@ fn which_single(
     // If prog_name is an external command, don't search for nu-specific programs.
     // If all is false, we can save some time by only searching for the first match.
     match (all, external) {
-        (true, true) => get_all_entries_in_path(&prog_name, application.span, cwd, paths, path_ext),
+        (true, true) => get_all_entries_in_path(
+            &prog_name,
+            application.span,
+            cwd,
+            paths,
+            path_ext,
+            follow_symlinks,
+            false,
+        ),
         (true, false) => {
-            let mut output: Vec<Value> = vec![];
-            if let Some(entry) = get_entry_in_commands(engine_state, &prog_name, application.span) {
-                output.push(entry);
-            }
+            let mut output =
+                get_all_entries_in_commands(engine_state, &prog_name, application.span);
+            let shadowed = !output.is_empty();
             output.extend(get_all_entries_in_path(
                 &prog_name,
                 application.span,
                 cwd,
                 paths,
                 path_ext,
+                follow_symlinks,
+                shadowed,
             ));
             output
         }
-        (false, true) => {
-            get_first_entry_in_path(&prog_name, application.span, cwd, paths, path_ext)
-                .into_iter()
-                .collect()
-        }
+        (false, true) => get_first_entry_in_path(
+            &prog_name,
+            application.span,
+            cwd,
+            paths,
+            path_ext,
+            follow_symlinks,
+        )
+        .into_iter()
+        .collect(),
         (false, false) => get_entry_in_commands(engine_state, &prog_name, application.span)
-            .or_else(|| get_first_entry_in_path(&prog_name, application.span, cwd, paths, path_ext))
+            .or_else(|| {
+                get_first_entry_in_path(
+                    &prog_name,
+                    application.span,
+                    cwd,
+                    paths,
+                    path_ext,
+                    follow_symlinks,
+                )
+            })
             .into_iter()
             .collect(),
     }
@@ -428,6 +637,7 @@ fn which(
     let which_args = WhichArgs {
         applications: call.rest(engine_state, stack, 0)?,
         all: call.has_flag(engine_state, stack, "all")?,
+        follow_symlinks: call.has_flag(engine_state, stack, "follow-symlinks")?,
     };
 
     let mut output = vec![];
@@ -446,16 +656,29 @@ fn which(
     let metadata = PipelineMetadata::default().with_path_columns(vec!["path".into()]);
 
     if which_args.applications.is_empty() {
-        return Ok(
-            list_all_executables(engine_state, &paths, &path_ext, which_args.all, head)
-                .into_iter()
-                .into_pipeline_data(head, engine_state.signals().clone())
-                .set_metadata(Some(metadata)),
-        );
+        return Ok(list_all_executables(
+            engine_state,
+            Path::new(&cwd),
+            &paths,
+            &path_ext,
+            which_args.all,
+            which_args.follow_symlinks,
+            head,
+        )
+        .into_iter()
+        .into_pipeline_data(head, engine_state.signals().clone())
+        .set_metadata(Some(metadata)));
     }
 
-    for app in which_args.applications {
-        let values = which_single(app, which_args.all, engine_state, &cwd, &paths, &path_ext);
+    for app in &which_args.applications {
+        let values = which_single(
+            app.clone(),
+            &which_args,
+            engine_state,
+            &cwd,
+            &paths,
+            &path_ext,
+        );
         output.extend(values);
     }
 
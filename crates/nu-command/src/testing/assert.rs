@@ -0,0 +1,134 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::{FromValue, LabeledError};
+
+#[derive(Clone)]
+pub struct Assert;
+
+impl Command for Assert {
+    fn name(&self) -> &str {
+        "assert"
+    }
+
+    fn description(&self) -> &str {
+        "Return an error if a condition isn't true."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Custom assert commands can point the error at their arguments with `--error-label` and \
+`--error-labels`, giving the span of a value with `(metadata $value).span`. More assertions, like \
+`assert error`, are in `std/assert`."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("assert")
+            .input_output_types(vec![(Type::Any, Type::Nothing)])
+            .required(
+                "condition",
+                SyntaxShape::Boolean,
+                "The condition, which should be true.",
+            )
+            .optional(
+                "message",
+                SyntaxShape::String,
+                "The message of the error if the condition is false.",
+            )
+            .named(
+                "error-label",
+                SyntaxShape::Record(vec![]),
+                "A label for the error, as `{text: string, span: {start: int, end: int}}`.",
+                None,
+            )
+            .named(
+                "error-labels",
+                SyntaxShape::Table(vec![]),
+                "Labels for the error, each like the one of `--error-label`.",
+                None,
+            )
+            .category(Category::Misc)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["check", "test", "expect"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "This assert passes.",
+                example: "assert (3 == 3)",
+                result: None,
+            },
+            Example {
+                description: "This assert fails with a message.",
+                example: "assert (42 == 3) 'the answer is wrong'",
+                result: None,
+            },
+            Example {
+                description: "Write a custom assert command that points at its argument.",
+                example: r#"def "assert even" [number: int] {
+        assert ($number mod 2 == 0) --error-label {
+            text: $"($number) is not an even number",
+            span: (metadata $number).span,
+        }
+    }"#,
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let condition: Spanned<bool> = call.req(engine_state, stack, 0)?;
+        // Wrappers like the ones of `std/assert` pass their own optional arguments on, so a null
+        // is the same as a missing argument
+        let message: Option<String> = call.opt::<Option<_>>(engine_state, stack, 1)?.flatten();
+        let label: Option<Value> = call
+            .get_flag::<Option<_>>(engine_state, stack, "error-label")?
+            .flatten();
+        let labels: Option<Vec<Value>> = call
+            .get_flag::<Option<_>>(engine_state, stack, "error-labels")?
+            .flatten();
+
+        if condition.item {
+            return Ok(PipelineData::empty());
+        }
+
+        let labels = labels
+            .into_iter()
+            .flatten()
+            .chain(label)
+            .map(ErrorLabel::from_value)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut error = LabeledError::new(message.unwrap_or_else(|| "Assertion failed.".into()));
+        if labels.is_empty() {
+            error = error.with_label("It is not true.", condition.span);
+        }
+        for label in labels {
+            error = error.with_label(label.text, label.span);
+        }
+        Err(error.into())
+    }
+}
+
+/// A label given with `--error-label`.
+#[derive(FromValue)]
+struct ErrorLabel {
+    text: String,
+    span: Span,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(Assert)
+    }
+}
@@ -0,0 +1,362 @@
+use itertools::{EitherOrBoth, Itertools};
+use nu_engine::command_prelude::*;
+use nu_protocol::LabeledError;
+use nuon::{ToNuonConfig, to_nuon};
+
+/// How many changed cells get a label of their own.
+const MAX_CELL_LABELS: usize = 8;
+/// How many changed cells are listed in the help of the error.
+const MAX_LISTED_CHANGES: usize = 20;
+/// How many characters of a value are shown.
+const MAX_VALUE_WIDTH: usize = 40;
+
+#[derive(Clone)]
+pub struct AssertEqual;
+
+impl Command for AssertEqual {
+    fn name(&self) -> &str {
+        "assert equal"
+    }
+
+    fn description(&self) -> &str {
+        "Return an error if two values aren't equal, showing how they differ."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Records and lists, and so tables, are compared cell by cell. When they differ, the error \
+lists the cells that were removed, added or changed side by side, and points at the cells whose \
+spans are known, like the ones of literals."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("assert equal")
+            .input_output_types(vec![(Type::Any, Type::Nothing)])
+            .required("left", SyntaxShape::Any, "The value to check.")
+            .required(
+                "right",
+                SyntaxShape::Any,
+                "The value it should be equal to.",
+            )
+            .optional(
+                "message",
+                SyntaxShape::String,
+                "The message of the error if the values differ.",
+            )
+            .category(Category::Misc)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["check", "test", "expect", "diff", "compare"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "This assert passes.",
+                example: "assert equal (0.1 + 0.2) 0.3",
+                result: None,
+            },
+            Example {
+                description: "This assert fails, listing the cells of the tables that differ.",
+                example: "assert equal [[name age]; [alice 30] [bob 25]] [[name age]; [alice 31]]",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let left: Value = call.req(engine_state, stack, 0)?;
+        let right: Value = call.req(engine_state, stack, 1)?;
+        // A null message from a wrapper command is the same as no message
+        let message: Option<String> = call.opt::<Option<_>>(engine_state, stack, 2)?.flatten();
+
+        let mut changes = vec![];
+        diff(String::new(), &left, &right, &mut changes);
+        if changes.is_empty() {
+            return Ok(PipelineData::empty());
+        }
+
+        let msg = match message {
+            Some(message) => format!("{message}\nThese are not equal."),
+            None => "These are not equal.".into(),
+        };
+        let sides = [Side::LEFT, Side::RIGHT];
+        Err(difference_error(engine_state, msg, sides, &left, &right, &changes).into())
+    }
+}
+
+/// How one of the compared values is called in an error.
+pub(super) struct Side {
+    name: &'static str,
+    /// Where a cell is, as in "only on the left"
+    place: &'static str,
+}
+
+impl Side {
+    pub(super) const LEFT: Side = Side::new("left", "on the left");
+    pub(super) const RIGHT: Side = Side::new("right", "on the right");
+
+    pub(super) const fn new(name: &'static str, place: &'static str) -> Self {
+        Side { name, place }
+    }
+}
+
+/// A cell that differs between two values, with its cell path.
+#[derive(Debug, PartialEq)]
+pub(super) enum Change<'a> {
+    Removed(String, &'a Value),
+    Added(String, &'a Value),
+    Changed(String, &'a Value, &'a Value),
+}
+
+/// Compares two values cell by cell, adding the cells that differ to `changes`.
+pub(super) fn diff<'a>(
+    path: String,
+    left: &'a Value,
+    right: &'a Value,
+    changes: &mut Vec<Change<'a>>,
+) {
+    match (left, right) {
+        (Value::Record { val: lhs, .. }, Value::Record { val: rhs, .. }) => {
+            for (column, lhs_value) in lhs.iter() {
+                let path = cell_path(&path, column);
+                match rhs.get(column) {
+                    Some(rhs_value) => diff(path, lhs_value, rhs_value, changes),
+                    None => changes.push(Change::Removed(path, lhs_value)),
+                }
+            }
+            for (column, rhs_value) in rhs.iter() {
+                if !lhs.contains(column) {
+                    changes.push(Change::Added(cell_path(&path, column), rhs_value));
+                }
+            }
+        }
+        (Value::List { vals: lhs, .. }, Value::List { vals: rhs, .. }) => {
+            for (index, pair) in lhs.iter().zip_longest(rhs.iter()).enumerate() {
+                let path = cell_path(&path, &index.to_string());
+                match pair {
+                    EitherOrBoth::Both(lhs, rhs) => diff(path, lhs, rhs, changes),
+                    EitherOrBoth::Left(lhs) => changes.push(Change::Removed(path, lhs)),
+                    EitherOrBoth::Right(rhs) => changes.push(Change::Added(path, rhs)),
+                }
+            }
+        }
+        _ => {
+            // The same comparison as `==`, which is lenient with floats
+            let equal = left.eq(Span::unknown(), right, Span::unknown());
+            if !matches!(equal, Ok(Value::Bool { val: true, .. })) {
+                changes.push(Change::Changed(path, left, right));
+            }
+        }
+    }
+}
+
+fn cell_path(parent: &str, member: &str) -> String {
+    let plain = !member.is_empty()
+        && member
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    let member = if plain {
+        member.to_string()
+    } else {
+        format!("{member:?}")
+    };
+    if parent.is_empty() {
+        member
+    } else {
+        format!("{parent}.{member}")
+    }
+}
+
+fn show(engine_state: &EngineState, value: &Value) -> String {
+    let text = to_nuon(engine_state, value, ToNuonConfig::default())
+        .unwrap_or_else(|_| value.to_abbreviated_string(&engine_state.config));
+    if text.chars().count() > MAX_VALUE_WIDTH {
+        let mut text: String = text.chars().take(MAX_VALUE_WIDTH - 1).collect();
+        text.push('…');
+        text
+    } else {
+        text
+    }
+}
+
+/// An error pointing at both values and at the cells that differ, listing them in its help.
+pub(super) fn difference_error(
+    engine_state: &EngineState,
+    msg: String,
+    [left_side, right_side]: [Side; 2],
+    left: &Value,
+    right: &Value,
+    changes: &[Change<'_>],
+) -> LabeledError {
+    let mut error = LabeledError::new(msg)
+        .with_label(
+            format!("{}: {}", left_side.name, show(engine_state, left)),
+            left.span(),
+        )
+        .with_label(
+            format!("{}: {}", right_side.name, show(engine_state, right)),
+            right.span(),
+        );
+
+    // The values as a whole differ, there's nothing more to show
+    if let [Change::Changed(path, ..)] = changes
+        && path.is_empty()
+    {
+        return error;
+    }
+
+    // Point at the cells that differ when they have spans of their own
+    let labelled = changes.iter().filter_map(|change| {
+        let (span, text) = match change {
+            Change::Removed(path, value) => (
+                value.span(),
+                format!("`{path}` is only {}", left_side.place),
+            ),
+            Change::Added(path, value) => (
+                value.span(),
+                format!("`{path}` is only {}", right_side.place),
+            ),
+            Change::Changed(path, lhs, rhs) => {
+                let rhs = show(engine_state, rhs);
+                (
+                    lhs.span(),
+                    format!("`{path}` is {rhs} {}", right_side.place),
+                )
+            }
+        };
+        let own_span = span != Span::unknown() && span != left.span() && span != right.span();
+        own_span.then_some((text, span))
+    });
+    for (text, span) in labelled.take(MAX_CELL_LABELS) {
+        error = error.with_label(text, span);
+    }
+
+    let names = [left_side.name, right_side.name];
+    error.with_help(side_by_side(engine_state, names, changes))
+}
+
+/// The changed cells, with the left and right values next to each other.
+fn side_by_side(
+    engine_state: &EngineState,
+    [left_name, right_name]: [&str; 2],
+    changes: &[Change<'_>],
+) -> String {
+    let rows: Vec<(char, &str, String, String)> = changes
+        .iter()
+        .take(MAX_LISTED_CHANGES)
+        .map(|change| match change {
+            Change::Removed(path, value) => {
+                ('-', path.as_str(), show(engine_state, value), "".into())
+            }
+            Change::Added(path, value) => {
+                ('+', path.as_str(), "".into(), show(engine_state, value))
+            }
+            Change::Changed(path, lhs, rhs) => (
+                '~',
+                path.as_str(),
+                show(engine_state, lhs),
+                show(engine_state, rhs),
+            ),
+        })
+        .collect();
+
+    let width = |column: &dyn Fn(&(char, &str, String, String)) -> usize, title: &str| {
+        rows.iter()
+            .map(column)
+            .chain([title.len()])
+            .max()
+            .unwrap_or(0)
+    };
+    let path_width = width(&|row| row.1.chars().count(), "cell");
+    let left_width = width(&|row| row.2.chars().count(), left_name);
+
+    let mut text = format!(
+        "{} cells differ:\n  {:path_width$}  {left_name:left_width$}  {right_name}",
+        changes.len(),
+        "cell",
+    );
+    for (marker, path, lhs, rhs) in &rows {
+        text.push_str(&format!(
+            "\n{marker} {path:path_width$}  {lhs:left_width$}  {rhs}"
+        ));
+    }
+    if changes.len() > rows.len() {
+        text.push_str(&format!("\n… and {} more", changes.len() - rows.len()));
+    }
+    text.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nu_protocol::record;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(AssertEqual)
+    }
+
+    fn changes<'a>(left: &'a Value, right: &'a Value) -> Vec<Change<'a>> {
+        let mut changes = vec![];
+        diff(String::new(), left, right, &mut changes);
+        changes
+    }
+
+    #[test]
+    fn equal_values_have_no_changes() {
+        let value = Value::test_record(record! {"a" => Value::test_int(1)});
+        assert!(changes(&value, &value.clone()).is_empty());
+        assert!(changes(&Value::test_float(0.1 + 0.2), &Value::test_float(0.3)).is_empty());
+    }
+
+    #[test]
+    fn diffs_records() {
+        let left = Value::test_record(record! {
+            "same" => Value::test_int(1),
+            "changed" => Value::test_int(2),
+            "removed" => Value::test_int(3),
+        });
+        let right = Value::test_record(record! {
+            "same" => Value::test_int(1),
+            "changed" => Value::test_int(20),
+            "added" => Value::test_int(4),
+        });
+        assert_eq!(
+            changes(&left, &right),
+            vec![
+                Change::Changed("changed".into(), &Value::test_int(2), &Value::test_int(20)),
+                Change::Removed("removed".into(), &Value::test_int(3)),
+                Change::Added("added".into(), &Value::test_int(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_tables() {
+        let row = |name: &str, age| {
+            Value::test_record(record! {
+                "name" => Value::test_string(name),
+                "age" => Value::test_int(age),
+            })
+        };
+        let left = Value::test_list(vec![row("alice", 30), row("bob", 25)]);
+        let right = Value::test_list(vec![row("alice", 31)]);
+        let changes = changes(&left, &right);
+        assert_eq!(changes.len(), 2);
+        assert!(matches!(&changes[0], Change::Changed(path, ..) if path == "0.age"));
+        assert!(matches!(&changes[1], Change::Removed(path, _) if path == "1"));
+    }
+
+    #[test]
+    fn quotes_unusual_columns() {
+        assert_eq!(cell_path("", "name"), "name");
+        assert_eq!(cell_path("0", "first name"), r#"0."first name""#);
+    }
+}
@@ -0,0 +1,150 @@
+use super::assert_equal::{Side, diff, difference_error};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::generic::GenericError;
+use nuon::{ToNuonConfig, ToStyle, from_nuon, to_nuon};
+use std::path::PathBuf;
+
+/// The directory snapshots are kept in, next to the file of the test.
+const SNAPSHOTS_DIR: &str = "__snapshots__";
+
+/// When this environment variable is true, snapshots that don't match are updated.
+pub(super) const UPDATE_SNAPSHOTS_ENV: &str = "NU_UPDATE_SNAPSHOTS";
+
+#[derive(Clone)]
+pub struct AssertSnapshot;
+
+impl Command for AssertSnapshot {
+    fn name(&self) -> &str {
+        "assert snapshot"
+    }
+
+    fn description(&self) -> &str {
+        "Return an error if the input doesn't match its stored snapshot."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The snapshot is the input saved as NUON in `__snapshots__/<name>.nuon`, next to the file \
+being run or in the current directory. The first time, the snapshot is written and the assertion \
+passes, so the file can be checked in as the baseline. After that, the input is compared to it \
+cell by cell, like with `assert equal`.
+
+When the output changes on purpose, update the snapshots with `--update`, by running the tests \
+with `test run --update-snapshots`, or by setting `$env.NU_UPDATE_SNAPSHOTS` to true."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("assert snapshot")
+            .input_output_types(vec![(Type::Any, Type::Nothing)])
+            .required(
+                "name",
+                SyntaxShape::String,
+                "The name of the snapshot, unique among the ones of the file.",
+            )
+            .switch(
+                "update",
+                "Save the input as the snapshot instead of comparing them.",
+                Some('u'),
+            )
+            .category(Category::Misc)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["golden", "baseline", "regression", "test", "bless"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Check the output of a pipeline against its snapshot.",
+                example: "open data.csv | group-by city | assert snapshot cities",
+                result: None,
+            },
+            Example {
+                description: "Save the current output as the snapshot.",
+                example: "open data.csv | group-by city | assert snapshot cities --update",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let update = call.has_flag(engine_state, stack, "update")?
+            || stack
+                .get_env_var(engine_state, UPDATE_SNAPSHOTS_ENV)
+                .is_some_and(|value| value.is_true());
+        let value = input.into_value(head)?;
+
+        if name.item.is_empty() || name.item.starts_with('.') || name.item.contains(['/', '\\']) {
+            return Err(ShellError::Generic(
+                GenericError::new(
+                    "Invalid snapshot name",
+                    "it must be a file name without a directory",
+                    name.span,
+                )
+                .with_help("use a name like `parse-config` or `sorted_rows`"),
+            ));
+        }
+
+        let dir = match stack.get_env_var(engine_state, "FILE_PWD") {
+            Some(file_pwd) => PathBuf::from(file_pwd.coerce_str()?.as_ref()),
+            None => engine_state.cwd(Some(stack))?.into_std_path_buf(),
+        }
+        .join(SNAPSHOTS_DIR);
+        let path = dir.join(format!("{}.nuon", name.item));
+
+        let nuon = to_nuon(
+            engine_state,
+            &value,
+            ToNuonConfig::default()
+                .style(ToStyle::Spaces(2))
+                .span(Some(head)),
+        )?;
+
+        if !update && path.exists() {
+            let baseline = std::fs::read_to_string(&path)
+                .map_err(|err| IoError::new(err, name.span, path.clone()))?;
+            let snapshot = from_nuon(&baseline, Some(head))?;
+
+            let mut changes = vec![];
+            diff(String::new(), &value, &snapshot, &mut changes);
+            if changes.is_empty() {
+                return Ok(PipelineData::empty());
+            }
+
+            let sides = [
+                Side::new("value", "in the value"),
+                Side::new("snapshot", "in the snapshot"),
+            ];
+            let msg = format!("The value doesn't match the snapshot `{}`.", name.item);
+            let error = difference_error(engine_state, msg, sides, &value, &snapshot, &changes);
+            let help = format!(
+                "{}\n\nIf the change is expected, update {} with `--update` or `test run --update-snapshots`.",
+                error.help.as_deref().unwrap_or_default(),
+                path.display()
+            );
+            return Err(error.with_help(help.trim_start()).into());
+        }
+
+        std::fs::create_dir_all(&dir).map_err(|err| IoError::new(err, head, dir.clone()))?;
+        std::fs::write(&path, nuon + "\n").map_err(|err| IoError::new(err, head, path))?;
+        Ok(PipelineData::empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(AssertSnapshot)
+    }
+}
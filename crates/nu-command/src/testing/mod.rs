@@ -0,0 +1,12 @@
+mod assert;
+mod assert_equal;
+mod assert_snapshot;
+mod report;
+mod test_;
+mod with_override;
+
+pub use assert::Assert;
+pub use assert_equal::AssertEqual;
+pub use assert_snapshot::AssertSnapshot;
+pub use test_::TestRun;
+pub use with_override::WithOverride;
@@ -0,0 +1,275 @@
+//! The results of `test run`, as a table, JSON or a JUnit XML report.
+
+use crate::formats::value_to_json_value;
+use nu_engine::command_prelude::*;
+use nu_protocol::{LabeledError, format_cli_error};
+use std::{fmt::Write, time::Duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Status {
+    Pass,
+    Fail,
+    Skip,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Skip => "skip",
+        }
+    }
+}
+
+/// Why a test failed.
+pub(super) struct Failure {
+    msg: String,
+    /// The error as it would be shown in the shell, without colors
+    rendered: String,
+    /// The error as a record, like `catch` gets it
+    details: Value,
+}
+
+impl Failure {
+    pub(super) fn new(
+        err: ShellError,
+        engine_state: &EngineState,
+        stack: &Stack,
+        span: Span,
+    ) -> Self {
+        let working_set = StateWorkingSet::new(engine_state);
+        let rendered = format_cli_error(Some(stack), &working_set, &err, None);
+        Self {
+            msg: err.to_string(),
+            rendered: nu_utils::strip_ansi_string_likely(rendered),
+            details: LabeledError::from(err).into_value(span, &working_set),
+        }
+    }
+
+    fn into_value(self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "msg" => Value::string(self.msg, span),
+                "rendered" => Value::string(self.rendered, span),
+                "details" => self.details,
+            },
+            span,
+        )
+    }
+}
+
+pub(super) struct TestResult {
+    pub(super) suite: String,
+    pub(super) name: String,
+    pub(super) status: Status,
+    pub(super) duration: Duration,
+    pub(super) failure: Option<Failure>,
+}
+
+impl TestResult {
+    fn into_value(self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "suite" => Value::string(self.suite, span),
+                "name" => Value::string(self.name, span),
+                "status" => Value::string(self.status.as_str(), span),
+                "duration" => duration_value(self.duration, span),
+                "error" => self
+                    .failure
+                    .map(|failure| failure.into_value(span))
+                    .unwrap_or_else(|| Value::nothing(span)),
+            },
+            span,
+        )
+    }
+}
+
+fn duration_value(duration: Duration, span: Span) -> Value {
+    Value::duration(i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX), span)
+}
+
+struct Summary {
+    total: usize,
+    failed: usize,
+    skipped: usize,
+    duration: Duration,
+}
+
+impl Summary {
+    fn of<'a>(results: impl IntoIterator<Item = &'a TestResult>) -> Self {
+        let mut summary = Summary {
+            total: 0,
+            failed: 0,
+            skipped: 0,
+            duration: Duration::ZERO,
+        };
+        for result in results {
+            summary.total += 1;
+            summary.duration += result.duration;
+            match result.status {
+                Status::Pass => {}
+                Status::Fail => summary.failed += 1,
+                Status::Skip => summary.skipped += 1,
+            }
+        }
+        summary
+    }
+}
+
+pub(super) fn table(results: Vec<TestResult>, span: Span) -> Value {
+    Value::list(
+        results
+            .into_iter()
+            .map(|result| result.into_value(span))
+            .collect(),
+        span,
+    )
+}
+
+pub(super) fn json(
+    engine_state: &EngineState,
+    results: Vec<TestResult>,
+    span: Span,
+) -> Result<String, ShellError> {
+    let summary = Summary::of(&results);
+    let report = Value::record(
+        record! {
+            "summary" => Value::record(
+                record! {
+                    "total" => Value::int(summary.total as i64, span),
+                    "passed" => Value::int((summary.total - summary.failed - summary.skipped) as i64, span),
+                    "failed" => Value::int(summary.failed as i64, span),
+                    "skipped" => Value::int(summary.skipped as i64, span),
+                    "duration" => duration_value(summary.duration, span),
+                },
+                span,
+            ),
+            "tests" => table(results, span),
+        },
+        span,
+    );
+    let json = value_to_json_value(engine_state, report, span, false)?;
+    nu_json::to_string_with_indent(&json, 2).map_err(|_| ShellError::CantConvert {
+        to_type: "JSON".into(),
+        from_type: "test report".into(),
+        span,
+        help: None,
+    })
+}
+
+/// A JUnit XML report, with a `<testsuite>` for each suite.
+pub(super) fn junit(results: &[TestResult]) -> String {
+    let mut suites: Vec<&str> = vec![];
+    for result in results {
+        if !suites.contains(&result.suite.as_str()) {
+            suites.push(&result.suite);
+        }
+    }
+
+    let summary = Summary::of(results);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        xml,
+        r#"<testsuites name="nushell" tests="{}" failures="{}" skipped="{}" time="{:.3}">"#,
+        summary.total,
+        summary.failed,
+        summary.skipped,
+        summary.duration.as_secs_f64()
+    )
+    .expect("writing to a String is infallible");
+
+    for suite in suites {
+        let tests: Vec<&TestResult> = results.iter().filter(|r| r.suite == suite).collect();
+        let summary = Summary::of(tests.iter().copied());
+        let name = escape_xml(if suite.is_empty() { "nushell" } else { suite });
+        writeln!(
+            xml,
+            r#"  <testsuite name="{name}" tests="{}" failures="{}" skipped="{}" time="{:.3}">"#,
+            summary.total,
+            summary.failed,
+            summary.skipped,
+            summary.duration.as_secs_f64()
+        )
+        .expect("writing to a String is infallible");
+
+        for test in tests {
+            let attributes = format!(
+                r#"name="{}" classname="{name}" time="{:.3}""#,
+                escape_xml(&test.name),
+                test.duration.as_secs_f64()
+            );
+            match (&test.status, &test.failure) {
+                (Status::Skip, _) => {
+                    writeln!(xml, "    <testcase {attributes}>\n      <skipped/>\n    </testcase>")
+                }
+                (Status::Fail, Some(failure)) => writeln!(
+                    xml,
+                    "    <testcase {attributes}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                    escape_xml(&failure.msg),
+                    escape_xml(&failure.rendered)
+                ),
+                _ => writeln!(xml, "    <testcase {attributes}/>"),
+            }
+            .expect("writing to a String is infallible");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Escapes text for XML attributes and content, dropping characters XML can't hold.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(suite: &str, name: &str, status: Status) -> TestResult {
+        TestResult {
+            suite: suite.into(),
+            name: name.into(),
+            status,
+            duration: Duration::from_millis(5),
+            failure: None,
+        }
+    }
+
+    #[test]
+    fn escapes_xml() {
+        assert_eq!(
+            escape_xml("a < b && \"c\"\u{1b}[31m"),
+            "a &lt; b &amp;&amp; &quot;c&quot;[31m"
+        );
+    }
+
+    #[test]
+    fn junit_groups_suites() {
+        let xml = junit(&[
+            result("math", "adds", Status::Pass),
+            result("math", "divides", Status::Skip),
+            result("", "loose", Status::Pass),
+        ]);
+        assert!(xml.contains(r#"<testsuites name="nushell" tests="3" failures="0" skipped="1""#));
+        assert!(xml.contains(r#"<testsuite name="math" tests="2" failures="0" skipped="1""#));
+        assert!(xml.contains(r#"<testcase name="adds" classname="math" time="0.005"/>"#));
+        assert!(xml.contains("<skipped/>"));
+        assert!(xml.contains(r#"<testsuite name="nushell" tests="1""#));
+    }
+}
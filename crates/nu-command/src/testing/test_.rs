@@ -0,0 +1,552 @@
+use super::{
+    assert_snapshot::UPDATE_SNAPSHOTS_ENV,
+    report::{self, Failure, Status, TestResult},
+};
+use fancy_regex::Regex;
+use nu_engine::{command_prelude::*, eval_call, get_eval_block_with_early_return};
+use nu_parser::parse;
+use nu_protocol::{
+    DeclId, ModuleId, ast,
+    debugger::WithoutDebug,
+    parser_path::{MAX_RUN_SCRIPT_BYTES, read_run_script_file},
+    shell_error::{generic::GenericError, io::IoError},
+};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+// The attributes the runner looks for, named like the ones of `std/testing`
+const TEST: &str = "test";
+const IGNORE: &str = "ignore";
+const BEFORE_ALL: &str = "before-all";
+const BEFORE_EACH: &str = "before-each";
+const AFTER_EACH: &str = "after-each";
+const AFTER_ALL: &str = "after-all";
+
+#[derive(Clone)]
+pub struct TestRun;
+
+impl Command for TestRun {
+    fn name(&self) -> &str {
+        "test run"
+    }
+
+    fn description(&self) -> &str {
+        "Find and run tests, the custom commands marked with `@test`."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Without paths, the tests in scope are run, in a suite for each module they come from. \
+Each file given is loaded on its own and its tests make up a suite. Directories are searched for \
+`test_*.nu` files.
+
+Each test runs in its own stack, so changes it makes to the environment don't reach other tests. \
+A test passes when it runs without an error, like the one of a failing `assert`. Tests marked \
+with `@ignore` are skipped. With `--update-snapshots`, the `assert snapshot` calls of the tests \
+save their input instead of comparing it.
+
+Commands marked with `@before-all`, `@before-each`, `@after-each` and `@after-all` run around the \
+tests of their suite. The output of `@before-all` is the input of `@before-each`, whose output is \
+the input of each test and of `@after-each`. These attributes come from `std/testing`.
+
+The report is a table, or a string with `--format json` or `--format junit` to save for CI."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("test run")
+            .input_output_types(vec![
+                (Type::Nothing, Type::table()),
+                (Type::Nothing, Type::String),
+            ])
+            .rest(
+                "path",
+                SyntaxShape::Filepath,
+                "Files or directories to load tests from.",
+            )
+            .named(
+                "filter",
+                SyntaxShape::String,
+                "Only run the tests whose name matches this regex.",
+                Some('f'),
+            )
+            .named(
+                "exclude",
+                SyntaxShape::String,
+                "Don't run the tests whose name matches this regex.",
+                Some('e'),
+            )
+            .param(
+                Flag::new("format")
+                    .arg(SyntaxShape::String)
+                    .desc("The format of the report. One of: table (default), json, junit")
+                    .completion(Completion::new_list(&["table", "json", "junit"])),
+            )
+            .switch(
+                "list",
+                "List the tests that would run instead of running them.",
+                Some('l'),
+            )
+            .switch(
+                "update-snapshots",
+                "Save the snapshots of `assert snapshot` instead of comparing them.",
+                None,
+            )
+            .category(Category::Misc)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["unit", "assert", "junit", "ci", "suite"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Run the tests in scope.",
+                example: "test run",
+                result: None,
+            },
+            Example {
+                description: "Run the tests of a directory and save a JUnit report.",
+                example: "test run tests/ --format junit | save --force report.xml",
+                result: None,
+            },
+            Example {
+                description: "Run the tests whose names contain `parse`, except the slow ones.",
+                example: "test run --filter parse --exclude slow",
+                result: None,
+            },
+            Example {
+                description: "Show the failed tests with their errors.",
+                example: "test run | where status == fail | select name error.msg",
+                result: None,
+            },
+            Example {
+                description: "List the tests of a file.",
+                example: "test run test_math.nu --list",
+                result: None,
+            },
+            Example {
+                description: "Run the tests, updating the snapshots they check.",
+                example: "test run --update-snapshots",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let paths: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+        let filter = regex_flag(engine_state, stack, call, "filter")?;
+        let exclude = regex_flag(engine_state, stack, call, "exclude")?;
+        let format: Option<Spanned<String>> = call.get_flag(engine_state, stack, "format")?;
+        let list = call.has_flag(engine_state, stack, "list")?;
+        let update_snapshots = call.has_flag(engine_state, stack, "update-snapshots")?;
+
+        let format = match format {
+            None => Format::Table,
+            Some(format) => match format.item.as_str() {
+                "table" => Format::Table,
+                "json" => Format::Json,
+                "junit" => Format::Junit,
+                other => {
+                    return Err(ShellError::InvalidValue {
+                        valid: "'table', 'json' or 'junit'".into(),
+                        actual: format!("'{other}'"),
+                        span: format.span,
+                    });
+                }
+            },
+        };
+
+        let mut suites = if paths.is_empty() {
+            scope_suites(engine_state, stack)
+        } else {
+            let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+            let mut suites = vec![];
+            for path in &paths {
+                let full_path = cwd.join(&path.item);
+                if !full_path.exists() {
+                    return Err(IoError::new(ErrorKind::FileNotFound, path.span, full_path).into());
+                }
+                for file in test_files(&full_path, path.span)? {
+                    let name = file
+                        .strip_prefix(&cwd)
+                        .unwrap_or(&file)
+                        .display()
+                        .to_string();
+                    suites.push(load_suite(engine_state, stack, &file, name, path.span)?);
+                }
+            }
+            suites
+        };
+
+        let selected = |name: &str| {
+            filter
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(name).unwrap_or(false))
+                && exclude
+                    .as_ref()
+                    .is_none_or(|regex| !regex.is_match(name).unwrap_or(false))
+        };
+        for suite in &mut suites {
+            suite.tests.retain(|test| selected(&test.name));
+        }
+        suites.retain(|suite| !suite.tests.is_empty());
+
+        if list {
+            let rows = suites
+                .iter()
+                .flat_map(|suite| {
+                    suite.tests.iter().map(|test| {
+                        Value::record(
+                            record! {
+                                "suite" => Value::string(&suite.name, head),
+                                "name" => Value::string(&test.name, head),
+                                "skip" => Value::bool(test.skip, head),
+                            },
+                            head,
+                        )
+                    })
+                })
+                .collect();
+            return Ok(Value::list(rows, head).into_pipeline_data());
+        }
+
+        if update_snapshots {
+            for suite in &mut suites {
+                suite
+                    .stack
+                    .add_env_var(UPDATE_SNAPSHOTS_ENV.into(), Value::bool(true, head));
+            }
+        }
+
+        let mut results = vec![];
+        for suite in &suites {
+            suite.run(&mut results, head)?;
+        }
+
+        let report = match format {
+            Format::Table => report::table(results, head),
+            Format::Json => Value::string(report::json(engine_state, results, head)?, head),
+            Format::Junit => Value::string(report::junit(&results), head),
+        };
+        Ok(report.into_pipeline_data())
+    }
+}
+
+enum Format {
+    Table,
+    Json,
+    Junit,
+}
+
+fn regex_flag(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    name: &str,
+) -> Result<Option<Regex>, ShellError> {
+    let Some(pattern) = call.get_flag::<Spanned<String>>(engine_state, stack, name)? else {
+        return Ok(None);
+    };
+    Regex::new(&pattern.item).map(Some).map_err(|err| {
+        ShellError::Generic(GenericError::new(
+            "Invalid regex",
+            err.to_string(),
+            pattern.span,
+        ))
+    })
+}
+
+/// The file itself, or the `test_*.nu` files in a directory and its subdirectories.
+fn test_files(path: &Path, span: Span) -> Result<Vec<PathBuf>, ShellError> {
+    fn find(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        let mut entries = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                find(&path, files)?;
+            } else if path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("test_") && name.ends_with(".nu"))
+            {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = vec![];
+    find(path, &mut files).map_err(|err| IoError::new(err, span, path.to_path_buf()))?;
+    Ok(files)
+}
+
+struct TestCase {
+    name: String,
+    decl_id: DeclId,
+    skip: bool,
+}
+
+#[derive(Default)]
+struct Hooks {
+    before_all: Option<DeclId>,
+    before_each: Option<DeclId>,
+    after_each: Option<DeclId>,
+    after_all: Option<DeclId>,
+}
+
+/// The tests of a file or module, with the state they run in.
+struct Suite<'a> {
+    name: String,
+    engine_state: Cow<'a, EngineState>,
+    stack: Stack,
+    tests: Vec<TestCase>,
+    hooks: Hooks,
+}
+
+impl Suite<'_> {
+    /// Adds a command to the suite if it's a test or a hook.
+    fn add(&mut self, name: String, decl_id: DeclId) {
+        let attributes: Vec<String> = self
+            .engine_state
+            .get_decl(decl_id)
+            .attributes()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        let has = |attribute: &str| attributes.iter().any(|name| name == attribute);
+
+        if has(TEST) || has(IGNORE) {
+            self.tests.push(TestCase {
+                name,
+                decl_id,
+                skip: has(IGNORE),
+            });
+        }
+        for (attribute, hook) in [
+            (BEFORE_ALL, &mut self.hooks.before_all),
+            (BEFORE_EACH, &mut self.hooks.before_each),
+            (AFTER_EACH, &mut self.hooks.after_each),
+            (AFTER_ALL, &mut self.hooks.after_all),
+        ] {
+            if has(attribute) {
+                *hook = Some(decl_id);
+            }
+        }
+    }
+
+    /// Runs a test or hook in a copy of the suite's stack.
+    fn call(&self, decl_id: DeclId, input: Value, head: Span) -> Result<Value, ShellError> {
+        let mut stack = self.stack.clone();
+        let call = ast::Call {
+            decl_id,
+            head,
+            arguments: vec![],
+            parser_info: HashMap::new(),
+        };
+        eval_call::<WithoutDebug>(
+            &self.engine_state,
+            &mut stack,
+            &call,
+            input.into_pipeline_data(),
+        )?
+        .into_value(head)
+    }
+
+    /// Runs a test with the `@before-each` and `@after-each` hooks around it.
+    fn run_test(&self, test: &TestCase, context: &Value, head: Span) -> Result<(), ShellError> {
+        let context = match self.hooks.before_each {
+            Some(before_each) => self.call(before_each, context.clone(), head)?,
+            None => context.clone(),
+        };
+        let result = self.call(test.decl_id, context.clone(), head);
+        if let Some(after_each) = self.hooks.after_each {
+            self.call(after_each, context, head)?;
+        }
+        result.map(|_| ())
+    }
+
+    fn run(&self, results: &mut Vec<TestResult>, head: Span) -> Result<(), ShellError> {
+        let context = match self.hooks.before_all {
+            Some(before_all) => self.call(before_all, Value::nothing(head), head),
+            None => Ok(Value::nothing(head)),
+        };
+
+        for test in &self.tests {
+            self.engine_state.signals().check(&head)?;
+
+            let start = Instant::now();
+            let (status, error) = if test.skip {
+                (Status::Skip, None)
+            } else {
+                let result = match &context {
+                    Ok(context) => self.run_test(test, context, head),
+                    // Without the setup of `@before-all`, every test fails with its error
+                    Err(err) => Err(err.clone()),
+                };
+                match result {
+                    Ok(()) => (Status::Pass, None),
+                    Err(err) => (Status::Fail, Some(err)),
+                }
+            };
+            results.push(TestResult {
+                suite: self.name.clone(),
+                name: test.name.clone(),
+                status,
+                duration: start.elapsed(),
+                failure: error.map(|err| self.failure(err, head)),
+            });
+        }
+
+        // A failing teardown is reported like a test of its own
+        if let (Some(after_all), Ok(context)) = (self.hooks.after_all, context) {
+            let start = Instant::now();
+            if let Err(err) = self.call(after_all, context, head) {
+                results.push(TestResult {
+                    suite: self.name.clone(),
+                    name: self.engine_state.get_decl(after_all).name().to_string(),
+                    status: Status::Fail,
+                    duration: start.elapsed(),
+                    failure: Some(self.failure(err, head)),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn failure(&self, err: ShellError, head: Span) -> Failure {
+        Failure::new(err, &self.engine_state, &self.stack, head)
+    }
+}
+
+/// The tests in scope, in a suite for each module they were defined in.
+fn scope_suites<'a>(engine_state: &'a EngineState, stack: &Stack) -> Vec<Suite<'a>> {
+    let mut modules: HashMap<DeclId, String> = HashMap::new();
+    for module_id in 0..engine_state.num_modules() {
+        let module = engine_state.get_module(ModuleId::new(module_id));
+        for (_, decl_id) in module.decls() {
+            modules
+                .entry(decl_id)
+                .or_insert_with(|| String::from_utf8_lossy(&module.name).into_owned());
+        }
+    }
+
+    let mut suites: Vec<Suite<'a>> = vec![];
+    let mut seen = HashSet::new();
+    for (name, decl_id) in engine_state.get_decls_sorted(false) {
+        // A command imported under several names is only run once
+        if !seen.insert(decl_id) {
+            continue;
+        }
+        let suite_name = modules.get(&decl_id).cloned().unwrap_or_default();
+        let index = match suites.iter().position(|suite| suite.name == suite_name) {
+            Some(index) => index,
+            None => {
+                suites.push(Suite {
+                    name: suite_name,
+                    engine_state: Cow::Borrowed(engine_state),
+                    stack: stack.clone(),
+                    tests: vec![],
+                    hooks: Hooks::default(),
+                });
+                suites.len() - 1
+            }
+        };
+        suites[index].add(String::from_utf8_lossy(&name).into_owned(), decl_id);
+    }
+
+    suites.retain(|suite| !suite.tests.is_empty());
+    suites
+}
+
+/// Loads a file in a copy of the engine state, running its top-level code, to get its tests.
+fn load_suite(
+    engine_state: &EngineState,
+    stack: &Stack,
+    path: &Path,
+    name: String,
+    span: Span,
+) -> Result<Suite<'static>, ShellError> {
+    let contents = read_run_script_file(path, MAX_RUN_SCRIPT_BYTES).map_err(|_| {
+        GenericError::new(
+            "Failed to load tests",
+            format!("could not read {name} as a script"),
+            span,
+        )
+    })?;
+
+    let mut engine_state = engine_state.clone();
+    let decls_before = engine_state.num_decls();
+    let mut working_set = StateWorkingSet::new(&engine_state);
+    working_set
+        .files
+        .push(path.to_path_buf(), span)
+        .map_err(|err| GenericError::new("Failed to load tests", err.to_string(), span))?;
+    let filename = path.to_string_lossy();
+    let block = parse(&mut working_set, Some(filename.as_ref()), &contents, false);
+    working_set.files.pop();
+
+    if let Some(err) = working_set.parse_errors.first() {
+        return Err(ShellError::Generic(
+            GenericError::new(format!("Failed to parse {name}"), err.to_string(), span)
+                .with_help("run the file with `nu` to see where the error is"),
+        ));
+    }
+    let delta = working_set.render();
+    engine_state.merge_delta(delta)?;
+
+    let mut stack = stack.captures_to_stack(vec![]);
+    if let Some(dir) = path.parent() {
+        stack.add_env_var(
+            "FILE_PWD".into(),
+            Value::string(dir.to_string_lossy(), span),
+        );
+    }
+    stack.add_env_var(
+        "CURRENT_FILE".into(),
+        Value::string(filename.as_ref(), span),
+    );
+    let eval_block = get_eval_block_with_early_return(&engine_state);
+    eval_block(&engine_state, &mut stack, &block, PipelineData::empty())?
+        .body
+        .drain()?;
+
+    let mut suite = Suite {
+        name,
+        engine_state: Cow::Owned(engine_state),
+        stack,
+        tests: vec![],
+        hooks: Hooks::default(),
+    };
+    for decl_id in (decls_before..suite.engine_state.num_decls()).map(DeclId::new) {
+        let name = suite.engine_state.get_decl(decl_id).name().to_string();
+        suite.add(name, decl_id);
+    }
+    Ok(suite)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(TestRun)
+    }
+}
@@ -0,0 +1,110 @@
+use nu_engine::{command_prelude::*, eval_block};
+use nu_protocol::{
+    FromValue, debugger::WithoutDebug, engine::Closure, shell_error::generic::GenericError,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct WithOverride;
+
+impl Command for WithOverride {
+    fn name(&self) -> &str {
+        "with-override"
+    }
+
+    fn description(&self) -> &str {
+        "Run a block with commands replaced by closures."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Each key of the record is the name of a command, and its closure runs instead of the \
+command for every call made while the block runs, including the calls in commands and modules \
+defined elsewhere. This way tests can replace commands that use the network or the filesystem.
+
+The closure gets the positional arguments of the call, and its input. Flags of the call aren't \
+passed on. Inside the closure, the command itself can still be called."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("with-override")
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .required(
+                "overrides",
+                SyntaxShape::Record(vec![]),
+                "The closures to run instead of the commands named by their keys.",
+            )
+            .required(
+                "block",
+                SyntaxShape::Closure(None),
+                "The block to run with the commands replaced.",
+            )
+            .category(Category::Misc)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["mock", "stub", "fake", "test", "replace"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Replace a command for the calls of a block.",
+                example: "with-override {'str upcase': {|| $in + '!' }} { 'hi' | str upcase }",
+                result: Some(Value::test_string("hi!")),
+            },
+            Example {
+                description: "Replace a command that is called by another one.",
+                example: "def greet [] { $'hello (whoami)' }
+    with-override {whoami: {|| 'nu' }} { greet }",
+                result: Some(Value::test_string("hello nu")),
+            },
+            Example {
+                description: "Test a command that downloads data without using the network.",
+                example: "def stars [repo: string] { http get $'https://example.com/($repo)' | get stars }
+    with-override {'http get': {|url| {stars: 5} }} { stars nushell }",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let overrides: Spanned<Record> = call.req(engine_state, stack, 0)?;
+        let block: Closure = call.req(engine_state, stack, 1)?;
+
+        let mut decl_overrides = Vec::with_capacity(overrides.item.len());
+        for (name, value) in overrides.item {
+            let Some(decl_id) = engine_state.find_decl(name.as_bytes(), &[]) else {
+                return Err(ShellError::Generic(
+                    GenericError::new(
+                        "Command not found",
+                        format!("there's no `{name}` command to override"),
+                        overrides.span,
+                    )
+                    .with_help("the keys must be the full names of commands in scope"),
+                ));
+            };
+            decl_overrides.push((decl_id, Closure::from_value(value)?));
+        }
+
+        let mut stack = stack.captures_to_stack_preserve_out_dest(block.captures);
+        Arc::make_mut(&mut stack.decl_overrides).extend(decl_overrides);
+        let block = engine_state.get_block(block.block_id);
+        eval_block::<WithoutDebug>(engine_state, &mut stack, block, input).map(|p| p.body)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(WithOverride)
+    }
+}
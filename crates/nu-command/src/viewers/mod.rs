@@ -1,6 +1,10 @@
 mod griddle;
 mod table;
+#[cfg(feature = "os")]
+mod view_image;
 
 pub use griddle::Griddle;
 pub use table::Table;
+#[cfg(feature = "os")]
+pub use view_image::ViewImage;
 pub(crate) use table::render_value_as_plain_table_text;
@@ -0,0 +1,458 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use image::{DynamicImage, ImageReader, RgbaImage, imageops::FilterType};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::{generic::GenericError, io::IoError};
+use std::{
+    fmt::Write as _,
+    io::{Cursor, Write},
+};
+
+/// The cell size assumed when the terminal doesn't report its size in pixels.
+const DEFAULT_CELL_SIZE: (u32, u32) = (10, 20);
+
+/// Kitty splits the image data in chunks of at most this many base64 bytes.
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+#[derive(Clone)]
+pub struct ViewImage;
+
+impl Command for ViewImage {
+    fn name(&self) -> &str {
+        "view image"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("view image")
+            .input_output_types(vec![(Type::Binary, Type::Nothing)])
+            .named(
+                "width",
+                SyntaxShape::Int,
+                "Width of the image in terminal columns.",
+                Some('w'),
+            )
+            .named(
+                "height",
+                SyntaxShape::Int,
+                "Height of the image in terminal rows.",
+                Some('H'),
+            )
+            .param(
+                Flag::new("protocol")
+                    .short('p')
+                    .arg(SyntaxShape::String)
+                    .desc("Graphics protocol to use: 'kitty', 'iterm' or 'sixel' (detected by default).")
+                    .completion(Completion::new_list(&["kitty", "iterm", "sixel"])),
+            )
+            .category(Category::Viewers)
+    }
+
+    fn description(&self) -> &str {
+        "Display an image in the terminal."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The image is drawn with the kitty, iTerm2 or sixel graphics protocol, detected from the `TERM`, `TERM_PROGRAM`, `LC_TERMINAL` and `KITTY_WINDOW_ID` environment variables unless `--protocol` is given.
+
+By default the image is shrunk to fit the terminal width. With `--width` or `--height` it is scaled to that many cells, keeping its aspect ratio; with both it fits inside them.
+
+PNG, JPEG, GIF, WebP and BMP images are supported."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["picture", "photo", "sixel", "kitty", "icat"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Display an image file",
+                example: "open --raw screenshot.png | view image",
+                result: None,
+            },
+            Example {
+                description: "Display a downloaded image 40 columns wide",
+                example: "http get https://www.nushell.sh/img/nushell-autocomplete6.gif | view image --width 40",
+                result: None,
+            },
+            Example {
+                description: "Display every screenshot of a directory with the sixel protocol",
+                example: "ls *.png | each { open --raw $in.name | view image --height 10 --protocol sixel }",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let width = cells_flag(engine_state, stack, call, "width")?;
+        let height = cells_flag(engine_state, stack, call, "height")?;
+        let protocol = match call.get_flag::<Spanned<String>>(engine_state, stack, "protocol")? {
+            Some(protocol) => Protocol::from_name(&protocol)?,
+            None => Protocol::detect(engine_state, stack).ok_or_else(|| {
+                ShellError::Generic(
+                    GenericError::new(
+                        "Can't tell which graphics protocol the terminal supports",
+                        "no supported terminal detected",
+                        head,
+                    )
+                    .with_help("Pick one with `--protocol kitty`, `--protocol iterm` or `--protocol sixel`."),
+                )
+            })?,
+        };
+
+        let from_io_error = IoError::factory(head, None);
+        let bytes = input.into_value(head)?.coerce_into_binary()?;
+        let image_error = |err: image::ImageError| {
+            ShellError::Generic(GenericError::new(
+                "Can't read the image",
+                err.to_string(),
+                head,
+            ))
+        };
+        let reader = ImageReader::new(Cursor::new(&bytes))
+            .with_guessed_format()
+            .map_err(&from_io_error)?;
+
+        let (columns, cell) = terminal_cells();
+        let output = match protocol {
+            // iTerm2 decodes and scales the image by itself, only its size is needed
+            Protocol::Iterm => {
+                let dimensions = reader.into_dimensions().map_err(image_error)?;
+                let size = fit(dimensions, cell, width, height, columns);
+                iterm(&bytes, cells(size, cell))
+            }
+            Protocol::Kitty | Protocol::Sixel => {
+                let image = reader.decode().map_err(image_error)?;
+                let size = fit(
+                    (image.width(), image.height()),
+                    cell,
+                    width,
+                    height,
+                    columns,
+                );
+                let image = scale(image, size);
+                match protocol {
+                    Protocol::Kitty => kitty(&image),
+                    _ => sixel(&image),
+                }
+            }
+        };
+
+        let mut stdout = std::io::stdout();
+        writeln!(stdout, "{output}")
+            .and_then(|_| stdout.flush())
+            .map_err(&from_io_error)?;
+
+        Ok(PipelineData::empty())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    Kitty,
+    Iterm,
+    Sixel,
+}
+
+impl Protocol {
+    fn from_name(name: &Spanned<String>) -> Result<Self, ShellError> {
+        match name.item.to_ascii_lowercase().as_str() {
+            "kitty" => Ok(Self::Kitty),
+            "iterm" | "iterm2" => Ok(Self::Iterm),
+            "sixel" => Ok(Self::Sixel),
+            _ => Err(ShellError::IncorrectValue {
+                msg: "expected 'kitty', 'iterm' or 'sixel'".into(),
+                val_span: name.span,
+                call_span: name.span,
+            }),
+        }
+    }
+
+    /// The protocol of the terminal, guessed from the variables terminals set.
+    fn detect(engine_state: &EngineState, stack: &Stack) -> Option<Self> {
+        let var = |name: &str| {
+            stack
+                .get_env_var(engine_state, name)
+                .and_then(|value| value.as_str().ok())
+                .map(str::to_string)
+                .unwrap_or_default()
+        };
+        let term = var("TERM");
+        let term_program = var("TERM_PROGRAM");
+
+        if !var("KITTY_WINDOW_ID").is_empty() || term.contains("kitty") || term_program == "ghostty"
+        {
+            Some(Self::Kitty)
+        } else if term_program == "iTerm.app"
+            || term_program == "WezTerm"
+            || var("LC_TERMINAL") == "iTerm2"
+        {
+            Some(Self::Iterm)
+        } else if term.contains("sixel") || term.starts_with("foot") || term == "mlterm" {
+            Some(Self::Sixel)
+        } else {
+            None
+        }
+    }
+}
+
+fn cells_flag(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    name: &str,
+) -> Result<Option<u32>, ShellError> {
+    let Some(value) = call.get_flag::<Spanned<i64>>(engine_state, stack, name)? else {
+        return Ok(None);
+    };
+
+    match u32::try_from(value.item) {
+        Ok(n) if n > 0 => Ok(Some(n)),
+        _ => Err(ShellError::IncorrectValue {
+            msg: format!("--{name} must be a positive number"),
+            val_span: value.span,
+            call_span: call.head,
+        }),
+    }
+}
+
+/// The terminal width in columns, and the size of its cells in pixels.
+fn terminal_cells() -> (u32, (u32, u32)) {
+    let Ok(size) = crossterm::terminal::window_size() else {
+        return (80, DEFAULT_CELL_SIZE);
+    };
+
+    let columns = u32::from(size.columns).max(1);
+    let rows = u32::from(size.rows).max(1);
+    // Terminals that don't report their size in pixels leave it 0
+    let cell = (
+        u32::from(size.width) / columns,
+        u32::from(size.height) / rows,
+    );
+    if cell.0 > 0 && cell.1 > 0 {
+        (columns, cell)
+    } else {
+        (columns, DEFAULT_CELL_SIZE)
+    }
+}
+
+/// The size in pixels to show an image at, keeping its aspect ratio.
+fn fit(
+    (image_width, image_height): (u32, u32),
+    (cell_width, cell_height): (u32, u32),
+    columns: Option<u32>,
+    rows: Option<u32>,
+    max_columns: u32,
+) -> (u32, u32) {
+    let image_width = image_width.max(1) as f64;
+    let image_height = image_height.max(1) as f64;
+    let by_width = columns.map(|columns| pixels(columns, cell_width) / image_width);
+    let by_height = rows.map(|rows| pixels(rows, cell_height) / image_height);
+
+    let scale = match (by_width, by_height) {
+        (Some(by_width), Some(by_height)) => by_width.min(by_height),
+        (Some(scale), None) | (None, Some(scale)) => scale,
+        // Shrink images wider than the terminal, but never enlarge them
+        (None, None) => (pixels(max_columns, cell_width) / image_width).min(1.0),
+    };
+
+    (
+        ((image_width * scale).round() as u32).max(1),
+        ((image_height * scale).round() as u32).max(1),
+    )
+}
+
+/// The size in pixels of `cells` cells, computed in u64 so large sizes don't overflow.
+fn pixels(cells: u32, cell_size: u32) -> f64 {
+    (u64::from(cells) * u64::from(cell_size)) as f64
+}
+
+/// The number of cells covered by a size in pixels.
+fn cells((width, height): (u32, u32), (cell_width, cell_height): (u32, u32)) -> (u32, u32) {
+    (width.div_ceil(cell_width), height.div_ceil(cell_height))
+}
+
+fn scale(image: DynamicImage, (width, height): (u32, u32)) -> RgbaImage {
+    if image.width() == width && image.height() == height {
+        image.into_rgba8()
+    } else {
+        image
+            .resize_exact(width, height, FilterType::Triangle)
+            .into_rgba8()
+    }
+}
+
+/// The iTerm2 inline image escape sequence, showing the file scaled to `columns` x `rows` cells.
+fn iterm(bytes: &[u8], (columns, rows): (u32, u32)) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={columns};height={rows};preserveAspectRatio=1:{}\x07",
+        bytes.len(),
+        STANDARD.encode(bytes)
+    )
+}
+
+/// The kitty graphics escape sequences transmitting and showing raw RGBA pixels.
+fn kitty(image: &RgbaImage) -> String {
+    let data = STANDARD.encode(image.as_raw());
+    let chunks = data.as_bytes().chunks(KITTY_CHUNK_SIZE);
+    let count = chunks.len();
+
+    let mut output = String::with_capacity(data.len() + count * 32);
+    for (i, chunk) in chunks.enumerate() {
+        // Base64 output is ASCII, any split of it is valid UTF-8
+        let chunk = String::from_utf8_lossy(chunk);
+        let more = u8::from(i + 1 < count);
+        if i == 0 {
+            let (width, height) = image.dimensions();
+            let _ = write!(
+                output,
+                "\x1b_Ga=T,q=2,f=32,s={width},v={height},m={more};{chunk}\x1b\\"
+            );
+        } else {
+            let _ = write!(output, "\x1b_Gm={more};{chunk}\x1b\\");
+        }
+    }
+
+    output
+}
+
+/// The sixel escape sequence of an image, with its colors reduced to a 6x6x6 color cube.
+fn sixel(image: &RgbaImage) -> String {
+    // The color register of each pixel, None for transparent ones
+    let registers = image
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            let level = |c: u8| (u16::from(c) * 5 + 127) / 255;
+            (a >= 128).then(|| level(r) * 36 + level(g) * 6 + level(b))
+        })
+        .collect::<Vec<_>>();
+    let (width, height) = (image.width() as usize, image.height() as usize);
+
+    // P2 = 1 leaves the pixels that aren't drawn transparent
+    let mut output = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    let mut used = [false; 216];
+    for register in registers.iter().flatten() {
+        used[*register as usize] = true;
+    }
+    for (register, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let percent = |level: usize| level * 100 / 5;
+        let _ = write!(
+            output,
+            "#{register};2;{};{};{}",
+            percent(register / 36),
+            percent(register / 6 % 6),
+            percent(register % 6)
+        );
+    }
+
+    for band in (0..height).step_by(6) {
+        let rows = band..(band + 6).min(height);
+        let mut colors = registers[band * width..rows.end * width]
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        colors.sort_unstable();
+        colors.dedup();
+
+        for color in colors {
+            let _ = write!(output, "#{color}");
+            let sixels = (0..width).map(|x| {
+                let bits = rows
+                    .clone()
+                    .enumerate()
+                    .filter(|(_, y)| registers[y * width + x] == Some(color))
+                    .fold(0u8, |bits, (bit, _)| bits | 1 << bit);
+                char::from(63 + bits)
+            });
+            push_run_length(&mut output, sixels);
+            // Go back to the start of the band for the next color
+            output.push('$');
+        }
+        output.push('-');
+    }
+
+    output.push_str("\x1b\\");
+    output
+}
+
+/// Writes sixels, using the `!<count><sixel>` repeat introducer for runs.
+fn push_run_length(output: &mut String, sixels: impl Iterator<Item = char>) {
+    let mut run: Option<(char, usize)> = None;
+    let flush = |output: &mut String, (sixel, count): (char, usize)| {
+        if count > 3 {
+            let _ = write!(output, "!{count}{sixel}");
+        } else {
+            output.extend(std::iter::repeat_n(sixel, count));
+        }
+    };
+
+    for sixel in sixels {
+        run = match run {
+            Some((current, count)) if current == sixel => Some((current, count + 1)),
+            Some(previous) => {
+                flush(output, previous);
+                Some((sixel, 1))
+            }
+            None => Some((sixel, 1)),
+        };
+    }
+    if let Some(run) = run {
+        flush(output, run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> nu_test_support::Result {
+        nu_test_support::test().examples(ViewImage)
+    }
+
+    #[test]
+    fn fit_keeps_the_aspect_ratio() {
+        // Wider than the terminal, shrunk to it
+        assert_eq!(fit((2000, 1000), (10, 20), None, None, 100), (1000, 500));
+        // Never enlarged by default
+        assert_eq!(fit((100, 50), (10, 20), None, None, 100), (100, 50));
+        assert_eq!(fit((100, 50), (10, 20), Some(20), None, 100), (200, 100));
+        // Fits inside both
+        assert_eq!(
+            fit((100, 100), (10, 20), Some(20), Some(5), 100),
+            (100, 100)
+        );
+    }
+
+    #[test]
+    fn fit_large_sizes_without_overflow() {
+        assert_eq!(
+            fit((u32::MAX, 1), (u32::MAX, 1), Some(2), None, 100),
+            (u32::MAX, 2)
+        );
+    }
+
+    #[test]
+    fn sixel_run_length() {
+        let mut output = String::new();
+        push_run_length(&mut output, "??????@@~".chars());
+        assert_eq!(output, "!6?@@~");
+    }
+
+    #[test]
+    fn sixel_of_a_red_pixel() {
+        let image = RgbaImage::from_pixel(2, 1, image::Rgba([255, 0, 0, 255]));
+        assert_eq!(
+            sixel(&image),
+            "\x1bP0;1;0q\"1;1;2;1#180;2;100;0;0#180@@$-\x1b\\"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use nu_protocol::test_record;
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::playground::Playground;
+use nu_test_support::prelude::*;
+
+#[test]
+fn assert_passes() -> Result {
+    test().run("assert (3 == 3)").expect_value_eq(())
+}
+
+#[test]
+fn assert_fails_with_message() -> Result {
+    let err = test()
+        .run("assert (42 == 3) 'the answer is wrong'")
+        .expect_labeled_error()?;
+    assert_eq!(err.msg, "the answer is wrong");
+    assert_eq!(err.labels[0].text, "It is not true.");
+    Ok(())
+}
+
+#[test]
+fn assert_uses_error_label() -> Result {
+    let code = "
+        def 'assert even' [number: int] {
+            assert ($number mod 2 == 0) --error-label {
+                text: $'($number) is not an even number',
+                span: (metadata $number).span,
+            }
+        }
+        assert even 3
+    ";
+    let err = test().run(code).expect_labeled_error()?;
+    assert_eq!(err.msg, "Assertion failed.");
+    assert_eq!(err.labels.len(), 1);
+    assert_eq!(err.labels[0].text, "3 is not an even number");
+    Ok(())
+}
+
+#[test]
+fn assert_equal_passes() -> Result {
+    test()
+        .run("assert equal {a: [1 (0.1 + 0.2)]} {a: [1 0.3]}")
+        .expect_value_eq(())
+}
+
+#[test]
+fn assert_equal_labels_values() -> Result {
+    let err = test()
+        .run("assert equal 1 2 'math is broken'")
+        .expect_labeled_error()?;
+    assert_eq!(err.msg, "math is broken\nThese are not equal.");
+    assert_eq!(err.labels[0].text, "left: 1");
+    assert_eq!(err.labels[1].text, "right: 2");
+    assert_eq!(err.help, None);
+    Ok(())
+}
+
+#[test]
+fn assert_equal_lists_changed_cells() -> Result {
+    let code = "
+        assert equal [[name age]; [alice 30] [bob 25]] [[name age city]; [alice 31 paris]]
+    ";
+    let err = test().run(code).expect_labeled_error()?;
+    let help = err.help.unwrap_or_default();
+    assert_contains("3 cells differ", &help);
+    assert_contains("~ 0.age ", &help);
+    assert_contains("+ 0.city ", &help);
+    assert_contains("- 1 ", &help);
+    assert!(
+        err.labels
+            .iter()
+            .any(|label| label.text == "`0.age` is 31 on the right")
+    );
+    Ok(())
+}
+
+#[test]
+fn std_assert_equal_uses_builtin() -> Result {
+    let code = "
+        use std/assert
+        assert equal {a: 1, b: 2} {a: 1, b: 3}
+    ";
+    let err = test().run(code).expect_labeled_error()?;
+    assert_contains("~ b ", &err.help.unwrap_or_default());
+    Ok(())
+}
+
+#[test]
+fn assert_snapshot_writes_missing_snapshot() -> Result {
+    Playground::setup("assert_snapshot_writes_missing_snapshot", |dirs, _| {
+        let mut tester = test().cwd(dirs.test());
+        let () = tester.run("{name: nu, tags: [shell]} | assert snapshot config")?;
+        tester
+            .run("open __snapshots__/config.nuon | get tags.0")
+            .expect_value_eq("shell")
+    })
+}
+
+#[test]
+fn assert_snapshot_compares_with_snapshot() -> Result {
+    Playground::setup("assert_snapshot_compares_with_snapshot", |dirs, sandbox| {
+        sandbox.mkdir("__snapshots__").with_files(&[FileWithContent(
+            "__snapshots__/rows.nuon",
+            "[[a, b]; [1, x], [2, y]]",
+        )]);
+
+        let mut tester = test().cwd(dirs.test());
+        let () = tester.run("[[a b]; [1 x] [2 y]] | assert snapshot rows")?;
+
+        let err = tester
+            .run("[[a b]; [1 x] [3 y]] | assert snapshot rows")
+            .expect_labeled_error()?;
+        assert_eq!(err.msg, "The value doesn't match the snapshot `rows`.");
+        let help = err.help.unwrap_or_default();
+        assert_contains("~ 1.a ", &help);
+        assert_contains("--update", &help);
+        Ok(())
+    })
+}
+
+#[test]
+fn assert_snapshot_updates_snapshot() -> Result {
+    Playground::setup("assert_snapshot_updates_snapshot", |dirs, sandbox| {
+        sandbox
+            .mkdir("__snapshots__")
+            .with_files(&[FileWithContent("__snapshots__/answer.nuon", "41")]);
+
+        let mut tester = test().cwd(dirs.test());
+        let () = tester.run("42 | assert snapshot answer --update")?;
+        tester
+            .run("open __snapshots__/answer.nuon")
+            .expect_value_eq(42)
+    })
+}
+
+#[test]
+fn assert_snapshot_rejects_paths() -> Result {
+    test()
+        .run("1 | assert snapshot ../outside")
+        .expect_error_code_eq("nu::shell::error")
+}
+
+#[test]
+fn test_updates_snapshots() -> Result {
+    Playground::setup("test_updates_snapshots", |dirs, sandbox| {
+        sandbox.mkdir("__snapshots__").with_files(&[
+            FileWithContent("__snapshots__/rows.nuon", "[[a]; [2]]"),
+            FileWithContent(
+                "test_rows.nu",
+                "@test\ndef rows [] { [[a]; [1]] | assert snapshot rows }",
+            ),
+        ]);
+
+        let mut tester = test().cwd(dirs.test());
+        tester
+            .run("test run test_rows.nu | get status.0")
+            .expect_value_eq("fail")?;
+        tester
+            .run("test run test_rows.nu --update-snapshots | get status.0")
+            .expect_value_eq("pass")?;
+        tester
+            .run("open __snapshots__/rows.nuon")
+            .expect_value_eq([test_record! {"a" => 1}])
+    })
+}
@@ -81,3 +81,19 @@ fn chunk_size_filesize_list_error() -> Result {
     );
     Ok(())
 }
+
+#[test]
+fn chunk_rows_by_time() -> Result {
+    test()
+        .run("[[t v]; [2024-01-01T00:00:01Z 1] [2024-01-01T00:00:04Z 2] [2024-01-01T00:00:16Z 3]] | chunks 5sec --by t | each { get v } | to nuon")
+        .expect_value_eq("[[1, 2], [3]]")
+}
+
+#[test]
+fn chunk_rows_by_non_date_column() -> Result {
+    let err = test()
+        .run("[[t]; [1]] | chunks 5sec --by t")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::RuntimeTypeMismatch { .. }));
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn trusted_env_file_activates_without_flag() {
+    Playground::setup("config_trust_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent(
+            "nuenv.nu",
+            r#"{ env: { DEMO_VAR: "on" } }"#,
+        )]);
+        let data_dir = dirs.test().join("data").to_string_lossy().into_owned();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            envs: vec![("XDG_DATA_HOME".to_string(), data_dir)],
+            "config trust nuenv.nu; env activate; $env.DEMO_VAR"
+        );
+
+        assert_eq!(actual.out, "on");
+    })
+}
+
+#[test]
+fn untrust_revokes_trust() {
+    Playground::setup("config_trust_test_2", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent(".nu.toml", "[env]\nDEMO_VAR = \"on\"\n")]);
+        let data_dir = dirs.test().join("data").to_string_lossy().into_owned();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            envs: vec![("XDG_DATA_HOME".to_string(), data_dir)],
+            "config trust; config untrust; config untrust"
+        );
+
+        assert!(actual.err.contains("File is not trusted"));
+    })
+}
+
+#[test]
+fn trust_without_local_config_fails() {
+    Playground::setup("config_trust_test_3", |dirs, _| {
+        let actual = nu!(cwd: dirs.test(), "config trust");
+
+        assert!(actual.err.contains("No .nu.toml found"));
+    })
+}
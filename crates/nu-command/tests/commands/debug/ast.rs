@@ -0,0 +1,48 @@
+use nu_protocol::test_record;
+use nu_test_support::prelude::*;
+
+#[test]
+fn ast_tree_has_kinds_and_spans() -> Result {
+    test()
+        .run("ast 'ls | length' --tree | get ast.children.0.children.1 | select kind detail span")
+        .expect_value_eq(test_record! {
+            "kind" => "call",
+            "detail" => "length",
+            "span" => test_record! {
+                "start" => 5,
+                "end" => 11,
+            },
+        })
+}
+
+#[test]
+fn ast_tree_has_types() -> Result {
+    test()
+        .run("ast '1 + 2' --tree | get ast.children.0.children.0 | select kind type")
+        .expect_value_eq(test_record! {
+            "kind" => "binary_op",
+            "type" => "int",
+        })
+}
+
+#[test]
+fn ast_tree_has_ir() -> Result {
+    test()
+        .run("ast '1 + 2' --tree | get ir | length | $in > 0")
+        .expect_value_eq(true)
+}
+
+#[test]
+fn ast_tree_has_errors() -> Result {
+    test()
+        .run("ast '(1 + ' --tree | get errors | length | $in > 0")
+        .expect_value_eq(true)
+}
+
+#[test]
+fn ast_tree_rejects_flatten() -> Result {
+    test()
+        .run("ast 'ls' --tree --flatten")
+        .expect_shell_error()?;
+    Ok(())
+}
@@ -1,4 +1,6 @@
+mod ast;
 mod metadata_access;
 mod metadata_set;
+mod test_examples;
 mod timeit;
 mod view_source;
@@ -0,0 +1,83 @@
+use nu_protocol::test_record;
+use nu_test_support::prelude::*;
+
+#[test]
+fn test_examples_checks_results() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "
+        # Add two numbers.
+        #
+        # Example: Add one and two
+        #   > add 1 2
+        #   => 3
+        # Example: Add two and two
+        #   > add 2 2
+        #   => 5
+        def add [a: int, b: int] { $a + $b }
+        ",
+    )?;
+    tester
+        .run("test-examples add | get status")
+        .expect_value_eq(["pass", "fail"])
+}
+
+#[test]
+fn test_examples_reports_unchecked_results() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "
+        # List files.
+        #
+        # Example: Not a constant
+        #   > files
+        #   => (ls)
+        def files [] { ls }
+        ",
+    )?;
+    tester
+        .run("test-examples files | get 0.status")
+        .expect_value_eq("unchecked")
+}
+
+#[test]
+fn test_examples_reports_errors() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "
+        @example \"Fail on purpose\" { broken }
+        def broken [] { error make {msg: nope} }
+        ",
+    )?;
+    tester
+        .run("test-examples broken | get 0.status")
+        .expect_value_eq("error")
+}
+
+#[test]
+fn test_examples_of_module() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "
+        module spam {
+            # Say hello.
+            #
+            # Example: Greet
+            #   > hello
+            #   => hi
+            export def hello [] { 'hi' }
+        }
+        use spam hello
+        ",
+    )?;
+    tester
+        .run("test-examples --module spam | select command status")
+        .expect_value_eq([test_record! {"command" => "hello", "status" => "pass"}])
+}
+
+#[test]
+fn test_examples_unknown_command() -> Result {
+    test()
+        .run("test-examples definitely-not-a-command")
+        .expect_error_code_eq("nu::shell::command_not_found")
+}
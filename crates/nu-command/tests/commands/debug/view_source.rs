@@ -28,3 +28,12 @@ fn datasource_filepath_metadata() -> Result {
         Ok(())
     })
 }
+
+#[test]
+fn view_source_of_builtin_shows_signature_and_examples() -> Result {
+    let outcome: String = test().run("view source 'str join'")?;
+    assert_contains("def \"str join\" [", &outcome);
+    assert_contains("# > ['nu', 'shell'] | str join", &outcome);
+    assert_contains("{ <internal command> }", outcome);
+    Ok(())
+}
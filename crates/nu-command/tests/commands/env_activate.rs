@@ -0,0 +1,49 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+const ENV_FILE: &str = r#"{ name: "demo", env: { DEMO_VAR: "on" }, path: ["bin"] }"#;
+
+#[test]
+fn activates_and_deactivates_env() {
+    Playground::setup("env_activate_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("nuenv.nu", ENV_FILE)]);
+        let data_dir = dirs.test().join("data").to_string_lossy().into_owned();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            envs: vec![("XDG_DATA_HOME".to_string(), data_dir)],
+            r#"
+                env activate --trust
+                let active = [$env.DEMO_VAR ($env.PATH | first | path basename) (overlay list | last | get name)]
+                env deactivate
+                $active | append ('DEMO_VAR' in $env | into string) | str join ','
+            "#
+        );
+
+        assert_eq!(actual.out, "on,bin,demo,false");
+    })
+}
+
+#[test]
+fn refuses_untrusted_env_file() {
+    Playground::setup("env_activate_test_2", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("nuenv.nu", ENV_FILE)]);
+        let data_dir = dirs.test().join("data").to_string_lossy().into_owned();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            envs: vec![("XDG_DATA_HOME".to_string(), data_dir)],
+            "env activate"
+        );
+
+        assert!(actual.err.contains("Untrusted environment file"));
+    })
+}
+
+#[test]
+fn deactivate_without_active_env_fails() {
+    let actual = nu!("env deactivate");
+
+    assert!(actual.err.contains("No active environment"));
+}
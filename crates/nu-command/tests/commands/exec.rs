@@ -37,3 +37,46 @@ fn exec_misc_values() -> Result {
         .run(r#"nu -n -c 'let x = "abc"; exec nu --testbin cococo $x ...[ a b c ]'"#)
         .expect_value_eq("abc a b c")
 }
+
+#[cfg(unix)]
+#[test]
+#[deps(NU)]
+fn exec_login_sets_shell() -> Result {
+    test()
+        .run("nu -n -c 'exec --login nu --testbin echo_env SHELL' | path basename")
+        .expect_value_eq("nu")
+}
+
+#[cfg(unix)]
+#[test]
+#[deps(NU)]
+fn exec_new_session() -> Result {
+    test()
+        .run("nu -n -c 'exec --new-session nu --testbin cococo a'")
+        .expect_value_eq("a")
+}
+
+#[test]
+#[deps(NU)]
+fn exec_flags_after_the_command_are_passed_on() -> Result {
+    test()
+        .run("nu -n -c 'exec nu --testbin cococo a --login b --new-session'")
+        .expect_value_eq("a --login b --new-session")
+}
+
+#[test]
+#[deps(NU)]
+fn exec_flags_after_the_command_keep_their_value() -> Result {
+    test()
+        .run("nu -n -c 'exec nu --testbin cococo a --login=false'")
+        .expect_value_eq("a --login=false")
+}
+
+#[test]
+fn exec_new_group_and_session_are_incompatible() -> Result {
+    let err = test()
+        .run("exec --new-group --new-session nu --testbin cococo")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::IncompatibleParameters { .. }));
+    Ok(())
+}
@@ -311,3 +311,32 @@ fn find_and_highlight_in_nested_list() {
         r#"[["\u001b[39m\u001b[0m\u001b[41;39mfoo\u001b[0m\u001b[39m\u001b[0m","bar"],["\u001b[39m\u001b[0m\u001b[41;39mfoo\u001b[0m\u001b[39m\u001b[0m","baz"]]"#
     );
 }
+
+#[test]
+fn find_detailed_describes_the_matches() {
+    let actual = nu!(
+        r#"[[a b]; [foo "foo bar foo"] [baz 1]] | find --detailed --columns [b] foo | to nuon"#
+    );
+
+    assert_eq!(
+        actual.out,
+        r#"[[item, matches]; [{a: foo, b: "foo bar foo"}, [[column, text, start, end]; [b, foo, 0, 3], [b, foo, 8, 11]]]]"#
+    );
+}
+
+#[test]
+fn find_detailed_with_rfind_and_terms_on_numbers() {
+    let actual = nu!("[5 15 'a5b5'] | find --detailed --rfind 5 | get matches | flatten | to nuon");
+
+    assert_eq!(
+        actual.out,
+        r#"[[column, text, start, end]; [null, "5", 0, 1], [null, "5", 3, 4]]"#
+    );
+}
+
+#[test]
+fn find_detailed_cant_be_inverted() {
+    let actual = nu!("[a b] | find --detailed --invert a");
+
+    assert!(actual.err.contains("no matches to detail"));
+}
@@ -181,3 +181,62 @@ fn flatten_more_than_one_column_that_are_subtables_not_supported() {
     assert!(actual.err.contains("tried flattening"));
     assert!(actual.err.contains("but is flattened already"));
 }
+
+#[test]
+fn flatten_keeps_outer_column_that_conflicts_with_nested_column() {
+    let actual = nu!("{a: {b: 9}, b: 1} | flatten | to nuon");
+
+    assert_eq!(actual.out, "[[a_b, b]; [9, 1]]");
+}
+
+#[test]
+fn flatten_nested_records_up_to_depth() {
+    let actual = nu!("{a: {b: {c: {d: 1}}}} | flatten --depth 2 | to nuon");
+
+    assert_eq!(actual.out, "[[c]; [{d: 1}]]");
+}
+
+#[test]
+fn flatten_nested_table_records_up_to_depth() {
+    let actual =
+        nu!("{id: 1, rows: [{meta: {id: 2, tag: x}}]} | flatten --all --depth 2 | to nuon");
+
+    assert_eq!(actual.out, "[[id, rows_meta_id, tag]; [1, 2, x]]");
+}
+
+#[test]
+fn flatten_renames_conflicts_with_separator() {
+    let actual = nu!("{a: {b: {x: 1}, x: 2}, x: 3} | flatten --depth 2 --separator '.' | to nuon");
+
+    assert_eq!(actual.out, r#"[["a.b.x", "a.x", x]; [1, 2, 3]]"#);
+}
+
+#[test]
+fn flatten_numbers_conflicts_that_are_still_taken() {
+    let actual = nu!("{a: {b: 1}, a_b: 2, c: {a: {b: 3}}} | flatten | flatten | to nuon");
+
+    assert_eq!(actual.out, r#"[[b, a_b, "a_b_2"]; [1, 2, 3]]"#);
+}
+
+#[test]
+fn flatten_conflicts_keep_first() {
+    let actual = nu!("{a: {b: 9, c: 8}, b: 1} | flatten --on-conflict keep-first | to nuon");
+
+    assert_eq!(actual.out, "[[c, b]; [8, 1]]");
+}
+
+#[test]
+fn flatten_conflicts_error() {
+    let actual = nu!("{a: {b: 9}, b: 1} | flatten --on-conflict error");
+
+    assert!(actual.err.contains("Column name conflict"));
+}
+
+#[test]
+fn flatten_rejects_invalid_options() {
+    let actual = nu!("{a: {b: 9}} | flatten --depth 0");
+    assert!(actual.err.contains("the depth must be at least 1"));
+
+    let actual = nu!("{a: {b: 9}} | flatten --on-conflict overwrite");
+    assert!(actual.err.contains("one of: error, suffix, keep-first"));
+}
@@ -1,4 +1,5 @@
 use nu_experimental::NATIVE_CLIP;
+use nu_protocol::test_record;
 use nu_test_support::fs::Stub::FileWithContent;
 use nu_test_support::playground::Playground;
 use nu_test_support::prelude::*;
@@ -505,3 +506,205 @@ fn nothing_type_annotation() -> Result {
         .run("help commands | where name == foo | get input_output.0.output.0")
         .expect_value_eq("nothing")
 }
+
+#[test]
+fn help_examples_shows_sample_input() -> Result {
+    test()
+        .run("help examples str join | get 0.input")
+        .expect_value_eq(["nu", "shell"])
+}
+
+#[test]
+fn help_examples_runs_example() -> Result {
+    test()
+        .run("help examples str join --run 0")
+        .expect_value_eq("nushell")
+}
+
+#[test]
+fn help_examples_runs_example_with_input() -> Result {
+    test()
+        .run("help examples str join --run 1 --input [a b]")
+        .expect_value_eq("a-b")
+}
+
+#[test]
+fn help_examples_rejects_missing_example() -> Result {
+    let err = test()
+        .run("help examples str join --run 99")
+        .expect_shell_error()?;
+    let ShellError::IncorrectValue { msg, .. } = err else {
+        return Err(err.into());
+    };
+    assert_eq!(msg, "`str join` has 2 examples");
+    Ok(())
+}
+
+#[test]
+fn help_find_ranks_commands_by_task() -> Result {
+    test()
+        .run("help --find 'merge two tables' | get name | first 3 | any {|name| $name == merge }")
+        .expect_value_eq(true)
+}
+
+#[test]
+fn help_find_ranks_name_matches_first() -> Result {
+    test()
+        .run("help --find sparkline | get 0.name")
+        .expect_value_eq("chart sparkline")
+}
+
+#[test]
+fn help_find_matches_part_of_a_word() -> Result {
+    test()
+        .run("help --find sparkl | get 0.name")
+        .expect_value_eq("chart sparkline")
+}
+
+#[test]
+fn help_find_shows_matched_snippet() -> Result {
+    let mut tester = test();
+    let () = tester.run("# Turn widgets into gadgets.\ndef gadgetize [--sprocket: int # Number of sprockets per gadget] {}")?;
+    tester
+        .run("help --find sprockets | where name == gadgetize | get 0 | select matched snippet")
+        .expect_value_eq(test_record! {
+            "matched" => "parameters",
+            "snippet" => "--sprocket: Number of sprockets per gadget",
+        })
+}
+
+#[test]
+fn help_shows_deprecations() -> Result {
+    let outcome: String = test().run("help str downcase")?;
+    assert_contains("Deprecated", &outcome);
+    assert_contains("Use `str lowercase` instead.", &outcome);
+
+    let outcome: String = test().run(
+        "@deprecated --flag all --since 1.2.0 --replacement '--every'
+        def my-command [--all, --every] {}
+        help my-command",
+    )?;
+    assert_contains(
+        "my-command --all was deprecated in 1.2.0 and will be removed in a future release. Use `--every` instead.",
+        outcome,
+    );
+    Ok(())
+}
+
+const ADD_WITH_DOC_EXAMPLE: &str = "
+    # Add two numbers.
+    #
+    # Example: Add one and two
+    #   > add 1 2
+    #   => 3
+    def add [a: int, b: int] { $a + $b }
+";
+
+#[test]
+fn doc_comment_examples_are_examples() -> Result {
+    let mut tester = test();
+    let () = tester.run(ADD_WITH_DOC_EXAMPLE)?;
+    tester
+        .run("help examples add | get 0 | reject input")
+        .expect_value_eq(test_record! {
+            "description" => "Add one and two",
+            "example" => "add 1 2",
+            "result" => 3,
+        })
+}
+
+#[test]
+fn doc_comment_examples_leave_description() -> Result {
+    let mut tester = test();
+    let () = tester.run(ADD_WITH_DOC_EXAMPLE)?;
+    tester
+        .run("scope commands | where name == add | get 0 | select description extra_description")
+        .expect_value_eq(test_record! {
+            "description" => "Add two numbers.",
+            "extra_description" => "",
+        })
+}
+
+#[test]
+fn doc_comment_examples_keep_multiline_code() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "
+        # Double each number.
+        #
+        # Example: Double a list
+        #   > [1 2]
+        #   > | double
+        def double [] { each { $in * 2 } }
+        ",
+    )?;
+    tester
+        .run("help examples double | get 0.example")
+        .expect_value_eq("[1 2]\n| double")
+}
+
+#[test]
+fn doc_comment_examples_without_constant_results_are_kept() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "
+        # List files.
+        #
+        # Example: Not a constant
+        #   > files
+        #   => (ls)
+        #
+        # Example: Not valid
+        #   > files
+        #   => [1 2
+        def files [] { ls }
+        ",
+    )?;
+    tester
+        .run("help examples files | each {|example| $example.result == null }")
+        .expect_value_eq([true, true])
+}
+
+#[test]
+fn doc_comment_examples_need_code_markers() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "
+        # Greet someone.
+        #
+        # Example: the greeting depends on the time of day
+        #   mornings get a different one
+        def greet [] { 'hi' }
+        ",
+    )?;
+    tester
+        .run("help examples greet | is-empty")
+        .expect_value_eq(true)?;
+    tester
+        .run("scope commands | where name == greet | get 0.extra_description | str contains 'mornings get'")
+        .expect_value_eq(true)
+}
+
+#[test]
+fn help_modules_full_shows_command_help() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "
+        module spam {
+            # Add two numbers.
+            #
+            # Example: Add one and two
+            #   > add 1 2
+            #   => 3
+            export def add [a: int, b: int] { $a + $b }
+        }
+        ",
+    )?;
+    let outcome: String = tester.run("help modules spam --full")?;
+    assert_contains("Add two numbers.", &outcome);
+    assert_contains("Add one and two", &outcome);
+
+    let outcome: String = tester.run("help modules spam")?;
+    assert!(!outcome.contains("Add one and two"));
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn removes_named_hooks() -> Result {
+    let code = r#"
+        $env.config.hooks.pre_prompt = [{ name: greet, code: "print hi" } { code: "print bye" }]
+        hook remove pre_prompt greet
+        $env.config.hooks.pre_prompt | length
+    "#;
+
+    test().run(code).expect_value_eq(1)
+}
+
+#[test]
+fn removes_named_env_change_hooks() -> Result {
+    let code = r#"
+        $env.config.hooks.env_change = { PWD: [{ name: ls, code: "ls" }] }
+        hook remove env_change.PWD ls
+        $env.config.hooks.env_change.PWD | is-empty
+    "#;
+
+    test().run(code).expect_value_eq(true)
+}
+
+#[test]
+fn missing_hook_is_an_error() -> Result {
+    test()
+        .run("hook remove pre_execution missing")
+        .expect_error()?;
+    Ok(())
+}
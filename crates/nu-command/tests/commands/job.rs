@@ -223,6 +223,86 @@ fn job_flush_with_tag() {
     assert_eq!(actual.err, "");
 }
 
+#[test]
+#[serial]
+fn job_channels_are_separate_from_mailbox() {
+    let actual = nu!(r#"
+        "mail" | job send 0
+        "first" | job send 0 --channel a
+        "second" | job send 0 --channel b
+
+        [
+            (job recv --channel b --timeout 1sec)
+            (job recv --timeout 1sec)
+            (job recv --channel a --timeout 1sec)
+        ] | to nuon
+        "#);
+
+    assert_eq!(actual.out, r#"["second", "mail", "first"]"#);
+}
+
+#[test]
+#[serial]
+fn job_select_reads_first_channel_with_message() {
+    let actual = nu!(r#"
+        job spawn { "done" | job send 0 --channel status }
+
+        job select [results status] --timeout 10sec | to nuon
+        "#);
+
+    assert_eq!(actual.out, r#"{channel: status, message: done}"#);
+}
+
+#[test]
+#[serial]
+fn job_select_timeout_works() {
+    let actual = nu!(r#"
+        "ignored" | job send 0 --channel other
+        job select [results status] --timeout 1sec
+        "#);
+
+    assert_eq!(actual.out, "");
+    assert!(actual.err.contains("timeout"));
+}
+
+#[test]
+#[serial]
+fn bounded_channel_blocks_sender() {
+    let actual = nu!(r#"
+        job channel work --capacity 1
+        job spawn {
+            1..3 | each { job send 0 --channel work }
+            "sent" | job send 0 --channel status
+        }
+        sleep 500ms
+
+        # the producer is waiting for room, so it can't be done yet
+        let early = try { job recv --channel status --timeout 0sec } catch { "waiting" }
+        let items = 1..3 | each { job recv --channel work --timeout 10sec }
+        let late = job recv --channel status --timeout 10sec
+
+        [$early $items $late] | to nuon
+        "#);
+
+    assert_eq!(actual.out, r#"["waiting", [1, 2, 3], "sent"]"#);
+}
+
+#[test]
+#[serial]
+fn job_channel_capacity_must_be_positive() {
+    let actual = nu!("job channel work --capacity 0");
+
+    assert!(actual.err.contains("positive"));
+}
+
+#[test]
+#[serial]
+fn channels_dont_take_tags() {
+    let actual = nu!("'hi' | job send 0 --channel a --tag 1");
+
+    assert!(actual.err.contains("Incompatible parameters"));
+}
+
 #[test]
 #[serial]
 fn first_job_id_is_one() {
@@ -0,0 +1,48 @@
+use nu_test_support::nu;
+
+#[test]
+fn lazy_runs_closure_once_when_used() {
+    let actual = nu!(r#"
+        let value = lazy { print computed; {a: [1 2]} }
+        print defined
+        let copy = $value
+        print ($value.a.1 + $copy.a.0)
+    "#);
+
+    assert_eq!(actual.out, "definedcomputed3");
+}
+
+#[test]
+fn lazy_is_not_run_when_unused() {
+    let actual = nu!("let value = lazy { print computed; 1 }; $value | describe");
+
+    assert_eq!(actual.out, "lazy");
+}
+
+#[test]
+fn lazy_converts_into_its_value() {
+    let actual = nu!("lazy { [1 2 3] } | into value | math sum");
+
+    assert_eq!(actual.out, "6");
+}
+
+#[test]
+fn lazy_keeps_errors() {
+    let actual = nu!("let value = lazy { error make {msg: boom} }; try { $value.a }; $value + 1");
+
+    assert!(actual.err.contains("boom"));
+}
+
+#[test]
+fn lazy_is_iterated_over() {
+    let actual = nu!("lazy { [1 2 3] } | where $it > 1 | math sum");
+
+    assert_eq!(actual.out, "5");
+}
+
+#[test]
+fn lazy_non_list_is_iterated_once() {
+    let actual = nu!("lazy { {a: 1} } | each { $in.a + 1 } | to nuon");
+
+    assert_eq!(actual.out, "[2]");
+}
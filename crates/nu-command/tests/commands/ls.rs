@@ -636,6 +636,16 @@ fn list_unknown_short_flag() -> Result {
     Ok(())
 }
 
+#[test]
+fn list_misspelled_long_flag_with_single_dash() -> Result {
+    let err = test().run("ls -fullpaths").expect_parse_error()?;
+    assert_matches!(
+        err,
+        ParseError::UnknownFlag(_, _, _, help) if help == "Did you mean: `--full-paths`?"
+    );
+    Ok(())
+}
+
 #[test]
 fn list_flag_false() -> Result {
     // Check that ls flags respect explicit values
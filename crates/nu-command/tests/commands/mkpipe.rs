@@ -0,0 +1,48 @@
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[cfg(unix)]
+#[test]
+fn creates_fifo() {
+    Playground::setup("mkpipe_test_1", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "mkpipe fifo; 'fifo' | path type"
+        );
+
+        assert_eq!(actual.out, "pipe");
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn passes_data_between_save_and_open() {
+    Playground::setup("mkpipe_test_2", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "mkpipe fifo; job spawn { 'hello fifo' | save fifo }; open --raw fifo | decode"
+        );
+
+        assert_eq!(actual.out, "hello fifo");
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn fails_if_pipe_exists() {
+    Playground::setup("mkpipe_test_3", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "mkpipe fifo; mkpipe fifo"
+        );
+
+        assert!(!actual.err.is_empty());
+    })
+}
+
+#[test]
+fn requires_a_path() {
+    let actual = nu!("mkpipe");
+
+    assert!(actual.err.contains("requires pipe paths"));
+}
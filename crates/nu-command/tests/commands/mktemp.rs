@@ -55,3 +55,34 @@ fn doesnt_create_temp_file() {
         assert!(!loc.exists());
     })
 }
+
+#[test]
+fn scope_removes_temp_file_afterwards() {
+    Playground::setup("mktemp_test_scope_1", |dirs, _| {
+        let output = nu!(
+            cwd: dirs.test(),
+            "let out = mktemp --scope {|path| 'hello' | save --force $path; [$path (open $path)] }; [$out.1 ($out.0 | path exists)] | to nuon"
+        );
+
+        assert_eq!(output.out, "[hello, false]");
+    })
+}
+
+#[test]
+fn scope_removes_temp_directory_on_error() {
+    Playground::setup("mktemp_test_scope_2", |dirs, _| {
+        let output = nu!(
+            cwd: dirs.test(),
+            "try { mktemp -d -p . --scope {|path| touch ($path | path join file); error make { msg: 'boom' } } }; ls | length"
+        );
+
+        assert_eq!(output.out, "0");
+    })
+}
+
+#[test]
+fn scope_conflicts_with_dry() {
+    let output = nu!("mktemp --dry --scope {|path| $path }");
+
+    assert!(output.err.contains("--dry can't be used with --scope"));
+}
@@ -2,6 +2,7 @@ mod alias;
 mod all;
 mod any;
 mod append;
+mod assert;
 mod assignment;
 mod base;
 mod break_;
@@ -14,6 +15,7 @@ mod compact;
 mod complete;
 mod config_env_default;
 mod config_nu_default;
+mod config_trust;
 mod continue_;
 mod conversions;
 #[cfg(feature = "sqlite")]
@@ -30,6 +32,7 @@ mod du;
 mod each;
 mod echo;
 mod empty;
+mod env_activate;
 mod error_make;
 mod every;
 mod exec;
@@ -51,6 +54,7 @@ mod hash_;
 mod headers;
 mod help;
 mod histogram;
+mod hook_remove;
 mod idx;
 mod if_;
 mod ignore;
@@ -63,6 +67,7 @@ mod into_filesize;
 mod into_int;
 mod join;
 mod last;
+mod lazy;
 mod length;
 mod let_;
 mod lines;
@@ -72,6 +77,7 @@ mod match_;
 mod math;
 mod merge;
 mod merge_deep;
+mod mkpipe;
 mod mktemp;
 mod move_;
 mod mut_;
@@ -90,6 +96,7 @@ mod query;
 mod random;
 mod redirection;
 mod reduce;
+mod ref_;
 mod reject;
 mod rename;
 mod return_;
@@ -118,6 +125,7 @@ mod table;
 mod take;
 mod tee;
 mod terminal;
+mod test_;
 mod to_text;
 mod transpose;
 mod try_;
@@ -145,5 +153,7 @@ mod where_;
 mod which;
 mod while_;
 mod with_env;
+mod with_lock;
+mod with_override;
 mod wrap;
 mod zip;
@@ -122,6 +122,50 @@ mod simple {
             r#"[{"level":"INFO","entry":"all is well"},{"level":"ERROR","entry":"something bad happened"}]"#
         );
     }
+
+    #[test]
+    fn typed_captures_only_match_their_type() -> Result {
+        let code = r#"
+            ["id=7 ok=true" "id=x ok=true" "id=-3 ok=no"]
+            | parse "id={id:int} ok={ok:bool}"
+            | to nuon
+        "#;
+
+        test().run(code).expect_value_eq("[[id, ok]; [7, true]]")
+    }
+
+    #[test]
+    fn errors_on_unknown_capture_type() -> Result {
+        let err = test()
+            .run(r#""a 1" | parse "{name} {n:number}""#)
+            .expect_shell_error()?;
+        assert_contains("Unknown capture type", &err.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn tries_the_patterns_in_order() -> Result {
+        let code = r#"
+            ["took 15ms" "took 2.5s" "idle"]
+            | each {|line| $line }
+            | parse ["took {ms:int}ms" "took {s:float}s" "{state}"]
+            | to nuon
+        "#;
+
+        test()
+            .run(code)
+            .expect_value_eq("[{ms: 15}, {s: 2.5}, {state: idle}]")
+    }
+
+    #[test]
+    fn regex_options_come_from_flags_and_config() -> Result {
+        let code = r#"
+            $env.config.regex.case_insensitive = true
+            "STATUS: OK\nstatus: failed" | parse --multiline '^status: {state}$' | get state
+        "#;
+
+        test().run(code).expect_value_eq(["OK", "failed"])
+    }
 }
 
 mod regex {
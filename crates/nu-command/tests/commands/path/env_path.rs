@@ -0,0 +1,91 @@
+use nu_test_support::{fs::Stub::EmptyFile, prelude::*};
+
+#[test]
+fn path_add_prepends_by_default() -> Result {
+    let outcome: bool = test().run(
+        "with-env {PATH: [a]} { path add b c; $env.PATH == ([b c a] | path expand --no-symlink) }",
+    )?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn path_add_appends() -> Result {
+    let outcome: bool = test().run(
+        "with-env {PATH: [a]} { path add --append b; $env.PATH == ([a b] | path expand --no-symlink) }",
+    )?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn path_add_moves_existing_entries_to_the_front() -> Result {
+    let outcome: bool = test().run(
+        "with-env {PATH: [a b]} { path add a; path add b --ret | $in == ([b a] | path expand --no-symlink) }",
+    )?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn path_add_splits_string_path() -> Result {
+    let outcome: i64 = test()
+        .run("with-env {PATH: ([/x /y] | str join (char esep))} { path add z --ret | length }")?;
+    assert_eq!(outcome, 3);
+    Ok(())
+}
+
+#[test]
+fn path_add_uses_record_for_current_os() -> Result {
+    let outcome: i64 = test().run(
+        "with-env {PATH: []} { path add {linux: a, macos: a, windows: a, android: a, freebsd: a} {nothing: b} --ret | length }",
+    )?;
+    assert_eq!(outcome, 1);
+    Ok(())
+}
+
+#[test]
+fn path_add_if_exists_skips_missing_directories() -> Result {
+    Playground::setup("path_add_if_exists", |dirs, sandbox| {
+        sandbox.mkdir("bin").with_files(&[EmptyFile("file.txt")]);
+
+        let outcome: Vec<String> = test()
+            .cwd(dirs.test())
+            .run("with-env {PATH: []} { path add --if-exists bin missing file.txt --ret | path basename }")?;
+        assert_eq!(outcome, ["bin"]);
+        Ok(())
+    })
+}
+
+#[test]
+fn path_add_rejects_other_values() -> Result {
+    let err = test().run("path add 1").expect_shell_error()?;
+    assert!(matches!(err, ShellError::OnlySupportsThisInputType { .. }));
+    Ok(())
+}
+
+#[test]
+fn path_add_cant_prepend_and_append() -> Result {
+    let err = test()
+        .run("path add --prepend --append a")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::IncompatibleParameters { .. }));
+    Ok(())
+}
+
+#[test]
+fn path_remove_removes_all_entries() -> Result {
+    let outcome: bool = test().run(
+        "with-env {PATH: ([a b a/ c] | path expand --no-symlink)} { path remove a c --ret | $in == ([b] | path expand --no-symlink) }",
+    )?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn path_dedupe_keeps_first_entries() -> Result {
+    let outcome: Vec<String> =
+        test().run("with-env {PATH: [/a /b /a /c/ /c]} { path dedupe; $env.PATH }")?;
+    assert_eq!(outcome, ["/a", "/b", "/c/"]);
+    Ok(())
+}
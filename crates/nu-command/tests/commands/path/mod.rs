@@ -1,5 +1,6 @@
 mod basename;
 mod dirname;
+mod env_path;
 mod exists;
 mod expand;
 mod join;
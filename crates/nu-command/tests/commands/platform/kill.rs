@@ -37,3 +37,40 @@ fn kill_negative_zero_signal_shorthand_requires_explicit_signal_flag() -> Result
         err => Err(err.into()),
     }
 }
+
+#[test]
+fn kill_by_name_without_matching_process() -> Result {
+    let err = test()
+        .run("kill 'no-such-process-name-*'")
+        .expect_shell_error()?;
+
+    assert_contains("No matching process", err.to_string());
+    Ok(())
+}
+
+#[test]
+fn kill_table_input_requires_pid_column() -> Result {
+    let err = test().run("[[name]; [nu]] | kill").expect_shell_error()?;
+
+    match err {
+        ShellError::CantFindColumn { col_name, .. } => {
+            assert_eq!(col_name, "pid");
+            Ok(())
+        }
+        err => Err(err.into()),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn kill_rejects_unknown_signal_name() -> Result {
+    let err = test().run("kill -s NOPE 12345").expect_shell_error()?;
+
+    match err {
+        ShellError::InvalidValue { actual, .. } => {
+            assert_contains("SIGNOPE", actual);
+            Ok(())
+        }
+        err => Err(err.into()),
+    }
+}
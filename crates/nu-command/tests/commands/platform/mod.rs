@@ -1,3 +1,4 @@
 mod ansi_;
 mod char_;
 mod kill;
+mod on_signal;
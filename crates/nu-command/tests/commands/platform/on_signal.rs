@@ -0,0 +1,53 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn unknown_signal() -> Result {
+    let err = test().run("on-signal kill {|| }").expect_shell_error()?;
+
+    match err {
+        ShellError::InvalidValue { actual, .. } => {
+            assert_eq!(actual, "kill");
+            Ok(())
+        }
+        err => Err(err.into()),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+#[deps(NU)]
+fn terminate_runs_closure_before_exiting() -> Result {
+    let code = r#"
+        nu -n -c '
+            on-signal SIGTERM {|signal| print $"cleaning up after ($signal)" }
+            kill $nu.pid
+            sleep 10sec
+            print "still running"
+        '
+        | complete
+    "#;
+    let result: CompleteResult = test().run(code)?;
+
+    assert_eq!(result.stdout.trim(), "cleaning up after terminate");
+    assert_ne!(result.exit_code, 0);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+#[deps(NU)]
+fn closures_run_in_order() -> Result {
+    let code = r#"
+        nu -n -c '
+            on-signal term {|| print first }
+            on-signal term {|| print second }
+            kill $nu.pid
+            sleep 10sec
+        '
+        | complete
+        | get stdout
+        | lines
+    "#;
+
+    test().run(code).expect_value_eq(["first", "second"])
+}
@@ -0,0 +1,86 @@
+use nu_test_support::nu;
+
+#[test]
+fn ref_updates_from_par_each_are_not_lost() {
+    let actual = nu!("
+        let count = ref new 0
+        1..1000 | par-each --threads 8 { $count | ref update { $in + 1 } } | ignore
+        $count | ref get
+    ");
+
+    assert_eq!(actual.out, "1000");
+}
+
+#[test]
+fn ref_copies_share_the_value() {
+    let actual = nu!("
+        let a = ref new [1]
+        let b = $a
+        $b | ref update { append 2 } | ignore
+        $a | ref get | to nuon
+    ");
+
+    assert_eq!(actual.out, "[1, 2]");
+}
+
+#[test]
+fn ref_is_shared_with_background_jobs() {
+    let actual = nu!("
+        let total = ref new 0
+        let job = job spawn { $total | ref update { $in + 5 }; 'done' | job send 0 }
+        job recv --timeout 10sec | ignore
+        $total | ref get
+    ");
+
+    assert_eq!(actual.out, "5");
+}
+
+#[test]
+fn ref_keeps_value_when_update_fails() {
+    let actual = nu!("
+        let r = ref new 1
+        try { $r | ref update { error make {msg: nope} } }
+        $r | ref get
+    ");
+
+    assert_eq!(actual.out, "1");
+}
+
+#[test]
+fn ref_update_inside_update_of_same_ref_fails() {
+    let actual = nu!("
+        let r = ref new 1
+        $r | ref update { $r | ref update { $in + 1 } }
+    ");
+
+    assert!(actual.err.contains("already being updated"));
+}
+
+#[test]
+fn ref_update_inside_update_of_same_ref_in_par_each_fails() {
+    let actual = nu!("
+        let r = ref new 1
+        $r | ref update { [1 2] | par-each { $r | ref update { $in + 1 } } }
+    ");
+
+    assert!(actual.err.contains("already being updated"));
+}
+
+#[test]
+fn ref_update_inside_update_of_other_ref_works() {
+    let actual = nu!("
+        let a = ref new 1
+        let b = ref new 10
+        $a | ref update {|n| [1 2] | par-each { $b | ref update { $in + $n } } | ignore; $n + 1 }
+        [($a | ref get) ($b | ref get)] | to nuon
+    ");
+
+    assert_eq!(actual.out, "[2, 12]");
+}
+
+#[test]
+fn ref_get_needs_a_ref() {
+    let actual = nu!("[5] | each { ref get }");
+
+    assert!(actual.err.contains("ref new"));
+}
@@ -1,6 +1,7 @@
 use nu_protocol::{IntoPipelineData, PipelineMetadata, test_record};
 use nu_test_support::nu;
 use nu_test_support::prelude::*;
+use pretty_assertions::assert_matches;
 use rstest::rstest;
 
 #[test]
@@ -246,3 +247,21 @@ fn reject_with_negative_index_reports_clear_error() {
             .contains("can't convert negative number to cell path")
     );
 }
+
+#[test]
+fn reject_misspelled_column_suggests_similar() -> Result {
+    let err = test()
+        .run("{name: nu, version: 1} | reject verison")
+        .expect_shell_error()?;
+    assert_matches!(err, ShellError::DidYouMean { suggestion, .. } if suggestion == "version");
+    Ok(())
+}
+
+#[test]
+fn reject_columns_matching_patterns() -> Result {
+    let code = "[[tmp_a tmp_b name]; [1 2 nu] [3 4 sh]] | reject -r '^tmp_' | to nuon";
+    test().run(code).expect_value_eq("[[name]; [nu], [sh]]")?;
+
+    let code = "[[tmp_a name]; [1 nu]] | each {|row| $row } | reject 'tmp*' | to nuon";
+    test().run(code).expect_value_eq("[[name]; [nu]]")
+}
@@ -553,3 +553,11 @@ fn error_when_command_list_empty(#[case] prefix: &str) {
         assert!(actual.err.contains("missing parameter"));
     })
 }
+
+#[cfg(not(windows))]
+#[test]
+fn elevated_is_only_supported_on_windows() {
+    let actual = nu!("run-external --elevated echo hello");
+    assert!(actual.err.contains("only supported on Windows"));
+    assert!(actual.out.is_empty());
+}
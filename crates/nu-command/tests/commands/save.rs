@@ -733,3 +733,60 @@ fn save_streaming_list_stream_to_csv() {
         assert!(actual.contains("5,50"));
     })
 }
+
+#[test]
+fn save_atomic_replaces_file_without_leftovers() {
+    Playground::setup("save_atomic", |dirs, sandbox| {
+        sandbox.with_files(&[Stub::FileWithContent("config.txt", "old")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "'new' | save --force --atomic config.txt; 'more' | save --append --atomic config.txt; ls --all | length"
+        );
+
+        assert_eq!(actual.out, "1");
+        assert_eq!(file_contents(dirs.test().join("config.txt")), "newmore");
+    })
+}
+
+#[test]
+fn save_backup_simple() {
+    Playground::setup("save_backup_simple", |dirs, sandbox| {
+        sandbox.with_files(&[Stub::FileWithContent("config.txt", "old")]);
+
+        nu!(cwd: dirs.test(), "'new' | save --force --backup simple config.txt");
+        nu!(cwd: dirs.test(), "'newer' | save --force --backup-suffix .bak config.txt");
+
+        assert_eq!(file_contents(dirs.test().join("config.txt")), "newer");
+        assert_eq!(file_contents(dirs.test().join("config.txt~")), "old");
+        assert_eq!(file_contents(dirs.test().join("config.txt.bak")), "new");
+    })
+}
+
+#[test]
+fn save_backup_numbered() {
+    Playground::setup("save_backup_numbered", |dirs, sandbox| {
+        sandbox.with_files(&[Stub::FileWithContent("config.txt", "1")]);
+
+        nu!(cwd: dirs.test(), "'2' | save --force --backup numbered config.txt");
+        nu!(cwd: dirs.test(), "'3' | save --force --atomic --backup existing config.txt");
+
+        assert_eq!(file_contents(dirs.test().join("config.txt")), "3");
+        assert_eq!(file_contents(dirs.test().join("config.txt.~1~")), "1");
+        assert_eq!(file_contents(dirs.test().join("config.txt.~2~")), "2");
+    })
+}
+
+#[test]
+fn save_backup_rejects_unknown_mode() {
+    Playground::setup("save_backup_invalid", |dirs, _sandbox| {
+        let actual = nu!(cwd: dirs.test(), "'new' | save --backup sometimes config.txt");
+
+        assert!(
+            actual
+                .err
+                .contains("expected `simple`, `numbered` or `existing`")
+        );
+        assert!(!dirs.test().join("config.txt").exists());
+    })
+}
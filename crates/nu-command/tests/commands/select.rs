@@ -338,3 +338,35 @@ fn select_with_negative_number_errors_out() -> Result {
         err => Err(err.into()),
     }
 }
+
+#[test]
+fn selects_columns_matching_globs_and_regexes() -> Result {
+    let code = "
+        [[http_host http_port user]; [example.com 80 ada] [nushell.sh 443 grace]]
+        | each {|row| $row }
+        | select 'http_*' user
+        | columns
+    ";
+    test()
+        .run(code)
+        .expect_value_eq(["http_host", "http_port", "user"])?;
+
+    let code = "{Tmp_a: 1, b: 2, tmp_c: 3} | select --regex --ignore-case '^tmp_' | columns";
+    test().run(code).expect_value_eq(["Tmp_a", "tmp_c"])
+}
+
+#[test]
+fn select_glob_prefers_the_column_with_its_name() -> Result {
+    let code = "{'a*': 1, ab: 2} | select 'a*' | columns";
+
+    test().run(code).expect_value_eq(["a*"])
+}
+
+#[test]
+fn select_pattern_matching_nothing() -> Result {
+    let err = test().run("{a: 1} | select 'x*'").expect_shell_error()?;
+    assert_contains("No column matches the pattern", err.to_string());
+
+    let code = "{a: 1} | select --optional 'x*' a | to nuon";
+    test().run(code).expect_value_eq("{a: 1}")
+}
@@ -106,3 +106,19 @@ fn split_column_number_error() -> Result {
     test().run(code).expect_shell_error()?;
     Ok(())
 }
+
+#[test]
+fn split_column_trim() -> Result {
+    let code = r#"" a | b | c " | split column "|" --trim right | first | values | to nuon"#;
+    test().run(code).expect_value_eq(r#"[" a", " b", " c"]"#)?;
+
+    let code = r#"" a | b " | split column "|" x y --trim {y: both} | to nuon"#;
+    test().run(code).expect_value_eq(r#"[[x, y]; [" a ", b]]"#)
+}
+
+#[test]
+fn split_column_trim_error() -> Result {
+    let code = r#""a|b" | split column "|" --trim middle"#;
+    test().run(code).expect_shell_error()?;
+    Ok(())
+}
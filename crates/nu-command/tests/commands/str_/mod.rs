@@ -282,6 +282,23 @@ fn find_and_replaces_regex_closure_type_mismatch() -> Result {
     Ok(())
 }
 
+#[test]
+fn find_and_replaces_ignoring_case_without_expanding() -> Result {
+    let code = "'Price: PRICE' | str replace --all --ignore-case 'price' '$1'";
+
+    test().run(code).expect_value_eq("$1: $1")
+}
+
+#[test]
+fn find_and_replaces_with_config_regex_options() -> Result {
+    let code = r#"
+         $env.config.regex.dotall = true
+         [("a\nb" | str replace -r 'a.b' 'x') ("a\nb" | str replace 'a.b' 'x')]
+     "#;
+
+    test().run(code).expect_value_eq(["x", "a\nb"])
+}
+
 #[test]
 fn substrings_the_input() -> Result {
     Playground::setup("str_test_8", |dirs, sandbox| {
@@ -4324,3 +4324,57 @@ fn metadata_path_columns_multiple_with_icons() {
     let expected = "\u{1b}[39m╭───┬────────┬────────────╮\u{1b}[0m\u{1b}[39m│\u{1b}[0m \u{1b}[1;32m#\u{1b}[0m \u{1b}[39m│\u{1b}[0m  \u{1b}[1;32mdir\u{1b}[0m   \u{1b}[39m│\u{1b}[0m    \u{1b}[1;32mfile\u{1b}[0m    \u{1b}[39m│\u{1b}[0m\u{1b}[39m├───┼────────┼────────────┤\u{1b}[0m\u{1b}[39m│\u{1b}[0m \u{1b}[1;32m0\u{1b}[0m \u{1b}[39m│\u{1b}[0m \u{1b}[39m\u{1b}[38;2;126;142;168m\u{f115}\u{1b}[0m  \u{1b}[38;5;81msrc\u{1b}[0m\u{1b}[0m \u{1b}[39m│\u{1b}[0m \u{1b}[39m\u{1b}[38;2;222;165;132m\u{e68b}\u{1b}[0m  \u{1b}[38;5;48mmain.rs\u{1b}[0m\u{1b}[0m \u{1b}[39m│\u{1b}[0m\u{1b}[39m╰───┴────────┴────────────╯\u{1b}[0m";
     assert_eq!(actual.out, expected);
 }
+
+#[test]
+fn table_columns_alignment() {
+    let actual = nu!(
+        "$env.config.table.columns = { a: { alignment: right } }; [[a]; [x] [xxx]] | table --width=80"
+    );
+    assert_eq!(
+        actual.out,
+        "╭───┬─────╮│ # │  a  │├───┼─────┤│ 0 │   x ││ 1 │ xxx │╰───┴─────╯"
+    );
+}
+
+#[test]
+fn table_columns_width() {
+    let actual = nu!(concat!(
+        "$env.config.table.trim = { methodology: truncating, truncating_suffix: '...' };",
+        "$env.config.table.columns = { a: { width: 5 } };",
+        "[[a b]; [abcdefghij abcdefghij]] | table --width=80"
+    ));
+    assert!(actual.out.contains("ab..."));
+    assert_eq!(actual.out.matches("abcdefghij").count(), 1);
+}
+
+#[test]
+fn table_row_style() {
+    let actual = nu!(concat!(
+        "$env.config.use_ansi_coloring = true;",
+        "$env.config.table.row_style = {|row| if $row.status == 'failed' { 'red' } };",
+        "[[status]; [ok] [failed]] | table --width=80"
+    ));
+    assert!(actual.out.contains("\u{1b}[31mfailed"));
+    assert!(!actual.out.contains("\u{1b}[31mok"));
+}
+
+#[test]
+fn table_expand_column_depth() {
+    let actual = nu!(concat!(
+        "$env.config.table.columns = { metadata: { expand: 0 } };",
+        "[[name metadata tags]; [a {x: 1, y: 2, z: 3} [1 2]]] | table --expand --width=80"
+    ));
+    assert!(actual.out.contains("{3 fields}"));
+    assert!(!actual.out.contains("│ x │"));
+    assert!(!actual.out.contains("[list 2 items]"));
+}
+
+#[test]
+fn table_expand_column_summary() {
+    let actual = nu!(concat!(
+        "$env.config.table.columns = { metadata: { expand: 0 } };",
+        "$env.config.table.summary = {|value| $value | columns | str join ',' };",
+        "[[name metadata]; [a {x: 1, y: 2, z: 3}]] | table --expand --width=80"
+    ));
+    assert!(actual.out.contains("x,y,z"));
+}
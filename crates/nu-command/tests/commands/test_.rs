@@ -0,0 +1,172 @@
+use nu_protocol::test_record;
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::playground::Playground;
+use nu_test_support::prelude::*;
+
+const MATH_TESTS: &str = "
+use std/assert
+use std/testing *
+
+@test
+def adds [] { assert equal (1 + 1) 2 }
+
+@test
+def subtracts [] { assert equal (2 - 1) 0 }
+
+@ignore
+def divides [] { assert false }
+";
+
+#[test]
+fn test_runs_tests_of_file() -> Result {
+    Playground::setup("test_runs_tests_of_file", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("test_math.nu", MATH_TESTS)]);
+
+        test()
+            .cwd(dirs.test())
+            .run("test run test_math.nu | select name status")
+            .expect_value_eq([
+                test_record! {"name" => "adds", "status" => "pass"},
+                test_record! {"name" => "subtracts", "status" => "fail"},
+                test_record! {"name" => "divides", "status" => "skip"},
+            ])
+    })
+}
+
+#[test]
+fn test_reports_assertion_errors() -> Result {
+    Playground::setup("test_reports_assertion_errors", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("test_math.nu", MATH_TESTS)]);
+
+        test()
+            .cwd(dirs.test())
+            .run("test run test_math.nu | where status == fail | get 0.error.rendered | str contains 'left: 1'")
+            .expect_value_eq(true)
+    })
+}
+
+#[test]
+fn test_finds_test_files_in_directories() -> Result {
+    Playground::setup("test_finds_test_files_in_directories", |dirs, sandbox| {
+        sandbox.mkdir("tests").with_files(&[
+            FileWithContent("tests/test_math.nu", MATH_TESTS),
+            FileWithContent("tests/helper.nu", "@test\ndef not-found [] {}"),
+        ]);
+
+        test()
+            .cwd(dirs.test())
+            .run("test run tests --list | get suite | uniq | path basename")
+            .expect_value_eq(["test_math.nu"])
+    })
+}
+
+#[test]
+fn test_filters_tests_by_name() -> Result {
+    Playground::setup("test_filters_tests_by_name", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("test_math.nu", MATH_TESTS)]);
+
+        let mut tester = test().cwd(dirs.test());
+        tester
+            .run("test run test_math.nu --filter '^add' | get name")
+            .expect_value_eq(["adds"])?;
+        tester
+            .run("test run test_math.nu --exclude 's$' | get name")
+            .expect_value_eq(Vec::<String>::new())
+    })
+}
+
+#[test]
+fn test_passes_setup_to_tests() -> Result {
+    Playground::setup("test_passes_setup_to_tests", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent(
+            "test_setup.nu",
+            "
+            use std/assert
+            use std/testing *
+
+            @before-each
+            def setup [] { {value: 42} }
+
+            @test
+            def uses-context [] { assert equal $in.value 42 }
+            ",
+        )]);
+
+        test()
+            .cwd(dirs.test())
+            .run("test run test_setup.nu | get status")
+            .expect_value_eq(["pass"])
+    })
+}
+
+#[test]
+fn test_isolates_environment() -> Result {
+    Playground::setup("test_isolates_environment", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent(
+            "test_env.nu",
+            "
+            use std/assert
+
+            @test
+            def --env sets-env [] { $env.LEAKED = 1 }
+
+            @test
+            def sees-no-env [] { assert ('LEAKED' not-in $env) }
+            ",
+        )]);
+
+        test()
+            .cwd(dirs.test())
+            .run("test run test_env.nu | get status")
+            .expect_value_eq(["pass", "pass"])
+    })
+}
+
+#[test]
+fn test_runs_tests_in_scope() -> Result {
+    let mut tester = test();
+    let () = tester.run(
+        "
+        module spam {
+            @test
+            export def works [] {}
+
+            export def helper [] {}
+        }
+        use spam
+        ",
+    )?;
+    tester
+        .run("test run | where suite == spam | select name status")
+        .expect_value_eq([test_record! {"name" => "spam works", "status" => "pass"}])
+}
+
+#[test]
+fn test_writes_reports() -> Result {
+    Playground::setup("test_writes_reports", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("test_math.nu", MATH_TESTS)]);
+
+        let mut tester = test().cwd(dirs.test());
+        tester
+            .run("test run test_math.nu --format json | from json | get summary | reject duration")
+            .expect_value_eq(test_record! {
+                "total" => 3,
+                "passed" => 1,
+                "failed" => 1,
+                "skipped" => 1,
+            })?;
+
+        let junit: String = tester.run("test run test_math.nu --format junit")?;
+        assert_contains(r#"failures="1""#, &junit);
+        assert_contains(r#"<testcase name="adds""#, &junit);
+        assert_contains("<skipped/>", &junit);
+        Ok(())
+    })
+}
+
+#[test]
+fn test_rejects_unknown_format() -> Result {
+    test()
+        .run("test run --format xml")
+        .expect_error_code_eq("nu::shell::invalid_value")
+}
@@ -40,3 +40,52 @@ fn rejects_non_table_stream_input() {
     assert!(actual.out.is_empty());
     assert!(actual.err.contains("only table"));
 }
+
+#[test]
+fn fills_columns_missing_in_some_rows() {
+    let actual = nu!("[{a: 1} {b: 2}] | transpose | to nuon");
+
+    assert_eq!(
+        actual.out,
+        r#"[["column0", "column1", "column2"]; [a, 1, null], [b, null, 2]]"#
+    );
+}
+
+#[test]
+fn slugifies_header_row() {
+    let actual = nu!(
+        "[[key value]; ['Total Count (all)' 3] ['--' 4]] | transpose -r -d --slugify | to nuon"
+    );
+
+    assert_eq!(actual.out, r#"{total_count_all: 3, "column1": 4}"#);
+}
+
+#[test]
+fn dedupes_header_row() {
+    let actual =
+        nu!("[[key value]; [foo 1] [foo 2] [foo_2 3]] | transpose -r -d --dedupe | to nuon");
+
+    assert_eq!(actual.out, r#"{foo: 1, "foo_2": 2, "foo_2_2": 3}"#);
+}
+
+#[test]
+fn header_cleanup_needs_header_row() {
+    let actual = nu!("[[key value]; [foo 1]] | transpose --slugify");
+
+    assert!(actual.err.contains("Can only be used with `--header-row`"));
+}
+
+#[test]
+fn dedupe_conflicts_with_keep_last() {
+    let actual = nu!("[[key value]; [foo 1]] | transpose -r --dedupe --keep-last");
+
+    assert!(actual.err.contains("because of `--dedupe`"));
+}
+
+#[test]
+fn transposes_long_stream_to_record() {
+    let actual =
+        nu!("1..10000 | each {|i| {key: $'k($i)', value: $i} } | transpose -r -d | get k10000");
+
+    assert_eq!(actual.out, "10000");
+}
@@ -135,7 +135,11 @@ fn limit_set_invalid4() -> Result {
     ",
     )?;
 
-    assert!(actual.stderr.contains("string, int or filesize required"));
+    assert!(
+        actual
+            .stderr
+            .contains("string, int, filesize or duration required")
+    );
     Ok(())
 }
 
@@ -215,3 +219,61 @@ fn limit_set_filesize2() -> Result {
     );
     Ok(())
 }
+
+#[test]
+#[deps(NU)]
+fn limits_get_lists_all_limits() -> Result {
+    let actual = run_ulimit(
+        "
+        let limits = ulimit get;
+        [
+            ($limits | length) == 20
+            ($limits | where name == file-descriptor-count | first | get supported)
+            (($limits | where name == stack-size | first | get soft | describe) in [filesize string])
+        ] | all { $in } | to nuon
+    ",
+    )?;
+
+    assert_eq!(actual.stdout.trim(), "true");
+    Ok(())
+}
+
+#[test]
+#[deps(NU)]
+fn limits_set_from_record() -> Result {
+    let actual = run_ulimit(
+        "
+        let hard = ulimit get file-descriptor-count | first | get hard;
+        ulimit set --soft {file-descriptor-count: $hard};
+        (ulimit get file-descriptor-count | first | get soft) == $hard
+    ",
+    )?;
+
+    assert!(actual.stdout.contains("true"));
+    Ok(())
+}
+
+#[test]
+#[deps(NU)]
+fn limits_set_checks_all_limits_first() -> Result {
+    let actual = run_ulimit(
+        "
+        let before = ulimit get core-size | first | get soft;
+        try { ulimit set {core-size: 0, nope: 1} };
+        (ulimit get core-size | first | get soft) == $before
+    ",
+    )?;
+
+    assert!(actual.stdout.contains("true"));
+    Ok(())
+}
+
+#[test]
+#[deps(NU)]
+fn limits_set_unknown_limit() -> Result {
+    let actual = run_ulimit("ulimit set {stack-sise: 1024}")?;
+
+    assert!(actual.stderr.contains("Unknown limit stack-sise"));
+    assert!(actual.stderr.contains("did you mean stack-size?"));
+    Ok(())
+}
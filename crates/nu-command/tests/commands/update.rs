@@ -359,3 +359,29 @@ fn update_nested_table_column_closure_with_in_place_transform() -> Result {
         )
         .expect_value_eq("datetime")
 }
+
+#[test]
+fn update_several_columns_at_once() -> Result {
+    let code = "
+        [[a b c]; [1 x true] [2 y false]]
+        | each {|row| $row }
+        | update {a: {$in * 10}, b: z, c: {|row| $row.a }}
+        | to nuon
+    ";
+
+    test()
+        .run(code)
+        .expect_value_eq("[[a, b, c]; [10, z, 10], [20, z, 20]]")
+}
+
+#[test]
+fn update_record_of_columns_errors_on_missing_column() -> Result {
+    let err = test()
+        .run("{a: 1} | update {a: 2, b: 3}")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::CantFindColumn { .. }));
+
+    let err = test().run("{a: 1} | update a").expect_shell_error()?;
+    assert!(matches!(err, ShellError::MissingParameter { .. }));
+    Ok(())
+}
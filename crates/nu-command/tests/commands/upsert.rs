@@ -335,3 +335,15 @@ fn upsert_nested_path_into_empty_list_errors_without_underflow() -> Result {
 
     Ok(())
 }
+
+#[test]
+fn upsert_several_columns_at_once() -> Result {
+    let code = "
+        [[a]; [1] [2]]
+        | each {|row| $row }
+        | upsert {a: {$in + 1}, b: {|row| $row.a * 2 }}
+        | to nuon
+    ";
+
+    test().run(code).expect_value_eq("[[a, b]; [2, 4], [3, 6]]")
+}
@@ -25,6 +25,16 @@ fn filters_with_nothing_comparison() -> Result {
     test().run(code).expect_value_eq(7)
 }
 
+#[test]
+fn regex_match_uses_config_options() -> Result {
+    let code = "
+        $env.config.regex.case_insensitive = true
+        [Nushell bash] | where $it =~ '^nu' | append ([Nushell] | where $it =~ '(?-i)^nu')
+    ";
+
+    test().run(code).expect_value_eq(["Nushell"])
+}
+
 #[test]
 fn where_inside_block_works() -> Result {
     test()
@@ -236,3 +236,35 @@ fn which_respects_hidden_pathext() -> Result {
         Ok(())
     })
 }
+
+#[test]
+fn which_all_reports_shadowed_overlay_commands() -> Result {
+    let code = "
+        module spam { export def foo [] { 'spam' } }
+        module eggs { export def foo [] { 'eggs' } }
+        overlay use spam
+        overlay use eggs
+        which --all foo | select module overlay shadowed | to nuon
+    ";
+
+    test()
+        .run(code)
+        .expect_value_eq("[[module, overlay, shadowed]; [eggs, eggs, false], [spam, spam, true]]")
+}
+
+#[test]
+fn which_all_reports_definition_position() -> Result {
+    let code = "
+        def foo [] { 'foo' }
+        which --all foo | get 0 | [$in.line $in.column] | describe
+    ";
+
+    test().run(code).expect_value_eq("list<int>")
+}
+
+#[test]
+fn which_without_all_has_no_origin_columns() -> Result {
+    test()
+        .run("def foo [] {}; which foo | columns | to nuon")
+        .expect_value_eq("[command, path, type]")
+}
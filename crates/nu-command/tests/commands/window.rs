@@ -134,3 +134,56 @@ fn stride_less_than_window_size_remainder() -> Result {
         .run("([0 1 2 3 4 5] | window 3 -s 2 -r | length) == 3")
         .expect_value_eq(true)
 }
+
+#[test]
+fn time_windows_tumble_by_default() -> Result {
+    test()
+        .run("[[t v]; [2024-01-01T00:00:01Z 1] [2024-01-01T00:00:09Z 2] [2024-01-01T00:00:12Z 3] [2024-01-01T00:00:31Z 4]] | window 10sec --by t | each { get v } | to nuon")
+        .expect_value_eq("[[1, 2], [3], [4]]")
+}
+
+#[test]
+fn time_windows_slide_by_stride() -> Result {
+    test()
+        .run("[[t v]; [2024-01-01T00:00:01Z 1] [2024-01-01T00:00:06Z 2] [2024-01-01T00:00:12Z 3]] | window 10sec --stride 5sec --by t | each { get v } | to nuon")
+        .expect_value_eq("[[1], [1, 2], [2, 3], [3]]")
+}
+
+#[test]
+fn time_windows_skip_rows_between_windows() -> Result {
+    test()
+        .run("[[t v]; [2024-01-01T00:00:01Z 1] [2024-01-01T00:00:07Z 2] [2024-01-01T00:00:11Z 3]] | window 5sec --stride 10sec --by t | each { get v } | to nuon")
+        .expect_value_eq("[[1], [3]]")
+}
+
+#[test]
+fn time_windows_end_at_the_latest_time() -> Result {
+    test()
+        .run("[[t v]; [(9223372036854775806 | into datetime) 1] [(9223372036854775807 | into datetime) 2]] | window 1day --by t | each { get v } | to nuon")
+        .expect_value_eq("[[1, 2]]")
+}
+
+#[test]
+fn time_windows_by_nested_column() -> Result {
+    test()
+        .run("[{meta: {at: 2024-01-01T00:00:01Z}} {meta: {at: 2024-01-01T00:01:01Z}}] | window 1min --by meta.at | length")
+        .expect_value_eq(2)
+}
+
+#[test]
+fn time_windows_need_sorted_rows() -> Result {
+    let err = test()
+        .run("[[t]; [2024-01-01T00:00:09Z] [2024-01-01T00:00:01Z]] | window 10sec --by t")
+        .expect_shell_error()?;
+    assert_contains("Rows out of order", err.to_string());
+    Ok(())
+}
+
+#[test]
+fn time_windows_need_positive_duration() -> Result {
+    let err = test()
+        .run("[[t]; [2024-01-01T00:00:01Z]] | window 0sec --by t")
+        .expect_shell_error()?;
+    assert!(matches!(err, ShellError::IncorrectValue { .. }));
+    Ok(())
+}
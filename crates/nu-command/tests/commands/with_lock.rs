@@ -0,0 +1,51 @@
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn runs_closure_and_returns_its_output() {
+    Playground::setup("with_lock_test_1", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "with-lock test.lock { [1 2 3] | math sum }"
+        );
+
+        assert_eq!(actual.out, "6");
+        assert!(dirs.test().join("test.lock").exists());
+    })
+}
+
+#[test]
+fn times_out_while_locked_elsewhere() {
+    Playground::setup("with_lock_test_2", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "with-lock test.lock { with-lock --timeout 100ms test.lock { 'unreachable' } }"
+        );
+
+        assert!(actual.err.contains("Timed out waiting for the lock"));
+    })
+}
+
+#[test]
+fn shared_locks_can_be_held_together() {
+    Playground::setup("with_lock_test_3", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "with-lock --shared test.lock { with-lock --shared --timeout 100ms test.lock { 'both' } }"
+        );
+
+        assert_eq!(actual.out, "both");
+    })
+}
+
+#[test]
+fn releases_lock_when_closure_fails() {
+    Playground::setup("with_lock_test_4", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "try { with-lock test.lock { error make { msg: 'boom' } } }; with-lock --timeout 0sec test.lock { 'free' }"
+        );
+
+        assert_eq!(actual.out, "free");
+    })
+}
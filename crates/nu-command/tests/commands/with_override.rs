@@ -0,0 +1,41 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn overrides_calls_in_modules() -> Result {
+    let code = "
+        module clock { export def year [] { date now | format date '%Y' } }
+        use clock
+        with-override {'date now': {|| 2000-01-01 }} { clock year }
+    ";
+    test().run(code).expect_value_eq("2000")
+}
+
+#[test]
+fn passes_positional_arguments() -> Result {
+    let code = "
+        def fetch [url: string] { http get $url }
+        with-override {'http get': {|url| {url: $url} }} { fetch https://example.com | get url }
+    ";
+    test().run(code).expect_value_eq("https://example.com")
+}
+
+#[test]
+fn closure_can_call_replaced_command() -> Result {
+    test()
+        .run("with-override {'str upcase': {|| str upcase | $in + '!' }} { 'hi' | str upcase }")
+        .expect_value_eq("HI!")
+}
+
+#[test]
+fn override_ends_with_block() -> Result {
+    test()
+        .run("with-override {'str upcase': {|| 'mocked' }} { }; 'hi' | str upcase")
+        .expect_value_eq("HI")
+}
+
+#[test]
+fn unknown_command_is_an_error() -> Result {
+    test()
+        .run("with-override {'no such command': {|| 1 }} { }")
+        .expect_error_code_eq("nu::shell::error")
+}
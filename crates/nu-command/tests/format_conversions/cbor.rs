@@ -0,0 +1,31 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn cbor_roundtrip() -> Result {
+    let code = "
+        let value = {name: nu, id: -42, ratio: 2.5, when: 2024-01-15T09:00:00Z, data: 0x[01 FF], tags: [shell], extra: null}
+        $value | to cbor | from cbor | $in == $value
+    ";
+
+    let outcome: bool = test().run(code)?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn from_cbor_reads_sequences() -> Result {
+    let code = "
+        [1 two] | each { to cbor } | bytes collect | from cbor --objects | $in == [1 two]
+    ";
+
+    let outcome: bool = test().run(code)?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn from_cbor_rejects_trailing_data() -> Result {
+    let err = test().run("0x[01 02] | from cbor").expect_shell_error()?;
+    assert_contains("Additional data", &err.to_string());
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use nu_test_support::{fs::Stub::FileWithContent, prelude::*};
+
+#[test]
+fn load_env_reads_a_dotenv_file() -> Result {
+    Playground::setup("load_env_dotenv_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent(
+            ".env",
+            "# settings\nexport HOST=example.com\nURL=\"https://${HOST}\"\nDEBUG=false\n",
+        )]);
+
+        let code = r#"
+            load-env --file .env {DEBUG: "true"}
+            [$env.HOST $env.URL $env.DEBUG] | str join " "
+        "#;
+
+        test()
+            .cwd(dirs.test())
+            .run(code)
+            .expect_value_eq("example.com https://${HOST} true")
+    })
+}
+
+#[test]
+fn from_dotenv_rejects_unterminated_quotes() -> Result {
+    let err = test()
+        .run(r#""A=1\nB='unterminated" | from dotenv"#)
+        .expect_shell_error()?;
+    assert_contains(".env", &err.to_string());
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn edn_roundtrip() -> Result {
+    let code = "
+        let value = {name: nu, 'db/id': 42, ratio: 2.0, when: 2024-01-15T09:00:00Z, tags: [shell, 'with space'], extra: null}
+        $value | to edn | from edn | $in == $value
+    ";
+
+    let outcome: bool = test().run(code)?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn from_edn_reads_clojure_data() -> Result {
+    let code = r#"
+        '{:db/id 17592186045418
+          :person/name "Ada"
+          :person/langs #{:clojure :nu} ; a comment
+          :person/born #inst "1815-12-10T00:00:00Z"}'
+        | from edn
+        | $in."db/id" == 17592186045418 and ($in."person/langs" | sort) == [clojure nu] and ($in."person/born" | format date "%Y") == "1815"
+    "#;
+
+    let outcome: bool = test().run(code)?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn from_edn_reports_position() -> Result {
+    let err = test().run("'[1 2' | from edn").expect_labeled_error()?;
+    assert_contains("at line 1, column 5", &err.labels[0].text);
+    Ok(())
+}
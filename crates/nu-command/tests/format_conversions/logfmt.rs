@@ -0,0 +1,37 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn logfmt_roundtrip() -> Result {
+    let code = r#"
+        let value = [{level: info, msg: "user \"ada\" logged in", code: "42", took: 1.5}]
+        $value | to logfmt | from logfmt | $in == $value
+    "#;
+
+    let outcome: bool = test().run(code)?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn from_logfmt_keeps_reading_after_a_bad_line() -> Result {
+    let code = r#"
+        ["at=info n=1" "broken=\"quote" "at=error n=3"]
+        | str join (char nl)
+        | from logfmt
+        | last
+        | get n
+    "#;
+
+    let outcome: i64 = test().run(code)?;
+    assert_eq!(outcome, 3);
+    Ok(())
+}
+
+#[test]
+fn to_logfmt_rejects_nested_values() -> Result {
+    let err = test()
+        .run("{tags: [a b]} | to logfmt")
+        .expect_shell_error()?;
+    assert_contains("logfmt", &err.to_string());
+    Ok(())
+}
@@ -1,11 +1,16 @@
+mod cbor;
 mod csv;
+mod dotenv;
+mod edn;
 mod html;
 mod json;
+mod logfmt;
 mod markdown;
 mod msgpack;
 mod msgpackz;
 mod nuon;
 mod ods;
+mod plist;
 mod ssv;
 mod toml;
 mod tsv;
@@ -0,0 +1,32 @@
+use nu_test_support::prelude::*;
+
+#[test]
+fn plist_roundtrip() -> Result {
+    let code = "
+        let value = {name: dock, tilesize: 48, hidden: false, data: 0x[01 02], when: 2020-01-01T00:00:00+00:00, apps: [a b]}
+        $value | to plist | from plist | $in == $value
+    ";
+
+    let outcome: bool = test().run(code)?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn binary_plist_roundtrip() -> Result {
+    let code = "
+        let value = {name: dock, ratio: 0.5, apps: [{id: 1} {id: 2}]}
+        $value | to plist --binary | from plist | $in == $value
+    ";
+
+    let outcome: bool = test().run(code)?;
+    assert!(outcome);
+    Ok(())
+}
+
+#[test]
+fn to_plist_rejects_nothing() -> Result {
+    let err = test().run("{a: null} | to plist").expect_shell_error()?;
+    assert!(matches!(err, ShellError::CantConvert { .. }));
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use miette::Diagnostic;
+use nu_protocol::{CompileError, ParseError, ShellError};
+use thiserror::Error;
+
+/// An error from setting up or evaluating code with [`Nushell`](crate::Nushell).
+///
+/// Every variant is a [`Diagnostic`], which can be rendered with its source code by
+/// [`Nushell::format_error`](crate::Nushell::format_error).
+#[derive(Debug, Clone, Error, Diagnostic)]
+pub enum EmbedError {
+    /// The code doesn't parse.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+
+    /// The code parses, but can't be compiled.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Compile(#[from] CompileError),
+
+    /// The code failed while running, or its result couldn't be converted.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Shell(#[from] ShellError),
+}
@@ -0,0 +1,294 @@
+#![doc = include_str!("../README.md")]
+mod error;
+
+pub use error::EmbedError;
+pub use nu_protocol::{FromValue, IntoValue, PipelineData, ShellError, Span, Value};
+
+use nu_engine::eval_block;
+use nu_protocol::{
+    Signals,
+    ast::Block,
+    debugger::WithoutDebug,
+    engine::{EngineState, Stack, StateDelta, StateWorkingSet},
+    format_cli_error,
+    shell_error::generic::GenericError,
+};
+use std::{
+    path::PathBuf,
+    sync::{Arc, atomic::AtomicBool},
+};
+
+/// Configures the engine of a [`Nushell`] before it is created.
+#[derive(Debug, Clone)]
+pub struct NushellBuilder {
+    std_lib: bool,
+    inherit_env: bool,
+    cwd: Option<PathBuf>,
+    env: Vec<(String, Value)>,
+    interrupt: Option<Arc<AtomicBool>>,
+}
+
+impl Default for NushellBuilder {
+    fn default() -> Self {
+        Self {
+            std_lib: true,
+            inherit_env: true,
+            cwd: None,
+            env: Vec::new(),
+            interrupt: None,
+        }
+    }
+}
+
+impl NushellBuilder {
+    /// Whether to load the standard library, so code can `use std`. Defaults to `true`.
+    pub fn std_lib(mut self, std_lib: bool) -> Self {
+        self.std_lib = std_lib;
+        self
+    }
+
+    /// Whether `$env` starts with the environment variables of the process. Defaults to `true`.
+    pub fn inherit_env(mut self, inherit_env: bool) -> Self {
+        self.inherit_env = inherit_env;
+        self
+    }
+
+    /// The directory the code runs in. Defaults to the current directory of the process.
+    pub fn cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Sets an environment variable, after those inherited from the process.
+    pub fn env_var(mut self, name: impl Into<String>, value: impl IntoValue) -> Self {
+        // No source span — the value comes from the embedding application
+        self.env
+            .push((name.into(), value.into_value(Span::unknown())));
+        self
+    }
+
+    /// A flag to interrupt running code, as ctrl-c does in the shell, by setting it to `true`.
+    pub fn interrupt(mut self, interrupt: Arc<AtomicBool>) -> Self {
+        self.interrupt = Some(interrupt);
+        self
+    }
+
+    /// Creates the engine, with the commands of the `nu` binary.
+    pub fn build(self) -> Result<Nushell, EmbedError> {
+        let engine_state = nu_cmd_lang::create_default_context();
+        let engine_state = nu_command::add_shell_command_context(engine_state);
+        let mut engine_state = nu_cmd_extra::add_extra_command_context(engine_state);
+
+        if self.inherit_env {
+            for (name, value) in std::env::vars() {
+                // No source span — the variables come from the environment of the process
+                engine_state.add_env_var(name, Value::string(value, Span::unknown()));
+            }
+        }
+
+        let cwd = match self.cwd {
+            Some(cwd) => cwd,
+            None => std::env::current_dir().map_err(|err| {
+                ShellError::Generic(GenericError::new_internal(
+                    "Could not get the current directory",
+                    err.to_string(),
+                ))
+            })?,
+        };
+        // No source span — the directory is given by the embedding application
+        engine_state.add_env_var(
+            "PWD".into(),
+            Value::string(cwd.to_string_lossy(), Span::unknown()),
+        );
+        for (name, value) in self.env {
+            engine_state.add_env_var(name, value);
+        }
+
+        if let Some(interrupt) = self.interrupt {
+            engine_state.set_signals(Signals::new(interrupt));
+        }
+        engine_state.generate_nu_constant();
+
+        if self.std_lib {
+            nu_std::load_standard_library(&mut engine_state).map_err(|err| {
+                ShellError::Generic(GenericError::new_internal(
+                    "Could not load the standard library",
+                    err.to_string(),
+                ))
+            })?;
+        }
+
+        Ok(Nushell {
+            engine_state,
+            stack: Stack::new(),
+            evaluations: 0,
+        })
+    }
+}
+
+/// An embedded Nushell engine, evaluating code from Rust.
+///
+/// Definitions, variables and changes to the environment persist from one evaluation to the
+/// next, as they do in the REPL.
+pub struct Nushell {
+    engine_state: EngineState,
+    stack: Stack,
+    /// The number of evaluations so far, to give each snippet its own file name in errors
+    evaluations: usize,
+}
+
+impl Nushell {
+    /// Creates an engine with the default settings, see [`NushellBuilder`].
+    pub fn new() -> Result<Self, EmbedError> {
+        Self::builder().build()
+    }
+
+    /// Configures an engine before creating it.
+    pub fn builder() -> NushellBuilder {
+        NushellBuilder::default()
+    }
+
+    /// Evaluates `source`, returning the value of its last pipeline.
+    pub fn eval(&mut self, source: &str) -> Result<Value, EmbedError> {
+        self.eval_pipeline(source, PipelineData::empty())
+    }
+
+    /// Evaluates `source` with `input` piped into it, as in `$input | source`.
+    pub fn eval_with_input(
+        &mut self,
+        source: &str,
+        input: impl IntoValue,
+    ) -> Result<Value, EmbedError> {
+        // No source span — the input comes from the embedding application
+        let input = input.into_value(Span::unknown());
+        self.eval_pipeline(source, PipelineData::value(input, None))
+    }
+
+    /// Evaluates `source` and converts its value into a Rust type.
+    pub fn eval_into<T: FromValue>(&mut self, source: &str) -> Result<T, EmbedError> {
+        Ok(T::from_value(self.eval(source)?)?)
+    }
+
+    /// Evaluates `source` with `input` piped into it, and converts its value into a Rust type.
+    pub fn eval_into_with_input<T: FromValue>(
+        &mut self,
+        source: &str,
+        input: impl IntoValue,
+    ) -> Result<T, EmbedError> {
+        Ok(T::from_value(self.eval_with_input(source, input)?)?)
+    }
+
+    /// Evaluates `source` with any pipeline as its input, like a stream of bytes.
+    pub fn eval_pipeline(
+        &mut self,
+        source: &str,
+        input: PipelineData,
+    ) -> Result<Value, EmbedError> {
+        let (delta, block) = self.parse_and_compile(source)?;
+        self.engine_state.merge_delta(delta)?;
+
+        let output =
+            eval_block::<WithoutDebug>(&self.engine_state, &mut self.stack, &block, input)?;
+        // No source span — the value is handed back to the embedding application
+        let value = output.body.into_value(Span::unknown())?;
+        if let Value::Error { error, .. } = value {
+            return Err((*error).into());
+        }
+        Ok(value)
+    }
+
+    fn parse_and_compile(&mut self, source: &str) -> Result<(StateDelta, Arc<Block>), EmbedError> {
+        self.evaluations += 1;
+        let name = format!("embedded-{}", self.evaluations);
+
+        let mut working_set = StateWorkingSet::new(&self.engine_state);
+        let block = nu_parser::parse(&mut working_set, Some(&name), source.as_bytes(), false);
+        if let Some(err) = working_set.parse_errors.into_iter().next() {
+            return Err(err.into());
+        }
+        if let Some(err) = working_set.compile_errors.into_iter().next() {
+            return Err(err.into());
+        }
+        Ok((working_set.delta, block))
+    }
+
+    /// Sets an environment variable, as `$env.name = value` does.
+    pub fn set_env_var(&mut self, name: impl Into<String>, value: impl IntoValue) {
+        // No source span — the value comes from the embedding application
+        self.stack
+            .add_env_var(name.into(), value.into_value(Span::unknown()));
+    }
+
+    /// The value of an environment variable, as in `$env.name`.
+    pub fn env_var(&self, name: &str) -> Option<&Value> {
+        self.stack.get_env_var(&self.engine_state, name)
+    }
+
+    /// Renders an error like the shell does, with the code it points to.
+    pub fn format_error(&self, error: &EmbedError) -> String {
+        let working_set = StateWorkingSet::new(&self.engine_state);
+        format_cli_error(Some(&self.stack), &working_set, error, None)
+    }
+
+    /// The engine state, for what this facade doesn't cover, like adding custom commands.
+    ///
+    /// Its API is not covered by the stability of this crate.
+    pub fn engine_state_mut(&mut self) -> &mut EngineState {
+        &mut self.engine_state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn nushell() -> Nushell {
+        Nushell::builder()
+            .std_lib(false)
+            .inherit_env(false)
+            .build()
+            .expect("engine should build")
+    }
+
+    #[test]
+    fn definitions_persist() -> Result<(), EmbedError> {
+        let mut nu = nushell();
+        nu.eval("def greet [name: string] { $'hello ($name)' }")?;
+        nu.eval("let answer = 42")?;
+        assert_eq!(nu.eval_into::<String>("greet nu")?, "hello nu");
+        assert_eq!(nu.eval_into::<i64>("$answer")?, 42);
+        Ok(())
+    }
+
+    #[test]
+    fn input_is_piped_in() -> Result<(), EmbedError> {
+        let mut nu = nushell();
+        let sum: i64 = nu.eval_into_with_input("math sum", vec![1, 2, 3])?;
+        assert_eq!(sum, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn environment_is_shared() -> Result<(), EmbedError> {
+        let mut nu = nushell();
+        nu.set_env_var("GREETING", "hi");
+        assert_eq!(nu.eval_into::<String>("$env.GREETING")?, "hi");
+        nu.eval("$env.GREETING = 'bye'")?;
+        assert_eq!(
+            nu.env_var("GREETING").and_then(|value| value.as_str().ok()),
+            Some("bye")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let mut nu = nushell();
+        assert!(matches!(nu.eval("let"), Err(EmbedError::Parse(_))));
+        let err = nu
+            .eval("error make {msg: boom}")
+            .expect_err("code should fail");
+        assert!(matches!(err, EmbedError::Shell(_)));
+        assert!(nu.format_error(&err).contains("boom"));
+    }
+}
@@ -296,6 +296,18 @@ fn get_command_documentation(
         .expect("writing to a String is infallible");
     }
 
+    let deprecations = command.deprecation_info();
+    if !deprecations.is_empty() {
+        write!(long_desc, "{help_section_name}Deprecated{RESET}:\n")
+            .expect("writing to a String is infallible");
+        for deprecation in deprecations {
+            for line in deprecation.describe(cmd_name).lines() {
+                writeln!(long_desc, "  {line}").expect("writing to a String is infallible");
+            }
+        }
+        long_desc.push('\n');
+    }
+
     write!(
         long_desc,
         "{help_section_name}Usage{RESET}:\n  > {}\n",
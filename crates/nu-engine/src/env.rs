@@ -1,9 +1,9 @@
 use crate::ClosureEvalOnce;
 use nu_path::absolute_with;
 use nu_protocol::{
-    ShellError, Span, Type, Value, VarId,
+    PipelineData, ShellError, Span, Type, Value, VarId,
     ast::Expr,
-    engine::{Call, EngineState, EnvName, Stack},
+    engine::{Call, Closure, EngineState, EnvName, Stack},
     shell_error::generic::GenericError,
 };
 use std::{
@@ -29,7 +29,55 @@ impl From<ShellError> for ConversionError {
     }
 }
 
-/// Translate environment variables from Strings to Values.
+/// Run the closures registered with `env watch` whose variable has a new value since they last
+/// ran, with the old and the new value as arguments.
+///
+/// Changes the closures make to the environment are kept, like for `def --env` commands. A
+/// closure that's running doesn't run again for the changes it makes, and its variable is
+/// considered seen once it finishes.
+pub fn run_env_watchers(engine_state: &EngineState, stack: &mut Stack) -> Result<(), ShellError> {
+    stack.env_watch_pending = false;
+
+    for index in 0..stack.env_watchers.len() {
+        let watcher = &stack.env_watchers[index];
+        let name = watcher.name.clone();
+        let value = stack.get_env_var(engine_state, name.as_str()).cloned();
+        if watcher.running || value == watcher.last_value {
+            continue;
+        }
+
+        let watcher = &mut Arc::make_mut(&mut stack.env_watchers)[index];
+        let before = std::mem::replace(&mut watcher.last_value, value.clone());
+        watcher.running = true;
+        let closure = watcher.closure.clone();
+
+        let span = closure_span(engine_state, &closure);
+        let before = before.unwrap_or_else(|| Value::nothing(span));
+        let after = value.unwrap_or_else(|| Value::nothing(span));
+        let result = ClosureEvalOnce::new_env_preserve_out_dest(engine_state, stack, closure)
+            .add_args(vec![before, after])
+            .and_then(|closure| closure.run_with_input(PipelineData::empty()))
+            .and_then(|output| output.drain());
+
+        // the environment of the closure brought the watchers back, still marked as running
+        let value = stack.get_env_var(engine_state, name.as_str()).cloned();
+        if let Some(watcher) = Arc::make_mut(&mut stack.env_watchers).get_mut(index) {
+            watcher.running = false;
+            watcher.last_value = value;
+        }
+        result?;
+    }
+
+    Ok(())
+}
+
+fn closure_span(engine_state: &EngineState, closure: &Closure) -> Span {
+    engine_state
+        .get_block(closure.block_id)
+        .span
+        .unwrap_or(Span::unknown())
+}
+
 pub fn convert_env_vars(
     stack: &mut Stack,
     engine_state: &EngineState,
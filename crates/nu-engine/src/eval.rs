@@ -385,6 +385,11 @@ pub fn redirect_env(engine_state: &EngineState, caller_stack: &mut Stack, callee
 
     // set config to callee config, to capture any updates to that
     caller_stack.config.clone_from(&callee_stack.config);
+
+    // the callee ran the watchers for its changes, and may have added some
+    caller_stack
+        .env_watchers
+        .clone_from(&callee_stack.env_watchers);
 }
 
 fn eval_external(
@@ -680,13 +685,15 @@ impl Eval for EvalRuntime {
 
     fn regex_match(
         engine_state: &EngineState,
+        stack: &mut Stack,
         op_span: Span,
         lhs: &Value,
         rhs: &Value,
         invert: bool,
         expr_span: Span,
     ) -> Result<Value, ShellError> {
-        lhs.regex_match(engine_state, op_span, rhs, invert, expr_span)
+        let options = stack.get_config(engine_state).regex;
+        lhs.regex_match(engine_state, options, op_span, rhs, invert, expr_span)
     }
 
     fn eval_assignment<D: DebugContext>(
@@ -1,18 +1,24 @@
-use std::{borrow::Cow, fs::File, sync::Arc};
+use std::{
+    borrow::Cow,
+    fs::File,
+    sync::{Arc, mpsc},
+    thread,
+};
 
 use nu_path::{dots::expand_ndots_safe, expand_path, expand_path_with, expand_tilde};
 #[cfg(feature = "os")]
 use nu_protocol::process::check_exit_status_future;
 use nu_protocol::{
-    CompareTypes, DeclId, ENV_VARIABLE_ID, Flag, IntoPipelineData, IntoSpanned, LabeledError,
-    ListStream, OutDest, PipelineData, PipelineExecutionData, PositionalArg, Range, Record, RegId,
-    ShellError, Signals, Signature, Span, Spanned, Type, Value, VarId,
+    CompareTypes, CustomValue, DeclId, ENV_VARIABLE_ID, Flag, IntoPipelineData, IntoSpanned,
+    LabeledError, ListStream, OutDest, PipelineData, PipelineExecutionData, PositionalArg, Range,
+    Record, RegId, ShellError, Signals, Signature, Span, Spanned, Type, Value, VarId,
     ast::{Bits, Block, Boolean, CellPath, Comparison, Math, Operator},
     combined_type_string,
-    debugger::DebugContext,
+    debugger::{DebugContext, WithoutDebug},
+    did_you_mean,
     engine::{
-        Argument, Closure, EngineState, EnvName, ErrorHandler, Matcher, Redirection, Stack,
-        StateWorkingSet,
+        Argument, Closure, Command, EngineState, EnvName, ErrorHandler, Matcher, Redirection,
+        Stack, StackWithInvocation, StateWorkingSet,
     },
     ir::{Call, DataSlice, Instruction, IrAstRef, IrBlock, Literal, RedirectMode},
     shell_error::{generic::GenericError, io::IoError},
@@ -20,7 +26,8 @@ use nu_protocol::{
 use nu_utils::IgnoreCaseExt;
 
 use crate::{
-    ENV_CONVERSIONS, convert_env_vars, eval::is_automatic_env_var, eval_block_with_early_return,
+    ClosureEvalOnce, ENV_CONVERSIONS, convert_env_vars, eval::is_automatic_env_var,
+    eval_block_with_early_return, run_env_watchers,
 };
 
 /// For `def --wrapped` and `known extern` rest params (`SyntaxShape::ExternalArgument`), convert
@@ -254,7 +261,16 @@ fn eval_ir_block_impl<D: DebugContext>(
 
         D::enter_instruction(ctx.engine_state, ir_block, pc, ctx.registers);
 
-        let result = eval_instruction::<D>(ctx, instruction, span, ast, need_backtrace);
+        let mut result = eval_instruction::<D>(ctx, instruction, span, ast, need_backtrace);
+
+        // Watchers run as part of the instruction that changed their variable, so that `try`
+        // around it catches their errors
+        if result.is_ok()
+            && ctx.stack.env_watch_pending
+            && let Err(err) = run_env_watchers(ctx.engine_state, ctx.stack)
+        {
+            result = Err(err);
+        }
 
         D::leave_instruction(
             ctx.engine_state,
@@ -1229,10 +1245,12 @@ fn binary_op(
             Comparison::LessThanOrEqual => lhs_val.lte(op_span, &rhs_val, span)?,
             Comparison::GreaterThanOrEqual => lhs_val.gte(op_span, &rhs_val, span)?,
             Comparison::RegexMatch => {
-                lhs_val.regex_match(ctx.engine_state, op_span, &rhs_val, false, span)?
+                let options = ctx.stack.get_config(ctx.engine_state).regex;
+                lhs_val.regex_match(ctx.engine_state, options, op_span, &rhs_val, false, span)?
             }
             Comparison::NotRegexMatch => {
-                lhs_val.regex_match(ctx.engine_state, op_span, &rhs_val, true, span)?
+                let options = ctx.stack.get_config(ctx.engine_state).regex;
+                lhs_val.regex_match(ctx.engine_state, options, op_span, &rhs_val, true, span)?
             }
             Comparison::In => lhs_val.r#in(op_span, &rhs_val, span)?,
             Comparison::NotIn => lhs_val.not_in(op_span, &rhs_val, span)?,
@@ -1297,7 +1315,6 @@ fn eval_call<D: DebugContext>(
         ..
     } = ctx;
 
-    let args_len = caller_stack.arguments.get_len(*args_base);
     let decl = engine_state.get_decl(decl_id);
     // Commands such as `ignore --stderr` need errors as pipeline values so they can decide
     // whether to suppress or rethrow.
@@ -1310,11 +1327,28 @@ fn eval_call<D: DebugContext>(
     let mut caller_stack = caller_stack.push_redirection(redirect_out.take(), redirect_err.take());
 
     let result = (|| {
+        if let Some(closure) = caller_stack.decl_overrides.get(&decl_id).cloned() {
+            let args_len = caller_stack.arguments.get_len(*args_base);
+            return run_override(
+                engine_state,
+                &mut caller_stack,
+                decl_id,
+                closure,
+                *args_base,
+                args_len,
+                input,
+            );
+        }
+
+        spread_records_as_flags(&mut caller_stack, *args_base, decl)?;
+        let args_len = caller_stack.arguments.get_len(*args_base);
+
         if let Some(block_id) = decl.block_id() {
             // If the decl is a custom command
             let block = engine_state.get_block(block_id);
 
             // check types after acquiring block to avoid unnecessarily cloning Signature
+            input = resolve_transparent_input(input, &block.signature)?;
             check_input_types(&input, &block.signature, head)?;
 
             // Set up a callee stack with the captures and move arguments from the stack into variables
@@ -1341,6 +1375,10 @@ fn eval_call<D: DebugContext>(
             // recoverable in Rust.
             callee_stack.recursion_count += 1;
 
+            if block.generator {
+                return run_generator(engine_state, callee_stack, block.clone(), input, head);
+            }
+
             let result =
                 eval_block_with_early_return::<D>(engine_state, &mut callee_stack, block, input)
                     .map(|p| p.body);
@@ -1359,9 +1397,15 @@ fn eval_call<D: DebugContext>(
                 && engine_state
                     .find_decl(b"ignore", &[])
                     .is_some_and(|ignore_decl_id| ignore_decl_id == decl_id);
+            let signature = decl.signature();
             if !allow_error_input {
-                check_input_types(&input, &decl.signature(), head)?;
+                input = resolve_transparent_input(input, &signature)?;
+                check_input_types(&input, &signature, head)?;
             }
+            resolve_transparent_arguments(
+                caller_stack.arguments.get_args_mut(*args_base, args_len),
+                &signature,
+            )?;
             // FIXME: precalculate this and save it somewhere
             let span = Span::merge_many(
                 std::iter::once(head).chain(
@@ -1404,6 +1448,162 @@ fn eval_call<D: DebugContext>(
     }
 }
 
+/// Run the closure that replaces a command in this scope, set by `with-override`, with the
+/// positional arguments of the call. Flags aren't passed on, as closures can't take them.
+fn run_override(
+    engine_state: &EngineState,
+    caller_stack: &mut Stack,
+    decl_id: DeclId,
+    closure: Closure,
+    args_base: usize,
+    args_len: usize,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let mut args = vec![];
+    for arg in caller_stack.arguments.get_args(args_base, args_len) {
+        match arg {
+            Argument::Positional { val, .. } => args.push(val.clone()),
+            Argument::Spread { vals, .. } => args.extend(vals.as_list()?.iter().cloned()),
+            _ => {}
+        }
+    }
+
+    // The closure may call the command it replaces, which must then run for real
+    let overrides = caller_stack.decl_overrides.clone();
+    Arc::make_mut(&mut caller_stack.decl_overrides).remove(&decl_id);
+    let result = ClosureEvalOnce::new_preserve_out_dest(engine_state, caller_stack, closure)
+        .add_args(args)
+        .and_then(|closure| closure.run_with_input(input));
+    caller_stack.decl_overrides = overrides;
+    result
+}
+
+/// Run the body of a command defined with `def --generator` on its own thread, streaming the
+/// values it yields. The channel has no buffer, so the body only runs ahead of the consumer by one
+/// value, and stops at its next `yield` once the stream is dropped.
+fn run_generator(
+    engine_state: &EngineState,
+    callee_stack: StackWithInvocation,
+    block: Arc<Block>,
+    input: PipelineData,
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    let (sender, receiver) = mpsc::sync_channel(0);
+    let mut callee_stack = callee_stack;
+    callee_stack.yield_to = Some(sender.clone());
+    let thread_engine_state = engine_state.clone();
+    thread::Builder::new()
+        .name("generator".into())
+        .spawn(move || {
+            let engine_state = thread_engine_state;
+            let result = eval_block_with_early_return::<WithoutDebug>(
+                &engine_state,
+                &mut callee_stack,
+                &block,
+                input,
+            )
+            .and_then(|output| output.body.drain());
+            // The stream ends when the senders are dropped, after the error if there is one
+            drop(callee_stack);
+            if let Err(error) = result {
+                let _ = sender.send(Value::error(error, head));
+            }
+        })
+        .map_err(|err| {
+            ShellError::Io(IoError::new_with_additional_context(
+                err,
+                head,
+                None,
+                "Failed to spawn thread for generator",
+            ))
+        })?;
+
+    let stream = ListStream::new(receiver.into_iter(), head, engine_state.signals().clone());
+    Ok(PipelineData::list_stream(stream, None))
+}
+
+/// Turn the records spread into a call, like `foo ...$options`, into named arguments, so that
+/// `{force: true, depth: 2}` is passed as `--force --depth 2`. Keys set to `null` are left out, which
+/// lets wrappers forward the options they were given without checking each one.
+fn spread_records_as_flags(
+    stack: &mut Stack,
+    args_base: usize,
+    decl: &dyn Command,
+) -> Result<(), ShellError> {
+    let args_len = stack.arguments.get_len(args_base);
+    let spreads_record = stack
+        .arguments
+        .get_args(args_base, args_len)
+        .iter()
+        .any(|arg| {
+            matches!(
+                arg,
+                Argument::Spread {
+                    vals: Value::Record { .. },
+                    ..
+                }
+            )
+        });
+    if !spreads_record {
+        return Ok(());
+    }
+
+    let signature = decl.signature();
+    let args: Vec<Argument> = stack.arguments.drain_args(args_base, args_len).collect();
+    for arg in args {
+        let Argument::Spread {
+            vals: Value::Record { val: record, .. },
+            span,
+            ..
+        } = arg
+        else {
+            stack.arguments.push(arg);
+            continue;
+        };
+
+        for (key, val) in record.into_owned() {
+            if val.is_nothing() {
+                continue;
+            }
+            let Some(flag) = signature.get_long_flag(&key) else {
+                let mut error = GenericError::new(
+                    "Unknown flag",
+                    format!("`{}` has no flag `--{key}`", signature.name),
+                    span,
+                );
+                let flags = signature.named.iter().map(|flag| flag.long.as_str());
+                if let Some(suggestion) = did_you_mean(flags, &key) {
+                    error = error.with_help(format!("Did you mean `--{suggestion}`?"));
+                }
+                return Err(ShellError::Generic(error));
+            };
+            if flag.arg.is_none() && !matches!(val, Value::Bool { .. }) {
+                return Err(ShellError::CantConvert {
+                    to_type: "bool".into(),
+                    from_type: val.get_type().to_string(),
+                    span: val.span(),
+                    help: Some(format!("`--{key}` is a switch")),
+                });
+            }
+            let data: Arc<[u8]> = key.into_bytes().into();
+            let name = DataSlice {
+                start: 0,
+                // Flag names are short
+                len: data.len() as u32,
+            };
+            stack.arguments.push(Argument::Named {
+                data,
+                name,
+                short: DataSlice::empty(),
+                span,
+                val,
+                ast: None,
+            });
+        }
+    }
+    Ok(())
+}
+
 fn find_named_var_id(
     sig: &Signature,
     name: &[u8],
@@ -1496,10 +1696,11 @@ fn gather_arguments(
                 let next = (!always_spread).then(|| positional_iter.next()).flatten();
                 if let Some((positional_arg, required)) = next {
                     let var_id = expect_positional_var_id(positional_arg, span)?;
+                    // By checking the type of the bound variable rather than converting the
+                    // SyntaxShape here, we might be able to save some allocations and effort
+                    let variable = engine_state.get_var(var_id);
+                    let val = resolve_transparent(val, &variable.ty)?;
                     if required {
-                        // By checking the type of the bound variable rather than converting the
-                        // SyntaxShape here, we might be able to save some allocations and effort
-                        let variable = engine_state.get_var(var_id);
                         check_type(&val, &variable.ty)?;
                     }
                     callee_stack.add_var(var_id, val);
@@ -1548,6 +1749,7 @@ fn gather_arguments(
                 ..
             } => {
                 let var_id = find_named_var_id(&block.signature, &data[name], &data[short], span)?;
+                let val = resolve_transparent(val, &engine_state.get_var(var_id).ty)?;
                 callee_stack.add_var(var_id, val)
             }
             Argument::ParserInfo { .. } => (),
@@ -1594,6 +1796,112 @@ fn gather_arguments(
     Ok(())
 }
 
+/// The value a transparent custom value, like a `lazy` one, stands for, unless `ty` takes the
+/// custom value itself.
+fn resolve_transparent(val: Value, ty: &Type) -> Result<Value, ShellError> {
+    match &val {
+        Value::Custom {
+            val: custom,
+            internal_span,
+        } if custom.is_transparent() && !takes_custom_value(ty, custom.as_ref()) => {
+            custom.to_base_value(*internal_span)
+        }
+        _ => Ok(val),
+    }
+}
+
+fn takes_custom_value(ty: &Type, custom: &dyn CustomValue) -> bool {
+    match ty {
+        Type::Any => true,
+        Type::Custom(name) => **name == custom.type_name(),
+        Type::OneOf(types) => types.iter().any(|ty| takes_custom_value(ty, custom)),
+        _ => false,
+    }
+}
+
+/// Resolve a transparent custom value in the input if no input type of the command takes it.
+/// Commands that take no input don't get it resolved.
+fn resolve_transparent_input(
+    input: PipelineData,
+    signature: &Signature,
+) -> Result<PipelineData, ShellError> {
+    let io_types = &signature.input_output_types;
+    match input {
+        PipelineData::Value(
+            Value::Custom {
+                ref val,
+                internal_span,
+            },
+            ref metadata,
+        ) if val.is_transparent()
+            && io_types.iter().any(|(ty, _)| *ty != Type::Nothing)
+            && !io_types
+                .iter()
+                .any(|(ty, _)| takes_custom_value(ty, val.as_ref())) =>
+        {
+            Ok(PipelineData::Value(
+                val.to_base_value(internal_span)?,
+                metadata.clone(),
+            ))
+        }
+        input => Ok(input),
+    }
+}
+
+/// Resolve transparent custom values in the arguments of a built-in command, where the parameter
+/// doesn't take them.
+fn resolve_transparent_arguments(
+    args: &mut [Argument],
+    signature: &Signature,
+) -> Result<(), ShellError> {
+    let is_transparent =
+        |val: &Value| matches!(val, Value::Custom { val, .. } if val.is_transparent());
+
+    let mut position = 0;
+    for arg in args {
+        let (val, shape) = match arg {
+            Argument::Positional { val, .. } => {
+                position += 1;
+                if !is_transparent(val) {
+                    continue;
+                }
+                let shape = signature
+                    .get_positional(position - 1)
+                    .map(|arg| arg.shape.clone());
+                (val, shape)
+            }
+            Argument::Named {
+                data,
+                name,
+                short,
+                val,
+                ..
+            } => {
+                if !is_transparent(val) {
+                    continue;
+                }
+                let flag = std::str::from_utf8(&data[*name])
+                    .ok()
+                    .and_then(|name| signature.get_long_flag(name))
+                    .or_else(|| {
+                        std::str::from_utf8(&data[*short])
+                            .ok()
+                            .and_then(|short| short.chars().next())
+                            .and_then(|short| signature.get_short_flag(short))
+                    });
+                (val, flag.and_then(|flag| flag.arg))
+            }
+            _ => continue,
+        };
+        if let Some(shape) = shape {
+            let span = val.span();
+            let custom = std::mem::replace(val, Value::nothing(span));
+            *val = resolve_transparent(custom, &shape.to_type())?;
+        }
+    }
+    Ok(())
+}
+
 /// Type check helper. Produces `CantConvert` error if `val` is not compatible with `ty`.
 fn check_type(val: &Value, ty: &Type) -> Result<(), ShellError> {
     match val {
@@ -11,6 +11,7 @@ mod eval_helpers;
 mod eval_ir;
 pub mod exit;
 mod glob_from;
+mod path_executables;
 pub mod scope;
 
 #[cfg(test)]
@@ -33,4 +34,5 @@ pub use eval::{
 pub use eval_helpers::*;
 pub use eval_ir::eval_ir_block;
 pub use glob_from::glob_from;
+pub use path_executables::{executables_in, path_executables};
 pub use scope::find_builtin_decl;
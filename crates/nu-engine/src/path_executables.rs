@@ -0,0 +1,54 @@
+use crate::env::path_str;
+use nu_protocol::{
+    Span,
+    engine::{EngineState, PathExecutable, Stack},
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The executables in the directories of `$env.PATH`, in the order they're looked up.
+///
+/// The listing is kept in the engine's [`ExternalsCache`](nu_protocol::engine::ExternalsCache)
+/// until `PATH` or one of its directories changes.
+pub fn path_executables(engine_state: &EngineState, stack: &Stack) -> Arc<[PathExecutable]> {
+    let dirs = path_str(engine_state, stack, Span::unknown())
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    executables_in(engine_state, dirs)
+}
+
+/// The executables in `dirs`, using the engine's cache like [`path_executables`].
+pub fn executables_in(engine_state: &EngineState, dirs: Vec<PathBuf>) -> Arc<[PathExecutable]> {
+    engine_state
+        .externals_cache
+        .get_or_list(dirs, list_executables)
+}
+
+fn list_executables(dirs: &[PathBuf]) -> Vec<PathExecutable> {
+    dirs.iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let path = entry.path();
+            is_executable_command(&path).then_some(PathExecutable { name, path })
+        })
+        .collect()
+}
+
+fn is_executable_command(path: &Path) -> bool {
+    if is_executable::is_executable(path) {
+        return true;
+    }
+
+    if cfg!(windows)
+        && let Some(ext) = path.extension()
+    {
+        return ext.eq_ignore_ascii_case("ps1") && path.is_file();
+    }
+
+    false
+}
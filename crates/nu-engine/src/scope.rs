@@ -1,5 +1,5 @@
 use nu_protocol::{
-    CommandWideCompleter, DeclId, ModuleId, Signature, Span, Type, Value, VarId,
+    CommandWideCompleter, DeclId, Module, ModuleId, Signature, Span, Type, Value, VarId,
     ast::Expr,
     engine::{Command, CommandType, EngineState, Stack, Visibility},
     record,
@@ -534,12 +534,60 @@ impl<'e, 's> ScopeData<'e, 's> {
                 "description" => Value::string(module_desc, span),
                 "extra_description" => Value::string(module_extra_desc, span),
                 "module_id" => Value::int(module_id.get() as i64, span),
-                "file" => Value::string(module.file.clone().map_or("unknown".to_string(), |(p, _)| p.path().to_string_lossy().to_string()), span),
+                "file" => Value::string(module_file(module), span),
             },
             span,
         )
     }
 
+    /// One row per module with the modules it uses and defines, which makes up the graph of
+    /// module dependencies.
+    pub fn collect_module_graph(&self, span: Span) -> Vec<Value> {
+        let module_ref = |module_id: ModuleId| {
+            let module = self.engine_state.get_module(module_id);
+            Value::record(
+                record! {
+                    "name" => Value::string(String::from_utf8_lossy(&module.name), span),
+                    "module_id" => Value::int(module_id.get() as i64, span),
+                },
+                span,
+            )
+        };
+
+        // The first module is the one behind the default overlay, not one that was defined
+        (1..self.engine_state.num_modules())
+            .map(|index| {
+                let module_id = ModuleId::new(index);
+                let module = self.engine_state.get_module(module_id);
+
+                let mut imported: Vec<ModuleId> = vec![];
+                for id in &module.imported_modules {
+                    if *id != module_id && !imported.contains(id) {
+                        imported.push(*id);
+                    }
+                }
+                let imports = imported.into_iter().map(module_ref).collect();
+                let submodules = module
+                    .submodules
+                    .values()
+                    .copied()
+                    .map(module_ref)
+                    .collect();
+
+                Value::record(
+                    record! {
+                        "name" => Value::string(String::from_utf8_lossy(&module.name), span),
+                        "module_id" => Value::int(module_id.get() as i64, span),
+                        "file" => Value::string(module_file(module), span),
+                        "imports" => Value::list(imports, span),
+                        "submodules" => Value::list(submodules, span),
+                    },
+                    span,
+                )
+            })
+            .collect()
+    }
+
     pub fn collect_modules(&self, span: Span) -> Vec<Value> {
         let mut modules = vec![];
 
@@ -573,6 +621,15 @@ impl<'e, 's> ScopeData<'e, 's> {
     }
 }
 
+fn module_file(module: &Module) -> String {
+    module
+        .file
+        .as_ref()
+        .map_or("unknown".to_string(), |(path, _)| {
+            path.path().to_string_lossy().to_string()
+        })
+}
+
 fn sort_rows(decls: &mut [Value]) {
     decls.sort_by(|a, b| match (a, b) {
         (Value::Record { val: rec_a, .. }, Value::Record { val: rec_b, .. }) => {
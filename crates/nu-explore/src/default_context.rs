@@ -1,6 +1,7 @@
 use crate::explore::Explore;
 use crate::explore_config::ExploreConfigCommand;
 use crate::explore_regex::ExploreRegex;
+use crate::ui::{Pick, Ui, UiForm, UiPanel, UiSelect};
 use nu_protocol::engine::{EngineState, StateWorkingSet};
 
 pub fn add_explore_context(mut engine_state: EngineState) -> EngineState {
@@ -9,6 +10,11 @@ pub fn add_explore_context(mut engine_state: EngineState) -> EngineState {
         working_set.add_decl(Box::new(Explore));
         working_set.add_decl(Box::new(ExploreRegex));
         working_set.add_decl(Box::new(ExploreConfigCommand));
+        working_set.add_decl(Box::new(Ui));
+        working_set.add_decl(Box::new(UiForm));
+        working_set.add_decl(Box::new(UiPanel));
+        working_set.add_decl(Box::new(UiSelect));
+        working_set.add_decl(Box::new(Pick));
         working_set.render()
     };
 
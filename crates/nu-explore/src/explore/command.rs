@@ -6,8 +6,9 @@ use crate::explore::pager::PagerConfig;
 use crate::explore::run_pager;
 use nu_ansi_term::Style;
 use nu_color_config::StyleComputer;
-use nu_engine::command_prelude::*;
-use nu_protocol::shell_error::generic::GenericError;
+use nu_engine::{command_prelude::*, get_eval_block};
+use nu_protocol::{DataSource, DeclId, ast, shell_error::generic::GenericError};
+use std::path::{Path, PathBuf};
 
 /// A `less` like program to render a [`Value`] as a table.
 #[derive(Clone)]
@@ -37,7 +38,7 @@ impl Command for Explore {
             .switch("index", "Show row indexes when viewing a list.", Some('i'))
             .switch(
                 "tail",
-                "Start with the viewport scrolled to the bottom.",
+                "Start with the viewport scrolled to the bottom, following streamed input.",
                 Some('t'),
             )
             .switch(
@@ -45,11 +46,20 @@ impl Command for Explore {
                 "When quitting, output the value of the cell the cursor was on.",
                 Some('p'),
             )
+            .switch(
+                "in-place",
+                "When quitting, save the edits back to the file the input was opened from.",
+                None,
+            )
             .category(Category::Viewers)
     }
 
     fn extra_description(&self) -> &str {
-        "Press `:` then `h` to get a help menu."
+        "Press `:` then `h` to get a help menu.
+
+Streamed input is shown as it arrives. Press `F` to follow new rows.
+
+In cursor mode, press `c` to edit the selected cell: strings are edited as is, other values as nuon. When quitting without `--peek`, the input is output with its edits, or nothing if it wasn't edited."
     }
 
     fn run(
@@ -63,6 +73,36 @@ impl Command for Explore {
         let show_index: bool = call.has_flag(engine_state, stack, "index")?;
         let tail: bool = call.has_flag(engine_state, stack, "tail")?;
         let peek_value: bool = call.has_flag(engine_state, stack, "peek")?;
+        let in_place: bool = call.has_flag(engine_state, stack, "in-place")?;
+
+        let source_file = if in_place {
+            if peek_value {
+                return Err(ShellError::IncompatibleParametersSingle {
+                    msg: "--in-place can't be used with --peek".into(),
+                    span: call.head,
+                });
+            }
+            match input.metadata().map(|metadata| metadata.data_source) {
+                // Checked before exploring, so the edits aren't lost to a file that can't be saved
+                Some(DataSource::FilePath(path)) => {
+                    let converter = find_converter(engine_state, &path, call.head)?;
+                    let is_list = matches!(
+                        input,
+                        PipelineData::Value(Value::List { .. }, ..) | PipelineData::ListStream(..)
+                    );
+                    Some((path, converter, is_list))
+                }
+                _ => {
+                    return Err(ShellError::Generic(GenericError::new(
+                        "Can't save the edits in place",
+                        "the input wasn't opened from a file",
+                        call.head,
+                    )));
+                }
+            }
+        } else {
+            None
+        };
 
         let nu_config = stack.get_config(engine_state);
         let style_computer = StyleComputer::from_config(engine_state, stack);
@@ -90,7 +130,20 @@ impl Command for Explore {
         let result = run_pager(engine_state, &mut stack.clone(), input, config);
 
         match result {
-            Ok(Some(value)) => Ok(PipelineData::value(value, None)),
+            Ok(Some(value)) => match source_file {
+                Some((path, converter, is_list)) => {
+                    // A record or a single value is edited as a table with one row
+                    let value = match value {
+                        Value::List { vals, .. } if !is_list && vals.len() == 1 => {
+                            vals.into_iter().next().unwrap_or_default()
+                        }
+                        value => value,
+                    };
+                    save_in_place(engine_state, stack, &path, converter, value, call.head)?;
+                    Ok(PipelineData::value(Value::default(), None))
+                }
+                None => Ok(PipelineData::value(value, None)),
+            },
             Ok(None) => Ok(PipelineData::value(Value::default(), None)),
             Err(err) => {
                 let shell_error = match err.downcast::<ShellError>() {
@@ -128,10 +181,83 @@ impl Command for Explore {
                 example: "open file.json | explore --peek | to json | save part.json",
                 result: None,
             },
+            Example {
+                description: "Follow the records appended to a log file",
+                example: "tail -f log.jsonl | from json --objects | explore --tail",
+                result: None,
+            },
+            Example {
+                description: "Edit the cells of a TOML file and save the changes back to it",
+                example: "open Cargo.toml | explore --in-place",
+                result: None,
+            },
         ]
     }
 }
 
+/// Find the `to` command converting to the format of `path`, from its extension.
+fn find_converter(
+    engine_state: &EngineState,
+    path: &Path,
+    span: Span,
+) -> Result<DeclId, ShellError> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    engine_state
+        .find_decl(format!("to {extension}").as_bytes(), &[])
+        .filter(|_| !extension.is_empty())
+        .ok_or_else(|| {
+            ShellError::Generic(GenericError::new(
+                "Can't save the edits in place",
+                format!(
+                    "no `to` command converts to the format of {}",
+                    path.display()
+                ),
+                span,
+            ))
+        })
+}
+
+/// Write the edited input back to the file it was opened from, converted with `converter`.
+fn save_in_place(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    path: &Path,
+    converter: DeclId,
+    value: Value,
+    span: Span,
+) -> Result<(), ShellError> {
+    let decl = engine_state.get_decl(converter);
+    let input = value.into_pipeline_data();
+    let output = if let Some(block_id) = decl.block_id() {
+        let block = engine_state.get_block(block_id);
+        let eval_block = get_eval_block(engine_state);
+        eval_block(engine_state, stack, block, input)?.body
+    } else {
+        let call = ast::Call::new(span);
+        decl.run(engine_state, stack, &(&call).into(), input)?
+    };
+
+    let bytes = match output.into_value(span)? {
+        Value::String { val, .. } => val.into_bytes(),
+        Value::Binary { val, .. } => val.into_owned(),
+        Value::Error { error, .. } => return Err(*error),
+        other => {
+            return Err(ShellError::Generic(GenericError::new(
+                "Can't save the edits in place",
+                format!("`{}` output a {}", decl.name(), other.get_type()),
+                span,
+            )));
+        }
+    };
+
+    std::fs::write(path, bytes).map_err(|err| IoError::new(err, span, PathBuf::from(path)))?;
+    Ok(())
+}
+
 fn lookup_color(style_computer: &StyleComputer, key: &str) -> Style {
     style_computer.compute(key, &Value::nothing(Span::unknown()))
 }
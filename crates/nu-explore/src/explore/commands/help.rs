@@ -39,11 +39,20 @@ static HELP_MESSAGE: LazyLock<String> = LazyLock::new(|| {
     {}              Drill into a cell (select it)
     {}            Go back / exit current view
     {}        Page up / Page down
+    {}                  Follow new rows of streamed input
 
   {} Data Manipulation
 
     {}                  Transpose (flip rows and columns)
     {}                  Expand (show all nested data)
+    {}                  Edit the selected cell (Enter to save, Esc to cancel)
+
+  {} Columns
+
+    {}                  Pick the columns to show
+    {}                  Hide the selected column
+    {}              Move the selected column left / right
+    {}                  Pin the selected column to the left
 
   {} Commands {}
 
@@ -67,9 +76,16 @@ static HELP_MESSAGE: LazyLock<String> = LazyLock::new(|| {
         key.paint("Enter"),
         key.paint("Esc / q"),
         key.paint("PgUp / PgDn"),
+        key.paint("F"),
         section.paint("▸"),
         key.paint("t"),
         key.paint("e"),
+        key.paint("c"),
+        section.paint("▸"),
+        key.paint("C"),
+        key.paint("H"),
+        key.paint("< / >"),
+        key.paint("P"),
         section.paint("▸"),
         dim.paint("(type : then command)"),
         key.paint(":help"),
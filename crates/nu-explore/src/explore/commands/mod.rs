@@ -15,7 +15,7 @@ mod r#try;
 
 pub use expand::ExpandCmd;
 pub use help::HelpCmd;
-pub use nu::NuCmd;
+pub use nu::{NuCmd, NuView};
 pub use quit::QuitCmd;
 pub use table::TableCmd;
 pub use r#try::TryCmd;
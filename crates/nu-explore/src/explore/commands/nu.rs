@@ -7,18 +7,22 @@ use super::super::{
     },
     views::{Layout, Orientation, Preview, RecordView, View, ViewConfig},
 };
-use super::ViewCommand;
+use super::{HelpCmd, ViewCommand};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
-use nu_engine::get_columns;
 use nu_protocol::{
-    PipelineData, Value,
+    Config, ListStream, PipelineData, Value,
     engine::{EngineState, Stack},
 };
 use ratatui::layout::Rect;
-use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError, TrySendError};
 use std::thread::{self, JoinHandle};
 
+/// How many messages can wait for the UI to pick them up.
+const STREAM_BUFFER: usize = 16;
+/// How many rows are gathered while the UI is busy before the stream is paused for it.
+const MAX_PENDING_ROWS: usize = 10_000;
+
 #[derive(Debug, Default, Clone)]
 pub struct NuCmd {
     command: String,
@@ -67,7 +71,7 @@ impl ViewCommand for NuCmd {
         let explore_config = config.explore_config.clone();
 
         // Create channel for communicating results
-        let (sender, receiver) = mpsc::channel();
+        let (sender, receiver) = mpsc::sync_channel(STREAM_BUFFER);
 
         // Spawn background thread to run the command
         let handle = thread::spawn(move || {
@@ -81,27 +85,19 @@ impl ViewCommand for NuCmd {
             );
         });
 
-        Ok(NuView {
-            state: ViewState::Loading,
-            receiver: Some(receiver),
-            _handle: Some(handle),
-            command_text: self.command.clone(),
-            explore_config: config.explore_config.clone(),
-            frame_count: 0,
-            // Streaming state
-            columns: Vec::new(),
-            rows: Vec::new(),
-            is_record: false,
-            stream_done: false,
-            last_error: None,
-            last_row_count: 0,
-        })
+        Ok(NuView::new(
+            receiver,
+            handle,
+            self.command.clone(),
+            config.explore_config.clone(),
+            false,
+        ))
     }
 }
 
 /// Messages sent from the background thread to the UI
 enum StreamMessage {
-    /// Column names (sent once, when first determined)
+    /// Column names, sent again with the new ones added whenever a row brings some
     Columns(Vec<String>),
     /// A batch of rows
     Rows(Vec<Vec<Value>>),
@@ -122,7 +118,7 @@ fn stream_command(
     engine_state: &EngineState,
     stack: &mut Stack,
     _explore_config: &ExploreConfig,
-    sender: mpsc::Sender<StreamMessage>,
+    sender: SyncSender<StreamMessage>,
 ) {
     let pipeline = match run_command_with_value(command, value, engine_state, stack) {
         Ok(p) => p,
@@ -148,7 +144,7 @@ fn stream_command(
         }
         PipelineData::Value(Value::List { vals, .. }, ..) => {
             // List value - stream it
-            stream_values(vals.into_iter(), &sender);
+            stream_values(vals.into_iter(), &engine_state.config, &sender);
         }
         PipelineData::Value(Value::String { val, .. }, ..) => {
             // String - show as preview
@@ -161,7 +157,7 @@ fn stream_command(
         }
         PipelineData::ListStream(stream, ..) => {
             // Stream values as they arrive
-            stream_values(stream.into_iter(), &sender);
+            stream_values(stream.into_iter(), &engine_state.config, &sender);
         }
         PipelineData::ByteStream(stream, ..) => {
             // ByteStream - collect to string and show as preview
@@ -181,86 +177,103 @@ fn stream_command(
     }
 }
 
-/// Stream values from an iterator, sending rows in batches
-fn stream_values<I>(iter: I, sender: &mpsc::Sender<StreamMessage>)
+/// Stream values from an iterator, sending rows as soon as the UI can take them.
+///
+/// While the UI is busy, rows are gathered into a batch, and once the batch is full the
+/// iterator isn't pulled until the UI catches up. A stream of a single simple value is shown
+/// as a preview, like the same value given directly.
+fn stream_values<I>(iter: I, config: &Config, sender: &SyncSender<StreamMessage>)
 where
     I: Iterator<Item = Value>,
 {
-    const BATCH_SIZE: usize = 1;
-    const INITIAL_ROWS_FOR_COLUMNS: usize = 1;
-
-    let mut columns: Option<Vec<String>> = None;
-    let mut batch: Vec<Vec<Value>> = Vec::with_capacity(BATCH_SIZE);
-    let mut initial_values: Vec<Value> = Vec::new();
+    let mut columns: Vec<String> = Vec::new();
+    let mut batch: Vec<Vec<Value>> = Vec::new();
+    let mut first_value = None;
+    let mut count = 0usize;
 
     for value in iter {
-        if columns.is_none() {
-            // Buffer initial values to determine columns
-            initial_values.push(value);
-
-            if initial_values.len() >= INITIAL_ROWS_FOR_COLUMNS {
-                // Determine columns from buffered values
-                let cols = get_columns(&initial_values);
-                if !cols.is_empty() {
-                    let _ = sender.send(StreamMessage::Columns(cols.clone()));
-                }
-
-                // Convert buffered values to rows and send
-                let rows: Vec<Vec<Value>> = initial_values
-                    .drain(..)
-                    .map(|v| value_to_row(&cols, &v))
-                    .collect();
-
-                if sender.send(StreamMessage::Rows(rows)).is_err() {
-                    return; // Receiver dropped
-                }
+        count += 1;
+        if count == 1 {
+            first_value = Some(value.clone());
+        }
 
-                columns = Some(cols);
+        // The columns are those of every row seen so far, in the order they first appear
+        if add_columns(&mut columns, &value) {
+            for row in &mut batch {
+                row.resize(columns.len(), Value::default());
             }
-        } else if let Some(ref cols) = columns {
-            // We have columns, add to batch
-            batch.push(value_to_row(cols, &value));
-
-            if batch.len() >= BATCH_SIZE {
-                if sender
-                    .send(StreamMessage::Rows(std::mem::take(&mut batch)))
-                    .is_err()
-                {
-                    return; // Receiver dropped
-                }
-                batch = Vec::with_capacity(BATCH_SIZE);
+            if sender
+                .send(StreamMessage::Columns(columns.clone()))
+                .is_err()
+            {
+                return; // Receiver dropped
             }
         }
-    }
 
-    // Handle case where we never got enough values to determine columns
-    if columns.is_none() && !initial_values.is_empty() {
-        let cols = get_columns(&initial_values);
-        if !cols.is_empty() {
-            let _ = sender.send(StreamMessage::Columns(cols.clone()));
+        batch.push(value_to_row(&columns, &value));
+        if !send_rows(sender, &mut batch) {
+            return; // Receiver dropped
         }
+    }
 
-        let rows: Vec<Vec<Value>> = initial_values
-            .drain(..)
-            .map(|v| value_to_row(&cols, &v))
-            .collect();
+    if !batch.is_empty() && sender.send(StreamMessage::Rows(batch)).is_err() {
+        return; // Receiver dropped
+    }
+
+    match first_value {
+        Some(value)
+            if count == 1 && !matches!(value, Value::List { .. } | Value::Record { .. }) =>
+        {
+            let text = value.to_abbreviated_string(config);
+            let _ = sender.send(StreamMessage::SimpleValue(text));
+        }
+        _ => {
+            let _ = sender.send(StreamMessage::Done);
+        }
+    }
+}
 
-        let _ = sender.send(StreamMessage::Rows(rows));
+/// Add the columns of `value` missing from `columns`. Returns whether any was added.
+///
+/// Values that aren't records go in the first column, which is unnamed when they come first.
+fn add_columns(columns: &mut Vec<String>, value: &Value) -> bool {
+    let known = columns.len();
+    match value {
+        Value::Record { val, .. } => {
+            for col in val.columns() {
+                if !columns.contains(col) {
+                    columns.push(col.clone());
+                }
+            }
+        }
+        _ if columns.is_empty() => columns.push(String::new()),
+        _ => {}
     }
+    columns.len() > known
+}
 
-    // Send any remaining batch
-    if !batch.is_empty() {
-        let _ = sender.send(StreamMessage::Rows(batch));
+/// Send the pending rows without waiting for the UI, unless there are too many of them.
+/// Returns false when the UI is gone.
+fn send_rows(sender: &SyncSender<StreamMessage>, batch: &mut Vec<Vec<Value>>) -> bool {
+    let rows = std::mem::take(batch);
+    if rows.len() >= MAX_PENDING_ROWS {
+        return sender.send(StreamMessage::Rows(rows)).is_ok();
     }
 
-    let _ = sender.send(StreamMessage::Done);
+    match sender.try_send(StreamMessage::Rows(rows)) {
+        Ok(()) => true,
+        Err(TrySendError::Full(StreamMessage::Rows(rows))) => {
+            *batch = rows;
+            true
+        }
+        Err(TrySendError::Full(_)) => true,
+        Err(TrySendError::Disconnected(_)) => false,
+    }
 }
 
 /// Convert a Value to a row based on expected columns
 fn value_to_row(cols: &[String], value: &Value) -> Vec<Value> {
-    if cols.is_empty() {
-        vec![value.clone()]
-    } else if let Value::Record { val, .. } = value {
+    if let Value::Record { val, .. } = value {
         cols.iter()
             .map(|col| val.get(col).cloned().unwrap_or_default())
             .collect()
@@ -300,10 +313,72 @@ pub struct NuView {
     is_record: bool,
     stream_done: bool,
     last_error: Option<String>,
+    row_count: usize,
     last_row_count: usize,
+    // Whether the view scrolls to new rows as they arrive
+    follow: bool,
+    // Whether the help is shown when there are no rows, as for empty input
+    help_when_empty: bool,
 }
 
 impl NuView {
+    fn new(
+        receiver: Receiver<StreamMessage>,
+        handle: JoinHandle<()>,
+        command_text: String,
+        explore_config: ExploreConfig,
+        follow: bool,
+    ) -> Self {
+        Self {
+            state: ViewState::Loading,
+            receiver: Some(receiver),
+            _handle: Some(handle),
+            command_text,
+            explore_config,
+            frame_count: 0,
+            // Streaming state
+            columns: Vec::new(),
+            rows: Vec::new(),
+            is_record: false,
+            stream_done: false,
+            last_error: None,
+            row_count: 0,
+            last_row_count: 0,
+            follow,
+            help_when_empty: false,
+        }
+    }
+
+    /// Show the values of a stream as they arrive, like `less +F`.
+    pub fn from_stream(
+        stream: ListStream,
+        nu_config: &Config,
+        explore_config: &ExploreConfig,
+        follow: bool,
+    ) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(STREAM_BUFFER);
+        let nu_config = nu_config.clone();
+        let handle = thread::spawn(move || stream_values(stream.into_iter(), &nu_config, &sender));
+
+        let mut view = Self::new(
+            receiver,
+            handle,
+            String::from("input"),
+            explore_config.clone(),
+            follow,
+        );
+        view.help_when_empty = true;
+        view
+    }
+
+    /// Show that the stream ended without rows.
+    fn set_empty(&mut self) {
+        self.state = match self.help_when_empty {
+            true => ViewState::Preview(HelpCmd::view()),
+            false => ViewState::Empty,
+        };
+    }
+
     /// Process any pending messages from the background thread
     fn process_messages(&mut self) {
         // Take receiver temporarily to avoid borrow issues
@@ -317,11 +392,25 @@ impl NuView {
         loop {
             match receiver.try_recv() {
                 Ok(StreamMessage::Columns(cols)) => {
+                    if let ViewState::Records(view) = &mut self.state {
+                        view.extend_columns(cols.clone());
+                    } else {
+                        // Rows gathered before the view exists get the new columns too
+                        for row in &mut self.rows {
+                            row.resize(cols.len(), Value::default());
+                        }
+                    }
                     self.columns = cols;
                 }
                 Ok(StreamMessage::Rows(new_rows)) => {
-                    self.rows.extend(new_rows);
-                    should_update_view = true;
+                    self.row_count += new_rows.len();
+                    match &mut self.state {
+                        ViewState::Records(view) => view.append_rows(new_rows),
+                        _ => {
+                            self.rows.extend(new_rows);
+                            should_update_view = true;
+                        }
+                    }
                 }
                 Ok(StreamMessage::IsRecord) => {
                     self.is_record = true;
@@ -338,7 +427,7 @@ impl NuView {
                     if self.rows.is_empty()
                         && !matches!(self.state, ViewState::Records(_) | ViewState::Preview(_))
                     {
-                        self.state = ViewState::Empty;
+                        self.set_empty();
                     }
                     // Don't put receiver back - we're done
                     if should_update_view {
@@ -370,7 +459,7 @@ impl NuView {
                     if self.rows.is_empty()
                         && !matches!(self.state, ViewState::Records(_) | ViewState::Preview(_))
                     {
-                        self.state = ViewState::Empty;
+                        self.set_empty();
                     }
                     // Don't put receiver back - we're done
                     if should_update_view {
@@ -382,9 +471,9 @@ impl NuView {
         }
     }
 
-    /// Create or update the RecordView with current data
+    /// Create the RecordView with the data received so far, later rows are appended to it
     fn update_record_view(&mut self) {
-        if self.rows.is_empty() {
+        if self.rows.is_empty() || matches!(self.state, ViewState::Records(_)) {
             return;
         }
 
@@ -394,29 +483,15 @@ impl NuView {
             self.columns.clone()
         };
 
-        match &mut self.state {
-            ViewState::Records(existing_view) => {
-                // Append new rows to existing view
-                let layer = existing_view.get_top_layer_mut();
-                layer.record_values = self.rows.clone();
-                // Update cursor limits
-                let _ = layer.cursor.y.view.set_size(layer.record_values.len());
-                let _ = layer.cursor.x.view.set_size(layer.column_names.len());
-                // Invalidate text to force redraw
-                layer.record_text = None;
-            }
-            _ => {
-                // Create new view with all accumulated data
-                let mut view =
-                    RecordView::new(cols, self.rows.clone(), self.explore_config.clone());
+        let rows = std::mem::take(&mut self.rows);
+        let mut view = RecordView::new(cols, rows, self.explore_config.clone());
 
-                if self.is_record {
-                    view.set_top_layer_orientation(Orientation::Left);
-                }
-
-                self.state = ViewState::Records(Box::new(view));
-            }
+        if self.is_record {
+            view.set_top_layer_orientation(Orientation::Left);
+            view.set_source_record();
         }
+
+        self.state = ViewState::Records(Box::new(view));
     }
 
     fn spinner_char(&self) -> char {
@@ -425,7 +500,7 @@ impl NuView {
     }
 
     fn row_count(&self) -> usize {
-        self.rows.len()
+        self.row_count
     }
 
     fn is_streaming(&self) -> bool {
@@ -445,10 +520,13 @@ impl View for NuView {
         self.frame_count = self.frame_count.wrapping_add(1);
 
         // Tail the view if new rows have been added during streaming
-        if self.rows.len() > self.last_row_count {
-            self.last_row_count = self.rows.len();
-            if let ViewState::Records(view) = &mut self.state {
-                view.tail(area.width, area.height);
+        if let ViewState::Records(view) = &mut self.state {
+            view.set_auto_tail(self.follow);
+            if self.row_count > self.last_row_count {
+                self.last_row_count = self.row_count;
+                if self.follow {
+                    view.tail(area.width, area.height);
+                }
             }
         }
 
@@ -478,7 +556,24 @@ impl View for NuView {
         key: KeyEvent,
     ) -> Transition {
         match &mut self.state {
-            ViewState::Records(view) => view.handle_input(engine_state, stack, layout, info, key),
+            ViewState::Records(view) => {
+                if self.is_streaming() {
+                    match key.code {
+                        KeyCode::Char('F') => {
+                            self.follow = !self.follow;
+                            return Transition::Ok;
+                        }
+                        // Scrolling back stops following, as in `less`
+                        KeyCode::Up
+                        | KeyCode::Char('k')
+                        | KeyCode::PageUp
+                        | KeyCode::Home
+                        | KeyCode::Char('g') => self.follow = false,
+                        _ => {}
+                    }
+                }
+                view.handle_input(engine_state, stack, layout, info, key)
+            }
             ViewState::Preview(view) => view.handle_input(engine_state, stack, layout, info, key),
             _ => Self::non_interactive_transition(key),
         }
@@ -505,7 +600,11 @@ impl View for NuView {
                 let row_count = self.row_count();
                 if self.is_streaming() {
                     let spinner = self.spinner_char();
-                    let msg = format!("{} Streaming: {} rows", spinner, row_count);
+                    let follow = match self.follow {
+                        true => "following, F to stop",
+                        false => "F to follow",
+                    };
+                    let msg = format!("{} Streaming: {} rows ({})", spinner, row_count, follow);
                     info.status = Some(Report::message(msg, Severity::Info));
                     true // Keep polling
                 } else {
@@ -553,6 +652,13 @@ impl View for NuView {
             _ => None,
         }
     }
+
+    fn edited_value(&mut self) -> Option<Value> {
+        match &mut self.state {
+            ViewState::Records(view) => view.edited_value(),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -605,7 +711,10 @@ mod test {
             is_record: false,
             stream_done: false,
             last_error: None,
+            row_count: 0,
             last_row_count: 0,
+            follow: false,
+            help_when_empty: false,
         };
 
         view.process_messages();
@@ -615,6 +724,47 @@ mod test {
         assert_eq!(view.last_error.as_deref(), Some("Command failed"));
     }
 
+    #[test]
+    fn rows_are_kept_while_the_ui_is_busy() {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let row = || vec![Value::test_int(1)];
+
+        let mut batch = vec![row()];
+        assert!(send_rows(&sender, &mut batch));
+        assert!(batch.is_empty());
+
+        // The channel is full, so the rows wait for the next send
+        batch.push(row());
+        batch.push(row());
+        assert!(send_rows(&sender, &mut batch));
+        assert_eq!(batch.len(), 2);
+
+        drop(receiver);
+        assert!(!send_rows(&sender, &mut batch));
+    }
+
+    #[test]
+    fn streamed_values_are_shown_as_records() {
+        let (sender, receiver) = mpsc::sync_channel(STREAM_BUFFER);
+        stream_values(
+            vec![Value::test_int(1), Value::test_int(2)].into_iter(),
+            &sender,
+        );
+
+        let mut view = NuView::new(
+            receiver,
+            thread::spawn(|| {}),
+            String::new(),
+            ExploreConfig::default(),
+            false,
+        );
+        view.process_messages();
+
+        assert!(view.stream_done);
+        assert_eq!(view.row_count(), 2);
+        assert!(matches!(view.state, ViewState::Records(_)));
+    }
+
     #[test]
     fn update_prioritizes_error_status() {
         let mut view = NuView {
@@ -629,7 +779,10 @@ mod test {
             is_record: false,
             stream_done: true,
             last_error: Some(String::from("stream failed")),
+            row_count: 0,
             last_row_count: 0,
+            follow: false,
+            help_when_empty: false,
         };
 
         let mut info = ViewInfo::default();
@@ -641,4 +794,51 @@ mod test {
         assert!(matches!(status.level, Severity::Err));
         assert_eq!(status.message, "stream failed");
     }
+
+    fn stream(values: Vec<Value>) -> Vec<StreamMessage> {
+        let (sender, receiver) = mpsc::sync_channel(values.len() * 2 + 2);
+        stream_values(values.into_iter(), &Config::default(), &sender);
+        drop(sender);
+        receiver.into_iter().collect()
+    }
+
+    #[test]
+    fn stream_adds_columns_of_later_rows() {
+        let messages = stream(vec![
+            Value::test_record(nu_protocol::record! { "a" => Value::test_int(1) }),
+            Value::test_record(nu_protocol::record! { "b" => Value::test_int(2) }),
+        ]);
+
+        let columns: Vec<_> = messages
+            .iter()
+            .filter_map(|message| match message {
+                StreamMessage::Columns(columns) => Some(columns.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            columns.last(),
+            Some(&vec!["a".to_string(), "b".to_string()])
+        );
+
+        let rows: Vec<_> = messages
+            .into_iter()
+            .filter_map(|message| match message {
+                StreamMessage::Rows(rows) => Some(rows),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1], vec![Value::test_nothing(), Value::test_int(2)]);
+    }
+
+    #[test]
+    fn stream_of_one_simple_value_is_a_preview() {
+        let messages = stream(vec![Value::test_int(42)]);
+        assert!(matches!(
+            messages.last(),
+            Some(StreamMessage::SimpleValue(text)) if text == "42"
+        ));
+    }
 }
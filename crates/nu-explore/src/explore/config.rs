@@ -22,6 +22,35 @@ pub struct ExploreConfig {
     pub title_bar_text: Style,
     /// if true, the explore view will immediately try to run the command as it is typed
     pub try_reactive: bool,
+    /// the aggregates shown in a footer under the numeric columns of tables, one line each
+    pub footer: Vec<Aggregate>,
+}
+
+/// An aggregate of the values of a column, shown in the table footer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Count,
+    Sum,
+    Mean,
+}
+
+impl Aggregate {
+    pub fn name(self) -> &'static str {
+        match self {
+            Aggregate::Count => "count",
+            Aggregate::Sum => "sum",
+            Aggregate::Mean => "mean",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "count" => Some(Aggregate::Count),
+            "sum" => Some(Aggregate::Sum),
+            "mean" => Some(Aggregate::Mean),
+            _ => None,
+        }
+    }
 }
 
 impl Default for ExploreConfig {
@@ -43,6 +72,7 @@ impl Default for ExploreConfig {
             title_bar_background: color(None, None),
             title_bar_text: color(None, None),
             try_reactive: false,
+            footer: Vec::new(),
         }
     }
 }
@@ -117,6 +147,16 @@ impl ExploreConfig {
             ret.try_reactive = b;
         }
 
+        if let Some(aggregates) = explore_cfg_hash_map.get("footer")
+            && let Ok(aggregates) = aggregates.as_list()
+        {
+            ret.footer = aggregates
+                .iter()
+                .filter_map(|aggregate| aggregate.as_str().ok())
+                .filter_map(Aggregate::from_name)
+                .collect();
+        }
+
         ret
     }
 }
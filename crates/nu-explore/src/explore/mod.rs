@@ -13,7 +13,7 @@ mod views;
 
 use anyhow::Result;
 pub use command::Explore;
-use commands::{ExpandCmd, HelpCmd, NuCmd, QuitCmd, TableCmd, TryCmd};
+use commands::{ExpandCmd, HelpCmd, NuCmd, NuView, QuitCmd, TableCmd, TryCmd};
 pub use config::ExploreConfig;
 use crossterm::terminal::size;
 use nu_common::{collect_pipeline, has_simple_value};
@@ -47,6 +47,18 @@ pub(crate) fn run_pager(
         return p.run(engine_state, stack, Some(view), commands);
     }
 
+    if let PipelineData::ListStream(stream, ..) = input {
+        p.show_message("Streaming input");
+
+        let view = NuView::from_stream(
+            stream,
+            config.nu_config,
+            config.explore_config,
+            config.tail,
+        );
+        return p.run(engine_state, stack, Some(Page::new(view, true)), commands);
+    }
+
     let (columns, data) = collect_pipeline(input)?;
 
     let has_no_input = columns.is_empty() && data.is_empty();
@@ -76,6 +88,7 @@ fn create_record_view(
     let mut view = RecordView::new(columns, data, config.explore_config.clone());
     if is_record {
         view.set_top_layer_orientation(Orientation::Left);
+        view.set_source_record();
     }
 
     if config.tail
@@ -266,7 +266,7 @@ fn render_ui(
             match out {
                 Ok(result) => {
                     if result.exit {
-                        break Ok(peek_value_from_view(&mut view_stack.curr_view, pager));
+                        break Ok(exit_value(&mut view_stack, pager));
                     }
 
                     if result.view_change && !result.cmd_name.is_empty() {
@@ -299,17 +299,11 @@ fn react_to_event_result(
     info: &mut ViewInfo,
 ) -> (Option<Option<Value>>, String) {
     match status {
-        Transition::Exit => (
-            Some(peek_value_from_view(&mut view_stack.curr_view, pager)),
-            String::default(),
-        ),
+        Transition::Exit => (Some(exit_value(view_stack, pager)), String::default()),
         Transition::Ok => {
             let exit = view_stack.stack.is_empty();
             if exit {
-                return (
-                    Some(peek_value_from_view(&mut view_stack.curr_view, pager)),
-                    String::default(),
-                );
+                return (Some(exit_value(view_stack, pager)), String::default());
             }
 
             // try to pop the view stack
@@ -322,10 +316,9 @@ fn react_to_event_result(
         Transition::Cmd(cmd) => {
             let out = pager_run_command(engine_state, stack, pager, view_stack, commands, cmd);
             match out {
-                Ok(result) if result.exit => (
-                    Some(peek_value_from_view(&mut view_stack.curr_view, pager)),
-                    String::default(),
-                ),
+                Ok(result) if result.exit => {
+                    (Some(exit_value(view_stack, pager)), String::default())
+                }
                 Ok(result) => (None, result.cmd_name),
                 Err(err) => {
                     info.report = Some(Report::error(err));
@@ -337,12 +330,19 @@ fn react_to_event_result(
     }
 }
 
-fn peek_value_from_view(view: &mut Option<Page>, pager: &mut Pager<'_>) -> Option<Value> {
+/// The value output when quitting: the one peeked at with `--peek`, otherwise the input with
+/// the edits made to it, if any.
+fn exit_value(view_stack: &mut ViewStack, pager: &mut Pager<'_>) -> Option<Value> {
     if pager.config.peek_value {
-        let view = view.as_mut().map(|p| &mut p.view);
+        let view = view_stack.curr_view.as_mut().map(|p| &mut p.view);
         view.and_then(|v| v.exit())
     } else {
-        None
+        // Views opened by commands are stacked on top of the one showing the input
+        let input_view = match view_stack.stack.first_mut() {
+            Some(page) => page,
+            None => view_stack.curr_view.as_mut()?,
+        };
+        input_view.view.edited_value()
     }
 }
 
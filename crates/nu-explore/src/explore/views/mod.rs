@@ -109,6 +109,12 @@ pub trait View {
     fn exit(&mut self) -> Option<Value> {
         None
     }
+
+    /// Called when quitting, to get the input back with the changes made to it in the view,
+    /// if there are any.
+    fn edited_value(&mut self) -> Option<Value> {
+        None
+    }
 }
 
 impl View for Box<dyn View> {
@@ -140,6 +146,10 @@ impl View for Box<dyn View> {
         self.as_mut().exit()
     }
 
+    fn edited_value(&mut self) -> Option<Value> {
+        self.as_mut().edited_value()
+    }
+
     fn show_data(&mut self, i: usize) -> bool {
         self.as_mut().show_data(i)
     }
@@ -2,7 +2,7 @@ mod table_widget;
 
 use self::table_widget::{TableWidget, TableWidgetState};
 use super::super::{
-    config::ExploreConfig,
+    config::{Aggregate, ExploreConfig},
     nu_common::{NuSpan, NuText, collect_input, lscolorize},
     pager::{
         Frame, Transition, ViewInfo,
@@ -15,13 +15,19 @@ use super::{
     util::{make_styled_string, nu_style_to_tui},
 };
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use nu_color_config::{StyleComputer, TextStyle};
 use nu_protocol::{
-    Config, Record, Value,
+    Config, Record, ShellError, Value,
     engine::{EngineState, Stack},
 };
-use ratatui::{layout::Rect, widgets::Block};
+use nuon::ToNuonConfig;
+use ratatui::{
+    layout::Rect,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub use self::table_widget::Orientation;
 
@@ -34,6 +40,8 @@ pub struct RecordView {
     auto_tail: bool, // Track if tail mode is active for auto-scroll
     previous_row_count: usize,
     page_size: usize,
+    editor: Option<CellEditor>,
+    picker: Option<ColumnPicker>,
 }
 
 impl RecordView {
@@ -47,9 +55,16 @@ impl RecordView {
             auto_tail: true, // Enable auto-tail by default
             previous_row_count: row_count,
             page_size: 0,
+            editor: None,
+            picker: None,
         }
     }
 
+    /// Mark the data as the fields of a single record, so it is edited back into one.
+    pub fn set_source_record(&mut self) {
+        self.get_top_layer_mut().shape = LayerShape::Record;
+    }
+
     pub fn tail(&mut self, width: u16, height: u16) {
         let page_size =
             estimate_page_size(Rect::new(0, 0, width, height), self.cfg.table.show_header);
@@ -57,6 +72,59 @@ impl RecordView {
         self.auto_tail = true; // Enable auto-tail mode
     }
 
+    pub fn set_auto_tail(&mut self, auto_tail: bool) {
+        self.auto_tail = auto_tail;
+    }
+
+    /// Add rows at the end of the input, as they are streamed in.
+    pub fn append_rows(&mut self, rows: Vec<Vec<Value>>) {
+        self.update_base_layer(|layer| layer.record_values.extend(rows));
+    }
+
+    /// Add the columns of `columns` the input doesn't have yet, as they are streamed in.
+    ///
+    /// The rows already shown get empty cells in them.
+    pub fn extend_columns(&mut self, columns: Vec<String>) {
+        self.update_base_layer(|layer| {
+            let known = layer.column_names.len();
+            let added: Vec<String> = columns.into_iter().skip(known).collect();
+            if added.is_empty() {
+                return;
+            }
+
+            for row in &mut layer.record_values {
+                row.resize(known + added.len(), Value::default());
+            }
+            layer
+                .column_names
+                .extend(added.iter().map(|name| strip_string(name)));
+            layer.shown_columns.extend(known..layer.column_names.len());
+            layer.update_shown_columns();
+        });
+    }
+
+    /// Change the data of the input layer as it was given, transposing it back if needed.
+    fn update_base_layer(&mut self, update: impl FnOnce(&mut RecordLayer)) {
+        let Some(layer) = self.layer_stack.first_mut() else {
+            return;
+        };
+
+        let transposed = layer.was_transposed;
+        if transposed {
+            transpose_table(layer);
+        }
+        update(layer);
+        if transposed {
+            transpose_table(layer);
+        }
+
+        // Update cursor limits
+        let _ = layer.cursor.y.view.set_size(layer.count_rows());
+        let _ = layer.cursor.x.view.set_size(layer.count_columns());
+        // Invalidate text to force redraw
+        layer.record_text = None;
+    }
+
     pub fn transpose(&mut self) {
         let layer = self.get_top_layer_mut();
         transpose_table(layer);
@@ -109,13 +177,8 @@ impl RecordView {
     }
 
     pub fn get_current_value(&self) -> &Value {
-        let Position { row, column } = self.get_cursor_position();
         let layer = self.get_top_layer();
-
-        let (row, column) = match layer.orientation {
-            Orientation::Top => (row, column),
-            Orientation::Left => (column, row),
-        };
+        let (row, column) = layer.cell_position();
 
         // These should never happen as long as the cursor is working correctly
         assert!(row < layer.record_values.len(), "row out of bounds");
@@ -124,26 +187,211 @@ impl RecordView {
         &layer.record_values[row][column]
     }
 
+    /// Leave the top layer, writing its edits back into the cell it was opened from.
+    fn pop_layer(&mut self) {
+        if self.layer_stack.len() < 2 {
+            return;
+        }
+
+        if let Some(layer) = self.layer_stack.pop()
+            && layer.modified
+        {
+            self.get_top_layer_mut().set_current_value(layer.to_value());
+        }
+    }
+
+    fn footer_height(&self) -> usize {
+        match self.get_top_layer().footer_text.len() {
+            0 => 0,
+            lines => lines + 1,
+        }
+    }
+
+    /// Hide, move or pin columns of tables. Returns `None` for other keys.
+    fn handle_column_key(&mut self, key: &KeyEvent) -> Option<Report> {
+        if !matches!(key.code, KeyCode::Char('C' | 'H' | 'P' | '<' | '>')) {
+            return None;
+        }
+
+        let layer = self.get_top_layer_mut();
+        if layer.orientation != Orientation::Top || layer.was_transposed {
+            return Some(Report::message(
+                "Columns can only be changed in tables",
+                Severity::Warn,
+            ));
+        }
+
+        let column = layer.cursor.column();
+        let report = match key.code {
+            KeyCode::Char('C') => {
+                self.picker = Some(ColumnPicker::default());
+                Report::message(
+                    "Space to show or hide a column, Esc to close",
+                    Severity::Info,
+                )
+            }
+            KeyCode::Char('H') => match layer.hide_column(column) {
+                true => Report::message("Column hidden, C to show it again", Severity::Info),
+                false => Report::message("The last column can't be hidden", Severity::Warn),
+            },
+            KeyCode::Char('P') => match layer.toggle_pin(column) {
+                true => Report::message("Column pinned", Severity::Info),
+                false => Report::message("Column unpinned", Severity::Info),
+            },
+            KeyCode::Char(c) => {
+                layer.move_column(column, c == '>');
+                self.create_records_report()
+            }
+            _ => return None,
+        };
+
+        Some(report)
+    }
+
+    fn handle_picker_input(&mut self, key: KeyEvent, info: &mut ViewInfo) -> Transition {
+        let Some(picker) = &mut self.picker else {
+            return Transition::None;
+        };
+        let layer = self
+            .layer_stack
+            .last_mut()
+            .expect("we guarantee that 1 entry is always in a list");
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'C') | KeyCode::Enter => {
+                self.picker = None;
+                info.status = Some(self.create_records_report());
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                picker.selected = picker.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                picker.selected =
+                    (picker.selected + 1).min(layer.column_names.len().saturating_sub(1));
+            }
+            KeyCode::Char(' ') => {
+                let index = picker.selected;
+                match layer.shown_columns.iter().position(|&shown| shown == index) {
+                    Some(column) => {
+                        if !layer.hide_column(column) {
+                            info.status = Some(Report::message(
+                                "The last column can't be hidden",
+                                Severity::Warn,
+                            ));
+                        }
+                    }
+                    None => layer.show_column(index),
+                }
+            }
+            _ => {}
+        }
+
+        Transition::Ok
+    }
+
+    fn start_editing(&mut self, engine_state: &EngineState) -> Report {
+        if self.get_top_layer().was_transposed {
+            return Report::message("Transpose the table back to edit it", Severity::Warn);
+        }
+
+        let editor = match self.get_current_value() {
+            Value::String { val, .. } => CellEditor::new(val.clone(), true),
+            value => match nuon::to_nuon(engine_state, value, ToNuonConfig::default()) {
+                Ok(text) => CellEditor::new(text, false),
+                Err(err) => {
+                    return Report::message(
+                        format!("This cell can't be edited: {err}"),
+                        Severity::Err,
+                    );
+                }
+            },
+        };
+        self.editor = Some(editor);
+
+        Report::message("Editing cell, Enter to save, Esc to cancel", Severity::Info)
+    }
+
+    fn handle_editor_input(&mut self, key: KeyEvent, info: &mut ViewInfo) -> Transition {
+        let Some(editor) = &mut self.editor else {
+            return Transition::None;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.editor = None;
+                info.status = Some(self.create_records_report());
+            }
+            KeyCode::Enter => match editor.value() {
+                Ok(value) => {
+                    self.editor = None;
+                    self.get_top_layer_mut().set_current_value(value);
+                    info.status = Some(Report::message("Cell updated", Severity::Success));
+                }
+                Err(err) => {
+                    info.status = Some(Report::message(
+                        format!("Invalid value: {err}"),
+                        Severity::Err,
+                    ));
+                }
+            },
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                editor.insert(c)
+            }
+            KeyCode::Backspace => editor.backspace(),
+            KeyCode::Delete => editor.delete(),
+            KeyCode::Left => editor.move_left(),
+            KeyCode::Right => editor.move_right(),
+            KeyCode::Home => editor.cursor = 0,
+            KeyCode::End => editor.cursor = editor.text.len(),
+            _ => {}
+        }
+
+        Transition::Ok
+    }
+
     fn create_table_widget<'a>(&'a mut self, cfg: ViewConfig<'a>) -> TableWidget<'a> {
         let style = self.cfg.table;
         let style_computer = cfg.style_computer;
         let Position { row, column } = self.get_window_origin();
 
-        let layer = self.get_top_layer_mut();
+        let footer = &self.cfg.footer;
+        let layer = self
+            .layer_stack
+            .last_mut()
+            .expect("we guarantee that 1 entry is always in a list");
         if layer.record_text.is_none() {
-            let mut data =
-                convert_records_to_string(&layer.record_values, cfg.nu_config, cfg.style_computer);
-            lscolorize(&layer.column_names, &mut data, cfg.cwd, cfg.lscolors);
+            let mut data = convert_records_to_string(
+                &layer.record_values,
+                &layer.shown_columns,
+                cfg.nu_config,
+                cfg.style_computer,
+            );
+            lscolorize(&layer.shown_names, &mut data, cfg.cwd, cfg.lscolors);
 
             layer.record_text = Some(data);
+            layer.footer_text = match layer.orientation {
+                Orientation::Top if !footer.is_empty() => aggregate_footer(
+                    &layer.record_values,
+                    &layer.shown_columns,
+                    footer,
+                    cfg.nu_config,
+                ),
+                _ => Vec::new(),
+            };
         }
 
-        let headers = &layer.column_names;
+        let headers = &layer.shown_names;
         let data = layer.record_text.as_ref().expect("always ok");
 
         TableWidget::new(
             headers,
             data,
+            &layer.footer_text,
+            layer.pinned_columns,
             style_computer,
             row,
             column,
@@ -196,7 +444,8 @@ impl View for RecordView {
         self.update_cursors(table_layout.count_rows, table_layout.count_columns);
 
         // Update page_size
-        self.page_size = estimate_page_size(area, self.cfg.table.show_header) as usize;
+        self.page_size = (estimate_page_size(area, self.cfg.table.show_header) as usize)
+            .saturating_sub(self.footer_height());
 
         // Check for new rows and handle auto-tail
         let current_row_count = self.get_top_layer().record_values.len();
@@ -227,19 +476,45 @@ impl View for RecordView {
             );
 
             if let Some(info) = info {
-                highlight_selected_cell(f, info.clone(), &self.cfg);
+                match &self.editor {
+                    Some(editor) => render_cell_editor(f, info.area, area, editor, &self.cfg),
+                    None => highlight_selected_cell(f, info.clone(), &self.cfg),
+                }
             }
         }
+
+        if let Some(picker) = &self.picker {
+            render_column_picker(f, area, self.get_top_layer(), picker, &self.cfg);
+        }
     }
 
     fn handle_input(
         &mut self,
-        _engine_state: &EngineState,
+        engine_state: &EngineState,
         _stack: &mut Stack,
         _layout: &Layout,
         info: &mut ViewInfo,
         key: KeyEvent,
     ) -> Transition {
+        if self.editor.is_some() {
+            return self.handle_editor_input(key, info);
+        }
+        if self.picker.is_some() {
+            return self.handle_picker_input(key, info);
+        }
+        if (self.mode == UIMode::Cursor || key.code == KeyCode::Char('C'))
+            && let Some(report) = self.handle_column_key(&key)
+        {
+            info.status = Some(report);
+            return Transition::Ok;
+        }
+        if self.mode == UIMode::Cursor
+            && key.code == KeyCode::Char('c')
+            && key.modifiers == KeyModifiers::NONE
+        {
+            info.status = Some(self.start_editing(engine_state));
+            return Transition::Ok;
+        }
         if key.code == KeyCode::PageUp {
             let page_size = self.page_size;
             let current_row = self.get_top_layer().cursor.window_origin().row;
@@ -289,13 +564,17 @@ impl View for RecordView {
         let mut texts = Vec::new();
 
         // Add headers
-        for name in &layer.column_names {
+        for name in &layer.shown_names {
             texts.push((name.clone(), TextStyle::default()));
         }
 
         // Add data
         for row in &layer.record_values {
-            for value in row {
+            for value in layer
+                .shown_columns
+                .iter()
+                .filter_map(|&column| row.get(column))
+            {
                 let text = value.to_abbreviated_string(&Config::default());
                 let text = strip_string(&text);
                 texts.push((text, TextStyle::default()));
@@ -307,7 +586,7 @@ impl View for RecordView {
 
     fn show_data(&mut self, pos: usize) -> bool {
         let layer = self.get_top_layer();
-        let num_headers = layer.column_names.len();
+        let num_headers = layer.shown_columns.len();
 
         if pos < num_headers {
             // Header
@@ -320,15 +599,15 @@ impl View for RecordView {
         } else {
             let data_pos = pos - num_headers;
             let mut i = 0;
-            for (data_row, cells) in layer.record_values.iter().enumerate() {
-                if data_pos >= i && data_pos < i + cells.len() {
+            for data_row in 0..layer.record_values.len() {
+                if data_pos >= i && data_pos < i + num_headers {
                     let column = data_pos - i;
                     self.get_top_layer_mut()
                         .cursor
                         .set_window_start_position(data_row, column);
                     return true;
                 }
-                i += cells.len();
+                i += num_headers;
             }
         }
 
@@ -342,6 +621,15 @@ impl View for RecordView {
     fn exit(&mut self) -> Option<Value> {
         Some(build_last_value(self))
     }
+
+    fn edited_value(&mut self) -> Option<Value> {
+        while self.layer_stack.len() > 1 {
+            self.pop_layer();
+        }
+
+        let layer = self.get_top_layer();
+        layer.modified.then(|| layer.to_value())
+    }
 }
 
 fn build_last_value(v: &RecordView) -> Value {
@@ -425,28 +713,58 @@ pub struct RecordLayer {
     // This is the text representation of the record values (the actual text that will be displayed to users).
     // It's an Option because we need configuration to set it and we (currently) don't have access to configuration when things are created.
     pub record_text: Option<Vec<Vec<NuText>>>,
+    // The aggregates shown under the columns, computed along with `record_text`.
+    footer_text: Vec<Vec<NuText>>,
+    // Indexes in `column_names` of the columns shown, in order. The pinned ones come first.
+    shown_columns: Vec<usize>,
+    shown_names: Vec<String>,
+    pinned_columns: usize,
     orientation: Orientation,
     name: Option<String>,
     was_transposed: bool,
+    shape: LayerShape,
+    // Whether a cell was edited, in this layer or in one opened from it.
+    modified: bool,
     pub cursor: WindowCursor2D,
 }
 
+/// What the cells of a layer are made of, to turn them back into a value after an edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayerShape {
+    /// The fields of a record, in a single row
+    Record,
+    /// A list of records, one per row
+    Table,
+    /// A list of other values, in a single column
+    List,
+}
+
 impl RecordLayer {
     fn new(columns: Vec<String>, records: Vec<Vec<Value>>) -> Self {
         // TODO: refactor so this is fallible and returns a Result instead of panicking
         let cursor =
             WindowCursor2D::new(records.len(), columns.len()).expect("Failed to create cursor");
 
-        let column_names = columns.iter().map(|s| strip_string(s)).collect();
+        let column_names: Vec<String> = columns.iter().map(|s| strip_string(s)).collect();
+        let shape = match columns.as_slice() {
+            [column] if column.is_empty() || column == "0" => LayerShape::List,
+            _ => LayerShape::Table,
+        };
 
         Self {
+            shown_columns: (0..column_names.len()).collect(),
+            shown_names: column_names.clone(),
+            pinned_columns: 0,
             column_names,
             record_values: records,
             record_text: None,
+            footer_text: Vec::new(),
             cursor,
             orientation: Orientation::Top,
             name: None,
             was_transposed: false,
+            shape,
+            modified: false,
         }
     }
 
@@ -457,20 +775,192 @@ impl RecordLayer {
     fn count_rows(&self) -> usize {
         match self.orientation {
             Orientation::Top => self.record_values.len(),
-            Orientation::Left => self.column_names.len(),
+            Orientation::Left => self.shown_columns.len(),
         }
     }
 
     fn count_columns(&self) -> usize {
         match self.orientation {
-            Orientation::Top => self.column_names.len(),
+            Orientation::Top => self.shown_columns.len(),
             Orientation::Left => self.record_values.len(),
         }
     }
 
     fn get_column_header(&self) -> Option<String> {
         let col = self.cursor.column();
-        self.column_names.get(col).map(|header| header.to_string())
+        self.shown_names.get(col).map(|header| header.to_string())
+    }
+
+    /// The row and the index in `column_names` of the cell under the cursor.
+    fn cell_position(&self) -> (usize, usize) {
+        let Position { row, column } = self.cursor.position();
+        let (row, column) = match self.orientation {
+            Orientation::Top => (row, column),
+            Orientation::Left => (column, row),
+        };
+
+        let column = self.shown_columns.get(column).copied().unwrap_or(column);
+        (row, column)
+    }
+
+    /// Show all the columns again, in their original order.
+    fn reset_columns(&mut self) {
+        self.shown_columns = (0..self.column_names.len()).collect();
+        self.pinned_columns = 0;
+        self.update_shown_columns();
+    }
+
+    fn update_shown_columns(&mut self) {
+        self.shown_names = self
+            .shown_columns
+            .iter()
+            .filter_map(|&column| self.column_names.get(column).cloned())
+            .collect();
+        self.record_text = None;
+
+        let _ = self.cursor.x.view.set_size(self.shown_columns.len());
+    }
+
+    /// Move the cursor to a shown column.
+    fn move_cursor_to_column(&mut self, column: usize) {
+        let current = self.cursor.column();
+        if column > current {
+            self.cursor.next_column_by(column - current);
+        } else {
+            self.cursor.prev_column_by(current - column);
+        }
+    }
+
+    /// Hide a shown column, unless it's the only one left.
+    fn hide_column(&mut self, column: usize) -> bool {
+        if self.shown_columns.len() < 2 || column >= self.shown_columns.len() {
+            return false;
+        }
+
+        self.shown_columns.remove(column);
+        if column < self.pinned_columns {
+            self.pinned_columns -= 1;
+        }
+        self.update_shown_columns();
+        self.move_cursor_to_column(column.min(self.shown_columns.len() - 1));
+        true
+    }
+
+    /// Show a hidden column, given by its index in `column_names`, next to the shown columns
+    /// around it in the original order.
+    fn show_column(&mut self, index: usize) {
+        if index >= self.column_names.len() || self.shown_columns.contains(&index) {
+            return;
+        }
+
+        let position = self.shown_columns[self.pinned_columns..]
+            .iter()
+            .position(|&shown| shown > index)
+            .map_or(self.shown_columns.len(), |position| {
+                position + self.pinned_columns
+            });
+        self.shown_columns.insert(position, index);
+        self.update_shown_columns();
+    }
+
+    /// Swap a shown column with its neighbour, within the pinned or the unpinned columns.
+    fn move_column(&mut self, column: usize, right: bool) -> bool {
+        let (start, end) = match column < self.pinned_columns {
+            true => (0, self.pinned_columns),
+            false => (self.pinned_columns, self.shown_columns.len()),
+        };
+        let other = match right {
+            true => column + 1,
+            false => column.wrapping_sub(1),
+        };
+        if column < start || other < start || other >= end {
+            return false;
+        }
+
+        self.shown_columns.swap(column, other);
+        self.update_shown_columns();
+        self.move_cursor_to_column(other);
+        true
+    }
+
+    /// Pin a shown column after the pinned ones, or unpin it if it is pinned.
+    fn toggle_pin(&mut self, column: usize) -> bool {
+        if column >= self.shown_columns.len() {
+            return false;
+        }
+
+        let index = self.shown_columns.remove(column);
+        let pinned = column >= self.pinned_columns;
+        let position = match pinned {
+            true => {
+                self.pinned_columns += 1;
+                self.pinned_columns - 1
+            }
+            false => {
+                self.pinned_columns -= 1;
+                self.pinned_columns
+            }
+        };
+        self.shown_columns.insert(position, index);
+        self.update_shown_columns();
+        self.move_cursor_to_column(position);
+        pinned
+    }
+
+    /// Replace the value of the cell under the cursor.
+    fn set_current_value(&mut self, value: Value) {
+        let (row, column) = self.cell_position();
+        if let Some(cell) = self
+            .record_values
+            .get_mut(row)
+            .and_then(|values| values.get_mut(column))
+        {
+            *cell = value;
+            self.record_text = None;
+            self.modified = true;
+        }
+    }
+
+    /// Build the value the layer was created from, with its edits.
+    fn to_value(&self) -> Value {
+        if self.was_transposed {
+            let mut layer = self.clone();
+            transpose_table(&mut layer);
+            return layer.to_value();
+        }
+
+        let span = NuSpan::unknown();
+        let to_record = |values: &Vec<Value>| -> Record {
+            self.column_names
+                .iter()
+                .cloned()
+                .zip(values.iter().cloned())
+                .collect()
+        };
+
+        match self.shape {
+            LayerShape::Record => Value::record(
+                self.record_values
+                    .first()
+                    .map(to_record)
+                    .unwrap_or_default(),
+                span,
+            ),
+            LayerShape::Table => Value::list(
+                self.record_values
+                    .iter()
+                    .map(|values| Value::record(to_record(values), span))
+                    .collect(),
+                span,
+            ),
+            LayerShape::List => Value::list(
+                self.record_values
+                    .iter()
+                    .filter_map(|values| values.first().cloned())
+                    .collect(),
+                span,
+            ),
+        }
     }
 
     fn reset_cursor(&mut self) {
@@ -516,7 +1006,7 @@ impl CursorMoveHandler for RecordView {
         match self.mode {
             UIMode::View => {
                 if self.layer_stack.len() > 1 {
-                    self.layer_stack.pop();
+                    self.pop_layer();
                     self.mode = UIMode::Cursor;
                 } else {
                     return Transition::Exit;
@@ -569,11 +1059,17 @@ impl CursorMoveHandler for RecordView {
 }
 
 fn create_layer(value: Value) -> Result<RecordLayer> {
+    let is_record = matches!(value, Value::Record { .. });
     let (columns, values) = collect_input(value)?;
     if columns.is_empty() {
         return Err(anyhow::anyhow!("Nothing to explore in empty collections!"));
     }
-    Ok(RecordLayer::new(columns, values))
+
+    let mut layer = RecordLayer::new(columns, values);
+    if is_record {
+        layer.shape = LayerShape::Record;
+    }
+    Ok(layer)
 }
 
 fn push_layer(view: &mut RecordView, mut next_layer: RecordLayer) {
@@ -613,13 +1109,16 @@ fn tail_data(state: &mut RecordView, page_size: usize) {
 
 fn convert_records_to_string(
     records: &[Vec<Value>],
+    columns: &[usize],
     cfg: &Config,
     style_computer: &StyleComputer,
 ) -> Vec<Vec<NuText>> {
     records
         .iter()
         .map(|row| {
-            row.iter()
+            columns
+                .iter()
+                .filter_map(|&column| row.get(column))
                 .map(|value| {
                     let text = value.clone().to_abbreviated_string(cfg);
                     let text = strip_string(&text);
@@ -632,6 +1131,79 @@ fn convert_records_to_string(
         .collect::<Vec<_>>()
 }
 
+/// The lines of the footer: for each aggregate, its value under the numeric columns.
+fn aggregate_footer(
+    records: &[Vec<Value>],
+    columns: &[usize],
+    aggregates: &[Aggregate],
+    cfg: &Config,
+) -> Vec<Vec<NuText>> {
+    let columns: Vec<_> = columns
+        .iter()
+        .map(|&column| aggregate_column(records, column, aggregates))
+        .collect();
+
+    aggregates
+        .iter()
+        .enumerate()
+        .map(|(i, aggregate)| {
+            columns
+                .iter()
+                .map(|values| {
+                    let text = values
+                        .as_ref()
+                        .and_then(|values| values.get(i))
+                        .map(|value| {
+                            let value = strip_string(&value.to_abbreviated_string(cfg));
+                            format!("{} {value}", aggregate.name())
+                        })
+                        .unwrap_or_default();
+                    (text, TextStyle::default())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The aggregates of a column, if its values are all numbers, file sizes or durations.
+fn aggregate_column(
+    records: &[Vec<Value>],
+    column: usize,
+    aggregates: &[Aggregate],
+) -> Option<Vec<Value>> {
+    let span = NuSpan::unknown();
+
+    let mut count = 0;
+    let mut sum: Option<Value> = None;
+    for value in records.iter().filter_map(|row| row.get(column)) {
+        match value {
+            Value::Nothing { .. } => continue,
+            Value::Int { .. }
+            | Value::Float { .. }
+            | Value::Filesize { .. }
+            | Value::Duration { .. } => {}
+            _ => return None,
+        }
+
+        count += 1;
+        sum = Some(match sum {
+            Some(sum) => sum.add(span, value, span).ok()?,
+            None => value.clone(),
+        });
+    }
+
+    let sum = sum?;
+    let count = Value::int(count, span);
+    aggregates
+        .iter()
+        .map(|aggregate| match aggregate {
+            Aggregate::Count => Some(count.clone()),
+            Aggregate::Sum => Some(sum.clone()),
+            Aggregate::Mean => sum.div(span, &count, span).ok(),
+        })
+        .collect()
+}
+
 fn highlight_selected_cell(f: &mut Frame, info: ElementInfo, cfg: &ExploreConfig) {
     let cell_style = cfg.selected_cell;
     let highlight_block = Block::default().style(nu_style_to_tui(cell_style));
@@ -639,6 +1211,165 @@ fn highlight_selected_cell(f: &mut Frame, info: ElementInfo, cfg: &ExploreConfig
     f.render_widget(highlight_block.clone(), area)
 }
 
+/// Draw the text being edited over the selected cell, widening it up to the edge of `bounds`.
+fn render_cell_editor(
+    f: &mut Frame,
+    cell: Rect,
+    bounds: Rect,
+    editor: &CellEditor,
+    cfg: &ExploreConfig,
+) {
+    let text_width = editor.text.width() as u16 + 1;
+    let width = cell
+        .width
+        .max(text_width)
+        .min(bounds.right().saturating_sub(cell.x));
+    if width == 0 {
+        return;
+    }
+
+    let area = Rect::new(cell.x, cell.y, width, 1);
+    let style = nu_style_to_tui(cfg.selected_cell);
+    f.render_widget(Block::default().style(style), area);
+
+    // Cut the start of long texts so the cursor stays visible
+    let before_cursor = &editor.text[..editor.cursor];
+    let mut cursor_x = before_cursor.width();
+    let mut start = 0;
+    for c in before_cursor.chars() {
+        if cursor_x < width as usize {
+            break;
+        }
+        cursor_x -= c.width().unwrap_or(0);
+        start += c.len_utf8();
+    }
+
+    f.buffer_mut()
+        .set_stringn(area.x, area.y, &editor.text[start..], width as usize, style);
+    f.set_cursor_position((area.x + cursor_x as u16, area.y));
+}
+
+/// Draw the list of all columns of a layer over the table, marking the shown and pinned ones.
+fn render_column_picker(
+    f: &mut Frame,
+    area: Rect,
+    layer: &RecordLayer,
+    picker: &ColumnPicker,
+    cfg: &ExploreConfig,
+) {
+    let lines: Vec<String> = layer
+        .column_names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let position = layer.shown_columns.iter().position(|&shown| shown == index);
+            let mark = if position.is_some() { "x" } else { " " };
+            let pinned = match position {
+                Some(position) if position < layer.pinned_columns => " (pinned)",
+                _ => "",
+            };
+            format!("[{mark}] {name}{pinned}")
+        })
+        .collect();
+
+    let width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16 + 4;
+    let width = width.max(12).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    if width < 5 || height < 3 {
+        return;
+    }
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    // Scroll so the selected column stays visible
+    let visible = (height - 2) as usize;
+    let start = picker.selected.saturating_sub(visible - 1);
+    let selected_style = nu_style_to_tui(cfg.selected_cell);
+    let lines: Vec<Line> = lines
+        .into_iter()
+        .enumerate()
+        .skip(start)
+        .take(visible)
+        .map(|(index, line)| match index == picker.selected {
+            true => Line::styled(line, selected_style),
+            false => Line::raw(line),
+        })
+        .collect();
+
+    let block = Block::default().borders(Borders::ALL).title(" Columns ");
+    f.render_widget(Clear, popup);
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+/// The line selected in the column picker.
+#[derive(Debug, Clone, Default)]
+struct ColumnPicker {
+    // Index in `column_names`
+    selected: usize,
+}
+
+/// The text of the cell being edited.
+#[derive(Debug, Clone)]
+struct CellEditor {
+    text: String,
+    // Byte offset of the cursor in `text`
+    cursor: usize,
+    // Whether `text` is the string in the cell, rather than the nuon of its value
+    is_string: bool,
+}
+
+impl CellEditor {
+    fn new(text: String, is_string: bool) -> Self {
+        Self {
+            cursor: text.len(),
+            text,
+            is_string,
+        }
+    }
+
+    fn value(&self) -> Result<Value, ShellError> {
+        if self.is_string {
+            Ok(Value::string(&self.text, NuSpan::unknown()))
+        } else {
+            nuon::from_nuon(&self.text, None)
+        }
+    }
+
+    fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    fn backspace(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.text.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    fn move_right(&mut self) {
+        if let Some(c) = self.text[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+}
+
 fn report_cursor_position(mode: UIMode, cursor: WindowCursor2D) -> String {
     if mode == UIMode::Cursor {
         let Position { row, column } = cursor.position();
@@ -676,6 +1407,7 @@ fn transpose_table(layer: &mut RecordLayer) {
     }
 
     layer.was_transposed = !layer.was_transposed;
+    layer.reset_columns();
 }
 
 fn transpose_from(layer: &mut RecordLayer) {
@@ -855,6 +1587,216 @@ mod tests {
         assert!(!layer.was_transposed);
     }
 
+    #[test]
+    fn test_append_rows_to_transposed_layer() {
+        let mut view = RecordView::new(
+            vec!["a".to_string(), "b".to_string()],
+            vec![vec![
+                Value::int(1, Span::test_data()),
+                Value::int(2, Span::test_data()),
+            ]],
+            ExploreConfig::default(),
+        );
+        view.transpose();
+        view.append_rows(vec![vec![
+            Value::int(3, Span::test_data()),
+            Value::int(4, Span::test_data()),
+        ]]);
+
+        let layer = view.get_top_layer();
+        assert!(layer.was_transposed);
+        assert_eq!(layer.count_rows(), 2);
+        assert_eq!(layer.count_columns(), 3);
+        assert_eq!(layer.record_values[0][2], Value::int(3, Span::test_data()));
+        assert_eq!(layer.record_values[1][2], Value::int(4, Span::test_data()));
+    }
+
+    #[test]
+    fn test_extend_columns_pads_rows() {
+        let mut view = RecordView::new(
+            vec!["a".to_string()],
+            vec![vec![Value::int(1, Span::test_data())]],
+            ExploreConfig::default(),
+        );
+        view.extend_columns(vec!["a".to_string(), "b".to_string()]);
+        view.append_rows(vec![vec![
+            Value::int(2, Span::test_data()),
+            Value::int(3, Span::test_data()),
+        ]]);
+
+        let layer = view.get_top_layer();
+        assert_eq!(layer.column_names, vec!["a", "b"]);
+        assert_eq!(layer.count_columns(), 2);
+        assert_eq!(layer.record_values[0][1], Value::default());
+        assert_eq!(layer.record_values[1][1], Value::int(3, Span::test_data()));
+    }
+
+    #[test]
+    fn test_edited_value_of_record() {
+        let mut view = RecordView::new(
+            vec!["name".to_string(), "value".to_string()],
+            vec![vec![
+                Value::string("sample", Span::test_data()),
+                Value::int(42, Span::test_data()),
+            ]],
+            ExploreConfig::default(),
+        );
+        view.set_top_layer_orientation(Orientation::Left);
+        view.set_source_record();
+        assert_eq!(view.edited_value(), None);
+
+        view.get_top_layer_mut().cursor.next_row();
+        view.get_top_layer_mut()
+            .set_current_value(Value::int(7, Span::test_data()));
+
+        let mut expected = Record::new();
+        expected.insert("name", Value::string("sample", Span::test_data()));
+        expected.insert("value", Value::int(7, Span::test_data()));
+        assert_eq!(
+            view.edited_value(),
+            Some(Value::record(expected, Span::test_data()))
+        );
+    }
+
+    #[test]
+    fn test_edits_in_nested_layers_are_kept() {
+        let nested = Value::list(
+            vec![
+                Value::int(1, Span::test_data()),
+                Value::int(2, Span::test_data()),
+            ],
+            Span::test_data(),
+        );
+        let mut view = RecordView::new(
+            vec!["a".to_string()],
+            vec![vec![nested]],
+            ExploreConfig::default(),
+        );
+
+        view.set_cursor_mode();
+        assert!(view.handle_enter().is_ok());
+        assert_eq!(view.layer_stack.len(), 2);
+        view.get_top_layer_mut()
+            .set_current_value(Value::int(5, Span::test_data()));
+
+        // Back to view mode, then out of the nested layer
+        view.handle_esc();
+        view.handle_esc();
+        assert_eq!(view.layer_stack.len(), 1);
+
+        let mut expected = Record::new();
+        expected.insert(
+            "a",
+            Value::list(
+                vec![
+                    Value::int(5, Span::test_data()),
+                    Value::int(2, Span::test_data()),
+                ],
+                Span::test_data(),
+            ),
+        );
+        assert_eq!(
+            view.edited_value(),
+            Some(Value::list(
+                vec![Value::record(expected, Span::test_data())],
+                Span::test_data()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_cell_editor() {
+        let mut editor = CellEditor::new("héllo".to_string(), true);
+        editor.move_left();
+        editor.backspace();
+        editor.insert('L');
+        assert_eq!(editor.text, "hélLo");
+        editor.cursor = 0;
+        editor.delete();
+        assert_eq!(editor.text, "élLo");
+
+        let editor = CellEditor::new("{a: 1}".to_string(), false);
+        let mut expected = Record::new();
+        expected.insert("a", Value::int(1, Span::test_data()));
+        assert_eq!(
+            editor.value().ok(),
+            Some(Value::record(expected, Span::test_data()))
+        );
+        assert!(CellEditor::new("{a:".to_string(), false).value().is_err());
+    }
+
+    fn create_test_layer() -> RecordLayer {
+        RecordLayer::new(
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            vec![vec![
+                Value::test_int(1),
+                Value::test_int(2),
+                Value::test_int(3),
+            ]],
+        )
+    }
+
+    #[test]
+    fn test_hide_and_show_columns() {
+        let mut layer = create_test_layer();
+
+        assert!(layer.hide_column(1));
+        assert_eq!(layer.shown_names, vec!["a", "c"]);
+        assert_eq!(layer.count_columns(), 2);
+
+        // The cursor moved to the column after the hidden one
+        assert_eq!(layer.cell_position(), (0, 2));
+
+        layer.show_column(1);
+        assert_eq!(layer.shown_names, vec!["a", "b", "c"]);
+
+        assert!(layer.hide_column(0));
+        assert!(layer.hide_column(0));
+        assert!(!layer.hide_column(0));
+        assert_eq!(layer.shown_names, vec!["c"]);
+    }
+
+    #[test]
+    fn test_move_and_pin_columns() {
+        let mut layer = create_test_layer();
+
+        assert!(layer.move_column(0, true));
+        assert_eq!(layer.shown_names, vec!["b", "a", "c"]);
+        assert!(!layer.move_column(2, true));
+
+        assert!(layer.toggle_pin(2));
+        assert_eq!(layer.shown_names, vec!["c", "b", "a"]);
+        assert_eq!(layer.pinned_columns, 1);
+        assert_eq!(layer.cell_position(), (0, 2));
+
+        // Pinned columns only move among themselves
+        assert!(!layer.move_column(1, false));
+
+        assert!(!layer.toggle_pin(0));
+        assert_eq!(layer.pinned_columns, 0);
+        assert_eq!(layer.shown_names, vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_aggregate_column() {
+        let records = vec![
+            vec![Value::test_int(1), Value::test_string("x")],
+            vec![Value::test_nothing(), Value::test_string("y")],
+            vec![Value::test_int(4), Value::test_string("z")],
+        ];
+        let aggregates = [Aggregate::Count, Aggregate::Sum, Aggregate::Mean];
+
+        assert_eq!(
+            aggregate_column(&records, 0, &aggregates),
+            Some(vec![
+                Value::test_int(2),
+                Value::test_int(5),
+                Value::test_float(2.5)
+            ])
+        );
+        assert_eq!(aggregate_column(&records, 1, &aggregates), None);
+    }
+
     #[test]
     fn test_estimate_page_size() {
         // Test with header
@@ -19,6 +19,8 @@ use std::cmp::{Ordering, max};
 pub struct TableWidget<'a> {
     columns: &'a [String],
     data: &'a [Vec<NuText>],
+    footer: &'a [Vec<NuText>],
+    pinned_columns: usize,
     index_row: usize,
     index_column: usize,
     config: TableConfig,
@@ -38,6 +40,8 @@ impl<'a> TableWidget<'a> {
     pub fn new(
         columns: &'a [String],
         data: &'a [Vec<NuText>],
+        footer: &'a [Vec<NuText>],
+        pinned_columns: usize,
         style_computer: &'a StyleComputer<'a>,
         index_row: usize,
         index_column: usize,
@@ -47,6 +51,8 @@ impl<'a> TableWidget<'a> {
         Self {
             columns,
             data,
+            footer,
+            pinned_columns,
             style_computer,
             index_row,
             index_column,
@@ -120,6 +126,14 @@ impl TableWidget<'_> {
             return;
         }
 
+        // The footer goes at the bottom, under a line, if there is room left for data
+        let mut footer_height = 0;
+        if !self.footer.is_empty() && data_height > self.footer.len() as u16 + 1 {
+            footer_height = self.footer.len() as u16 + 1;
+            data_height -= footer_height;
+        }
+        let footer_y = data_y + data_height + 1;
+
         let mut width = area.x;
         let mut data = &self.data[self.index_row..];
         if data.len() > data_height as usize {
@@ -153,14 +167,23 @@ impl TableWidget<'_> {
             return;
         }
 
-        for col in self.index_column..self.columns.len() {
-            let need_split_line = state.count_columns > 0 && width < area.width;
+        // Pinned columns scrolled out of the window are still shown first, but the cursor only
+        // moves over the window, so they aren't part of the layout
+        let pinned = self.pinned_columns.min(self.index_column);
+        let mut count_rendered = 0;
+        for col in (0..pinned).chain(self.index_column..self.columns.len()) {
+            let in_window = col >= self.index_column;
+            let need_split_line = count_rendered > 0 && width < area.width;
             if need_split_line {
                 width += render_split_line(buf, width, area.y, area.height, show_head, separator_s);
             }
 
             let mut column = create_column(data, col);
-            let column_width = calculate_column_width(&column);
+            let mut footer = create_column(self.footer, col);
+            let column_width = max(
+                calculate_column_width(&column),
+                calculate_column_width(&footer),
+            );
 
             let mut head = String::from(&self.columns[col]);
             let head_width = string_width(&head);
@@ -188,6 +211,7 @@ impl TableWidget<'_> {
                 }
 
                 use_space = w;
+                truncate_list(&mut footer, use_space as usize);
             }
 
             if show_head {
@@ -204,22 +228,31 @@ impl TableWidget<'_> {
                 w += render_space(buf, w, head_y, 1, padding_r);
 
                 let x = w - padding_r - use_space;
-                state.layout.push(&head, x, head_y, use_space, 1);
+                if in_window {
+                    state.layout.push(&head, x, head_y, use_space, 1);
+                }
             }
 
             let column_rows = column.iter().map(|(t, s)| (t, *s));
 
             width += render_space(buf, width, data_y, data_height, padding_l);
+            if footer_height > 0 {
+                let footer_rows = footer.iter().map(|(t, s)| (t, *s));
+                render_column(buf, width, footer_y, use_space, footer_rows);
+            }
             width += render_column(buf, width, data_y, use_space, column_rows);
             width += render_space(buf, width, data_y, data_height, padding_r);
 
-            for (row, (text, _)) in column.iter().enumerate() {
-                let x = width - padding_r - use_space;
-                let y = data_y + row as u16;
-                state.layout.push(text, x, y, use_space, 1);
-            }
+            if in_window {
+                for (row, (text, _)) in column.iter().enumerate() {
+                    let x = width - padding_r - use_space;
+                    let y = data_y + row as u16;
+                    state.layout.push(text, x, y, use_space, 1);
+                }
 
-            state.count_columns += 1;
+                state.count_columns += 1;
+            }
+            count_rendered += 1;
 
             if show_overflow_indicator {
                 break;
@@ -243,6 +276,10 @@ impl TableWidget<'_> {
                 render_space(buf, width, head_y, 1, rest);
             }
         }
+
+        if footer_height > 0 {
+            render_footer_border(buf, area, footer_y - 1, separator_s);
+        }
     }
 
     // header at the left; header is always 1 line
@@ -532,6 +569,23 @@ fn render_header_borders(buf: &mut Buffer, area: Rect, span: u16, style: NuStyle
     (height.saturating_sub(2), height)
 }
 
+/// Draw the line above the footer, crossing the lines between columns.
+fn render_footer_border(buf: &mut Buffer, area: Rect, y: u16, style: NuStyle) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(nu_style_to_tui(style));
+    block.render(Rect::new(area.x, y, area.width, 1), buf);
+
+    for x in area.x..area.right() {
+        let crosses_line = buf
+            .cell((x, y.saturating_sub(1)))
+            .is_some_and(|cell| cell.symbol() == "│");
+        if crosses_line {
+            render_inner_connector(buf, x, y, style);
+        }
+    }
+}
+
 fn render_index(
     buf: &mut Buffer,
 
@@ -25,7 +25,7 @@ use tui_tree_widget::{Tree, TreeState};
 ///
 /// This parses ANSI escape codes in the input string and converts them
 /// to ratatui Span objects with appropriate styles.
-fn ansi_string_to_line(ansi_text: &str) -> Line<'static> {
+pub(crate) fn ansi_string_to_line(ansi_text: &str) -> Line<'static> {
     let mut spans = Vec::new();
 
     for block in get_blocks(ansi_text) {
@@ -13,4 +13,5 @@ mod tree;
 mod tui;
 mod types;
 
+pub(crate) use app::ansi_string_to_line;
 pub use command::ExploreConfigCommand;
@@ -3,7 +3,9 @@ mod default_context;
 mod explore;
 mod explore_config;
 mod explore_regex;
+mod ui;
 
 pub use default_context::add_explore_context;
 pub use explore::{Explore, ExploreConfig};
 pub use explore_regex::ExploreRegex;
+pub use ui::{Pick, Ui, UiForm, UiPanel, UiSelect};
@@ -0,0 +1,296 @@
+use super::terminal::{LineInput, UiTerminal, next_key, run_in_terminal};
+use nu_engine::command_prelude::*;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Paragraph},
+};
+use std::io;
+
+/// The height of one field, including its border.
+const FIELD_HEIGHT: u16 = 3;
+
+#[derive(Clone)]
+pub struct UiForm;
+
+impl Command for UiForm {
+    fn name(&self) -> &str {
+        "ui form"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ui form")
+            .input_output_types(vec![(Type::record(), Type::Any)])
+            .named(
+                "title",
+                SyntaxShape::String,
+                "A title to show above the form.",
+                Some('t'),
+            )
+            .category(Category::Platform)
+    }
+
+    fn description(&self) -> &str {
+        "Edit the fields of a record in a full-screen form."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Each column of the input record becomes a field, starting out with the column's value. Use Tab
+and the arrow keys to move between fields, and Enter on the last field or Ctrl-S to submit the form,
+which returns the edited record. Esc cancels the form and returns nothing.
+
+Fields that start out as an int, float or bool have to stay one, and are returned as that type.
+Fields that start out empty are returned as null if they are left empty, and all other fields are
+returned as strings."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["prompt", "dialog", "input", "edit", "tui"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let title: Option<String> = call.get_flag(engine_state, stack, "title")?;
+        let config = stack.get_config(engine_state);
+
+        let value = input.into_value(head)?;
+        let span = value.span();
+        let record = value.into_record()?;
+        if record.is_empty() {
+            return Err(ShellError::IncorrectValue {
+                msg: "the form needs at least one field".into(),
+                val_span: span,
+                call_span: head,
+            });
+        }
+
+        let mut form = Form {
+            fields: record
+                .into_iter()
+                .map(|(name, original)| {
+                    let text = match &original {
+                        Value::String { val, .. } => val.clone(),
+                        Value::Nothing { .. } => String::new(),
+                        other => other.to_expanded_string(", ", &config),
+                    };
+                    Field {
+                        name,
+                        original,
+                        input: LineInput::new(text),
+                    }
+                })
+                .collect(),
+            focus: 0,
+            message: None,
+        };
+
+        let submitted = run_in_terminal(head, |terminal| form.run(terminal, title.as_deref()))?;
+        if !submitted {
+            return Ok(PipelineData::empty());
+        }
+
+        let record = form
+            .fields
+            .iter()
+            .map(|field| {
+                let value = field.parse(head).unwrap_or_else(|_| Value::nothing(head));
+                (field.name.clone(), value)
+            })
+            .collect::<Record>();
+        Ok(Value::record(record, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Ask for a name and a port number.",
+                example: "{name: '', port: 8080} | ui form --title 'New server'",
+                result: None,
+            },
+            Example {
+                description: "Edit some settings and save them.",
+                example: "open settings.json | ui form | save --force settings.json",
+                result: None,
+            },
+        ]
+    }
+}
+
+struct Field {
+    name: String,
+    /// The value the field started out with, which decides how its text is parsed.
+    original: Value,
+    input: LineInput,
+}
+
+impl Field {
+    /// Parse the text of the field into a value of the type it started out with.
+    fn parse(&self, span: Span) -> Result<Value, String> {
+        let text = self.input.text();
+        match &self.original {
+            Value::Int { .. } => text
+                .trim()
+                .parse()
+                .map(|val| Value::int(val, span))
+                .map_err(|_| format!("`{}` must be an int", self.name)),
+            Value::Float { .. } => text
+                .trim()
+                .parse()
+                .map(|val| Value::float(val, span))
+                .map_err(|_| format!("`{}` must be a float", self.name)),
+            Value::Bool { .. } => match text.trim() {
+                "true" => Ok(Value::bool(true, span)),
+                "false" => Ok(Value::bool(false, span)),
+                _ => Err(format!("`{}` must be true or false", self.name)),
+            },
+            Value::Nothing { .. } if text.is_empty() => Ok(Value::nothing(span)),
+            _ => Ok(Value::string(text, span)),
+        }
+    }
+}
+
+struct Form {
+    fields: Vec<Field>,
+    focus: usize,
+    /// Why the form couldn't be submitted.
+    message: Option<String>,
+}
+
+impl Form {
+    /// Check every field, focusing the first invalid one. Returns whether all are valid.
+    fn validate(&mut self) -> bool {
+        let invalid =
+            self.fields.iter().enumerate().find_map(|(index, field)| {
+                field.parse(Span::unknown()).err().map(|err| (index, err))
+            });
+        match invalid {
+            Some((index, message)) => {
+                self.focus = index;
+                self.message = Some(message);
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn draw_field(&self, frame: &mut ratatui::Frame, index: usize, area: Rect) {
+        let field = &self.fields[index];
+        let focused = index == self.focus;
+        let mut block = Block::bordered().title(field.name.as_str());
+        if focused {
+            block = block.border_style(Style::new().yellow());
+        }
+        frame.render_widget(Paragraph::new(field.input.text()).block(block), area);
+        if focused {
+            frame.set_cursor_position((area.x + 1 + field.input.cursor_width(), area.y + 1));
+        }
+    }
+
+    /// Run the form until it's submitted or cancelled. Returns whether it was submitted.
+    fn run(&mut self, terminal: &mut UiTerminal, title: Option<&str>) -> io::Result<bool> {
+        let last = self.fields.len() - 1;
+        loop {
+            terminal.draw(|frame| {
+                let mut block = Block::bordered();
+                if let Some(title) = title {
+                    block = block.title(title);
+                }
+                let [form_area, help_area] =
+                    Layout::vertical([Constraint::Min(FIELD_HEIGHT + 2), Constraint::Length(1)])
+                        .areas(frame.area());
+                let inner = block.inner(form_area);
+                frame.render_widget(block, form_area);
+
+                // Scroll so the focused field is always visible
+                let visible = usize::from(inner.height / FIELD_HEIGHT).max(1);
+                let first = (self.focus + 1).saturating_sub(visible);
+                for (row, index) in (first..self.fields.len()).take(visible).enumerate() {
+                    let area = Rect {
+                        y: inner.y + FIELD_HEIGHT * row as u16,
+                        height: FIELD_HEIGHT.min(inner.height),
+                        ..inner
+                    };
+                    self.draw_field(frame, index, area);
+                }
+
+                let help = match &self.message {
+                    Some(message) => Line::from(message.as_str()).red(),
+                    None => {
+                        Line::from("tab/↑↓ move · enter next · ctrl-s submit · esc cancel").dim()
+                    }
+                };
+                frame.render_widget(help, help_area);
+            })?;
+
+            let Some(key) = next_key(None)? else {
+                continue;
+            };
+            let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+            match key.code {
+                KeyCode::Esc => return Ok(false),
+                KeyCode::Char('s') if ctrl => {
+                    if self.validate() {
+                        return Ok(true);
+                    }
+                }
+                KeyCode::Enter if self.focus == last => {
+                    if self.validate() {
+                        return Ok(true);
+                    }
+                }
+                KeyCode::Enter | KeyCode::Tab | KeyCode::Down => {
+                    self.focus = (self.focus + 1).min(last);
+                }
+                KeyCode::BackTab | KeyCode::Up => self.focus = self.focus.saturating_sub(1),
+                _ => {
+                    if self.fields[self.focus].input.handle_key(&key) {
+                        self.message = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(original: Value, text: &str) -> Field {
+        Field {
+            name: "field".into(),
+            original,
+            input: LineInput::new(text.into()),
+        }
+    }
+
+    #[test]
+    fn fields_keep_their_type() {
+        let span = Span::test_data();
+        assert_eq!(
+            field(Value::test_int(1), " 42 ").parse(span),
+            Ok(Value::test_int(42))
+        );
+        assert_eq!(
+            field(Value::test_bool(true), "false").parse(span),
+            Ok(Value::test_bool(false))
+        );
+        assert!(field(Value::test_float(1.5), "abc").parse(span).is_err());
+        assert_eq!(
+            field(Value::test_nothing(), "").parse(span),
+            Ok(Value::test_nothing())
+        );
+        assert_eq!(
+            field(Value::test_nothing(), "x").parse(span),
+            Ok(Value::test_string("x"))
+        );
+    }
+}
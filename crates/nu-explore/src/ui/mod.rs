@@ -0,0 +1,74 @@
+//! Full-screen widgets for scripts: `ui panel`, `ui select` and `ui form`.
+//!
+//! Unlike `explore`, these are small building blocks that take structured data and return what
+//! the user picked or entered, so scripts can build interactive tools out of them.
+
+mod form;
+mod panel;
+mod pick;
+mod select;
+mod selector;
+mod terminal;
+
+use nu_engine::{command_prelude::*, get_full_help};
+
+pub use form::UiForm;
+pub use panel::UiPanel;
+pub use pick::Pick;
+pub use select::UiSelect;
+
+#[derive(Clone)]
+pub struct Ui;
+
+impl Command for Ui {
+    fn name(&self) -> &str {
+        "ui"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ui")
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .category(Category::Platform)
+    }
+
+    fn description(&self) -> &str {
+        "Full-screen terminal widgets for interactive scripts."
+    }
+
+    fn extra_description(&self) -> &str {
+        "You must use one of the following subcommands. Using this command as-is will only produce this help message."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["tui", "widget", "interactive", "dialog"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::string(
+            get_full_help(self, engine_state, stack, call.head),
+            call.head,
+        )
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Pick a branch to check out.",
+                example: "git branch --format '%(refname:short)' | lines | ui select | git checkout $in",
+                result: None,
+            },
+            Example {
+                description: "Ask for a few settings at once.",
+                example: "{name: '', port: 8080} | ui form --title 'New server'",
+                result: None,
+            },
+        ]
+    }
+}
@@ -0,0 +1,185 @@
+use super::terminal::{UiTerminal, next_key, run_in_terminal};
+use crate::explore_config::ansi_string_to_line;
+use nu_engine::{command_prelude::*, eval_call};
+use nu_protocol::{
+    ast::{self, Argument, Expr, Expression},
+    debugger::WithoutDebug,
+};
+use ratatui::{
+    crossterm::{event::KeyCode, terminal::size},
+    layout::{Constraint, Layout},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Paragraph},
+};
+use std::{collections::HashMap, io};
+
+#[derive(Clone)]
+pub struct UiPanel;
+
+impl Command for UiPanel {
+    fn name(&self) -> &str {
+        "ui panel"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ui panel")
+            .input_output_types(vec![(Type::Any, Type::Nothing)])
+            .named(
+                "title",
+                SyntaxShape::String,
+                "A title to show above the panel.",
+                Some('t'),
+            )
+            .category(Category::Platform)
+    }
+
+    fn description(&self) -> &str {
+        "Show a value in a full-screen, scrollable panel until a key is pressed."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Strings are shown as they are, other values are rendered like `table` does. Use the arrow
+keys, Page Up and Page Down to scroll, and q, Esc or Enter to close the panel."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["pager", "message", "dialog", "view", "tui"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let title: Option<String> = call.get_flag(engine_state, stack, "title")?;
+
+        let text = match input.into_value(head)? {
+            Value::String { val, .. } => val,
+            value => render_table(engine_state, stack, value, head)?,
+        };
+        let lines: Vec<Line> = text.lines().map(ansi_string_to_line).collect();
+
+        run_in_terminal(head, |terminal| show(terminal, &lines, title.as_deref()))?;
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Show the contents of a file.",
+                example: "open --raw README.md | ui panel --title README.md",
+                result: None,
+            },
+            Example {
+                description: "Show a table of the largest files before asking to delete them.",
+                example: "ls | sort-by size --reverse | first 20 | ui panel --title 'Largest files'",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Render a value with the `table` command, sized to fit in a bordered panel.
+pub(super) fn render_table(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    value: Value,
+    head: Span,
+) -> Result<String, ShellError> {
+    let Some(decl_id) = engine_state.find_decl(b"table", &[]) else {
+        let config = stack.get_config(engine_state);
+        return Ok(value.to_expanded_string("\n", &config));
+    };
+
+    // Leave room for the panel's borders
+    let width = size().map_or(80, |(width, _)| i64::from(width)) - 2;
+    let call = ast::Call {
+        decl_id,
+        head,
+        arguments: vec![Argument::Named((
+            Spanned {
+                item: "width".to_string(),
+                span: head,
+            },
+            None,
+            Some(Expression::new_unknown(Expr::Int(width), head, Type::Int)),
+        ))],
+        parser_info: HashMap::new(),
+    };
+    let mut stack = stack.start_collect_value();
+    let output = eval_call::<WithoutDebug>(
+        engine_state,
+        &mut stack,
+        &call,
+        PipelineData::value(value, None),
+    )?;
+    output.collect_string("", &stack.get_config(engine_state))
+}
+
+fn show(terminal: &mut UiTerminal, lines: &[Line], title: Option<&str>) -> io::Result<()> {
+    let mut scroll: usize = 0;
+    let mut page = 1;
+    loop {
+        terminal.draw(|frame| {
+            let [panel_area, help_area] =
+                Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+            page = usize::from(panel_area.height.saturating_sub(2)).max(1);
+            scroll = scroll.min(lines.len().saturating_sub(page));
+
+            let mut block = Block::bordered();
+            if let Some(title) = title {
+                block = block.title(title);
+            }
+            let visible = lines
+                .iter()
+                .skip(scroll)
+                .take(page)
+                .cloned()
+                .collect::<Vec<_>>();
+            frame.render_widget(Paragraph::new(visible).block(block), panel_area);
+
+            let position = format!(
+                "{}-{} of {}",
+                (scroll + 1).min(lines.len()),
+                (scroll + page).min(lines.len()),
+                lines.len()
+            );
+            let help = format!("↑↓ scroll · q close · {position}");
+            frame.render_widget(Line::from(help).dim(), help_area);
+        })?;
+
+        let Some(key) = next_key(None)? else {
+            continue;
+        };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => return Ok(()),
+            KeyCode::Up | KeyCode::Char('k') => scroll = scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => scroll += 1,
+            KeyCode::PageUp => scroll = scroll.saturating_sub(page),
+            KeyCode::PageDown | KeyCode::Char(' ') => scroll += page,
+            KeyCode::Home | KeyCode::Char('g') => scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => scroll = lines.len(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_without_table_command() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        let list = Value::test_list(vec![Value::test_int(1), Value::test_string("two")]);
+
+        let text = render_table(&engine_state, &mut stack, list, Span::test_data())
+            .expect("rendering works");
+        assert_eq!(text, "[1\ntwo]");
+    }
+}
@@ -0,0 +1,406 @@
+use super::{
+    panel::render_table,
+    selector::{Selector, Source, item_label, picked_items},
+    terminal::run_in_terminal,
+};
+use nu_engine::{ClosureEval, command_prelude::*};
+use nu_protocol::{
+    Config, OutDest,
+    ast::{Casing, PathMember},
+    engine::{Closure, Redirection},
+    shell_error::{generic::GenericError, io::IoError},
+};
+use std::{
+    sync::{
+        Arc,
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+};
+
+/// How many items are taken from the input at once, so that the finder stays responsive while an
+/// endless stream is still coming in.
+const MAX_ITEMS_PER_POLL: usize = 1000;
+
+#[derive(Clone)]
+pub struct Pick;
+
+impl Command for Pick {
+    fn name(&self) -> &str {
+        "pick"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("pick")
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .switch(
+                "multi",
+                "Allow picking several items with Tab, and return a list.",
+                Some('m'),
+            )
+            .named(
+                "preview",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any])),
+                "A closure that is given the highlighted item and returns its preview.",
+                Some('p'),
+            )
+            .named(
+                "display",
+                SyntaxShape::CellPath,
+                "Show and match this field of each item instead of the whole item.",
+                Some('d'),
+            )
+            .named(
+                "query",
+                SyntaxShape::String,
+                "Start out with this filter.",
+                Some('q'),
+            )
+            .switch(
+                "complete",
+                "Pick from the completions of `commandline complete --detailed`, and put the picked one into the command line.",
+                Some('c'),
+            )
+            .category(Category::Platform)
+    }
+
+    fn description(&self) -> &str {
+        "Interactively pick items from a stream with a fuzzy finder."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Items are shown as soon as they arrive, so the finder can be used while a slow command is still
+producing them. Text from external commands is split into lines. Typing filters the items, best
+matches first. Use the arrow keys to move, Enter to pick the highlighted item and Esc to cancel,
+which returns nothing. With `--multi`, Tab toggles items and Enter returns all toggled items.
+
+Unlike piping through an external fuzzy finder, the picked items are returned as they were
+given, so records stay records. The preview closure's output is shown next to the list; strings
+are shown as they are, and other values as a table.
+
+With `--complete`, the finder works as a completion menu: it takes the completions of
+`commandline complete --detailed`, shows their values, and puts the picked completion into the
+command line in place of the text it completes. Bind it to a key with `executehostcommand` to use
+it in the REPL."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["fzf", "fuzzy", "finder", "select", "choose", "menu"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let multi = call.has_flag(engine_state, stack, "multi")?;
+        let preview: Option<Closure> = call.get_flag(engine_state, stack, "preview")?;
+        let display: Option<CellPath> = call.get_flag(engine_state, stack, "display")?;
+        let query: Option<String> = call.get_flag(engine_state, stack, "query")?;
+        let complete = call.has_flag(engine_state, stack, "complete")?;
+
+        if complete && multi {
+            return Err(ShellError::IncompatibleParameters {
+                left_message: "only one completion can be put into the command line".into(),
+                left_span: call.get_flag_span(stack, "complete").unwrap_or(head),
+                right_message: "but several items can be picked with this".into(),
+                right_span: call.get_flag_span(stack, "multi").unwrap_or(head),
+            });
+        }
+        // Completions are shown by their value, like the completion menu does
+        let display = display.or_else(|| {
+            complete.then(|| CellPath {
+                members: vec![PathMember::string(
+                    "value".into(),
+                    false,
+                    Casing::Sensitive,
+                    head,
+                )],
+            })
+        });
+
+        let preview = preview.map(|closure| {
+            // Anything the preview writes to stderr would end up on top of the finder
+            let stack = stack.push_redirection(None, Some(Redirection::Pipe(OutDest::Null)));
+            ClosureEval::new_preserve_out_dest(engine_state, &stack, closure)
+        });
+
+        let mut source = PickSource {
+            receiver: read_in_background(input, head)?,
+            loading: true,
+            items: Vec::new(),
+            display,
+            config: stack.get_config(engine_state),
+            preview,
+            engine_state,
+            stack,
+            head,
+        };
+
+        let mut selector = Selector::new(Vec::new(), multi).with_filter(query.unwrap_or_default());
+        let picked = run_in_terminal(head, |terminal| selector.run(terminal, None, &mut source))?;
+        let picked = picked_items(source.items, picked, multi, head);
+
+        if complete {
+            if !picked.is_nothing() {
+                insert_completion(engine_state, &picked, head)?;
+            }
+            return Ok(PipelineData::empty());
+        }
+        Ok(picked.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Pick a file, previewing its contents.",
+                example: "ls | pick --display name --preview {|file| open --raw $file.name | lines | first 50 | str join (char nl) }",
+                result: None,
+            },
+            Example {
+                description: "Pick several lines of an external command's output.",
+                example: "^git log --oneline | pick --multi",
+                result: None,
+            },
+            Example {
+                description: "Pick a command from the history, starting with a filter.",
+                example: "history | reverse | pick --display command --query cargo | get command",
+                result: None,
+            },
+            Example {
+                description: "Use the finder as a completion menu on Ctrl+T.",
+                example: r#"$env.config.keybindings ++= [{
+    name: fuzzy_completion
+    modifier: control
+    keycode: char_t
+    mode: [emacs vi_insert]
+    event: { send: executehostcommand, cmd: "commandline complete --detailed | pick --complete" }
+}]"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Read the input on another thread, so the finder can start before all of it has arrived.
+fn read_in_background(input: PipelineData, head: Span) -> Result<Receiver<Value>, ShellError> {
+    let items: Box<dyn Iterator<Item = Value> + Send> = match input {
+        PipelineData::ByteStream(stream, ..) => match stream.lines() {
+            Some(lines) => Box::new(lines.map(move |line| match line {
+                Ok(line) => Value::string(line, head),
+                Err(err) => Value::error(err, head),
+            })),
+            None => Box::new(std::iter::empty()),
+        },
+        input => Box::new(input.into_iter()),
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("pick input".into())
+        .spawn(move || {
+            for item in items {
+                // The finder is gone, so the rest of the input isn't needed
+                if sender.send(item).is_err() {
+                    break;
+                }
+            }
+        })
+        .map_err(|err| {
+            IoError::new_with_additional_context(err, head, None, "Could not read the input")
+        })?;
+    Ok(receiver)
+}
+
+/// Put a completion picked with `--complete` into the command line, in place of the text it
+/// completes, and move the cursor after it.
+fn insert_completion(
+    engine_state: &EngineState,
+    completion: &Value,
+    head: Span,
+) -> Result<(), ShellError> {
+    let not_a_completion = || {
+        ShellError::Generic(
+            GenericError::new(
+                "Not a completion",
+                "expected a record with a `value` and a `span`",
+                completion.span(),
+            )
+            .with_help("pass the output of `commandline complete --detailed` to `pick --complete`"),
+        )
+    };
+
+    let record = completion.as_record().map_err(|_| not_a_completion())?;
+    let value = record
+        .get("value")
+        .and_then(|value| value.as_str().ok())
+        .ok_or_else(not_a_completion)?;
+    let span = record
+        .get("span")
+        .and_then(|span| span.as_record().ok())
+        .ok_or_else(not_a_completion)?;
+    let offset = |name| {
+        span.get(name)
+            .and_then(|offset| offset.as_int().ok())
+            .and_then(|offset| usize::try_from(offset).ok())
+            .ok_or_else(not_a_completion)
+    };
+    let (start, end) = (offset("start")?, offset("end")?);
+
+    let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+    if start > end || repl.buffer.get(start..end).is_none() {
+        return Err(ShellError::Generic(GenericError::new(
+            "Completion doesn't fit the command line",
+            "the span of this completion is outside of the command line",
+            head,
+        )));
+    }
+    repl.buffer.replace_range(start..end, value);
+    repl.cursor_pos = start + value.len();
+    Ok(())
+}
+
+struct PickSource<'a> {
+    receiver: Receiver<Value>,
+    loading: bool,
+    items: Vec<Value>,
+    display: Option<CellPath>,
+    config: Arc<Config>,
+    preview: Option<ClosureEval>,
+    engine_state: &'a EngineState,
+    stack: &'a mut Stack,
+    head: Span,
+}
+
+impl Source for PickSource<'_> {
+    fn poll(&mut self) -> Vec<String> {
+        let mut labels = Vec::new();
+        while labels.len() < MAX_ITEMS_PER_POLL {
+            match self.receiver.try_recv() {
+                Ok(item) => {
+                    // Items without the displayed field are shown whole, instead of failing halfway
+                    let label = item_label(&item, self.display.as_ref(), &self.config)
+                        .unwrap_or_else(|_| item.to_expanded_string(", ", &self.config));
+                    labels.push(label);
+                    self.items.push(item);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.loading = false;
+                    break;
+                }
+            }
+        }
+        labels
+    }
+
+    fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    fn has_preview(&self) -> bool {
+        self.preview.is_some()
+    }
+
+    fn preview(&mut self, index: usize) -> String {
+        let (Some(closure), Some(item)) = (&mut self.preview, self.items.get(index)) else {
+            return String::new();
+        };
+        closure
+            .run_with_value(item.clone())
+            .and_then(|output| output.into_value(self.head))
+            .and_then(|value| match value {
+                Value::String { val, .. } => Ok(val),
+                value => render_table(self.engine_state, self.stack, value, self.head),
+            })
+            .unwrap_or_else(|err| format!("Error: {err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source<'a>(
+        engine_state: &'a EngineState,
+        stack: &'a mut Stack,
+        receiver: Receiver<Value>,
+    ) -> PickSource<'a> {
+        PickSource {
+            receiver,
+            loading: true,
+            items: Vec::new(),
+            display: None,
+            config: stack.get_config(engine_state),
+            preview: None,
+            engine_state,
+            stack,
+            head: Span::test_data(),
+        }
+    }
+
+    #[test]
+    fn poll_takes_a_limited_number_of_items() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        let (sender, receiver) = mpsc::channel();
+        for i in 0..MAX_ITEMS_PER_POLL + 2 {
+            sender
+                .send(Value::test_int(i as i64))
+                .expect("receiver is alive");
+        }
+        let mut source = source(&engine_state, &mut stack, receiver);
+
+        assert_eq!(source.poll().len(), MAX_ITEMS_PER_POLL);
+        assert_eq!(source.poll(), ["1000", "1001"]);
+        assert!(source.is_loading());
+
+        drop(sender);
+        assert!(source.poll().is_empty());
+        assert!(!source.is_loading());
+        assert_eq!(source.items.len(), MAX_ITEMS_PER_POLL + 2);
+    }
+
+    #[test]
+    fn insert_completion_replaces_its_span() {
+        let engine_state = EngineState::new();
+        {
+            let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+            repl.buffer = "ls --al | length".into();
+            repl.cursor_pos = 7;
+        }
+        let completion = Value::test_record(record! {
+            "value" => Value::test_string("--all"),
+            "span" => Value::test_record(record! {
+                "start" => Value::test_int(3),
+                "end" => Value::test_int(7),
+            }),
+        });
+
+        insert_completion(&engine_state, &completion, Span::test_data())
+            .expect("completion is valid");
+
+        let repl = engine_state.repl_state.lock().expect("repl state mutex");
+        assert_eq!(repl.buffer, "ls --all | length");
+        assert_eq!(repl.cursor_pos, 8);
+    }
+
+    #[test]
+    fn insert_completion_rejects_other_values() {
+        let engine_state = EngineState::new();
+        let out_of_bounds = Value::test_record(record! {
+            "value" => Value::test_string("ls"),
+            "span" => Value::test_record(record! {
+                "start" => Value::test_int(0),
+                "end" => Value::test_int(10),
+            }),
+        });
+
+        for value in [Value::test_string("ls"), out_of_bounds] {
+            assert!(insert_completion(&engine_state, &value, Span::test_data()).is_err());
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use super::{
+    selector::{Selector, item_label, picked_items},
+    terminal::run_in_terminal,
+};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct UiSelect;
+
+impl Command for UiSelect {
+    fn name(&self) -> &str {
+        "ui select"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ui select")
+            .input_output_types(vec![(Type::List(Box::new(Type::Any)), Type::Any)])
+            .named(
+                "title",
+                SyntaxShape::String,
+                "A title to show above the list.",
+                Some('t'),
+            )
+            .switch(
+                "multi",
+                "Allow selecting several items with Tab, and return a list.",
+                Some('m'),
+            )
+            .named(
+                "display",
+                SyntaxShape::CellPath,
+                "Show this field of each item instead of the whole item.",
+                Some('d'),
+            )
+            .category(Category::Platform)
+    }
+
+    fn description(&self) -> &str {
+        "Pick items from a list in a full-screen fuzzy finder."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Typing filters the list, best matches first. Use the arrow keys to move, Enter to pick the
+highlighted item and Esc to cancel, which returns nothing. With `--multi`, Tab toggles the
+highlighted item and Enter returns all toggled items, or just the highlighted one if none are.
+
+The selected items are returned as they were given, not as they are displayed."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["fzf", "fuzzy", "pick", "choose", "menu", "tui"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let title: Option<String> = call.get_flag(engine_state, stack, "title")?;
+        let multi = call.has_flag(engine_state, stack, "multi")?;
+        let display: Option<CellPath> = call.get_flag(engine_state, stack, "display")?;
+        let config = stack.get_config(engine_state);
+
+        let items: Vec<Value> = input.into_iter().collect();
+        let labels = items
+            .iter()
+            .map(|item| item_label(item, display.as_ref(), &config))
+            .collect::<Result<Vec<_>, ShellError>>()?;
+
+        let mut selector = Selector::new(labels, multi);
+        let picked = run_in_terminal(head, |terminal| {
+            selector.run(terminal, title.as_deref(), &mut ())
+        })?;
+        Ok(picked_items(items, picked, multi, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Pick a file to open.",
+                example: "ls | ui select --display name | open $in.name",
+                result: None,
+            },
+            Example {
+                description: "Pick several processes to kill.",
+                example: "ps | ui select --multi --display name --title 'Kill processes' | each { kill $in.pid }",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::{
+        Config,
+        ast::{Casing, PathMember},
+    };
+
+    fn name_path() -> CellPath {
+        CellPath {
+            members: vec![PathMember::test_string("name", false, Casing::Sensitive)],
+        }
+    }
+
+    #[test]
+    fn display_field_is_required() {
+        let config = Config::default();
+        let items = [
+            Value::test_record(record! { "name" => Value::test_string("a") }),
+            Value::test_record(record! { "size" => Value::test_int(1) }),
+        ];
+
+        let label = item_label(&items[0], Some(&name_path()), &config).expect("has a name");
+        assert_eq!(label, "a");
+        assert!(item_label(&items[1], Some(&name_path()), &config).is_err());
+    }
+
+    #[test]
+    fn selected_items_are_returned_as_given() {
+        let items = vec![
+            Value::test_record(record! { "name" => Value::test_string("a") }),
+            Value::test_record(record! { "name" => Value::test_string("b") }),
+        ];
+
+        let picked = picked_items(items.clone(), Some(vec![1]), false, Span::test_data());
+        assert_eq!(picked, items[1]);
+
+        let picked = picked_items(items.clone(), Some(vec![1, 0]), true, Span::test_data());
+        assert_eq!(
+            picked,
+            Value::test_list(vec![items[1].clone(), items[0].clone()])
+        );
+
+        let cancelled = picked_items(items, None, true, Span::test_data());
+        assert!(cancelled.is_nothing());
+    }
+}
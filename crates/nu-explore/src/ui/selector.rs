@@ -0,0 +1,361 @@
+//! The fuzzy finder behind `ui select` and `pick`.
+
+use super::terminal::{LineInput, UiTerminal, next_key};
+use crate::explore_config::ansi_string_to_line;
+use nu_protocol::{Config as NuConfig, Span, Value, ast::CellPath};
+use nucleo_matcher::{
+    Config, Matcher, Utf32Str,
+    pattern::{Atom, AtomKind, CaseMatching, Normalization},
+};
+use ratatui::{
+    crossterm::event::{KeyCode, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+use std::{borrow::Cow, io, time::Duration};
+
+/// How often to check for new items while the input is still being read.
+const LOADING_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Where the items of a [`Selector`] come from, and how to preview them.
+pub(super) trait Source {
+    /// Labels of items that arrived since the last call.
+    fn poll(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether more items may still arrive.
+    fn is_loading(&self) -> bool {
+        false
+    }
+
+    /// Whether items have a preview at all, to decide whether to make room for it.
+    fn has_preview(&self) -> bool {
+        false
+    }
+
+    /// The preview of the item at `index`, which may contain ANSI styles.
+    fn preview(&mut self, _index: usize) -> String {
+        String::new()
+    }
+}
+
+/// A source whose items are all known up front, without previews.
+impl Source for () {}
+
+/// The text shown for an item: the field at `display` if given, or else the whole item.
+pub(super) fn item_label(
+    item: &Value,
+    display: Option<&CellPath>,
+    config: &NuConfig,
+) -> Result<String, nu_protocol::ShellError> {
+    let shown = match display {
+        Some(path) => item.follow_cell_path(&path.members)?,
+        None => Cow::Borrowed(item),
+    };
+    Ok(shown.to_expanded_string(", ", config))
+}
+
+/// The result of a finder: the picked items as a list with `multi`, or else the one picked item.
+/// Nothing is returned if the finder was cancelled.
+pub(super) fn picked_items(
+    items: Vec<Value>,
+    picked: Option<Vec<usize>>,
+    multi: bool,
+    span: Span,
+) -> Value {
+    let Some(picked) = picked else {
+        return Value::nothing(span);
+    };
+    let mut items: Vec<Option<Value>> = items.into_iter().map(Some).collect();
+    let mut picked = picked
+        .into_iter()
+        .filter_map(|index| items.get_mut(index).and_then(Option::take));
+    if multi {
+        Value::list(picked.collect(), span)
+    } else {
+        picked.next().unwrap_or(Value::nothing(span))
+    }
+}
+
+/// The state of the fuzzy finder.
+pub(super) struct Selector {
+    labels: Vec<String>,
+    multi: bool,
+    filter: LineInput,
+    /// Indices of the labels matching the filter, best match first.
+    matches: Vec<usize>,
+    /// Which labels are toggled, with `--multi`.
+    toggled: Vec<bool>,
+    list: ListState,
+    matcher: Matcher,
+    /// The preview of the highlighted item, and which item it's for.
+    preview: Option<(usize, Vec<Line<'static>>)>,
+}
+
+impl Selector {
+    pub(super) fn new(labels: Vec<String>, multi: bool) -> Self {
+        let mut config = Config::DEFAULT;
+        config.prefer_prefix = true;
+        let mut selector = Self {
+            toggled: vec![false; labels.len()],
+            labels,
+            multi,
+            filter: LineInput::default(),
+            matches: Vec::new(),
+            list: ListState::default(),
+            matcher: Matcher::new(config),
+            preview: None,
+        };
+        selector.refilter(true);
+        selector
+    }
+
+    /// Start out with a filter, like it was typed in.
+    pub(super) fn with_filter(mut self, filter: String) -> Self {
+        self.filter = LineInput::new(filter);
+        self.refilter(true);
+        self
+    }
+
+    fn push(&mut self, labels: Vec<String>) {
+        if labels.is_empty() {
+            return;
+        }
+        self.toggled
+            .resize(self.toggled.len() + labels.len(), false);
+        self.labels.extend(labels);
+        self.refilter(false);
+    }
+
+    /// Match the labels against the filter again. Unless `reset` is set, the highlighted item
+    /// stays highlighted if it still matches.
+    fn refilter(&mut self, reset: bool) {
+        let current = if reset { None } else { self.current() };
+
+        if self.filter.text().is_empty() {
+            self.matches = (0..self.labels.len()).collect();
+        } else {
+            let atom = Atom::new(
+                self.filter.text(),
+                CaseMatching::Smart,
+                Normalization::Smart,
+                AtomKind::Fuzzy,
+                false,
+            );
+            let mut buf = Vec::new();
+            let mut scored: Vec<(usize, u16)> = self
+                .labels
+                .iter()
+                .enumerate()
+                .filter_map(|(index, label)| {
+                    atom.score(Utf32Str::new(label, &mut buf), &mut self.matcher)
+                        .map(|score| (index, score))
+                })
+                .collect();
+            // The sort is stable, so equally good matches keep their order
+            scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+            self.matches = scored.into_iter().map(|(index, _)| index).collect();
+        }
+
+        let row = current
+            .and_then(|current| self.matches.iter().position(|&index| index == current))
+            .unwrap_or(0);
+        self.list.select((!self.matches.is_empty()).then_some(row));
+    }
+
+    fn current(&self) -> Option<usize> {
+        self.list
+            .selected()
+            .and_then(|row| self.matches.get(row).copied())
+    }
+
+    fn move_by(&mut self, rows: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let last = self.matches.len() - 1;
+        let row = self.list.selected().unwrap_or(0);
+        self.list
+            .select(Some(row.saturating_add_signed(rows).min(last)));
+    }
+
+    /// The picked label indices, in their original order.
+    fn picked(&self) -> Vec<usize> {
+        let toggled: Vec<usize> = (0..self.labels.len())
+            .filter(|&index| self.toggled[index])
+            .collect();
+        if toggled.is_empty() {
+            self.current().into_iter().collect()
+        } else {
+            toggled
+        }
+    }
+
+    fn update_preview(&mut self, source: &mut dyn Source) {
+        let current = self.current();
+        if self.preview.as_ref().map(|(index, _)| *index) == current {
+            return;
+        }
+        self.preview = current.map(|index| {
+            let text = source.preview(index);
+            (index, text.lines().map(ansi_string_to_line).collect())
+        });
+    }
+
+    /// Run the finder until something is picked or it's cancelled. Returns the indices of the
+    /// picked items, or `None` if it was cancelled.
+    pub(super) fn run(
+        &mut self,
+        terminal: &mut UiTerminal,
+        title: Option<&str>,
+        source: &mut dyn Source,
+    ) -> io::Result<Option<Vec<usize>>> {
+        let mut page = 1;
+        loop {
+            self.push(source.poll());
+            if source.has_preview() {
+                self.update_preview(source);
+            }
+
+            terminal.draw(|frame| {
+                let [filter_area, main_area, help_area] = Layout::vertical([
+                    Constraint::Length(3),
+                    Constraint::Min(1),
+                    Constraint::Length(1),
+                ])
+                .areas(frame.area());
+                let [list_area, preview_area] = if source.has_preview() {
+                    Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .areas(main_area)
+                } else {
+                    [main_area, Rect::default()]
+                };
+                page = usize::from(list_area.height.saturating_sub(2)).max(1);
+
+                let filter_block = Block::bordered().title(title.unwrap_or("Filter"));
+                frame.render_widget(
+                    Paragraph::new(self.filter.text()).block(filter_block),
+                    filter_area,
+                );
+                frame.set_cursor_position((
+                    filter_area.x + 1 + self.filter.cursor_width(),
+                    filter_area.y + 1,
+                ));
+
+                let items = self.matches.iter().map(|&index| {
+                    let label = self.labels[index].as_str();
+                    match (self.multi, self.toggled[index]) {
+                        (false, _) => ListItem::new(label),
+                        (true, false) => ListItem::new(format!("[ ] {label}")),
+                        (true, true) => ListItem::new(format!("[x] {label}")).bold(),
+                    }
+                });
+                let loading = if source.is_loading() { " …" } else { "" };
+                let count = format!("{}/{}{loading}", self.matches.len(), self.labels.len());
+                let list = List::new(items)
+                    .block(Block::bordered().title(Line::from(count).right_aligned()))
+                    .highlight_style(Style::new().reversed())
+                    .highlight_symbol("> ");
+                frame.render_stateful_widget(list, list_area, &mut self.list);
+
+                if source.has_preview() {
+                    let lines = self
+                        .preview
+                        .as_ref()
+                        .map(|(_, lines)| lines.clone())
+                        .unwrap_or_default();
+                    frame.render_widget(
+                        Paragraph::new(lines).block(Block::bordered().title("Preview")),
+                        preview_area,
+                    );
+                }
+
+                let help = if self.multi {
+                    "↑↓ move · tab toggle · enter accept · esc cancel"
+                } else {
+                    "↑↓ move · enter accept · esc cancel"
+                };
+                frame.render_widget(Line::from(help).dim(), help_area);
+            })?;
+
+            let timeout = source.is_loading().then_some(LOADING_POLL_INTERVAL);
+            let Some(key) = next_key(timeout)? else {
+                continue;
+            };
+            let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+            match key.code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Enter => {
+                    let picked = self.picked();
+                    if !picked.is_empty() {
+                        return Ok(Some(picked));
+                    }
+                }
+                KeyCode::Up => self.move_by(-1),
+                KeyCode::Char('p') if ctrl => self.move_by(-1),
+                KeyCode::Down => self.move_by(1),
+                KeyCode::Char('n') if ctrl => self.move_by(1),
+                KeyCode::PageUp => self.move_by(-(page as isize)),
+                KeyCode::PageDown => self.move_by(page as isize),
+                KeyCode::Tab if self.multi => {
+                    if let Some(index) = self.current() {
+                        self.toggled[index] = !self.toggled[index];
+                        self.move_by(1);
+                    }
+                }
+                _ => {
+                    let before = self.filter.text().to_owned();
+                    if self.filter.handle_key(&key) && self.filter.text() != before {
+                        self.refilter(true);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selector(labels: &[&str]) -> Selector {
+        Selector::new(labels.iter().map(|label| label.to_string()).collect(), true)
+    }
+
+    #[test]
+    fn filter_ranks_matches() {
+        let mut selector = selector(&["main", "feature/menu", "release"]);
+        assert_eq!(selector.matches, [0, 1, 2]);
+
+        selector = selector.with_filter("men".into());
+        assert_eq!(selector.matches, [1]);
+        assert_eq!(selector.current(), Some(1));
+
+        selector = selector.with_filter("zzz".into());
+        assert!(selector.matches.is_empty());
+        assert!(selector.picked().is_empty());
+    }
+
+    #[test]
+    fn picks_toggled_items_in_order() {
+        let mut selector = selector(&["a", "b", "c"]);
+        assert_eq!(selector.picked(), [0]);
+
+        selector.toggled[2] = true;
+        selector.toggled[0] = true;
+        assert_eq!(selector.picked(), [0, 2]);
+    }
+
+    #[test]
+    fn new_items_keep_the_highlight() {
+        let mut selector = selector(&["a", "b"]);
+        selector.move_by(1);
+        selector.push(vec!["c".into(), "d".into()]);
+        assert_eq!(selector.matches, [0, 1, 2, 3]);
+        assert_eq!(selector.current(), Some(1));
+        assert_eq!(selector.toggled.len(), 4);
+    }
+}
@@ -0,0 +1,177 @@
+//! Terminal handling and line editing shared by the `ui` widgets.
+
+use nu_protocol::{ShellError, Span, shell_error::generic::GenericError};
+use ratatui::{
+    Terminal,
+    backend::CrosstermBackend,
+    crossterm::{
+        event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+        execute,
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    },
+};
+use std::{
+    io::{self, Stderr},
+    time::Duration,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// The widgets draw on stderr, so their result can be piped or redirected from stdout.
+pub(crate) type UiTerminal = Terminal<CrosstermBackend<Stderr>>;
+
+fn terminal_error(error: &str, cause: impl std::fmt::Display, span: Span) -> ShellError {
+    ShellError::Generic(GenericError::new(
+        error.to_string(),
+        format!("terminal error: {cause}"),
+        span,
+    ))
+}
+
+/// Run a widget on the alternate screen, restoring the terminal afterwards even if it fails.
+///
+/// A widget returns [`io::ErrorKind::Interrupted`] when the user presses `Ctrl-C`, which is
+/// reported like any other interrupt.
+pub(crate) fn run_in_terminal<T>(
+    span: Span,
+    widget: impl FnOnce(&mut UiTerminal) -> io::Result<T>,
+) -> Result<T, ShellError> {
+    enable_raw_mode().map_err(|e| terminal_error("Could not enable raw mode", e, span))?;
+
+    let mut stderr = io::stderr();
+    if let Err(err) = execute!(stderr, EnterAlternateScreen) {
+        let _ = disable_raw_mode();
+        return Err(terminal_error(
+            "Could not enter alternate screen",
+            err,
+            span,
+        ));
+    }
+
+    let result = Terminal::new(CrosstermBackend::new(stderr)).and_then(|mut terminal| {
+        let result = widget(&mut terminal);
+        terminal.show_cursor().and(result)
+    });
+
+    let restored = disable_raw_mode().and_then(|()| execute!(io::stderr(), LeaveAlternateScreen));
+
+    let value = result.map_err(|err| match err.kind() {
+        io::ErrorKind::Interrupted => ShellError::Interrupted { span },
+        _ => terminal_error("Terminal UI error", err, span),
+    })?;
+    restored.map_err(|e| terminal_error("Could not restore the terminal", e, span))?;
+    Ok(value)
+}
+
+/// Wait for the next key press, for at most `timeout` if given. Returns `None` on timeout and for
+/// other events, like a resize, after which the widget should just be drawn again.
+pub(crate) fn next_key(timeout: Option<Duration>) -> io::Result<Option<KeyEvent>> {
+    if let Some(timeout) = timeout
+        && !event::poll(timeout)?
+    {
+        return Ok(None);
+    }
+    match event::read()? {
+        Event::Key(key) if key.kind != KeyEventKind::Release => {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                Err(io::ErrorKind::Interrupted.into())
+            } else {
+                Ok(Some(key))
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+/// A single line of editable text.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct LineInput {
+    text: String,
+    /// The cursor position, as a byte offset into `text`.
+    cursor: usize,
+}
+
+impl LineInput {
+    pub(crate) fn new(text: String) -> Self {
+        let cursor = text.len();
+        Self { text, cursor }
+    }
+
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The width of the text before the cursor, to place the terminal cursor.
+    pub(crate) fn cursor_width(&self) -> u16 {
+        u16::try_from(self.text[..self.cursor].width()).unwrap_or(u16::MAX)
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.text[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    /// Apply an editing key. Returns whether the key was used.
+    pub(crate) fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.text.len(),
+            KeyCode::Char('u') if ctrl => {
+                self.text.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            KeyCode::Char(_) if ctrl => return false,
+            KeyCode::Char(c) => {
+                self.text.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+            }
+            KeyCode::Backspace => {
+                let start = self.prev_boundary();
+                self.text.drain(start..self.cursor);
+                self.cursor = start;
+            }
+            KeyCode::Delete => {
+                let end = self.next_boundary();
+                self.text.drain(self.cursor..end);
+            }
+            KeyCode::Left => self.cursor = self.prev_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.text.len(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut LineInput, code: KeyCode) {
+        input.handle_key(&KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn edits_multibyte_text() {
+        let mut input = LineInput::new("añb".into());
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.text(), "ab");
+        press(&mut input, KeyCode::Char('é'));
+        assert_eq!(input.text(), "aéb");
+        assert_eq!(input.cursor_width(), 2);
+        press(&mut input, KeyCode::Home);
+        press(&mut input, KeyCode::Delete);
+        assert_eq!(input.text(), "éb");
+    }
+}
@@ -1,16 +1,18 @@
 use crate::{LanguageServer, span_to_range};
 use lsp_types::{
-    Diagnostic, DiagnosticSeverity, PublishDiagnosticsParams, Uri,
+    Diagnostic, DiagnosticSeverity, DiagnosticTag, NumberOrString, PublishDiagnosticsParams, Uri,
     notification::{Notification, PublishDiagnostics},
 };
 use miette::{IntoDiagnostic, Result, miette};
+use nu_parser::LintRule;
 
 impl LanguageServer {
     pub(crate) fn publish_diagnostics_for_file(&mut self, uri: Uri) -> Result<()> {
         let mut engine_state = self.new_engine_state(Some(&uri));
         engine_state.generate_nu_constant();
 
-        let Some((_, span, working_set)) = self.parse_file(&mut engine_state, &uri, true) else {
+        let Some((block, span, working_set)) = self.parse_file(&mut engine_state, &uri, true)
+        else {
             return Ok(());
         };
 
@@ -49,6 +51,28 @@ impl LanguageServer {
             });
         }
 
+        // Deprecations are already among the parse warnings
+        let lints = nu_parser::lint(&working_set, &block)
+            .into_iter()
+            .filter(|lint| lint.rule != LintRule::DeprecatedCommand)
+            .filter(|lint| span.contains_span(lint.span));
+        for lint in lints {
+            let message = match lint.help {
+                Some(help) => format!("{}\n{help}", lint.message),
+                None => lint.message,
+            };
+
+            diagnostics.diagnostics.push(Diagnostic {
+                range: span_to_range(&lint.span, file, span.start),
+                severity: Some(DiagnosticSeverity::WARNING),
+                code: Some(NumberOrString::String(lint.rule.name().into())),
+                tags: (lint.rule == LintRule::UnusedVariable)
+                    .then(|| vec![DiagnosticTag::UNNECESSARY]),
+                message,
+                ..Default::default()
+            });
+        }
+
         self.connection
             .sender
             .send(lsp_server::Message::Notification(
@@ -80,6 +104,27 @@ mod tests {
         "message": "Variable not found.",
         "severity": 1
     }]))]
+    #[case::lints("lints.nu", None, serde_json::json!([
+        {
+            "range": {
+                "start": { "line": 1, "character": 6 },
+                "end": { "line": 1, "character": 12 }
+            },
+            "message": "The variable `unused` is never used.\nRemove it, or name it `_unused` if it is meant to be unused.",
+            "severity": 2,
+            "code": "unused-variable",
+            "tags": [1]
+        },
+        {
+            "range": {
+                "start": { "line": 5, "character": 18 },
+                "end": { "line": 5, "character": 21 }
+            },
+            "message": "The parameter `arg` of the exported command `typed` has no type.\nAnnotate it, as in `arg: string`, so callers know what to pass.",
+            "severity": 2,
+            "code": "missing-type-annotation"
+        }
+    ]))]
     fn publish_diagnostics(
         #[case] filename: &str,
         #[case] update_op: Option<(&str, lsp_types::Range)>,
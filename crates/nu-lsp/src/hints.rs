@@ -21,6 +21,40 @@ fn type_short_name(t: &Type) -> String {
     }
 }
 
+/// For pipelines spanning multiple lines, the type of the data each element
+/// pipes into the next one, shown at the end of the line
+fn extract_pipeline_hints(
+    block: &Block,
+    offset: &usize,
+    file: &FullTextDocument,
+) -> Vec<InlayHint> {
+    block
+        .pipelines
+        .iter()
+        .flat_map(|pipeline| pipeline.elements.windows(2))
+        .filter_map(|elements| {
+            let [element, next] = elements else {
+                return None;
+            };
+            let position = span_to_range(&element.expr.span, file, *offset).end;
+            let next_line = span_to_range(&next.expr.span, file, *offset).start.line;
+            if next_line == position.line || element.expr.ty == Type::Any {
+                return None;
+            }
+            Some(InlayHint {
+                kind: Some(InlayHintKind::TYPE),
+                label: InlayHintLabel::String(format!(": {}", type_short_name(&element.expr.ty))),
+                position,
+                text_edits: None,
+                tooltip: None,
+                data: None,
+                padding_left: None,
+                padding_right: None,
+            })
+        })
+        .collect()
+}
+
 fn extract_inlay_hints_from_expression(
     expr: &Expression,
     working_set: &StateWorkingSet,
@@ -132,6 +166,12 @@ fn extract_inlay_hints_from_expression(
             }
             hints
         }
+        Expr::Block(block_id)
+        | Expr::Closure(block_id)
+        | Expr::Subexpression(block_id)
+        | Expr::RowCondition(block_id) => {
+            extract_pipeline_hints(working_set.get_block(*block_id), offset, file)
+        }
         _ => vec![],
     }
 }
@@ -148,8 +188,10 @@ impl LanguageServer {
         file: &FullTextDocument,
     ) -> Vec<InlayHint> {
         let closure = |e| extract_inlay_hints_from_expression(e, working_set, &offset, file);
-        let mut results = Vec::new();
+        let mut results = extract_pipeline_hints(block, &offset, file);
         block.flat_map(working_set, &closure, &mut results);
+        // pipeline hints of a block come before those of its expressions
+        results.sort_by_key(|hint| (hint.position.line, hint.position.character));
         results
     }
 }
@@ -273,6 +315,13 @@ mod tests {
             }
         ])
     )]
+    #[case::pipeline_type(
+        "pipeline.nu",
+        false,
+        serde_json::json!([
+            { "position": { "line": 0, "character": 7 }, "label": ": string", "kind": 1 }
+        ])
+    )]
     #[case::script_loaded_on_init(
         "type.nu",
         true,
@@ -3,10 +3,9 @@ use lsp_server::{Connection, IoThreads, Message, Response, ResponseError};
 use lsp_textdocument::{FullTextDocument, TextDocuments};
 use lsp_types::{
     InlayHint, MessageType, OneOf, Position, Range, ReferencesOptions, RenameOptions,
-    SemanticToken, SemanticTokenType, SemanticTokensLegend, SemanticTokensOptions,
-    SemanticTokensServerCapabilities, ServerCapabilities, SignatureHelpOptions,
-    TextDocumentSyncKind, Uri, WorkDoneProgressOptions, WorkspaceFolder,
-    WorkspaceFoldersServerCapabilities, WorkspaceServerCapabilities,
+    SemanticToken, SemanticTokensLegend, SemanticTokensOptions, SemanticTokensServerCapabilities,
+    ServerCapabilities, SignatureHelpOptions, TextDocumentSyncKind, Uri, WorkDoneProgressOptions,
+    WorkspaceFolder, WorkspaceFoldersServerCapabilities, WorkspaceServerCapabilities,
     request::{self, Request},
 };
 use miette::{IntoDiagnostic, Result, miette};
@@ -149,10 +148,9 @@ impl LanguageServer {
             workspace_symbol_provider: Some(OneOf::Left(true)),
             semantic_tokens_provider: Some(
                 SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
-                    // NOTE: only internal command names with space supported for now
                     legend: SemanticTokensLegend {
-                        token_types: vec![SemanticTokenType::FUNCTION],
-                        token_modifiers: vec![],
+                        token_types: semantic_tokens::TOKEN_TYPES.to_vec(),
+                        token_modifiers: semantic_tokens::TOKEN_MODIFIERS.to_vec(),
                     },
                     full: Some(lsp_types::SemanticTokensFullOptions::Bool(true)),
                     ..Default::default()
@@ -1,25 +1,111 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use lsp_textdocument::FullTextDocument;
-use lsp_types::{SemanticToken, SemanticTokens, SemanticTokensParams};
+use lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensParams,
+};
 use nu_protocol::{
-    Span,
+    Span, VarId,
     ast::{Block, Expr, Expression, Traverse},
     engine::StateWorkingSet,
 };
 
 use crate::{LanguageServer, span_to_range};
 
+/// Token types in the order of the legend sent to the client
+pub(crate) const TOKEN_TYPES: [SemanticTokenType; 4] = [
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::PROPERTY,
+];
+
+/// Token modifiers in the order of the legend sent to the client
+pub(crate) const TOKEN_MODIFIERS: [SemanticTokenModifier; 1] = [SemanticTokenModifier::DECLARATION];
+
+const FUNCTION: u32 = 0;
+const VARIABLE: u32 = 1;
+const PARAMETER: u32 = 2;
+const PROPERTY: u32 = 3;
+
+const DECLARATION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Token {
+    span: Span,
+    token_type: u32,
+    modifiers: u32,
+}
+
+impl Token {
+    fn new(span: Span, token_type: u32) -> Self {
+        Self {
+            span,
+            token_type,
+            modifiers: 0,
+        }
+    }
+}
+
+/// Span of the name in a parameter declaration like `--flag(-f)`, `opt?` or `...rest`
+fn parameter_name_span(span: Span, working_set: &StateWorkingSet) -> Span {
+    let content = working_set.get_span_contents(span);
+    let leading = content
+        .iter()
+        .take_while(|c| matches!(c, b'-' | b'.' | b'$'))
+        .count();
+    let len = content
+        .iter()
+        .skip(leading)
+        .take_while(|c| !matches!(c, b'?' | b'(' | b':' | b'='))
+        .count();
+    Span::new(span.start + leading, span.start + leading + len)
+}
+
+/// Parameters declared in signatures of custom commands and closures,
+/// with the span of their names
+fn extract_parameters_from_expression(
+    expr: &Expression,
+    working_set: &StateWorkingSet,
+) -> Vec<(VarId, Span)> {
+    let signature = match &expr.expr {
+        Expr::Signature(signature) => signature.as_ref(),
+        Expr::Closure(block_id) => &working_set.get_block(*block_id).signature,
+        _ => return vec![],
+    };
+    signature
+        .required_positional
+        .iter()
+        .chain(&signature.optional_positional)
+        .chain(&signature.rest_positional)
+        .filter_map(|arg| arg.var_id)
+        .chain(signature.named.iter().filter_map(|flag| flag.var_id))
+        .map(|var_id| {
+            let span = working_set.get_variable(var_id).declaration_span;
+            (var_id, parameter_name_span(span, working_set))
+        })
+        .collect()
+}
+
 /// Important to keep spans in increasing order,
 /// since `SemanticToken`s are created by relative positions
 /// to one's previous token
 ///
 /// Currently supported types:
 /// 1. internal command names with space
+/// 2. variables and parameters, and the cell path members accessed on them
 fn extract_semantic_tokens_from_expression(
     expr: &Expression,
     working_set: &StateWorkingSet,
-) -> Vec<Span> {
+    parameters: &HashSet<VarId>,
+) -> Vec<Token> {
+    let variable_type = |var_id: &VarId| {
+        if parameters.contains(var_id) {
+            PARAMETER
+        } else {
+            VARIABLE
+        }
+    };
     match &expr.expr {
         Expr::Call(call) => {
             let command_name = working_set.get_span_contents(call.head);
@@ -29,11 +115,29 @@ fn extract_semantic_tokens_from_expression(
                 && !command_name.starts_with(b"export")
                 && !command_name.starts_with(b"overlay")
             {
-                vec![call.head]
+                vec![Token::new(call.head, FUNCTION)]
             } else {
                 vec![]
             }
         }
+        Expr::Var(var_id) => {
+            // excluding the `$` sign
+            let span = Span::new(expr.span.start.saturating_add(1), expr.span.end);
+            vec![Token::new(span, variable_type(var_id))]
+        }
+        Expr::VarDecl(var_id) => {
+            let span = parameter_name_span(expr.span, working_set);
+            vec![Token {
+                span,
+                token_type: variable_type(var_id),
+                modifiers: DECLARATION,
+            }]
+        }
+        Expr::FullCellPath(fcp) if matches!(fcp.head.expr, Expr::Var(_)) => fcp
+            .tail
+            .iter()
+            .map(|member| Token::new(member.span(), PROPERTY))
+            .collect(),
         _ => vec![],
     }
 }
@@ -57,14 +161,32 @@ impl LanguageServer {
         offset: usize,
         file: &FullTextDocument,
     ) -> Vec<SemanticToken> {
-        let mut results = Vec::new();
-        let closure = |e| extract_semantic_tokens_from_expression(e, working_set);
+        let mut declarations = Vec::new();
+        let closure = |e| extract_parameters_from_expression(e, working_set);
+        block.flat_map(working_set, &closure, &mut declarations);
+        let parameters: HashSet<VarId> = declarations.iter().map(|(var_id, _)| *var_id).collect();
+
+        let mut results: Vec<Token> = declarations
+            .into_iter()
+            .map(|(_, span)| Token {
+                span,
+                token_type: PARAMETER,
+                modifiers: DECLARATION,
+            })
+            .collect();
+        let closure = |e| extract_semantic_tokens_from_expression(e, working_set, &parameters);
         block.flat_map(working_set, &closure, &mut results);
+        // declarations of command parameters are found in both the signature and the body
+        results.retain(|token| token.span.end > token.span.start);
+        results.sort_by_key(|token| (token.span.start, token.span.end));
+        results.dedup_by_key(|token| token.span);
+
         let mut last_token_line = 0;
         let mut last_token_char = 0;
         let mut last_span = Span::unknown();
         let mut tokens = vec![];
-        for sp in results {
+        for token in results {
+            let sp = token.span;
             let range = span_to_range(&sp, file, offset);
             // shouldn't happen
             if sp < last_span {
@@ -78,9 +200,8 @@ impl LanguageServer {
                 delta_start,
                 delta_line: range.end.line.saturating_sub(last_token_line),
                 length: range.end.character.saturating_sub(range.start.character),
-                // 0 means function in semantic_token_legend
-                token_type: 0,
-                token_modifiers_bitset: 0,
+                token_type: token.token_type,
+                token_modifiers_bitset: token.modifiers,
             });
             last_span = sp;
             last_token_line = range.end.line;
@@ -148,4 +269,38 @@ mod tests {
             ]})
         );
     }
+
+    #[test]
+    fn semantic_token_variables() {
+        let (client_connection, _recv) = initialize_language_server(None, None);
+
+        let mut script = fixtures();
+        script.push("lsp/semantic_tokens/variables.nu");
+        let script = path_to_uri(&script);
+
+        open_unchecked(&client_connection, script.clone());
+        let resp = send_semantic_token_request(&client_connection, script);
+
+        assert_json_eq!(
+            result_from_message(resp),
+            serde_json::json!(
+            { "data": [
+                // delta_line, delta_start, length, token_type, token_modifiers_bitset
+                // parameter declarations: name, loud
+                0, 11, 4, 2, 1,
+                0, 16, 4, 2, 1,
+                // variable declaration and parameter reference: msg, name
+                1, 6, 3, 1, 1,
+                0, 16, 4, 2, 0,
+                // loud, msg, env, LANG
+                1, 6, 4, 2, 0,
+                0, 8, 3, 1, 0,
+                0, 14, 3, 1, 0,
+                0, 4, 4, 3, 0,
+                // closure parameter: x
+                3, 15, 1, 2, 1,
+                0, 4, 1, 2, 0
+            ]})
+        );
+    }
 }
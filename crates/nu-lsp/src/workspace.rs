@@ -62,6 +62,16 @@ fn find_nu_scripts_in_folder(folder_uri: &Uri) -> Result<Vec<std::path::PathBuf>
     Ok(out)
 }
 
+/// The last word of a name, for a quick check of whether a file can refer to it.
+///
+/// Module-qualified command calls like `mod name  cmd  name` only keep the words
+/// of the command name, not the spaces in between.
+fn last_word(name: &[u8]) -> &[u8] {
+    name.split(|c| c.is_ascii_whitespace())
+        .rfind(|word| !word.is_empty())
+        .unwrap_or(name)
+}
+
 /// HACK: when current file is imported (use keyword) by others in the workspace,
 /// it will get parsed a second time via `parse_module_block`, so that its definitions'
 /// ids are renewed, making it harder to track the references.
@@ -101,17 +111,28 @@ impl IDTracker {
 }
 
 impl LanguageServer {
-    /// Get initial workspace folders from initialization response
+    /// Get initial workspace folders from initialization response,
+    /// falling back to the root uri for clients without workspace folder support
     pub(crate) fn initialize_workspace_folders(
         &mut self,
         init_params: serde_json::Value,
     ) -> Option<()> {
-        if let Some(array) = init_params.get("workspaceFolders") {
+        if let Some(array) = init_params.get("workspaceFolders")
+            && !array.is_null()
+        {
             let folders: Vec<WorkspaceFolder> = serde_json::from_value(array.clone()).ok()?;
             for folder in folders {
                 self.workspace_folders.insert(folder.name.clone(), folder);
             }
+            return Some(());
         }
+        let uri: Uri = serde_json::from_value(init_params.get("rootUri")?.clone()).ok()?;
+        let name = uri_to_path(&uri)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| uri.to_string());
+        self.workspace_folders
+            .insert(name.clone(), WorkspaceFolder { uri, name });
         Some(())
     }
 
@@ -478,7 +499,7 @@ impl LanguageServer {
 
             let len = scripts.len();
             let definition_span = Self::find_definition_span_by_id(&working_set, &id_tracker.id);
-            let bytes_to_search = id_tracker.name.to_owned();
+            let bytes_to_search = last_word(&id_tracker.name).to_owned();
             let finder = memchr::memmem::Finder::new(&bytes_to_search);
 
             for (i, fp) in scripts.iter().enumerate() {
@@ -803,6 +824,15 @@ mod tests {
         "baz.nu", (0, 12), true,
         vec![make_location_ref("bar", 0, 4, 0, 12), make_location_ref("baz", 6, 4, 6, 12)],
     )]
+    // The call through the alias has extra spaces between the words of the command name
+    #[case::module_qualified_reference(
+        "foo.nu", (10, 20), true,
+        vec![
+            make_location_ref("foo", 10, 16, 10, 29),
+            make_location_ref("baz", 1, 41, 1, 56),
+            make_location_ref("baz", 9, 20, 9, 33),
+        ],
+    )]
     fn reference_in_workspace(
         #[case] main_file: &str,
         #[case] cursor_position: (u32, u32),
@@ -881,6 +911,46 @@ mod tests {
         assert!(has_response);
     }
 
+    /// Clients without workspace folder support only send the root uri
+    #[test]
+    fn reference_in_root_uri() {
+        let mut script = fixtures();
+        script.push("lsp/workspace");
+        let (client_connection, _recv) = initialize_language_server(
+            None,
+            Some(serde_json::json!({
+                "workspaceFolders": serde_json::Value::Null,
+                "rootUri": path_to_uri(&script),
+            })),
+        );
+        script.push("foo.nu");
+        let script = path_to_uri(&script);
+
+        open_unchecked(&client_connection, script.clone());
+        let messages = send_reference_request(&client_connection, script.clone(), 0, 12, 6);
+        let Some(Message::Response(r)) = messages
+            .into_iter()
+            .find(|message| matches!(message, Message::Response(_)))
+        else {
+            panic!("no response to the reference request");
+        };
+        let result = r.response_result.unwrap();
+        let bar = script.to_string().replace("foo.nu", "bar.nu");
+        assert!(
+            result
+                .as_array()
+                .unwrap()
+                .contains(&make_location_ref(&bar, 4, 2, 4, 7))
+        );
+    }
+
+    #[test]
+    fn last_word_of_names() {
+        assert_eq!(super::last_word(b"cmd name long"), b"long");
+        assert_eq!(super::last_word(b"foooo"), b"foooo");
+        assert_eq!(super::last_word(b"trailing "), b"trailing");
+    }
+
     #[rstest]
     #[case::quoted_command(
         "foo.nu", (6, 12), (6, 11),
@@ -0,0 +1,308 @@
+//! The code formatter behind `format nu` and `nu --format`.
+//!
+//! Code is laid out token by token, so comments and the contents of strings stay as they were
+//! written. The parsed AST tells which brackets hold code and which hold data; their contents
+//! are formatted in turn and indented one level deeper when the brackets span several lines.
+
+use crate::{TokenContents, lex, parse};
+use nu_protocol::{
+    ParseError, Span,
+    ast::{Expr, Expression, Traverse},
+    config::FormatterConfig,
+    engine::{EngineState, StateWorkingSet},
+};
+use std::collections::HashMap;
+
+/// What a pair of brackets holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Contents {
+    /// Blocks and closures, written as `{ ls }` on a single line
+    Code,
+    /// Subexpressions, lists, records, tables and signatures, written as `[1 2]`
+    Data,
+}
+
+fn brackets_in_expression(expr: &Expression) -> Vec<(Span, Contents)> {
+    let contents = match &expr.expr {
+        Expr::Block(_) | Expr::Closure(_) | Expr::RowCondition(_) | Expr::MatchBlock(_) => {
+            Contents::Code
+        }
+        Expr::Subexpression(_)
+        | Expr::List(_)
+        | Expr::Record(_)
+        | Expr::Table(_)
+        | Expr::Signature(_) => Contents::Data,
+        _ => return vec![],
+    };
+    vec![(expr.span, contents)]
+}
+
+/// A part of a line, separated from the previous one by a space
+enum Piece {
+    Text(String),
+    Pipe(String),
+    Semicolon,
+}
+
+struct Formatter<'a> {
+    source: &'a [u8],
+    offset: usize,
+    /// Spans of the tokens in brackets, with what they hold
+    brackets: HashMap<Span, Contents>,
+    indent_width: usize,
+    max_line_length: usize,
+}
+
+impl Formatter<'_> {
+    fn bytes(&self, start: usize, end: usize) -> &[u8] {
+        &self.source[start - self.offset..end - self.offset]
+    }
+
+    fn text(&self, span: Span) -> String {
+        String::from_utf8_lossy(self.bytes(span.start, span.end)).into_owned()
+    }
+
+    fn indent(&self, depth: usize) -> String {
+        " ".repeat(depth * self.indent_width)
+    }
+
+    /// Formats the code between `start` and `end` as lines indented by `depth` levels.
+    ///
+    /// Line breaks are kept where they were written, and runs of blank lines are
+    /// collapsed into one.
+    fn format_block(
+        &self,
+        start: usize,
+        end: usize,
+        depth: usize,
+    ) -> Result<Vec<String>, ParseError> {
+        let (tokens, err) = lex(self.bytes(start, end), start, &[], &[], false);
+        if let Some(err) = err {
+            return Err(err);
+        }
+
+        let mut lines = Vec::new();
+        let mut line = Vec::new();
+        let mut previous_end = start;
+        for token in tokens {
+            // Line breaks are found from the source, since the lexer drops the end of a line
+            // before a pipe continuing the pipeline
+            if token.contents == TokenContents::Eol {
+                continue;
+            }
+            let newlines = self
+                .bytes(previous_end, token.span.start)
+                .iter()
+                .filter(|c| **c == b'\n')
+                .count();
+            if newlines > 0 && !line.is_empty() {
+                self.push_line(&mut lines, std::mem::take(&mut line), depth);
+                if newlines > 1 {
+                    lines.push(String::new());
+                }
+            }
+            previous_end = token.span.end;
+
+            let piece = match token.contents {
+                TokenContents::Pipe
+                | TokenContents::ErrGreaterPipe
+                | TokenContents::OutErrGreaterPipe => Piece::Pipe(self.text(token.span)),
+                TokenContents::Semicolon => Piece::Semicolon,
+                TokenContents::Comment => Piece::Text(self.text(token.span).trim_end().into()),
+                TokenContents::Item => match self.brackets.get(&token.span) {
+                    Some(contents) => {
+                        Piece::Text(self.format_brackets(token.span, *contents, depth)?)
+                    }
+                    None => Piece::Text(self.text(token.span)),
+                },
+                _ => Piece::Text(self.text(token.span)),
+            };
+            line.push(piece);
+        }
+        if !line.is_empty() {
+            self.push_line(&mut lines, line, depth);
+        }
+        Ok(lines)
+    }
+
+    /// Adds a line made of `pieces`, splitting it before its pipes when it is too long.
+    fn push_line(&self, lines: &mut Vec<String>, pieces: Vec<Piece>, depth: usize) {
+        let indent = self.indent(depth);
+        let line = format!("{indent}{}", join(&pieces));
+        let width = line.lines().next().unwrap_or_default().chars().count();
+        if width <= self.max_line_length {
+            lines.push(line);
+            return;
+        }
+
+        let mut segment = Vec::new();
+        for piece in pieces {
+            if matches!(piece, Piece::Pipe(_)) && !segment.is_empty() {
+                lines.push(format!("{indent}{}", join(&segment)));
+                segment.clear();
+            }
+            segment.push(piece);
+        }
+        if !segment.is_empty() {
+            lines.push(format!("{indent}{}", join(&segment)));
+        }
+    }
+
+    /// Formats the contents of the brackets at `span`, keeping them on a single line
+    /// unless they were written over several lines.
+    fn format_brackets(
+        &self,
+        span: Span,
+        contents: Contents,
+        depth: usize,
+    ) -> Result<String, ParseError> {
+        let source = self.bytes(span.start, span.end);
+        let (open, close) = match (source.first(), source.last()) {
+            (Some(b'{'), Some(b'}')) => ('{', '}'),
+            (Some(b'['), Some(b']')) => ('[', ']'),
+            (Some(b'('), Some(b')')) => ('(', ')'),
+            _ => return Ok(self.text(span)),
+        };
+        if source.len() < 2 {
+            return Ok(self.text(span));
+        }
+        let padded = contents == Contents::Code && open == '{';
+
+        // The parameters of a closure stay right after the opening brace
+        let mut start = span.start + 1;
+        let mut params = String::new();
+        if padded {
+            let inner = &source[1..source.len() - 1];
+            let leading = inner.iter().take_while(|c| c.is_ascii_whitespace()).count();
+            let rest = &inner[leading..];
+            let params_len = if rest.starts_with(b"||") {
+                Some(2)
+            } else if rest.starts_with(b"|") {
+                rest[1..].iter().position(|c| *c == b'|').map(|end| end + 2)
+            } else {
+                None
+            };
+            if let Some(len) = params_len {
+                params = String::from_utf8_lossy(&rest[..len]).into_owned();
+                start += leading + len;
+            }
+        }
+
+        let lines = self.format_block(start, span.end - 1, depth + 1)?;
+        if lines.len() <= 1 && !source.contains(&b'\n') {
+            let inner = lines.first().map_or("", |line| line.trim_start());
+            return Ok(if padded && !inner.is_empty() {
+                format!("{open}{params} {inner} {close}")
+            } else {
+                format!("{open}{params}{inner}{close}")
+            });
+        }
+
+        let mut text = format!("{open}{params}\n");
+        for line in lines {
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text.push_str(&self.indent(depth));
+        text.push(close);
+        Ok(text)
+    }
+}
+
+fn join(pieces: &[Piece]) -> String {
+    let mut text = String::new();
+    for piece in pieces {
+        match piece {
+            Piece::Semicolon => text.push(';'),
+            Piece::Text(piece) | Piece::Pipe(piece) => {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(piece);
+            }
+        }
+    }
+    text
+}
+
+/// Formats nushell code, laying it out as configured by `config`.
+///
+/// Code the parser rejects is still formatted, as long as its brackets and strings are
+/// closed. The formatted code is parsed again, and an error is returned if it has more
+/// errors than the original.
+pub fn format_source(
+    engine_state: &EngineState,
+    source: &[u8],
+    config: &FormatterConfig,
+) -> Result<String, ParseError> {
+    let mut working_set = StateWorkingSet::new(engine_state);
+    let offset = working_set.next_span_start();
+    let block = parse(&mut working_set, None, source, false);
+    let errors = working_set.parse_errors.len();
+
+    let mut brackets = Vec::new();
+    block.flat_map(&working_set, &brackets_in_expression, &mut brackets);
+    let formatter = Formatter {
+        source,
+        offset,
+        // An expression comes after those around it, so the innermost one wins for a span
+        brackets: brackets.into_iter().collect(),
+        indent_width: usize::try_from(config.indent_width).unwrap_or(4),
+        max_line_length: usize::try_from(config.max_line_length).unwrap_or(100),
+    };
+
+    let lines = formatter.format_block(offset, offset + source.len(), 0)?;
+    let mut output = lines.join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    let mut working_set = StateWorkingSet::new(engine_state);
+    parse(&mut working_set, None, output.as_bytes(), false);
+    if working_set.parse_errors.len() > errors {
+        return Err(ParseError::InternalError(
+            "the formatted code doesn't parse like the original, so it was left as is".into(),
+            Span::new(offset, offset + source.len()),
+        ));
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn format(source: &str, config: &FormatterConfig) -> String {
+        format_source(&EngineState::new(), source.as_bytes(), config).expect("source should format")
+    }
+
+    #[test]
+    fn normalizes_spaces() {
+        let config = FormatterConfig::default();
+        assert_eq!(format("ls    |   length", &config), "ls | length\n");
+        assert_eq!(format("ls;ls", &config), "ls; ls\n");
+    }
+
+    #[test]
+    fn keeps_comments_and_collapses_blank_lines() {
+        let config = FormatterConfig::default();
+        let source = "\n\necho   'a   b'\n\n\n\necho b   # trailing   \n";
+        assert_eq!(
+            format(source, &config),
+            "echo 'a   b'\n\necho b # trailing\n"
+        );
+    }
+
+    #[test]
+    fn splits_long_pipelines() {
+        let config = FormatterConfig {
+            max_line_length: 20,
+            ..Default::default()
+        };
+        assert_eq!(
+            format("echo abcdef | sort | uniq", &config),
+            "echo abcdef\n| sort\n| uniq\n"
+        );
+    }
+}
@@ -111,6 +111,12 @@ impl Command for KnownExternal {
             .map(CustomExample::to_example)
             .collect()
     }
+
+    fn example_result_unchecked(&self, index: usize) -> bool {
+        self.examples
+            .get(index)
+            .is_some_and(|example| example.unchecked_result)
+    }
 }
 
 /// Transform the args from an `ast::Call` onto a `run-external` call
@@ -2,8 +2,10 @@
 mod deparse;
 mod exportable;
 mod flatten;
+mod format;
 mod known_external;
 mod lex;
+mod lint;
 mod lite_parser;
 mod parse_alias;
 mod parse_bindings;
@@ -27,8 +29,10 @@ pub use deparse::escape_for_script_arg;
 pub use flatten::{
     FlatShape, flatten_block, flatten_expression, flatten_pipeline, flatten_pipeline_element,
 };
+pub use format::format_source;
 pub use known_external::KnownExternal;
 pub use lex::{LexState, Token, TokenContents, lex, lex_n_tokens, lex_signature};
+pub use lint::{LintDiagnostic, LintRule, lint};
 pub use lite_parser::{LiteBlock, LiteCommand, lite_parse};
 pub use nu_protocol::parser_path::*;
 pub use parse_keywords::*;
@@ -0,0 +1,350 @@
+//! The lints behind `nu --check --lint` and the warnings of the language server.
+//!
+//! Lints look at code that parses fine, for what is likely a mistake or hard to use from other
+//! scripts: unused variables, definitions shadowing other commands, deprecated commands and
+//! exported commands with untyped parameters.
+
+use nu_protocol::{
+    BlockId, ParseWarning, Span, SyntaxShape, VarId,
+    ast::{Block, Call, Expr, Expression, Traverse},
+    engine::{CommandType, StateWorkingSet},
+};
+use std::collections::HashSet;
+
+/// The kinds of problems found by [`lint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// A variable that is declared but never used
+    UnusedVariable,
+    /// A definition hiding another command of the same name
+    ShadowedDefinition,
+    /// A call to a deprecated command, or with a deprecated flag
+    DeprecatedCommand,
+    /// A parameter of an exported command without a type annotation
+    MissingTypeAnnotation,
+}
+
+impl LintRule {
+    /// The name of the rule, as shown in diagnostics
+    pub fn name(self) -> &'static str {
+        match self {
+            LintRule::UnusedVariable => "unused-variable",
+            LintRule::ShadowedDefinition => "shadowed-definition",
+            LintRule::DeprecatedCommand => "deprecated-command",
+            LintRule::MissingTypeAnnotation => "missing-type-annotation",
+        }
+    }
+}
+
+/// A problem found by [`lint`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    pub rule: LintRule,
+    pub message: String,
+    pub span: Span,
+    pub help: Option<String>,
+}
+
+/// The parts of the AST the lints look at
+enum Node<'a> {
+    Declared(VarId, Span),
+    Used(VarId),
+    Call(&'a Call),
+    Block(BlockId),
+}
+
+fn nodes_in_expression(expr: &Expression) -> Vec<Node<'_>> {
+    match &expr.expr {
+        Expr::VarDecl(var_id) => vec![Node::Declared(*var_id, expr.span)],
+        Expr::Var(var_id) => vec![Node::Used(*var_id)],
+        Expr::Call(call) => vec![Node::Call(call)],
+        Expr::Block(block_id)
+        | Expr::Closure(block_id)
+        | Expr::Subexpression(block_id)
+        | Expr::RowCondition(block_id) => vec![Node::Block(*block_id)],
+        _ => vec![],
+    }
+}
+
+/// A `def` or `export def`, with the span of the block it is in
+struct Definition {
+    name: String,
+    span: Span,
+    /// `None` for the block of the file
+    scope: Option<Span>,
+    exported: bool,
+}
+
+/// Looks for likely mistakes in a parsed block, returning them in source order.
+pub fn lint(working_set: &StateWorkingSet, block: &Block) -> Vec<LintDiagnostic> {
+    let mut nodes = Vec::new();
+    block.flat_map(working_set, &nodes_in_expression, &mut nodes);
+
+    let mut diagnostics = Vec::new();
+    lint_unused_variables(working_set, block, &nodes, &mut diagnostics);
+    lint_shadowed_definitions(working_set, block, &nodes, &mut diagnostics);
+    for node in &nodes {
+        if let Node::Call(call) = node {
+            lint_deprecations(working_set, call, &mut diagnostics);
+            lint_type_annotations(working_set, call, &mut diagnostics);
+        }
+    }
+
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.span.start, diagnostic.span.end));
+    diagnostics
+}
+
+fn decl_name<'a>(working_set: &'a StateWorkingSet, call: &Call) -> &'a str {
+    working_set.get_decl(call.decl_id).name()
+}
+
+fn declared_variables(call: &Call) -> impl Iterator<Item = VarId> + '_ {
+    call.arguments
+        .iter()
+        .filter_map(|arg| arg.expr())
+        .filter_map(|expr| match expr.expr {
+            Expr::VarDecl(var_id) => Some(var_id),
+            _ => None,
+        })
+}
+
+fn lint_unused_variables(
+    working_set: &StateWorkingSet,
+    block: &Block,
+    nodes: &[Node],
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    // Variables at the top of a file stay in scope for the scripts sourcing it, and exported
+    // constants are used by the scripts importing them
+    let mut ignored = HashSet::new();
+    for pipeline in &block.pipelines {
+        for element in &pipeline.elements {
+            if let Expr::Call(call) = &element.expr.expr {
+                ignored.extend(declared_variables(call));
+            }
+        }
+    }
+    for node in nodes {
+        if let Node::Call(call) = node
+            && decl_name(working_set, call) == "export const"
+        {
+            ignored.extend(declared_variables(call));
+        }
+    }
+
+    let used = nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Used(var_id) => Some(*var_id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    for node in nodes {
+        let Node::Declared(var_id, span) = node else {
+            continue;
+        };
+        if used.contains(var_id) || ignored.contains(var_id) {
+            continue;
+        }
+        let name = String::from_utf8_lossy(working_set.get_span_contents(*span));
+        let name = name.trim_start_matches('$');
+        if name.starts_with('_') {
+            continue;
+        }
+        diagnostics.push(LintDiagnostic {
+            rule: LintRule::UnusedVariable,
+            message: format!("The variable `{name}` is never used."),
+            span: *span,
+            help: Some(format!(
+                "Remove it, or name it `_{name}` if it is meant to be unused."
+            )),
+        });
+    }
+}
+
+fn definitions_in_block(
+    working_set: &StateWorkingSet,
+    block: &Block,
+    scope: Option<Span>,
+    definitions: &mut Vec<Definition>,
+) {
+    for pipeline in &block.pipelines {
+        for element in &pipeline.elements {
+            let Expr::Call(call) = &element.expr.expr else {
+                continue;
+            };
+            let exported = match decl_name(working_set, call) {
+                "def" => false,
+                "export def" => true,
+                _ => continue,
+            };
+            let Some(name) = call.positional_nth(0) else {
+                continue;
+            };
+            if let Some(text) = name.as_string() {
+                definitions.push(Definition {
+                    name: text,
+                    span: name.span,
+                    scope,
+                    exported,
+                });
+            }
+        }
+    }
+}
+
+fn lint_shadowed_definitions(
+    working_set: &StateWorkingSet,
+    block: &Block,
+    nodes: &[Node],
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let mut definitions = Vec::new();
+    definitions_in_block(working_set, block, None, &mut definitions);
+    for node in nodes {
+        if let Node::Block(block_id) = node {
+            let block = working_set.get_block(*block_id);
+            if let Some(span) = block.span {
+                definitions_in_block(working_set, block, Some(span), &mut definitions);
+            }
+        }
+    }
+
+    for definition in &definitions {
+        let Some(scope) = definition.scope else {
+            continue;
+        };
+        let outer = definitions.iter().find(|other| {
+            other.name == definition.name
+                && other
+                    .scope
+                    .is_none_or(|outer| outer != scope && outer.contains_span(scope))
+        });
+        if outer.is_some() {
+            diagnostics.push(LintDiagnostic {
+                rule: LintRule::ShadowedDefinition,
+                message: format!(
+                    "The definition of `{}` shadows a command defined in an outer block.",
+                    definition.name
+                ),
+                span: definition.span,
+                help: Some("Rename it, so each name refers to a single command.".into()),
+            });
+        }
+    }
+
+    // Exported commands are namespaced by their module, so only plain definitions are checked
+    for definition in definitions.iter().filter(|definition| !definition.exported) {
+        let shadowed = working_set
+            .permanent_state
+            .find_decl(definition.name.as_bytes(), &[])
+            .map(|decl_id| working_set.permanent_state.get_decl(decl_id));
+        if let Some(decl) = shadowed
+            && matches!(
+                decl.command_type(),
+                CommandType::Builtin | CommandType::Keyword | CommandType::Plugin
+            )
+        {
+            diagnostics.push(LintDiagnostic {
+                rule: LintRule::ShadowedDefinition,
+                message: format!(
+                    "The definition of `{}` shadows the {} command of the same name.",
+                    definition.name,
+                    decl.command_type()
+                ),
+                span: definition.span,
+                help: Some(format!(
+                    "Rename it, unless it is meant to replace `{}` wherever it is in scope.",
+                    definition.name
+                )),
+            });
+        }
+    }
+}
+
+fn lint_deprecations(
+    working_set: &StateWorkingSet,
+    call: &Call,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let decl = working_set.get_decl(call.decl_id);
+    for entry in decl.deprecation_info() {
+        if let Some(ParseWarning::Deprecated {
+            label, span, help, ..
+        }) = entry.parse_warning(decl.name(), call)
+        {
+            diagnostics.push(LintDiagnostic {
+                rule: LintRule::DeprecatedCommand,
+                message: label,
+                span,
+                help,
+            });
+        }
+    }
+}
+
+/// Whether the parameter declared at `declaration` is followed by a type, as in `name: string`
+fn has_type_annotation(working_set: &StateWorkingSet, declaration: Span, end: usize) -> bool {
+    if declaration.end >= end {
+        return false;
+    }
+    let rest = working_set
+        .get_span_contents(Span::new(declaration.end, end))
+        .trim_ascii_start();
+    // Skip the short form of a flag, as in `--verbose (-v): bool`
+    let rest = match rest.strip_prefix(b"(") {
+        Some(short) => short
+            .iter()
+            .position(|c| *c == b')')
+            .map_or(rest, |close| short[close + 1..].trim_ascii_start()),
+        None => rest,
+    };
+    rest.starts_with(b":")
+}
+
+fn lint_type_annotations(
+    working_set: &StateWorkingSet,
+    call: &Call,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    if decl_name(working_set, call) != "export def" {
+        return;
+    }
+    let (Some(name), Some(signature)) = (call.positional_nth(0), call.positional_nth(1)) else {
+        return;
+    };
+    let (Some(command), Expr::Signature(sig)) = (name.as_string(), &signature.expr) else {
+        return;
+    };
+
+    let positionals = sig
+        .required_positional
+        .iter()
+        .chain(&sig.optional_positional)
+        .chain(&sig.rest_positional)
+        .filter(|param| param.shape == SyntaxShape::Any)
+        .filter_map(|param| Some((param.name.clone(), param.var_id?)));
+    let flags = sig
+        .named
+        .iter()
+        .filter(|flag| flag.arg == Some(SyntaxShape::Any))
+        .filter_map(|flag| Some((format!("--{}", flag.long), flag.var_id?)));
+
+    for (param, var_id) in positionals.chain(flags) {
+        let declaration = working_set.get_variable(var_id).declaration_span;
+        if has_type_annotation(working_set, declaration, signature.span.end) {
+            continue;
+        }
+        diagnostics.push(LintDiagnostic {
+            rule: LintRule::MissingTypeAnnotation,
+            message: format!(
+                "The parameter `{param}` of the exported command `{command}` has no type."
+            ),
+            span: declaration,
+            help: Some(format!(
+                "Annotate it, as in `{param}: string`, so callers know what to pass."
+            )),
+        });
+    }
+}
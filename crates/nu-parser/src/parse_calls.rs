@@ -1,6 +1,8 @@
 use crate::{
     lite_parser::LiteCommand,
-    parse_helpers::{PERCENT_FORCED_BUILTIN_PARSER_INFO, extract_spread_list, garbage},
+    parse_helpers::{
+        PERCENT_FORCED_BUILTIN_PARSER_INFO, extract_spread_list, extract_spread_record, garbage,
+    },
     parse_source::find_dirs_var,
     type_check::type_compatible,
 };
@@ -69,7 +71,12 @@ pub(crate) fn check_call(
         ));
         return CallKind::Invalid;
     } else {
-        for req_flag in sig.named.iter().filter(|x| x.required) {
+        // A spread record may provide the flags, which is only known when the call runs
+        let spreads_record = call
+            .arguments
+            .iter()
+            .any(|arg| matches!(arg, Argument::Spread(expr) if matches!(expr.ty, Type::Record(_))));
+        for req_flag in sig.named.iter().filter(|x| x.required && !spreads_record) {
             if call.named_iter().all(|(n, _, _)| n.item != req_flag.long) {
                 working_set.error(ParseError::MissingRequiredFlag(
                     req_flag.long.clone(),
@@ -603,11 +610,17 @@ fn parse_short_flags(
                 return None;
             } else if let Some(first) = unmatched_short_flags.first() {
                 let contents = working_set.get_span_contents(*first);
+                // `-forse` is more likely a misspelled long flag than a batch of short flags
+                let suggestion = (num_chars > 1)
+                    .then(|| did_you_mean(sig.get_names(), short_flags))
+                    .flatten()
+                    .map(|name| format!("Did you mean: `--{name}`?"))
+                    .unwrap_or("Use `--help` to see available flags".to_owned());
                 working_set.error(ParseError::UnknownFlag(
                     sig.name.clone(),
                     format!("-{}", String::from_utf8_lossy(contents)),
                     *first,
-                    "Use `--help` to see available flags".to_owned(),
+                    suggestion,
                 ));
             }
 
@@ -900,6 +913,33 @@ pub enum ArgumentParsingLevel {
     FirstK { k: usize },
 }
 
+/// Parse a record spread into a call, like `...{force: true}` or `...$options`, which passes its
+/// keys as flags. Returns `None` if the spread value isn't known to be a record, in which case it's
+/// spread as rest arguments.
+fn parse_spread_record(working_set: &mut StateWorkingSet, span: Span) -> Option<Expression> {
+    match working_set.get_span_contents(span) {
+        [b'{', ..] => Some(crate::parser::parse_value(
+            working_set,
+            span,
+            &SyntaxShape::Record(vec![]),
+            None,
+        )),
+        // Only variables, as parsing a subexpression or interpolation twice would add its blocks
+        // twice
+        [b'$', next, ..] if !matches!(next, b'"' | b'\'' | b'(') => {
+            let starting_error_count = working_set.parse_errors.len();
+            let expr = crate::parser::parse_value(working_set, span, &SyntaxShape::Any, None);
+            if matches!(expr.ty, Type::Record(_)) {
+                Some(expr)
+            } else {
+                working_set.parse_errors.truncate(starting_error_count);
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
 pub fn parse_internal_call(
     working_set: &mut StateWorkingSet,
     command_span: Span,
@@ -1192,8 +1232,12 @@ pub fn parse_internal_call(
                 span: spread_arg_span,
                 ..
             }) = extract_spread_list(contents.into_spanned(spans[spans_idx]))
+                .or_else(|| extract_spread_record(contents.into_spanned(spans[spans_idx])))
             {
-                if signature.rest_positional.is_none() && !signature.allows_unknown_args {
+                if let Some(record) = parse_spread_record(working_set, spread_arg_span) {
+                    // The record's keys are checked against the flags when the call runs
+                    call.add_spread(record);
+                } else if signature.rest_positional.is_none() && !signature.allows_unknown_args {
                     working_set.error(ParseError::UnexpectedSpreadArg(
                         signature.call_signature(),
                         arg_span,
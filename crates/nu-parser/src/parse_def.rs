@@ -6,7 +6,7 @@ use crate::{
     parse_pipelines::redirecting_builtin_error,
     parser::{
         ArgumentParsingLevel, CallKind, ParsedInternalCall, compile_block_with_id, parse_attribute,
-        parse_full_signature, parse_internal_call, parse_string,
+        parse_full_signature, parse_internal_call, parse_string, parse_value,
     },
     type_check::check_block_input_output,
 };
@@ -426,7 +426,8 @@ fn parse_def_inner(
 ) -> (Expression, Option<(Vec<u8>, DeclId)>) {
     let spans = lite_command.command_parts();
 
-    let (desc, extra_desc) = working_set.build_desc(&lite_command.comments);
+    let (comments, doc_examples) = take_doc_examples(working_set, &lite_command.comments);
+    let (desc, extra_desc) = working_set.build_desc(&comments);
     let garbage_result =
         |working_set: &mut StateWorkingSet<'_>| (garbage(working_set, Span::concat(spans)), None);
 
@@ -539,6 +540,17 @@ fn parse_def_inner(
     let Ok(has_wrapped) = has_flag_const(working_set, &call, "wrapped") else {
         return garbage_result(working_set);
     };
+    let Ok(has_generator) = has_flag_const(working_set, &call, "generator") else {
+        return garbage_result(working_set);
+    };
+    if has_env && has_generator {
+        working_set.error(ParseError::LabeledError(
+            "Generators can't keep their environment".into(),
+            "--env and --generator can't be used together".into(),
+            call.head,
+        ));
+        return garbage_result(working_set);
+    }
 
     let Some([name_expr, sig_expr, block_expr]) = call.positional_iter().next_array() else {
         working_set.error(ParseError::UnknownState(
@@ -639,8 +651,9 @@ fn parse_def_inner(
             signature.extra_description = extra_desc;
             signature.allows_unknown_args = has_wrapped;
 
-            let (attribute_vals, examples) =
+            let (attribute_vals, mut examples) =
                 handle_special_attributes(attributes, working_set, &mut signature);
+            examples.extend(doc_examples);
 
             let declaration = working_set.get_decl_mut(decl_id);
 
@@ -651,6 +664,7 @@ fn parse_def_inner(
             let block = working_set.get_block_mut(block_id);
             block.signature = signature;
             block.redirect_env = has_env;
+            block.generator = has_generator;
 
             if block.signature.input_output_types.is_empty() {
                 block
@@ -661,7 +675,12 @@ fn parse_def_inner(
 
             let block = working_set.get_block(block_id);
 
-            let typecheck_errors = check_block_input_output(working_set, block);
+            // The output of a generator is what it yields, not what its body returns
+            let typecheck_errors = if has_generator {
+                vec![]
+            } else {
+                check_block_input_output(working_set, block)
+            };
 
             working_set
                 .parse_errors
@@ -692,7 +711,8 @@ fn parse_extern_inner(
 ) -> Expression {
     let spans = lite_command.command_parts();
 
-    let (description, extra_description) = working_set.build_desc(&lite_command.comments);
+    let (comments, doc_examples) = take_doc_examples(working_set, &lite_command.comments);
+    let (description, extra_description) = working_set.build_desc(&comments);
 
     let (name_span, split_id) =
         if spans.len() > 1 && (working_set.get_span_contents(spans[0]) == b"export") {
@@ -792,8 +812,9 @@ fn parse_extern_inner(
                 signature.extra_description = extra_description;
                 signature.allows_unknown_args = true;
 
-                let (attribute_vals, examples) =
+                let (attribute_vals, mut examples) =
                     handle_special_attributes(attributes, working_set, &mut signature);
+                examples.extend(doc_examples);
 
                 let declaration = working_set.get_decl_mut(decl_id);
 
@@ -850,6 +871,144 @@ fn parse_extern_inner(
     Expression::new(working_set, Expr::Call(call), call_span, Type::Any)
 }
 
+/// Takes the `Example:` sections out of the doc comments of a command, returning the other
+/// comment lines and the examples:
+///
+/// ```nushell
+/// # Add two numbers.
+/// #
+/// # Example: Add one and two
+/// #   > add 1 2
+/// #   => 3
+/// def add [a: int, b: int] { $a + $b }
+/// ```
+///
+/// The lines indented under `Example:` and starting with `>` are the code of the example, so an
+/// `Example:` in the middle of the description stays text. A last line starting with `=>` is the
+/// result the example should have. A result that isn't a constant is kept as unchecked.
+fn take_doc_examples(
+    working_set: &mut StateWorkingSet,
+    comments: &[Span],
+) -> (Vec<Span>, Vec<CustomExample>) {
+    // The text of a comment line after the `#`, if it's valid UTF-8
+    fn comment_text(working_set: &StateWorkingSet, span: Span) -> Option<String> {
+        let contents = working_set.get_span_contents(span);
+        std::str::from_utf8(contents.get(1..)?)
+            .ok()
+            .map(String::from)
+    }
+    fn indentation(text: &str) -> usize {
+        text.len() - text.trim_start().len()
+    }
+    fn is_blank(working_set: &StateWorkingSet, span: Span) -> bool {
+        comment_text(working_set, span).is_some_and(|text| text.trim().is_empty())
+    }
+
+    let mut lines: Vec<Span> = vec![];
+    let mut examples = vec![];
+    let mut index = 0;
+
+    while index < comments.len() {
+        let span = comments[index];
+        index += 1;
+
+        let Some(text) = comment_text(working_set, span) else {
+            lines.push(span);
+            continue;
+        };
+        let Some(description) = text.trim_start().strip_prefix("Example:") else {
+            lines.push(span);
+            continue;
+        };
+        let indent = indentation(&text);
+
+        let mut code_lines = vec![];
+        let mut result_span = None;
+        let mut end = index;
+        while let Some(&line_span) = comments.get(end) {
+            let Some(line) = comment_text(working_set, line_span) else {
+                break;
+            };
+            if line.trim().is_empty() || indentation(&line) <= indent {
+                break;
+            }
+            if let Some(result) = line.trim().strip_prefix("=>") {
+                if code_lines.is_empty() {
+                    break;
+                }
+                end += 1;
+                // The `#`, the text before the result, and the `=>`
+                let start = 1 + line.len() - line.trim_start().len() + 2;
+                let start = start + result.len() - result.trim_start().len();
+                result_span = Some(Span::new(
+                    line_span.start + start,
+                    line_span.start + start + result.trim().len(),
+                ));
+                break;
+            }
+            let Some(code) = line.trim_start().strip_prefix('>') else {
+                break;
+            };
+            end += 1;
+            code_lines.push(
+                code.strip_prefix(' ')
+                    .unwrap_or(code)
+                    .trim_end()
+                    .to_string(),
+            );
+        }
+
+        // Without code it's just a line of the description
+        if code_lines.is_empty() {
+            lines.push(span);
+            continue;
+        }
+        index = end;
+        let example = code_lines.join("\n");
+
+        // A result that isn't a constant can't be checked, so the example is kept with its
+        // result marked as unchecked instead of making the whole definition fail
+        let result = result_span
+            .filter(|span| !span.is_empty())
+            .and_then(|span| {
+                let errors = working_set.parse_errors.len();
+                let expr = parse_value(working_set, span, &SyntaxShape::Any, None);
+                if working_set.parse_errors.len() > errors {
+                    working_set.parse_errors.truncate(errors);
+                    return None;
+                }
+                eval_constant(working_set, &expr).ok()
+            });
+
+        examples.push(CustomExample {
+            example,
+            description: description.trim().to_string(),
+            unchecked_result: result_span.is_some() && result.is_none(),
+            result,
+        });
+
+        // Don't leave two blank lines where the example was
+        if comments
+            .get(index)
+            .is_some_and(|&next| is_blank(working_set, next))
+            && lines.last().is_none_or(|&last| is_blank(working_set, last))
+        {
+            index += 1;
+        }
+    }
+
+    if !examples.is_empty() {
+        while lines
+            .last()
+            .is_some_and(|&last| is_blank(working_set, last))
+        {
+            lines.pop();
+        }
+    }
+
+    (lines, examples)
+}
+
 fn handle_special_attributes(
     attributes: Vec<(String, Value)>,
     working_set: &mut StateWorkingSet<'_>,
@@ -1572,8 +1572,10 @@ pub fn parse_expression(
                 parse_call(working_set, &spans[pos..], spans[0], input_type)
             }
             b"overlay" => {
-                if spans.len() > 1 && working_set.get_span_contents(spans[1]) == b"list" {
-                    // whitelist 'overlay list'
+                if spans.len() > 1
+                    && matches!(working_set.get_span_contents(spans[1]), b"list" | b"export")
+                {
+                    // whitelist 'overlay list' and 'overlay export'
                     parse_call(working_set, &spans[pos..], spans[0], input_type)
                 } else {
                     working_set.error(ParseError::BuiltinCommandInPipeline(
@@ -1588,12 +1590,20 @@ pub fn parse_expression(
             b"run" => parse_run_expr(working_set, &spans[pos..]),
             #[cfg(feature = "plugin")]
             b"plugin" => {
-                if spans.len() > 1 && working_set.get_span_contents(spans[1]) == b"use" {
-                    // only 'plugin use' is banned
-                    working_set.error(ParseError::BuiltinCommandInPipeline(
+                // only 'plugin use' and 'plugin reload' are banned
+                match spans
+                    .get(1)
+                    .map(|span| working_set.get_span_contents(*span))
+                {
+                    Some(b"use") => working_set.error(ParseError::BuiltinCommandInPipeline(
                         "plugin use".into(),
                         spans[0],
-                    ));
+                    )),
+                    Some(b"reload") => working_set.error(ParseError::BuiltinCommandInPipeline(
+                        "plugin reload".into(),
+                        spans[0],
+                    )),
+                    _ => (),
                 }
 
                 parse_call(working_set, &spans[pos..], spans[0], input_type)
@@ -1743,16 +1753,20 @@ pub fn parse_builtin_commands(
         b"run" => parse_run(working_set, lite_command),
         b"hide" => parse_hide(working_set, lite_command),
         b"where" => parse_where(working_set, lite_command),
-        // Only "plugin use" is a keyword
+        // Only "plugin use" and "plugin reload" are keywords
         #[cfg(feature = "plugin")]
         b"plugin"
-            if lite_command
-                .parts
-                .get(1)
-                .is_some_and(|span| working_set.get_span_contents(*span) == b"use") =>
+            if lite_command.parts.get(1).is_some_and(|span| {
+                matches!(working_set.get_span_contents(*span), b"use" | b"reload")
+            }) =>
         {
             if let Some(redirection) = lite_command.redirection.as_ref() {
-                working_set.error(redirecting_builtin_error("plugin use", redirection));
+                let name = if working_set.get_span_contents(lite_command.parts[1]) == b"use" {
+                    "plugin use"
+                } else {
+                    "plugin reload"
+                };
+                working_set.error(redirecting_builtin_error(name, redirection));
                 return garbage_pipeline(working_set, &lite_command.parts);
             }
             parse_keyword(working_set, lite_command)
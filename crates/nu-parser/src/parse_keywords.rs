@@ -46,6 +46,7 @@ pub const UNALIASABLE_PARSER_KEYWORDS: &[&[u8]] = &[
     b"run",
     b"where",
     b"plugin use",
+    b"plugin reload",
 ];
 
 /// Check whether spans start with a parser keyword that can be aliased
@@ -174,6 +175,8 @@ pub fn parse_keyword(working_set: &mut StateWorkingSet, lite_command: &LiteComma
             "overlay use" => crate::parse_module::parse_overlay_use(working_set, call),
             #[cfg(feature = "plugin")]
             "plugin use" => crate::parse_source::parse_plugin_use(working_set, call),
+            #[cfg(feature = "plugin")]
+            "plugin reload" => crate::parse_source::parse_plugin_reload(working_set, call),
             _ => Pipeline::from_vec(vec![call_expr]),
         }
     } else {
@@ -644,6 +644,112 @@ pub fn parse_plugin_use(working_set: &mut StateWorkingSet, call: Box<Call>) -> P
     )])
 }
 
+#[cfg(feature = "plugin")]
+pub fn parse_plugin_reload(working_set: &mut StateWorkingSet, call: Box<Call>) -> Pipeline {
+    use nu_protocol::{FromValue, PluginRegistryFile, PluginRegistryItemData};
+
+    #[allow(deprecated)]
+    let cwd = working_set.get_cwd();
+
+    if let Err(err) = (|| {
+        let name = call
+            .positional_iter()
+            .next()
+            .map(|expr| {
+                eval_constant(working_set, expr)
+                    .and_then(nu_protocol::Spanned::<String>::from_value)
+                    .map_err(|err| err.wrap(working_set, call.head))
+            })
+            .expect("required positional should have been checked")?;
+
+        let plugin_config = call
+            .named_iter()
+            .find(|(arg_name, _, _)| arg_name.item == "plugin-config")
+            .map(|(_, _, expr)| {
+                let expr = expr
+                    .as_ref()
+                    .expect("--plugin-config arg should have been checked already");
+                eval_constant(working_set, expr)
+                    .and_then(nu_protocol::Spanned::<String>::from_value)
+                    .map_err(|err| err.wrap(working_set, call.head))
+            })
+            .transpose()?;
+
+        let filename_query = nu_path::expand_path_with(&name.item, &cwd, true);
+
+        // Only plugins that are already loaded can be reloaded
+        let identity = working_set
+            .permanent_state
+            .plugins()
+            .iter()
+            .map(|plugin| plugin.identity())
+            .find(|identity| identity.name() == name.item || identity.filename() == filename_query)
+            .cloned()
+            .ok_or_else(|| ParseError::LabeledErrorWithHelp {
+                error: format!("Plugin `{}` is not loaded", name.item),
+                label: "can't find a loaded plugin with this name".into(),
+                span: name.span,
+                help: "load it with `plugin use` first".into(),
+            })?;
+
+        // The plugin is restarted when the command runs, which also updates its entry in the
+        // registry file. Parsing only loads the signatures from that entry, so that it never
+        // touches the running plugin. If the restarted plugin provides other commands than the
+        // ones loaded here, including plugins without an entry, the command fails at run time.
+        let plugin_config_path = match &plugin_config {
+            Some(custom_path) => find_in_dirs(&custom_path.item, working_set, &cwd, None)
+                .ok_or_else(|| {
+                    ParseError::FileNotFound(custom_path.item.clone(), custom_path.span)
+                })?,
+            None => match &working_set.permanent_state.plugin_path {
+                Some(path) => ParserPath::RealPath(path.clone()),
+                None => return Ok(()),
+            },
+        };
+
+        let file = match plugin_config_path.open(working_set) {
+            Ok(file) => file,
+            // Without a default registry file, no plugin was added to it yet
+            Err(_) if plugin_config.is_none() => return Ok(()),
+            Err(err) => {
+                return Err(ParseError::LabeledError(
+                    "Plugin registry file can't be opened".into(),
+                    err.to_string(),
+                    plugin_config.as_ref().map(|p| p.span).unwrap_or(call.head),
+                ));
+            }
+        };
+
+        let contents = PluginRegistryFile::read_from(file, Some(call.head))
+            .map_err(|err| err.wrap(working_set, call.head))?;
+
+        if let Some(PluginRegistryItemData::Valid { commands, .. }) = contents
+            .plugins
+            .iter()
+            .find(|item| {
+                item.filename == identity.filename() && item.shell.as_deref() == identity.shell()
+            })
+            .map(|item| &item.data)
+        {
+            nu_plugin_engine::replace_plugin_declarations(working_set, &identity, commands)
+                .map_err(|err| err.wrap(working_set, call.head))?;
+        }
+
+        Ok(())
+    })() {
+        working_set.error(err);
+    }
+
+    let call_span = call.span();
+
+    Pipeline::from_vec(vec![Expression::new(
+        working_set,
+        Expr::Call(call),
+        call_span,
+        Type::Nothing,
+    )])
+}
+
 pub fn find_dirs_var(working_set: &StateWorkingSet, var_name: &str) -> Option<VarId> {
     working_set
         .find_variable(format!("${var_name}").as_bytes())
@@ -289,7 +289,7 @@ impl PluginExecutionContext for PluginExecutionCommandContext<'_> {
 //
 // The `plugin` must match the registered name of a plugin.  For `plugin add nu_plugin_example` the
 // plugin config lookup uses `"example"`
-fn plugin_config(
+pub(crate) fn plugin_config(
     config: Arc<Config>,
     plugin_name: &str,
     engine_state: &EngineState,
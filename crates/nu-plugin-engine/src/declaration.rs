@@ -2,7 +2,7 @@ use nu_engine::{command_prelude::*, get_eval_expression};
 use nu_plugin_protocol::{CallInfo, EvaluatedCall, GetCompletionArgType, GetCompletionInfo};
 use nu_protocol::engine::ArgType;
 use nu_protocol::shell_error::generic::GenericError;
-use nu_protocol::{DynamicCompletionCallRef, DynamicSuggestion};
+use nu_protocol::{Completion, DynamicCompletionCallRef, DynamicSuggestion};
 use nu_protocol::{PluginIdentity, PluginSignature, engine::CommandType};
 use std::sync::Arc;
 
@@ -154,9 +154,26 @@ impl Command for PluginDeclaration {
                 ))
             })?;
 
-        let arg_info = match arg_type {
-            ArgType::Flag(flag_name) => GetCompletionArgType::Flag(flag_name.to_string()),
-            ArgType::Positional(index) => GetCompletionArgType::Positional(*index),
+        let (arg_info, completion) = match arg_type {
+            ArgType::Flag(flag_name) => (
+                GetCompletionArgType::Flag(flag_name.to_string()),
+                self.signature
+                    .sig
+                    .get_long_flag(flag_name)
+                    .and_then(|flag| flag.completion),
+            ),
+            ArgType::Positional(index) => (
+                GetCompletionArgType::Positional(*index),
+                self.signature
+                    .sig
+                    .get_positional(*index)
+                    .and_then(|arg| arg.completion.clone()),
+            ),
+        };
+        // Arguments can name another command of the plugin to complete them
+        let completer = match completion {
+            Some(Completion::CommandName(name)) => Some(name),
+            _ => None,
         };
 
         let mut context = PluginGetDynamicCompletionContext::new(
@@ -171,6 +188,7 @@ impl Command for PluginDeclaration {
                 name: self.name.clone(),
                 arg_type: arg_info,
                 call: (&call).into(),
+                completer,
             },
             &mut context,
         )
@@ -15,14 +15,16 @@ use nu_plugin_core::{
     ServerCommunicationIo,
 };
 use nu_protocol::{
-    PluginIdentity, PluginRegistryFile, PluginRegistryItem, PluginRegistryItemData,
-    RegisteredPlugin, ShellError, Span, engine::StateWorkingSet, report_shell_error,
+    PluginIdentity, PluginMetadata, PluginRegistryFile, PluginRegistryItem, PluginRegistryItemData,
+    PluginSignature, RegisteredPlugin, ShellError, Span,
+    engine::{EngineState, Stack, StateWorkingSet},
+    report_shell_error,
     shell_error::generic::GenericError,
 };
 
 use crate::{
-    PersistentPlugin, PluginDeclaration, PluginGc, PluginInterface, PluginInterfaceManager,
-    PluginSource,
+    GetPlugin, PersistentPlugin, PluginDeclaration, PluginGc, PluginInterface,
+    PluginInterfaceManager, PluginSource,
 };
 
 /// This should be larger than the largest commonly sent message to avoid excessive fragmentation.
@@ -293,6 +295,66 @@ pub fn load_plugin_registry_item(
     }
 }
 
+/// Replace the declarations of the plugin with the given `identity` in the `working_set` with
+/// `commands`, hiding the commands the plugin no longer provides.
+///
+/// The plugin itself is left alone, so this is safe to do while parsing: the highlighter and the
+/// completer parse on every keystroke, and throw their working sets away. Nothing sees a mix of
+/// old and new declarations, as the changes only become visible once the working set is merged.
+pub fn replace_plugin_declarations(
+    working_set: &mut StateWorkingSet,
+    identity: &PluginIdentity,
+    commands: &[PluginSignature],
+) -> Result<(), ShellError> {
+    let engine_state = working_set.permanent_state;
+    let plugin = add_plugin_to_working_set(working_set, identity)?;
+
+    let removed: Vec<Vec<u8>> = engine_state
+        .get_decls_sorted(false)
+        .into_iter()
+        .filter(|(name, decl_id)| {
+            engine_state.get_decl(*decl_id).plugin_identity() == Some(identity)
+                && !commands
+                    .iter()
+                    .any(|command| command.sig.name.as_bytes() == name.as_slice())
+        })
+        .map(|(name, _)| name)
+        .collect();
+    working_set.hide_decls(&removed);
+
+    for signature in commands {
+        let decl = PluginDeclaration::new(plugin.clone(), signature.clone());
+        working_set.add_decl(Box::new(decl));
+    }
+
+    Ok(())
+}
+
+/// Stop the `plugin` and start it again, then get its metadata and signatures anew.
+///
+/// The cached metadata is replaced, including the protocol version the new signatures were
+/// received with. The signatures are only returned, see [`replace_plugin_declarations()`].
+pub fn respawn_plugin(
+    plugin: Arc<PersistentPlugin>,
+    engine_state: &EngineState,
+    stack: &mut Stack,
+) -> Result<(PluginMetadata, Vec<PluginSignature>), ShellError> {
+    // Forget the old metadata too, so that a protocol version mismatch doesn't prevent us from
+    // talking to the new binary
+    plugin.reset()?;
+    plugin.set_metadata(None);
+
+    let interface = plugin.clone().get_plugin(Some((engine_state, stack)))?;
+    let protocol_version = interface.protocol_info()?.version.clone();
+    let metadata = interface
+        .get_metadata()?
+        .with_protocol_version(protocol_version);
+    let commands = interface.get_signature()?;
+
+    plugin.set_metadata(Some(metadata.clone()));
+    Ok((metadata, commands))
+}
+
 /// Find [`PersistentPlugin`] with the given `identity` in the `working_set`, or construct it
 /// if it doesn't exist.
 ///
@@ -325,3 +387,48 @@ pub fn add_plugin_to_working_set(
             msg: "encountered unexpected RegisteredPlugin type".into(),
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_plugin_declarations_leaves_plugin_alone() -> Result<(), ShellError> {
+        let mut engine_state = EngineState::new();
+        let identity = PluginIdentity::new_fake("foo");
+
+        let mut working_set = StateWorkingSet::new(&engine_state);
+        replace_plugin_declarations(
+            &mut working_set,
+            &identity,
+            &[
+                PluginSignature::build("foo a"),
+                PluginSignature::build("foo b"),
+            ],
+        )?;
+        engine_state.merge_delta(working_set.render())?;
+
+        let mut working_set = StateWorkingSet::new(&engine_state);
+        replace_plugin_declarations(
+            &mut working_set,
+            &identity,
+            &[
+                PluginSignature::build("foo b"),
+                PluginSignature::build("foo c"),
+            ],
+        )?;
+
+        assert!(working_set.find_decl(b"foo a").is_none());
+        assert!(working_set.find_decl(b"foo b").is_some());
+        assert!(working_set.find_decl(b"foo c").is_some());
+
+        let plugin = engine_state
+            .plugins()
+            .iter()
+            .find(|plugin| plugin.identity() == &identity)
+            .expect("plugin should have been added");
+        assert!(!plugin.is_running());
+        assert!(plugin.metadata().is_none());
+        Ok(())
+    }
+}
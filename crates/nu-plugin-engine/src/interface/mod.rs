@@ -6,20 +6,20 @@ use nu_plugin_core::{
     util::{Waitable, WaitableMut, with_custom_values_in},
 };
 use nu_plugin_protocol::{
-    CallInfo, CustomValueOp, EngineCall, EngineCallId, EngineCallResponse, EvaluatedCall,
+    CallInfo, CustomValueOp, EngineCall, EngineCallId, EngineCallResponse, EvaluatedCall, Feature,
     GetCompletionInfo, Ordering, PluginCall, PluginCallId, PluginCallResponse, PluginCustomValue,
     PluginInput, PluginOption, PluginOutput, ProtocolInfo, StreamId, StreamMessage,
 };
 use nu_protocol::{
-    CustomValue, DynamicSuggestion, IntoSpanned, PipelineData, PluginMetadata, PluginSignature,
-    ShellError, SignalAction, Signals, Span, Spanned, Value, ast::Operator, casing::Casing,
-    engine::Sequence, shell_error::generic::GenericError,
+    CustomValue, DynamicSuggestion, IntoSpanned, ListStream, PipelineData, PluginMetadata,
+    PluginSignature, ShellError, SignalAction, Signals, Span, Spanned, Value, ast::Operator,
+    casing::Casing, engine::Sequence, shell_error::generic::GenericError,
 };
 use nu_utils::SharedCow;
 use std::{
     collections::{BTreeMap, btree_map},
     path::Path,
-    sync::{Arc, OnceLock, mpsc},
+    sync::{Arc, Mutex, OnceLock, mpsc},
 };
 
 use crate::{
@@ -78,6 +78,9 @@ struct PluginInterfaceState {
     plugin_call_subscription_sender: mpsc::Sender<(PluginCallId, PluginCallState)>,
     /// An error that should be propagated to further plugin calls
     error: OnceLock<ShellError>,
+    /// The section of `$env.config.plugins` the plugin was most recently configured from, if it
+    /// has been configured yet
+    config: Mutex<Option<Option<Value>>>,
     /// The synchronized output writer
     writer: Box<dyn PluginWrite<PluginInput>>,
 }
@@ -94,6 +97,7 @@ impl std::fmt::Debug for PluginInterfaceState {
                 &self.plugin_call_subscription_sender,
             )
             .field("error", &self.error)
+            .field("config", &self.config)
             .finish_non_exhaustive()
     }
 }
@@ -173,6 +177,7 @@ impl PluginInterfaceManager {
                 stream_id_sequence: Sequence::default(),
                 plugin_call_subscription_sender: subscription_tx,
                 error: OnceLock::new(),
+                config: Mutex::new(None),
                 writer: Box::new(writer),
             }),
             protocol_info_mut,
@@ -736,6 +741,7 @@ impl PluginInterface {
             PluginCall::GetCompletion(flag_name) => {
                 (PluginCall::GetCompletion(flag_name), Default::default())
             }
+            PluginCall::Configure(config) => (PluginCall::Configure(config), Default::default()),
             PluginCall::Run(CallInfo { name, call, input }) => {
                 let (header, writer) = self.init_write_pipeline_data(input, &state)?;
                 (
@@ -990,6 +996,49 @@ impl PluginInterface {
         }
     }
 
+    /// Send the plugin its section of `$env.config.plugins`, if it supports
+    /// [`Feature::Configure`] and the section differs from the one it was last configured from.
+    ///
+    /// `config` is the section as it is in the config. `evaluate` is only called if it changed, to
+    /// evaluate a closure in it into the config that is sent to the plugin.
+    pub fn configure(
+        &self,
+        config: Option<&Value>,
+        evaluate: impl FnOnce() -> Option<Value>,
+    ) -> Result<(), ShellError> {
+        if !self.protocol_info()?.supports_feature(&Feature::Configure) {
+            return Ok(());
+        }
+
+        // Hold the lock for the whole call, so that the plugin never receives the same config
+        // twice from concurrent calls
+        let mut last_config = self
+            .state
+            .config
+            .lock()
+            .map_err(|_| ShellError::NushellFailed {
+                msg: "plugin config mutex poisoned".into(),
+            })?;
+
+        if last_config
+            .as_ref()
+            .is_some_and(|last| last.as_ref() == config)
+        {
+            return Ok(());
+        }
+
+        match self.plugin_call(PluginCall::Configure(evaluate()), None)? {
+            PluginCallResponse::Ok => {
+                *last_config = Some(config.cloned());
+                Ok(())
+            }
+            PluginCallResponse::Error(err) => Err(err),
+            _ => Err(ShellError::PluginFailedToDecode {
+                msg: "Received unexpected response to plugin Configure call".into(),
+            }),
+        }
+    }
+
     /// Run the plugin with the given call and execution context.
     pub fn run(
         &self,
@@ -1140,6 +1189,30 @@ impl PluginInterface {
         }
     }
 
+    /// Iterate over a custom value as a stream. The plugin produces the items as they are read.
+    pub fn custom_value_iterate(
+        &self,
+        value: Spanned<PluginCustomValueWithSource>,
+    ) -> Result<ListStream, ShellError> {
+        let span = value.span;
+
+        // Check that the value came from the right source
+        value.item.verify_source(span, &self.state.source)?;
+
+        let call =
+            PluginCall::CustomValueOp(value.map(|cv| cv.without_source()), CustomValueOp::Iterate);
+        match self.plugin_call(call, None)? {
+            PluginCallResponse::PipelineData(PipelineData::ListStream(stream, ..)) => Ok(stream),
+            PluginCallResponse::PipelineData(PipelineData::Value(Value::List { vals, .. }, ..)) => {
+                Ok(ListStream::new(vals.into_iter(), span, Signals::empty()))
+            }
+            PluginCallResponse::Error(err) => Err(err),
+            _ => Err(ShellError::PluginFailedToDecode {
+                msg: "Received unexpected response to custom value iterate() call".into(),
+            }),
+        }
+    }
+
     /// Notify the plugin about a dropped custom value.
     pub fn custom_value_dropped(&self, value: PluginCustomValue) -> Result<(), ShellError> {
         // Make sure we don't block here. This can happen on the receiver thread, which would cause
@@ -1327,6 +1400,10 @@ impl CurrentCallState {
             PluginCall::Metadata => Ok(()),
             PluginCall::Signature => Ok(()),
             PluginCall::GetCompletion(_) => Ok(()),
+            PluginCall::Configure(config) => config
+                .as_mut()
+                .map(|value| self.prepare_value(value, source))
+                .unwrap_or(Ok(())),
             PluginCall::Run(CallInfo { call, .. }) => self.prepare_call_args(call, source),
             PluginCall::CustomValueOp(_, op) => {
                 // Handle anything within the op.
@@ -1337,6 +1414,7 @@ impl CurrentCallState {
                     CustomValueOp::PartialCmp(value) => self.prepare_value(value, source),
                     CustomValueOp::Operation(_, value) => self.prepare_value(value, source),
                     CustomValueOp::Save { .. } => Ok(()),
+                    CustomValueOp::Iterate => Ok(()),
                     CustomValueOp::Dropped => Ok(()),
                 }
             }
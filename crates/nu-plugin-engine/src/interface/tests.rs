@@ -1149,6 +1149,7 @@ fn interface_get_dynamic_completion() -> Result<(), ShellError> {
                 pos: 0,
                 strip: true,
             },
+            completer: None,
         },
         &mut PluginExecutionBogusContext,
     )?;
@@ -1174,6 +1175,7 @@ fn interface_get_dynamic_completion() -> Result<(), ShellError> {
                 pos: 0,
                 strip: true,
             },
+            completer: None,
         },
         &mut PluginExecutionBogusContext,
     )?;
@@ -1,13 +1,15 @@
 use crate::{
     PluginGc,
+    context::plugin_config,
     init::{create_command, make_plugin_interface},
 };
 
 use super::{PluginInterface, PluginSource};
 use nu_plugin_core::CommunicationMode;
+use nu_plugin_protocol::ProtocolInfo;
 use nu_protocol::{
     HandlerGuard, Handlers, PluginGcConfig, PluginIdentity, PluginMetadata, RegisteredPlugin,
-    ShellError,
+    ShellError, Span,
     engine::{EngineState, Stack},
     shell_error::io::IoError,
 };
@@ -201,6 +203,32 @@ impl PersistentPlugin {
             Some(gc.clone()),
         )?;
 
+        // If the signatures we have were cached with an incompatible protocol version, they may
+        // not match what the binary expects anymore, so refuse to talk to it
+        if let Some(cached_version) = mutable
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.protocol_version.as_ref())
+        {
+            let protocol_info = interface.protocol_info()?;
+            if !cached_version_is_compatible(cached_version, &protocol_info) {
+                gc.stop_tracking();
+                return Err(ShellError::PluginFailedToLoad {
+                    msg: format!(
+                        "Plugin `{}` was registered with protocol version {}, but the binary at \
+                            `{}` now uses protocol version {}, which is not compatible. The \
+                            cached signatures may be out of date: run `plugin reload {}` to \
+                            refresh them",
+                        self.identity.name(),
+                        cached_version,
+                        source_file.display(),
+                        protocol_info.version,
+                        self.identity.name(),
+                    ),
+                });
+            }
+        }
+
         // If our current preferred mode is None, check to see if the plugin might support another
         // mode. If so, retry spawn() with that mode
         #[cfg(feature = "local-socket")]
@@ -225,6 +253,21 @@ impl PersistentPlugin {
         Ok(())
     }
 
+    /// Send the running plugin its section of `$env.config.plugins` from the `stack`, if it
+    /// changed since the plugin was last configured.
+    fn configure(
+        &self,
+        interface: &PluginInterface,
+        engine_state: &EngineState,
+        stack: &Stack,
+    ) -> Result<(), ShellError> {
+        let config = stack.get_config(engine_state);
+        let name = self.identity.name();
+        interface.configure(config.plugins.get(name), || {
+            plugin_config(config.clone(), name, engine_state, stack, Span::unknown())
+        })
+    }
+
     fn stop_internal(&self, reset: bool) -> Result<(), ShellError> {
         let mut mutable = self.mutable.lock().map_err(|_| ShellError::NushellFailed {
             msg: format!(
@@ -306,6 +349,24 @@ impl RegisteredPlugin for PersistentPlugin {
         }
     }
 
+    fn update_plugin_config(
+        &self,
+        engine_state: &EngineState,
+        stack: &Stack,
+    ) -> Result<(), ShellError> {
+        let interface = self
+            .mutable
+            .lock()
+            .ok()
+            .and_then(|m| m.running.as_ref().map(|running| running.interface.clone()));
+
+        // A plugin that isn't running is configured when it's started
+        match interface {
+            Some(interface) => self.configure(&interface, engine_state, stack),
+            None => Ok(()),
+        }
+    }
+
     fn as_any(self: Arc<Self>) -> Arc<dyn std::any::Any + Send + Sync> {
         self
     }
@@ -338,7 +399,8 @@ impl RegisteredPlugin for PersistentPlugin {
 /// Anything that can produce a plugin interface.
 pub trait GetPlugin: RegisteredPlugin {
     /// Retrieve or spawn a [`PluginInterface`]. The `context` may be used for determining
-    /// environment variables to launch the plugin with.
+    /// environment variables to launch the plugin with, and to send the plugin its config when it
+    /// was started or the config changed.
     fn get_plugin(
         self: Arc<Self>,
         context: Option<(&EngineState, &mut Stack)>,
@@ -350,7 +412,7 @@ impl GetPlugin for PersistentPlugin {
         self: Arc<Self>,
         mut context: Option<(&EngineState, &mut Stack)>,
     ) -> Result<PluginInterface, ShellError> {
-        self.get(|| {
+        let interface = self.clone().get(|| {
             // Get envs from the context if provided.
             let envs = context
                 .as_mut()
@@ -364,6 +426,72 @@ impl GetPlugin for PersistentPlugin {
                 .transpose()?;
 
             Ok(envs.unwrap_or_default())
-        })
+        })?;
+
+        if let Some((engine_state, stack)) = context {
+            self.configure(&interface, engine_state, stack)?;
+        }
+
+        Ok(interface)
+    }
+}
+
+/// True if signatures cached with the protocol `cached_version` can still be used with a plugin
+/// that speaks `protocol_info`. This uses the same semver rules as the handshake, so e.g. a patch
+/// release of the plugin doesn't require a reload. A version that can't be parsed never matches.
+fn cached_version_is_compatible(cached_version: &str, protocol_info: &ProtocolInfo) -> bool {
+    ProtocolInfo {
+        version: cached_version.into(),
+        ..ProtocolInfo::default()
+    }
+    .is_compatible_with(protocol_info)
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protocol_info(version: &str) -> ProtocolInfo {
+        ProtocolInfo {
+            version: version.into(),
+            ..ProtocolInfo::default()
+        }
+    }
+
+    #[test]
+    fn cached_version_same() {
+        assert!(cached_version_is_compatible(
+            "0.105.0",
+            &protocol_info("0.105.0")
+        ));
+    }
+
+    #[test]
+    fn cached_version_patch_and_nightly_are_compatible() {
+        assert!(cached_version_is_compatible(
+            "0.105.0",
+            &protocol_info("0.105.1")
+        ));
+        assert!(cached_version_is_compatible(
+            "0.105.1-nightly.3",
+            &protocol_info("0.105.1")
+        ));
+    }
+
+    #[test]
+    fn cached_version_minor_is_incompatible() {
+        assert!(!cached_version_is_compatible(
+            "0.104.0",
+            &protocol_info("0.105.0")
+        ));
+    }
+
+    #[test]
+    fn cached_version_invalid_is_incompatible() {
+        assert!(!cached_version_is_compatible(
+            "not a version",
+            &protocol_info("0.105.0")
+        ));
     }
 }
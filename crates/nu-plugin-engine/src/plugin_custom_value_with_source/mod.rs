@@ -3,8 +3,8 @@ use std::{cmp::Ordering, path::Path, sync::Arc};
 use nu_plugin_core::util::with_custom_values_in;
 use nu_plugin_protocol::PluginCustomValue;
 use nu_protocol::{
-    CustomValue, IntoSpanned, ShellError, Span, Spanned, Value, ast::Operator, casing::Casing,
-    shell_error::generic::GenericError,
+    CustomValue, IntoSpanned, ListStream, ShellError, Signals, Span, Spanned, Value, ast::Operator,
+    casing::Casing, shell_error::generic::GenericError,
 };
 use serde::Serialize;
 
@@ -257,6 +257,18 @@ impl CustomValue for PluginCustomValueWithSource {
             .custom_value_save(self.clone().into_spanned(value_span), path, save_span)
     }
 
+    fn is_streamable(&self) -> bool {
+        self.streamable()
+    }
+
+    fn iterate(&self, span: Span, signals: Signals) -> Result<ListStream, ShellError> {
+        // Use the caller's signals, so reading the stream stops when interrupted
+        let stream = self
+            .get_plugin(Some(span), "iterate")?
+            .custom_value_iterate(self.clone().into_spanned(span))?;
+        Ok(ListStream::new(stream.into_inner(), span, signals))
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
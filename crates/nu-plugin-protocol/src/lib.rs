@@ -109,6 +109,11 @@ pub struct GetCompletionInfo {
     pub arg_type: GetCompletionArgType,
     /// Information about the invocation.
     pub call: DynamicCompletionCall,
+    /// The command named as the completer of the argument with
+    /// [`Completion::CommandName`](nu_protocol::Completion::CommandName). It is asked for the
+    /// completion items instead of the command being run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completer: Option<String>,
 }
 
 impl<D> CallInfo<D> {
@@ -217,6 +222,11 @@ pub enum PluginCall<D> {
     Run(CallInfo<D>),
     GetCompletion(GetCompletionInfo),
     CustomValueOp(Spanned<PluginCustomValue>, CustomValueOp),
+    /// The plugin's section of `$env.config.plugins`. Sent before the first call that runs in the
+    /// context of the engine, and again whenever it changes. Responded to with `Ok`.
+    ///
+    /// Only sent if the plugin supports [`Feature::Configure`].
+    Configure(Option<Value>),
 }
 
 impl<D> PluginCall<D> {
@@ -234,6 +244,7 @@ impl<D> PluginCall<D> {
             PluginCall::CustomValueOp(custom_value, op) => {
                 PluginCall::CustomValueOp(custom_value, op)
             }
+            PluginCall::Configure(config) => PluginCall::Configure(config),
         })
     }
 
@@ -245,6 +256,7 @@ impl<D> PluginCall<D> {
             PluginCall::GetCompletion(_) => None,
             PluginCall::Run(CallInfo { call, .. }) => Some(call.head),
             PluginCall::CustomValueOp(val, _) => Some(val.span),
+            PluginCall::Configure(_) => None,
         }
     }
 }
@@ -274,6 +286,8 @@ pub enum CustomValueOp {
         path: Spanned<PathBuf>,
         save_call_span: Span,
     },
+    /// [`iterate()`](nu_protocol::CustomValue::iterate), responded to with a list stream
+    Iterate,
     /// Notify that the custom value has been dropped, if
     /// [`notify_plugin_on_drop()`](nu_protocol::CustomValue::notify_plugin_on_drop) is true
     Dropped,
@@ -289,6 +303,7 @@ impl CustomValueOp {
             CustomValueOp::PartialCmp(_) => "partial_cmp",
             CustomValueOp::Operation(_, _) => "operation",
             CustomValueOp::Save { .. } => "save",
+            CustomValueOp::Iterate => "iterate",
             CustomValueOp::Dropped => "dropped",
         }
     }
@@ -1,6 +1,9 @@
 use std::{cmp::Ordering, path::Path};
 
-use nu_protocol::{CustomValue, ShellError, Span, Spanned, Value, ast::Operator, casing::Casing};
+use nu_protocol::{
+    CustomValue, ListStream, ShellError, Signals, Span, Spanned, Value, ast::Operator,
+    casing::Casing,
+};
 use nu_utils::SharedCow;
 
 use serde::{Deserialize, Serialize};
@@ -38,6 +41,11 @@ struct SharedContent {
     /// This is not serialized if `false`, since most custom values don't need it.
     #[serde(default, skip_serializing_if = "is_false")]
     notify_on_drop: bool,
+    /// True if the custom value can be iterated over as a stream (`is_streamable()`).
+    ///
+    /// This is not serialized if `false`, since most custom values don't need it.
+    #[serde(default, skip_serializing_if = "is_false")]
+    streamable: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -102,6 +110,14 @@ impl CustomValue for PluginCustomValue {
         panic!("save() not available on plugin custom value without source");
     }
 
+    fn is_streamable(&self) -> bool {
+        self.streamable()
+    }
+
+    fn iterate(&self, _span: Span, _signals: Signals) -> Result<ListStream, ShellError> {
+        panic!("iterate() not available on plugin custom value without source");
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -118,9 +134,17 @@ impl PluginCustomValue {
             name,
             data,
             notify_on_drop,
+            streamable: false,
         }))
     }
 
+    /// Mark the custom value as iterable over a stream, see
+    /// [`is_streamable()`](CustomValue::is_streamable).
+    pub fn with_streamable(mut self, streamable: bool) -> PluginCustomValue {
+        self.0.to_mut().streamable = streamable;
+        self
+    }
+
     /// Create a [`Value`] containing this custom value.
     pub fn into_value(self, span: Span) -> Value {
         Value::custom(Box::new(self), span)
@@ -141,6 +165,11 @@ impl PluginCustomValue {
         self.0.notify_on_drop
     }
 
+    /// True if the custom value can be iterated over as a stream.
+    pub fn streamable(&self) -> bool {
+        self.0.streamable
+    }
+
     /// Count the number of shared copies of this [`PluginCustomValue`].
     pub fn ref_count(&self) -> usize {
         SharedCow::ref_count(&self.0)
@@ -154,8 +183,11 @@ impl PluginCustomValue {
     ) -> Result<PluginCustomValue, ShellError> {
         let name = custom_value.type_name();
         let notify_on_drop = custom_value.notify_plugin_on_drop();
+        let streamable = custom_value.is_streamable();
         rmp_serde::to_vec(custom_value)
-            .map(|data| PluginCustomValue::new(name, data, notify_on_drop))
+            .map(|data| {
+                PluginCustomValue::new(name, data, notify_on_drop).with_streamable(streamable)
+            })
             .map_err(|err| ShellError::CustomValueFailedToEncode {
                 msg: err.to_string(),
                 span,
@@ -88,6 +88,16 @@ fn expected_serialize_output() -> Result<(), ShellError> {
     Ok(())
 }
 
+#[test]
+fn streamable_survives_serialization() {
+    let streamable = test_plugin_custom_value().with_streamable(true);
+    let data = rmp_serde::to_vec(&streamable).expect("failed to serialize");
+    let roundtrip: PluginCustomValue = rmp_serde::from_slice(&data).expect("failed to deserialize");
+    assert!(roundtrip.streamable());
+    assert!(roundtrip.is_streamable());
+    assert!(!test_plugin_custom_value().streamable());
+}
+
 #[test]
 fn serialize_in_root() -> Result<(), ShellError> {
     let span = Span::new(4, 10);
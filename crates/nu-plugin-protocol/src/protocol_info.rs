@@ -89,6 +89,10 @@ pub enum Feature {
     /// stdio.
     LocalSocket,
 
+    /// The plugin accepts [`PluginCall::Configure`](crate::PluginCall::Configure) to receive its
+    /// configuration from the engine.
+    Configure,
+
     /// A feature that was not recognized on deserialization. Attempting to serialize this feature
     /// is an error. Matching against it may only be used if necessary to determine whether
     /// unsupported features are present.
@@ -99,7 +103,10 @@ pub enum Feature {
 impl Feature {
     /// True if the feature is considered to be compatible with another feature.
     pub fn is_compatible_with(&self, other: &Feature) -> bool {
-        matches!((self, other), (Feature::LocalSocket, Feature::LocalSocket))
+        matches!(
+            (self, other),
+            (Feature::LocalSocket, Feature::LocalSocket) | (Feature::Configure, Feature::Configure)
+        )
     }
 }
 
@@ -109,5 +116,6 @@ pub fn default_features() -> Vec<Feature> {
         // Only available if compiled with the `local-socket` feature flag (enabled by default).
         #[cfg(feature = "local-socket")]
         Feature::LocalSocket,
+        Feature::Configure,
     ]
 }
@@ -0,0 +1,166 @@
+//! Arguments completed by another command of the plugin, through the plugin protocol.
+
+use nu_plugin::*;
+use nu_plugin_test_support::PluginTest;
+use nu_protocol::{
+    Completion, DynamicCompletionCallRef, DynamicSuggestion, Flag, LabeledError, PipelineData,
+    ShellError, Signature, Span, SyntaxShape,
+    ast::Call,
+    engine::{ArgType, Stack},
+};
+use std::collections::HashMap;
+
+struct PetPlugin;
+struct Pet;
+struct PetAnimals;
+
+impl Plugin for PetPlugin {
+    fn version(&self) -> String {
+        "0.0.0".into()
+    }
+
+    fn commands(&self) -> Vec<Box<dyn PluginCommand<Plugin = Self>>> {
+        vec![Box::new(Pet), Box::new(PetAnimals)]
+    }
+}
+
+impl PluginCommand for Pet {
+    type Plugin = PetPlugin;
+
+    fn name(&self) -> &str {
+        "pet"
+    }
+
+    fn description(&self) -> &str {
+        "Pet an animal"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .param(
+                Flag::new("animal")
+                    .arg(SyntaxShape::String)
+                    .desc("The animal to pet")
+                    .completion(Completion::CommandName("pet animals".into())),
+            )
+            .named("times", SyntaxShape::Int, "How many times", None)
+    }
+
+    fn run(
+        &self,
+        _plugin: &PetPlugin,
+        _engine: &EngineInterface,
+        _call: &EvaluatedCall,
+        _input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        Ok(PipelineData::empty())
+    }
+
+    #[expect(deprecated, reason = "internal usage")]
+    fn get_dynamic_completion(
+        &self,
+        _plugin: &PetPlugin,
+        _engine: &EngineInterface,
+        _call: DynamicCompletionCall,
+        _arg_type: ArgType,
+        _experimental: nu_protocol::engine::ExperimentalMarker,
+    ) -> Option<Vec<DynamicSuggestion>> {
+        Some(vec![DynamicSuggestion {
+            value: "1".into(),
+            ..Default::default()
+        }])
+    }
+}
+
+impl PluginCommand for PetAnimals {
+    type Plugin = PetPlugin;
+
+    fn name(&self) -> &str {
+        "pet animals"
+    }
+
+    fn description(&self) -> &str {
+        "Complete the animals to pet"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+    }
+
+    fn run(
+        &self,
+        _plugin: &PetPlugin,
+        _engine: &EngineInterface,
+        _call: &EvaluatedCall,
+        _input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        Ok(PipelineData::empty())
+    }
+
+    #[expect(deprecated, reason = "internal usage")]
+    fn get_dynamic_completion(
+        &self,
+        _plugin: &PetPlugin,
+        _engine: &EngineInterface,
+        _call: DynamicCompletionCall,
+        arg_type: ArgType,
+        _experimental: nu_protocol::engine::ExperimentalMarker,
+    ) -> Option<Vec<DynamicSuggestion>> {
+        // The argument is the one of the command being completed
+        match arg_type {
+            ArgType::Flag(flag) if flag == "animal" => Some(
+                ["cat", "dog", "eel"]
+                    .into_iter()
+                    .map(|value| DynamicSuggestion {
+                        value: value.into(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+fn complete(flag: &str) -> Result<Option<Vec<String>>, ShellError> {
+    let test = PluginTest::new("pet", PetPlugin.into())?;
+    let engine_state = test.engine_state();
+    let decl_id = engine_state
+        .find_decl(b"pet", &[])
+        .expect("pet should be in scope");
+    let call = Call {
+        decl_id,
+        head: Span::test_data(),
+        arguments: vec![],
+        parser_info: HashMap::new(),
+    };
+
+    let items = engine_state.get_decl(decl_id).get_dynamic_completion(
+        engine_state,
+        &mut Stack::new(),
+        DynamicCompletionCallRef {
+            call: &call,
+            strip: true,
+            pos: 0,
+        },
+        &ArgType::Flag(flag.to_string().into()),
+        #[expect(deprecated, reason = "internal usage")]
+        nu_protocol::engine::ExperimentalMarker,
+    )?;
+    Ok(items.map(|items| items.into_iter().map(|item| item.value).collect()))
+}
+
+#[test]
+fn named_completer_is_asked() -> Result<(), ShellError> {
+    assert_eq!(
+        Some(vec!["cat".to_string(), "dog".into(), "eel".into()]),
+        complete("animal")?
+    );
+    Ok(())
+}
+
+#[test]
+fn command_is_asked_without_named_completer() -> Result<(), ShellError> {
+    assert_eq!(Some(vec!["1".to_string()]), complete("times")?);
+    Ok(())
+}
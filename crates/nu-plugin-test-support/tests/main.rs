@@ -1,3 +1,4 @@
+mod completer;
 mod custom_value;
 mod hello;
 mod lowercase;
@@ -87,6 +87,12 @@ pub trait PluginCommand: Sync {
     /// The signature of the command.
     ///
     /// This defines the arguments and input/output types of the command.
+    ///
+    /// An argument can be completed by another command of this plugin, by setting its
+    /// completion to [`Completion::CommandName`](nu_protocol::Completion::CommandName). The engine
+    /// then asks that command for the completion items with its
+    /// [`get_dynamic_completion()`](Self::get_dynamic_completion), passing the call and argument
+    /// of the command being completed.
     fn signature(&self) -> Signature;
 
     /// A brief description of usage for the command.
@@ -53,6 +53,10 @@ pub enum ReceivedPluginCall {
         custom_value: Spanned<PluginCustomValue>,
         op: CustomValueOp,
     },
+    Configure {
+        engine: EngineInterface,
+        config: Option<Value>,
+    },
 }
 
 #[cfg(test)]
@@ -334,6 +338,20 @@ impl InterfaceManager for EngineInterfaceManager {
                             info,
                         })
                     }
+                    // Deserialize custom values in the config and send it on
+                    PluginCall::Configure(mut config) => {
+                        if let Err(err) = config
+                            .as_mut()
+                            .map(PluginCustomValue::deserialize_custom_values_in)
+                            .unwrap_or(Ok(()))
+                        {
+                            return interface.write_ok(Err(err));
+                        }
+                        self.send_plugin_call(ReceivedPluginCall::Configure {
+                            engine: interface,
+                            config,
+                        })
+                    }
                 }
             }
             PluginInput::Goodbye => {
@@ -12,7 +12,7 @@ use nu_plugin_protocol::{
 use nu_protocol::{
     BlockId, ByteStreamType, Config, CustomValue, IntoInterruptiblePipelineData, LabeledError,
     PipelineData, PluginSignature, ShellError, Signals, Span, Spanned, Value, VarId,
-    engine::Closure, shell_error,
+    engine::Closure, record, shell_error,
 };
 use std::{
     collections::HashMap,
@@ -361,6 +361,34 @@ fn manager_consume_call_signature_forwards_to_receiver_with_context() -> Result<
     }
 }
 
+#[test]
+fn manager_consume_call_configure_forwards_to_receiver_with_context() -> Result<(), ShellError> {
+    let mut manager = TestCase::new().engine();
+    set_default_protocol_info(&mut manager)?;
+
+    let rx = manager
+        .take_plugin_call_receiver()
+        .expect("couldn't take receiver");
+
+    let config = Value::test_record(record! { "host" => Value::test_string("localhost") });
+    manager.consume(PluginInput::Call(
+        0,
+        PluginCall::Configure(Some(config.clone())),
+    ))?;
+
+    match rx.try_recv().expect("call was not forwarded to receiver") {
+        ReceivedPluginCall::Configure {
+            engine,
+            config: received,
+        } => {
+            assert_eq!(Some(0), engine.context);
+            assert_eq!(Some(config), received);
+            Ok(())
+        }
+        call => panic!("wrong call type: {call:?}"),
+    }
+}
+
 #[test]
 fn manager_consume_call_run_forwards_to_receiver_with_context() -> Result<(), ShellError> {
     let mut manager = TestCase::new().engine();
@@ -19,8 +19,8 @@ use nu_plugin_protocol::{
     CallInfo, CustomValueOp, GetCompletionInfo, PluginCustomValue, PluginInput, PluginOutput,
 };
 use nu_protocol::{
-    CustomValue, IntoSpanned, LabeledError, PipelineData, PluginMetadata, ShellError, Span,
-    Spanned, Value, ast::Operator, casing::Casing,
+    CustomValue, IntoSpanned, LabeledError, ListStream, PipelineData, PluginMetadata, ShellError,
+    Span, Spanned, Value, ast::Operator, casing::Casing,
 };
 use thiserror::Error;
 
@@ -235,6 +235,26 @@ pub trait Plugin: Sync {
             .map_err(LabeledError::from)
     }
 
+    /// Iterate over a custom value as a stream, for values whose
+    /// [`CustomValue::is_streamable`] is true.
+    ///
+    /// The stream is sent to the engine as it is read, so items can be computed lazily, e.g. by
+    /// keeping a cursor into a query result. If the engine stops reading early, the rest of the
+    /// stream is dropped without being produced.
+    ///
+    /// The default implementation of this method just calls [`CustomValue::iterate`], but
+    /// the method can be implemented differently if accessing plugin state is desirable.
+    fn custom_value_iterate(
+        &self,
+        engine: &EngineInterface,
+        custom_value: Spanned<Box<dyn CustomValue>>,
+    ) -> Result<ListStream, LabeledError> {
+        custom_value
+            .item
+            .iterate(custom_value.span, engine.signals().clone())
+            .map_err(LabeledError::from)
+    }
+
     /// Handle a notification that all copies of a custom value within the engine have been dropped.
     ///
     /// This notification is only sent if [`CustomValue::notify_plugin_on_drop`] was true. Unlike
@@ -255,6 +275,34 @@ pub trait Plugin: Sync {
         let _ = (engine, custom_value);
         Ok(())
     }
+
+    /// Receive the plugin's section of `$env.config.plugins`.
+    ///
+    /// This is called with the config before the first command of the plugin runs, and again
+    /// whenever the config changes, so that the plugin can set up or reconfigure long-lived state
+    /// such as network connections. `config` is `None` if the user hasn't configured the plugin.
+    /// Closures in the config have already been evaluated.
+    ///
+    /// An error returned here is reported to the user in place of the command's result.
+    ///
+    /// The default implementation does nothing. [`EngineInterface::get_plugin_config`] can be used
+    /// instead from a command if the config is only needed on demand.
+    fn configure(
+        &self,
+        engine: &EngineInterface,
+        config: Option<Value>,
+    ) -> Result<(), LabeledError> {
+        let _ = (engine, config);
+        Ok(())
+    }
+
+    /// Handle the plugin being shut down.
+    ///
+    /// This is called once after the engine has said goodbye, or has gone away, and all plugin
+    /// calls have finished. Use it to release resources cleanly, e.g. to close connections.
+    ///
+    /// The default implementation does nothing.
+    fn shutdown(&self) {}
 }
 
 /// Function used to implement the communication protocol between nushell and an external plugin.
@@ -508,7 +556,7 @@ where
     }
 
     // Handle each Run plugin call on a thread
-    thread::scope(|scope| {
+    let result = thread::scope(|scope| {
         let run = |engine, call_info| {
             // SAFETY: It should be okay to use `AssertUnwindSafe` here, because we don't use any
             // of the references after we catch the unwind, and immediately exit.
@@ -547,8 +595,10 @@ where
                     name,
                     arg_type,
                     call,
+                    completer,
                 } = get_dynamic_completion_info;
-                let items = if let Some(command) = commands.get(&name) {
+                let items = if let Some(command) = commands.get(completer.as_ref().unwrap_or(&name))
+                {
                     let arg_type = arg_type.into();
                     command.get_dynamic_completion(
                         plugin,
@@ -635,11 +685,21 @@ where
                 ReceivedPluginCall::GetCompletion { engine, info } => {
                     get_dynamic_completion(engine, info)
                 }
+                // Hand the new config to the plugin
+                ReceivedPluginCall::Configure { engine, config } => {
+                    let result = plugin.configure(&engine, config);
+                    engine.write_ok(result).try_to_report(&engine)?;
+                }
             }
         }
 
         Ok::<_, ServePluginError>(())
-    })?;
+    });
+
+    // No more plugin calls will come in, and they've all finished. This happens even if serving
+    // them failed, so that the plugin can still release its resources
+    plugin.shutdown();
+    result?;
 
     // This will stop the manager
     drop(interface);
@@ -717,6 +777,15 @@ fn custom_value_op(
             let result = plugin.custom_value_save(engine, local_value, path, save_call_span);
             engine.write_ok(result)
         }
+        CustomValueOp::Iterate => {
+            let result = plugin
+                .custom_value_iterate(engine, local_value)
+                .map(|stream| PipelineData::list_stream(stream, None));
+            engine
+                .write_response(result)
+                .and_then(|writer| writer.write_background())
+                .map(|_| ())
+        }
         CustomValueOp::Dropped => {
             let result = plugin
                 .custom_value_dropped(engine, local_value.item)
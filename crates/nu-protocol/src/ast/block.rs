@@ -8,6 +8,9 @@ pub struct Block {
     pub pipelines: Vec<Pipeline>,
     pub captures: Vec<(VarId, Span)>,
     pub redirect_env: bool,
+    /// Whether this is the body of a command defined with `def --generator`, which runs alongside
+    /// its caller and streams the values it `yield`s.
+    pub generator: bool,
     /// The block compiled to IR instructions. Not available for subexpressions.
     pub ir_block: Option<IrBlock>,
     pub span: Option<Span>, // None option encodes no span to avoid using test_span()
@@ -47,6 +50,7 @@ impl Block {
             pipelines: vec![],
             captures: vec![],
             redirect_env: false,
+            generator: false,
             ir_block: None,
             span: None,
         }
@@ -58,6 +62,7 @@ impl Block {
             pipelines: Vec::with_capacity(capacity),
             captures: vec![],
             redirect_env: false,
+            generator: false,
             ir_block: None,
             span: None,
         }
@@ -95,6 +100,7 @@ where
             pipelines: pipelines.collect(),
             captures: vec![],
             redirect_env: false,
+            generator: false,
             ir_block: None,
             span: None,
         }
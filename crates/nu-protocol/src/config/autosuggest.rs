@@ -0,0 +1,94 @@
+use super::prelude::*;
+
+/// Configures the suggestions shown as dimmed text after the cursor while typing
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AutosuggestConfig {
+    /// Suggest the most recent history entry starting with the line
+    pub history: bool,
+    /// Suggest the first completion when the history has nothing to offer
+    pub completions: bool,
+    /// Number of characters to type before suggesting anything
+    pub min_chars: i64,
+    /// How long to wait for completions before giving up on a suggestion (in nanoseconds)
+    pub timeout: i64,
+    /// Style of the suggestion, `null` to use `color_config.hints`
+    pub style: Value,
+}
+
+impl Default for AutosuggestConfig {
+    fn default() -> Self {
+        Self {
+            history: true,
+            completions: false,
+            min_chars: 1,
+            timeout: 50_000_000, // 50ms
+            style: Value::nothing(Span::unknown()),
+        }
+    }
+}
+
+impl IntoValue for AutosuggestConfig {
+    fn into_value(self, span: Span) -> Value {
+        record! {
+            "history" => self.history.into_value(span),
+            "completions" => self.completions.into_value(span),
+            "min_chars" => self.min_chars.into_value(span),
+            "timeout" => Value::duration(self.timeout, span),
+            "style" => self.style,
+        }
+        .into_value(span)
+    }
+}
+
+impl UpdateFromValue for AutosuggestConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "history" => self.history.update(val, path, errors),
+                "completions" => self.completions.update(val, path, errors),
+                "min_chars" => {
+                    if let Ok(min_chars) = val.as_int() {
+                        if min_chars >= 0 {
+                            self.min_chars = min_chars;
+                        } else {
+                            errors.invalid_value(path, "an int greater than or equal to 0", val);
+                        }
+                    } else {
+                        errors.type_mismatch(path, Type::Int, val);
+                    }
+                }
+                "timeout" => {
+                    if let Ok(duration) = val.as_duration() {
+                        if duration >= 0 {
+                            self.timeout = duration;
+                        } else {
+                            errors.invalid_value(path, "a non-negative duration", val);
+                        }
+                    } else {
+                        errors.type_mismatch(path, Type::Duration, val);
+                    }
+                }
+                "style" => match val {
+                    Value::Nothing { .. } | Value::String { .. } | Value::Record { .. } => {
+                        self.style = val.clone();
+                    }
+                    _ => {
+                        errors.type_mismatch(path, Type::custom("string, record, or nothing"), val)
+                    }
+                },
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
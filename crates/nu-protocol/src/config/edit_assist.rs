@@ -0,0 +1,63 @@
+use super::prelude::*;
+use crate as nu_protocol;
+
+/// Configures the editing helpers for multi-line commands
+#[derive(Clone, Debug, IntoValue, Serialize, Deserialize)]
+pub struct EditAssistConfig {
+    /// Insert the closing `)`, `]`, `}` or `"` when typing the opening one
+    pub auto_pairs: bool,
+    /// Indent new lines and pasted blocks by the depth of the brackets around them
+    pub smart_indent: bool,
+    /// Number of spaces per indentation level
+    pub indent_width: i64,
+}
+
+impl Default for EditAssistConfig {
+    fn default() -> Self {
+        Self {
+            auto_pairs: false,
+            smart_indent: false,
+            indent_width: 4,
+        }
+    }
+}
+
+impl EditAssistConfig {
+    pub fn enabled(&self) -> bool {
+        self.auto_pairs || self.smart_indent
+    }
+}
+
+impl UpdateFromValue for EditAssistConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "auto_pairs" => self.auto_pairs.update(val, path, errors),
+                "smart_indent" => self.smart_indent.update(val, path, errors),
+                "indent_width" => {
+                    if let Ok(width) = val.as_int() {
+                        if (1..=16).contains(&width) {
+                            self.indent_width = width;
+                        } else {
+                            errors.invalid_value(path, "an int between 1 and 16", val);
+                        }
+                    } else {
+                        errors.type_mismatch(path, Type::Int, val);
+                    }
+                }
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use super::prelude::*;
+use crate as nu_protocol;
+
+/// Configures the layout of code written by `format nu` and `nu --format`
+#[derive(Clone, Copy, Debug, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormatterConfig {
+    /// Number of spaces per indentation level
+    pub indent_width: i64,
+    /// Lines longer than this are split before their pipes
+    pub max_line_length: i64,
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            max_line_length: 100,
+        }
+    }
+}
+
+impl UpdateFromValue for FormatterConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "indent_width" => {
+                    if let Ok(width) = val.as_int() {
+                        if (1..=16).contains(&width) {
+                            self.indent_width = width;
+                        } else {
+                            errors.invalid_value(path, "an int between 1 and 16", val);
+                        }
+                    } else {
+                        errors.type_mismatch(path, Type::Int, val);
+                    }
+                }
+                "max_line_length" => {
+                    if let Ok(length) = val.as_int() {
+                        if length >= 20 {
+                            self.max_line_length = length;
+                        } else {
+                            errors.invalid_value(path, "an int of at least 20", val);
+                        }
+                    } else {
+                        errors.type_mismatch(path, Type::Int, val);
+                    }
+                }
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
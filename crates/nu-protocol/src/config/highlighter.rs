@@ -0,0 +1,35 @@
+use super::prelude::*;
+use crate as nu_protocol;
+use crate::engine::Closure;
+
+#[derive(Clone, Debug, Default, IntoValue, Serialize, Deserialize)]
+pub struct HighlighterConfig {
+    /// Styles the arguments of external commands, on top of the shape colors.
+    pub external_args: Option<Closure>,
+}
+
+impl UpdateFromValue for HighlighterConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "external_args" => match val {
+                    Value::Nothing { .. } => self.external_args = None,
+                    Value::Closure { val, .. } => self.external_args = Some(val.as_ref().clone()),
+                    _ => errors.type_mismatch(path, Type::custom("closure or nothing"), val),
+                },
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
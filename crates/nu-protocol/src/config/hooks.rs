@@ -7,9 +7,15 @@ use std::collections::HashMap;
 pub struct Hooks {
     pub pre_prompt: Vec<Value>,
     pub pre_execution: Vec<Value>,
+    /// Run after `pre_prompt`, right before the prompt is computed and drawn.
+    pub pre_render: Vec<Value>,
+    /// Run when a command entered in the REPL fails, receiving the error as `$error`.
+    pub post_command_error: Vec<Value>,
     pub env_change: HashMap<EnvName, Vec<Value>>,
     pub display_output: Option<Value>,
     pub command_not_found: Option<Value>,
+    /// Run on text pasted in the line editor, receiving it as `$text`, returns the text to insert.
+    pub paste: Option<Value>,
 }
 
 impl Hooks {
@@ -17,12 +23,15 @@ impl Hooks {
         Self {
             pre_prompt: Vec::new(),
             pre_execution: Vec::new(),
+            pre_render: Vec::new(),
+            post_command_error: Vec::new(),
             env_change: HashMap::new(),
             display_output: Some(Value::string(
                 "if (term size).columns >= 100 { table -e } else { table }",
                 Span::unknown(),
             )),
             command_not_found: None,
+            paste: None,
         }
     }
 }
@@ -44,9 +53,12 @@ impl IntoValue for Hooks {
         record! {
             "pre_prompt" => self.pre_prompt.into_value(span),
             "pre_execution" => self.pre_execution.into_value(span),
+            "pre_render" => self.pre_render.into_value(span),
+            "post_command_error" => self.post_command_error.into_value(span),
             "env_change" => env_change.into_value(span),
             "display_output" => self.display_output.into_value(span),
             "command_not_found" => self.command_not_found.into_value(span),
+            "paste" => self.paste.into_value(span),
         }
         .into_value(span)
     }
@@ -81,6 +93,20 @@ impl UpdateFromValue for Hooks {
                         errors.type_mismatch(path, Type::list(Type::Any), val);
                     }
                 }
+                "pre_render" => {
+                    if let Ok(hooks) = val.as_list() {
+                        self.pre_render = hooks.into()
+                    } else {
+                        errors.type_mismatch(path, Type::list(Type::Any), val);
+                    }
+                }
+                "post_command_error" => {
+                    if let Ok(hooks) = val.as_list() {
+                        self.post_command_error = hooks.into()
+                    } else {
+                        errors.type_mismatch(path, Type::list(Type::Any), val);
+                    }
+                }
                 "env_change" => {
                     if let Ok(record) = val.as_record() {
                         self.env_change = record
@@ -121,6 +147,13 @@ impl UpdateFromValue for Hooks {
                         Some(val.clone())
                     }
                 }
+                "paste" => {
+                    self.paste = if val.is_nothing() {
+                        None
+                    } else {
+                        Some(val.clone())
+                    }
+                }
                 _ => errors.unknown_option(path, val),
             }
         }
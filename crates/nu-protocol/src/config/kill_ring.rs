@@ -0,0 +1,47 @@
+use super::prelude::*;
+use crate as nu_protocol;
+
+/// Configures the kill ring, the registers that text cut in the line editor is kept in
+#[derive(Clone, Copy, Debug, IntoValue, Serialize, Deserialize)]
+pub struct KillRingConfig {
+    /// Number of cuts to keep
+    pub size: i64,
+}
+
+impl Default for KillRingConfig {
+    fn default() -> Self {
+        Self { size: 10 }
+    }
+}
+
+impl UpdateFromValue for KillRingConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "size" => {
+                    if let Ok(size) = val.as_int() {
+                        if (1..=100).contains(&size) {
+                            self.size = size;
+                        } else {
+                            errors.invalid_value(path, "an int between 1 and 100", val);
+                        }
+                    } else {
+                        errors.type_mismatch(path, Type::Int, val);
+                    }
+                }
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
@@ -7,6 +7,7 @@ use prelude::*;
 use std::collections::HashMap;
 
 pub use ansi_coloring::UseAnsiColoring;
+pub use autosuggest::AutosuggestConfig;
 pub use clip::ClipConfig;
 pub use completions::{
     CompletionAlgorithm, CompletionConfig, CompletionSort, ExternalCompleterConfig,
@@ -14,39 +15,57 @@ pub use completions::{
 pub use datetime_format::DatetimeFormatConfig;
 pub use display_errors::DisplayErrors;
 pub use duration_max_unit::DurationMaxUnit;
+pub use edit_assist::EditAssistConfig;
 pub use filesize::FilesizeConfig;
+pub use formatter::FormatterConfig;
 pub use helper::extract_value;
+pub use highlighter::HighlighterConfig;
 pub use hinter::HinterConfig;
 pub use history::{HistoryConfig, HistoryFileFormat, HistoryPath};
 pub use hooks::Hooks;
+pub use kill_ring::KillRingConfig;
 pub use ls::LsConfig;
 pub use output::{BannerKind, ErrorStyle};
 pub use plugin_gc::{PluginGcConfig, PluginGcConfigs};
+pub use regex::RegexConfig;
 pub use reedline::{CursorShapeConfig, EditBindings, NuCursorShape, ParsedKeybinding, ParsedMenu};
 pub use rm::RmConfig;
 pub use shell_integration::ShellIntegrationConfig;
-pub use table::{FooterMode, TableConfig, TableIndent, TableIndexMode, TableMode, TrimStrategy};
+pub use table::{
+    FooterMode, TableAlignment, TableColumnConfig, TableConfig, TableIndent, TableIndexMode,
+    TableMode, TrimStrategy,
+};
+pub use transient_prompt::TransientPromptConfig;
+pub use undo::UndoConfig;
 
 mod ansi_coloring;
+mod autosuggest;
 mod clip;
 mod completions;
 mod datetime_format;
 mod display_errors;
 mod duration_max_unit;
+mod edit_assist;
 mod error;
 mod filesize;
+mod formatter;
 mod helper;
+mod highlighter;
 mod hinter;
 mod history;
 mod hooks;
+mod kill_ring;
 mod ls;
 mod output;
 mod plugin_gc;
 mod prelude;
 mod reedline;
+mod regex;
 mod rm;
 mod shell_integration;
 mod table;
+mod transient_prompt;
+mod undo;
 
 #[derive(Clone, Debug, IntoValue, Serialize, Deserialize)]
 pub struct Config {
@@ -63,17 +82,26 @@ pub struct Config {
     pub edit_mode: EditBindings,
     pub show_hints: bool,
     pub hinter: HinterConfig,
+    pub autosuggest: AutosuggestConfig,
+    pub highlighter: HighlighterConfig,
     pub history: HistoryConfig,
     pub keybindings: Vec<ParsedKeybinding>,
     pub abbreviations: HashMap<String, String>,
     pub menus: Vec<ParsedMenu>,
     pub hooks: Hooks,
     pub rm: RmConfig,
+    pub regex: RegexConfig,
     pub shell_integration: ShellIntegrationConfig,
     pub buffer_editor: Value,
     pub show_banner: BannerKind,
     pub bracketed_paste: bool,
+    pub recover_parse_errors: bool,
+    pub edit_assist: EditAssistConfig,
+    pub kill_ring: KillRingConfig,
+    pub undo: UndoConfig,
+    pub formatter: FormatterConfig,
     pub render_right_prompt_on_last_line: bool,
+    pub transient_prompt: TransientPromptConfig,
     pub explore: HashMap<String, Value>,
     pub cursor_shape: CursorShapeConfig,
     pub datetime_format: DatetimeFormatConfig,
@@ -82,8 +110,12 @@ pub struct Config {
     pub display_errors: DisplayErrors,
     pub use_kitty_protocol: bool,
     pub highlight_resolved_externals: bool,
+    /// Look up the packages that provide a command that isn't found, to suggest installing them.
+    pub suggest_packages: bool,
     pub auto_cd_implicit: bool,
     pub duration_max_unit: DurationMaxUnit,
+    /// Overlay module files (usually written by `overlay export`) activated when the REPL starts.
+    pub startup_overlays: Vec<String>,
     /// Configuration for plugins.
     ///
     /// Users can provide configuration for a plugin through this entry.  The entry name must
@@ -101,6 +133,7 @@ impl Default for Config {
 
             table: TableConfig::default(),
             rm: RmConfig::default(),
+            regex: RegexConfig::default(),
             ls: LsConfig::default(),
 
             datetime_format: DatetimeFormatConfig::default(),
@@ -125,13 +158,21 @@ impl Default for Config {
             buffer_editor: Value::nothing(Span::unknown()),
             use_ansi_coloring: UseAnsiColoring::default(),
             bracketed_paste: true,
+            recover_parse_errors: false,
+            edit_assist: EditAssistConfig::default(),
+            kill_ring: KillRingConfig::default(),
+            undo: UndoConfig::default(),
+            formatter: FormatterConfig::default(),
             edit_mode: EditBindings::default(),
             show_hints: true,
             hinter: HinterConfig::default(),
+            autosuggest: AutosuggestConfig::default(),
+            highlighter: HighlighterConfig::default(),
 
             shell_integration: ShellIntegrationConfig::default(),
 
             render_right_prompt_on_last_line: false,
+            transient_prompt: TransientPromptConfig::default(),
 
             hooks: Hooks::new(),
 
@@ -146,9 +187,11 @@ impl Default for Config {
 
             use_kitty_protocol: false,
             highlight_resolved_externals: false,
+            suggest_packages: false,
 
             auto_cd_implicit: false,
             duration_max_unit: DurationMaxUnit::default(),
+            startup_overlays: Vec::new(),
 
             plugins: HashMap::new(),
             plugin_gc: PluginGcConfigs::default(),
@@ -173,6 +216,7 @@ impl UpdateFromValue for Config {
             match col.as_str() {
                 "ls" => self.ls.update(val, path, errors),
                 "rm" => self.rm.update(val, path, errors),
+                "regex" => self.regex.update(val, path, errors),
                 "history" => self.history.update(val, path, errors),
                 "completions" => self.completions.update(val, path, errors),
                 "cursor_shape" => self.cursor_shape.update(val, path, errors),
@@ -187,6 +231,8 @@ impl UpdateFromValue for Config {
                 "edit_mode" => self.edit_mode.update(val, path, errors),
                 "show_hints" => self.show_hints.update(val, path, errors),
                 "hinter" => self.hinter.update(val, path, errors),
+                "autosuggest" => self.autosuggest.update(val, path, errors),
+                "highlighter" => self.highlighter.update(val, path, errors),
                 "shell_integration" => self.shell_integration.update(val, path, errors),
                 "buffer_editor" => match val {
                     Value::Nothing { .. } | Value::String { .. } => {
@@ -208,11 +254,18 @@ impl UpdateFromValue for Config {
                 "render_right_prompt_on_last_line" => self
                     .render_right_prompt_on_last_line
                     .update(val, path, errors),
+                "transient_prompt" => self.transient_prompt.update(val, path, errors),
                 "bracketed_paste" => self.bracketed_paste.update(val, path, errors),
+                "recover_parse_errors" => self.recover_parse_errors.update(val, path, errors),
+                "edit_assist" => self.edit_assist.update(val, path, errors),
+                "kill_ring" => self.kill_ring.update(val, path, errors),
+                "undo" => self.undo.update(val, path, errors),
+                "formatter" => self.formatter.update(val, path, errors),
                 "use_kitty_protocol" => self.use_kitty_protocol.update(val, path, errors),
                 "highlight_resolved_externals" => {
                     self.highlight_resolved_externals.update(val, path, errors)
                 }
+                "suggest_packages" => self.suggest_packages.update(val, path, errors),
                 "auto_cd_implicit" => self.auto_cd_implicit.update(val, path, errors),
                 "duration_max_unit" => self.duration_max_unit.update(val, path, errors),
                 "plugins" => self.plugins.update(val, path, errors),
@@ -225,6 +278,10 @@ impl UpdateFromValue for Config {
                     Ok(keybindings) => self.keybindings = keybindings,
                     Err(err) => errors.error(err.into()),
                 },
+                "startup_overlays" => match Vec::from_value(val.clone()) {
+                    Ok(overlays) => self.startup_overlays = overlays,
+                    Err(err) => errors.error(err.into()),
+                },
                 "abbreviations" => self.abbreviations.update(val, path, errors),
                 "hooks" => self.hooks.update(val, path, errors),
                 "datetime_format" => self.datetime_format.update(val, path, errors),
@@ -0,0 +1,74 @@
+use super::prelude::*;
+use crate as nu_protocol;
+
+/// The default options of regular expressions, used by `=~`, `!~` and the commands matching them.
+#[derive(Clone, Copy, Debug, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegexConfig {
+    pub case_insensitive: bool,
+    pub multiline: bool,
+    pub dotall: bool,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for RegexConfig {
+    fn default() -> Self {
+        Self {
+            case_insensitive: false,
+            multiline: false,
+            dotall: false,
+        }
+    }
+}
+
+impl RegexConfig {
+    /// The options that are turned on in either of these.
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            case_insensitive: self.case_insensitive || other.case_insensitive,
+            multiline: self.multiline || other.multiline,
+            dotall: self.dotall || other.dotall,
+        }
+    }
+
+    /// The inline flags turning these options on, like `(?im)`, to put before a pattern.
+    pub fn inline_flags(&self) -> String {
+        let flags = [
+            (self.case_insensitive, 'i'),
+            (self.multiline, 'm'),
+            (self.dotall, 's'),
+        ]
+        .into_iter()
+        .filter_map(|(on, flag)| on.then_some(flag))
+        .collect::<String>();
+
+        if flags.is_empty() {
+            flags
+        } else {
+            format!("(?{flags})")
+        }
+    }
+}
+
+impl UpdateFromValue for RegexConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "case_insensitive" => self.case_insensitive.update(val, path, errors),
+                "multiline" => self.multiline.update(val, path, errors),
+                "dotall" => self.dotall.update(val, path, errors),
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{num::NonZeroU16, time::Duration};
+use std::{collections::HashMap, num::NonZeroU16, time::Duration};
 
 use super::{config_update_string_enum, prelude::*};
 use crate::{self as nu_protocol, ConfigError, FromValue};
@@ -340,7 +340,109 @@ impl UpdateFromValue for TableIndent {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableAlignment {
+    Left,
+    Right,
+    Center,
+}
+
+impl FromStr for TableAlignment {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "center" => Ok(Self::Center),
+            _ => Err("'left', 'right' or 'center'"),
+        }
+    }
+}
+
+impl UpdateFromValue for TableAlignment {
+    fn update(&mut self, value: &Value, path: &mut ConfigPath, errors: &mut ConfigErrors) {
+        config_update_string_enum(self, value, path, errors)
+    }
+}
+
+/// Display settings of a single table column, picked by the column name.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableColumnConfig {
+    /// Overrides the alignment chosen by the cell type.
+    pub alignment: Option<TableAlignment>,
+    /// Maximum width of the column cells, they are trimmed with `table.trim` past it.
+    pub width: Option<usize>,
+    /// A color name or a style record, applied to every cell of the column.
+    pub color: Option<Value>,
+    /// How many levels of nested values `table --expand` expands in the column,
+    /// values past it are collapsed into a summary.
+    pub expand: Option<usize>,
+}
+
+impl IntoValue for TableColumnConfig {
+    fn into_value(self, span: Span) -> Value {
+        record! {
+            "alignment" => self.alignment.into_value(span),
+            "width" => self.width.map(|w| w as i64).into_value(span),
+            "color" => self.color.into_value(span),
+            "expand" => self.expand.map(|e| e as i64).into_value(span),
+        }
+        .into_value(span)
+    }
+}
+
+impl UpdateFromValue for TableColumnConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "alignment" => match val {
+                    Value::Nothing { .. } => self.alignment = None,
+                    _ => {
+                        let mut alignment = self.alignment.unwrap_or(TableAlignment::Left);
+                        alignment.update(val, path, errors);
+                        self.alignment = Some(alignment);
+                    }
+                },
+                "width" => match val {
+                    Value::Nothing { .. } => self.width = None,
+                    &Value::Int { val: width, .. } => match usize::try_from(width) {
+                        Ok(width) if width > 0 => self.width = Some(width),
+                        _ => errors.invalid_value(path, "a positive integer", val),
+                    },
+                    _ => errors.type_mismatch(path, Type::custom("int or nothing"), val),
+                },
+                "color" => match val {
+                    Value::Nothing { .. } => self.color = None,
+                    Value::String { .. } | Value::Record { .. } => self.color = Some(val.clone()),
+                    _ => errors.type_mismatch(path, Type::custom("string, record or nothing"), val),
+                },
+                "expand" => match val {
+                    Value::Nothing { .. } => self.expand = None,
+                    &Value::Int { val: depth, .. } => match usize::try_from(depth) {
+                        Ok(depth) => self.expand = Some(depth),
+                        Err(_) => errors.invalid_value(path, "a non-negative integer", val),
+                    },
+                    _ => errors.type_mismatch(path, Type::custom("int or nothing"), val),
+                },
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TableConfig {
     pub mode: TableMode,
     pub index_mode: TableIndexMode,
@@ -353,6 +455,12 @@ pub struct TableConfig {
     pub missing_value_symbol: String,
     pub batch_duration: Duration,
     pub stream_page_size: NonZeroU16,
+    /// Per column display settings, keyed by the column name.
+    pub columns: HashMap<String, TableColumnConfig>,
+    /// A closure run on each row, returning the style of the whole row.
+    pub row_style: Option<Value>,
+    /// A closure run on nested values collapsed by a column `expand` depth, returning their text.
+    pub summary: Option<Value>,
 }
 
 impl IntoValue for TableConfig {
@@ -362,6 +470,13 @@ impl IntoValue for TableConfig {
             .map(|t| t as i64)
             .into_value(span);
 
+        let columns = self
+            .columns
+            .into_iter()
+            .map(|(name, column)| (name, column.into_value(span)))
+            .collect::<crate::Record>()
+            .into_value(span);
+
         record! {
             "mode" => self.mode.into_value(span),
             "index_mode" => self.index_mode.into_value(span),
@@ -374,6 +489,9 @@ impl IntoValue for TableConfig {
             "missing_value_symbol" => self.missing_value_symbol.into_value(span),
             "batch_duration" => self.batch_duration.into_value(span),
             "stream_page_size" => self.stream_page_size.get().into_value(span),
+            "columns" => columns,
+            "row_style" => self.row_style.into_value(span),
+            "summary" => self.summary.into_value(span),
         }
         .into_value(span)
     }
@@ -393,6 +511,9 @@ impl Default for TableConfig {
             missing_value_symbol: "❎".into(),
             batch_duration: Duration::from_secs(1),
             stream_page_size: const { NonZeroU16::new(1000).expect("Non zero integer") },
+            columns: HashMap::new(),
+            row_style: None,
+            summary: None,
         }
     }
 }
@@ -451,6 +572,17 @@ impl UpdateFromValue for TableConfig {
                     };
                     self.stream_page_size = n;
                 }
+                "columns" => self.columns.update(val, path, errors),
+                "row_style" => match val {
+                    Value::Nothing { .. } => self.row_style = None,
+                    Value::Closure { .. } => self.row_style = Some(val.clone()),
+                    _ => errors.type_mismatch(path, Type::custom("closure or nothing"), val),
+                },
+                "summary" => match val {
+                    Value::Nothing { .. } => self.summary = None,
+                    Value::Closure { .. } => self.summary = Some(val.clone()),
+                    _ => errors.type_mismatch(path, Type::custom("closure or nothing"), val),
+                },
                 _ => errors.unknown_option(path, val),
             }
         }
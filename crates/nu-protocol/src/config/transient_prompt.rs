@@ -0,0 +1,58 @@
+use super::prelude::*;
+use crate as nu_protocol;
+use crate::engine::Closure;
+
+/// Settings of the transient prompt, the prompt that replaces the full prompt of a line once
+/// its command has been submitted.
+#[derive(Clone, Debug, IntoValue, Serialize, Deserialize)]
+pub struct TransientPromptConfig {
+    /// Whether to replace the prompt of submitted lines at all.
+    pub enabled: bool,
+    /// Computes the left prompt at the moment the command is submitted, instead of when the
+    /// prompt was drawn. Takes precedence over `$env.TRANSIENT_PROMPT_COMMAND`.
+    pub closure: Option<Closure>,
+    /// Overrides `render_right_prompt_on_last_line` for the transient prompt.
+    pub render_right_prompt_on_last_line: Option<bool>,
+}
+
+impl Default for TransientPromptConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            closure: None,
+            render_right_prompt_on_last_line: None,
+        }
+    }
+}
+
+impl UpdateFromValue for TransientPromptConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "enabled" => self.enabled.update(val, path, errors),
+                "closure" => match val {
+                    Value::Nothing { .. } => self.closure = None,
+                    Value::Closure { val, .. } => self.closure = Some(val.as_ref().clone()),
+                    _ => errors.type_mismatch(path, Type::custom("closure or nothing"), val),
+                },
+                "render_right_prompt_on_last_line" => match val {
+                    Value::Nothing { .. } => self.render_right_prompt_on_last_line = None,
+                    Value::Bool { val, .. } => self.render_right_prompt_on_last_line = Some(*val),
+                    _ => errors.type_mismatch(path, Type::custom("bool or nothing"), val),
+                },
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
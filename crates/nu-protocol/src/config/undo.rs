@@ -0,0 +1,41 @@
+use super::prelude::*;
+use crate as nu_protocol;
+
+/// Configures the undo history of the line editor
+#[derive(Clone, Copy, Debug, Default, IntoValue, Serialize, Deserialize)]
+pub struct UndoConfig {
+    /// Number of edits that can be undone, or 0 for no limit
+    pub depth: i64,
+}
+
+impl UpdateFromValue for UndoConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "depth" => {
+                    if let Ok(depth) = val.as_int() {
+                        if depth >= 0 {
+                            self.depth = depth;
+                        } else {
+                            errors.invalid_value(path, "a non-negative int", val);
+                        }
+                    } else {
+                        errors.type_mismatch(path, Type::Int, val);
+                    }
+                }
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
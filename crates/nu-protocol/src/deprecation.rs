@@ -10,7 +10,7 @@ use crate::{self as nu_protocol, ReportMode, Span};
 /// Commands can implement [`Command::deprecation_info`](crate::engine::Command::deprecation_info)
 /// to return deprecation entries, which will cause a parse-time warning.
 /// Additionally, custom commands can use the `@deprecated` attribute to add a
-/// `DeprecationEntry`.
+/// `DeprecationEntry`. The entries are also listed in the help of the command.
 #[derive(FromValue)]
 pub struct DeprecationEntry {
     /// The type of deprecation
@@ -24,6 +24,8 @@ pub struct DeprecationEntry {
     pub since: Option<String>,
     /// When this item is expected to be removed
     pub expected_removal: Option<String>,
+    /// What to use instead, like `str lowercase` for a command or `--optional` for a flag
+    pub replacement: Option<String>,
     /// Help text, possibly including a suggestion for what to use instead
     pub help: Option<String>,
 }
@@ -123,6 +125,27 @@ impl DeprecationEntry {
         format!("{name} {since} {removal}.")
     }
 
+    /// The help text of the warning, suggesting the replacement first.
+    fn help_text(&self) -> Option<String> {
+        let replacement = self
+            .replacement
+            .as_ref()
+            .map(|replacement| format!("Use `{replacement}` instead."));
+        match (replacement, &self.help) {
+            (Some(replacement), Some(help)) => Some(format!("{replacement}\n{help}")),
+            (replacement, help) => replacement.or_else(|| help.clone()),
+        }
+    }
+
+    /// Describes this deprecation in the documentation of a command, with what to use instead.
+    pub fn describe(&self, command_name: &str) -> String {
+        let label = self.label(command_name);
+        match self.help_text() {
+            Some(help) => format!("{label} {help}"),
+            None => label,
+        }
+    }
+
     fn span(&self, call: &Call) -> Span {
         match &self.ty {
             DeprecationType::Command => call.span(),
@@ -142,12 +165,13 @@ impl DeprecationEntry {
         let label = self.label(command_name);
         let span = self.span(call);
         let report_mode = self.report_mode;
+        let help = self.help_text();
         Some(ParseWarning::Deprecated {
             dep_type,
             label,
             span,
             report_mode,
-            help: self.help,
+            help,
         })
     }
 }
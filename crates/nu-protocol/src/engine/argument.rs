@@ -116,6 +116,11 @@ impl ArgumentStack {
         &self.arguments[base..(base + len)]
     }
 
+    /// Get arguments for the frame mutably, like [`get_args()`](Self::get_args).
+    pub fn get_args_mut(&mut self, base: usize, len: usize) -> &mut [Argument] {
+        &mut self.arguments[base..(base + len)]
+    }
+
     /// Move arguments for the frame based on the given [`base`](`.get_base()`) and
     /// [`len`](`.get_len()`) parameters.
     pub fn drain_args(&mut self, base: usize, len: usize) -> impl Iterator<Item = Argument> + '_ {
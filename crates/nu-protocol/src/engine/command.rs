@@ -95,6 +95,13 @@ pub trait Command: Send + Sync + CommandClone + Any {
         Vec::new()
     }
 
+    /// Whether the example at `index` in [`examples`](Self::examples) shows a result that can't be
+    /// checked, so that it has no [`result`](Example::result).
+    #[allow(unused_variables)]
+    fn example_result_unchecked(&self, index: usize) -> bool {
+        false
+    }
+
     // Related terms to help with command search
     fn search_terms(&self) -> Vec<&str> {
         vec![]
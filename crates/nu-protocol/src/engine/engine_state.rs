@@ -1,12 +1,12 @@
 use crate::{
     BlockId, Config, DeclId, FileId, GetSpan, Handlers, HistoryConfig, JobId, Module, ModuleId,
-    OverlayId, ShellError, SignalAction, Signals, Signature, Span, SpanId, Type, Value, VarId,
-    VirtualPathId,
+    OverlayId, ShellError, SignalAction, SignalHooks, Signals, Signature, Span, SpanId, Type,
+    Value, VarId, VirtualPathId,
     ast::{Block, Expr},
     debugger::{Debugger, NoopDebugger},
     engine::{
-        CachedFile, Command, DEFAULT_OVERLAY_NAME, EnvName, EnvVars, OverlayFrame, ScopeFrame,
-        Stack, StateDelta, Variable, Visibility,
+        CachedFile, Command, DEFAULT_OVERLAY_NAME, EnvName, EnvVars, ExternalsCache, OverlayFrame,
+        ScopeFrame, Stack, StateDelta, Variable, Visibility,
         description::{Doccomments, build_desc},
     },
     eval_const::create_nu_constant,
@@ -34,7 +34,7 @@ type PoisonDebuggerError<'a> = PoisonError<MutexGuard<'a, Box<dyn Debugger>>>;
 #[cfg(feature = "plugin")]
 use crate::{PluginRegistryFile, PluginRegistryItem, RegisteredPlugin};
 
-use super::{CurrentJob, Jobs, Mail, Mailbox, ThreadJob};
+use super::{Channels, CurrentJob, Jobs, Mail, Mailbox, ThreadJob};
 
 #[derive(Clone, Debug)]
 pub enum VirtualPath {
@@ -49,6 +49,8 @@ pub struct ReplState {
     pub cursor_pos: usize,
     /// Immediately accept the buffer on the next loop.
     pub accept: bool,
+    /// Start of the output of the command being run, kept for the session recording.
+    pub recorded_output: Option<String>,
 }
 
 #[derive(Debug)]
@@ -102,6 +104,8 @@ pub struct EngineState {
     pub scope: ScopeFrame,
     signals: Signals,
     pub signal_handlers: Option<Handlers>,
+    /// Closures registered by scripts with `on-signal`.
+    pub signal_hooks: SignalHooks,
     pub env_vars: Arc<EnvVars>,
     pub previous_env_vars: Arc<HashMap<EnvName, Value>>,
     pub config: Arc<Config>,
@@ -133,6 +137,9 @@ pub struct EngineState {
     // Path to the file Nushell is currently evaluating, or None if we're in an interactive session.
     pub file: Option<PathBuf>,
     pub regex_cache: Arc<Mutex<LruCache<String, Regex>>>,
+    /// The executables in the directories of `PATH`, shared by the commands and completions
+    /// that need them.
+    pub externals_cache: ExternalsCache,
     pub is_interactive: bool,
     pub is_login: bool,
     pub is_lsp: bool,
@@ -149,6 +156,9 @@ pub struct EngineState {
 
     pub root_job_sender: Sender<Mail>,
 
+    // The named channels of the main job, which background jobs can send to
+    pub root_job_channels: Arc<Channels>,
+
     // When there are background jobs running, the interactive behavior of `exit` changes depending on
     // the value of this flag:
     // - if this is false, then a warning about running jobs is shown and `exit` enables this flag
@@ -172,6 +182,7 @@ pub const UNKNOWN_SPAN_ID: SpanId = SpanId::new(0);
 impl EngineState {
     pub fn new() -> Self {
         let (send, recv) = channel::<Mail>();
+        let root_job_channels = Arc::<Channels>::default();
 
         Self {
             files: vec![],
@@ -197,6 +208,7 @@ impl EngineState {
                 false,
             ),
             signal_handlers: None,
+            signal_hooks: SignalHooks::default(),
             signals: Signals::empty(),
             env_vars: Arc::new(
                 [(DEFAULT_OVERLAY_NAME.to_string(), HashMap::new())]
@@ -210,6 +222,7 @@ impl EngineState {
                 buffer: "".to_string(),
                 cursor_pos: 0,
                 accept: false,
+                recorded_output: None,
             })),
             table_decl_id: None,
             #[cfg(feature = "plugin")]
@@ -224,6 +237,7 @@ impl EngineState {
             regex_cache: Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(REGEX_CACHE_SIZE).expect("tried to create cache of size zero"),
             ))),
+            externals_cache: ExternalsCache::default(),
             is_interactive: false,
             is_login: false,
             is_lsp: false,
@@ -237,8 +251,10 @@ impl EngineState {
                 id: JobId::new(0),
                 background_thread_job: None,
                 mailbox: Arc::new(Mutex::new(Mailbox::new(recv))),
+                channels: root_job_channels.clone(),
             },
             root_job_sender: send,
+            root_job_channels,
             exit_warning_given: Arc::new(AtomicBool::new(false)),
         }
     }
@@ -387,12 +403,21 @@ impl EngineState {
             .map_err(|err| IoError::new_internal(err, "Could not set current dir"))?;
 
         if let Some(config) = stack.config.take() {
+            #[cfg(feature = "plugin")]
+            let plugins_changed = config.plugins != self.config.plugins;
+
             // If config was updated in the stack, replace it.
             self.config = config;
 
             // Make plugin GC config changes take effect immediately.
             #[cfg(feature = "plugin")]
             self.update_plugin_gc_configs(&self.config.plugin_gc);
+
+            // Send running plugins their new config right away.
+            #[cfg(feature = "plugin")]
+            if plugins_changed {
+                self.update_plugin_configs(stack);
+            }
         }
 
         Ok(())
@@ -608,6 +633,21 @@ impl EngineState {
         }
     }
 
+    /// Send running plugins their config after it changed
+    #[cfg(feature = "plugin")]
+    fn update_plugin_configs(&self, stack: &Stack) {
+        for plugin in &self.plugins {
+            // If this fails, the plugin is configured again before it's next used, which reports
+            // the error
+            if let Err(err) = plugin.update_plugin_config(self, stack) {
+                log::warn!(
+                    "failed to configure plugin `{}`: {err}",
+                    plugin.identity().name()
+                );
+            }
+        }
+    }
+
     pub fn num_files(&self) -> usize {
         self.files.len()
     }
@@ -1104,6 +1144,7 @@ impl EngineState {
                 buffer: "".to_string(),
                 cursor_pos: 0,
                 accept: false,
+                recorded_output: None,
             }));
         }
         if Mutex::is_poisoned(&self.jobs) {
@@ -0,0 +1,94 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// An executable file in one of the directories of `PATH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathExecutable {
+    /// The file name, which is how the executable is run.
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// The executables found the last time the directories of `PATH` were listed.
+///
+/// The listing is reused as long as `PATH` has the same directories and none of them was
+/// modified since, so completions and prompts don't have to read every directory each time.
+#[derive(Debug, Clone, Default)]
+pub struct ExternalsCache(Arc<Mutex<Option<Listing>>>);
+
+#[derive(Debug)]
+struct Listing {
+    /// The directories that were listed, with the time they were last modified.
+    dirs: Vec<(PathBuf, Option<SystemTime>)>,
+    executables: Arc<[PathExecutable]>,
+}
+
+impl ExternalsCache {
+    /// The executables in `dirs`, which are listed with `list` if the cached ones are outdated.
+    pub fn get_or_list(
+        &self,
+        dirs: Vec<PathBuf>,
+        list: impl FnOnce(&[PathBuf]) -> Vec<PathExecutable>,
+    ) -> Arc<[PathExecutable]> {
+        let dirs: Vec<(PathBuf, Option<SystemTime>)> = dirs
+            .into_iter()
+            .map(|dir| {
+                let modified = std::fs::metadata(&dir)
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                (dir, modified)
+            })
+            .collect();
+
+        let mut listing = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(listing) = &*listing
+            && listing.dirs == dirs
+        {
+            return listing.executables.clone();
+        }
+
+        let paths: Vec<PathBuf> = dirs.iter().map(|(dir, _)| dir.clone()).collect();
+        let executables: Arc<[PathExecutable]> = list(&paths).into();
+        *listing = Some(Listing {
+            dirs,
+            executables: executables.clone(),
+        });
+        executables
+    }
+
+    /// Forget the cached executables, so the next lookup lists the directories again.
+    pub fn clear(&self) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_listing_of_same_dirs() {
+        let cache = ExternalsCache::default();
+        let dirs = vec![PathBuf::from("/nonexistent/bin")];
+        let listing = |_: &[PathBuf]| {
+            vec![PathExecutable {
+                name: "foo".into(),
+                path: PathBuf::from("/nonexistent/bin/foo"),
+            }]
+        };
+
+        let first = cache.get_or_list(dirs.clone(), listing);
+        let second = cache.get_or_list(dirs.clone(), |_| unreachable!("listed twice"));
+        assert_eq!(first, second);
+
+        let other = cache.get_or_list(vec![PathBuf::from("/nonexistent/sbin")], |_| vec![]);
+        assert!(other.is_empty());
+
+        cache.clear();
+        let relisted = cache.get_or_list(dirs, listing);
+        assert_eq!(relisted.len(), 1);
+    }
+}
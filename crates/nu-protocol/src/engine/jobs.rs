@@ -1,7 +1,7 @@
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet, hash_map::Entry},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque, hash_map::Entry},
     sync::{
-        Arc, Mutex,
+        Arc, Condvar, Mutex, MutexGuard,
         mpsc::{Receiver, RecvTimeoutError, Sender, TryRecvError},
     },
 };
@@ -150,6 +150,7 @@ pub struct ThreadJob {
     pids: Arc<Mutex<HashSet<u32>>>,
     description: Option<String>,
     pub sender: Sender<Mail>,
+    pub channels: Arc<Channels>,
 }
 
 impl ThreadJob {
@@ -158,6 +159,7 @@ impl ThreadJob {
             signals,
             pids: Arc::new(Mutex::new(HashSet::default())),
             sender,
+            channels: Arc::default(),
             description,
         }
     }
@@ -264,6 +266,9 @@ pub struct CurrentJob {
     // note: although the mailbox is Mutex'd, it is only ever accessed
     // by the current job's threads
     pub mailbox: Arc<Mutex<Mailbox>>,
+
+    // The named channels of the current job, shared with its ThreadJob
+    pub channels: Arc<Channels>,
 }
 
 // The storage for unread messages
@@ -407,3 +412,147 @@ impl IgnoredMail {
         Some(self.messages.remove(&id)?.1)
     }
 }
+
+/// Why sending to or receiving from [`Channels`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelError {
+    /// The waiting thread was interrupted.
+    Interrupted,
+    /// The job owning the channels has finished.
+    Closed,
+    /// No message arrived before the timeout.
+    Timeout,
+}
+
+// How often threads waiting on channels check whether they were interrupted
+#[cfg(not(target_family = "wasm"))]
+const CHANNEL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The named channels of a job, in addition to its mailbox.
+///
+/// Unlike the mailbox, channels can be bounded: sending to a full channel blocks until the
+/// job receives from it. Channels are created by the first message sent to them, or when a
+/// capacity is set for them.
+#[derive(Debug, Default)]
+pub struct Channels {
+    state: Mutex<ChannelsState>,
+    // notified whenever a message is sent or received, or a capacity changes
+    changed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct ChannelsState {
+    channels: HashMap<String, Channel>,
+    closed: bool,
+}
+
+#[derive(Debug, Default)]
+struct Channel {
+    messages: VecDeque<PipelineData>,
+    capacity: Option<usize>,
+}
+
+impl Channel {
+    fn is_full(&self) -> bool {
+        self.capacity
+            .is_some_and(|capacity| self.messages.len() >= capacity)
+    }
+}
+
+impl Channels {
+    fn lock(&self) -> MutexGuard<'_, ChannelsState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Bounds the channel `name` to `capacity` messages, or makes it unbounded with `None`.
+    ///
+    /// Messages already in the channel are kept, even if there are more than the new capacity.
+    pub fn set_capacity(&self, name: &str, capacity: Option<usize>) {
+        let mut state = self.lock();
+        state.channels.entry(name.to_owned()).or_default().capacity = capacity;
+        self.changed.notify_all();
+    }
+
+    /// Drops all the messages and wakes up all the waiting senders, as nobody will receive them.
+    pub fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+        state.channels.clear();
+        self.changed.notify_all();
+    }
+
+    /// Sends a message to the channel `name`, waiting while it is full.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn send(
+        &self,
+        name: &str,
+        message: PipelineData,
+        signals: &Signals,
+    ) -> Result<(), ChannelError> {
+        let mut state = self.lock();
+        loop {
+            if state.closed {
+                return Err(ChannelError::Closed);
+            }
+            let channel = state.channels.entry(name.to_owned()).or_default();
+            if !channel.is_full() {
+                channel.messages.push_back(message);
+                self.changed.notify_all();
+                return Ok(());
+            }
+            if signals.interrupted() {
+                return Err(ChannelError::Interrupted);
+            }
+            state = self
+                .changed
+                .wait_timeout(state, CHANNEL_CHECK_INTERVAL)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    /// Receives the oldest message of the first channel in `names` which has one, waiting for
+    /// one to arrive for at most `timeout`, or indefinitely with `None`.
+    ///
+    /// Returns the name of the channel together with the message.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn recv(
+        &self,
+        names: &[String],
+        timeout: Option<Duration>,
+        signals: &Signals,
+    ) -> Result<(String, PipelineData), ChannelError> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut state = self.lock();
+        loop {
+            let ready = names.iter().find_map(|name| {
+                let message = state.channels.get_mut(name)?.messages.pop_front()?;
+                Some((name.clone(), message))
+            });
+            if let Some(ready) = ready {
+                // a sender may be waiting for room in this channel
+                self.changed.notify_all();
+                return Ok(ready);
+            }
+            if signals.interrupted() {
+                return Err(ChannelError::Interrupted);
+            }
+
+            let wait = match deadline {
+                Some(deadline) => {
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    if left.is_zero() {
+                        return Err(ChannelError::Timeout);
+                    }
+                    left.min(CHANNEL_CHECK_INTERVAL)
+                }
+                None => CHANNEL_CHECK_INTERVAL,
+            };
+            state = self
+                .changed
+                .wait_timeout(state, wait)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+}
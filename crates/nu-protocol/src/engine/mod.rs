@@ -9,6 +9,7 @@ mod description;
 mod engine_state;
 mod env_name;
 mod error_handler;
+mod externals_cache;
 mod jobs;
 mod overlay;
 mod pattern_match;
@@ -29,6 +30,7 @@ pub use command::*;
 pub use engine_state::*;
 pub use env_name::*;
 pub use error_handler::*;
+pub use externals_cache::{ExternalsCache, PathExecutable};
 pub use jobs::*;
 pub use overlay::*;
 pub use pattern_match::*;
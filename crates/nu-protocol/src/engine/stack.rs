@@ -1,9 +1,11 @@
 use crate::{
-    Config, ENV_VARIABLE_ID, IntoValue, NU_VARIABLE_ID, OutDest, ShellError, Span, Value, VarId,
+    Config, DeclId, ENV_VARIABLE_ID, IntoValue, NU_VARIABLE_ID, OutDest, ShellError, Span, Value,
+    VarId,
     ast::PathMember,
     engine::{
-        ArgumentStack, DEFAULT_OVERLAY_NAME, EngineState, EnvName, ErrorHandlerStack, Redirection,
-        StackCallArgGuard, StackCollectValueGuard, StackIoGuard, StackOutDest, StackWithInvocation,
+        ArgumentStack, Closure, DEFAULT_OVERLAY_NAME, EngineState, EnvName, ErrorHandlerStack,
+        Redirection, StackCallArgGuard, StackCollectValueGuard, StackIoGuard, StackOutDest,
+        StackWithInvocation,
     },
     report_shell_warning,
     shell_error::generic::GenericError,
@@ -12,12 +14,23 @@ use std::{
     collections::{HashMap, HashSet},
     fs::File,
     path::{Component, MAIN_SEPARATOR},
-    sync::Arc,
+    sync::{Arc, mpsc::SyncSender},
 };
 
 /// Environment variables per overlay
 pub type EnvVars = HashMap<String, HashMap<EnvName, Value>>;
 
+/// A closure run when an environment variable changes, registered with `env watch`.
+#[derive(Debug, Clone)]
+pub struct EnvWatcher {
+    pub name: EnvName,
+    pub closure: Closure,
+    /// The value of the variable when the closure last ran, or when it was registered
+    pub last_value: Option<Value>,
+    /// Whether the closure is running, so that the changes it makes don't run it again
+    pub running: bool,
+}
+
 /// A runtime value stack used during evaluation
 ///
 /// A note on implementation:
@@ -68,6 +81,26 @@ pub struct Stack {
     /// When `true`, external processes spawned with `PipelineData::Empty` input
     /// receive `/dev/null` for stdin instead of inheriting the terminal.
     pub suppress_stdin: bool,
+    /// Where `yield` sends values, in the body of a command defined with `def --generator`.
+    pub yield_to: Option<SyncSender<Value>>,
+    /// Closures run when environment variables of this scope change.
+    pub env_watchers: Arc<Vec<EnvWatcher>>,
+    /// Whether a watched environment variable was set or hidden since the watchers last ran.
+    ///
+    /// The evaluator checks this after each instruction and runs the watchers whose variable
+    /// actually has a new value.
+    pub env_watch_pending: bool,
+    /// Closures run instead of commands, in this scope and the commands it calls.
+    ///
+    /// Set by `with-override`, so tests can replace commands that use the network or the
+    /// filesystem.
+    pub decl_overrides: Arc<HashMap<DeclId, Closure>>,
+    /// The `ref update`s that this scope runs in, as the id of the job that ran each and the id
+    /// of the update.
+    ///
+    /// Threads started by the closure of an update, like the ones of `par-each`, inherit this, so
+    /// an update of the same reference on them fails instead of waiting forever.
+    pub ref_updates: Arc<Vec<(usize, i64)>>,
 }
 
 impl Default for Stack {
@@ -101,6 +134,11 @@ impl Stack {
             config: None,
             out_dest: StackOutDest::new(),
             suppress_stdin: false,
+            yield_to: None,
+            env_watchers: Arc::new(Vec::new()),
+            env_watch_pending: false,
+            decl_overrides: Arc::new(HashMap::new()),
+            ref_updates: Arc::new(Vec::new()),
         }
     }
 
@@ -125,6 +163,11 @@ impl Stack {
             config: parent.config.clone(),
             out_dest: parent.out_dest.clone(),
             suppress_stdin: parent.suppress_stdin,
+            yield_to: parent.yield_to.clone(),
+            env_watchers: parent.env_watchers.clone(),
+            env_watch_pending: parent.env_watch_pending,
+            decl_overrides: parent.decl_overrides.clone(),
+            ref_updates: parent.ref_updates.clone(),
             parent_stack: Some(parent),
         }
     }
@@ -152,6 +195,7 @@ impl Stack {
         unique_stack.env_hide_history = child.env_hide_history;
         unique_stack.active_overlays = child.active_overlays;
         unique_stack.config = child.config;
+        unique_stack.env_watchers = child.env_watchers;
         unique_stack
     }
 
@@ -319,6 +363,7 @@ impl Stack {
         if let Some(last_overlay) = self.active_overlays.last().cloned() {
             let env_name = EnvName::from(var);
             self.clear_env_var_marks_in_active_overlay(&last_overlay, &env_name);
+            self.mark_env_change(&env_name);
 
             if let Some(scope) = self.env_vars.last_mut() {
                 let scope = Arc::make_mut(scope);
@@ -340,6 +385,17 @@ impl Stack {
         }
     }
 
+    /// Lets the evaluator know that the watchers of `env_name` may have to run.
+    fn mark_env_change(&mut self, env_name: &EnvName) {
+        if self
+            .env_watchers
+            .iter()
+            .any(|watcher| &watcher.name == env_name)
+        {
+            self.env_watch_pending = true;
+        }
+    }
+
     fn clear_env_var_marks_in_active_overlay(&mut self, overlay: &str, env_name: &EnvName) {
         if let Some(env_hidden) = Arc::make_mut(&mut self.env_hidden).get_mut(overlay) {
             // Re-assigning re-activates a previously hidden env var in this overlay.
@@ -399,6 +455,12 @@ impl Stack {
             config: self.config.clone(),
             out_dest: self.out_dest.clone(),
             suppress_stdin: self.suppress_stdin,
+            yield_to: None,
+            // changes made by the callee run the watchers too, and `redirect_env` brings them back
+            env_watchers: self.env_watchers.clone(),
+            env_watch_pending: false,
+            decl_overrides: self.decl_overrides.clone(),
+            ref_updates: self.ref_updates.clone(),
         }
     }
 
@@ -436,6 +498,12 @@ impl Stack {
             config: self.config.clone(),
             out_dest: self.out_dest.clone(),
             suppress_stdin: self.suppress_stdin,
+            yield_to: None,
+            // changes made by the callee run the watchers too, and `redirect_env` brings them back
+            env_watchers: self.env_watchers.clone(),
+            env_watch_pending: false,
+            decl_overrides: self.decl_overrides.clone(),
+            ref_updates: self.ref_updates.clone(),
         }
     }
 
@@ -637,6 +705,7 @@ impl Stack {
     /// to subsequent lookups (e.g. `hide-env`).
     pub fn remove_env_var(&mut self, engine_state: &EngineState, name: &str) -> bool {
         let env_name = EnvName::from(name);
+        self.mark_env_change(&env_name);
 
         self.remove_env_var_from_stack(&env_name)
             || self.hide_engine_state_env_var(engine_state, &env_name)
@@ -745,6 +814,7 @@ impl Stack {
     /// stack-level override (e.g. an empty-string assignment) was present at hide time.
     pub fn hide_env_var(&mut self, engine_state: &EngineState, name: &str) -> bool {
         let env_name = EnvName::from(name);
+        self.mark_env_change(&env_name);
 
         // Re-hiding the same env var in the same scope should report not found.
         if self.is_env_var_hide_recorded(&env_name) {
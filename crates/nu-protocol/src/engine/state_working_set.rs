@@ -1198,11 +1198,24 @@ impl miette::SourceCode for &StateWorkingSet<'_> {
 /// When a file source/import another file, the new file is pushed onto the stack.
 /// Attempting to add files that are already in the stack (circular import) results in an error.
 ///
-/// Note that file paths are compared without canonicalization, so the same
-/// physical file may still appear multiple times under different paths.
-/// This doesn't affect circular import detection though.
+/// Files are compared by their canonical paths, so a file that is reached again through another
+/// path, like `../lib/a.nu` instead of `a.nu` or through a symbolic link, is still detected.
 #[derive(Debug, Default)]
-pub struct FileStack(Vec<PathBuf>);
+pub struct FileStack(Vec<StackedFile>);
+
+#[derive(Debug)]
+struct StackedFile {
+    path: PathBuf,
+    /// The canonical path, or the path itself when it can't be canonicalized (e.g. virtual files).
+    canonical: PathBuf,
+}
+
+impl StackedFile {
+    fn new(path: PathBuf) -> Self {
+        let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        Self { path, canonical }
+    }
+}
 
 impl FileStack {
     /// Creates an empty stack.
@@ -1215,41 +1228,44 @@ impl FileStack {
     /// This is a convenience method that creates an empty stack, then pushes the file onto it.
     /// It skips the circular import check and always succeeds.
     pub fn with_file(path: PathBuf) -> Self {
-        Self(vec![path])
+        Self(vec![StackedFile::new(path)])
     }
 
     /// Adds a file to the stack.
     ///
-    /// If the same file is already present in the stack, returns `ParseError::CircularImport`.
+    /// If the same file is already present in the stack, returns `ParseError::CircularImport`
+    /// showing the chain of files from its first occurrence.
     pub fn push(&mut self, path: PathBuf, span: Span) -> Result<(), ParseError> {
+        let file = StackedFile::new(path);
+
         // Check for circular import.
-        if let Some(i) = self.0.iter().rposition(|p| p == &path) {
+        if let Some(i) = self.0.iter().rposition(|f| f.canonical == file.canonical) {
             let filenames: Vec<String> = self.0[i..]
                 .iter()
-                .chain(std::iter::once(&path))
-                .map(|p| p.to_string_lossy().to_string())
+                .chain(std::iter::once(&file))
+                .map(|f| f.path.to_string_lossy().to_string())
                 .collect();
             let msg = filenames.join("\nuses ");
             return Err(ParseError::CircularImport(msg, span));
         }
 
-        self.0.push(path);
+        self.0.push(file);
         Ok(())
     }
 
     /// Removes a file from the stack and returns its path, or None if the stack is empty.
     pub fn pop(&mut self) -> Option<PathBuf> {
-        self.0.pop()
+        self.0.pop().map(|file| file.path)
     }
 
     /// Returns the active file (that is, the file on the top of the stack), or None if the stack is empty.
     pub fn top(&self) -> Option<&Path> {
-        self.0.last().map(PathBuf::as_path)
+        self.0.last().map(|file| file.path.as_path())
     }
 
     /// Returns the parent directory of the active file, or None if the stack is empty
     /// or the active file doesn't have a parent directory as part of its path.
     pub fn current_working_directory(&self) -> Option<&Path> {
-        self.0.last().and_then(|path| path.parent())
+        self.0.last().and_then(|file| file.path.parent())
     }
 }
@@ -151,7 +151,12 @@ pub enum ParseError {
     },
 
     #[error("Capture of mutable variable.")]
-    #[diagnostic(code(nu::parser::expected_keyword))]
+    #[diagnostic(
+        code(nu::parser::expected_keyword),
+        help(
+            "Closures can't change mutable variables. To share a value that closures can change, use a reference: `let counter = ref new 0; $counter | ref update {|n| $n + 1 }`"
+        )
+    )]
     CaptureOfMutableVar(#[label("capture of mutable variable")] Span),
 
     #[error("Expected keyword.")]
@@ -290,7 +295,10 @@ pub enum ParseError {
     ),
 
     #[error("Circular import.")]
-    #[diagnostic(code(nu::parser::circular_import), help("{0}"))]
+    #[diagnostic(
+        code(nu::parser::circular_import),
+        help("{0}\n\nbreak the cycle by moving what these files share into another module")
+    )]
     CircularImport(String, #[label = "detected circular import"] Span),
 
     #[error("Can't export {0} named same as the module.")]
@@ -24,12 +24,24 @@ pub enum ParseWarning {
         help: Option<String>,
         report_mode: ReportMode,
     },
+
+    /// Only the statements before a parse error were run, see `$env.config.recover_parse_errors`.
+    #[error("Ran the statements before the parse error.")]
+    #[diagnostic(
+        code(nu::parser::partial_run),
+        help("fix the statement and run it again, the ones before it already ran")
+    )]
+    PartialRun {
+        #[label("skipped from here")]
+        span: Span,
+    },
 }
 
 impl ParseWarning {
     pub fn span(&self) -> Span {
         match self {
             ParseWarning::Deprecated { span, .. } => *span,
+            ParseWarning::PartialRun { span } => *span,
         }
     }
 }
@@ -38,6 +50,7 @@ impl Reportable for ParseWarning {
     fn report_mode(&self) -> ReportMode {
         match self {
             ParseWarning::Deprecated { report_mode, .. } => *report_mode,
+            ParseWarning::PartialRun { .. } => ReportMode::EveryUse,
         }
     }
 }
@@ -52,6 +65,7 @@ impl Hash for ParseWarning {
                 dep_type.hash(state);
                 label.hash(state);
             }
+            ParseWarning::PartialRun { span } => span.hash(state),
         }
     }
 }
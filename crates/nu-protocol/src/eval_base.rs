@@ -286,10 +286,10 @@ pub trait Eval {
                             Comparison::EndsWith => lhs.ends_with(op_span, &rhs, expr_span),
                             Comparison::NotEndsWith => lhs.not_ends_with(op_span, &rhs, expr_span),
                             Comparison::RegexMatch => {
-                                Self::regex_match(state, op_span, &lhs, &rhs, false, expr_span)
+                                Self::regex_match(state, mut_state, op_span, &lhs, &rhs, false, expr_span)
                             }
                             Comparison::NotRegexMatch => {
-                                Self::regex_match(state, op_span, &lhs, &rhs, true, expr_span)
+                                Self::regex_match(state, mut_state, op_span, &lhs, &rhs, true, expr_span)
                             }
                         }
                     }
@@ -386,6 +386,7 @@ pub trait Eval {
 
     fn regex_match(
         state: Self::State<'_>,
+        mut_state: &mut Self::MutState,
         op_span: Span,
         lhs: &Value,
         rhs: &Value,
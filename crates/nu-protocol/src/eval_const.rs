@@ -364,6 +364,7 @@ impl Eval for EvalConst {
 
     fn regex_match(
         _: &StateWorkingSet,
+        _: &mut (),
         _op_span: Span,
         _: &Value,
         _: &Value,
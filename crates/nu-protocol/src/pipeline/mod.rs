@@ -4,6 +4,7 @@ pub mod list_stream;
 mod metadata;
 mod out_dest;
 mod pipeline_data;
+mod signal_hooks;
 mod signals;
 
 pub use byte_stream::*;
@@ -12,4 +13,5 @@ pub use list_stream::*;
 pub use metadata::*;
 pub use out_dest::*;
 pub use pipeline_data::*;
+pub use signal_hooks::*;
 pub use signals::*;
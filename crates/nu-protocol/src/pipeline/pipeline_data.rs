@@ -66,6 +66,22 @@ impl PipelineData {
         PipelineData::ListStream(stream, metadata.into())
     }
 
+    /// Turns a streamable custom value into a stream of its items, leaving other input as is.
+    ///
+    /// Commands working on the items of their input, like `each` and `where`, call this before
+    /// iterating, so custom values are only streamed where that makes sense.
+    pub fn stream_custom_value(self, signals: &Signals) -> Result<PipelineData, ShellError> {
+        match self {
+            PipelineData::Value(Value::Custom { val, internal_span }, metadata)
+                if val.is_streamable() =>
+            {
+                let stream = val.iterate(internal_span, signals.clone())?;
+                Ok(PipelineData::list_stream(stream, metadata))
+            }
+            input => Ok(input),
+        }
+    }
+
     pub fn byte_stream(stream: ByteStream, metadata: impl Into<Option<PipelineMetadata>>) -> Self {
         PipelineData::ByteStream(stream, metadata.into())
     }
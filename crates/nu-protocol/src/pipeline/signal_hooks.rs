@@ -0,0 +1,157 @@
+use std::{
+    fmt,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+};
+
+/// The signals scripts can run closures for, registered with `on-signal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookedSignal {
+    /// SIGINT, or Ctrl-C on Windows.
+    Interrupt,
+    /// SIGTERM.
+    Terminate,
+    /// SIGHUP, or the console window being closed on Windows.
+    Hangup,
+}
+
+impl HookedSignal {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Interrupt => "interrupt",
+            Self::Terminate => "terminate",
+            Self::Hangup => "hangup",
+        }
+    }
+
+    /// Parse a signal from its name, its short name like `int`, or its Unix name like `SIGINT`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        match name.strip_prefix("sig").unwrap_or(&name) {
+            "int" | "interrupt" => Some(Self::Interrupt),
+            "term" | "terminate" => Some(Self::Terminate),
+            "hup" | "hangup" => Some(Self::Hangup),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HookedSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A hook run when a signal arrives. It's called on the thread handling the signal.
+pub type SignalHook = Arc<dyn Fn(HookedSignal) + Send + Sync>;
+
+#[derive(Default)]
+struct SignalHooksState {
+    hooks: Vec<(HookedSignal, SignalHook)>,
+    /// How many signals are having their hooks run.
+    running: usize,
+}
+
+/// The hooks registered by scripts for signals, shared by all clones of the engine state.
+#[derive(Clone, Default, derive_more::Debug)]
+#[debug("SignalHooks")]
+pub struct SignalHooks(Arc<(Mutex<SignalHooksState>, Condvar)>);
+
+impl SignalHooks {
+    fn lock(&self) -> MutexGuard<'_, SignalHooksState> {
+        self.0.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn add(&self, signal: HookedSignal, hook: SignalHook) {
+        self.lock().hooks.push((signal, hook));
+    }
+
+    pub fn has_hooks(&self, signal: HookedSignal) -> bool {
+        self.lock()
+            .hooks
+            .iter()
+            .any(|(hooked, _)| *hooked == signal)
+    }
+
+    /// Run the hooks for `signal`, in the order they were added.
+    pub fn run(&self, signal: HookedSignal) {
+        self.run_after(signal, || {})
+    }
+
+    /// Run `before`, then the hooks for `signal`. The hooks count as running from before
+    /// `before` is called, so [`wait`](Self::wait) can't miss them.
+    pub fn run_after(&self, signal: HookedSignal, before: impl FnOnce()) {
+        let hooks: Vec<SignalHook> = {
+            let mut state = self.lock();
+            state.running += 1;
+            state
+                .hooks
+                .iter()
+                .filter(|(hooked, _)| *hooked == signal)
+                .map(|(_, hook)| hook.clone())
+                .collect()
+        };
+
+        before();
+        // Hooks may add other hooks, so they run without the lock
+        for hook in hooks {
+            hook(signal);
+        }
+
+        self.lock().running -= 1;
+        self.0.1.notify_all();
+    }
+
+    /// Wait for the hooks that are running to finish, so nushell doesn't exit in the middle of
+    /// them.
+    pub fn wait(&self) {
+        let mut state = self.lock();
+        while state.running > 0 {
+            state = self.0.1.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn runs_hooks_of_the_signal() {
+        let hooks = SignalHooks::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        for signal in [HookedSignal::Interrupt, HookedSignal::Terminate] {
+            let calls = calls.clone();
+            hooks.add(
+                signal,
+                Arc::new(move |_| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                }),
+            );
+        }
+
+        hooks.run(HookedSignal::Interrupt);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        hooks.run(HookedSignal::Hangup);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(hooks.has_hooks(HookedSignal::Terminate));
+        assert!(!hooks.has_hooks(HookedSignal::Hangup));
+    }
+
+    #[test]
+    fn parses_signal_names() {
+        assert_eq!(
+            HookedSignal::from_name("SIGINT"),
+            Some(HookedSignal::Interrupt)
+        );
+        assert_eq!(
+            HookedSignal::from_name("term"),
+            Some(HookedSignal::Terminate)
+        );
+        assert_eq!(
+            HookedSignal::from_name("hangup"),
+            Some(HookedSignal::Hangup)
+        );
+        assert_eq!(HookedSignal::from_name("kill"), None);
+    }
+}
@@ -7,12 +7,21 @@ use serde::{Deserialize, Serialize};
 pub struct PluginMetadata {
     /// The version of the plugin itself, as self-reported.
     pub version: Option<String>,
+    /// The version of the plugin protocol spoken by the plugin when its signatures were cached.
+    ///
+    /// This is set by the engine, not the plugin, and is used to detect a plugin binary that
+    /// changed since it was added to the registry file.
+    #[serde(default)]
+    pub protocol_version: Option<String>,
 }
 
 impl PluginMetadata {
     /// Create empty metadata.
     pub const fn new() -> PluginMetadata {
-        PluginMetadata { version: None }
+        PluginMetadata {
+            version: None,
+            protocol_version: None,
+        }
     }
 
     /// Set the version of the plugin on the metadata. A suggested way to construct this is:
@@ -29,6 +38,12 @@ impl PluginMetadata {
         self.version = Some(version.into());
         self
     }
+
+    /// Set the version of the plugin protocol the signatures were cached with.
+    pub fn with_protocol_version(mut self, protocol_version: impl Into<String>) -> Self {
+        self.protocol_version = Some(protocol_version.into());
+        self
+    }
 }
 
 impl Default for PluginMetadata {
@@ -1,6 +1,9 @@
 use std::{any::Any, sync::Arc};
 
-use crate::{Handlers, PluginGcConfig, PluginIdentity, PluginMetadata, ShellError};
+use crate::{
+    Handlers, PluginGcConfig, PluginIdentity, PluginMetadata, ShellError,
+    engine::{EngineState, Stack},
+};
 
 /// Trait for plugins registered in the [`EngineState`](crate::engine::EngineState).
 pub trait RegisteredPlugin: Send + Sync {
@@ -22,6 +25,16 @@ pub trait RegisteredPlugin: Send + Sync {
     /// Set garbage collection config for the plugin.
     fn set_gc_config(&self, gc_config: &PluginGcConfig);
 
+    /// Send the plugin its section of `$env.config.plugins` if it's running and the section
+    /// changed. Closures in the config are evaluated with the `engine_state` and `stack`.
+    fn update_plugin_config(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &Stack,
+    ) -> Result<(), ShellError> {
+        Ok(())
+    }
+
     /// Stop the plugin.
     fn stop(&self) -> Result<(), ShellError>;
 
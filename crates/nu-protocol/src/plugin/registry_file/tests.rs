@@ -14,6 +14,7 @@ fn foo_plugin() -> PluginRegistryItem {
         data: PluginRegistryItemData::Valid {
             metadata: PluginMetadata {
                 version: Some("0.1.0".into()),
+                protocol_version: Some("0.93.0".into()),
             },
             commands: vec![PluginSignature {
                 sig: Signature::new("foo")
@@ -42,6 +43,7 @@ fn bar_plugin() -> PluginRegistryItem {
         data: PluginRegistryItemData::Valid {
             metadata: PluginMetadata {
                 version: Some("0.2.0".into()),
+                protocol_version: None,
             },
             commands: vec![PluginSignature {
                 sig: Signature::new("bar")
@@ -165,6 +165,12 @@ pub enum CommandWideCompleter {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Completion {
     Command(DeclId),
+    /// A command looked up by name when completing, like a plugin command completing the
+    /// arguments of the other commands of its plugin, since plugins don't know their [`DeclId`]s.
+    ///
+    /// Plugin commands are asked through the plugin protocol for their dynamic completions of the
+    /// argument, other commands are called like a custom completer in a `def`.
+    CommandName(String),
     List(NuCow<&'static [&'static str], Vec<String>>),
 }
 
@@ -180,6 +186,7 @@ impl Completion {
                 .name()
                 .to_owned()
                 .into_value(span),
+            Completion::CommandName(name) => name.clone().into_value(span),
             Completion::List(list) => match list {
                 NuCow::Borrowed(list) => list
                     .iter()
@@ -920,6 +927,10 @@ pub struct CustomExample {
     pub example: String,
     pub description: String,
     pub result: Option<Value>,
+    /// Whether the example shows a result that can't be checked, like a result in a doc comment
+    /// that isn't a constant
+    #[nu_value(default)]
+    pub unchecked_result: bool,
 }
 
 impl CustomExample {
@@ -989,6 +1000,12 @@ impl Command for BlockCommand {
             .collect()
     }
 
+    fn example_result_unchecked(&self, index: usize) -> bool {
+        self.examples
+            .get(index)
+            .is_some_and(|example| example.unchecked_result)
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         self.signature
             .search_terms
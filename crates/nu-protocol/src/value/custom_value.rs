@@ -1,7 +1,9 @@
 use crate::ast::PathMember;
 use crate::shell_error::generic::GenericError;
 use crate::value::CellPathMutation;
-use crate::{ShellError, Span, Spanned, Type, Value, ast::Operator, casing::Casing};
+use crate::{
+    ListStream, ShellError, Signals, Span, Spanned, Type, Value, ast::Operator, casing::Casing,
+};
 use std::any::Any;
 use std::{cmp::Ordering, fmt, path::Path};
 
@@ -177,6 +179,49 @@ pub trait CustomValue: fmt::Debug + Send + Sync + Any {
         std::mem::size_of_val(self)
     }
 
+    /// Returns `true` if this custom value stands for the value returned by
+    /// [`to_base_value()`](Self::to_base_value), like a value that is computed lazily. Commands
+    /// whose input or arguments take a specific type, other than this custom value's type, get
+    /// that value instead of the custom value.
+    ///
+    /// The default is `false`.
+    fn is_transparent(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if this custom value can be iterated over as a stream with
+    /// [`iterate()`](Self::iterate), when used with `each` and `where`. Other commands get the
+    /// custom value itself, see
+    /// [`PipelineData::stream_custom_value`](crate::PipelineData::stream_custom_value).
+    ///
+    /// This is checked before iterating, so it should be cheap and must not fail.
+    ///
+    /// The default is `false`.
+    fn is_streamable(&self) -> bool {
+        false
+    }
+
+    /// Iterates over the items of this custom value as a stream, without first materializing
+    /// them into a list. Only called if [`is_streamable()`](Self::is_streamable) returns `true`.
+    ///
+    /// Items are only produced as they are consumed, so this works well for lazy data structures
+    /// like query results or data frames. For plugin custom values, the stream is produced by the
+    /// plugin and read by the engine on demand, and dropping it stops the plugin's iteration.
+    ///
+    /// The default implementation streams the items of the list returned by
+    /// [`to_base_value()`](Self::to_base_value).
+    fn iterate(&self, span: Span, signals: Signals) -> Result<ListStream, ShellError> {
+        match self.to_base_value(span)? {
+            Value::List { vals, .. } => Ok(ListStream::new(vals.into_iter(), span, signals)),
+            other => Err(ShellError::OnlySupportsThisInputType {
+                exp_input_type: "list".into(),
+                wrong_type: other.get_type().to_string(),
+                dst_span: span,
+                src_span: other.span(),
+            }),
+        }
+    }
+
     /// Returns `true` if this custom value should be iterable (like a list) when used with
     /// commands like `each`, `where`, etc.
     ///
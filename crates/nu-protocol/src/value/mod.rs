@@ -23,7 +23,7 @@ pub use range::{FloatRange, IntRange, ParseRangeError, Range};
 pub use record::Record;
 
 use crate::{
-    BlockId, CompareTypes, Config, ShellError, Signals, Span, Type, TypeRelation,
+    BlockId, CompareTypes, Config, RegexConfig, ShellError, Signals, Span, Type, TypeRelation,
     ast::{Bits, Boolean, CellPath, Comparison, Math, Operator, PathMember},
     did_you_mean,
     engine::{Closure, EngineState},
@@ -1445,11 +1445,7 @@ impl Value {
                             Value::Record { val: record, .. } => {
                                 let value = record.to_mut().cased_mut(*casing).remove(col_name);
                                 if value.is_none() && !optional {
-                                    return Err(ShellError::CantFindColumn {
-                                        col_name: col_name.clone(),
-                                        span: Some(*span),
-                                        src_span: v_span,
-                                    });
+                                    return Err(column_not_found(record, col_name, *span, v_span));
                                 }
                             }
                             v => {
@@ -1471,11 +1467,7 @@ impl Value {
                         .is_none()
                         && !optional
                     {
-                        return Err(ShellError::CantFindColumn {
-                            col_name: col_name.clone(),
-                            span: Some(*span),
-                            src_span: v_span,
-                        });
+                        return Err(column_not_found(record, col_name, *span, v_span));
                     }
                     Ok(())
                 }
@@ -1545,11 +1537,7 @@ impl Value {
             match action {
                 CellPathMutation::Update | CellPathMutation::Remove { .. } => {
                     if !optional {
-                        return Err(ShellError::CantFindColumn {
-                            col_name: col_name.to_owned(),
-                            span: Some(*span),
-                            src_span,
-                        });
+                        return Err(column_not_found(record, col_name, *span, src_span));
                     }
                     Ok(())
                 }
@@ -2335,17 +2323,8 @@ fn get_value_member<'a>(
                     } else if *optional {
                         Ok(ControlFlow::Break(*origin_span))
                         // short-circuit
-                    } else if let Some(suggestion) = did_you_mean(val.columns(), column_name) {
-                        Err(ShellError::DidYouMean {
-                            suggestion,
-                            span: *origin_span,
-                        })
                     } else {
-                        Err(ShellError::CantFindColumn {
-                            col_name: column_name.clone(),
-                            span: Some(*origin_span),
-                            src_span: span,
-                        })
+                        Err(column_not_found(val, column_name, *origin_span, span))
                     }
                 }
                 // String access of Lists always means Table access.
@@ -2363,19 +2342,13 @@ fn get_value_member<'a>(
                                         Ok(found.clone())
                                     } else if *optional {
                                         Ok(Value::nothing(*origin_span))
-                                    } else if let Some(suggestion) =
-                                        did_you_mean(val.columns(), column_name)
-                                    {
-                                        Err(ShellError::DidYouMean {
-                                            suggestion,
-                                            span: *origin_span,
-                                        })
                                     } else {
-                                        Err(ShellError::CantFindColumn {
-                                            col_name: column_name.clone(),
-                                            span: Some(*origin_span),
-                                            src_span: val_span,
-                                        })
+                                        Err(column_not_found(
+                                            val,
+                                            column_name,
+                                            *origin_span,
+                                            val_span,
+                                        ))
                                     }
                                 }
                                 Value::Nothing { .. } if *optional => {
@@ -2422,6 +2395,18 @@ fn get_value_member<'a>(
     }
 }
 
+/// The error for a column missing from a record, which suggests a similar column if there is one.
+fn column_not_found(record: &Record, col_name: &str, span: Span, src_span: Span) -> ShellError {
+    match did_you_mean(record.columns(), col_name) {
+        Some(suggestion) => ShellError::DidYouMean { suggestion, span },
+        None => ShellError::CantFindColumn {
+            col_name: col_name.to_owned(),
+            span: Some(span),
+            src_span,
+        },
+    }
+}
+
 impl Default for Value {
     fn default() -> Self {
         Value::Nothing {
@@ -3983,9 +3968,12 @@ impl Value {
         rhs.r#not_in(op, self, span)
     }
 
+    /// Match against the regex on the right, with the `options` of `$env.config.regex`, which the
+    /// regex can still turn off inline.
     pub fn regex_match(
         &self,
         engine_state: &EngineState,
+        options: RegexConfig,
         op: Span,
         rhs: &Value,
         invert: bool,
@@ -3994,6 +3982,7 @@ impl Value {
         let rhs_span = rhs.span();
         match (self, rhs) {
             (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => {
+                let rhs = &format!("{}{rhs}", options.inline_flags());
                 let is_match = match engine_state.regex_cache.try_lock() {
                     Ok(mut cache) => {
                         if let Some(regex) = cache.get(rhs) {
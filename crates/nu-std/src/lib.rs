@@ -66,6 +66,16 @@ pub fn load_standard_library(
         ),
         ("mod.nu", "std/clip", include_str!("../std/clip/mod.nu")),
         ("mod.nu", "std/random", include_str!("../std/random/mod.nu")),
+        (
+            "mod.nu",
+            "std/package",
+            include_str!("../std/package/mod.nu"),
+        ),
+        (
+            "mod.nu",
+            "std/schedule",
+            include_str!("../std/schedule/mod.nu"),
+        ),
     ];
 
     for (filename, std_subdir_name, content) in std_submodules.drain(..) {
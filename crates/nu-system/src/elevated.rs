@@ -0,0 +1,138 @@
+//! Running a program as administrator, which needs the user's consent through a UAC prompt.
+//!
+//! `CreateProcess` can't start elevated processes, so this goes through `ShellExecuteEx` with the
+//! `runas` verb. The program gets its own console window, so its output can't be captured.
+
+use std::{
+    ffi::{OsStr, OsString},
+    io, iter,
+    os::windows::ffi::OsStrExt,
+    path::Path,
+};
+use windows::{
+    Win32::{
+        Foundation::{CloseHandle, WAIT_TIMEOUT},
+        System::Threading::{GetExitCodeProcess, WaitForSingleObject},
+        UI::{
+            Shell::{
+                SEE_MASK_NOASYNC, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW, ShellExecuteExW,
+            },
+            WindowsAndMessaging::SW_SHOWNORMAL,
+        },
+    },
+    core::PCWSTR,
+};
+
+/// How long to wait for the elevated process between checks for an interrupt, in milliseconds.
+const INTERRUPT_CHECK_INTERVAL: u32 = 100;
+
+/// Run `program` as administrator after a UAC prompt, and wait for it to exit.
+///
+/// Returns the exit code, or `None` if `interrupted` returned true before the program exited. The
+/// program keeps running then, as an unelevated process can't terminate it. Declining the UAC
+/// prompt is an error of kind [`io::ErrorKind::PermissionDenied`].
+pub fn run_elevated<'a>(
+    program: &OsStr,
+    args: impl IntoIterator<Item = &'a OsStr>,
+    cwd: Option<&Path>,
+    interrupted: impl Fn() -> bool,
+) -> io::Result<Option<i32>> {
+    let verb = wide("runas");
+    let file = wide(program);
+    let parameters = wide(join_args(args));
+    let directory = cwd.map(wide);
+
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS | SEE_MASK_NOASYNC,
+        lpVerb: PCWSTR(verb.as_ptr()),
+        lpFile: PCWSTR(file.as_ptr()),
+        lpParameters: PCWSTR(parameters.as_ptr()),
+        lpDirectory: directory
+            .as_ref()
+            .map_or(PCWSTR::null(), |directory| PCWSTR(directory.as_ptr())),
+        nShow: SW_SHOWNORMAL.0,
+        ..Default::default()
+    };
+    // SAFETY: the strings live until the end of the function
+    unsafe { ShellExecuteExW(&mut info) }?;
+
+    let process = info.hProcess;
+    let result = loop {
+        // SAFETY: `SEE_MASK_NOCLOSEPROCESS` gives us the process handle, closed below
+        if unsafe { WaitForSingleObject(process, INTERRUPT_CHECK_INTERVAL) } != WAIT_TIMEOUT {
+            let mut code = 0;
+            // SAFETY: the process handle is valid until closed below
+            break unsafe { GetExitCodeProcess(process, &mut code) }
+                .map(|()| Some(code as i32))
+                .map_err(io::Error::from);
+        }
+        if interrupted() {
+            break Ok(None);
+        }
+    };
+    // SAFETY: the handle isn't used anymore
+    let _ = unsafe { CloseHandle(process) };
+    result
+}
+
+fn wide(text: impl AsRef<OsStr>) -> Vec<u16> {
+    text.as_ref().encode_wide().chain(iter::once(0)).collect()
+}
+
+/// Join arguments into a command line, quoting them like the Rust standard library does for
+/// `CreateProcess`.
+fn join_args<'a>(args: impl IntoIterator<Item = &'a OsStr>) -> OsString {
+    let mut line = OsString::new();
+    for (index, arg) in args.into_iter().enumerate() {
+        if index > 0 {
+            line.push(" ");
+        }
+        line.push(quote_arg(&arg.to_string_lossy()));
+    }
+    line
+}
+
+fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_owned();
+    }
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                // Backslashes before a quote are escaped, and so is the quote
+                quoted.extend(iter::repeat_n('\\', backslashes * 2 + 1));
+                backslashes = 0;
+            }
+            _ => {
+                quoted.extend(iter::repeat_n('\\', backslashes));
+                backslashes = 0;
+            }
+        }
+        if c != '\\' {
+            quoted.push(c);
+        }
+    }
+    // The closing quote must not be escaped by trailing backslashes
+    quoted.extend(iter::repeat_n('\\', backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_args() {
+        assert_eq!(quote_arg("plain"), "plain");
+        assert_eq!(quote_arg(""), r#""""#);
+        assert_eq!(quote_arg("with space"), r#""with space""#);
+        assert_eq!(quote_arg(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(quote_arg(r"C:\dir with space\"), r#""C:\dir with space\\""#);
+        assert_eq!(quote_arg(r#"a\"b"#), r#""a\\\"b""#);
+    }
+}
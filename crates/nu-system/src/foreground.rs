@@ -34,6 +34,32 @@ pub fn prepare_background_command(command: &mut Command) {
     }
 }
 
+/// The process group and session of a program that replaces nushell through `exec`.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExecGroup {
+    /// Keep nushell's process group and session.
+    #[default]
+    Inherit,
+    /// Lead a new process group, which is given the terminal if nushell is in control of it.
+    NewGroup,
+    /// Lead a new session, without a controlling terminal.
+    NewSession,
+}
+
+/// Prepare `command` to replace nushell with [`exec`](std::os::unix::process::CommandExt::exec).
+///
+/// Interactive nushell ignores job control signals, and ignored signals stay ignored across
+/// `exec`, so they are reset to their default here.
+///
+/// With [`ExecGroup::NewGroup`] or [`ExecGroup::NewSession`], `command` must be spawned instead:
+/// nushell may already lead its process group and session, which `setpgid` and `setsid` can
+/// only leave in a child.
+#[cfg(unix)]
+pub fn prepare_exec_command(command: &mut Command, group: ExecGroup) {
+    child_pgroup::prepare_exec_command(command, group);
+}
+
 #[cfg(unix)]
 use nix::{sys::signal, sys::wait, unistd::Pid};
 
@@ -50,11 +76,19 @@ use nix::{sys::signal, sys::wait, unistd::Pid};
 ///
 /// For non-interactive mode, processes are spawned normally without any foreground process handling.
 ///
+/// ## Windows
+///
+/// The child is put in its own job object, so [`kill_by_pid`](crate::kill_by_pid) with its PID
+/// terminates the processes it started too. If the child is ended by Ctrl-C, the processes it
+/// left behind are terminated as well.
+///
 /// ## Other systems
 ///
-/// It does nothing special on non-unix systems, so `spawn` is the same as [`std::process::Command::spawn`].
+/// It does nothing special on other systems, so `spawn` is the same as [`std::process::Command::spawn`].
 pub struct ForegroundChild {
     inner: Child,
+    #[cfg(windows)]
+    job: Option<Arc<crate::job_object::JobObject>>,
     #[cfg(unix)]
     pipeline_state: Option<Arc<(AtomicU32, AtomicU32)>>,
 
@@ -64,7 +98,16 @@ pub struct ForegroundChild {
 }
 
 impl ForegroundChild {
-    #[cfg(not(unix))]
+    #[cfg(windows)]
+    pub fn spawn(mut command: Command) -> io::Result<Self> {
+        command.spawn().map(|child| {
+            // Without a job object, only the child itself can be killed
+            let job = crate::job_object::JobObject::assign(&child).ok();
+            Self { inner: child, job }
+        })
+    }
+
+    #[cfg(not(any(unix, windows)))]
     pub fn spawn(mut command: Command) -> io::Result<Self> {
         command.spawn().map(|child| Self { inner: child })
     }
@@ -124,7 +167,17 @@ impl ForegroundChild {
                 }
             })
         }
-        #[cfg(not(unix))]
+        #[cfg(windows)]
+        {
+            let status = self.as_mut().wait()?;
+            if let Some(job) = &self.job
+                && status.code() == Some(crate::job_object::CONTROL_C_EXIT)
+            {
+                let _ = job.terminate(crate::job_object::CONTROL_C_EXIT);
+            }
+            Ok(status.into())
+        }
+        #[cfg(not(any(unix, windows)))]
         self.as_mut().wait().map(Into::into)
     }
 
@@ -234,6 +287,15 @@ impl Drop for ForegroundChild {
     }
 }
 
+#[cfg(windows)]
+impl Drop for ForegroundChild {
+    fn drop(&mut self) {
+        if self.job.is_some() {
+            crate::job_object::JobObject::release(self.inner.id());
+        }
+    }
+}
+
 /// Keeps a specific already existing process in the foreground as long as the [`ForegroundGuard`].
 /// If the process needs to be spawned in the foreground, use [`ForegroundChild`] instead. This is
 /// used to temporarily bring frozen and plugin processes into the foreground.
@@ -371,7 +433,7 @@ mod child_pgroup {
         unistd::{self, Pid},
     };
     use std::{
-        io::Write,
+        io::{IsTerminal, Write},
         os::{
             fd::{AsFd, BorrowedFd},
             unix::prelude::CommandExt,
@@ -407,6 +469,49 @@ mod child_pgroup {
         }
     }
 
+    pub fn prepare_exec_command(command: &mut Command, group: super::ExecGroup) {
+        // Decided before spawning the command in a new group, as the child can't tell anymore
+        let owns_terminal = std::io::stdin().is_terminal()
+            && unistd::tcgetpgrp(unsafe { stdin_fd() }).ok() == Some(unistd::getpgrp());
+        unsafe {
+            // Safety:
+            // POSIX only allows async-signal-safe functions to be called.
+            // `setpgid`, `setsid`, `tcsetpgrp`, `getpid` and `sigaction` are async-signal-safe
+            // according to:
+            // https://manpages.ubuntu.com/manpages/bionic/man7/signal-safety.7.html
+            command.pre_exec(move || {
+                match group {
+                    super::ExecGroup::Inherit => {}
+                    super::ExecGroup::NewGroup => {
+                        unistd::setpgid(Pid::from_raw(0), Pid::from_raw(0))?;
+                        if owns_terminal {
+                            // The new group is in the background until it takes the terminal,
+                            // and SIGTTOU would stop it; the signal is reset below
+                            let ignore = SigAction::new(
+                                SigHandler::SigIgn,
+                                SaFlags::empty(),
+                                SigSet::empty(),
+                            );
+                            let _ = sigaction(Signal::SIGTTOU, &ignore);
+                            let _ = unistd::tcsetpgrp(stdin_fd(), Pid::this());
+                        }
+                    }
+                    super::ExecGroup::NewSession => {
+                        unistd::setsid()?;
+                    }
+                }
+
+                let default = SigAction::new(SigHandler::SigDfl, SaFlags::empty(), SigSet::empty());
+                let _ = sigaction(Signal::SIGQUIT, &default);
+                let _ = sigaction(Signal::SIGTSTP, &default);
+                let _ = sigaction(Signal::SIGTTIN, &default);
+                let _ = sigaction(Signal::SIGTTOU, &default);
+
+                Ok(())
+            });
+        }
+    }
+
     pub fn prepare_command(external_command: &mut Command, existing_pgrp: u32, background: bool) {
         unsafe {
             // Safety:
@@ -0,0 +1,79 @@
+//! Windows job objects, which group an external command with the processes it starts, so the
+//! whole process tree can be terminated at once.
+//!
+//! Windows has no process groups like Unix, and killing a process leaves its children running.
+//! Each [`ForegroundChild`](crate::ForegroundChild) is put in its own job object, and
+//! [`kill_by_pid`](crate::kill_by_pid) terminates that job when it's given the child's PID.
+
+use std::{
+    collections::HashMap,
+    io,
+    os::windows::io::AsRawHandle,
+    process::Child,
+    sync::{Arc, LazyLock, Mutex, MutexGuard},
+};
+use windows::{
+    Win32::{
+        Foundation::{CloseHandle, HANDLE},
+        System::JobObjects::{AssignProcessToJobObject, CreateJobObjectW, TerminateJobObject},
+    },
+    core::PCWSTR,
+};
+
+/// The exit code of a process that was ended by Ctrl-C, `STATUS_CONTROL_C_EXIT`.
+pub(crate) const CONTROL_C_EXIT: i32 = 0xC000_013A_u32 as i32;
+
+/// The job objects of the running foreground children, by PID.
+static PROCESS_TREES: LazyLock<Mutex<HashMap<u32, Arc<JobObject>>>> =
+    LazyLock::new(Default::default);
+
+fn trees() -> MutexGuard<'static, HashMap<u32, Arc<JobObject>>> {
+    PROCESS_TREES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub(crate) struct JobObject(HANDLE);
+
+// SAFETY: job object handles can be used from any thread
+unsafe impl Send for JobObject {}
+unsafe impl Sync for JobObject {}
+
+impl JobObject {
+    /// Put `child` in a new job object, which its children will be part of too.
+    ///
+    /// Processes the child starts before it's assigned escape the job, which is unlikely as it
+    /// has only just been spawned.
+    pub(crate) fn assign(child: &Child) -> io::Result<Arc<Self>> {
+        // SAFETY: the name may be null, and the handle is closed by `Drop`
+        let job = Self(unsafe { CreateJobObjectW(None, PCWSTR::null()) }?);
+        // SAFETY: both handles are valid for the duration of the call
+        unsafe { AssignProcessToJobObject(job.0, HANDLE(child.as_raw_handle())) }?;
+        let job = Arc::new(job);
+        trees().insert(child.id(), job.clone());
+        Ok(job)
+    }
+
+    /// Terminate all the processes still in the job.
+    pub(crate) fn terminate(&self, exit_code: i32) -> io::Result<()> {
+        // SAFETY: the handle is valid as long as `self` is
+        unsafe { TerminateJobObject(self.0, exit_code as u32) }?;
+        Ok(())
+    }
+
+    /// Forget the job of `pid`, once the process has exited.
+    pub(crate) fn release(pid: u32) {
+        trees().remove(&pid);
+    }
+}
+
+impl Drop for JobObject {
+    fn drop(&mut self) {
+        // SAFETY: the handle was created by `CreateJobObjectW` and is only closed here
+        let _ = unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// Terminate the process tree of a foreground child, if `pid` is one.
+pub(crate) fn kill_tree(pid: u32) -> Option<io::Result<()>> {
+    let job = trees().get(&pid).cloned()?;
+    Some(job.terminate(1))
+}
@@ -7,8 +7,13 @@
     )
 )]
 
+#[cfg(target_os = "windows")]
+mod elevated;
 mod exit_status;
 mod foreground;
+#[cfg(target_os = "windows")]
+mod job_object;
+mod termination;
 mod util;
 
 #[cfg(target_os = "freebsd")]
@@ -25,10 +30,13 @@ mod unix;
 #[cfg(target_os = "windows")]
 mod windows;
 
+#[cfg(target_os = "windows")]
+pub use self::elevated::run_elevated;
 pub use self::exit_status::ExitStatus;
+pub use self::termination::{TerminationSignal, set_termination_handler};
 pub use self::foreground::prepare_background_command;
 #[cfg(unix)]
-pub use self::foreground::stdin_fd;
+pub use self::foreground::{ExecGroup, prepare_exec_command, stdin_fd};
 pub use self::foreground::{
     ForegroundChild, ForegroundGuard, ForegroundWaitStatus, UnfreezeHandle,
 };
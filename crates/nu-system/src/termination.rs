@@ -0,0 +1,158 @@
+//! Running a handler before nushell is terminated by a signal, so scripts can clean up.
+//!
+//! On Unix, SIGTERM and SIGHUP are caught, and a thread runs the handler before the signal is
+//! raised again with the disposition it had before, which usually ends nushell. On Windows, the
+//! handler runs when the console window is closed, or the user logs off or shuts down, which
+//! only leaves a few seconds before the process is ended.
+
+use std::{io, sync::OnceLock};
+
+/// A signal that ends nushell unless it's handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminationSignal {
+    /// SIGTERM.
+    Terminate,
+    /// SIGHUP, or the console going away on Windows.
+    Hangup,
+}
+
+type TerminationHandler = Box<dyn Fn(TerminationSignal) + Send + Sync>;
+
+static HANDLER: OnceLock<TerminationHandler> = OnceLock::new();
+
+/// Run `handler` when a termination signal arrives, before the signal takes effect.
+///
+/// Only the first handler is installed, later calls do nothing. An ignored SIGHUP, like under
+/// `nohup`, stays ignored.
+pub fn set_termination_handler(
+    handler: impl Fn(TerminationSignal) + Send + Sync + 'static,
+) -> io::Result<()> {
+    let mut installed = Ok(());
+    HANDLER.get_or_init(|| {
+        installed = imp::install();
+        Box::new(handler)
+    });
+    installed
+}
+
+fn run_handler(signal: TerminationSignal) {
+    if let Some(handler) = HANDLER.get() {
+        handler(signal);
+    }
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::{TerminationSignal, run_handler};
+    use nix::{
+        libc,
+        sys::signal::{SaFlags, SigAction, SigHandler, SigSet, Signal, raise, sigaction},
+    };
+    use std::{
+        io::{self, Read},
+        os::{fd::IntoRawFd, unix::net::UnixStream},
+        sync::atomic::{AtomicI32, Ordering},
+        thread,
+    };
+
+    /// Where the signal handler writes the signals it gets, for the handler thread to read.
+    static SIGNAL_WRITER: AtomicI32 = AtomicI32::new(-1);
+
+    extern "C" fn on_signal(signum: libc::c_int) {
+        // Safety: can only call async-signal-safe functions here, and `write` is one
+        let fd = SIGNAL_WRITER.load(Ordering::Relaxed);
+        if fd >= 0 {
+            let byte = signum as u8;
+            unsafe { libc::write(fd, (&raw const byte).cast(), 1) };
+        }
+    }
+
+    pub(super) fn install() -> io::Result<()> {
+        // Sockets from the standard library are closed on exec, so externals don't get them
+        let (mut reader, writer) = UnixStream::pair()?;
+        SIGNAL_WRITER.store(writer.into_raw_fd(), Ordering::Relaxed);
+
+        let handler = SigAction::new(
+            SigHandler::Handler(on_signal),
+            SaFlags::SA_RESTART,
+            SigSet::empty(),
+        );
+        let mut previous = Vec::new();
+        for signal in [Signal::SIGTERM, Signal::SIGHUP] {
+            // Safety: the handler only calls async-signal-safe functions
+            let old = unsafe { sigaction(signal, &handler) }?;
+            if signal == Signal::SIGHUP && matches!(old.handler(), SigHandler::SigIgn) {
+                let _ = unsafe { sigaction(signal, &old) };
+                continue;
+            }
+            previous.push((signal, old));
+        }
+
+        thread::Builder::new()
+            .name("termination signals".into())
+            .spawn(move || {
+                let mut byte = [0];
+                while reader.read_exact(&mut byte).is_ok() {
+                    let Some((signal, old)) = previous
+                        .iter()
+                        .find(|(signal, _)| *signal as i32 == i32::from(byte[0]))
+                    else {
+                        continue;
+                    };
+                    run_handler(if *signal == Signal::SIGHUP {
+                        TerminationSignal::Hangup
+                    } else {
+                        TerminationSignal::Terminate
+                    });
+
+                    // Let the signal do what it would have done without us
+                    unsafe {
+                        let _ = sigaction(*signal, old);
+                        let _ = raise(*signal);
+                        // Still running, so the signal didn't end nushell and may come again
+                        let _ = sigaction(*signal, &handler);
+                    }
+                }
+            })?;
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::{TerminationSignal, run_handler};
+    use std::io;
+    use windows::{
+        Win32::{
+            Foundation::FALSE,
+            System::Console::{
+                CTRL_CLOSE_EVENT, CTRL_LOGOFF_EVENT, CTRL_SHUTDOWN_EVENT, SetConsoleCtrlHandler,
+            },
+        },
+        core::BOOL,
+    };
+
+    unsafe extern "system" fn on_console_event(event: u32) -> BOOL {
+        if matches!(
+            event,
+            CTRL_CLOSE_EVENT | CTRL_LOGOFF_EVENT | CTRL_SHUTDOWN_EVENT
+        ) {
+            run_handler(TerminationSignal::Hangup);
+        }
+        // Let the next handler, or the default one ending the process, see the event
+        FALSE
+    }
+
+    pub(super) fn install() -> io::Result<()> {
+        // Safety: the handler is a function that lives as long as the process
+        unsafe { SetConsoleCtrlHandler(Some(on_console_event), true) }?;
+        Ok(())
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    pub(super) fn install() -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -2,7 +2,17 @@ use std::io;
 use std::process::Command as CommandSys;
 
 /// Tries to forcefully kill a process by its PID
+///
+/// On Windows, killing a [`ForegroundChild`](crate::ForegroundChild) also kills the processes it
+/// started.
 pub fn kill_by_pid(pid: i64) -> Result<(), KillByPidError> {
+    #[cfg(windows)]
+    if let Ok(pid) = u32::try_from(pid)
+        && let Some(result) = crate::job_object::kill_tree(pid)
+    {
+        return result.map_err(KillByPidError::Output);
+    }
+
     let mut cmd = build_kill_command(true, std::iter::once(pid), None);
 
     let output = cmd.output().map_err(KillByPidError::Output)?;
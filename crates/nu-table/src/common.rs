@@ -1,7 +1,11 @@
-use crate::{TableOutput, TableTheme, clean_charset, colorize_space_str, string_wrap};
+use crate::{
+    TableOutput, TableTheme, clean_charset, colorize_space_str, string_truncate, string_width,
+    string_wrap,
+};
+use nu_ansi_term::Style;
 use nu_color_config::{Alignment, StyleComputer, TextStyle};
 use nu_protocol::{
-    Config, FooterMode, ShellError, Span, TableMode, TrimStrategy, Value,
+    Config, FooterMode, ShellError, Span, TableAlignment, TableMode, TrimStrategy, Value,
     shell_error::generic::GenericError,
 };
 use nu_utils::terminal_size;
@@ -86,6 +90,78 @@ pub fn wrap_text(text: &str, width: usize, config: &Config) -> String {
     string_wrap(text, width, keep_words)
 }
 
+/// Trims a text to a width the way `table.trim` says to.
+pub fn trim_text(text: &str, width: usize, config: &Config) -> String {
+    match &config.table.trim {
+        TrimStrategy::Wrap { try_to_keep_words } => string_wrap(text, width, *try_to_keep_words),
+        TrimStrategy::Truncate { suffix } => {
+            let suffix = suffix.as_deref().unwrap_or_default();
+            let suffix_width = string_width(suffix);
+            if suffix_width < width {
+                string_truncate(text, width - suffix_width) + suffix
+            } else {
+                string_truncate(text, width)
+            }
+        }
+    }
+}
+
+/// Trims a cell text to the `table.columns.<name>.width` of its column.
+pub fn trim_column_text(text: String, column: &str, config: &Config) -> String {
+    match get_column_width(column, config) {
+        Some(width) if string_width(&text) > width => trim_text(&text, width, config),
+        _ => text,
+    }
+}
+
+/// The `table.columns.<name>.width` of a column.
+pub fn get_column_width(column: &str, config: &Config) -> Option<usize> {
+    config
+        .table
+        .columns
+        .get(column)
+        .and_then(|column| column.width)
+}
+
+/// The `table.columns.<name>.expand` depth of a column.
+pub fn get_column_expand(column: &str, config: &Config) -> Option<usize> {
+    config
+        .table
+        .columns
+        .get(column)
+        .and_then(|column| column.expand)
+}
+
+/// Applies the `table.columns.<name>` alignment and color of a column to a cell style.
+///
+/// A style returned by `table.row_style` for the cell row takes precedence over any color.
+pub fn get_column_style(
+    mut style: TextStyle,
+    column: &str,
+    row_style: Option<Style>,
+    config: &Config,
+    style_computer: &StyleComputer,
+) -> TextStyle {
+    if let Some(alignment) = config
+        .table
+        .columns
+        .get(column)
+        .and_then(|column| column.alignment)
+    {
+        style.alignment = match alignment {
+            TableAlignment::Left => Alignment::Left,
+            TableAlignment::Right => Alignment::Right,
+            TableAlignment::Center => Alignment::Center,
+        };
+    }
+
+    if let Some(color) = row_style.or_else(|| style_computer.compute_column(column)) {
+        style.color_style = Some(color);
+    }
+
+    style
+}
+
 pub fn get_header_style(style_computer: &StyleComputer) -> TextStyle {
     TextStyle::with_style(
         Alignment::Center,
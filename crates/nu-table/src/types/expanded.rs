@@ -9,8 +9,9 @@ use crate::{
     NuTable, TableOpts, TableOutput,
     common::{
         INDEX_COLUMN_NAME, NuText, StringResult, TableResult, check_value, configure_table,
-        error_sign, get_header_style, get_index_style, load_theme, nu_value_to_string,
-        nu_value_to_string_clean, nu_value_to_string_colored, wrap_text,
+        error_sign, get_column_expand, get_column_style, get_column_width, get_header_style,
+        get_index_style, load_theme, nu_value_to_string, nu_value_to_string_clean,
+        nu_value_to_string_colored, wrap_text,
     },
     string_width,
     types::has_index,
@@ -21,6 +22,9 @@ pub struct ExpandedTable {
     expand_limit: Option<usize>,
     flatten: bool,
     flatten_sep: String,
+    // Whether the expand limit comes from a column `expand` depth,
+    // in which case collapsed values are shown as summaries.
+    summarize: bool,
 }
 
 impl ExpandedTable {
@@ -29,6 +33,7 @@ impl ExpandedTable {
             expand_limit,
             flatten,
             flatten_sep,
+            summarize: false,
         }
     }
 
@@ -227,6 +232,11 @@ fn expand_list(input: &[Value], cfg: Cfg<'_>) -> TableResult {
 
     let mut widths = Vec::new();
 
+    let row_styles: Vec<_> = input
+        .iter()
+        .map(|item| cfg.opts.style_computer.compute_row(item))
+        .collect();
+
     if with_index {
         table.insert((0, 0), String::from("#"));
 
@@ -288,14 +298,20 @@ fn expand_list(input: &[Value], cfg: Cfg<'_>) -> TableResult {
             }
         }
 
+        if let Some(width) = get_column_width(&header, cfg.opts.config) {
+            available = available.min(width);
+        }
+
         let mut total_column_rows = 0usize;
         let mut column_width = 0;
 
-        for (row, item) in input.iter().enumerate() {
+        let column_cfg = cfg_expand_column(cfg.clone(), &header);
+
+        for ((row, item), row_style) in input.iter().enumerate().zip(&row_styles) {
             cfg.opts.signals.check(&cfg.opts.span)?;
             check_value(item)?;
 
-            let inner_cfg = cfg_expand_reset_table(cfg.clone(), available);
+            let inner_cfg = cfg_expand_reset_table(column_cfg.clone(), available);
             let cell = expand_entry_with_header(item, &header, inner_cfg);
             // TODO: optimize cause when we expand we alrready know the width (most of the time or all)
             let mut value = NuTable::create(cell.text);
@@ -313,8 +329,16 @@ fn expand_list(input: &[Value], cfg: Cfg<'_>) -> TableResult {
 
             column_width = max(column_width, value_width);
 
+            let style = get_column_style(
+                cell.style,
+                &header,
+                *row_style,
+                cfg.opts.config,
+                &cfg.opts.style_computer,
+            );
+
             table.insert_value((row + 1, column), value);
-            table.insert_style((row + 1, column), cell.style);
+            table.insert_style((row + 1, column), style);
 
             total_column_rows = total_column_rows.saturating_add(cell.size);
         }
@@ -412,7 +436,7 @@ fn expanded_table_kv(record: &Record, cfg: Cfg<'_>) -> CellResult {
     for (i, (key, value)) in record.iter().enumerate() {
         cfg.opts.signals.check(&cfg.opts.span)?;
 
-        let cell = match expand_value(value, value_width, &cfg)? {
+        let cell = match expand_value(value, value_width, &cfg_expand_column(cfg.clone(), key))? {
             Some(val) => val,
             None => return Ok(None),
         };
@@ -450,6 +474,10 @@ fn expanded_table_kv(record: &Record, cfg: Cfg<'_>) -> CellResult {
 // the flag is used as an optimization to not do `value.lines().count()` search.
 fn expand_value(value: &Value, width: usize, cfg: &Cfg<'_>) -> CellResult {
     if is_limit_reached(cfg) {
+        if let Some(summary) = value_to_summary(value, cfg) {
+            return Ok(Some(CellOutput::clean(summary, 1, false)));
+        }
+
         let value = value_to_string_clean(value, cfg);
         return Ok(Some(CellOutput::clean(value, 1, false)));
     }
@@ -519,6 +547,12 @@ fn expand_entry_with_header(item: &Value, header: &str, cfg: Cfg<'_>) -> CellOut
 
 fn expand_entry(item: &Value, cfg: Cfg<'_>) -> CellOutput {
     if is_limit_reached(&cfg) {
+        if let Some(summary) = value_to_summary(item, &cfg) {
+            let (_, style) = nu_value_to_string(item, cfg.opts.config, &cfg.opts.style_computer);
+            let value = nutext_wrap((summary, style), &cfg);
+            return CellOutput::styled(value);
+        }
+
         let value = nu_value_to_string_clean(item, cfg.opts.config, &cfg.opts.style_computer);
         let value = nutext_wrap(value, &cfg);
         return CellOutput::styled(value);
@@ -602,6 +636,30 @@ fn is_limit_reached(cfg: &Cfg<'_>) -> bool {
     matches!(cfg.format.expand_limit, Some(0))
 }
 
+/// The summary of a nested value collapsed by a column `expand` depth,
+/// e.g. `{3 fields}` or `[5 items]`, unless `table.summary` gives one.
+fn value_to_summary(value: &Value, cfg: &Cfg<'_>) -> Option<String> {
+    if !cfg.format.summarize || !matches!(value, Value::Record { .. } | Value::List { .. }) {
+        return None;
+    }
+
+    if let Some(summary) = cfg.opts.style_computer.compute_summary(value) {
+        return Some(summary);
+    }
+
+    let plural = |count: usize| if count == 1 { "" } else { "s" };
+    match value {
+        Value::Record { val, .. } => Some(format!("{{{} field{}}}", val.len(), plural(val.len()))),
+        Value::List { vals, .. } => {
+            let is_table =
+                !vals.is_empty() && vals.iter().all(|v| matches!(v, Value::Record { .. }));
+            let unit = if is_table { "row" } else { "item" };
+            Some(format!("[{} {unit}{}]", vals.len(), plural(vals.len())))
+        }
+        _ => None,
+    }
+}
+
 fn is_simple_list(vals: &[Value]) -> bool {
     vals.iter()
         .all(|v| !matches!(v, Value::Record { .. } | Value::List { .. }))
@@ -677,6 +735,21 @@ fn cfg_expand_next_level(mut cfg: Cfg<'_>, span: Span) -> Cfg<'_> {
     cfg
 }
 
+// Applies the `table.columns.<name>.expand` depth of a column,
+// unless the current limit is already lower.
+fn cfg_expand_column<'a>(mut cfg: Cfg<'a>, column: &str) -> Cfg<'a> {
+    let Some(depth) = get_column_expand(column, cfg.opts.config) else {
+        return cfg;
+    };
+
+    if cfg.format.expand_limit.is_none_or(|limit| depth < limit) {
+        cfg.format.expand_limit = Some(depth);
+        cfg.format.summarize = true;
+    }
+
+    cfg
+}
+
 fn cfg_expand_reset_table(mut cfg: Cfg<'_>, width: usize) -> Cfg<'_> {
     cfg.opts.width = width;
     cfg.opts.index_offset = 0;
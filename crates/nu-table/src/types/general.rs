@@ -6,8 +6,9 @@ use crate::{
     NuRecordsValue, NuTable, StringResult, TableOpts, TableOutput, TableResult, clean_charset,
     colorize_space,
     common::{
-        INDEX_COLUMN_NAME, NuText, check_value, configure_table, error_sign, get_header_style,
-        get_index_style, get_value_style, nu_value_to_string_colored,
+        INDEX_COLUMN_NAME, NuText, check_value, configure_table, error_sign, get_column_style,
+        get_header_style, get_index_style, get_value_style, nu_value_to_string_colored,
+        trim_column_text,
     },
     types::has_index,
 };
@@ -105,8 +106,10 @@ fn create_table_with_header(
         opts.signals.check(&opts.span)?;
         check_value(&item)?;
 
+        let row_style = opts.style_computer.compute_row(&item);
         for (col, header) in headers.iter().enumerate() {
             let (text, style) = get_string_value_with_header(&item, header, opts);
+            let (text, style) = style_column_cell(text, style, header, row_style, opts);
 
             let pos = (row + 1, col);
             table.insert(pos, text);
@@ -147,8 +150,10 @@ fn create_table_with_header_and_index(
         let text = get_table_row_index(&item, opts.config, row, row_offset);
         table.insert((row + 1, 0), text);
 
+        let row_style = opts.style_computer.compute_row(&item);
         for (col, head) in head.iter().enumerate().skip(1) {
             let (text, style) = get_string_value_with_header(&item, head.as_ref(), opts);
+            let (text, style) = style_column_cell(text, style, head.as_ref(), row_style, opts);
 
             let pos = (row + 1, col);
             table.insert(pos, text);
@@ -217,6 +222,18 @@ fn get_string_value_with_header(item: &Value, header: &str, opts: &TableOpts) ->
     }
 }
 
+fn style_column_cell(
+    text: String,
+    style: TextStyle,
+    column: &str,
+    row_style: Option<nu_ansi_term::Style>,
+    opts: &TableOpts,
+) -> NuText {
+    let text = trim_column_text(text, column, opts.config);
+    let style = get_column_style(style, column, row_style, opts.config, &opts.style_computer);
+    (text, style)
+}
+
 fn get_string_value(item: &Value, opts: &TableOpts) -> NuText {
     let (mut text, style) = get_value_style(item, opts.config, &opts.style_computer);
 
@@ -0,0 +1,187 @@
+//! Helpers to read the values of iCalendar and vCard properties.
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone};
+use ical::property::Property;
+
+/// Find the first property with a name, which is case insensitive.
+pub(super) fn property<'a>(properties: &'a [Property], name: &str) -> Option<&'a Property> {
+    properties
+        .iter()
+        .find(|prop| prop.name.eq_ignore_ascii_case(name))
+}
+
+/// Find the values of all the properties with a name.
+pub(super) fn values<'a>(properties: &'a [Property], name: &str) -> Vec<&'a str> {
+    properties
+        .iter()
+        .filter(|prop| prop.name.eq_ignore_ascii_case(name))
+        .filter_map(|prop| prop.value.as_deref())
+        .collect()
+}
+
+/// Get the first value of a parameter of a property, like the `TZID` of a date.
+pub(super) fn param<'a>(property: &'a Property, name: &str) -> Option<&'a str> {
+    property
+        .params
+        .as_ref()?
+        .iter()
+        .find(|(param, _)| param.eq_ignore_ascii_case(name))?
+        .1
+        .first()
+        .map(String::as_str)
+}
+
+/// Split a text value on a separator that isn't escaped, and unescape the parts.
+pub(super) fn split_text(value: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '\\' => match chars.next() {
+                Some('n' | 'N') => '\n',
+                Some(c) => c,
+                None => '\\',
+            },
+            c if c == separator => {
+                parts.push(String::new());
+                continue;
+            }
+            c => c,
+        };
+        if let Some(part) = parts.last_mut() {
+            part.push(c);
+        }
+    }
+    parts
+}
+
+/// Unescape a text value, like `\n` and `\,`.
+pub(super) fn unescape_text(value: &str) -> String {
+    split_text(value, '\n').join("\n")
+}
+
+/// Parse a date, like `20240115`, or a date with a time, like `20240115T090000Z`.
+///
+/// Times ending with `Z` are UTC, times with a `TZID` are in that time zone, and the other times
+/// and dates are local. Returns whether the value is only a date too.
+pub(super) fn parse_datetime(
+    value: &str,
+    tzid: Option<&str>,
+) -> Option<(DateTime<FixedOffset>, bool)> {
+    let value = value.trim();
+    if !value.contains('T') {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+            .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+            .ok()?;
+        return Some((local_datetime(date.and_hms_opt(0, 0, 0)?)?, true));
+    }
+
+    if let Some(utc) = value.strip_suffix(['Z', 'z']) {
+        let time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((time.and_utc().fixed_offset(), false));
+    }
+
+    let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    // Time zones that aren't in the IANA database, like the ones of Windows, fall back to local
+    let datetime = match tzid.and_then(|tzid| tzid.parse::<chrono_tz::Tz>().ok()) {
+        Some(tz) => tz
+            .from_local_datetime(&time)
+            .earliest()
+            .map(|time| time.fixed_offset()),
+        None => local_datetime(time),
+    }?;
+    Some((datetime, false))
+}
+
+/// Parse a date with a time in the `TZID` of its property.
+pub(super) fn property_datetime(property: &Property) -> Option<(DateTime<FixedOffset>, bool)> {
+    parse_datetime(property.value.as_deref()?, param(property, "TZID"))
+}
+
+fn local_datetime(time: NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|time| time.fixed_offset())
+}
+
+/// Parse a duration, like `PT1H30M` or `-P1W`.
+pub(super) fn parse_duration(value: &str) -> Option<chrono::TimeDelta> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let value = value.strip_prefix('P')?;
+
+    let mut seconds = 0i64;
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in value.chars() {
+        let unit = match c {
+            '0'..='9' => {
+                number.push(c);
+                continue;
+            }
+            'T' => {
+                in_time = true;
+                continue;
+            }
+            'W' if !in_time => 7 * 24 * 3600,
+            'D' if !in_time => 24 * 3600,
+            'H' if in_time => 3600,
+            'M' if in_time => 60,
+            'S' if in_time => 1,
+            _ => return None,
+        };
+        seconds = seconds.checked_add(number.parse::<i64>().ok()?.checked_mul(unit)?)?;
+        number.clear();
+    }
+    if !number.is_empty() {
+        return None;
+    }
+
+    chrono::TimeDelta::try_seconds(if negative { -seconds } else { seconds })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn splits_escaped_text() {
+        assert_eq!(split_text(r"Acme\, Inc;Sales", ';'), ["Acme, Inc", "Sales"]);
+        assert_eq!(unescape_text(r"one\ntwo\\"), "one\ntwo\\");
+    }
+
+    #[test]
+    fn parses_datetimes() {
+        let (utc, date_only) = parse_datetime("20240115T090000Z", None).expect("valid date");
+        assert_eq!(utc.to_rfc3339(), "2024-01-15T09:00:00+00:00");
+        assert!(!date_only);
+
+        let (paris, _) =
+            parse_datetime("20240115T090000", Some("Europe/Paris")).expect("valid date");
+        assert_eq!(paris.to_rfc3339(), "2024-01-15T09:00:00+01:00");
+
+        let (_, date_only) = parse_datetime("20240115", None).expect("valid date");
+        assert!(date_only);
+
+        assert_eq!(parse_datetime("2024-13-45", None), None);
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(
+            parse_duration("PT1H30M"),
+            Some(chrono::TimeDelta::minutes(90))
+        );
+        assert_eq!(parse_duration("-P1W"), Some(chrono::TimeDelta::weeks(-1)));
+        assert_eq!(
+            parse_duration("P1DT12H"),
+            Some(chrono::TimeDelta::hours(36))
+        );
+        assert_eq!(parse_duration("P1H"), None);
+        assert_eq!(parse_duration("PT5"), None);
+    }
+}
@@ -1,5 +1,10 @@
 use crate::FormatCmdsPlugin;
 
+use super::ical_values::{
+    param, parse_datetime, parse_duration, property, property_datetime, split_text, unescape_text,
+    values,
+};
+use chrono::{DateTime, FixedOffset, TimeDelta, TimeZone, Utc};
 use ical::{parser::ical::component::*, property::Property};
 use indexmap::IndexMap;
 use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
@@ -21,9 +26,22 @@ impl SimplePluginCommand for FromIcs {
         "Parse text as .ics and create table."
     }
 
+    fn extra_description(&self) -> &str {
+        "With --events, the output is a table with a row for each event of the calendars. Dates \
+become datetimes: times in UTC or with a time zone keep it, and the other times and the dates of \
+all-day events are local. The end of an event comes from its duration when it has none. \
+Recurrence rules become records, with the lists of their `by` parts, and the dates excluded from \
+the recurrence are in `exceptions`."
+    }
+
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .input_output_types(vec![(Type::String, Type::table())])
+            .switch(
+                "events",
+                "Output a table of the events of the calendars.",
+                Some('e'),
+            )
             .category(Category::Formats)
     }
 
@@ -41,6 +59,7 @@ impl SimplePluginCommand for FromIcs {
         let span = input.span();
         let input_string = input.coerce_str()?;
         let head = call.head;
+        let events = call.has_flag("events")?;
 
         let input_string = input_string
             .lines()
@@ -64,6 +83,7 @@ impl SimplePluginCommand for FromIcs {
 
         for calendar in parser {
             match calendar {
+                Ok(c) if events => output.extend(c.events.iter().map(|e| event_row(e, head))),
                 Ok(c) => output.push(calendar_to_value(c, head)),
                 Err(e) => output.push(Value::error(
                     ShellError::UnsupportedInput {
@@ -81,20 +101,178 @@ impl SimplePluginCommand for FromIcs {
 }
 
 pub fn examples() -> Vec<Example<'static>> {
-    vec![Example {
-        example: "'BEGIN:VCALENDAR
+    vec![
+        Example {
+            example: "'BEGIN:VCALENDAR
 END:VCALENDAR' | from ics",
-        description: "Converts ics formatted string to table",
-        result: Some(Value::test_list(vec![Value::test_record(record! {
-                "properties" => Value::test_list(vec![]),
-                "events" =>     Value::test_list(vec![]),
-                "alarms" =>     Value::test_list(vec![]),
-                "to-Dos" =>     Value::test_list(vec![]),
-                "journals" =>   Value::test_list(vec![]),
-                "free-busys" => Value::test_list(vec![]),
-                "timezones" =>  Value::test_list(vec![]),
-        })])),
-    }]
+            description: "Converts ics formatted string to table",
+            result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "properties" => Value::test_list(vec![]),
+                    "events" =>     Value::test_list(vec![]),
+                    "alarms" =>     Value::test_list(vec![]),
+                    "to-Dos" =>     Value::test_list(vec![]),
+                    "journals" =>   Value::test_list(vec![]),
+                    "free-busys" => Value::test_list(vec![]),
+                    "timezones" =>  Value::test_list(vec![]),
+            })])),
+        },
+        Example {
+            example: "'BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:standup
+SUMMARY:Standup
+DTSTART:20240115T090000Z
+DURATION:PT15M
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR
+END:VEVENT
+END:VCALENDAR' | from ics --events",
+            description: "Get the events of a calendar, with their dates and recurrence",
+            result: Some(Value::test_list(vec![Value::test_record(record! {
+                "summary" => Value::test_string("Standup"),
+                "start" => Value::test_date(Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap().into()),
+                "end" => Value::test_date(Utc.with_ymd_and_hms(2024, 1, 15, 9, 15, 0).unwrap().into()),
+                "all-day" => Value::test_bool(false),
+                "location" => Value::test_nothing(),
+                "description" => Value::test_nothing(),
+                "status" => Value::test_nothing(),
+                "organizer" => Value::test_nothing(),
+                "attendees" => Value::test_list(vec![]),
+                "categories" => Value::test_list(vec![]),
+                "recurrence" => Value::test_record(record! {
+                    "freq" => Value::test_string("weekly"),
+                    "interval" => Value::test_int(1),
+                    "count" => Value::test_nothing(),
+                    "until" => Value::test_nothing(),
+                    "byday" => Value::test_list(vec![
+                        Value::test_string("MO"),
+                        Value::test_string("WE"),
+                        Value::test_string("FR"),
+                    ]),
+                }),
+                "exceptions" => Value::test_list(vec![]),
+                "uid" => Value::test_string("standup"),
+            })])),
+        },
+    ]
+}
+
+fn event_row(event: &IcalEvent, span: Span) -> Value {
+    let props = &event.properties;
+    let text = |name: &str| {
+        property(props, name)
+            .and_then(|prop| prop.value.as_deref())
+            .map_or_else(
+                || Value::nothing(span),
+                |v| Value::string(unescape_text(v), span),
+            )
+    };
+    let date = |date: Option<DateTime<FixedOffset>>| {
+        date.map_or_else(|| Value::nothing(span), |date| Value::date(date, span))
+    };
+    let address = |value: &str| {
+        let value = value.trim();
+        value
+            .strip_prefix("mailto:")
+            .or_else(|| value.strip_prefix("MAILTO:"))
+            .unwrap_or(value)
+            .to_string()
+    };
+
+    let start = property(props, "DTSTART").and_then(property_datetime);
+    let all_day = start.is_some_and(|(_, all_day)| all_day);
+    let end = match property(props, "DTEND").and_then(property_datetime) {
+        Some((end, _)) => Some(end),
+        // Without an end, all-day events last a day and the others have no duration
+        // An end out of the range of dates is left out
+        None => start.and_then(|(start, _)| {
+            let duration = property(props, "DURATION")
+                .and_then(|prop| prop.value.as_deref())
+                .and_then(parse_duration)
+                .unwrap_or(if all_day {
+                    TimeDelta::days(1)
+                } else {
+                    TimeDelta::zero()
+                });
+            start.checked_add_signed(duration)
+        }),
+    };
+
+    let attendees = values(props, "ATTENDEE")
+        .into_iter()
+        .map(|value| Value::string(address(value), span))
+        .collect();
+    let categories = values(props, "CATEGORIES")
+        .into_iter()
+        .flat_map(|value| split_text(value, ','))
+        .map(|category| Value::string(category, span))
+        .collect();
+    let exceptions = props
+        .iter()
+        .filter(|prop| prop.name.eq_ignore_ascii_case("EXDATE"))
+        .flat_map(|prop| {
+            let tzid = param(prop, "TZID");
+            prop.value
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(move |value| parse_datetime(value, tzid))
+        })
+        .map(|(date, _)| Value::date(date, span))
+        .collect();
+
+    Value::record(
+        record! {
+            "summary" => text("SUMMARY"),
+            "start" => date(start.map(|(start, _)| start)),
+            "end" => date(end),
+            "all-day" => Value::bool(all_day, span),
+            "location" => text("LOCATION"),
+            "description" => text("DESCRIPTION"),
+            "status" => text("STATUS"),
+            "organizer" => property(props, "ORGANIZER")
+                .and_then(|prop| prop.value.as_deref())
+                .map_or_else(|| Value::nothing(span), |v| Value::string(address(v), span)),
+            "attendees" => Value::list(attendees, span),
+            "categories" => Value::list(categories, span),
+            "recurrence" => property(props, "RRULE")
+                .and_then(|prop| prop.value.as_deref())
+                .map_or_else(|| Value::nothing(span), |rule| recurrence_to_value(rule, span)),
+            "exceptions" => Value::list(exceptions, span),
+            "uid" => text("UID"),
+        },
+        span,
+    )
+}
+
+/// Convert a recurrence rule, like `FREQ=WEEKLY;COUNT=4;BYDAY=MO`, to a record.
+fn recurrence_to_value(rule: &str, span: Span) -> Value {
+    let mut record = record! {
+        "freq" => Value::nothing(span),
+        "interval" => Value::int(1, span),
+        "count" => Value::nothing(span),
+        "until" => Value::nothing(span),
+    };
+    for part in rule.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = match key.as_str() {
+            "freq" => Value::string(value.to_ascii_lowercase(), span),
+            "interval" | "count" => value
+                .parse::<i64>()
+                .map_or_else(|_| Value::nothing(span), |n| Value::int(n, span)),
+            "until" => parse_datetime(value, None)
+                .map_or_else(|| Value::nothing(span), |(date, _)| Value::date(date, span)),
+            "wkst" => Value::string(value, span),
+            _ => Value::list(
+                value.split(',').map(|v| Value::string(v, span)).collect(),
+                span,
+            ),
+        };
+        record.insert(key, value);
+    }
+    Value::record(record, span)
 }
 
 fn calendar_to_value(calendar: IcalCalendar, span: Span) -> Value {
@@ -276,3 +454,20 @@ fn test_examples() -> Result<(), nu_protocol::ShellError> {
 
     PluginTest::new("formats", crate::FormatCmdsPlugin.into())?.test_command_examples(&FromIcs)
 }
+
+#[test]
+fn end_out_of_range_is_null() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+
+    let calendar = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+DTSTART:99991231T000000Z
+DURATION:P99999999W
+END:VEVENT
+END:VCALENDAR";
+    let value = PluginTest::new("formats", crate::FormatCmdsPlugin.into())?
+        .eval(&format!("'{calendar}' | from ics --events | get 0.end"))?
+        .into_value(Span::test_data())?;
+    assert_eq!(value, Value::test_nothing());
+    Ok(())
+}
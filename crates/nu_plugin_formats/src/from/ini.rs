@@ -2,7 +2,7 @@ use crate::FormatCmdsPlugin;
 
 use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
 use nu_protocol::{
-    Category, Example, LabeledError, Record, ShellError, Signature, Type, Value, record,
+    Category, Example, LabeledError, Record, ShellError, Signature, Span, Type, Value, record,
 };
 
 pub struct FromIni;
@@ -15,7 +15,19 @@ impl SimplePluginCommand for FromIni {
     }
 
     fn description(&self) -> &str {
-        "Parse text as .ini and create table."
+        "Parse text as .ini and create a record of its sections."
+    }
+
+    fn extra_description(&self) -> &str {
+        "A section like `[a.b]` is nested in the section `a`, unless `--flat` is given. Dots between \
+double quotes don't nest sections, so `[branch \"v1.2\"]` is one section, and a section whose name \
+is also a key of its parent section, like `[a.b]` next to `b=1` in `[a]`, isn't nested either. The \
+keys without a section are in the section named by an empty string. Sections and keys keep their \
+order, and the last value of a repeated key wins.
+
+With `--comments`, the comments of a section are kept as a key named by their `#` or `;`, with the \
+comment lines as value, which `to ini` writes back as comments. The key comes first if the comments \
+are before the keys of the section, and last otherwise."
     }
 
     fn signature(&self) -> Signature {
@@ -37,6 +49,16 @@ impl SimplePluginCommand for FromIni {
                 "Preserve leading whitespace in keys.",
                 Some('w'),
             )
+            .switch(
+                "flat",
+                "Keep sections like `[a.b]` as they are instead of nesting them.",
+                Some('f'),
+            )
+            .switch(
+                "comments",
+                "Keep comments as keys named `#` or `;`.",
+                Some('c'),
+            )
             .category(Category::Formats)
     }
 
@@ -64,7 +86,8 @@ impl SimplePluginCommand for FromIni {
             parse_option.enabled_escape = false;
         }
 
-        if call.has_flag("indented-multiline-value")? {
+        let multiline = call.has_flag("indented-multiline-value")?;
+        if multiline {
             parse_option.enabled_indented_mutiline_value = true;
         }
 
@@ -72,48 +95,173 @@ impl SimplePluginCommand for FromIni {
             parse_option.enabled_preserve_key_leading_whitespace = true;
         }
 
-        let ini_config: Result<ini::Ini, ini::ParseError> =
-            ini::Ini::load_from_str_opt(&input_string, parse_option);
-        match ini_config {
-            Ok(config) => {
-                let mut sections = Record::new();
-
-                for (section, properties) in config.iter() {
-                    let mut section_record = Record::new();
+        let config = ini::Ini::load_from_str_opt(&input_string, parse_option).map_err(|err| {
+            ShellError::UnsupportedInput {
+                msg: format!("Could not load ini: {err}"),
+                input: "value originates from here".into(),
+                msg_span: head,
+                input_span: span,
+            }
+        })?;
 
-                    // section's key value pairs
-                    for (key, value) in properties.iter() {
-                        section_record.push(key, Value::string(value, span));
-                    }
+        let flat = call.has_flag("flat")?;
+        let comments = if call.has_flag("comments")? {
+            find_comments(&input_string, multiline)
+        } else {
+            vec![]
+        };
 
-                    let section_record = Value::record(section_record, span);
-
-                    // section
-                    match section {
-                        Some(section_name) => {
-                            sections.push(section_name, section_record);
-                        }
-                        None => {
-                            // Section (None) allows for key value pairs without a section
-                            if !properties.is_empty() {
-                                sections.push(String::new(), section_record);
-                            }
-                        }
+        let mut sections = Record::new();
+        for (section, properties) in config.iter() {
+            let section_comments: Vec<&Comment> = comments
+                .iter()
+                .filter(|comment| comment.section.as_deref() == section)
+                .collect();
+            let path = match section {
+                // `[a.b]` is the section `b` nested in the section `a`
+                Some(section_name) => {
+                    let path = section_path(section_name);
+                    if flat || !nests(&config, &path) {
+                        vec![section_name]
+                    } else {
+                        path
                     }
                 }
+                // Section (None) allows for key value pairs without a section
+                None if !properties.is_empty() || !section_comments.is_empty() => vec![""],
+                None => continue,
+            };
+            let section_record = section_mut(&mut sections, &path, span).ok_or_else(|| {
+                LabeledError::new("Could not load ini").with_label(
+                    format!("section `{}` conflicts with a key", path.join(".")),
+                    span,
+                )
+            })?;
 
-                // all sections with all its key value pairs
-                Ok(Value::record(sections, span))
+            // section's key value pairs, in order, the last one wins for repeated keys
+            for (key, value) in properties.iter() {
+                section_record.insert(key, Value::string(value, span));
             }
-            Err(err) => Err(ShellError::UnsupportedInput {
-                msg: format!("Could not load ini: {err}"),
-                input: "value originates from here".into(),
-                msg_span: head,
-                input_span: span,
+            add_comments(section_record, &section_comments, span);
+        }
+
+        // all sections with all its key value pairs
+        Ok(Value::record(sections, span))
+    }
+}
+
+/// Split a section name like `a.b` into the names of the nested sections, except at the dots
+/// between double quotes, like in `branch "v1.2"`.
+fn section_path(name: &str) -> Vec<&str> {
+    let mut path = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    for (index, c) in name.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '.' if !quoted => {
+                path.push(&name[start..index]);
+                start = index + 1;
             }
-            .into()),
+            _ => {}
         }
     }
+    path.push(&name[start..]);
+    path
+}
+
+/// Whether the section at `path` can be nested in its parents, which it can't when a parent
+/// has a key with the name of the next section in the path.
+fn nests(config: &ini::Ini, path: &[&str]) -> bool {
+    (1..path.len()).all(|len| {
+        config
+            .section(Some(path[..len].join(".")))
+            .is_none_or(|parent| !parent.contains_key(path[len]))
+    })
+}
+
+/// A comment line, which the `ini` crate drops.
+struct Comment {
+    /// The section the comment is in
+    section: Option<String>,
+    /// Whether the comment comes before the first key of its section
+    before_keys: bool,
+    /// `#` or `;`
+    marker: char,
+    text: String,
+}
+
+/// Find the comment lines of ini text, with the section they're in.
+fn find_comments(input: &str, multiline: bool) -> Vec<Comment> {
+    let mut comments = vec![];
+    let mut section = None;
+    let mut before_keys = true;
+    for line in input.lines() {
+        // The continuation of a value can look like a comment
+        if multiline && !before_keys && line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix('[')
+            && let Some((name, _)) = name.rsplit_once(']')
+        {
+            section = Some(name.trim().to_string());
+            before_keys = true;
+        } else if let Some(marker) = line.chars().next().filter(|c| matches!(c, '#' | ';')) {
+            let text = &line[1..];
+            comments.push(Comment {
+                section: section.clone(),
+                before_keys,
+                marker,
+                text: text.strip_prefix(' ').unwrap_or(text).to_string(),
+            });
+        } else if !line.is_empty() {
+            before_keys = false;
+        }
+    }
+    comments
+}
+
+/// Add the comments of a section as keys named by their marker, with one line per comment.
+fn add_comments(section: &mut Record, comments: &[&Comment], span: Span) {
+    let mut leading = Record::new();
+    for marker in ['#', ';'] {
+        let mut with_marker = comments.iter().filter(|comment| comment.marker == marker);
+        let Some(first) = with_marker.next() else {
+            continue;
+        };
+        let text = std::iter::once(*first)
+            .chain(with_marker.copied())
+            .map(|comment| comment.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let value = Value::string(text, span);
+        if first.before_keys {
+            leading.push(marker.to_string(), value);
+        } else {
+            section.insert(marker.to_string(), value);
+        }
+    }
+    if !leading.is_empty() {
+        leading.extend(std::mem::take(section));
+        *section = leading;
+    }
+}
+
+/// Get the record of a section, creating it and its parents as needed.
+///
+/// Returns `None` if a part of the path is already used by a value that isn't a section.
+fn section_mut<'a>(record: &'a mut Record, path: &[&str], span: Span) -> Option<&'a mut Record> {
+    let Some((name, rest)) = path.split_first() else {
+        return Some(record);
+    };
+    if !record.contains(name) {
+        record.push(*name, Value::record(Record::new(), span));
+    }
+    match record.get_mut(name) {
+        Some(Value::Record { val, .. }) => section_mut(val.to_mut(), rest, span),
+        _ => None,
+    }
 }
 
 pub fn examples() -> Vec<Example<'static>> {
@@ -130,6 +278,56 @@ b=2' | from ini",
                 }),
             })),
         },
+        Example {
+            example: "'[server]
+host=localhost
+
+[server.tls]
+enabled=true' | from ini",
+            description: "Nested sections become nested records",
+            result: Some(Value::test_record(record! {
+                "server" => Value::test_record(record! {
+                    "host" => Value::test_string("localhost"),
+                    "tls" => Value::test_record(record! {
+                        "enabled" => Value::test_string("true"),
+                    }),
+                }),
+            })),
+        },
+        Example {
+            example: "'[branch \"v1.2\"]
+remote=origin' | from ini",
+            description: "Dots between quotes don't nest sections",
+            result: Some(Value::test_record(record! {
+                "branch \"v1.2\"" => Value::test_record(record! {
+                    "remote" => Value::test_string("origin"),
+                }),
+            })),
+        },
+        Example {
+            example: "'[server.tls]
+enabled=true' | from ini --flat",
+            description: "Keep dotted sections as they are",
+            result: Some(Value::test_record(record! {
+                "server.tls" => Value::test_record(record! {
+                    "enabled" => Value::test_string("true"),
+                }),
+            })),
+        },
+        Example {
+            example: "'# written by nu
+[foo]
+a=1' | from ini --comments",
+            description: "Keep comments, which `to ini` writes back",
+            result: Some(Value::test_record(record! {
+                "" => Value::test_record(record! {
+                    "#" => Value::test_string("written by nu"),
+                }),
+                "foo" => Value::test_record(record! {
+                    "a" => Value::test_string("1"),
+                }),
+            })),
+        },
         Example {
             example: "'[start]
 file=C:\\Windows\\System32\\xcopy.exe' | from ini --no-escape",
@@ -180,3 +378,25 @@ fn test_examples() -> Result<(), nu_protocol::ShellError> {
 
     PluginTest::new("formats", crate::FormatCmdsPlugin.into())?.test_command_examples(&FromIni)
 }
+
+#[test]
+fn section_named_like_a_key_is_not_nested() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+
+    let mut plugin_test = PluginTest::new("formats", crate::FormatCmdsPlugin.into())?;
+    let value = plugin_test
+        .eval("'[a.b]\nc=2\n[a]\nb=1' | from ini")?
+        .into_value(Span::test_data())?;
+    assert_eq!(
+        value,
+        Value::test_record(record! {
+            "a.b" => Value::test_record(record! {
+                "c" => Value::test_string("2"),
+            }),
+            "a" => Value::test_record(record! {
+                "b" => Value::test_string("1"),
+            }),
+        })
+    );
+    Ok(())
+}
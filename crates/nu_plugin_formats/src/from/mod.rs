@@ -1,5 +1,5 @@
 pub(crate) mod eml;
+mod ical_values;
 pub(crate) mod ics;
 pub(crate) mod ini;
-pub(crate) mod plist;
 pub(crate) mod vcf;
@@ -1,5 +1,7 @@
 use crate::FormatCmdsPlugin;
 
+use super::ical_values::{parse_datetime, property, split_text, unescape_text, values};
+use chrono::{Local, TimeZone};
 use ical::{parser::vcard::component::*, property::Property};
 use indexmap::IndexMap;
 use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
@@ -20,9 +22,20 @@ impl SimplePluginCommand for FromVcf {
         "Parse text as .vcf and create table."
     }
 
+    fn extra_description(&self) -> &str {
+        "With --contacts, the output is a table with a row for each contact, with the parts of \
+their name, the lists of their emails, phones and addresses, and their birthday as a local \
+datetime."
+    }
+
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .input_output_types(vec![(Type::String, Type::table())])
+            .switch(
+                "contacts",
+                "Output a table of the contacts and their main fields.",
+                Some('c'),
+            )
             .category(Category::Formats)
     }
 
@@ -40,6 +53,7 @@ impl SimplePluginCommand for FromVcf {
         let span = input.span();
         let input_string = input.coerce_str()?;
         let head = call.head;
+        let contacts = call.has_flag("contacts")?;
 
         let input_string = input_string
             .lines()
@@ -60,6 +74,7 @@ impl SimplePluginCommand for FromVcf {
         let parser = ical::VcardParser::new(cursor);
 
         let iter = parser.map(move |contact| match contact {
+            Ok(c) if contacts => contact_row(&c, head),
             Ok(c) => contact_to_value(c, head),
             Err(e) => Value::error(
                 ShellError::UnsupportedInput {
@@ -78,35 +93,138 @@ impl SimplePluginCommand for FromVcf {
 }
 
 pub fn examples() -> Vec<Example<'static>> {
-    vec![Example {
-        example: "'BEGIN:VCARD
+    vec![
+        Example {
+            example: "'BEGIN:VCARD
 N:Foo
 FN:Bar
 EMAIL:foo@bar.com
 END:VCARD' | from vcf",
-        description: "Converts ics formatted string to table",
-        result: Some(Value::test_list(vec![Value::test_record(record! {
-            "properties" => Value::test_list(
-                vec![
-                    Value::test_record(record! {
-                            "name" =>   Value::test_string("N"),
-                            "value" =>  Value::test_string("Foo"),
-                            "params" => Value::nothing(Span::test_data()),
-                    }),
-                    Value::test_record(record! {
-                            "name" =>   Value::test_string("FN"),
-                            "value" =>  Value::test_string("Bar"),
-                            "params" => Value::nothing(Span::test_data()),
-                    }),
-                    Value::test_record(record! {
-                            "name" =>   Value::test_string("EMAIL"),
-                            "value" =>  Value::test_string("foo@bar.com"),
-                            "params" => Value::nothing(Span::test_data()),
-                    }),
-                ],
-            ),
-        })])),
-    }]
+            description: "Converts ics formatted string to table",
+            result: Some(Value::test_list(vec![Value::test_record(record! {
+                "properties" => Value::test_list(
+                    vec![
+                        Value::test_record(record! {
+                                "name" =>   Value::test_string("N"),
+                                "value" =>  Value::test_string("Foo"),
+                                "params" => Value::nothing(Span::test_data()),
+                        }),
+                        Value::test_record(record! {
+                                "name" =>   Value::test_string("FN"),
+                                "value" =>  Value::test_string("Bar"),
+                                "params" => Value::nothing(Span::test_data()),
+                        }),
+                        Value::test_record(record! {
+                                "name" =>   Value::test_string("EMAIL"),
+                                "value" =>  Value::test_string("foo@bar.com"),
+                                "params" => Value::nothing(Span::test_data()),
+                        }),
+                    ],
+                ),
+            })])),
+        },
+        Example {
+            example: "'BEGIN:VCARD
+VERSION:4.0
+FN:Ada Lovelace
+N:Lovelace;Ada;;;
+EMAIL;TYPE=home:ada@example.com
+TEL:tel:+44 20 1234 5678
+ORG:Analytical Engines\\, Ltd;Research
+BDAY:1815-12-10
+END:VCARD' | from vcf --contacts",
+            description: "Get the main fields of the contacts",
+            result: Some(Value::test_list(vec![Value::test_record(record! {
+                "name" => Value::test_string("Ada Lovelace"),
+                "family-name" => Value::test_string("Lovelace"),
+                "given-name" => Value::test_string("Ada"),
+                "emails" => Value::test_list(vec![Value::test_string("ada@example.com")]),
+                "phones" => Value::test_list(vec![Value::test_string("+44 20 1234 5678")]),
+                "organization" => Value::test_string("Analytical Engines, Ltd"),
+                "title" => Value::test_nothing(),
+                "birthday" => Value::test_date(Local.with_ymd_and_hms(1815, 12, 10, 0, 0, 0).unwrap().into()),
+                "addresses" => Value::test_list(vec![]),
+                "note" => Value::test_nothing(),
+                "uid" => Value::test_nothing(),
+            })])),
+        },
+    ]
+}
+
+fn contact_row(contact: &VcardContact, span: Span) -> Value {
+    let props = &contact.properties;
+    let text = |name: &str| {
+        property(props, name)
+            .and_then(|prop| prop.value.as_deref())
+            .map_or_else(
+                || Value::nothing(span),
+                |v| Value::string(unescape_text(v), span),
+            )
+    };
+    // Structured values, like the name and the organization, are separated by `;`
+    let part = |parts: &[String], index: usize| {
+        parts
+            .get(index)
+            .filter(|part| !part.is_empty())
+            .map_or_else(|| Value::nothing(span), |part| Value::string(part, span))
+    };
+    let parts = |name: &str| {
+        property(props, name)
+            .and_then(|prop| prop.value.as_deref())
+            .map(|value| split_text(value, ';'))
+            .unwrap_or_default()
+    };
+    let name = parts("N");
+
+    let emails = values(props, "EMAIL")
+        .into_iter()
+        .map(|value| Value::string(unescape_text(value), span))
+        .collect();
+    let phones = values(props, "TEL")
+        .into_iter()
+        .map(|value| {
+            let value = value.trim();
+            Value::string(value.strip_prefix("tel:").unwrap_or(value), span)
+        })
+        .collect();
+    let addresses = values(props, "ADR")
+        .into_iter()
+        .map(|value| {
+            // The post office box and the extended address come before the street
+            let address = split_text(value, ';');
+            Value::record(
+                record! {
+                    "street" => part(&address, 2),
+                    "locality" => part(&address, 3),
+                    "region" => part(&address, 4),
+                    "postal-code" => part(&address, 5),
+                    "country" => part(&address, 6),
+                },
+                span,
+            )
+        })
+        .collect();
+    let birthday = property(props, "BDAY")
+        .and_then(|prop| prop.value.as_deref())
+        .and_then(|value| parse_datetime(value, None))
+        .map_or_else(|| Value::nothing(span), |(date, _)| Value::date(date, span));
+
+    Value::record(
+        record! {
+            "name" => text("FN"),
+            "family-name" => part(&name, 0),
+            "given-name" => part(&name, 1),
+            "emails" => Value::list(emails, span),
+            "phones" => Value::list(phones, span),
+            "organization" => part(&parts("ORG"), 0),
+            "title" => text("TITLE"),
+            "birthday" => birthday,
+            "addresses" => Value::list(addresses, span),
+            "note" => text("NOTE"),
+            "uid" => text("UID"),
+        },
+        span,
+    )
 }
 
 fn contact_to_value(contact: VcardContact, span: Span) -> Value {
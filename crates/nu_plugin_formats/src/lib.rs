@@ -6,9 +6,8 @@ use nu_plugin::{Plugin, PluginCommand};
 use from::eml::FromEml;
 use from::ics::FromIcs;
 use from::ini::FromIni;
-use from::plist::FromPlist;
 use from::vcf::FromVcf;
-use to::plist::IntoPlist;
+use to::ini::ToIni;
 
 pub struct FormatCmdsPlugin;
 
@@ -23,8 +22,7 @@ impl Plugin for FormatCmdsPlugin {
             Box::new(FromIcs),
             Box::new(FromIni),
             Box::new(FromVcf),
-            Box::new(FromPlist),
-            Box::new(IntoPlist),
+            Box::new(ToIni),
         ]
     }
 }
@@ -0,0 +1,253 @@
+use crate::FormatCmdsPlugin;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Record, ShellError, Signature, SyntaxShape, Type, Value,
+};
+
+pub struct ToIni;
+
+impl SimplePluginCommand for ToIni {
+    type Plugin = FormatCmdsPlugin;
+
+    fn name(&self) -> &str {
+        "to ini"
+    }
+
+    fn description(&self) -> &str {
+        "Convert a record of sections into .ini text."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Records become sections, and records nested in them become sections like `[a.b]`. The \
+other values of the input, and the section named by an empty string, are the keys without a \
+section, written first. Keys starting with `;` or `#` are written as comments, followed by their \
+value, like `from ini --comments` reads them. This is the reverse of `from ini`."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::record(), Type::String)])
+            .named(
+                "delimiter",
+                SyntaxShape::String,
+                "The text between keys and values, `=` by default.",
+                Some('d'),
+            )
+            .category(Category::Formats)
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        examples()
+    }
+
+    fn run(
+        &self,
+        _plugin: &FormatCmdsPlugin,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let head = call.head;
+        let delimiter = call
+            .get_flag::<String>("delimiter")?
+            .unwrap_or_else(|| "=".into());
+        let record = match input {
+            Value::Record { val, .. } => val,
+            other => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "record".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: other.span(),
+                }
+                .into());
+            }
+        };
+
+        let writer = Writer {
+            delimiter: &delimiter,
+        };
+        let mut out = String::new();
+
+        // The keys without a section must come before the first section
+        let mut sections = Vec::new();
+        for (key, value) in record.iter() {
+            match value {
+                Value::Record { val, .. } if key.is_empty() => {
+                    for (key, value) in val.iter() {
+                        writer.write_key(&mut out, key, value)?;
+                    }
+                }
+                Value::Record { val, .. } => sections.push((key.as_str(), &**val)),
+                value => writer.write_key(&mut out, key, value)?,
+            }
+        }
+        for (name, section) in sections {
+            writer.write_section(&mut out, name, section)?;
+        }
+
+        Ok(Value::string(out, head))
+    }
+}
+
+struct Writer<'a> {
+    delimiter: &'a str,
+}
+
+impl Writer<'_> {
+    fn write_section(
+        &self,
+        out: &mut String,
+        name: &str,
+        section: &Record,
+    ) -> Result<(), ShellError> {
+        let (subsections, keys): (Vec<_>, Vec<_>) = section
+            .iter()
+            .partition(|(_, value)| matches!(value, Value::Record { .. }));
+
+        // A section with only subsections doesn't need its own header
+        if !keys.is_empty() || subsections.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("[{name}]\n"));
+            for (key, value) in keys {
+                self.write_key(out, key, value)?;
+            }
+        }
+
+        for (subname, value) in subsections {
+            if let Value::Record { val, .. } = value {
+                self.write_section(out, &format!("{name}.{subname}"), val)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_key(&self, out: &mut String, key: &str, value: &Value) -> Result<(), ShellError> {
+        if key.starts_with([';', '#']) {
+            let text = match value {
+                Value::Nothing { .. } => String::new(),
+                value => Self::value_to_string(value)?,
+            };
+            if text.is_empty() {
+                out.push_str(&format!("{key}\n"));
+            }
+            for line in text.lines() {
+                out.push_str(&format!("{key} {line}\n"));
+            }
+            return Ok(());
+        }
+
+        let key = escape(key).replace('=', "\\=").replace(':', "\\:");
+        let value = escape(&Self::value_to_string(value)?);
+        out.push_str(&format!("{key}{}{value}\n", self.delimiter));
+        Ok(())
+    }
+
+    fn value_to_string(value: &Value) -> Result<String, ShellError> {
+        Ok(match value {
+            Value::String { val, .. } | Value::Glob { val, .. } => val.clone(),
+            Value::Int { val, .. } => val.to_string(),
+            Value::Float { val, .. } => val.to_string(),
+            Value::Bool { val, .. } => val.to_string(),
+            Value::Filesize { val, .. } => val.get().to_string(),
+            Value::Date { val, .. } => val.to_rfc3339(),
+            Value::Nothing { .. } => String::new(),
+            Value::Error { error, .. } => return Err(*error.clone()),
+            other => {
+                return Err(ShellError::CantConvert {
+                    to_type: "ini".into(),
+                    from_type: other.get_type().to_string(),
+                    span: other.span(),
+                    help: Some("values must be strings, numbers, bools or dates".into()),
+                });
+            }
+        })
+    }
+}
+
+/// Escape the characters that `from ini` unescapes by default.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0' => escaped.push_str("\\0"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn examples() -> Vec<Example<'static>> {
+    vec![
+        Example {
+            example: "{foo: {a: 1, b: 2}} | to ini",
+            description: "Converts a record of sections to ini",
+            result: Some(Value::test_string("[foo]\na=1\nb=2\n")),
+        },
+        Example {
+            example: "{name: app, server: {host: localhost, tls: {enabled: true}}} | to ini",
+            description: "Keys without a section come first, and nested records become nested sections",
+            result: Some(Value::test_string(
+                "name=app\n\n[server]\nhost=localhost\n\n[server.tls]\nenabled=true\n",
+            )),
+        },
+        Example {
+            example: "{foo: {'#': 'written by nu', a: 1}} | to ini --delimiter ' = '",
+            description: "Write comments and put spaces around the delimiter",
+            result: Some(Value::test_string("[foo]\n# written by nu\na = 1\n")),
+        },
+    ]
+}
+
+#[test]
+fn test_examples() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+
+    PluginTest::new("formats", crate::FormatCmdsPlugin.into())?.test_command_examples(&ToIni)
+}
+
+#[test]
+fn roundtrip() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+
+    let mut plugin_test = PluginTest::new("formats", crate::FormatCmdsPlugin.into())?;
+    let value = plugin_test
+        .eval(r#"{"": {top: 1}, a: {path: 'C:\dir', text: "two\nlines", b: {c: x}}} | to ini | from ini"#)?
+        .into_value(nu_protocol::Span::test_data())?;
+    assert_eq!(
+        value,
+        Value::test_record(nu_protocol::record! {
+            "" => Value::test_record(nu_protocol::record! {
+                "top" => Value::test_string("1"),
+            }),
+            "a" => Value::test_record(nu_protocol::record! {
+                "path" => Value::test_string(r"C:\dir"),
+                "text" => Value::test_string("two\nlines"),
+                "b" => Value::test_record(nu_protocol::record! {
+                    "c" => Value::test_string("x"),
+                }),
+            }),
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn roundtrip_comments() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+
+    let text = "# top\n\n[a]\n; about a\n; two lines\nkey=1\n";
+    let mut plugin_test = PluginTest::new("formats", crate::FormatCmdsPlugin.into())?;
+    let value = plugin_test
+        .eval(&format!("'{text}' | from ini --comments | to ini"))?
+        .into_value(nu_protocol::Span::test_data())?;
+    assert_eq!(value, Value::test_string(text));
+    Ok(())
+}
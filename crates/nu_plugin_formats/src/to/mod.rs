@@ -1 +1 @@
-pub(crate) mod plist;
+pub(crate) mod ini;
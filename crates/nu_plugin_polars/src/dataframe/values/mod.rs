@@ -14,7 +14,7 @@ use nu_dtype::custom_value::NuDataTypeCustomValue;
 use nu_plugin::EngineInterface;
 use nu_protocol::shell_error::generic::GenericError;
 use nu_protocol::{
-    CustomValue, PipelineData, ShellError, Span, Spanned, Type, Value, ast::Operator,
+    CustomValue, ListStream, PipelineData, ShellError, Span, Spanned, Type, Value, ast::Operator,
 };
 use nu_schema::custom_value::NuSchemaCustomValue;
 use std::{cmp::Ordering, fmt};
@@ -351,6 +351,19 @@ pub trait PolarsPluginCustomValue: CustomValue {
     ) -> Result<Option<Ordering>, ShellError> {
         Ok(None)
     }
+
+    fn custom_value_iterate(
+        &self,
+        _plugin: &PolarsPlugin,
+        _engine: &EngineInterface,
+        span: Span,
+    ) -> Result<ListStream, ShellError> {
+        Err(ShellError::Generic(GenericError::new(
+            format!("Can't iterate over a {}", self.type_name()),
+            "only dataframes and lazy frames can be iterated over",
+            span,
+        )))
+    }
 }
 
 /// Handles the ability for a PolarsObjectType implementations to convert between
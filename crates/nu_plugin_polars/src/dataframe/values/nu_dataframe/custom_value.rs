@@ -1,7 +1,7 @@
 use std::cmp::Ordering;
 
 use nu_plugin::EngineInterface;
-use nu_protocol::{CustomValue, ShellError, Span, Spanned, Value};
+use nu_protocol::{CustomValue, ListStream, ShellError, Span, Spanned, Value};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -48,6 +48,10 @@ impl CustomValue for NuDataFrameCustomValue {
     fn notify_plugin_on_drop(&self) -> bool {
         true
     }
+
+    fn is_streamable(&self) -> bool {
+        true
+    }
 }
 
 impl PolarsPluginCustomValue for NuDataFrameCustomValue {
@@ -111,6 +115,16 @@ impl PolarsPluginCustomValue for NuDataFrameCustomValue {
             .into_value(self_span))
     }
 
+    fn custom_value_iterate(
+        &self,
+        plugin: &PolarsPlugin,
+        engine: &EngineInterface,
+        span: Span,
+    ) -> Result<ListStream, ShellError> {
+        let df = NuDataFrame::try_from_custom_value(plugin, self)?;
+        Ok(df.into_row_stream(span, engine.signals().clone()))
+    }
+
     fn custom_value_partial_cmp(
         &self,
         plugin: &PolarsPlugin,
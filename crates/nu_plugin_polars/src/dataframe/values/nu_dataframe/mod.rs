@@ -8,7 +8,9 @@ pub use operations::Axis;
 
 use indexmap::map::IndexMap;
 use nu_protocol::shell_error::generic::GenericError;
-use nu_protocol::{PipelineData, Record, ShellError, Span, Value, did_you_mean};
+use nu_protocol::{
+    ListStream, PipelineData, Record, ShellError, Signals, Span, Value, did_you_mean,
+};
 use polars::prelude::{
     Column as PolarsColumn, DataFrame, DataType, IntoLazy, PolarsObject, Series,
 };
@@ -30,7 +32,8 @@ pub use self::custom_value::NuDataFrameCustomValue;
 
 use super::{
     CustomValueSupport, NuLazyFrame, PolarsPluginObject, PolarsPluginType, cant_convert_err,
-    nu_schema::NuSchema, utils::DEFAULT_ROWS,
+    nu_schema::NuSchema,
+    utils::{DEFAULT_ROWS, ROW_STREAM_BATCH_SIZE},
 };
 
 // DataFrameValue is an encapsulation of Nushell Value that can be used
@@ -439,6 +442,17 @@ impl NuDataFrame {
         Ok(values)
     }
 
+    /// Streams the rows of the dataframe as records, converting them in batches as they are read
+    pub fn into_row_stream(self, span: Span, signals: Signals) -> ListStream {
+        let rows = (0..self.height())
+            .step_by(ROW_STREAM_BATCH_SIZE)
+            .flat_map(move |from_row| {
+                self.to_rows(from_row, from_row + ROW_STREAM_BATCH_SIZE, false, span)
+                    .unwrap_or_else(|err| vec![Value::error(err, span)])
+            });
+        ListStream::new(rows, span, signals)
+    }
+
     // Dataframes are considered equal if they have the same shape, column name and values
     pub fn is_equal(&self, other: &Self) -> Option<Ordering> {
         let polars_self = self.to_polars();
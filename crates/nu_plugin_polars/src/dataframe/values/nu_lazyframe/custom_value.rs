@@ -1,7 +1,7 @@
 use std::cmp::Ordering;
 
 use nu_plugin::EngineInterface;
-use nu_protocol::{CustomValue, ShellError, Span, Value};
+use nu_protocol::{CustomValue, ListStream, ShellError, Span, Value};
 use polars::prelude::{col, nth};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -51,6 +51,10 @@ impl CustomValue for NuLazyFrameCustomValue {
     fn notify_plugin_on_drop(&self) -> bool {
         true
     }
+
+    fn is_streamable(&self) -> bool {
+        true
+    }
 }
 
 impl PolarsPluginCustomValue for NuLazyFrameCustomValue {
@@ -74,6 +78,17 @@ impl PolarsPluginCustomValue for NuLazyFrameCustomValue {
         &self.lazyframe
     }
 
+    /// The lazy frame has to be collected first, then its rows are streamed like a dataframe's.
+    fn custom_value_iterate(
+        &self,
+        plugin: &PolarsPlugin,
+        engine: &EngineInterface,
+        span: Span,
+    ) -> Result<ListStream, ShellError> {
+        let df = NuLazyFrame::try_from_custom_value(plugin, self)?.collect(span)?;
+        Ok(df.into_row_stream(span, engine.signals().clone()))
+    }
+
     fn custom_value_partial_cmp(
         &self,
         plugin: &PolarsPlugin,
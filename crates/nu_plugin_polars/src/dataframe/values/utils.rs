@@ -4,6 +4,9 @@ use nu_protocol::{ShellError, Span, Spanned, Value};
 // Default value used when selecting rows from dataframe
 pub const DEFAULT_ROWS: usize = 5;
 
+// Number of rows converted at a time when streaming the rows of a dataframe
+pub const ROW_STREAM_BATCH_SIZE: usize = 1024;
+
 // Converts a Vec<Value> to a Vec<Spanned<String>> with a Span marking the whole
 // location of the columns for error referencing
 pub(crate) fn convert_columns(
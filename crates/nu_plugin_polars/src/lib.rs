@@ -20,8 +20,8 @@ mod cloud;
 pub mod dataframe;
 pub use dataframe::*;
 use nu_protocol::{
-    CustomValue, LabeledError, ShellError, Span, Spanned, Value, ast::Operator, casing::Casing,
-    shell_error::generic::GenericError,
+    CustomValue, LabeledError, ListStream, ShellError, Span, Spanned, Value, ast::Operator,
+    casing::Casing, shell_error::generic::GenericError,
 };
 use tokio::runtime::Runtime;
 use values::CustomValueType;
@@ -282,6 +282,25 @@ impl Plugin for PolarsPlugin {
         };
         Ok(result?)
     }
+
+    fn custom_value_iterate(
+        &self,
+        engine: &EngineInterface,
+        custom_value: Spanned<Box<dyn CustomValue>>,
+    ) -> Result<ListStream, LabeledError> {
+        let span = custom_value.span;
+        let result = match CustomValueType::try_from_custom_value(custom_value.item, span)? {
+            CustomValueType::NuDataFrame(cv) => cv.custom_value_iterate(self, engine, span),
+            CustomValueType::NuLazyFrame(cv) => cv.custom_value_iterate(self, engine, span),
+            CustomValueType::NuExpression(cv) => cv.custom_value_iterate(self, engine, span),
+            CustomValueType::NuLazyGroupBy(cv) => cv.custom_value_iterate(self, engine, span),
+            CustomValueType::NuWhen(cv) => cv.custom_value_iterate(self, engine, span),
+            CustomValueType::NuDataType(cv) => cv.custom_value_iterate(self, engine, span),
+            CustomValueType::NuSchema(cv) => cv.custom_value_iterate(self, engine, span),
+            CustomValueType::NuSelector(cv) => cv.custom_value_iterate(self, engine, span),
+        };
+        Ok(result?)
+    }
 }
 
 pub(crate) fn handle_panic<F, R>(f: F, span: Span) -> Result<R, ShellError>
@@ -0,0 +1,309 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Record, Signature, Span, Spanned, SyntaxShape, Type, Value,
+    record,
+};
+use scraper::{ElementRef, Html, Selector};
+
+use crate::Query;
+
+/// The most cells a single `colspan` can stand for, so a bogus value can't exhaust memory.
+const MAX_COLSPAN: usize = 1000;
+
+pub struct FromHtml;
+
+impl SimplePluginCommand for FromHtml {
+    type Plugin = Query;
+
+    fn name(&self) -> &str {
+        "from html"
+    }
+
+    fn description(&self) -> &str {
+        "Extract tables or elements from HTML text."
+    }
+
+    fn extra_description(&self) -> &str {
+        "With --tables, every <table> becomes a table, with the cells of its first row as the \
+column names when they're all <th>. With --query, the elements matching the CSS selector become \
+records with their tag, text and attributes. Use both to only extract the tables matching the \
+selector. Without either flag the text is returned as is, so that `open` keeps working on HTML \
+files."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::String, Type::Any)])
+            .switch(
+                "tables",
+                "Extract the tables into nushell tables.",
+                Some('t'),
+            )
+            .named(
+                "query",
+                SyntaxShape::String,
+                "Only return the elements matching this CSS selector.",
+                Some('q'),
+            )
+            .category(Category::Formats)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["scrape", "css", "selector", "web"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "http get https://en.wikipedia.org/wiki/List_of_terminal_emulators | from html --tables",
+                description: "Extract all the tables of a web page",
+                result: None,
+            },
+            Example {
+                example: "open report.html | from html --tables --query 'table.results' | first",
+                description: "Extract the first table with the `results` class",
+                result: None,
+            },
+            Example {
+                example: "http get https://www.nushell.sh | from html --query 'nav a' | select text attributes.href",
+                description: "List the text and the target of the navigation links",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Query,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let tables = call.has_flag("tables")?;
+        let query: Option<Spanned<String>> = call.get_flag("query")?;
+
+        let span = input.span();
+        let Value::String { val, .. } = input else {
+            return Err(LabeledError::new("Requires text input")
+                .with_label("expected text from pipeline", span));
+        };
+
+        if !tables && query.is_none() {
+            return Ok(input.clone());
+        }
+
+        let selector = match &query {
+            Some(query) => parse_selector(query)?,
+            None => parse_selector(&Spanned {
+                item: "table".into(),
+                span: call.head,
+            })?,
+        };
+        let html = Html::parse_document(val);
+        let elements = html.select(&selector);
+        let vals = if tables {
+            elements
+                .filter(|element| element.value().name() == "table")
+                .map(|table| table_to_value(table, span))
+                .collect()
+        } else {
+            elements
+                .map(|element| element_to_value(element, span))
+                .collect()
+        };
+        Ok(Value::list(vals, span))
+    }
+}
+
+fn parse_selector(query: &Spanned<String>) -> Result<Selector, LabeledError> {
+    Selector::parse(&query.item).map_err(|err| {
+        LabeledError::new("CSS query parse error")
+            .with_label(err.to_string(), query.span)
+            .with_help("cannot parse query as a valid CSS selector")
+    })
+}
+
+fn element_to_value(element: ElementRef, span: Span) -> Value {
+    let attributes = element
+        .value()
+        .attrs()
+        .map(|(name, value)| (name.to_string(), Value::string(value, span)))
+        .collect();
+    Value::record(
+        record! {
+            "tag" => Value::string(element.value().name(), span),
+            "text" => Value::string(element_text(element), span),
+            "attributes" => Value::record(attributes, span),
+        },
+        span,
+    )
+}
+
+fn table_to_value(table: ElementRef, span: Span) -> Value {
+    let mut rows = rows(table).into_iter().map(cells).peekable();
+
+    // The first row is the header when it's only made of header cells
+    let headers = match rows.peek() {
+        Some(first) if !first.is_empty() && first.iter().all(|(header, _)| *header) => {
+            let first = rows.next().unwrap_or_default();
+            column_names(first.into_iter().map(|(_, text)| text))
+        }
+        _ => vec![],
+    };
+
+    let vals = rows
+        .filter(|row| !row.is_empty())
+        .map(|row| {
+            let mut record = Record::new();
+            for (i, (_, text)) in row.into_iter().enumerate() {
+                let column = headers
+                    .get(i)
+                    .cloned()
+                    .unwrap_or_else(|| format!("column{i}"));
+                record.push(column, Value::string(text, span));
+            }
+            Value::record(record, span)
+        })
+        .collect();
+    Value::list(vals, span)
+}
+
+/// The rows of a table, without the ones of the tables nested inside of it.
+fn rows(table: ElementRef) -> Vec<ElementRef> {
+    let Ok(selector) = Selector::parse("tr") else {
+        return vec![];
+    };
+    table
+        .select(&selector)
+        .filter(|row| {
+            row.ancestors()
+                .filter_map(ElementRef::wrap)
+                .find(|element| element.value().name() == "table")
+                .is_some_and(|parent| parent.id() == table.id())
+        })
+        .collect()
+}
+
+/// The text of the cells of a row, and whether they're header cells.
+fn cells(row: ElementRef) -> Vec<(bool, String)> {
+    row.children()
+        .filter_map(ElementRef::wrap)
+        .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+        .flat_map(|cell| {
+            let colspan = cell
+                .value()
+                .attr("colspan")
+                .and_then(|colspan| colspan.trim().parse::<usize>().ok())
+                .unwrap_or(1)
+                .clamp(1, MAX_COLSPAN);
+            let cell = (cell.value().name() == "th", element_text(cell));
+            std::iter::repeat_n(cell, colspan)
+        })
+        .collect()
+}
+
+/// Make unique column names out of the header cells, numbering the repeated ones.
+fn column_names(headers: impl Iterator<Item = String>) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    for (i, header) in headers.enumerate() {
+        let mut name = if header.is_empty() {
+            format!("column{i}")
+        } else {
+            header
+        };
+        if names.contains(&name) {
+            let mut n = 1;
+            while names.contains(&format!("{name}_{n}")) {
+                n += 1;
+            }
+            name = format!("{name}_{n}");
+        }
+        names.push(name);
+    }
+    names
+}
+
+/// The text of an element, with its whitespace collapsed like a browser shows it.
+fn element_text(element: ElementRef) -> String {
+    element
+        .text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLES: &str = r#"
+        <table class="people">
+            <tr><th>Name</th><th>Role</th><th>Name</th></tr>
+            <tr><td>Ada</td><td>Engineer
+                </td><td>Lovelace</td></tr>
+            <tr><td colspan="2">Grace</td><td>Hopper</td></tr>
+        </table>
+        <table>
+            <tr><td>a</td><td><table><tr><td>nested</td></tr></table></td></tr>
+        </table>
+    "#;
+
+    fn tables(html: &str, query: &str) -> Vec<Value> {
+        let html = Html::parse_document(html);
+        let selector = Selector::parse(query).expect("valid selector");
+        html.select(&selector)
+            .map(|table| table_to_value(table, Span::test_data()))
+            .collect()
+    }
+
+    #[test]
+    fn uses_header_cells_as_columns() {
+        assert_eq!(
+            tables(TABLES, "table.people"),
+            vec![Value::test_list(vec![
+                Value::test_record(record! {
+                    "Name" => Value::test_string("Ada"),
+                    "Role" => Value::test_string("Engineer"),
+                    "Name_1" => Value::test_string("Lovelace"),
+                }),
+                Value::test_record(record! {
+                    "Name" => Value::test_string("Grace"),
+                    "Role" => Value::test_string("Grace"),
+                    "Name_1" => Value::test_string("Hopper"),
+                }),
+            ])]
+        );
+    }
+
+    #[test]
+    fn skips_rows_of_nested_tables() {
+        let outer = tables(TABLES, "table:not(.people)");
+        let Some(Value::List { vals, .. }) = outer.first() else {
+            panic!("expected a table, got {outer:?}");
+        };
+        assert_eq!(vals.len(), 1);
+        assert_eq!(
+            vals[0].get_data_by_key("column1"),
+            Some(Value::test_string("nested"))
+        );
+    }
+
+    #[test]
+    fn extracts_elements() {
+        let html =
+            Html::parse_document(r#"<div class="item"><a href="/x" id="y"> X  link </a></div>"#);
+        let selector = Selector::parse("div.item a").expect("valid selector");
+        let element = html.select(&selector).next().expect("a match");
+        let value = element_to_value(element, Span::test_data());
+        assert_eq!(
+            value.get_data_by_key("text"),
+            Some(Value::test_string("X link"))
+        );
+        assert_eq!(
+            value
+                .get_data_by_key("attributes")
+                .and_then(|attributes| attributes.get_data_by_key("href")),
+            Some(Value::test_string("/x"))
+        );
+    }
+}
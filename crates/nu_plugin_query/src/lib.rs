@@ -1,3 +1,4 @@
+mod from_html;
 mod query;
 mod query_json;
 mod query_web;
@@ -5,6 +6,7 @@ mod query_webpage_info;
 mod query_xml;
 mod web_tables;
 
+pub use from_html::FromHtml;
 pub use query::Query;
 pub use query_json::{QueryJson, execute_json_query};
 pub use query_web::{QueryWeb, parse_selector_params};
@@ -44,6 +44,7 @@ const TABLE_MODE_VALUES: &[&str] = &[
     "double",
 ];
 const ERROR_STYLE_VALUES: &[&str] = &["fancy", "plain", "short"];
+const OUTPUT_FORMAT_VALUES: &[&str] = &["json", "nuon", "msgpack"];
 const LOG_LEVEL_VALUES: &[&str] = &["error", "warn", "info", "debug", "trace", "perf"];
 const LOG_TARGET_VALUES: &[&str] = &["stdout", "stderr", "mixed", "file"];
 #[cfg(feature = "mcp")]
@@ -217,6 +218,14 @@ const CLI_FLAGS: &[CliFlag] = &[
         CliCategory::Startup,
         "nu --error-style plain",
     ),
+    CliFlag::value(
+        "output-format",
+        None,
+        ValueHint::String,
+        "with --commands, write the result as json, nuon or msgpack instead of a table, and errors as JSON on stderr",
+        CliCategory::Startup,
+        "nu --output-format json -c 'ls | select name size'",
+    ),
     CliFlag::switch(
         "no-newline",
         None,
@@ -379,6 +388,34 @@ const CLI_FLAGS: &[CliFlag] = &[
         CliCategory::Ide,
         "nu --ide-ast script.nu",
     ),
+    CliFlag::switch(
+        "format",
+        None,
+        "format the given script file in place, with the default formatter settings",
+        CliCategory::Ide,
+        "nu --format script.nu",
+    ),
+    CliFlag::switch(
+        "check",
+        None,
+        "check the given script file for errors without running it, exiting with 1 if there are any",
+        CliCategory::Ide,
+        "nu --check script.nu",
+    ),
+    CliFlag::switch(
+        "lint",
+        None,
+        "with --check, also report unused variables, shadowed definitions, deprecated commands and untyped parameters of exported commands",
+        CliCategory::Ide,
+        "nu --check --lint script.nu",
+    ),
+    CliFlag::switch(
+        "json",
+        None,
+        "with --check, print the diagnostics as a JSON object",
+        CliCategory::Ide,
+        "nu --check --lint --json script.nu",
+    ),
     #[cfg(feature = "plugin")]
     CliFlag::value(
         "plugin-config",
@@ -451,6 +488,7 @@ struct CliValues {
     execute: Option<Spanned<String>>,
     table_mode: Option<Value>,
     error_style: Option<Value>,
+    output_format: Option<Value>,
     no_newline: Option<Spanned<String>>,
     include_path: Option<Spanned<String>>,
     #[cfg(feature = "lsp")]
@@ -460,6 +498,10 @@ struct CliValues {
     ide_complete: Option<Value>,
     ide_check: Option<Value>,
     ide_ast: Option<Spanned<String>>,
+    format: Option<Spanned<String>>,
+    check: Option<Spanned<String>>,
+    lint: Option<Spanned<String>>,
+    json: Option<Spanned<String>>,
     experimental_options: Option<Vec<Spanned<String>>>,
     #[cfg(feature = "mcp")]
     mcp: bool,
@@ -602,6 +644,16 @@ pub(crate) fn parse_cli_args(args: Vec<OsString>) -> Result<ParsedCli, CliError>
                 // No source span — CLI argument parsing happens before engine setup
                 cli.error_style = Some(Value::string(normalized, Span::unknown()));
             }
+            Long("output-format") => {
+                let normalized = parse_validated_option(
+                    &mut parser,
+                    "output-format",
+                    OUTPUT_FORMAT_VALUES,
+                    "output format",
+                )?;
+                // No source span — CLI argument parsing happens before engine setup
+                cli.output_format = Some(Value::string(normalized, Span::unknown()));
+            }
             Long("no-newline") => cli.no_newline = Some(spanned_true()),
             Short('n') | Long("no-config-file") => cli.no_config_file = Some(spanned_true()),
             Long("no-history") => cli.no_history = Some(spanned_true()),
@@ -681,6 +733,10 @@ pub(crate) fn parse_cli_args(args: Vec<OsString>) -> Result<ParsedCli, CliError>
                 cli.ide_check = Some(parse_ide_int_option(&mut parser, "ide-check")?)
             }
             Long("ide-ast") => cli.ide_ast = Some(spanned_true()),
+            Long("format") => cli.format = Some(spanned_true()),
+            Long("check") => cli.check = Some(spanned_true()),
+            Long("lint") => cli.lint = Some(spanned_true()),
+            Long("json") => cli.json = Some(spanned_true()),
             #[cfg(feature = "plugin")]
             Long("plugin-config") => {
                 let value = parse_string_value(&mut parser, "plugin-config")?;
@@ -787,6 +843,7 @@ pub(crate) fn parse_cli_args(args: Vec<OsString>) -> Result<ParsedCli, CliError>
             execute: cli.execute,
             table_mode: cli.table_mode,
             error_style: cli.error_style,
+            output_format: cli.output_format,
             no_newline: cli.no_newline,
             include_path: cli.include_path,
             #[cfg(feature = "lsp")]
@@ -796,6 +853,10 @@ pub(crate) fn parse_cli_args(args: Vec<OsString>) -> Result<ParsedCli, CliError>
             ide_complete: cli.ide_complete,
             ide_check: cli.ide_check,
             ide_ast: cli.ide_ast,
+            format: cli.format,
+            check: cli.check,
+            lint: cli.lint,
+            json: cli.json,
             experimental_options: cli.experimental_options,
             #[cfg(feature = "mcp")]
             mcp: cli.mcp,
@@ -1108,6 +1169,7 @@ fn missing_value_help(option: &str) -> String {
     match option {
         "-m" | "--table-mode" => format!("Valid table modes: {}", TABLE_MODE_VALUES.join(", ")),
         "--error-style" => format!("Valid error styles: {}", ERROR_STYLE_VALUES.join(", ")),
+        "--output-format" => format!("Valid output formats: {}", OUTPUT_FORMAT_VALUES.join(", ")),
         "--testbin" => format!("Valid test bins: {}", TEST_BIN_VALUES.join(", ")),
         "--log-level" | "--log-include" | "--log-exclude" => {
             format!("Valid log levels: {}", LOG_LEVEL_VALUES.join(", "))
@@ -1229,6 +1291,7 @@ fn prevalidate_short_groups_before_lexopt(args: &[OsString]) -> Result<(), CliEr
             || arg == "-m"
             || arg == "--table-mode"
             || arg == "--error-style"
+            || arg == "--output-format"
             || arg == "--ide-check"
             || arg == "--ide-goto-def"
             || arg == "--ide-hover"
@@ -1471,6 +1534,7 @@ pub(crate) struct NushellCliArgs {
     pub(crate) execute: Option<Spanned<String>>,
     pub(crate) table_mode: Option<Value>,
     pub(crate) error_style: Option<Value>,
+    pub(crate) output_format: Option<Value>,
     pub(crate) no_newline: Option<Spanned<String>>,
     pub(crate) include_path: Option<Spanned<String>>,
     #[cfg(feature = "lsp")]
@@ -1480,6 +1544,10 @@ pub(crate) struct NushellCliArgs {
     pub(crate) ide_complete: Option<Value>,
     pub(crate) ide_check: Option<Value>,
     pub(crate) ide_ast: Option<Spanned<String>>,
+    pub(crate) format: Option<Spanned<String>>,
+    pub(crate) check: Option<Spanned<String>>,
+    pub(crate) lint: Option<Spanned<String>>,
+    pub(crate) json: Option<Spanned<String>>,
     pub(crate) experimental_options: Option<Vec<Spanned<String>>>,
     #[cfg(feature = "mcp")]
     pub(crate) mcp: bool,
@@ -8,6 +8,7 @@ use nu_protocol::{
     engine::{EngineState, Stack, StateWorkingSet},
     report_parse_error, report_shell_error,
 };
+use nu_utils::escape_quote_string;
 use std::{
     fs,
     fs::File,
@@ -226,6 +227,32 @@ pub(crate) fn read_vendor_autoload_files(engine_state: &mut EngineState, stack:
         });
 }
 
+/// Activate every overlay module listed in `$env.config.startup_overlays`.
+///
+/// Each entry goes through `overlay use` like it was typed at the prompt, so a broken entry
+/// only reports its error and doesn't prevent the remaining overlays from loading.
+pub(crate) fn read_startup_overlays(engine_state: &mut EngineState, stack: &mut Stack) {
+    let overlays = stack.get_config(engine_state).startup_overlays.clone();
+
+    for path in overlays {
+        info!("read_startup_overlays: {path}");
+        let source = format!("overlay use {}", escape_quote_string(&path));
+        eval_source(
+            engine_state,
+            stack,
+            source.as_bytes(),
+            "startup_overlays",
+            PipelineData::empty(),
+            false,
+        );
+    }
+
+    // Merge the environment in case env vars changed in the overlays
+    if let Err(e) = engine_state.merge_env(stack) {
+        report_shell_error(Some(stack), engine_state, &e);
+    }
+}
+
 fn eval_default_config(
     engine_state: &mut EngineState,
     stack: &mut Stack,
@@ -282,6 +309,8 @@ pub(crate) fn setup_config(
         }
         // read and auto load vendor autoload files
         read_vendor_autoload_files(engine_state, stack);
+        // re-activate the overlays persisted with `overlay export`
+        read_startup_overlays(engine_state, stack);
     }));
     if result.is_err() {
         eprintln!(
@@ -1,11 +1,14 @@
-use miette::IntoDiagnostic;
+use miette::{Diagnostic, IntoDiagnostic};
 use nu_cli::NuCompleter;
 use nu_parser::{FlatShape, flatten_block, parse};
 use nu_protocol::{
     DeclId, ShellError, Span, Value, VarId,
     engine::{EngineState, Stack, StateWorkingSet},
-    report_shell_error,
-    shell_error::io::{IoError, IoErrorExt, NotFound},
+    report_parse_error, report_parse_warning, report_shell_error,
+    shell_error::{
+        generic::GenericError,
+        io::{IoError, IoErrorExt, NotFound},
+    },
 };
 use serde_json::{Value as JsonValue, json};
 use std::{fmt::Write, path::PathBuf, sync::Arc};
@@ -688,6 +691,182 @@ pub fn ast(engine_state: &mut EngineState, file_path: &str) {
     }
 }
 
+/// Formats the script at `file_path` in place, with the default formatter settings
+pub fn format(engine_state: &mut EngineState, file_path: &str) {
+    let cwd = std::env::current_dir().expect("Could not get current working directory.");
+    engine_state.add_env_var("PWD".into(), Value::test_string(cwd.to_string_lossy()));
+
+    let (file, _) = read_in_file(engine_state, file_path);
+    let config = engine_state.get_config().formatter;
+
+    let result = nu_parser::format_source(engine_state, &file, &config)
+        .map_err(|err| {
+            // No source span — the file was parsed in a working set that is gone by now
+            ShellError::Generic(GenericError::new(
+                "Can't format the file",
+                err.to_string(),
+                Span::unknown(),
+            ))
+        })
+        .and_then(|formatted| {
+            if formatted.as_bytes() == file.as_slice() {
+                return Ok(());
+            }
+            std::fs::write(file_path, formatted).map_err(|err| {
+                ShellError::Io(IoError::new_with_additional_context(
+                    err,
+                    Span::unknown(),
+                    PathBuf::from(file_path),
+                    "Could not write file",
+                ))
+            })
+        });
+
+    if let Err(err) = result {
+        report_shell_error(None, engine_state, &err);
+        std::process::exit(1);
+    }
+}
+
+/// A problem found by `nu --check`
+struct CheckDiagnostic {
+    severity: &'static str,
+    rule: &'static str,
+    message: String,
+    help: Option<String>,
+    span: Span,
+}
+
+/// The 1-based line and column of `offset` in `source`
+fn line_column(source: &[u8], offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line_start = before
+        .iter()
+        .rposition(|c| *c == b'\n')
+        .map_or(0, |newline| newline + 1);
+    let line = before.iter().filter(|c| **c == b'\n').count() + 1;
+    let column = String::from_utf8_lossy(&before[line_start..])
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+/// Checks the script at `file_path` for errors without running it, also running the lints when
+/// `lint` is set. Exits with 1 if there is an error, or any lint when linting.
+pub fn check_file(engine_state: &mut EngineState, file_path: &str, lint: bool, json: bool) {
+    let cwd = std::env::current_dir().expect("Could not get current working directory.");
+    engine_state.add_env_var("PWD".into(), Value::test_string(cwd.to_string_lossy()));
+    engine_state.generate_nu_constant();
+
+    let (file, mut working_set) = read_in_file(engine_state, file_path);
+    let offset = working_set.next_span_start();
+    // Top-level IDE check — no source location triggered this file load
+    let _ = working_set.files.push(file_path.into(), Span::unknown());
+    let block = parse(&mut working_set, Some(file_path), &file, false);
+    let file_span = Span::new(offset, offset + file.len());
+
+    let mut diagnostics = working_set
+        .parse_errors
+        .iter()
+        .map(|err| CheckDiagnostic {
+            severity: "error",
+            rule: "parse-error",
+            message: err.to_string(),
+            help: err.help().map(|help| help.to_string()),
+            span: err.span(),
+        })
+        .chain(
+            working_set
+                .parse_warnings
+                .iter()
+                .map(|warning| CheckDiagnostic {
+                    severity: "warning",
+                    rule: "parse-warning",
+                    message: warning.to_string(),
+                    help: warning.help().map(|help| help.to_string()),
+                    span: warning.span(),
+                }),
+        )
+        .collect::<Vec<_>>();
+    let mut lints = Vec::new();
+    if lint {
+        // Deprecations are already reported by the parser
+        let warned = working_set
+            .parse_warnings
+            .iter()
+            .map(|warning| warning.span())
+            .collect::<Vec<_>>();
+        lints = nu_parser::lint(&working_set, &block)
+            .into_iter()
+            .filter(|found| file_span.contains_span(found.span))
+            .filter(|found| {
+                found.rule != nu_parser::LintRule::DeprecatedCommand
+                    || !warned.contains(&found.span)
+            })
+            .map(|found| CheckDiagnostic {
+                severity: "warning",
+                rule: found.rule.name(),
+                message: found.message,
+                help: found.help,
+                span: found.span,
+            })
+            .collect();
+    }
+    let failed = !working_set.parse_errors.is_empty() || !lints.is_empty();
+
+    if json {
+        diagnostics.append(&mut lints);
+        let diagnostics = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let in_file = file_span.contains_span(diagnostic.span);
+                let position = in_file.then(|| line_column(&file, diagnostic.span.start - offset));
+                json!({
+                    "severity": diagnostic.severity,
+                    "rule": diagnostic.rule,
+                    "message": diagnostic.message,
+                    "help": diagnostic.help,
+                    "span": in_file.then(|| json!({
+                        "start": diagnostic.span.start - offset,
+                        "end": diagnostic.span.end - offset,
+                    })),
+                    "line": position.map(|(line, _)| line),
+                    "column": position.map(|(_, column)| column),
+                })
+            })
+            .collect::<Vec<_>>();
+        println!(
+            "{}",
+            json!({
+                "file": file_path,
+                "diagnostics": diagnostics,
+            })
+        );
+    } else {
+        for err in &working_set.parse_errors {
+            report_parse_error(None, &working_set, err);
+        }
+        for warning in &working_set.parse_warnings {
+            report_parse_warning(None, &working_set, warning);
+        }
+        for lint in &lints {
+            let (line, column) = line_column(&file, lint.span.start - offset);
+            eprintln!(
+                "{file_path}:{line}:{column}: {}[{}]: {}",
+                lint.severity, lint.rule, lint.message
+            );
+            if let Some(help) = &lint.help {
+                eprintln!("  help: {help}");
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
 fn json_merge(a: &mut JsonValue, b: &JsonValue) {
     match (a, b) {
         (JsonValue::Object(a), JsonValue::Object(b)) => {
@@ -252,6 +252,8 @@ fn main() -> Result<()> {
 
     // Begin: Default NU_LIB_DIRS, NU_PLUGIN_DIRS
     let default_nushell_completions_path = engine_state.config_dirs.data_home.join("completions");
+    // Modules installed with `std/package`
+    let default_nushell_modules_path = engine_state.config_dirs.data_home.join("modules");
     let mut default_nu_lib_dirs_path = engine_state.config_dirs.config_home.clone();
     default_nu_lib_dirs_path.push("scripts");
 
@@ -452,6 +454,7 @@ fn main() -> Result<()> {
             default_nushell_completions_path
                 .to_string_lossy()
                 .to_string(),
+            default_nushell_modules_path.to_string_lossy().to_string(),
         ];
         let all_lib_dirs: Vec<String> = user_lib_dirs.into_iter().chain(default_paths).collect();
 
@@ -517,6 +520,19 @@ fn main() -> Result<()> {
     } else if parsed_nu_cli_args.ide_ast.is_some() {
         ide::ast(&mut engine_state, &script_name);
 
+        return Ok(());
+    } else if parsed_nu_cli_args.format.is_some() {
+        ide::format(&mut engine_state, &script_name);
+
+        return Ok(());
+    } else if parsed_nu_cli_args.check.is_some() {
+        ide::check_file(
+            &mut engine_state,
+            &script_name,
+            parsed_nu_cli_args.lint.is_some(),
+            parsed_nu_cli_args.json.is_some(),
+        );
+
         return Ok(());
     }
 
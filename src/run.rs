@@ -5,11 +5,14 @@ use crate::{
 use log::trace;
 #[cfg(feature = "plugin")]
 use nu_cli::read_plugin_file;
-use nu_cli::{EvaluateCommandsOpts, evaluate_commands, evaluate_file, evaluate_repl};
+use nu_cli::{
+    EvaluateCommandsOpts, OutputFormat, evaluate_commands, evaluate_file, evaluate_repl,
+    report_error_as_json,
+};
 use nu_config::ConfigFileKind;
 use nu_protocol::{
     PipelineData, ShellError,
-    engine::{EngineState, Stack},
+    engine::{EngineState, Stack, StateWorkingSet},
     report_shell_error,
 };
 use nu_utils::perf;
@@ -96,6 +99,13 @@ pub(crate) fn run_commands(
     // Regenerate the $nu constant to contain the startup time and any other potential updates
     engine_state.generate_nu_constant();
 
+    // The value was validated while parsing the CLI arguments
+    let output_format = parsed_nu_cli_args
+        .output_format
+        .as_ref()
+        .and_then(|format| format.as_str().ok())
+        .and_then(|format| format.parse::<OutputFormat>().ok());
+
     let start_time = Instant::now();
     let result = evaluate_commands(
         commands,
@@ -106,15 +116,22 @@ pub(crate) fn run_commands(
             table_mode: parsed_nu_cli_args.table_mode,
             error_style: parsed_nu_cli_args.error_style,
             no_newline: parsed_nu_cli_args.no_newline.is_some(),
+            output_format,
         },
     );
     perf!("evaluate_commands", start_time, use_color);
 
     if let Err(err) = result {
+        // Let `on-signal` closures finish their cleanup
+        engine_state.signal_hooks.wait();
         if let ShellError::Exit { code, .. } = &err {
             std::process::exit(*code)
         }
-        report_shell_error(Some(&stack), engine_state, &err);
+        if output_format.is_some() {
+            report_error_as_json(&StateWorkingSet::new(engine_state), &err);
+        } else {
+            report_shell_error(Some(&stack), engine_state, &err);
+        }
         std::process::exit(err.exit_code().unwrap_or(0));
     }
 }
@@ -197,6 +214,8 @@ pub(crate) fn run_file(
     perf!("evaluate_file", start_time, use_color);
 
     if let Err(err) = result {
+        // Let `on-signal` closures finish their cleanup
+        engine_state.signal_hooks.wait();
         if let ShellError::Exit { code, .. } = &err {
             std::process::exit(*code)
         }
@@ -1,4 +1,4 @@
-use nu_protocol::{Handlers, SignalAction, Signals, engine::EngineState};
+use nu_protocol::{Handlers, HookedSignal, SignalAction, Signals, engine::EngineState};
 use std::sync::{
     Arc,
     atomic::{AtomicBool, Ordering},
@@ -26,9 +26,13 @@ pub(crate) fn ctrlc_protection(engine_state: &mut EngineState) {
 
     engine_state.signal_handlers = Some(signal_handlers.clone());
 
+    let signal_hooks = engine_state.signal_hooks.clone();
     ctrlc::set_handler(move || {
-        interrupt.store(true, Ordering::Relaxed);
-        signal_handlers.run(SignalAction::Interrupt);
+        // The hooks count as running before the interrupt, so nushell waits for them to finish
+        signal_hooks.run_after(HookedSignal::Interrupt, || {
+            interrupt.store(true, Ordering::Relaxed);
+            signal_handlers.run(SignalAction::Interrupt);
+        });
     })
     .expect("Error setting Ctrl-C handler");
 }
@@ -41,6 +41,7 @@ fn help_lists_all_flags() -> TestResult {
         "--include-path",
         "--table-mode",
         "--error-style",
+        "--output-format",
         "--no-newline",
         "--no-config-file",
         "--no-history",
@@ -60,6 +61,10 @@ fn help_lists_all_flags() -> TestResult {
         "--ide-complete",
         "--ide-check",
         "--ide-ast",
+        "--format",
+        "--check",
+        "--lint",
+        "--json",
     ];
 
     for flag in required_flags {
@@ -638,6 +643,147 @@ fn ide_ast_flag_runs() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn format_flag_formats_file_in_place() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let script = dir.path().join("script.nu");
+    std::fs::write(&script, "if true {\nls   |   length\n}")?;
+
+    let mut cmd = Command::new(cargo_bin!());
+    let output = cmd
+        .args(["--no-config-file", "--no-std-lib", "--format"])
+        .arg(&script)
+        .output()?;
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(&script)?,
+        "if true {\n    ls | length\n}\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn output_format_json_serializes_result() -> TestResult {
+    let mut cmd = Command::new(cargo_bin!());
+    let output = cmd
+        .args([
+            "--no-config-file",
+            "--no-std-lib",
+            "--output-format",
+            "json",
+        ])
+        .args(["-c", "{name: nu, sizes: [1 2]}"])
+        .output()?;
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "{\"name\":\"nu\",\"sizes\":[1,2]}\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn output_format_nuon_serializes_result() -> TestResult {
+    let mut cmd = Command::new(cargo_bin!());
+    let output = cmd
+        .args([
+            "--no-config-file",
+            "--no-std-lib",
+            "--output-format",
+            "nuon",
+        ])
+        .args(["-c", "[1 2 3]"])
+        .output()?;
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "[1, 2, 3]\n");
+
+    Ok(())
+}
+
+#[test]
+fn output_format_reports_errors_as_json() -> TestResult {
+    let mut cmd = Command::new(cargo_bin!());
+    let output = cmd
+        .args([
+            "--no-config-file",
+            "--no-std-lib",
+            "--output-format",
+            "json",
+        ])
+        .args(["-c", "error make {msg: boom}"])
+        .output()?;
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let report: serde_json::Value = serde_json::from_slice(&output.stderr)?;
+    assert_eq!(report["error"]["msg"], "boom");
+
+    Ok(())
+}
+
+#[test]
+fn output_format_rejects_unknown_format() -> TestResult {
+    let mut cmd = Command::new(cargo_bin!());
+    let output = cmd.args(["--output-format", "xml", "-c", "1"]).output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--output-format"));
+
+    Ok(())
+}
+
+#[test]
+fn check_flag_reports_lints_as_json() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let script = dir.path().join("script.nu");
+    std::fs::write(&script, "def main [] {\n    let unused = 1\n}\n")?;
+
+    let mut cmd = Command::new(cargo_bin!());
+    let output = cmd
+        .args([
+            "--no-config-file",
+            "--no-std-lib",
+            "--check",
+            "--lint",
+            "--json",
+        ])
+        .arg(&script)
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let diagnostics = report["diagnostics"].as_array().ok_or("no diagnostics")?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["rule"], "unused-variable");
+    assert_eq!(diagnostics[0]["line"], 2);
+    assert_eq!(diagnostics[0]["column"], 9);
+
+    Ok(())
+}
+
+#[test]
+fn check_flag_passes_clean_script() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let script = dir.path().join("script.nu");
+    std::fs::write(&script, "def main [name: string] {\n    print $name\n}\n")?;
+
+    let mut cmd = Command::new(cargo_bin!());
+    let output = cmd
+        .args(["--no-config-file", "--no-std-lib", "--check", "--lint"])
+        .arg(&script)
+        .output()?;
+
+    assert!(output.status.success());
+
+    Ok(())
+}
+
 #[test]
 fn lsp_flag_accepts_run() -> TestResult {
     let mut cmd = Command::new(cargo_bin!());
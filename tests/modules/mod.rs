@@ -352,6 +352,43 @@ fn module_import_env_1() {
     })
 }
 
+#[test]
+fn use_env_first_runs_dependencies_in_import_order() -> Result {
+    Playground::setup("use_env_first_order", |dirs, sandbox| {
+        sandbox.with_files(&[
+            FileWithContent(
+                "base.nu",
+                "export-env { $env.LOG = ($env.LOG? | default [] | append base) }",
+            ),
+            FileWithContent(
+                "theme.nu",
+                "use base.nu\nexport-env { $env.LOG = ($env.LOG | append theme) }",
+            ),
+            FileWithContent(
+                "app.nu",
+                "use theme.nu\nuse base.nu\nexport-env { $env.LOG = ($env.LOG | append app) }",
+            ),
+        ]);
+
+        test()
+            .cwd(dirs.test())
+            .run("use --env-first app.nu; $env.LOG | to nuon")
+            .expect_value_eq("[base, theme, app]")
+    })
+}
+
+#[test]
+fn use_without_env_first_only_runs_own_env() -> Result {
+    let code = r#"
+        module base { export-env { $env.BASE = "base" } }
+        module app { use base; export-env { $env.APP = "app" } }
+        use app
+        [($env.BASE? | default missing) $env.APP] | to nuon
+    "#;
+
+    test().run(code).expect_value_eq("[missing, app]")
+}
+
 #[test]
 fn module_import_env_2() {
     Playground::setup("module_import_env_2", |dirs, sandbox| {
@@ -1670,3 +1670,38 @@ fn report_errors_in_export_env() {
     assert!(actual.err.contains("reported"));
     assert!(actual_repl.err.contains("reported"));
 }
+
+#[test]
+fn overlay_export_restores_env() {
+    Playground::setup("overlay_export_restores_env", |dirs, _| {
+        let inp = &[
+            "overlay new spam",
+            "$env.FOO = 'foo'",
+            "$env.BAR = [a b]",
+            "overlay export spam --path spam.nu",
+        ];
+
+        let actual = nu!(cwd: dirs.test(), &inp.join("; "));
+        assert!(actual.err.is_empty());
+
+        let inp = &[
+            "overlay use spam.nu",
+            "[(overlay list | last | get name) $env.FOO ($env.BAR | str join)] | str join",
+        ];
+
+        let actual = nu!(cwd: dirs.test(), &inp.join("; "));
+        assert_eq!(actual.out, "spamfooab");
+    })
+}
+
+#[test]
+fn overlay_export_refuses_to_overwrite() {
+    Playground::setup("overlay_export_refuses_to_overwrite", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("spam.nu", "")]);
+
+        let inp = &["overlay new spam", "overlay export spam --path spam.nu"];
+
+        let actual = nu!(cwd: dirs.test(), &inp.join("; "));
+        assert!(actual.err.contains("already exists"));
+    })
+}
@@ -287,6 +287,48 @@ fn source_circular() -> Result {
         .expect_error_code_eq("nu::parser::circular_import")
 }
 
+#[test]
+fn use_circular_shows_import_chain() -> Result {
+    Playground::setup("use_circular_shows_import_chain", |dirs, sandbox| {
+        sandbox.with_files(&[
+            Stub::FileWithContent("a.nu", "export use b.nu *"),
+            Stub::FileWithContent("b.nu", "export use c.nu *"),
+            Stub::FileWithContent("c.nu", "export use a.nu *"),
+        ]);
+
+        let err = test()
+            .cwd(dirs.test())
+            .run("use a.nu")
+            .expect_parse_error()?;
+        let ParseError::CircularImport(chain, _) = err else {
+            panic!("expected a circular import, got {err:?}");
+        };
+        let files: Vec<&str> = chain
+            .lines()
+            .map(|line| line.trim_start_matches("uses "))
+            .filter_map(|path| path.rsplit(['/', '\\']).next())
+            .collect();
+        assert_eq!(files, ["a.nu", "b.nu", "c.nu", "a.nu"]);
+        Ok(())
+    })
+}
+
+#[test]
+fn use_circular_through_other_paths() -> Result {
+    // Every round through the cycle reaches the files with a longer path
+    Playground::setup("use_circular_through_other_paths", |dirs, sandbox| {
+        sandbox.mkdir("lib").with_files(&[
+            Stub::FileWithContent("lib/a.nu", "export use ../lib/b.nu *"),
+            Stub::FileWithContent("lib/b.nu", "export use ../lib/a.nu *"),
+        ]);
+
+        test()
+            .cwd(dirs.test())
+            .run("use lib/a.nu")
+            .expect_error_code_eq("nu::parser::circular_import")
+    })
+}
+
 #[test]
 #[deps(NU)]
 fn run_nu_script_single_line() -> Result {
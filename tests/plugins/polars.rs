@@ -0,0 +1,17 @@
+use nu_test_support::prelude::*;
+
+#[test]
+#[deps(NU_PLUGIN_POLARS)]
+fn dataframe_rows_are_streamed_from_plugin() -> Result {
+    test()
+        .run("[[a]; [1] [2] [3]] | polars into-df | each {|row| $row.a * 2 }")
+        .expect_value_eq([2, 4, 6])
+}
+
+#[test]
+#[deps(NU_PLUGIN_POLARS)]
+fn lazyframe_rows_are_streamed_from_plugin() -> Result {
+    test()
+        .run("[[a]; [1] [2] [3]] | polars into-lazy | polars filter ((polars col a) > 1) | each {|row| $row.a }")
+        .expect_value_eq([2, 3])
+}
@@ -361,3 +361,48 @@ fn glob_literal_string_interpolation_with_metachars() -> TestResult {
         "/path/[foo]*.txt",
     )
 }
+
+#[test]
+fn generator_streams_yielded_values() -> TestResult {
+    run_test(
+        "def --generator gen [n: int] { for i in 1..$n { if $i mod 2 == 0 { yield $i } }; 'ignored' }; gen 6 | to nuon",
+        "[2, 4, 6]",
+    )
+}
+
+#[test]
+fn generator_stops_when_stream_is_dropped() -> TestResult {
+    run_test(
+        "def --generator naturals [] { mut n = 0; loop { yield $n; $n += 1 } }; naturals | skip 2 | first",
+        "2",
+    )
+}
+
+#[test]
+fn generator_output_is_a_stream() -> TestResult {
+    run_test(
+        "def --generator gen [] { yield 1 }; gen | describe",
+        "list<int> (stream)",
+    )
+}
+
+#[test]
+fn generator_error_ends_stream() -> TestResult {
+    fail_test(
+        "def --generator gen [] { yield 1; error make {msg: boom} }; gen | to nuon",
+        "boom",
+    )
+}
+
+#[test]
+fn yield_outside_generator() -> TestResult {
+    fail_test("def f [] { yield 1 }; f", "`yield` outside of a generator")
+}
+
+#[test]
+fn generator_cannot_keep_env() -> TestResult {
+    fail_test(
+        "def --env --generator gen [] { yield 1 }",
+        "--env and --generator can't be used together",
+    )
+}
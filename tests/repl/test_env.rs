@@ -5,6 +5,38 @@ use nu_test_support::prelude::*;
 #[rstest]
 #[case::env_shorthand("FOO=BAZ $env.FOO", "BAZ")]
 #[case::env_shorthand_multiple("FOO=BAZ BAR=MOO [$env.FOO, $env.BAR]", ["BAZ", "MOO"])]
+#[case::env_watch_runs_on_change(
+    "$env.LOG = []; env watch FOO {|_, new| $env.LOG ++= [$new] }; $env.FOO = a; $env.FOO = a; $env.FOO = b; $env.LOG | to nuon",
+    "[a, b]"
+)]
+#[case::env_watch_sees_old_and_new_value(
+    "$env.FOO = a; env watch FOO {|old, new| $env.SEEN = [$old $new] }; hide-env FOO; $env.SEEN | to nuon",
+    "[a, null]"
+)]
+#[case::env_watch_sees_def_env_changes(
+    "def --env set-foo [] { $env.FOO = x }; env watch FOO { $env.RAN = yes }; set-foo; $env.RAN",
+    "yes"
+)]
+#[case::env_watch_sees_changes_in_closures(
+    "let ran = ref new 'no'; env watch FOO { $ran | ref update { 'yes' } }; do { $env.FOO = x }; $ran | ref get",
+    "yes"
+)]
+#[case::env_watch_keeps_changes_in_their_scope(
+    "$env.RAN = no; env watch FOO { $env.RAN = yes }; do { $env.FOO = x }; $env.RAN",
+    "no"
+)]
+#[case::env_watch_sees_each_change_of_def_env(
+    "def --env twice [] { $env.FOO = x; $env.FOO = y }; $env.LOG = []; env watch FOO {|_, new| $env.LOG ++= [$new] }; twice; $env.LOG | to nuon",
+    "[x, y]"
+)]
+#[case::env_watch_does_not_trigger_itself(
+    "$env.COUNT = 0; env watch FOO {|_, new| $env.COUNT += 1; $env.FOO = $'($new)!' }; $env.FOO = a; [$env.FOO $env.COUNT] | to nuon",
+    "[a!, 1]"
+)]
+#[case::env_watch_errors_are_caught(
+    "env watch FOO { error make {msg: boom} }; try { $env.FOO = x } catch {|err| $err.msg }",
+    "boom"
+)]
 fn successful(#[case] code: &str, #[case] expect: impl IntoValue) -> Result {
     test().run(code).expect_value_eq(expect)
 }
@@ -219,3 +219,44 @@ fn spread_null() -> TestResult {
 
     Ok(())
 }
+
+#[test]
+fn spread_record_as_flags() -> TestResult {
+    run_test(
+        "
+        def f [a --flag: int --switch] { [$a $flag $switch] | to nuon }
+        f 1 ...{flag: 5, switch: true}",
+        "[1, 5, true]",
+    )?;
+    run_test(
+        "
+        def f [--flag: int --switch] { [$flag $switch] | to nuon }
+        let options = {flag: null, switch: false}
+        f ...$options",
+        "[null, false]",
+    )?;
+    // Wrappers can forward their options
+    run_test(
+        "
+        def inner [--flag: string] { $flag }
+        def outer [--options: record] { inner ...$options }
+        outer --options {flag: foo}",
+        "foo",
+    )?;
+    run_test(
+        "[[a]; [1] [2]] | sort-by a ...{reverse: true} | get a | to nuon",
+        "[2, 1]",
+    )
+}
+
+#[test]
+fn bad_spread_record_as_flags() -> TestResult {
+    fail_test(
+        "def f [--flag: int] {}; f ...{flga: 1}",
+        "Did you mean `--flag`?",
+    )?;
+    fail_test(
+        "def f [--switch] {}; f ...{switch: 1}",
+        "can't convert int to bool",
+    )
+}
@@ -210,6 +210,44 @@ export def foo [] {}
     )
 }
 
+#[test]
+fn scope_modules_graph_lists_imports() -> Result {
+    let code = "
+        module eggs { export def bar [] {} }
+        module spam { use eggs; export def foo [] {} }
+        scope modules --graph | where name == spam | get 0.imports.name | to nuon
+    ";
+
+    test().run(code).expect_value_eq("[eggs]")
+}
+
+#[test]
+fn scope_externals_path_cache_of_empty_path() -> Result {
+    test()
+        .run("with-env { PATH: [] } { scope externals-path-cache | length }")
+        .expect_value_eq(0)
+}
+
+#[cfg(unix)]
+#[test]
+fn scope_externals_path_cache_lists_executables() -> Result {
+    Playground::setup("scope_externals_path_cache", |dirs, sandbox| {
+        sandbox.mkdir("bin");
+        let mut tester = test().cwd(dirs.test());
+        let names: Vec<String> = tester.run(
+            "
+            touch bin/hello-cache bin/not-executable
+            ^chmod +x bin/hello-cache
+            with-env { PATH: [($env.PWD | path join bin)] } {
+                scope externals-path-cache | get name
+            }
+            ",
+        )?;
+        assert_eq!(names, ["hello-cache"]);
+        Ok(())
+    })
+}
+
 #[test]
 fn correct_scope_aliases_fields() {
     let module_setup = "